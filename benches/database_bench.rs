@@ -1,37 +1,204 @@
 //! Comprehensive PostgreSQL Library Benchmark
 //!
 //! This benchmark suite provides fair and comprehensive comparisons between:
-//! - tokio-postgres: Low-level async PostgreSQL driver
-//! - sqlx: Compile-time checked SQL queries
+//! - tokio-postgres: Low-level async PostgreSQL driver, also with prepared statements
+//! - postgres: The synchronous driver over tokio-postgres, for sync vs async
+//! - sqlx: SQL strings, and compile-time checked queries through its `query!` macros
 //! - sea-orm: Async ORM built on sqlx
+//! - sea-query: SeaORM's query builder on its own, run through sqlx
 //! - diesel: Synchronous ORM with type safety
 //! - clorinde: Generated type-safe queries from SQL
+//! - wtx: Newer async driver with its own codecs
 //!
 //! Benchmark Categories:
 //! 1. Insert Operations (single and batch)
 //! 2. Select Operations (simple and filtered)
 //! 3. Update Operations
-//! 4. Delete Operations
-//! 5. Join Operations (single and multi-table)
-//! 6. Aggregate Operations
-//! 7. Transaction Operations
-//! 8. Heavy Workload Simulation
+//! 4. Join Operations (single and multi-table)
+//! 5. Aggregate Operations
+//! 6. Transaction Operations
+//! 7. Concurrent Operations (connection pooling)
+//! 8. Heavy Workload Simulation (including multi-statement batching)
+//! 9. Statement Timeout and Cancellation
+//! 10. Backpressure under a slow database
+//! 11. TLS (connect and per-query overhead per TLS stack, plaintext vs rustls vs OpenSSL across result sizes)
+//! 12. Connect time per authentication method (trust, md5, SCRAM-SHA-256)
+//! 13. Text vs binary result decoding
+//! 14. Session configuration (`work_mem` effect on joins and aggregates)
+//! 15. Failover detection with multi-host connection strings
+//! 16. Read-replica routing (reads on a replica, writes on the primary) and replica staleness
+//! 17. sqlx statement cache capacity over many distinct queries
+//! 18. sqlx query styles: `query` strings vs `query!` macros vs `query_as` with `FromRow`
+//! 19. SeaORM partial models and single-statement joins vs whole-entity reads
+//! 20. Column subsets: id and username only vs whole rows, for the ORMs and the drivers
+//! 21. Large results (100k wide rows): transfer time and peak memory, optionally over a bandwidth-limited link
+//! 22. Unicode text (Cyrillic, CJK, emoji, combining marks): insert, read and `ILIKE` name search per script
+//! 23. Query building alone (diesel, SeaORM, `format!`), without a database
+//! 24. Row decoding alone: pre-fetched rows into `User` per backend
+//! 25. Connection setup: time to the first usable connection, alone and with a pool filled
+//! 26. Column access by name vs by position when decoding rows
+//! 27. Primary key type: insert, point lookup and join on UUID vs BIGSERIAL keys
+//! 28. Index sensitivity: select and join scenarios with and without the secondary indexes
+//! 29. Logical replication lag while the write scenarios run
+//! 30. Streaming vs collecting the large result: time and peak memory per backend
+//! 31. Buffer cache state: select and join scenarios warm (prewarmed) and cold (after a server restart)
+//! 32. Web API: the same axum JSON endpoints on every backend under concurrent HTTP requests
+//! 33. Work queue: producers and `SKIP LOCKED` consumers, end-to-end job throughput per backend
+//! 34. Session temp table: create, bulk-load, join against posts and drop on one connection
+//! 35. Chunked export: `WITH HOLD` cursor `FETCH`es vs keyset pagination across transactions
+//! 36. Batch size sweep: looped, multi-row `VALUES`, `UNNEST` and `COPY` inserts from 1 to 1000 users
+//! 37. Read-your-writes: an insert on one connection until a second connection reads it back
+//! 38. Unique violations: inserts repeating a taken username, recovered with `ON CONFLICT` or by catching the error
+//! 39. Generated column: inserting, reading and filtering on a stored `GENERATED ALWAYS AS` column
+//! 40. Calibration: a fixed-cost `pg_sleep` query returning a constant row, client overhead alone
+//! 41. Sync vs async: the blocking `postgres` crate against tokio-postgres on the same SQL
+//! 42. Row allocation: decoding into owned `String`s, one shared `Arc<str>` per row, or borrowed `&str`s
+//! 43. Zero- and single-row results: point lookups that hit or miss, list queries returning nothing or one row
+//! 44. Aggregate interference: `count_posts_per_user` latency while writers insert posts through the same pool
+//! 45. Pool comparison: deadpool, bb8 and mobc handing the same tokio-postgres connections to the same tasks
+//! 46. Retry: point lookups under induced serialization failures, giving up at once or retrying with backoff
+//! 47. Statement invalidation: cached `SELECT *` lookups after a column is added or dropped mid-run
+//! 48. JSON response: the newest posts with their authors, queried and serialized into an API response body
+//! 49. Comment pagination: keyset pages of a hot post's comments, at the start of the thread and deep into it
+//! 50. Status filter: the newest posts of a status through the `(status, created_at)` index, each library's plan checked
+//! 51. Email lookup: case-insensitive lookups on a `citext` column vs a `LOWER(email)` expression index
+//! 52. Deferred constraints: a user, posts and comments inserted in one transaction with foreign keys checked immediately or at commit
+//!
+//! Every group is tagged with what it exercises (see `pg_benchmark::tags`);
+//! `BENCH_TAGS=join,write` runs only the groups with one of those tags.
 
-use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use criterion::measurement::WallTime;
+use criterion::{
+    criterion_group, Bencher, BenchmarkGroup, BenchmarkId, Criterion, SamplingMode, Throughput,
+};
 use pg_benchmark::{
     bench_clorinde::ClorindeBench,
     bench_diesel::DieselBench,
+    bench_null::NullBackend,
+    bench_postgres_sync::PostgresSyncBench,
     bench_seaorm::SeaOrmBench,
-    bench_sqlx::SqlxBench,
-    bench_tokio_postgres::TokioPostgresBench,
-    NewPost, NewUser,
+    bench_seaquery::SeaQueryBench,
+    bench_sqlx::{SqlxBench, SqlxConfig, SqlxMacroBench},
+    bench_tokio_postgres::{TokioPostgresBench, TokioPostgresPreparedBench},
+    bench_wtx::WtxBench,
+    consume, database_url, database_url_with, verify, BoxError, CommentKey, DatabaseBenchmark, HeavyWorkloadConfig, NewPost,
+    NewUser, User, WorkloadOp,
 };
-use std::time::Duration;
+use pg_benchmark::auth::AuthMethod;
+use pg_benchmark::bulk_insert::{BulkInsert, Strategy as InsertStrategy, BATCH_SIZES};
+use pg_benchmark::audit;
+use pg_benchmark::cache;
+use pg_benchmark::calibration::{self, Calibration};
+use pg_benchmark::fast_path::{self, FastPath};
+use pg_benchmark::interference::{self, PostPool, Writers};
+use pg_benchmark::invalidation::{
+    self, CachedLookup, DieselLookup, SeaOrmLookup, SqlxLookup, TokioPostgresLookup, TokioPostgresPreparedLookup,
+};
+use pg_benchmark::cursor::{ChunkedExport, Method as ExportMethod};
+use pg_benchmark::failover;
+use pg_benchmark::generated_column::{self, GeneratedColumn};
+use pg_benchmark::connection_mode::{ConnectionMode, ConnectionModes, SAMPLE_POOL_SIZE, SamplePool};
+use pg_benchmark::deferred_constraints::{self, ConstraintTiming, DeferredConstraints};
+use pg_benchmark::email_lookup::{self, EmailLookup};
+use pg_benchmark::hot_posts;
+use pg_benchmark::indexes;
+use pg_benchmark::logical;
+use pg_benchmark::memory;
+use pg_benchmark::parity::{self, ParityConfig, Severity};
+use pg_benchmark::primary_key::{self, KeyType, SerialKeys};
+use pg_benchmark::progress::bench_progress;
+use pg_benchmark::proxy::{self, TcpProxy};
+use pg_benchmark::queue::{self, JobQueue, QueueLoad};
+use pg_benchmark::query_build::{self, Query};
+use pg_benchmark::read_your_writes::{self, Pair};
+use pg_benchmark::replica::{self, Routed};
+use pg_benchmark::result_format;
+use pg_benchmark::retry::{FailureSchedule, RetryPolicy, Transient};
+use pg_benchmark::row_decode;
+use pg_benchmark::scenario::{Category, Fixture, Scenario, SCENARIOS, SIZES};
+use pg_benchmark::seed::TextScript;
+use pg_benchmark::session::SessionConfig;
+use pg_benchmark::status_filter;
+use pg_benchmark::tags::{Tag, TagFilter};
+use pg_benchmark::temp_table::{self, StagedUser, TempTable};
+use pg_benchmark::unique_violation::{self, Recovery, UniqueViolation};
+use pg_benchmark::tls::{sqlx_stack, tls_database_url, TlsStack, TOKIO_POSTGRES_STACKS};
+use pg_benchmark::web::{self, ApiServer, Endpoint, LoadClient};
+use sqlx::PgPool;
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Display};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
 use tokio::runtime::Runtime;
 use uuid::Uuid;
 
-// Benchmark sizes
-const SIZES: &[usize] = &[10, 100, 1000];
+// Concurrent benchmarks: tasks (and pool size) for the read and mixed groups
+const CONCURRENCY_LEVELS: &[usize] = &[10, 50, 100];
+const MIXED_CONCURRENCY: usize = 50;
+
+// Pool comparison: every pool holds this many connections and serves each of
+// `CONCURRENCY_LEVELS` tasks at once, so beyond it tasks queue for checkout
+const POOL_COMPARISON_SIZE: usize = 10;
+
+// Statement timeout benchmarks: the server-side timeout fires long before the
+// sleep would finish, so a backend that fails to cancel shows up as ~50ms samples
+const STATEMENT_TIMEOUT_MS: u64 = 5;
+const CLIENT_TIMEOUT: Duration = Duration::from_millis(5);
+const SLEEP_SECS: f64 = 0.05;
+// Pings allowed after a cancel request before the connection counts as broken
+const CANCEL_PING_ATTEMPTS: usize = 5;
+
+// Backpressure benchmarks: every Nth task runs a slow query that holds its pooled
+// connection, the rest are point lookups whose latency (including pool wait) is measured
+const BACKPRESSURE_POOL_SIZE: usize = 10;
+const BACKPRESSURE_TASKS: usize = 50;
+const SLOW_QUERY_EVERY: usize = 10;
+const SLOW_QUERY_SECS: f64 = 0.02;
+
+/// Smoke-test mode: run the generic groups against the in-memory `NullBackend`
+/// only, so the harness can be exercised without a PostgreSQL server
+fn smoke_test() -> bool {
+    std::env::var_os("BENCH_SMOKE").is_some_and(|v| v != "0")
+}
+
+/// Whether `BENCH_TAGS` selects a group tagged `tags`; every group runs without it
+fn tagged(tags: &[Tag]) -> bool {
+    static FILTER: OnceLock<TagFilter> = OnceLock::new();
+    FILTER
+        .get_or_init(|| TagFilter::from_env().unwrap_or_else(|e| panic!("{e}")))
+        .matches(tags)
+}
+
+/// Connection mode of the group being registered, set by [`benchmark_group`]
+static CONNECTION_MODE: Mutex<ConnectionMode> = Mutex::new(ConnectionMode::Reused);
+
+/// The connection mode `BENCH_CONNECTION` gives the group named `group`
+fn connection_mode(group: &str) -> ConnectionMode {
+    static MODES: OnceLock<ConnectionModes> = OnceLock::new();
+    MODES
+        .get_or_init(|| ConnectionModes::from_env().unwrap_or_else(|e| panic!("{e}")))
+        .for_group(group)
+}
+
+/// A group with its measurement time and sample size, announced to the live
+/// progress display (`BENCH_PROGRESS=1`) so it can show how far along it is.
+/// The backends [`register`] adds to it get their connections as
+/// `BENCH_CONNECTION` sets for the group.
+fn benchmark_group<'a>(
+    c: &'a mut Criterion,
+    name: impl Into<String>,
+    measurement_time: Duration,
+    sample_size: usize,
+) -> BenchmarkGroup<'a, WallTime> {
+    let name = name.into();
+    bench_progress().group(&name, measurement_time);
+    *CONNECTION_MODE.lock().unwrap_or_else(|e| e.into_inner()) = connection_mode(&name);
+    let mut group = c.benchmark_group(name);
+    group.measurement_time(measurement_time);
+    group.sample_size(sample_size);
+    group
+}
 
 fn create_runtime() -> Runtime {
     tokio::runtime::Builder::new_multi_thread()
@@ -40,1315 +207,4463 @@ fn create_runtime() -> Runtime {
         .unwrap()
 }
 
+/// Mean of the recorded fast-query latencies, ignoring slow tasks (`None`)
+fn mean_latency(latencies: impl IntoIterator<Item = Option<Duration>>) -> Duration {
+    let fast: Vec<Duration> = latencies.into_iter().flatten().collect();
+    fast.iter().sum::<Duration>() / fast.len().max(1) as u32
+}
+
 // ============================================================================
-// Insert Benchmarks
+// Error Recording
 // ============================================================================
+//
+// A failed operation is counted and skipped instead of aborting a long run, and
+// a backend whose benchmark panics (e.g. it cannot connect) is skipped for that
+// benchmark. Everything recorded is summarised per backend at the end of the
+// run. Set `BENCH_FAIL_FAST=1` to abort on the first error instead.
 
-fn bench_insert_single(c: &mut Criterion) {
-    let rt = create_runtime();
-    let mut group = c.benchmark_group("insert_single_user");
-    group.measurement_time(Duration::from_secs(10));
-    group.sample_size(100);
-
-    // tokio-postgres
-    group.bench_function("tokio_postgres", |b| {
-        let client = rt.block_on(TokioPostgresBench::connect()).unwrap();
-        let mut counter = 0usize;
-        b.iter(|| {
-            counter += 1;
-            let user = NewUser::generate(counter);
-            rt.block_on(TokioPostgresBench::insert_user(&client, &user))
-                .unwrap()
-        });
-        rt.block_on(TokioPostgresBench::cleanup(&client)).unwrap();
-    });
+/// Error counts by backend, then by message
+static ERRORS: Mutex<BTreeMap<String, BTreeMap<String, usize>>> = Mutex::new(BTreeMap::new());
 
-    // sqlx
-    group.bench_function("sqlx", |b| {
-        let pool = rt.block_on(SqlxBench::connect()).unwrap();
-        let mut counter = 0usize;
-        b.iter(|| {
-            counter += 1;
-            let user = NewUser::generate(counter);
-            rt.block_on(SqlxBench::insert_user(&pool, &user)).unwrap()
-        });
-        rt.block_on(SqlxBench::cleanup(&pool)).unwrap();
-    });
+fn fail_fast() -> bool {
+    std::env::var_os("BENCH_FAIL_FAST").is_some_and(|v| v != "0")
+}
 
-    // sea-orm
-    group.bench_function("sea_orm", |b| {
-        let db = rt.block_on(SeaOrmBench::connect()).unwrap();
-        let mut counter = 0usize;
-        b.iter(|| {
-            counter += 1;
-            let user = NewUser::generate(counter);
-            rt.block_on(SeaOrmBench::insert_user(&db, &user)).unwrap()
-        });
-        rt.block_on(SeaOrmBench::cleanup(&db)).unwrap();
-    });
+/// Record an error for the end-of-run summary, or panic in fail-fast mode
+fn record_error(backend: &str, message: String) {
+    if fail_fast() {
+        panic!("{backend}: {message}");
+    }
+    let mut errors = ERRORS.lock().unwrap_or_else(|e| e.into_inner());
+    *errors
+        .entry(backend.to_string())
+        .or_default()
+        .entry(message)
+        .or_default() += 1;
+}
 
-    // diesel (sync)
-    group.bench_function("diesel", |b| {
-        let pool = DieselBench::connect().unwrap();
-        let mut conn = pool.get().unwrap();
-        let mut counter = 0usize;
-        b.iter(|| {
-            counter += 1;
-            let user = NewUser::generate(counter);
-            DieselBench::insert_user(&mut conn, &user).unwrap()
-        });
-        DieselBench::cleanup(&mut conn).unwrap();
-    });
-
-    // clorinde
-    group.bench_function("clorinde", |b| {
-        let client = rt.block_on(ClorindeBench::connect()).unwrap();
-        let mut counter = 0usize;
-        b.iter(|| {
-            counter += 1;
-            let user = NewUser::generate(counter);
-            rt.block_on(ClorindeBench::insert_user(&client, &user))
-                .unwrap()
-        });
-        rt.block_on(ClorindeBench::cleanup(&client)).unwrap();
-    });
+/// The result's value, or `None` once its error has been recorded.
+///
+/// The value goes through [`std::hint::black_box`], so results that a
+/// benchmark drops still count as used and their decoding can't be optimized out.
+fn checked<T, E: Debug>(backend: &str, result: Result<T, E>) -> Option<T> {
+    bench_progress().operation(result.is_ok());
+    result.map(std::hint::black_box).map_err(|e| record_error(backend, format!("{e:?}"))).ok()
+}
 
-    group.finish();
+/// Run one backend's benchmark, recording a panic instead of unwinding further
+fn guarded(backend: &str, benchmark: impl FnOnce()) {
+    bench_progress().start(backend);
+    if fail_fast() {
+        benchmark();
+        return bench_progress().finish();
+    }
+    let result = panic::catch_unwind(AssertUnwindSafe(benchmark));
+    bench_progress().finish();
+    if let Err(panic) = result {
+        let message = panic
+            .downcast_ref::<String>()
+            .map(String::as_str)
+            .or_else(|| panic.downcast_ref::<&str>().copied())
+            .unwrap_or("unknown panic");
+        record_error(backend, format!("benchmark aborted: {message}"));
+    }
 }
 
-fn bench_insert_batch(c: &mut Criterion) {
-    let rt = create_runtime();
-    let mut group = c.benchmark_group("insert_batch_users");
-    group.measurement_time(Duration::from_secs(15));
-    group.sample_size(50);
+fn print_error_summary() {
+    let errors = ERRORS.lock().unwrap_or_else(|e| e.into_inner());
+    if errors.is_empty() {
+        return;
+    }
+    eprintln!();
+    eprintln!("Errors recorded during the run (failed operations were skipped):");
+    for (backend, messages) in errors.iter() {
+        eprintln!("  {backend}: {} error(s)", messages.values().sum::<usize>());
+        for (message, count) in messages {
+            eprintln!("    {count:>6}x  {message}");
+        }
+    }
+}
 
-    for size in SIZES {
-        let users: Vec<NewUser> = (0..*size).map(|i| NewUser::generate(i)).collect();
+// ============================================================================
+// Generic Backend Registration
+// ============================================================================
+//
+// Every per-operation group below is written once against `DatabaseBenchmark`
+// and registered for all backends by `bench_all_backends`, so each library runs
+// exactly the same logic and only the driver/ORM calls differ. Groups that call
+// backend-specific methods expand the same code per backend with
+// `for_each_backend!` (or `bench_backends!` for an async block on one
+// connection) instead of spelling out every backend by hand.
+
+/// A single benchmarked operation, generic over the backend
+trait Operation {
+    /// Whether the operation inserts `bench_user_*` rows that must be cleaned up
+    fn writes(&self) -> bool;
+
+    /// Run one iteration; `iteration` starts at 1 and increases for every call.
+    /// For writing operations it restarts at 1 after each sample's cleanup.
+    async fn run<B: DatabaseBenchmark>(&self, conn: &B::Connection, iteration: usize) -> Result<(), B::Error>;
+}
 
-        group.throughput(Throughput::Elements(*size as u64));
+/// Expand `$body` once per backend, with `$B` naming the backend type and
+/// `$name` its name in benchmark ids and the error summary. Without a list it
+/// covers every PostgreSQL backend, so a group can't leave one out by accident.
+macro_rules! for_each_backend {
+    (|$B:ident, $name:ident| $body:expr) => {
+        for_each_backend!(
+            [
+                TokioPostgresBench => "tokio_postgres",
+                TokioPostgresPreparedBench => "tokio_postgres_prepared",
+                SqlxBench => "sqlx",
+                SqlxMacroBench => "sqlx_macro",
+                SeaOrmBench => "sea_orm",
+                SeaQueryBench => "sea_query",
+                DieselBench => "diesel",
+                ClorindeBench => "clorinde",
+                WtxBench => "wtx",
+            ]
+            |$B, $name| $body
+        )
+    };
+    ([$($backend:ty => $label:literal),+ $(,)?] |$B:ident, $name:ident| $body:expr) => {
+        $({
+            type $B = $backend;
+            let $name: &'static str = $label;
+            $body;
+        })+
+    };
+}
 
-        // tokio-postgres
-        group.bench_with_input(BenchmarkId::new("tokio_postgres", size), size, |b, _| {
-            let client = rt.block_on(TokioPostgresBench::connect()).unwrap();
-            b.iter(|| {
-                rt.block_on(TokioPostgresBench::insert_users_batch(&client, &users))
-                    .unwrap()
+/// Benchmark `$body`, an async block run to completion every iteration, on
+/// each backend of [`for_each_backend!`], with `$conn` opened once per backend
+/// through `DatabaseBenchmark::connect`
+macro_rules! bench_backends {
+    ($group:expr, $rt:expr, $([$($backends:tt)*])? |$B:ident, $name:ident, $conn:ident| $body:expr) => {
+        for_each_backend!($([$($backends)*])? |$B, $name| guarded($name, || {
+            $group.bench_function($name, |b| {
+                let $conn = $rt.block_on(<$B as DatabaseBenchmark>::connect()).unwrap();
+                b.iter(|| $rt.block_on($body));
             });
-            rt.block_on(TokioPostgresBench::cleanup(&client)).unwrap();
-        });
+        }))
+    };
+}
 
-        // sqlx
-        group.bench_with_input(BenchmarkId::new("sqlx", size), size, |b, _| {
-            let pool = rt.block_on(SqlxBench::connect()).unwrap();
-            b.iter(|| {
-                rt.block_on(SqlxBench::insert_users_batch(&pool, &users))
-                    .unwrap()
+/// Register one backend for an operation, optionally parameterised by size.
+///
+/// Criterion calls the routine once per sample. In the group's
+/// [`ConnectionMode::Reused`] mode every call runs on the connection opened
+/// when the benchmark starts; in [`ConnectionMode::PerSample`] each call checks
+/// one out of a [`SamplePool`] opened when the benchmark starts, before it is
+/// timed, and returns it when it ends.
+fn register<B: DatabaseBenchmark, O: Operation>(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    rt: &Runtime,
+    name: &str,
+    parameter: Option<usize>,
+    op: &O,
+) {
+    let mode = *CONNECTION_MODE.lock().unwrap_or_else(|e| e.into_inner());
+    let mut reused = None;
+    let mut pool = None;
+    let routine = |b: &mut Bencher<'_, WallTime>| {
+        let checkout;
+        let conn = match mode {
+            ConnectionMode::Reused => &*reused.get_or_insert_with(|| rt.block_on(B::connect()).unwrap()),
+            ConnectionMode::PerSample => {
+                let pool = pool.get_or_insert_with(|| {
+                    SamplePool::new((0..SAMPLE_POOL_SIZE).map(|_| rt.block_on(B::connect()).unwrap()))
+                });
+                checkout = pool.checkout();
+                &*checkout
+            }
+        };
+        if op.writes() {
+            // Reset at every sample boundary (outside the timed region) so later
+            // samples don't run against a users table grown by earlier ones
+            b.iter_custom(|iters| {
+                checked(name, rt.block_on(B::cleanup(conn)));
+                let start = Instant::now();
+                for iteration in 1..=iters as usize {
+                    checked(name, rt.block_on(op.run::<B>(conn, iteration)));
+                }
+                start.elapsed()
             });
-            rt.block_on(SqlxBench::cleanup(&pool)).unwrap();
-        });
-
-        // sea-orm
-        group.bench_with_input(BenchmarkId::new("sea_orm", size), size, |b, _| {
-            let db = rt.block_on(SeaOrmBench::connect()).unwrap();
+            checked(name, rt.block_on(B::cleanup(conn)));
+        } else {
+            let mut iteration = 0usize;
             b.iter(|| {
-                rt.block_on(SeaOrmBench::insert_users_batch(&db, &users))
-                    .unwrap()
+                iteration += 1;
+                checked(name, rt.block_on(op.run::<B>(conn, iteration)))
             });
-            rt.block_on(SeaOrmBench::cleanup(&db)).unwrap();
-        });
+        }
+    };
+
+    guarded(name, || {
+        match parameter {
+            Some(parameter) => group.bench_function(BenchmarkId::new(name, parameter), routine),
+            None => group.bench_function(name, routine),
+        };
+
+        if op.writes() {
+            match parameter {
+                Some(parameter) => assert_cleaned_up(rt, &format!("{name}/{parameter}")),
+                None => assert_cleaned_up(rt, name),
+            }
+        }
+    });
+}
 
-        // diesel
-        group.bench_with_input(BenchmarkId::new("diesel", size), size, |b, _| {
-            let pool = DieselBench::connect().unwrap();
-            let mut conn = pool.get().unwrap();
-            b.iter(|| DieselBench::insert_users_batch(&mut conn, &users).unwrap());
-            DieselBench::cleanup(&mut conn).unwrap();
-        });
+/// Fail the run if a benchmark's cleanup left rows behind, since they would
+/// inflate the table sizes every later group runs against
+fn assert_cleaned_up(rt: &Runtime, benchmark: &str) {
+    if smoke_test() {
+        let leftover = NullBackend::leftover_rows(&NullBackend::connect());
+        assert!(leftover.is_empty(), "{benchmark} left {leftover} behind after cleanup");
+        return;
+    }
+    let client = rt.block_on(TokioPostgresBench::connect()).unwrap();
+    let leftover = rt.block_on(verify::leftover_rows(&client)).unwrap();
+    assert!(leftover.is_empty(), "{benchmark} left {leftover} behind after cleanup");
+}
 
-        // clorinde
-        group.bench_with_input(BenchmarkId::new("clorinde", size), size, |b, _| {
-            let client = rt.block_on(ClorindeBench::connect()).unwrap();
-            b.iter(|| {
-                rt.block_on(ClorindeBench::insert_users_batch(&client, &users))
-                    .unwrap()
-            });
-            rt.block_on(ClorindeBench::cleanup(&client)).unwrap();
-        });
+/// Register every backend for an operation
+fn bench_all_backends<O: Operation>(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    rt: &Runtime,
+    parameter: Option<usize>,
+    op: &O,
+) {
+    if smoke_test() {
+        return register::<NullBackend, O>(group, rt, "null", parameter, op);
     }
+    for_each_backend!(|B, name| register::<B, O>(group, rt, name, parameter, op));
+}
 
-    group.finish();
+/// User IDs from the seeded dataset, used by the point lookup and update groups
+fn sample_user_ids(rt: &Runtime) -> Vec<Uuid> {
+    if smoke_test() {
+        let users = NullBackend::select_users_limit(&NullBackend::connect(), 100).unwrap();
+        return users.iter().map(|u| u.id).collect();
+    }
+    let client = rt.block_on(TokioPostgresBench::connect()).unwrap();
+    let users = rt
+        .block_on(TokioPostgresBench::select_users_limit(&client, 100))
+        .unwrap();
+    users.iter().map(|u| u.id).collect()
 }
 
 // ============================================================================
-// Select Benchmarks
+// Scenario Benchmarks
 // ============================================================================
+//
+// The insert, select, update, join, aggregate and transaction groups are the
+// entries of `scenario::SCENARIOS`, which `cargo run -- audit` runs as well.
+
+/// One size of a scenario, with the fixture its setup produced
+struct ScenarioRun<'a> {
+    scenario: &'a Scenario,
+    fixture: &'a Fixture,
+    size: usize,
+}
 
-fn bench_select_limit(c: &mut Criterion) {
-    let rt = create_runtime();
-    let mut group = c.benchmark_group("select_users_limit");
-    group.measurement_time(Duration::from_secs(10));
-    group.sample_size(100);
-
-    for size in SIZES {
-        group.throughput(Throughput::Elements(*size as u64));
+impl Operation for ScenarioRun<'_> {
+    fn writes(&self) -> bool {
+        self.scenario.writes()
+    }
 
-        let limit = *size as i64;
+    async fn run<B: DatabaseBenchmark>(&self, conn: &B::Connection, iteration: usize) -> Result<(), B::Error> {
+        self.scenario.run::<B>(conn, self.fixture, self.size, iteration).await
+    }
+}
 
-        // tokio-postgres
-        group.bench_with_input(BenchmarkId::new("tokio_postgres", size), size, |b, _| {
-            let client = rt.block_on(TokioPostgresBench::connect()).unwrap();
-            b.iter(|| {
-                rt.block_on(TokioPostgresBench::select_users_limit(&client, limit))
-                    .unwrap()
+/// One group per scenario, every size on every backend it runs on
+fn bench_scenarios(c: &mut Criterion) {
+    let rt = create_runtime();
+    for scenario in SCENARIOS.iter().filter(|s| tagged(s.tags())) {
+        let mut group = benchmark_group(c, scenario.name, scenario.measurement_time, scenario.sample_size);
+
+        // Set up once on the raw driver, as all backends share the seeded rows
+        let client = (!smoke_test()).then(|| rt.block_on(TokioPostgresBench::connect()).unwrap());
+        let fixture = match &client {
+            Some(client) => rt.block_on(scenario.setup::<TokioPostgresBench>(client)).unwrap(),
+            None => rt.block_on(scenario.setup::<NullBackend>(&NullBackend::connect())).unwrap(),
+        };
+
+        let sizes: Vec<Option<usize>> = match scenario.sizes {
+            [] => vec![None],
+            sizes => sizes.iter().copied().map(Some).collect(),
+        };
+        for parameter in sizes {
+            if let (Some(size), true) = (parameter, scenario.throughput) {
+                group.throughput(Throughput::Elements(size as u64));
+            }
+            let op = ScenarioRun {
+                scenario,
+                fixture: &fixture,
+                size: parameter.unwrap_or(1),
+            };
+            if smoke_test() {
+                register::<NullBackend, _>(&mut group, &rt, "null", parameter, &op);
+                continue;
+            }
+            for_each_backend!(|B, name| if scenario.runs_on(name) {
+                register::<B, _>(&mut group, &rt, name, parameter, &op)
             });
-        });
+        }
 
-        // sqlx
-        group.bench_with_input(BenchmarkId::new("sqlx", size), size, |b, _| {
-            let pool = rt.block_on(SqlxBench::connect()).unwrap();
-            b.iter(|| {
-                rt.block_on(SqlxBench::select_users_limit(&pool, limit))
-                    .unwrap()
-            });
-        });
+        group.finish();
+        match &client {
+            Some(client) => rt.block_on(scenario.teardown::<TokioPostgresBench>(client)).unwrap(),
+            None => rt.block_on(scenario.teardown::<NullBackend>(&NullBackend::connect())).unwrap(),
+        }
+    }
+}
 
-        // sea-orm
-        group.bench_with_input(BenchmarkId::new("sea_orm", size), size, |b, _| {
-            let db = rt.block_on(SeaOrmBench::connect()).unwrap();
-            b.iter(|| {
-                rt.block_on(SeaOrmBench::select_users_limit(&db, *size as u64))
-                    .unwrap()
-            });
-        });
+// ============================================================================
+// Shared Operations
+// ============================================================================
 
-        // diesel
-        group.bench_with_input(BenchmarkId::new("diesel", size), size, |b, _| {
-            let pool = DieselBench::connect().unwrap();
-            let mut conn = pool.get().unwrap();
-            b.iter(|| DieselBench::select_users_limit(&mut conn, limit).unwrap());
-        });
+struct SelectById<'a> {
+    ids: &'a [Uuid],
+}
 
-        // clorinde
-        group.bench_with_input(BenchmarkId::new("clorinde", size), size, |b, _| {
-            let client = rt.block_on(ClorindeBench::connect()).unwrap();
-            b.iter(|| {
-                rt.block_on(ClorindeBench::select_users_limit(&client, limit))
-                    .unwrap()
-            });
-        });
+impl Operation for SelectById<'_> {
+    fn writes(&self) -> bool {
+        false
     }
 
-    group.finish();
+    async fn run<B: DatabaseBenchmark>(&self, conn: &B::Connection, iteration: usize) -> Result<(), B::Error> {
+        let id = self.ids[iteration % self.ids.len()];
+        B::select_user_by_id(conn, id).await.map(consume)
+    }
 }
 
-fn bench_select_filtered(c: &mut Criterion) {
-    let rt = create_runtime();
-    let mut group = c.benchmark_group("select_users_filtered");
-    group.measurement_time(Duration::from_secs(10));
-    group.sample_size(100);
+struct JoinPostsUsers {
+    limit: i64,
+}
 
-    for size in SIZES {
-        group.throughput(Throughput::Elements(*size as u64));
+impl Operation for JoinPostsUsers {
+    fn writes(&self) -> bool {
+        false
+    }
 
-        let limit = *size as i64;
-        let min_age = 25;
-        let max_age = 55;
+    async fn run<B: DatabaseBenchmark>(&self, conn: &B::Connection, _: usize) -> Result<(), B::Error> {
+        B::select_posts_with_user(conn, self.limit).await.map(consume)
+    }
+}
 
-        // tokio-postgres
-        group.bench_with_input(BenchmarkId::new("tokio_postgres", size), size, |b, _| {
-            let client = rt.block_on(TokioPostgresBench::connect()).unwrap();
-            b.iter(|| {
-                rt.block_on(TokioPostgresBench::select_users_filtered(
-                    &client, min_age, max_age, limit,
-                ))
-                .unwrap()
-            });
-        });
+struct JoinTriple {
+    limit: i64,
+}
 
-        // sqlx
-        group.bench_with_input(BenchmarkId::new("sqlx", size), size, |b, _| {
-            let pool = rt.block_on(SqlxBench::connect()).unwrap();
-            b.iter(|| {
-                rt.block_on(SqlxBench::select_users_filtered(&pool, min_age, max_age, limit))
-                    .unwrap()
-            });
-        });
+impl Operation for JoinTriple {
+    fn writes(&self) -> bool {
+        false
+    }
 
-        // sea-orm
-        group.bench_with_input(BenchmarkId::new("sea_orm", size), size, |b, _| {
-            let db = rt.block_on(SeaOrmBench::connect()).unwrap();
-            b.iter(|| {
-                rt.block_on(SeaOrmBench::select_users_filtered(
-                    &db,
-                    min_age,
-                    max_age,
-                    *size as u64,
-                ))
-                .unwrap()
-            });
-        });
+    async fn run<B: DatabaseBenchmark>(&self, conn: &B::Connection, _: usize) -> Result<(), B::Error> {
+        B::select_users_posts_comments(conn, self.limit)
+            .await
+            .map(consume)
+    }
+}
 
-        // diesel
-        group.bench_with_input(BenchmarkId::new("diesel", size), size, |b, _| {
-            let pool = DieselBench::connect().unwrap();
-            let mut conn = pool.get().unwrap();
-            b.iter(|| {
-                DieselBench::select_users_filtered(&mut conn, min_age, max_age, limit).unwrap()
-            });
-        });
+struct CountPostsPerUser;
 
-        // clorinde
-        group.bench_with_input(BenchmarkId::new("clorinde", size), size, |b, _| {
-            let client = rt.block_on(ClorindeBench::connect()).unwrap();
-            b.iter(|| {
-                rt.block_on(ClorindeBench::select_users_filtered(
-                    &client, min_age, max_age, limit,
-                ))
-                .unwrap()
-            });
-        });
+impl Operation for CountPostsPerUser {
+    fn writes(&self) -> bool {
+        false
     }
 
-    group.finish();
+    async fn run<B: DatabaseBenchmark>(&self, conn: &B::Connection, _: usize) -> Result<(), B::Error> {
+        B::count_posts_per_user(conn).await.map(consume)
+    }
 }
 
-fn bench_select_by_id(c: &mut Criterion) {
-    let rt = create_runtime();
-    let mut group = c.benchmark_group("select_user_by_id");
-    group.measurement_time(Duration::from_secs(10));
-    group.sample_size(200);
+// ============================================================================
+// Heavy Workload Benchmarks
+// ============================================================================
 
-    // Setup: get some user IDs
-    let client = rt.block_on(TokioPostgresBench::connect()).unwrap();
-    let users = rt
-        .block_on(TokioPostgresBench::select_users_limit(&client, 100))
-        .unwrap();
-    let user_ids: Vec<Uuid> = users.iter().map(|u| u.id).collect();
+/// Reads and writes in the proportion given by `HeavyWorkloadConfig`
+struct MixedWorkload {
+    mix: Vec<WorkloadOp>,
+}
 
-    // tokio-postgres
-    group.bench_function("tokio_postgres", |b| {
-        let client = rt.block_on(TokioPostgresBench::connect()).unwrap();
-        let mut idx = 0;
-        b.iter(|| {
-            let id = user_ids[idx % user_ids.len()];
-            idx += 1;
-            rt.block_on(TokioPostgresBench::select_user_by_id(&client, id))
-                .unwrap()
-        });
-    });
+impl Operation for MixedWorkload {
+    fn writes(&self) -> bool {
+        true
+    }
 
-    // sqlx
-    group.bench_function("sqlx", |b| {
-        let pool = rt.block_on(SqlxBench::connect()).unwrap();
-        let mut idx = 0;
-        b.iter(|| {
-            let id = user_ids[idx % user_ids.len()];
-            idx += 1;
-            rt.block_on(SqlxBench::select_user_by_id(&pool, id)).unwrap()
-        });
-    });
+    async fn run<B: DatabaseBenchmark>(&self, conn: &B::Connection, iteration: usize) -> Result<(), B::Error> {
+        let start = (iteration - 1) * self.mix.len();
+        for (i, op) in self.mix.iter().enumerate() {
+            match op {
+                WorkloadOp::Write => {
+                    let user = NewUser::generate(start + i);
+                    B::insert_user(conn, &user).await?;
+                }
+                WorkloadOp::Read => {
+                    consume(B::select_users_limit(conn, 50).await?);
+                }
+            }
+        }
+        Ok(())
+    }
+}
 
-    // sea-orm
-    group.bench_function("sea_orm", |b| {
-        let db = rt.block_on(SeaOrmBench::connect()).unwrap();
-        let mut idx = 0;
-        b.iter(|| {
-            let id = user_ids[idx % user_ids.len()];
-            idx += 1;
-            rt.block_on(SeaOrmBench::select_user_by_id(&db, id)).unwrap()
-        });
-    });
+struct ReadIntensive {
+    operations: usize,
+}
 
-    // diesel
-    group.bench_function("diesel", |b| {
-        let pool = DieselBench::connect().unwrap();
-        let mut conn = pool.get().unwrap();
-        let mut idx = 0;
-        b.iter(|| {
-            let id = user_ids[idx % user_ids.len()];
-            idx += 1;
-            DieselBench::select_user_by_id(&mut conn, id).unwrap()
-        });
-    });
+impl Operation for ReadIntensive {
+    fn writes(&self) -> bool {
+        false
+    }
 
-    // clorinde
-    group.bench_function("clorinde", |b| {
-        let client = rt.block_on(ClorindeBench::connect()).unwrap();
-        let mut idx = 0;
-        b.iter(|| {
-            let id = user_ids[idx % user_ids.len()];
-            idx += 1;
-            rt.block_on(ClorindeBench::select_user_by_id(&client, id))
-                .unwrap()
-        });
-    });
+    async fn run<B: DatabaseBenchmark>(&self, conn: &B::Connection, _: usize) -> Result<(), B::Error> {
+        for i in 0..self.operations {
+            match i % 4 {
+                0 => {
+                    consume(B::select_users_limit(conn, 100).await?);
+                }
+                1 => {
+                    consume(B::select_users_filtered(conn, 25, 55, 50).await?);
+                }
+                2 => {
+                    consume(B::select_posts_with_user(conn, 50).await?);
+                }
+                _ => {
+                    consume(B::count_posts_per_user(conn).await?);
+                }
+            }
+        }
+        Ok(())
+    }
+}
 
-    group.finish();
+/// Insert a user, insert a post for it, then update the user
+struct WriteIntensive {
+    batch_size: usize,
+    /// Send each step as one multi-statement batch instead of three statements
+    batched: bool,
 }
 
-// ============================================================================
-// Update Benchmarks
-// ============================================================================
+impl Operation for WriteIntensive {
+    fn writes(&self) -> bool {
+        true
+    }
 
-fn bench_update_user(c: &mut Criterion) {
-    let rt = create_runtime();
-    let mut group = c.benchmark_group("update_user");
-    group.measurement_time(Duration::from_secs(10));
-    group.sample_size(100);
+    async fn run<B: DatabaseBenchmark>(&self, conn: &B::Connection, iteration: usize) -> Result<(), B::Error> {
+        let start = (iteration - 1) * self.batch_size;
+        for index in start..start + self.batch_size {
+            let user = NewUser::generate(index);
+            if self.batched {
+                let post = NewPost::generate(Uuid::nil(), index);
+                B::insert_user_post_update_batched(conn, &user, &post, "Modified", "Name").await?;
+                continue;
+            }
+            let user_id = B::insert_user(conn, &user).await?;
 
-    // Setup: get some user IDs
-    let client = rt.block_on(TokioPostgresBench::connect()).unwrap();
-    let users = rt
-        .block_on(TokioPostgresBench::select_users_limit(&client, 100))
-        .unwrap();
-    let user_ids: Vec<Uuid> = users.iter().map(|u| u.id).collect();
+            let post = NewPost::generate(user_id, index);
+            B::insert_post(conn, &post).await?;
 
-    // tokio-postgres
-    group.bench_function("tokio_postgres", |b| {
-        let client = rt.block_on(TokioPostgresBench::connect()).unwrap();
-        let mut idx = 0;
-        b.iter(|| {
-            let id = user_ids[idx % user_ids.len()];
-            idx += 1;
-            rt.block_on(TokioPostgresBench::update_user(
-                &client,
-                id,
-                "UpdatedFirst",
-                "UpdatedLast",
-            ))
-            .unwrap()
-        });
-    });
+            B::update_user(conn, user_id, "Modified", "Name").await?;
+        }
+        Ok(())
+    }
+}
 
-    // sqlx
-    group.bench_function("sqlx", |b| {
-        let pool = rt.block_on(SqlxBench::connect()).unwrap();
-        let mut idx = 0;
-        b.iter(|| {
-            let id = user_ids[idx % user_ids.len()];
-            idx += 1;
-            rt.block_on(SqlxBench::update_user(&pool, id, "UpdatedFirst", "UpdatedLast"))
-                .unwrap()
-        });
-    });
+fn bench_heavy_mixed_workload(c: &mut Criterion) {
+    if !tagged(&[Tag::Read, Tag::Write]) {
+        return;
+    }
+    let rt = create_runtime();
+    let mut group = benchmark_group(c, "heavy_mixed_workload", Duration::from_secs(30), 20);
 
-    // sea-orm
-    group.bench_function("sea_orm", |b| {
-        let db = rt.block_on(SeaOrmBench::connect()).unwrap();
-        let mut idx = 0;
-        b.iter(|| {
-            let id = user_ids[idx % user_ids.len()];
-            idx += 1;
-            rt.block_on(SeaOrmBench::update_user(&db, id, "UpdatedFirst", "UpdatedLast"))
-                .unwrap()
-        });
-    });
+    let config = HeavyWorkloadConfig::default();
+    let op = MixedWorkload {
+        mix: config.workload_mix(config.operations_per_connection),
+    };
+    bench_all_backends(&mut group, &rt, None, &op);
 
-    // diesel
-    group.bench_function("diesel", |b| {
-        let pool = DieselBench::connect().unwrap();
-        let mut conn = pool.get().unwrap();
-        let mut idx = 0;
-        b.iter(|| {
-            let id = user_ids[idx % user_ids.len()];
-            idx += 1;
-            DieselBench::update_user(&mut conn, id, "UpdatedFirst", "UpdatedLast").unwrap()
-        });
-    });
+    group.finish();
+}
 
-    // clorinde
-    group.bench_function("clorinde", |b| {
-        let client = rt.block_on(ClorindeBench::connect()).unwrap();
-        let mut idx = 0;
-        b.iter(|| {
-            let id = user_ids[idx % user_ids.len()];
-            idx += 1;
-            rt.block_on(ClorindeBench::update_user(
-                &client,
-                id,
-                "UpdatedFirst",
-                "UpdatedLast",
-            ))
-            .unwrap()
-        });
-    });
+fn bench_heavy_read_intensive(c: &mut Criterion) {
+    if !tagged(&[Tag::Read]) {
+        return;
+    }
+    let rt = create_runtime();
+    let mut group = benchmark_group(c, "heavy_read_intensive", Duration::from_secs(20), 30);
+
+    bench_all_backends(&mut group, &rt, None, &ReadIntensive { operations: 200 });
 
     group.finish();
 }
 
-// ============================================================================
-// Join Benchmarks
-// ============================================================================
-
-fn bench_join_posts_users(c: &mut Criterion) {
+fn bench_heavy_write_intensive(c: &mut Criterion) {
+    if !tagged(&[Tag::Write]) {
+        return;
+    }
     let rt = create_runtime();
-    let mut group = c.benchmark_group("join_posts_users");
-    group.measurement_time(Duration::from_secs(10));
-    group.sample_size(50);
+    let mut group = benchmark_group(c, "heavy_write_intensive", Duration::from_secs(20), 20);
 
-    for size in SIZES {
-        group.throughput(Throughput::Elements(*size as u64));
+    bench_all_backends(&mut group, &rt, None, &WriteIntensive { batch_size: 50, batched: false });
 
-        let limit = *size as i64;
+    group.finish();
+}
 
-        // tokio-postgres
-        group.bench_with_input(BenchmarkId::new("tokio_postgres", size), size, |b, _| {
-            let client = rt.block_on(TokioPostgresBench::connect()).unwrap();
-            b.iter(|| {
-                rt.block_on(TokioPostgresBench::select_posts_with_user(&client, limit))
-                    .unwrap()
-            });
-        });
+/// `heavy_write_intensive` with each step in one round trip instead of three
+fn bench_heavy_write_batched(c: &mut Criterion) {
+    if !tagged(&[Tag::Write, Tag::Transaction]) {
+        return;
+    }
+    let rt = create_runtime();
+    let mut group = benchmark_group(c, "heavy_write_batched", Duration::from_secs(20), 20);
 
-        // sqlx
-        group.bench_with_input(BenchmarkId::new("sqlx", size), size, |b, _| {
-            let pool = rt.block_on(SqlxBench::connect()).unwrap();
-            b.iter(|| {
-                rt.block_on(SqlxBench::select_posts_with_user(&pool, limit))
-                    .unwrap()
-            });
-        });
+    bench_all_backends(&mut group, &rt, None, &WriteIntensive { batch_size: 50, batched: true });
 
-        // sea-orm
-        group.bench_with_input(BenchmarkId::new("sea_orm", size), size, |b, _| {
-            let db = rt.block_on(SeaOrmBench::connect()).unwrap();
-            b.iter(|| {
-                rt.block_on(SeaOrmBench::select_posts_with_user(&db, *size as u64))
-                    .unwrap()
-            });
-        });
+    group.finish();
+}
 
-        // diesel
-        group.bench_with_input(BenchmarkId::new("diesel", size), size, |b, _| {
-            let pool = DieselBench::connect().unwrap();
-            let mut conn = pool.get().unwrap();
-            b.iter(|| DieselBench::select_posts_with_user(&mut conn, limit).unwrap());
-        });
+// ============================================================================
+// Unicode Benchmarks
+// ============================================================================
+//
+// The same writes, reads and name searches with names and post bodies in each
+// `TextScript`: multi-byte text costs more to encode, decode and case-fold,
+// and the trigram indexes on the name columns (with the `C` ctype) only hold
+// ASCII trigrams, so `ILIKE` on other scripts falls back to rechecking rows.
+
+/// Users, one post each, inserted in the script under test for the read groups
+const UNICODE_USERS: usize = 1000;
+const UNICODE_READ_LIMIT: i64 = 100;
+const UNICODE_SEARCH_LIMIT: i64 = 50;
+
+struct InsertUnicode {
+    script: TextScript,
+}
 
-        // clorinde
-        group.bench_with_input(BenchmarkId::new("clorinde", size), size, |b, _| {
-            let client = rt.block_on(ClorindeBench::connect()).unwrap();
-            b.iter(|| {
-                rt.block_on(ClorindeBench::select_posts_with_user(&client, limit))
-                    .unwrap()
-            });
-        });
+impl Operation for InsertUnicode {
+    fn writes(&self) -> bool {
+        true
     }
 
-    group.finish();
+    async fn run<B: DatabaseBenchmark>(&self, conn: &B::Connection, iteration: usize) -> Result<(), B::Error> {
+        let user_id = B::insert_user(conn, &NewUser::generate_in(iteration, self.script)).await?;
+        B::insert_post(conn, &NewPost::generate_in(user_id, iteration, self.script))
+            .await
+            .map(consume)
+    }
 }
 
-fn bench_join_triple(c: &mut Criterion) {
-    let rt = create_runtime();
-    let mut group = c.benchmark_group("join_users_posts_comments");
-    group.measurement_time(Duration::from_secs(15));
-    group.sample_size(30);
-
-    for size in SIZES {
-        group.throughput(Throughput::Elements(*size as u64));
+struct SearchNames {
+    fragment: &'static str,
+}
 
-        let limit = *size as i64;
+impl Operation for SearchNames {
+    fn writes(&self) -> bool {
+        false
+    }
 
-        // tokio-postgres
-        group.bench_with_input(BenchmarkId::new("tokio_postgres", size), size, |b, _| {
+    async fn run<B: DatabaseBenchmark>(&self, conn: &B::Connection, _: usize) -> Result<(), B::Error> {
+        B::search_users_by_name(conn, self.fragment, UNICODE_SEARCH_LIMIT)
+            .await
+            .map(consume)
+    }
+}
+
+/// Insert [`UNICODE_USERS`] users in `script`, each with one post, which are
+/// then the newest posts the read group joins
+async fn insert_unicode_dataset<B: DatabaseBenchmark>(conn: &B::Connection, script: TextScript) -> Result<(), B::Error> {
+    B::cleanup(conn).await?;
+    let users: Vec<NewUser> = (0..UNICODE_USERS).map(|i| NewUser::generate_in(i, script)).collect();
+    let user_ids = B::insert_users_batch(conn, &users).await?;
+    for (i, user_id) in user_ids.into_iter().enumerate() {
+        B::insert_post(conn, &NewPost::generate_in(user_id, i, script)).await?;
+    }
+    Ok(())
+}
+
+fn bench_unicode(c: &mut Criterion) {
+    if !tagged(&[Tag::Read, Tag::Write, Tag::Types]) {
+        return;
+    }
+    let rt = create_runtime();
+    for script in TextScript::ALL {
+        let mut group = benchmark_group(c, format!("unicode_insert/{}", script.name()), Duration::from_secs(10), 50);
+        bench_all_backends(&mut group, &rt, None, &InsertUnicode { script });
+        group.finish();
+
+        if smoke_test() {
+            rt.block_on(insert_unicode_dataset::<NullBackend>(&NullBackend::connect(), script)).unwrap();
+        } else {
+            let client = rt.block_on(TokioPostgresBench::connect()).unwrap();
+            rt.block_on(insert_unicode_dataset::<TokioPostgresBench>(&client, script)).unwrap();
+        }
+
+        let mut group = benchmark_group(c, format!("unicode_read_posts/{}", script.name()), Duration::from_secs(10), 50);
+        group.throughput(Throughput::Elements(UNICODE_READ_LIMIT as u64));
+        bench_all_backends(&mut group, &rt, None, &JoinPostsUsers { limit: UNICODE_READ_LIMIT });
+        group.finish();
+
+        let mut group = benchmark_group(c, format!("unicode_search_names/{}", script.name()), Duration::from_secs(10), 50);
+        bench_all_backends(&mut group, &rt, None, &SearchNames { fragment: script.search_fragment() });
+        group.finish();
+
+        if smoke_test() {
+            NullBackend::cleanup(&NullBackend::connect()).unwrap();
+        } else {
+            let client = rt.block_on(TokioPostgresBench::connect()).unwrap();
+            rt.block_on(TokioPostgresBench::cleanup(&client)).unwrap();
+        }
+    }
+}
+
+// ============================================================================
+// Zero- and Single-Row Benchmarks
+// ============================================================================
+//
+// Point lookups that hit and miss, and list queries returning nothing and one
+// row, on every backend. See `pg_benchmark::fast_path`.
+
+/// Ids no user has, cycled through by the miss lookups
+const MISSING_IDS: usize = 100;
+
+struct FastPathRun<'a> {
+    path: FastPath,
+    hits: &'a [Uuid],
+    misses: &'a [Uuid],
+}
+
+impl Operation for FastPathRun<'_> {
+    fn writes(&self) -> bool {
+        false
+    }
+
+    async fn run<B: DatabaseBenchmark>(&self, conn: &B::Connection, iteration: usize) -> Result<(), B::Error> {
+        self.path.run::<B>(conn, self.hits, self.misses, iteration).await.map(consume)
+    }
+}
+
+fn bench_fast_path(c: &mut Criterion) {
+    if !tagged(&[Tag::Read]) {
+        return;
+    }
+    let rt = create_runtime();
+    let hits = sample_user_ids(&rt);
+    let misses = fast_path::missing_ids(MISSING_IDS);
+    for path in FastPath::ALL {
+        let mut group = benchmark_group(c, format!("fast_path/{}", path.name()), Duration::from_secs(10), 100);
+        bench_all_backends(&mut group, &rt, None, &FastPathRun { path, hits: &hits, misses: &misses });
+        group.finish();
+    }
+}
+
+// ============================================================================
+// JSON Response Benchmarks
+// ============================================================================
+//
+// The newest posts with their authors, serialized into the body `GET /posts`
+// sends (`web::posts_body`): the database-to-API-response path a handler
+// spends, without the HTTP server and pool of the `web_api` group.
+
+struct JsonResponse {
+    limit: i64,
+}
+
+impl Operation for JsonResponse {
+    fn writes(&self) -> bool {
+        false
+    }
+
+    async fn run<B: DatabaseBenchmark>(&self, conn: &B::Connection, _: usize) -> Result<(), B::Error> {
+        let posts = B::select_posts_with_user(conn, self.limit).await?;
+        consume(web::posts_body(posts).expect("posts serialize to JSON"));
+        Ok(())
+    }
+}
+
+fn bench_json_response(c: &mut Criterion) {
+    if !tagged(&[Tag::Read, Tag::Join]) {
+        return;
+    }
+    let rt = create_runtime();
+    let mut group = benchmark_group(c, "json_response", Duration::from_secs(10), 50);
+    for &limit in SIZES {
+        group.throughput(Throughput::Elements(limit as u64));
+        bench_all_backends(&mut group, &rt, Some(limit), &JsonResponse { limit: limit as i64 });
+    }
+    group.finish();
+}
+
+// ============================================================================
+// Comment Pagination Benchmarks
+// ============================================================================
+//
+// A page of a hot post's comments, newest first (`select_comments_for_post`),
+// starting at each of `COMMENT_PAGE_DEPTHS`: the foreign key lookup of a post
+// with thousands of comments, paged with keyset pagination. The group seeds
+// the hot posts itself (`hot_posts::seed`), as the seeded comments are spread
+// a handful to a post.
+
+/// Comments into each hot post the pages start at: the first page, the middle
+/// of the thread and its last full page
+const COMMENT_PAGE_DEPTHS: [usize; 3] = [
+    0,
+    hot_posts::COMMENTS_PER_POST / 2,
+    hot_posts::COMMENTS_PER_POST - hot_posts::PAGE_SIZE as usize,
+];
+
+struct CommentPage {
+    /// Each hot post with the key its page starts after, taken in turn
+    pages: Vec<(Uuid, Option<CommentKey>)>,
+}
+
+impl Operation for CommentPage {
+    fn writes(&self) -> bool {
+        false
+    }
+
+    async fn run<B: DatabaseBenchmark>(&self, conn: &B::Connection, iteration: usize) -> Result<(), B::Error> {
+        let (post, before) = self.pages[iteration % self.pages.len()];
+        consume(B::select_comments_for_post(conn, post, hot_posts::PAGE_SIZE, before).await?);
+        Ok(())
+    }
+}
+
+/// Seed the hot posts through `B` and find where the pages of every one of
+/// `COMMENT_PAGE_DEPTHS` start
+async fn seed_comment_pages<B: DatabaseBenchmark>(conn: &B::Connection) -> Result<Vec<CommentPage>, B::Error> {
+    B::cleanup(conn).await?;
+    let posts = hot_posts::seed::<B>(conn).await?;
+    let mut pages = Vec::with_capacity(COMMENT_PAGE_DEPTHS.len());
+    for depth in COMMENT_PAGE_DEPTHS {
+        let mut page = CommentPage { pages: Vec::with_capacity(posts.len()) };
+        for &post in &posts {
+            page.pages.push((post, hot_posts::key_at::<B>(conn, post, depth).await?));
+        }
+        pages.push(page);
+    }
+    Ok(pages)
+}
+
+fn bench_comment_pagination(c: &mut Criterion) {
+    if !tagged(&[Tag::Read]) {
+        return;
+    }
+    let rt = create_runtime();
+    let client = (!smoke_test()).then(|| rt.block_on(TokioPostgresBench::connect()).unwrap());
+    let pages = match &client {
+        Some(client) => rt.block_on(seed_comment_pages::<TokioPostgresBench>(client)).unwrap(),
+        None => rt.block_on(seed_comment_pages::<NullBackend>(&NullBackend::connect())).unwrap(),
+    };
+
+    let mut group = benchmark_group(c, "comment_pagination", Duration::from_secs(10), 50);
+    group.throughput(Throughput::Elements(hot_posts::PAGE_SIZE as u64));
+    for (depth, page) in COMMENT_PAGE_DEPTHS.into_iter().zip(&pages) {
+        bench_all_backends(&mut group, &rt, Some(depth), page);
+    }
+    group.finish();
+
+    match &client {
+        Some(client) => rt.block_on(TokioPostgresBench::cleanup(client)).unwrap(),
+        None => NullBackend::cleanup(&NullBackend::connect()).unwrap(),
+    }
+}
+
+// ============================================================================
+// Status Filter Benchmarks
+// ============================================================================
+//
+// The newest posts of a status (`select_posts_by_status`), which the
+// `(status, created_at)` index serves without a sort. Before timing, every
+// backend's statement is planned (`status_filter::explain`) and a backend whose
+// plan doesn't scan the index is reported with the plan it gets, as its time
+// then measures that plan rather than the library. The group creates the
+// index if the database predates it.
+
+struct PostsByStatus;
+
+impl Operation for PostsByStatus {
+    fn writes(&self) -> bool {
+        false
+    }
+
+    async fn run<B: DatabaseBenchmark>(&self, conn: &B::Connection, iteration: usize) -> Result<(), B::Error> {
+        let status = status_filter::STATUSES[iteration % status_filter::STATUSES.len()];
+        consume(B::select_posts_by_status(conn, status, status_filter::LIMIT).await?);
+        Ok(())
+    }
+}
+
+/// Report every backend whose `select_posts_by_status` doesn't scan the index
+fn check_status_filter_plans(rt: &Runtime, client: &tokio_postgres::Client) {
+    if !rt.block_on(audit::available(client)) {
+        return eprintln!("skipping the status_filter plan check, pg_stat_statements is not loaded");
+    }
+    for_each_backend!(|B, name| {
+        let conn = rt.block_on(<B as DatabaseBenchmark>::connect()).unwrap();
+        match rt.block_on(status_filter::explain::<B>(name, &conn, client)) {
+            Ok(usage) if !usage.uses_index() => eprintln!("status_filter: {usage}"),
+            Ok(_) => {}
+            Err(e) => eprintln!("status_filter ({name}): can't check the plan: {e}"),
+        }
+    });
+}
+
+fn bench_status_filter(c: &mut Criterion) {
+    if !tagged(&[Tag::Read]) {
+        return;
+    }
+    let rt = create_runtime();
+    if !smoke_test() {
+        let client = rt.block_on(TokioPostgresBench::connect()).unwrap();
+        rt.block_on(indexes::restore(&client)).unwrap();
+        check_status_filter_plans(&rt, &client);
+    }
+
+    let mut group = benchmark_group(c, "status_filter", Duration::from_secs(10), 50);
+    group.throughput(Throughput::Elements(status_filter::LIMIT as u64));
+    bench_all_backends(&mut group, &rt, None, &PostsByStatus);
+    group.finish();
+}
+
+// ============================================================================
+// Query Builder Benchmarks
+// ============================================================================
+//
+// SQL generation alone, with no connection: what each backend's builder costs
+// before anything is sent. Needs no database, so it runs in smoke tests too.
+
+fn bench_query_build(c: &mut Criterion) {
+    if !tagged(&[Tag::Read, Tag::Write, Tag::Join]) {
+        return;
+    }
+    let mut group = benchmark_group(c, "query_build", Duration::from_secs(5), 100);
+    let id = Uuid::new_v4();
+    let limit = 100;
+
+    for &query in Query::ALL {
+        group.bench_function(BenchmarkId::new(query.name(), "diesel"), |b| {
+            b.iter(|| query_build::diesel(query, std::hint::black_box(id), std::hint::black_box(limit)))
+        });
+        group.bench_function(BenchmarkId::new(query.name(), "sea_orm"), |b| {
+            b.iter(|| query_build::sea_orm(query, std::hint::black_box(id), std::hint::black_box(limit as u64)))
+        });
+        group.bench_function(BenchmarkId::new(query.name(), "sea_query"), |b| {
+            b.iter(|| query_build::sea_query(query, std::hint::black_box(id), std::hint::black_box(limit)))
+        });
+        group.bench_function(BenchmarkId::new(query.name(), "raw_format"), |b| {
+            b.iter(|| query_build::raw(query, std::hint::black_box(id), std::hint::black_box(limit)))
+        });
+    }
+
+    group.finish();
+}
+
+// ============================================================================
+// Calibration Benchmarks
+// ============================================================================
+//
+// The same statement on every backend, sleeping `calibration::SLEEP` on the
+// server and returning one constant row, so the server's time is fixed and
+// what is left is each library's overhead. That overhead is printed after
+// each backend's run.
+
+fn register_calibration<B: Calibration>(group: &mut BenchmarkGroup<'_, WallTime>, rt: &Runtime, name: &str) {
+    guarded(name, || {
+        let conn = rt.block_on(B::connect()).unwrap();
+        let mut total = (Duration::ZERO, 0u32);
+        group.bench_function(name, |b| {
+            b.iter_custom(|iters| {
+                let start = Instant::now();
+                for _ in 0..iters {
+                    checked(name, rt.block_on(B::fixed_row(&conn, calibration::SLEEP)));
+                }
+                let elapsed = start.elapsed();
+                total = (total.0 + elapsed, total.1 + iters as u32);
+                elapsed
+            })
+        });
+        let (elapsed, queries) = total;
+        eprintln!(
+            "calibration_fixed_cost ({name}): {:?} client overhead per query",
+            calibration::overhead(elapsed, queries)
+        );
+    });
+}
+
+fn bench_calibration(c: &mut Criterion) {
+    if !tagged(&[Tag::Read]) {
+        return;
+    }
+    let rt = create_runtime();
+    let mut group = benchmark_group(c, "calibration_fixed_cost", Duration::from_secs(10), 100);
+    for_each_backend!(
+        [
+            TokioPostgresBench => "tokio_postgres",
+            SqlxBench => "sqlx",
+            SeaOrmBench => "sea_orm",
+            DieselBench => "diesel",
+            ClorindeBench => "clorinde",
+        ]
+        |B, name| register_calibration::<B>(&mut group, &rt, name)
+    );
+    group.finish();
+}
+
+// ============================================================================
+// Sync vs Async Driver Benchmarks
+// ============================================================================
+//
+// The blocking `postgres` crate is tokio-postgres driven on a runtime of its
+// own per call, so running the same SQL on both measures what the caller's
+// tokio runtime costs. The blocking client panics inside another runtime, so
+// its series calls it directly rather than through `rt.block_on`.
+
+/// One operation both drivers run with the same SQL
+#[derive(Clone, Copy)]
+enum DriverOp {
+    SelectById,
+    SelectLimit,
+    JoinPostsUsers,
+    CountPostsPerUser,
+    InsertUser,
+    InsertUserWithPosts,
+}
+
+impl DriverOp {
+    const ALL: [DriverOp; 6] = [
+        DriverOp::SelectById,
+        DriverOp::SelectLimit,
+        DriverOp::JoinPostsUsers,
+        DriverOp::CountPostsPerUser,
+        DriverOp::InsertUser,
+        DriverOp::InsertUserWithPosts,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            DriverOp::SelectById => "select_by_id",
+            DriverOp::SelectLimit => "select_limit_100",
+            DriverOp::JoinPostsUsers => "join_posts_users_100",
+            DriverOp::CountPostsPerUser => "count_posts_per_user",
+            DriverOp::InsertUser => "insert_user",
+            DriverOp::InsertUserWithPosts => "insert_user_with_5_posts",
+        }
+    }
+
+    fn tags(self) -> &'static [Tag] {
+        match self {
+            DriverOp::SelectById | DriverOp::SelectLimit | DriverOp::CountPostsPerUser => &[Tag::Read],
+            DriverOp::JoinPostsUsers => &[Tag::Read, Tag::Join],
+            DriverOp::InsertUser => &[Tag::Write],
+            DriverOp::InsertUserWithPosts => &[Tag::Write, Tag::Transaction],
+        }
+    }
+
+    fn posts(iteration: usize) -> Vec<NewPost> {
+        (0..5).map(|i| NewPost::generate(Uuid::nil(), iteration * 5 + i)).collect()
+    }
+
+    /// The operation on the blocking client
+    fn run_sync(self, client: &mut postgres::Client, ids: &[Uuid], iteration: usize) -> Result<(), postgres::Error> {
+        match self {
+            DriverOp::SelectById => {
+                PostgresSyncBench::select_user_by_id(client, ids[iteration % ids.len()]).map(consume)
+            }
+            DriverOp::SelectLimit => PostgresSyncBench::select_users_limit(client, 100).map(consume),
+            DriverOp::JoinPostsUsers => PostgresSyncBench::select_posts_with_user(client, 100).map(consume),
+            DriverOp::CountPostsPerUser => PostgresSyncBench::count_posts_per_user(client).map(consume),
+            DriverOp::InsertUser => PostgresSyncBench::insert_user(client, &NewUser::generate(iteration)).map(consume),
+            DriverOp::InsertUserWithPosts => {
+                let user = NewUser::generate(iteration);
+                PostgresSyncBench::insert_user_with_posts(client, &user, &Self::posts(iteration)).map(consume)
+            }
+        }
+    }
+}
+
+/// A [`DriverOp`] on the sampled user ids, for the async series
+struct DriverRun<'a> {
+    op: DriverOp,
+    ids: &'a [Uuid],
+}
+
+impl Operation for DriverRun<'_> {
+    fn writes(&self) -> bool {
+        matches!(self.op, DriverOp::InsertUser | DriverOp::InsertUserWithPosts)
+    }
+
+    async fn run<B: DatabaseBenchmark>(&self, conn: &B::Connection, iteration: usize) -> Result<(), B::Error> {
+        match self.op {
+            DriverOp::SelectById => B::select_user_by_id(conn, self.ids[iteration % self.ids.len()]).await.map(consume),
+            DriverOp::SelectLimit => B::select_users_limit(conn, 100).await.map(consume),
+            DriverOp::JoinPostsUsers => B::select_posts_with_user(conn, 100).await.map(consume),
+            DriverOp::CountPostsPerUser => B::count_posts_per_user(conn).await.map(consume),
+            DriverOp::InsertUser => B::insert_user(conn, &NewUser::generate(iteration)).await.map(consume),
+            DriverOp::InsertUserWithPosts => {
+                let user = NewUser::generate(iteration);
+                B::insert_user_with_posts(conn, &user, &DriverOp::posts(iteration)).await.map(consume)
+            }
+        }
+    }
+}
+
+/// [`register`] for the blocking client, without a runtime around the calls
+fn register_postgres_sync(group: &mut BenchmarkGroup<'_, WallTime>, rt: &Runtime, run: &DriverRun<'_>) {
+    let name = "postgres_sync";
+    guarded(name, || {
+        let mut client = PostgresSyncBench::connect().unwrap();
+        group.bench_function(name, |b| {
+            if run.writes() {
+                b.iter_custom(|iters| {
+                    checked(name, PostgresSyncBench::cleanup(&mut client));
+                    let start = Instant::now();
+                    for iteration in 1..=iters as usize {
+                        checked(name, run.op.run_sync(&mut client, run.ids, iteration));
+                    }
+                    start.elapsed()
+                });
+                checked(name, PostgresSyncBench::cleanup(&mut client));
+            } else {
+                let mut iteration = 0usize;
+                b.iter(|| {
+                    iteration += 1;
+                    checked(name, run.op.run_sync(&mut client, run.ids, iteration))
+                });
+            }
+        });
+        if run.writes() {
+            assert_cleaned_up(rt, name);
+        }
+    });
+}
+
+fn bench_sync_driver(c: &mut Criterion) {
+    let rt = create_runtime();
+    let user_ids = sample_user_ids(&rt);
+    for op in DriverOp::ALL.into_iter().filter(|op| tagged(op.tags())) {
+        let mut group = benchmark_group(c, format!("sync_vs_async/{}", op.name()), Duration::from_secs(10), 100);
+        let run = DriverRun { op, ids: &user_ids };
+        register::<TokioPostgresBench, _>(&mut group, &rt, "tokio_postgres", None, &run);
+        register_postgres_sync(&mut group, &rt, &run);
+        group.finish();
+    }
+}
+
+// ============================================================================
+// Concurrent Query Benchmarks (Connection Pooling)
+// ============================================================================
+//
+// Every pool is pre-warmed (all connections opened) before measurement, so the
+// first samples never include connection establishment for some libraries
+// (deadpool, sqlx) but not others (r2d2 fills itself while building).
+
+fn bench_concurrent_reads(c: &mut Criterion) {
+    if !tagged(&[Tag::Read, Tag::Concurrency]) {
+        return;
+    }
+    let rt = create_runtime();
+    let mut group = benchmark_group(c, "concurrent_reads", Duration::from_secs(20), 20);
+
+    // Test with different concurrency levels
+    for concurrency in CONCURRENCY_LEVELS {
+        group.throughput(Throughput::Elements(*concurrency as u64));
+
+        // tokio-postgres with deadpool
+        guarded("tokio_postgres_pooled", || {
+            group.bench_with_input(
+                BenchmarkId::new("tokio_postgres_pooled", concurrency),
+                concurrency,
+                |b, &conc| {
+                    let pool = TokioPostgresBench::create_pool(conc);
+                    rt.block_on(TokioPostgresBench::warm_pool(&pool)).unwrap();
+                    b.iter(|| {
+                        rt.block_on(async {
+                            let mut handles = Vec::with_capacity(conc);
+                            for _ in 0..conc {
+                                let pool = pool.clone();
+                                handles.push(tokio::spawn(async move {
+                                    TokioPostgresBench::pooled_select_users_limit(&pool, 50).await
+                                }));
+                            }
+                            for handle in handles {
+                                checked("tokio_postgres_pooled", handle.await);
+                            }
+                        });
+                    });
+                },
+            );
+        });
+
+        // tokio-postgres with bb8
+        guarded("tokio_postgres_bb8", || {
+            group.bench_with_input(BenchmarkId::new("tokio_postgres_bb8", concurrency), concurrency, |b, &conc| {
+                let pool = rt.block_on(TokioPostgresBench::create_bb8_pool(conc)).unwrap();
+                b.iter(|| {
+                    rt.block_on(async {
+                        let mut handles = Vec::with_capacity(conc);
+                        for _ in 0..conc {
+                            let pool = pool.clone();
+                            handles.push(tokio::spawn(async move {
+                                TokioPostgresBench::bb8_select_users_limit(&pool, 50).await
+                            }));
+                        }
+                        for handle in handles {
+                            checked("tokio_postgres_bb8", handle.await);
+                        }
+                    });
+                });
+            });
+        });
+
+        // sqlx (already pooled)
+        guarded("sqlx", || {
+            group.bench_with_input(BenchmarkId::new("sqlx", concurrency), concurrency, |b, &conc| {
+                let pool = rt.block_on(SqlxBench::connect_with_pool_size(conc as u32)).unwrap();
+                rt.block_on(SqlxBench::warm_pool(&pool)).unwrap();
+                b.iter(|| {
+                    rt.block_on(async {
+                        let mut handles = Vec::with_capacity(conc);
+                        for _ in 0..conc {
+                            let pool = pool.clone();
+                            handles.push(tokio::spawn(async move {
+                                SqlxBench::select_users_limit(&pool, 50).await
+                            }));
+                        }
+                        for handle in handles {
+                            checked("sqlx", handle.await);
+                        }
+                    });
+                });
+            });
+        });
+
+        // sea-orm (uses sqlx pool)
+        guarded("sea_orm", || {
+            group.bench_with_input(BenchmarkId::new("sea_orm", concurrency), concurrency, |b, &conc| {
+                let db = rt.block_on(SeaOrmBench::connect_with_pool_size(conc as u32)).unwrap();
+                rt.block_on(SeaOrmBench::warm_pool(&db)).unwrap();
+                b.iter(|| {
+                    rt.block_on(async {
+                        let mut handles = Vec::with_capacity(conc);
+                        for _ in 0..conc {
+                            let db = db.clone();
+                            handles.push(tokio::spawn(async move {
+                                SeaOrmBench::select_users_limit(&db, 50).await
+                            }));
+                        }
+                        for handle in handles {
+                            checked("sea_orm", handle.await);
+                        }
+                    });
+                });
+            });
+        });
+
+        // diesel with r2d2 (sync - uses thread pool)
+        guarded("diesel", || {
+            group.bench_with_input(BenchmarkId::new("diesel", concurrency), concurrency, |b, &conc| {
+                let pool = DieselBench::connect_with_pool_size(conc as u32).unwrap();
+                DieselBench::warm_pool(&pool).unwrap();
+                b.iter(|| {
+                    let pool = pool.clone();
+                    std::thread::scope(|s| {
+                        for _ in 0..conc {
+                            let pool = pool.clone();
+                            s.spawn(move || {
+                                let Some(mut conn) = checked("diesel", pool.get()) else {
+                                    return;
+                                };
+                                checked("diesel", DieselBench::select_users_limit(&mut conn, 50));
+                            });
+                        }
+                    });
+                });
+            });
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_concurrent_mixed(c: &mut Criterion) {
+    if !tagged(&[Tag::Read, Tag::Write, Tag::Concurrency]) {
+        return;
+    }
+    let rt = create_runtime();
+    let mut group = benchmark_group(c, "concurrent_mixed_workload", Duration::from_secs(30), 15);
+
+    let concurrency = MIXED_CONCURRENCY;
+    let ops_per_task = 20;
+    // Each task starts at a different offset into the shared read/write mix
+    let mix: Arc<[WorkloadOp]> = HeavyWorkloadConfig::default()
+        .workload_mix(ops_per_task)
+        .into();
+    // As in `register`, inserted users are removed before every sample
+
+    // tokio-postgres with deadpool
+    guarded("tokio_postgres_pooled", || {
+        group.bench_function("tokio_postgres_pooled", |b| {
+            let pool = TokioPostgresBench::create_pool(concurrency);
+            rt.block_on(TokioPostgresBench::warm_pool(&pool)).unwrap();
+            let counter = std::sync::atomic::AtomicUsize::new(0);
+            b.iter_custom(|iters| {
+                checked("tokio_postgres_pooled", rt.block_on(TokioPostgresBench::pooled_cleanup(&pool)));
+                let start = Instant::now();
+                for _ in 0..iters {
+                    rt.block_on(async {
+                        let mut handles = Vec::with_capacity(concurrency);
+                        for _ in 0..concurrency {
+                            let pool = pool.clone();
+                            let mix = mix.clone();
+                            let cnt = counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                            handles.push(tokio::spawn(async move {
+                                for i in 0..ops_per_task {
+                                    if mix[(cnt + i) % mix.len()] == WorkloadOp::Write {
+                                        let user = NewUser::generate(cnt * 1000 + i);
+                                        checked("tokio_postgres_pooled", TokioPostgresBench::pooled_insert_user(&pool, &user).await);
+                                    } else {
+                                        checked("tokio_postgres_pooled", TokioPostgresBench::pooled_select_users_limit(&pool, 50).await);
+                                    }
+                                }
+                            }));
+                        }
+                        for handle in handles {
+                            checked("tokio_postgres_pooled", handle.await);
+                        }
+                    });
+                }
+                start.elapsed()
+            });
+            checked("tokio_postgres_pooled", rt.block_on(TokioPostgresBench::pooled_cleanup(&pool)));
+        });
+        assert_cleaned_up(&rt, "concurrent_mixed_workload/tokio_postgres_pooled");
+    });
+
+    // tokio-postgres with bb8
+    guarded("tokio_postgres_bb8", || {
+        group.bench_function("tokio_postgres_bb8", |b| {
+            let pool = rt.block_on(TokioPostgresBench::create_bb8_pool(concurrency)).unwrap();
+            let counter = std::sync::atomic::AtomicUsize::new(0);
+            b.iter_custom(|iters| {
+                checked("tokio_postgres_bb8", rt.block_on(TokioPostgresBench::bb8_cleanup(&pool)));
+                let start = Instant::now();
+                for _ in 0..iters {
+                    rt.block_on(async {
+                        let mut handles = Vec::with_capacity(concurrency);
+                        for _ in 0..concurrency {
+                            let pool = pool.clone();
+                            let mix = mix.clone();
+                            let cnt = counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                            handles.push(tokio::spawn(async move {
+                                for i in 0..ops_per_task {
+                                    if mix[(cnt + i) % mix.len()] == WorkloadOp::Write {
+                                        let user = NewUser::generate(cnt * 1000 + i);
+                                        checked("tokio_postgres_bb8", TokioPostgresBench::bb8_insert_user(&pool, &user).await);
+                                    } else {
+                                        checked("tokio_postgres_bb8", TokioPostgresBench::bb8_select_users_limit(&pool, 50).await);
+                                    }
+                                }
+                            }));
+                        }
+                        for handle in handles {
+                            checked("tokio_postgres_bb8", handle.await);
+                        }
+                    });
+                }
+                start.elapsed()
+            });
+            checked("tokio_postgres_bb8", rt.block_on(TokioPostgresBench::bb8_cleanup(&pool)));
+        });
+        assert_cleaned_up(&rt, "concurrent_mixed_workload/tokio_postgres_bb8");
+    });
+
+    // sqlx
+    guarded("sqlx", || {
+        group.bench_function("sqlx", |b| {
+            let pool = rt.block_on(SqlxBench::connect_with_pool_size(concurrency as u32)).unwrap();
+            rt.block_on(SqlxBench::warm_pool(&pool)).unwrap();
+            let counter = std::sync::atomic::AtomicUsize::new(0);
+            b.iter_custom(|iters| {
+                checked("sqlx", rt.block_on(SqlxBench::cleanup(&pool)));
+                let start = Instant::now();
+                for _ in 0..iters {
+                    rt.block_on(async {
+                        let mut handles = Vec::with_capacity(concurrency);
+                        for _ in 0..concurrency {
+                            let pool = pool.clone();
+                            let mix = mix.clone();
+                            let cnt = counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                            handles.push(tokio::spawn(async move {
+                                for i in 0..ops_per_task {
+                                    if mix[(cnt + i) % mix.len()] == WorkloadOp::Write {
+                                        let user = NewUser::generate(cnt * 1000 + i);
+                                        checked("sqlx", SqlxBench::insert_user(&pool, &user).await);
+                                    } else {
+                                        checked("sqlx", SqlxBench::select_users_limit(&pool, 50).await);
+                                    }
+                                }
+                            }));
+                        }
+                        for handle in handles {
+                            checked("sqlx", handle.await);
+                        }
+                    });
+                }
+                start.elapsed()
+            });
+            checked("sqlx", rt.block_on(SqlxBench::cleanup(&pool)));
+        });
+        assert_cleaned_up(&rt, "concurrent_mixed_workload/sqlx");
+    });
+
+    // sea-orm
+    guarded("sea_orm", || {
+        group.bench_function("sea_orm", |b| {
+            let db = rt.block_on(SeaOrmBench::connect_with_pool_size(concurrency as u32)).unwrap();
+            rt.block_on(SeaOrmBench::warm_pool(&db)).unwrap();
+            let counter = std::sync::atomic::AtomicUsize::new(0);
+            b.iter_custom(|iters| {
+                checked("sea_orm", rt.block_on(SeaOrmBench::cleanup(&db)));
+                let start = Instant::now();
+                for _ in 0..iters {
+                    rt.block_on(async {
+                        let mut handles = Vec::with_capacity(concurrency);
+                        for _ in 0..concurrency {
+                            let db = db.clone();
+                            let mix = mix.clone();
+                            let cnt = counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                            handles.push(tokio::spawn(async move {
+                                for i in 0..ops_per_task {
+                                    if mix[(cnt + i) % mix.len()] == WorkloadOp::Write {
+                                        let user = NewUser::generate(cnt * 1000 + i);
+                                        checked("sea_orm", SeaOrmBench::insert_user(&db, &user).await);
+                                    } else {
+                                        checked("sea_orm", SeaOrmBench::select_users_limit(&db, 50).await);
+                                    }
+                                }
+                            }));
+                        }
+                        for handle in handles {
+                            checked("sea_orm", handle.await);
+                        }
+                    });
+                }
+                start.elapsed()
+            });
+            checked("sea_orm", rt.block_on(SeaOrmBench::cleanup(&db)));
+        });
+        assert_cleaned_up(&rt, "concurrent_mixed_workload/sea_orm");
+    });
+
+    // diesel with r2d2
+    guarded("diesel", || {
+        group.bench_function("diesel", |b| {
+            let pool = DieselBench::connect_with_pool_size(concurrency as u32).unwrap();
+            DieselBench::warm_pool(&pool).unwrap();
+            let counter = std::sync::atomic::AtomicUsize::new(0);
+            b.iter_custom(|iters| {
+                checked("diesel", DieselBench::cleanup(&mut pool.get().unwrap()));
+                let start = Instant::now();
+                for _ in 0..iters {
+                    let pool = pool.clone();
+                    std::thread::scope(|s| {
+                        for _ in 0..concurrency {
+                            let pool = pool.clone();
+                            let mix = mix.clone();
+                            let cnt = counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                            s.spawn(move || {
+                                let Some(mut conn) = checked("diesel", pool.get()) else {
+                                    return;
+                                };
+                                for i in 0..ops_per_task {
+                                    if mix[(cnt + i) % mix.len()] == WorkloadOp::Write {
+                                        let user = NewUser::generate(cnt * 1000 + i);
+                                        checked("diesel", DieselBench::insert_user(&mut conn, &user));
+                                    } else {
+                                        checked("diesel", DieselBench::select_users_limit(&mut conn, 50));
+                                    }
+                                }
+                            });
+                        }
+                    });
+                }
+                start.elapsed()
+            });
+            let mut conn = pool.get().unwrap();
+            checked("diesel", DieselBench::cleanup(&mut conn));
+        });
+        assert_cleaned_up(&rt, "concurrent_mixed_workload/diesel");
+    });
+
+    group.finish();
+}
+
+// ============================================================================
+// Pool Comparison Benchmarks
+// ============================================================================
+//
+// tokio-postgres behind deadpool, bb8 and mobc, each built warm with
+// `POOL_COMPARISON_SIZE` connections and none checked on checkout. Every
+// iteration spawns one primary-key lookup per task; the query is cheap, so
+// what differs between the series is how each pool hands out connections and
+// queues the tasks that find none free.
+
+/// One `pool_comparison` series: `tasks` lookups at once, each checking out
+/// its own connection from `pool` through `lookup`
+fn bench_pool_lookups<P, F, Fut>(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    rt: &Runtime,
+    name: &str,
+    tasks: usize,
+    ids: &Arc<[Uuid]>,
+    pool: P,
+    lookup: F,
+) where
+    P: Clone + Send + 'static,
+    F: Fn(P, Uuid) -> Fut + Copy + Send + 'static,
+    Fut: std::future::Future<Output = Result<Option<User>, BoxError>> + Send + 'static,
+{
+    guarded(name, || {
+        group.bench_function(BenchmarkId::new(name, tasks), |b| {
+            let mut offset = 0;
+            b.iter(|| {
+                rt.block_on(async {
+                    let handles: Vec<_> = (0..tasks)
+                        .map(|task| tokio::spawn(lookup(pool.clone(), ids[(offset + task) % ids.len()])))
+                        .collect();
+                    for handle in handles {
+                        checked(name, handle.await.unwrap_or_else(|e| Err(e.into())));
+                    }
+                });
+                offset += tasks;
+            });
+        });
+    });
+}
+
+fn bench_pool_comparison(c: &mut Criterion) {
+    if !tagged(&[Tag::Read, Tag::Concurrency, Tag::Connection]) {
+        return;
+    }
+    let rt = create_runtime();
+    let mut group = benchmark_group(c, "pool_comparison", Duration::from_secs(15), 30);
+    let ids: Arc<[Uuid]> = sample_user_ids(&rt).into();
+
+    let deadpool = TokioPostgresBench::create_pool(POOL_COMPARISON_SIZE);
+    rt.block_on(TokioPostgresBench::warm_pool(&deadpool)).unwrap();
+    let bb8 = rt.block_on(TokioPostgresBench::create_bb8_pool(POOL_COMPARISON_SIZE)).unwrap();
+    let mobc = rt.block_on(TokioPostgresBench::create_mobc_pool(POOL_COMPARISON_SIZE)).unwrap();
+
+    for &tasks in CONCURRENCY_LEVELS {
+        group.throughput(Throughput::Elements(tasks as u64));
+        bench_pool_lookups(&mut group, &rt, "deadpool", tasks, &ids, deadpool.clone(), |pool, id| async move {
+            TokioPostgresBench::pooled_select_user_by_id(&pool, id).await
+        });
+        bench_pool_lookups(&mut group, &rt, "bb8", tasks, &ids, bb8.clone(), |pool, id| async move {
+            TokioPostgresBench::bb8_select_user_by_id(&pool, id).await
+        });
+        bench_pool_lookups(&mut group, &rt, "mobc", tasks, &ids, mobc.clone(), |pool, id| async move {
+            TokioPostgresBench::mobc_select_user_by_id(&pool, id).await
+        });
+    }
+
+    group.finish();
+}
+
+// ============================================================================
+// Web API Benchmarks
+// ============================================================================
+//
+// The same axum JSON API (`web::router`) in front of each backend's pool,
+// driven in-process over keep-alive HTTP connections. Every iteration sends
+// one request per connection at once, so the time covers routing, pool
+// checkout, the query, JSON serialization and the HTTP round trip of
+// `WEB_CONNECTIONS` concurrent requests.
+
+/// Load client connections, and so requests in flight; also each backend's pool size
+const WEB_CONNECTIONS: usize = 16;
+
+/// Posts each `GET /posts` asks for
+const WEB_POST_LIMIT: i64 = 20;
+
+/// Serve `api` and time every endpoint under [`WEB_CONNECTIONS`] concurrent requests
+fn bench_api<A: web::Api>(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    rt: &Runtime,
+    name: &str,
+    api: A,
+    targets: &web::Targets,
+) {
+    let server = rt.block_on(ApiServer::start(api)).unwrap();
+    let connects = (0..WEB_CONNECTIONS).map(|_| LoadClient::connect(server.address()));
+    let mut clients = rt.block_on(futures::future::try_join_all(connects)).unwrap();
+    let mut iteration = 0;
+    for endpoint in Endpoint::ALL {
+        group.bench_function(BenchmarkId::new(name, endpoint.name()), |b| {
+            b.iter(|| {
+                iteration += 1;
+                let requests = clients
+                    .iter_mut()
+                    .enumerate()
+                    .map(|(i, client)| targets.request(client, endpoint, iteration * WEB_CONNECTIONS + i));
+                for response in rt.block_on(futures::future::join_all(requests)) {
+                    checked(name, response.map(consume));
+                }
+            })
+        });
+    }
+    rt.block_on(server.shutdown());
+}
+
+fn bench_web_api(c: &mut Criterion) {
+    if !tagged(&[Tag::Read, Tag::Write, Tag::Concurrency]) {
+        return;
+    }
+    let rt = create_runtime();
+    let mut group = benchmark_group(c, "web_api", Duration::from_secs(10), 50);
+    group.throughput(Throughput::Elements(WEB_CONNECTIONS as u64));
+
+    let client = rt.block_on(TokioPostgresBench::connect()).unwrap();
+    let comments = SCENARIOS.iter().find(|s| s.name == "insert_comment").unwrap();
+    let fixture = rt.block_on(comments.setup::<TokioPostgresBench>(&client)).unwrap();
+    let targets = web::Targets {
+        user_ids: sample_user_ids(&rt),
+        post_limit: WEB_POST_LIMIT,
+        comment_on: fixture.comment_on.unwrap(),
+    };
+
+    guarded("tokio_postgres", || {
+        let pool = TokioPostgresBench::create_pool(WEB_CONNECTIONS);
+        rt.block_on(TokioPostgresBench::warm_pool(&pool)).unwrap();
+        bench_api(&mut group, &rt, "tokio_postgres", web::TokioPostgresApi(pool), &targets);
+    });
+    guarded("sqlx", || {
+        let pool = rt.block_on(SqlxBench::connect_with_pool_size(WEB_CONNECTIONS as u32)).unwrap();
+        rt.block_on(SqlxBench::warm_pool(&pool)).unwrap();
+        bench_api(&mut group, &rt, "sqlx", web::SqlxApi(pool), &targets);
+    });
+    guarded("sea_orm", || {
+        let db = rt.block_on(SeaOrmBench::connect_with_pool_size(WEB_CONNECTIONS as u32)).unwrap();
+        rt.block_on(SeaOrmBench::warm_pool(&db)).unwrap();
+        bench_api(&mut group, &rt, "sea_orm", web::SeaOrmApi(db), &targets);
+    });
+    guarded("diesel", || {
+        let pool = DieselBench::connect_with_pool_size(WEB_CONNECTIONS as u32).unwrap();
+        DieselBench::warm_pool(&pool).unwrap();
+        bench_api(&mut group, &rt, "diesel", web::DieselApi(pool), &targets);
+    });
+    guarded("clorinde", || {
+        let pool = TokioPostgresBench::create_pool(WEB_CONNECTIONS);
+        rt.block_on(TokioPostgresBench::warm_pool(&pool)).unwrap();
+        bench_api(&mut group, &rt, "clorinde", web::ClorindeApi(pool), &targets);
+    });
+
+    group.finish();
+    rt.block_on(comments.teardown::<TokioPostgresBench>(&client)).unwrap();
+}
+
+// ============================================================================
+// Work Queue Benchmarks
+// ============================================================================
+//
+// Producers insert jobs into the `jobs` table while consumers claim them with
+// `FOR UPDATE SKIP LOCKED`, process them and mark them done, all on one pool
+// per backend (`queue::run`). Every iteration is one round of `QUEUE_LOAD`
+// from an empty queue, timed from the first insert until the last job is done.
+
+const QUEUE_LOAD: QueueLoad = QueueLoad {
+    jobs: 1000,
+    producers: 4,
+    consumers: 8,
+    batch: 10,
+};
+
+/// Time rounds of [`QUEUE_LOAD`] on `queue`, emptying the table before each
+fn bench_queue<Q: JobQueue>(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    rt: &Runtime,
+    client: &tokio_postgres::Client,
+    name: &str,
+    queue: Q,
+) {
+    let mut round = 0;
+    group.bench_function(name, |b| {
+        b.iter_custom(|iters| {
+            let mut elapsed = Duration::ZERO;
+            for _ in 0..iters {
+                round += 1;
+                rt.block_on(queue::reset(client)).unwrap();
+                if let Some(time) = checked(name, rt.block_on(queue::run(&queue, QUEUE_LOAD, round))) {
+                    elapsed += time;
+                }
+            }
+            elapsed
+        })
+    });
+    if let Some(latency) = checked(name, rt.block_on(queue::mean_latency(client))) {
+        eprintln!(
+            "work_queue ({name}): {:.1} ms mean from insert to done over the last round",
+            latency.as_secs_f64() * 1000.0
+        );
+    }
+}
+
+fn bench_work_queue(c: &mut Criterion) {
+    if !tagged(&[Tag::Write, Tag::Transaction, Tag::Concurrency]) {
+        return;
+    }
+    let rt = create_runtime();
+    let client = rt.block_on(TokioPostgresBench::connect()).unwrap();
+    rt.block_on(queue::create(&client)).unwrap();
+
+    let mut group = benchmark_group(c, "work_queue", Duration::from_secs(20), 10);
+    // A round takes a good fraction of a second
+    group.sampling_mode(SamplingMode::Flat);
+    group.throughput(Throughput::Elements(QUEUE_LOAD.jobs as u64));
+    let size = QUEUE_LOAD.connections();
+
+    guarded("tokio_postgres", || {
+        let pool = TokioPostgresBench::create_pool(size);
+        rt.block_on(TokioPostgresBench::warm_pool(&pool)).unwrap();
+        bench_queue(&mut group, &rt, &client, "tokio_postgres", queue::TokioPostgresQueue(pool));
+    });
+    guarded("sqlx", || {
+        let pool = rt.block_on(SqlxBench::connect_with_pool_size(size as u32)).unwrap();
+        rt.block_on(SqlxBench::warm_pool(&pool)).unwrap();
+        bench_queue(&mut group, &rt, &client, "sqlx", queue::SqlxQueue(pool));
+    });
+    guarded("sea_orm", || {
+        let db = rt.block_on(SeaOrmBench::connect_with_pool_size(size as u32)).unwrap();
+        rt.block_on(SeaOrmBench::warm_pool(&db)).unwrap();
+        bench_queue(&mut group, &rt, &client, "sea_orm", queue::SeaOrmQueue(db));
+    });
+    guarded("diesel", || {
+        let pool = DieselBench::connect_with_pool_size(size as u32).unwrap();
+        DieselBench::warm_pool(&pool).unwrap();
+        bench_queue(&mut group, &rt, &client, "diesel", queue::DieselQueue(pool));
+    });
+    guarded("clorinde", || {
+        let pool = TokioPostgresBench::create_pool(size);
+        rt.block_on(TokioPostgresBench::warm_pool(&pool)).unwrap();
+        bench_queue(&mut group, &rt, &client, "clorinde", queue::ClorindeQueue(pool));
+    });
+
+    group.finish();
+    rt.block_on(queue::reset(&client)).unwrap();
+}
+
+// ============================================================================
+// Aggregate Interference Benchmarks
+// ============================================================================
+//
+// `count_posts_per_user` timed on a pool of `interference::POOL_SIZE` while
+// 0, 2 or 8 writer tasks insert posts through the same pool. The writers start
+// at every sample boundary, after the previous sample's posts are removed,
+// and stop once the sample is timed. The posts written alongside are printed
+// per backend and writer count.
+
+fn bench_interference<P: PostPool>(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    rt: &Runtime,
+    client: &tokio_postgres::Client,
+    author: Uuid,
+    name: &str,
+    pool: P,
+) {
+    for writers in interference::WRITER_COUNTS {
+        let (mut aggregates, mut elapsed_total, mut written) = (0u64, Duration::ZERO, 0usize);
+        group.bench_function(BenchmarkId::new(name, writers), |b| {
+            b.iter_custom(|iters| {
+                rt.block_on(interference::reset(client, author)).unwrap();
+                let running = rt.block_on(async { Writers::start(&pool, writers, author) });
+                let start = Instant::now();
+                for _ in 0..iters {
+                    checked(name, rt.block_on(pool.count_posts_per_user()));
+                }
+                let elapsed = start.elapsed();
+                written += checked(name, rt.block_on(running.stop())).unwrap_or(0);
+                (aggregates, elapsed_total) = (aggregates + iters, elapsed_total + elapsed);
+                elapsed
+            })
+        });
+        if writers > 0 && !elapsed_total.is_zero() {
+            eprintln!(
+                "aggregate_interference ({name}, {writers} writers): {aggregates} aggregates and {written} posts in {:.1} s",
+                elapsed_total.as_secs_f64()
+            );
+        }
+    }
+    rt.block_on(interference::reset(client, author)).unwrap();
+}
+
+fn bench_aggregate_interference(c: &mut Criterion) {
+    if !tagged(&[Tag::Read, Tag::Write, Tag::Concurrency]) {
+        return;
+    }
+    let rt = create_runtime();
+    let client = rt.block_on(TokioPostgresBench::connect()).unwrap();
+    let author = rt.block_on(interference::create_author(&client)).unwrap();
+    let mut group = benchmark_group(c, "aggregate_interference", Duration::from_secs(15), 20);
+    let size = interference::POOL_SIZE;
+
+    guarded("tokio_postgres", || {
+        let pool = TokioPostgresBench::create_pool(size);
+        rt.block_on(TokioPostgresBench::warm_pool(&pool)).unwrap();
+        bench_interference(&mut group, &rt, &client, author, "tokio_postgres", interference::TokioPostgresPosts(pool));
+    });
+    guarded("sqlx", || {
+        let pool = rt.block_on(SqlxBench::connect_with_pool_size(size as u32)).unwrap();
+        rt.block_on(SqlxBench::warm_pool(&pool)).unwrap();
+        bench_interference(&mut group, &rt, &client, author, "sqlx", interference::SqlxPosts(pool));
+    });
+    guarded("sea_orm", || {
+        let db = rt.block_on(SeaOrmBench::connect_with_pool_size(size as u32)).unwrap();
+        rt.block_on(SeaOrmBench::warm_pool(&db)).unwrap();
+        bench_interference(&mut group, &rt, &client, author, "sea_orm", interference::SeaOrmPosts(db));
+    });
+    guarded("diesel", || {
+        let pool = DieselBench::connect_with_pool_size(size as u32).unwrap();
+        DieselBench::warm_pool(&pool).unwrap();
+        bench_interference(&mut group, &rt, &client, author, "diesel", interference::DieselPosts(pool));
+    });
+    guarded("clorinde", || {
+        let pool = TokioPostgresBench::create_pool(size);
+        rt.block_on(TokioPostgresBench::warm_pool(&pool)).unwrap();
+        bench_interference(&mut group, &rt, &client, author, "clorinde", interference::ClorindePosts(pool));
+    });
+
+    group.finish();
+    rt.block_on(TokioPostgresBench::cleanup(&client)).unwrap();
+    assert_cleaned_up(&rt, "aggregate_interference");
+}
+
+// ============================================================================
+// Temp Table Benchmarks
+// ============================================================================
+//
+// One iteration creates a session temp table, bulk-loads `TEMP_TABLE_ROWS`
+// users into it, joins it against `posts` and drops it, all on the backend's
+// own connection (`temp_table::TempTable`).
+
+/// Users staged by every `temp_table` iteration
+const TEMP_TABLE_ROWS: i64 = 1000;
+
+fn register_temp_table<B: TempTable>(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    rt: &Runtime,
+    name: &str,
+    rows: &[StagedUser],
+) {
+    guarded(name, || {
+        let conn = rt.block_on(B::connect()).unwrap();
+        group.bench_function(name, |b| b.iter(|| checked(name, rt.block_on(B::stage_and_join(&conn, rows)))));
+    });
+}
+
+fn bench_temp_table(c: &mut Criterion) {
+    if !tagged(&[Tag::Write, Tag::Join]) {
+        return;
+    }
+    let rt = create_runtime();
+    let client = rt.block_on(TokioPostgresBench::connect()).unwrap();
+    let users = rt.block_on(TokioPostgresBench::select_users_limit(&client, TEMP_TABLE_ROWS)).unwrap();
+    let ids: Vec<Uuid> = users.iter().map(|u| u.id).collect();
+    let rows = temp_table::stage(&ids);
+
+    let mut group = benchmark_group(c, "temp_table", Duration::from_secs(10), 50);
+    group.throughput(Throughput::Elements(rows.len() as u64));
+    for_each_backend!(
+        [
+            TokioPostgresBench => "tokio_postgres",
+            SqlxBench => "sqlx",
+            SeaOrmBench => "sea_orm",
+            DieselBench => "diesel",
+            ClorindeBench => "clorinde",
+        ]
+        |B, name| register_temp_table::<B>(&mut group, &rt, name, &rows)
+    );
+    group.finish();
+}
+
+// ============================================================================
+// Chunked Export Benchmarks
+// ============================================================================
+//
+// Every post read `EXPORT_CHUNK` rows at a time, each chunk in its own
+// transaction: `FETCH` from a `WITH HOLD` cursor vs keyset pagination on the
+// primary key (`cursor::ChunkedExport`), for the backends that can keep a
+// cursor open on one connection across transactions.
+
+/// Rows per `FETCH` or keyset page
+const EXPORT_CHUNK: i64 = 1000;
+
+fn register_export<B: ChunkedExport>(group: &mut BenchmarkGroup<'_, WallTime>, rt: &Runtime, name: &str) {
+    guarded(name, || {
+        let conn = rt.block_on(B::connect()).unwrap();
+        for method in ExportMethod::ALL {
+            group.bench_function(BenchmarkId::new(name, method), |b| {
+                b.iter(|| checked(name, rt.block_on(B::export(&conn, method, EXPORT_CHUNK, consume))))
+            });
+        }
+    });
+}
+
+fn bench_chunked_export(c: &mut Criterion) {
+    if !tagged(&[Tag::Read, Tag::Transaction]) {
+        return;
+    }
+    let rt = create_runtime();
+    let client = rt.block_on(TokioPostgresBench::connect()).unwrap();
+    let posts: i64 = rt.block_on(client.query_one("SELECT COUNT(*) FROM posts", &[])).unwrap().get(0);
+
+    let mut group = benchmark_group(c, "chunked_export", Duration::from_secs(20), 10);
+    // An export reads the whole posts table
+    group.sampling_mode(SamplingMode::Flat);
+    group.throughput(Throughput::Elements(posts as u64));
+    for_each_backend!(
+        [
+            TokioPostgresBench => "tokio_postgres",
+            SqlxBench => "sqlx",
+            DieselBench => "diesel",
+            ClorindeBench => "clorinde",
+        ]
+        |B, name| register_export::<B>(&mut group, &rt, name)
+    );
+    group.finish();
+}
+
+// ============================================================================
+// Batch Size Sweep Benchmarks
+// ============================================================================
+//
+// A batch of users inserted in each of the ways `bulk_insert::Strategy` names,
+// at every one of `BATCH_SIZES`: a group per strategy with a series per
+// backend, so criterion's line chart plots throughput against batch size.
+// COPY runs on the backends with a COPY of their own.
+
+fn register_sweep<B: BulkInsert>(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    rt: &Runtime,
+    name: &str,
+    strategy: InsertStrategy,
+) {
+    if strategy == InsertStrategy::Copy && !B::capabilities().copy {
+        return;
+    }
+    guarded(name, || {
+        let conn = rt.block_on(B::connect()).unwrap();
+        for &size in BATCH_SIZES {
+            group.throughput(Throughput::Elements(size as u64));
+            group.bench_function(BenchmarkId::new(name, size), |b| {
+                // Emptied at every sample boundary, as the scenario writes are
+                b.iter_custom(|iters| {
+                    checked(name, rt.block_on(B::cleanup(&conn)));
+                    let mut elapsed = Duration::ZERO;
+                    for iteration in 0..iters as usize {
+                        let users: Vec<NewUser> = (iteration * size..(iteration + 1) * size).map(NewUser::generate).collect();
+                        let start = Instant::now();
+                        checked(name, rt.block_on(B::insert(&conn, strategy, &users)));
+                        elapsed += start.elapsed();
+                    }
+                    elapsed
+                });
+            });
+            checked(name, rt.block_on(B::cleanup(&conn)));
+            assert_cleaned_up(rt, &format!("{name}/{size}"));
+        }
+    });
+}
+
+fn bench_batch_sweep(c: &mut Criterion) {
+    if !tagged(&[Tag::Write]) {
+        return;
+    }
+    let rt = create_runtime();
+    for strategy in InsertStrategy::ALL {
+        let mut group = benchmark_group(c, format!("batch_sweep/{strategy}"), Duration::from_secs(5), 20);
+        for_each_backend!(
+            [
+                TokioPostgresBench => "tokio_postgres",
+                SqlxBench => "sqlx",
+                SeaOrmBench => "sea_orm",
+                DieselBench => "diesel",
+                ClorindeBench => "clorinde",
+            ]
+            |B, name| register_sweep::<B>(&mut group, &rt, name, strategy)
+        );
+        group.finish();
+    }
+}
+
+// ============================================================================
+// Unique Violation Benchmarks
+// ============================================================================
+//
+// Users of which a fraction repeat a taken username, each stored under the
+// next free name with every `unique_violation::Recovery`: a group per
+// recovery with a series per backend over the `BENCH_CONFLICT_RATES`. A last
+// group times picking the violated constraint out of an error already caught,
+// the error mapping alone.
+
+/// Users inserted per iteration of the recovery groups
+const CONFLICT_INSERTS: usize = 100;
+
+fn register_recovery<B: UniqueViolation>(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    rt: &Runtime,
+    name: &str,
+    recovery: Recovery,
+    rates: &[f64],
+) {
+    guarded(name, || {
+        let conn = rt.block_on(B::connect()).unwrap();
+        for &rate in rates {
+            let colliding = unique_violation::colliding_usernames(rate);
+            group.bench_function(BenchmarkId::new(name, rate), |b| {
+                b.iter_custom(|iters| {
+                    checked(name, rt.block_on(B::cleanup(&conn)));
+                    let start = Instant::now();
+                    for index in 0..iters as usize * CONFLICT_INSERTS {
+                        let user = colliding.generate(index);
+                        checked(name, rt.block_on(B::insert_user_recovering(&conn, recovery, &user)));
+                    }
+                    start.elapsed()
+                })
+            });
+            checked(name, rt.block_on(B::cleanup(&conn)));
+            assert_cleaned_up(rt, &format!("{name}/{rate}"));
+        }
+    });
+}
+
+fn register_error_mapping<B: UniqueViolation>(group: &mut BenchmarkGroup<'_, WallTime>, rt: &Runtime, name: &str) {
+    guarded(name, || {
+        let conn = rt.block_on(B::connect()).unwrap();
+        let user = NewUser::generate(0);
+        checked(name, rt.block_on(B::insert_user(&conn, &user)));
+        let error = rt.block_on(B::insert_user(&conn, &user)).expect_err("a repeated username is rejected");
+        checked(name, rt.block_on(B::cleanup(&conn)));
+        assert_eq!(B::violated(&error), Some(unique_violation::USERNAME_KEY), "{name}: {error:?}");
+
+        group.bench_function(name, |b| b.iter(|| B::violated(std::hint::black_box(&error)).is_some()));
+        assert_cleaned_up(rt, name);
+    });
+}
+
+fn bench_unique_violation(c: &mut Criterion) {
+    if !tagged(&[Tag::Write]) {
+        return;
+    }
+    let rt = create_runtime();
+    let rates = unique_violation::conflict_rates();
+    for recovery in Recovery::ALL {
+        let mut group = benchmark_group(c, format!("unique_violation/{recovery}"), Duration::from_secs(10), 20);
+        group.throughput(Throughput::Elements(CONFLICT_INSERTS as u64));
+        for_each_backend!(
+            [
+                TokioPostgresBench => "tokio_postgres",
+                SqlxBench => "sqlx",
+                SeaOrmBench => "sea_orm",
+                DieselBench => "diesel",
+                ClorindeBench => "clorinde",
+            ]
+            |B, name| register_recovery::<B>(&mut group, &rt, name, recovery, &rates)
+        );
+        group.finish();
+    }
+
+    let mut group = benchmark_group(c, "unique_violation/error_mapping", Duration::from_secs(5), 100);
+    for_each_backend!(
+        [
+            TokioPostgresBench => "tokio_postgres",
+            SqlxBench => "sqlx",
+            SeaOrmBench => "sea_orm",
+            DieselBench => "diesel",
+            ClorindeBench => "clorinde",
+        ]
+        |B, name| register_error_mapping::<B>(&mut group, &rt, name)
+    );
+    group.finish();
+}
+
+// ============================================================================
+// Retry Benchmarks
+// ============================================================================
+//
+// `RETRY_LOOKUPS` point lookups per iteration, each attempt failing first at
+// a rate of `FAILURE_RATES` with a serialization failure the server raises
+// (`retry::INDUCED_FAILURE`), on the same attempts for every backend.
+// `retry/disabled` gives up at the first failure and `retry/backoff` retries
+// with `RetryPolicy::BACKOFF`, so the difference is what retrying costs and
+// the printed lookups that still failed are what it saves.
+
+/// Lookups per iteration of the retry groups
+const RETRY_LOOKUPS: usize = 100;
+
+/// Fraction of attempts that fail: none, and some with and without retries
+/// usually succeeding
+const FAILURE_RATES: [f64; 3] = [0.0, 0.1, 0.3];
+
+/// The policies compared, by group name
+const RETRY_POLICIES: [(&str, RetryPolicy); 2] = [("disabled", RetryPolicy::DISABLED), ("backoff", RetryPolicy::BACKOFF)];
+
+fn register_retry<B: Transient>(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    rt: &Runtime,
+    name: &str,
+    policy: (&str, RetryPolicy),
+    ids: &[Uuid],
+) {
+    let (policy_name, policy) = policy;
+    guarded(name, || {
+        let conn = rt.block_on(B::connect()).unwrap();
+        for rate in FAILURE_RATES {
+            let schedule = FailureSchedule { rate };
+            let (mut lookups, mut failed, mut attempts) = (0usize, 0usize, 0usize);
+            group.bench_function(BenchmarkId::new(name, rate), |b| {
+                b.iter_custom(|iters| {
+                    let start = Instant::now();
+                    for request in 0..iters as usize * RETRY_LOOKUPS {
+                        let id = ids[request % ids.len()];
+                        let (result, ran) = rt.block_on(policy.run(B::is_transient, |attempt| {
+                            B::flaky_lookup(&conn, id, schedule, request, attempt)
+                        }));
+                        match result {
+                            Err(e) if B::is_transient(&e) => failed += 1,
+                            result => {
+                                checked(name, result);
+                            }
+                        }
+                        (lookups, attempts) = (lookups + 1, attempts + ran);
+                    }
+                    start.elapsed()
+                })
+            });
+            if rate > 0.0 && lookups > 0 {
+                eprintln!("retry/{policy_name} ({name}, {rate}): {failed} of {lookups} lookups failed after {attempts} attempts");
+            }
+        }
+    });
+}
+
+fn bench_retry(c: &mut Criterion) {
+    if !tagged(&[Tag::Read, Tag::Connection]) {
+        return;
+    }
+    let rt = create_runtime();
+    let ids = sample_user_ids(&rt);
+    for policy in RETRY_POLICIES {
+        let mut group = benchmark_group(c, format!("retry/{}", policy.0), Duration::from_secs(10), 20);
+        group.throughput(Throughput::Elements(RETRY_LOOKUPS as u64));
+        for_each_backend!(
+            [
+                TokioPostgresBench => "tokio_postgres",
+                SqlxBench => "sqlx",
+                SeaOrmBench => "sea_orm",
+                DieselBench => "diesel",
+                ClorindeBench => "clorinde",
+            ]
+            |B, name| register_retry::<B>(&mut group, &rt, name, policy, &ids)
+        );
+        group.finish();
+    }
+}
+
+// ============================================================================
+// Statement Invalidation Benchmarks
+// ============================================================================
+//
+// Every iteration opens a fresh connection, warms `invalidation::LOOKUP` (a
+// `SELECT *`) so it is prepared, adds or drops a column from another
+// connection (untimed), then times `INVALIDATION_LOOKUPS` lookups. A backend
+// whose statement cache keeps the old result type fails every one of them with
+// `cached plan must not change result type`; the failures and the time until
+// a lookup succeeded again are printed per backend.
+
+/// Lookups timed after each schema change
+const INVALIDATION_LOOKUPS: usize = 100;
+
+fn register_invalidation<L: CachedLookup>(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    rt: &Runtime,
+    admin: &tokio_postgres::Client,
+    name: &str,
+    change: &mut usize,
+) {
+    guarded(name, || {
+        let (mut changes, mut errors, mut recovered, mut recovery) = (0usize, 0usize, 0usize, Duration::ZERO);
+        let mut first_error = None;
+        group.bench_function(name, |b| {
+            b.iter_custom(|iters| {
+                let mut total = Duration::ZERO;
+                for _ in 0..iters {
+                    let Some(mut lookup) = checked(name, rt.block_on(L::connect())) else { continue };
+                    let measured = rt.block_on(invalidation::measure(&mut lookup, admin, *change, INVALIDATION_LOOKUPS));
+                    *change += 1;
+                    let Some(run) = checked(name, measured) else { continue };
+                    total += run.elapsed;
+                    (changes, errors) = (changes + 1, errors + run.errors);
+                    if let Some(time) = run.recovery {
+                        (recovered, recovery) = (recovered + 1, recovery + time);
+                    }
+                    if first_error.is_none() {
+                        first_error = run.first_error;
+                    }
+                }
+                total
+            })
+        });
+        if changes > 0 {
+            let recovery = match recovered {
+                0 => "never recovered".to_string(),
+                n => format!("recovered after {:?} on average", recovery / n as u32),
+            };
+            eprintln!(
+                "statement_invalidation ({name}): {errors} of {} lookups failed after {changes} schema changes, {recovery}{}",
+                changes * INVALIDATION_LOOKUPS,
+                first_error.map(|e| format!(" ({e})")).unwrap_or_default(),
+            );
+        }
+    });
+}
+
+fn bench_statement_invalidation(c: &mut Criterion) {
+    if !tagged(&[Tag::Read]) {
+        return;
+    }
+    let rt = create_runtime();
+    let admin = rt.block_on(TokioPostgresBench::connect()).unwrap();
+    let mut group = benchmark_group(c, "statement_invalidation", Duration::from_secs(10), 20);
+    group.throughput(Throughput::Elements(INVALIDATION_LOOKUPS as u64));
+
+    let mut change = 0;
+    register_invalidation::<TokioPostgresLookup>(&mut group, &rt, &admin, "tokio_postgres", &mut change);
+    register_invalidation::<TokioPostgresPreparedLookup>(&mut group, &rt, &admin, "tokio_postgres_prepared", &mut change);
+    register_invalidation::<SqlxLookup>(&mut group, &rt, &admin, "sqlx", &mut change);
+    register_invalidation::<SeaOrmLookup>(&mut group, &rt, &admin, "sea_orm", &mut change);
+    register_invalidation::<DieselLookup>(&mut group, &rt, &admin, "diesel", &mut change);
+    group.finish();
+    rt.block_on(invalidation::drop(&admin)).unwrap();
+}
+
+// ============================================================================
+// Statement Timeout and Cancellation Benchmarks
+// ============================================================================
+
+fn bench_statement_timeout(c: &mut Criterion) {
+    if !tagged(&[Tag::Read]) {
+        return;
+    }
+    let rt = create_runtime();
+    let mut group = benchmark_group(c, "statement_timeout", Duration::from_secs(10), 50);
+
+    // Each iteration: a statement cancelled by the server's statement_timeout,
+    // followed by a ping proving the same handle is still usable.
+
+    bench_backends!(
+        group,
+        rt,
+        [
+            TokioPostgresBench => "tokio_postgres",
+            SqlxBench => "sqlx",
+            SeaOrmBench => "sea_orm",
+            ClorindeBench => "clorinde",
+        ]
+        |B, name, conn| async {
+            let cancelled = checked(name, B::sleep_with_statement_timeout(&conn, STATEMENT_TIMEOUT_MS, SLEEP_SECS).await);
+            assert_ne!(cancelled, Some(false), "statement_timeout did not fire");
+            checked(name, B::ping(&conn).await);
+        }
+    );
+
+    // diesel is sync and runs on a pooled connection of its own
+    guarded("diesel", || {
+        group.bench_function("diesel", |b| {
+            let pool = DieselBench::connect().unwrap();
+            let mut conn = pool.get().unwrap();
+            b.iter(|| {
+                let cancelled = checked(
+                    "diesel",
+                    DieselBench::sleep_with_statement_timeout(&mut conn, STATEMENT_TIMEOUT_MS, SLEEP_SECS),
+                );
+                assert_ne!(cancelled, Some(false), "statement_timeout did not fire");
+                checked("diesel", DieselBench::ping(&mut conn));
+            });
+        });
+    });
+
+    group.finish();
+}
+
+fn bench_client_timeout(c: &mut Criterion) {
+    if !tagged(&[Tag::Read]) {
+        return;
+    }
+    let rt = create_runtime();
+    let mut group = benchmark_group(c, "client_timeout_recovery", Duration::from_secs(10), 30);
+
+    // Each iteration: the client gives up on a slow query via tokio::time::timeout
+    // and then issues a ping. The measured time is timeout + how long the backend
+    // takes to hand back a usable connection (the server keeps sleeping unless
+    // the query is explicitly cancelled). diesel is sync and cannot be timed out
+    // from the client, so it only appears in `statement_timeout`.
+
+    // The future is dropped and the server keeps sleeping, so the ping queues
+    // behind the sleep (or, for sqlx and sea-orm, the pool may hand it a
+    // different, idle connection)
+    bench_backends!(
+        group,
+        rt,
+        [
+            TokioPostgresBench => "tokio_postgres",
+            SqlxBench => "sqlx",
+            SeaOrmBench => "sea_orm",
+            ClorindeBench => "clorinde",
+        ]
+        |B, name, conn| async {
+            let res = tokio::time::timeout(CLIENT_TIMEOUT, B::pg_sleep(&conn, SLEEP_SECS)).await;
+            assert!(res.is_err(), "client timeout did not fire");
+            checked(name, B::ping(&conn).await);
+        }
+    );
+
+    // tokio-postgres: dropped future plus an explicit cancel request
+    guarded("tokio_postgres_cancel_token", || {
+        group.bench_function("tokio_postgres_cancel_token", |b| {
             let client = rt.block_on(TokioPostgresBench::connect()).unwrap();
             b.iter(|| {
-                rt.block_on(TokioPostgresBench::select_users_posts_comments(&client, limit))
-                    .unwrap()
+                rt.block_on(async {
+                    let res = tokio::time::timeout(
+                        CLIENT_TIMEOUT,
+                        TokioPostgresBench::pg_sleep(&client, SLEEP_SECS),
+                    )
+                    .await;
+                    // A cancel request is delivered asynchronously, so a late one may hit
+                    // the next sleep (or the ping) instead: accept either outcome and
+                    // count the retries as recovery time.
+                    assert!(!matches!(res, Ok(Ok(()))), "slow query was not interrupted");
+                    checked("tokio_postgres_cancel_token", TokioPostgresBench::cancel_query(&client).await);
+                    let mut ping = TokioPostgresBench::ping(&client).await;
+                    for _ in 1..CANCEL_PING_ATTEMPTS {
+                        if ping.is_ok() {
+                            break;
+                        }
+                        ping = TokioPostgresBench::ping(&client).await;
+                    }
+                    checked("tokio_postgres_cancel_token", ping);
+                })
+            });
+        });
+    });
+
+    group.finish();
+}
+
+// ============================================================================
+// Backpressure Benchmarks
+// ============================================================================
+
+fn bench_backpressure(c: &mut Criterion) {
+    if !tagged(&[Tag::Read, Tag::Concurrency]) {
+        return;
+    }
+    let rt = create_runtime();
+    let mut group = benchmark_group(c, "backpressure_slow_queries", Duration::from_secs(20), 20);
+
+    // Setup: get some user IDs
+    let client = rt.block_on(TokioPostgresBench::connect()).unwrap();
+    let users = rt
+        .block_on(TokioPostgresBench::select_users_limit(&client, 100))
+        .unwrap();
+    let user_ids: Vec<Uuid> = users.iter().map(|u| u.id).collect();
+
+    // The reported time is the mean latency of the fast queries only, so it shows
+    // how much each pool's queueing makes them pay for the slow ones.
+
+    // tokio-postgres with deadpool
+    guarded("tokio_postgres_pooled", || {
+        group.bench_function("tokio_postgres_pooled", |b| {
+            let pool = TokioPostgresBench::create_pool(BACKPRESSURE_POOL_SIZE);
+            rt.block_on(TokioPostgresBench::warm_pool(&pool)).unwrap();
+            b.iter_custom(|iters| {
+                let mut total = Duration::ZERO;
+                for _ in 0..iters {
+                    total += rt.block_on(async {
+                        let mut handles = Vec::with_capacity(BACKPRESSURE_TASKS);
+                        for i in 0..BACKPRESSURE_TASKS {
+                            let pool = pool.clone();
+                            let id = user_ids[i % user_ids.len()];
+                            handles.push(tokio::spawn(async move {
+                                if i % SLOW_QUERY_EVERY == 0 {
+                                    checked(
+                                        "tokio_postgres_pooled",
+                                        TokioPostgresBench::pooled_pg_sleep(&pool, SLOW_QUERY_SECS).await,
+                                    );
+                                    None
+                                } else {
+                                    let start = Instant::now();
+                                    checked(
+                                        "tokio_postgres_pooled",
+                                        TokioPostgresBench::pooled_select_user_by_id(&pool, id).await,
+                                    );
+                                    Some(start.elapsed())
+                                }
+                            }));
+                        }
+                        let mut latencies = Vec::with_capacity(BACKPRESSURE_TASKS);
+                        for handle in handles {
+                            latencies.push(checked("tokio_postgres_pooled", handle.await).flatten());
+                        }
+                        mean_latency(latencies)
+                    });
+                }
+                total
+            });
+        });
+    });
+
+    // sqlx
+    guarded("sqlx", || {
+        group.bench_function("sqlx", |b| {
+            let pool = rt
+                .block_on(SqlxBench::connect_with_pool_size(BACKPRESSURE_POOL_SIZE as u32))
+                .unwrap();
+            rt.block_on(SqlxBench::warm_pool(&pool)).unwrap();
+            b.iter_custom(|iters| {
+                let mut total = Duration::ZERO;
+                for _ in 0..iters {
+                    total += rt.block_on(async {
+                        let mut handles = Vec::with_capacity(BACKPRESSURE_TASKS);
+                        for i in 0..BACKPRESSURE_TASKS {
+                            let pool = pool.clone();
+                            let id = user_ids[i % user_ids.len()];
+                            handles.push(tokio::spawn(async move {
+                                if i % SLOW_QUERY_EVERY == 0 {
+                                    checked("sqlx", SqlxBench::pg_sleep(&pool, SLOW_QUERY_SECS).await);
+                                    None
+                                } else {
+                                    let start = Instant::now();
+                                    checked("sqlx", SqlxBench::select_user_by_id(&pool, id).await);
+                                    Some(start.elapsed())
+                                }
+                            }));
+                        }
+                        let mut latencies = Vec::with_capacity(BACKPRESSURE_TASKS);
+                        for handle in handles {
+                            latencies.push(checked("sqlx", handle.await).flatten());
+                        }
+                        mean_latency(latencies)
+                    });
+                }
+                total
+            });
+        });
+    });
+
+    // sea-orm
+    guarded("sea_orm", || {
+        group.bench_function("sea_orm", |b| {
+            let db = rt
+                .block_on(SeaOrmBench::connect_with_pool_size(BACKPRESSURE_POOL_SIZE as u32))
+                .unwrap();
+            rt.block_on(SeaOrmBench::warm_pool(&db)).unwrap();
+            b.iter_custom(|iters| {
+                let mut total = Duration::ZERO;
+                for _ in 0..iters {
+                    total += rt.block_on(async {
+                        let mut handles = Vec::with_capacity(BACKPRESSURE_TASKS);
+                        for i in 0..BACKPRESSURE_TASKS {
+                            let db = db.clone();
+                            let id = user_ids[i % user_ids.len()];
+                            handles.push(tokio::spawn(async move {
+                                if i % SLOW_QUERY_EVERY == 0 {
+                                    checked("sea_orm", SeaOrmBench::pg_sleep(&db, SLOW_QUERY_SECS).await);
+                                    None
+                                } else {
+                                    let start = Instant::now();
+                                    checked("sea_orm", SeaOrmBench::select_user_by_id(&db, id).await);
+                                    Some(start.elapsed())
+                                }
+                            }));
+                        }
+                        let mut latencies = Vec::with_capacity(BACKPRESSURE_TASKS);
+                        for handle in handles {
+                            latencies.push(checked("sea_orm", handle.await).flatten());
+                        }
+                        mean_latency(latencies)
+                    });
+                }
+                total
+            });
+        });
+    });
+
+    // diesel with r2d2 (sync - one thread per task)
+    guarded("diesel", || {
+        group.bench_function("diesel", |b| {
+            let pool = DieselBench::connect_with_pool_size(BACKPRESSURE_POOL_SIZE as u32).unwrap();
+            DieselBench::warm_pool(&pool).unwrap();
+            b.iter_custom(|iters| {
+                let mut total = Duration::ZERO;
+                for _ in 0..iters {
+                    total += std::thread::scope(|s| {
+                        let handles: Vec<_> = (0..BACKPRESSURE_TASKS)
+                            .map(|i| {
+                                let pool = pool.clone();
+                                let id = user_ids[i % user_ids.len()];
+                                s.spawn(move || {
+                                    if i % SLOW_QUERY_EVERY == 0 {
+                                        let mut conn = checked("diesel", pool.get())?;
+                                        checked("diesel", DieselBench::pg_sleep(&mut conn, SLOW_QUERY_SECS));
+                                        None
+                                    } else {
+                                        let start = Instant::now();
+                                        let mut conn = checked("diesel", pool.get())?;
+                                        checked("diesel", DieselBench::select_user_by_id(&mut conn, id));
+                                        Some(start.elapsed())
+                                    }
+                                })
+                            })
+                            .collect();
+                        mean_latency(handles.into_iter().map(|h| checked("diesel", h.join()).flatten()))
+                    });
+                }
+                total
             });
         });
+    });
+
+    group.finish();
+}
+
+// ============================================================================
+// TLS Benchmarks
+// ============================================================================
+//
+// `tls_connect` times opening one connection (TCP, TLS handshake, startup and
+// authentication); `tls_query` times point lookups on an open connection, so
+// only the per-query encryption differs. Every backend runs without TLS and
+// with each stack it can use, see `pg_benchmark::tls`.
+
+/// Whether the server accepts TLS connections at all
+fn server_accepts_tls(rt: &Runtime) -> bool {
+    match rt.block_on(TokioPostgresBench::connect_tls(TlsStack::NativeTls)) {
+        Ok(_) => true,
+        Err(e) => {
+            eprintln!("skipping TLS benchmarks, the server refused a TLS connection: {e}");
+            false
+        }
+    }
+}
+
+/// Time `connect` alone; whatever it returns is dropped outside the measurement
+fn bench_connects<T, E: Debug>(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    name: &str,
+    parameter: impl Display,
+    mut connect: impl FnMut() -> Result<T, E>,
+) {
+    guarded(name, || {
+        group.bench_function(BenchmarkId::new(name, parameter), |b| {
+            b.iter_custom(|iters| {
+                let mut total = Duration::ZERO;
+                for _ in 0..iters {
+                    let start = Instant::now();
+                    let conn = checked(name, connect());
+                    total += start.elapsed();
+                    drop(conn);
+                }
+                total
+            })
+        });
+    });
+}
+
+/// A read-only operation over a connection opened by `connect`, rather than `B::connect`
+fn bench_on_connection<B: DatabaseBenchmark, O: Operation>(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    rt: &Runtime,
+    name: &str,
+    parameter: impl Display,
+    connect: impl std::future::Future<Output = Result<B::Connection, impl Debug>>,
+    op: &O,
+) {
+    guarded(name, || {
+        let conn = rt.block_on(connect).unwrap();
+        let mut iteration = 0usize;
+        group.bench_function(BenchmarkId::new(name, parameter), |b| {
+            b.iter(|| {
+                iteration += 1;
+                checked(name, rt.block_on(op.run::<B>(&conn, iteration)))
+            })
+        });
+    });
+}
+
+fn bench_tls_connect(c: &mut Criterion) {
+    if !tagged(&[Tag::Connection]) {
+        return;
+    }
+    let rt = create_runtime();
+    if !server_accepts_tls(&rt) {
+        return;
+    }
+    let mut group = benchmark_group(c, "tls_connect", Duration::from_secs(10), 50);
+
+    // sea-orm and clorinde connect through the sqlx and tokio-postgres code measured here
+    for &stack in TOKIO_POSTGRES_STACKS {
+        bench_connects(&mut group, "tokio_postgres", stack, || {
+            rt.block_on(TokioPostgresBench::connect_tls(stack))
+        });
+    }
+    let (plain_url, tls_url) = (database_url(), tls_database_url());
+    for (stack, url) in [(TlsStack::None, &plain_url), (sqlx_stack(), &tls_url)] {
+        bench_connects(&mut group, "sqlx", stack, || rt.block_on(SqlxBench::connect_single(url)));
+    }
+    for (stack, url) in [(TlsStack::None, &plain_url), (TlsStack::Libpq, &tls_url)] {
+        bench_connects(&mut group, "diesel", stack, || DieselBench::establish(url));
+    }
+
+    group.finish();
+}
+
+fn bench_tls_query(c: &mut Criterion) {
+    if !tagged(&[Tag::Read, Tag::Connection]) {
+        return;
+    }
+    let rt = create_runtime();
+    if !server_accepts_tls(&rt) {
+        return;
+    }
+    let mut group = benchmark_group(c, "tls_query", Duration::from_secs(10), 100);
+    let user_ids = sample_user_ids(&rt);
+    let lookups = &SelectById { ids: &user_ids };
+
+    for &stack in TOKIO_POSTGRES_STACKS {
+        let connect = TokioPostgresBench::connect_tls(stack);
+        bench_on_connection::<TokioPostgresBench, _>(&mut group, &rt, "tokio_postgres", stack, connect, lookups);
+    }
+    bench_on_connection::<SqlxBench, _>(&mut group, &rt, "sqlx", TlsStack::None, SqlxBench::connect(), lookups);
+    bench_on_connection::<SqlxBench, _>(&mut group, &rt, "sqlx", sqlx_stack(), SqlxBench::connect_tls(), lookups);
+    bench_on_connection::<SeaOrmBench, _>(&mut group, &rt, "sea_orm", TlsStack::None, SeaOrmBench::connect(), lookups);
+    bench_on_connection::<SeaOrmBench, _>(&mut group, &rt, "sea_orm", sqlx_stack(), SeaOrmBench::connect_tls(), lookups);
+    bench_on_connection::<DieselBench, _>(
+        &mut group,
+        &rt,
+        "diesel",
+        TlsStack::None,
+        <DieselBench as DatabaseBenchmark>::connect(),
+        lookups,
+    );
+    let diesel_tls = async { Ok::<_, BoxError>(Mutex::new(DieselBench::connect_tls()?.get()?)) };
+    bench_on_connection::<DieselBench, _>(&mut group, &rt, "diesel", TlsStack::Libpq, diesel_tls, lookups);
+    for &stack in TOKIO_POSTGRES_STACKS {
+        let connect = ClorindeBench::connect_tls(stack);
+        bench_on_connection::<ClorindeBench, _>(&mut group, &rt, "clorinde", stack, connect, lookups);
+    }
+
+    group.finish();
+}
+
+/// Rows returned by the `tls_overhead` join queries
+const TLS_OVERHEAD_JOIN_LIMITS: [i64; 2] = [100, 1000];
+
+fn bench_tls_overhead(c: &mut Criterion) {
+    if !tagged(&[Tag::Read, Tag::Join, Tag::Connection]) {
+        return;
+    }
+    if !cfg!(feature = "tls") {
+        eprintln!("skipping tls_overhead, it needs the `tls` feature (cargo bench --features tls)");
+        return;
+    }
+    let rt = create_runtime();
+    if !server_accepts_tls(&rt) {
+        return;
+    }
+    let mut group = benchmark_group(c, "tls_overhead", Duration::from_secs(10), 100);
+    let user_ids = sample_user_ids(&rt);
+
+    // The same queries in plaintext, over rustls and over OpenSSL (native-tls);
+    // the more rows a query returns, the more of its round trip goes to encryption
+    for &stack in TOKIO_POSTGRES_STACKS {
+        group.throughput(Throughput::Elements(1));
+        let lookups = &SelectById { ids: &user_ids };
+        let connect = TokioPostgresBench::connect_tls(stack);
+        bench_on_connection::<TokioPostgresBench, _>(&mut group, &rt, "select_by_id", stack, connect, lookups);
+
+        for limit in TLS_OVERHEAD_JOIN_LIMITS {
+            group.throughput(Throughput::Elements(limit as u64));
+            let parameter = format!("{stack}/{limit}");
+            let connect = TokioPostgresBench::connect_tls(stack);
+            let join = &JoinPostsUsers { limit };
+            bench_on_connection::<TokioPostgresBench, _>(&mut group, &rt, "posts_with_user", &parameter, connect, join);
+            let connect = TokioPostgresBench::connect_tls(stack);
+            let join = &JoinTriple { limit };
+            bench_on_connection::<TokioPostgresBench, _>(&mut group, &rt, "join_triple", &parameter, connect, join);
+        }
+    }
+
+    group.finish();
+}
+
+// ============================================================================
+// Authentication Benchmarks
+// ============================================================================
+
+/// The methods whose role can log in; `init-auth.sh` creates them
+fn configured_auth_methods(rt: &Runtime) -> Vec<AuthMethod> {
+    AuthMethod::ALL
+        .into_iter()
+        .filter(|method| match rt.block_on(TokioPostgresBench::connect_to(&method.database_url())) {
+            Ok(_) => true,
+            Err(e) => {
+                eprintln!("skipping {method} authentication, {} can't log in: {e}", method.role());
+                false
+            }
+        })
+        .collect()
+}
+
+fn bench_auth_connect(c: &mut Criterion) {
+    if !tagged(&[Tag::Connection]) {
+        return;
+    }
+    let rt = create_runtime();
+    let methods = configured_auth_methods(&rt);
+    if methods.is_empty() {
+        return;
+    }
+    let mut group = benchmark_group(c, "auth_connect", Duration::from_secs(10), 50);
+
+    // As for TLS, sea-orm and clorinde authenticate through sqlx and tokio-postgres
+    for method in methods {
+        let url = method.database_url();
+        bench_connects(&mut group, "tokio_postgres", method, || {
+            rt.block_on(TokioPostgresBench::connect_to(&url))
+        });
+        bench_connects(&mut group, "sqlx", method, || rt.block_on(SqlxBench::connect_single(&url)));
+        bench_connects(&mut group, "diesel", method, || DieselBench::establish(&url));
+    }
+
+    group.finish();
+}
+
+// ============================================================================
+// Connection Setup Benchmarks
+// ============================================================================
+//
+// Time to the first usable connection, apart from any query and without TLS:
+// `single` opens one connection outside a pool, `pool_first` builds a pool and
+// checks out its first connection, and `pool_filled` builds a pool and opens
+// every connection it can hold, as the concurrent groups do before measuring.
+
+/// Pool size for `pool_first` and `pool_filled`, the backends' default
+const CONNECT_POOL_SIZE: usize = 10;
+
+fn bench_connection_setup(c: &mut Criterion) {
+    if !tagged(&[Tag::Connection]) {
+        return;
+    }
+    let rt = create_runtime();
+    let mut group = benchmark_group(c, "connection_setup", Duration::from_secs(10), 50);
+    let url = database_url();
+
+    // clorinde connects through tokio-postgres' client, measured here
+    bench_connects(&mut group, "tokio_postgres", "single", || rt.block_on(TokioPostgresBench::connect()));
+    bench_connects(&mut group, "tokio_postgres", "pool_first", || {
+        rt.block_on(async {
+            let pool = TokioPostgresBench::create_pool(CONNECT_POOL_SIZE);
+            TokioPostgresBench::get_pooled_client(&pool).await.map(|_| pool)
+        })
+    });
+    bench_connects(&mut group, "tokio_postgres", "pool_filled", || {
+        rt.block_on(async {
+            let pool = TokioPostgresBench::create_pool(CONNECT_POOL_SIZE);
+            TokioPostgresBench::warm_pool(&pool).await.map(|_| pool)
+        })
+    });
 
-        // sqlx
-        group.bench_with_input(BenchmarkId::new("sqlx", size), size, |b, _| {
-            let pool = rt.block_on(SqlxBench::connect()).unwrap();
-            b.iter(|| {
-                rt.block_on(SqlxBench::select_users_posts_comments(&pool, limit))
-                    .unwrap()
-            });
-        });
+    // sqlx opens the pool's first connection before `connect` returns; sea-orm
+    // only has pools
+    bench_connects(&mut group, "sqlx", "single", || rt.block_on(SqlxBench::connect_single(&url)));
+    bench_connects(&mut group, "sqlx", "pool_first", || {
+        rt.block_on(SqlxBench::connect_with_pool_size(CONNECT_POOL_SIZE as u32))
+    });
+    bench_connects(&mut group, "sqlx", "pool_filled", || {
+        rt.block_on(async {
+            let pool = SqlxBench::connect_with_pool_size(CONNECT_POOL_SIZE as u32).await?;
+            SqlxBench::warm_pool(&pool).await.map(|_| pool)
+        })
+    });
+    bench_connects(&mut group, "sea_orm", "pool_first", || {
+        rt.block_on(SeaOrmBench::connect_with_pool_size(CONNECT_POOL_SIZE as u32))
+    });
+    bench_connects(&mut group, "sea_orm", "pool_filled", || {
+        rt.block_on(async {
+            let db = SeaOrmBench::connect_with_pool_size(CONNECT_POOL_SIZE as u32).await?;
+            SeaOrmBench::warm_pool(&db).await.map(|_| db)
+        })
+    });
 
-        // sea-orm (note: less efficient due to ORM limitations)
-        group.bench_with_input(BenchmarkId::new("sea_orm", size), size, |b, _| {
-            let db = rt.block_on(SeaOrmBench::connect()).unwrap();
-            b.iter(|| {
-                rt.block_on(SeaOrmBench::select_users_posts_comments(&db, *size as u64))
-                    .unwrap()
-            });
-        });
+    // r2d2 fills the pool while building it unless told not to
+    bench_connects(&mut group, "diesel", "single", || DieselBench::establish(&url));
+    bench_connects(&mut group, "diesel", "pool_first", || {
+        let pool = DieselBench::connect_unfilled(&url, CONNECT_POOL_SIZE as u32)?;
+        pool.get().map(|_| pool)
+    });
+    bench_connects(&mut group, "diesel", "pool_filled", || {
+        let pool = DieselBench::connect_with_pool_size(CONNECT_POOL_SIZE as u32)?;
+        DieselBench::warm_pool(&pool).map(|_| pool)
+    });
 
-        // diesel
-        group.bench_with_input(BenchmarkId::new("diesel", size), size, |b, _| {
-            let pool = DieselBench::connect().unwrap();
-            let mut conn = pool.get().unwrap();
-            b.iter(|| DieselBench::select_users_posts_comments(&mut conn, limit).unwrap());
-        });
+    group.finish();
+}
 
-        // clorinde
-        group.bench_with_input(BenchmarkId::new("clorinde", size), size, |b, _| {
-            let client = rt.block_on(ClorindeBench::connect()).unwrap();
-            b.iter(|| {
-                rt.block_on(ClorindeBench::select_users_posts_comments(&client, limit))
-                    .unwrap()
-            });
-        });
+// ============================================================================
+// Result Format Benchmarks
+// ============================================================================
+
+fn bench_result_format(c: &mut Criterion) {
+    if !tagged(&[Tag::Read, Tag::Types]) {
+        return;
     }
+    let rt = create_runtime();
+    let mut group = benchmark_group(c, "result_format", Duration::from_secs(10), 100);
+
+    guarded("tokio_postgres", || {
+        let client = rt.block_on(TokioPostgresBench::connect()).unwrap();
+        let statement = rt.block_on(result_format::prepare_select_users(&client)).unwrap();
+
+        for size in SIZES {
+            let limit = *size as i64;
+            group.throughput(Throughput::Elements(*size as u64));
+
+            // Decoding alone, over rows fetched once
+            let binary_rows = rt
+                .block_on(result_format::fetch_users_binary(&client, &statement, limit))
+                .unwrap();
+            let text_rows = rt.block_on(result_format::fetch_users_text(&client, limit)).unwrap();
+            group.bench_with_input(BenchmarkId::new("decode_binary", size), &binary_rows, |b, rows| {
+                b.iter(|| {
+                    let users = rows.iter().map(result_format::decode_user_binary).collect();
+                    checked::<Vec<_>, _>("tokio_postgres", users)
+                })
+            });
+            group.bench_with_input(BenchmarkId::new("decode_text", size), &text_rows, |b, rows| {
+                b.iter(|| {
+                    let users = rows.iter().map(result_format::decode_user_text).collect();
+                    checked::<Vec<_>, _>("tokio_postgres", users)
+                })
+            });
+
+            // The same, including the round trip
+            group.bench_function(BenchmarkId::new("query_binary", size), |b| {
+                b.iter(|| {
+                    let rows = checked(
+                        "tokio_postgres",
+                        rt.block_on(result_format::fetch_users_binary(&client, &statement, limit)),
+                    )?;
+                    let users = rows.iter().map(result_format::decode_user_binary).collect();
+                    checked::<Vec<_>, _>("tokio_postgres", users)
+                })
+            });
+            group.bench_function(BenchmarkId::new("query_text", size), |b| {
+                b.iter(|| {
+                    let rows = checked("tokio_postgres", rt.block_on(result_format::fetch_users_text(&client, limit)))?;
+                    let users = rows.iter().map(result_format::decode_user_text).collect();
+                    checked::<Vec<_>, _>("tokio_postgres", users)
+                })
+            });
+        }
+    });
 
     group.finish();
 }
 
 // ============================================================================
-// Aggregate Benchmarks
+// Row Decoding Benchmarks
 // ============================================================================
 
-fn bench_aggregate_count(c: &mut Criterion) {
+/// Time `decode` over `rows`, fetched once per size
+fn bench_decode<R, T, E: Debug>(
+    group: &mut BenchmarkGroup<WallTime>,
+    backend: &str,
+    size: usize,
+    rows: &[R],
+    decode: impl Fn(&R) -> Result<T, E>,
+) {
+    group.bench_with_input(BenchmarkId::new(backend, size), rows, |b, rows| {
+        b.iter(|| checked(backend, rows.iter().map(&decode).collect::<Result<Vec<_>, _>>()))
+    });
+}
+
+fn bench_row_decode(c: &mut Criterion) {
+    if !tagged(&[Tag::Types]) {
+        return;
+    }
     let rt = create_runtime();
-    let mut group = c.benchmark_group("aggregate_count_posts_per_user");
-    group.measurement_time(Duration::from_secs(10));
-    group.sample_size(50);
+    let mut group = benchmark_group(c, "row_decode", Duration::from_secs(10), 100);
 
-    // tokio-postgres
-    group.bench_function("tokio_postgres", |b| {
+    guarded("tokio_postgres", || {
         let client = rt.block_on(TokioPostgresBench::connect()).unwrap();
-        b.iter(|| {
-            rt.block_on(TokioPostgresBench::count_posts_per_user(&client))
-                .unwrap()
-        });
+        for &size in SIZES {
+            group.throughput(Throughput::Elements(size as u64));
+            let rows = rt.block_on(row_decode::fetch_tokio_postgres(&client, size as i64)).unwrap();
+            bench_decode(&mut group, "tokio_postgres", size, &rows, row_decode::decode_tokio_postgres);
+        }
     });
 
-    // sqlx
-    group.bench_function("sqlx", |b| {
+    guarded("sqlx", || {
         let pool = rt.block_on(SqlxBench::connect()).unwrap();
-        b.iter(|| rt.block_on(SqlxBench::count_posts_per_user(&pool)).unwrap());
+        for &size in SIZES {
+            group.throughput(Throughput::Elements(size as u64));
+            let rows = rt.block_on(row_decode::fetch_sqlx(&pool, size as i64)).unwrap();
+            bench_decode(&mut group, "sqlx", size, &rows, row_decode::decode_sqlx);
+        }
     });
 
-    // sea-orm
-    group.bench_function("sea_orm", |b| {
+    guarded("sea_orm", || {
         let db = rt.block_on(SeaOrmBench::connect()).unwrap();
-        b.iter(|| rt.block_on(SeaOrmBench::count_posts_per_user(&db)).unwrap());
+        for &size in SIZES {
+            group.throughput(Throughput::Elements(size as u64));
+            let rows = rt.block_on(row_decode::fetch_sea_orm(&db, size as i64)).unwrap();
+            bench_decode(&mut group, "sea_orm", size, &rows, row_decode::decode_sea_orm);
+        }
     });
 
-    // diesel
-    group.bench_function("diesel", |b| {
-        let pool = DieselBench::connect().unwrap();
-        let mut conn = pool.get().unwrap();
-        b.iter(|| DieselBench::count_posts_per_user(&mut conn).unwrap());
+    group.finish();
+}
+
+// ============================================================================
+// Column Access Benchmarks
+// ============================================================================
+//
+// The row decoding of `row_decode` for the two drivers that expose their rows,
+// reading every column by name (`try_get("username")`, as every other group
+// does) and by position (`try_get(1)`), over the same pre-fetched rows
+
+fn bench_column_access(c: &mut Criterion) {
+    if !tagged(&[Tag::Read, Tag::Types]) {
+        return;
+    }
+    let rt = create_runtime();
+    let mut group = benchmark_group(c, "column_access", Duration::from_secs(10), 100);
+
+    guarded("tokio_postgres", || {
+        let client = rt.block_on(TokioPostgresBench::connect()).unwrap();
+        for &size in SIZES {
+            group.throughput(Throughput::Elements(size as u64));
+            let rows = rt.block_on(row_decode::fetch_tokio_postgres(&client, size as i64)).unwrap();
+            bench_decode(&mut group, "tokio_postgres_by_name", size, &rows, row_decode::decode_tokio_postgres);
+            bench_decode(&mut group, "tokio_postgres_by_index", size, &rows, row_decode::decode_tokio_postgres_by_index);
+        }
     });
 
-    // clorinde
-    group.bench_function("clorinde", |b| {
-        let client = rt.block_on(ClorindeBench::connect()).unwrap();
-        b.iter(|| {
-            rt.block_on(ClorindeBench::count_posts_per_user(&client))
-                .unwrap()
-        });
+    guarded("sqlx", || {
+        let pool = rt.block_on(SqlxBench::connect()).unwrap();
+        for &size in SIZES {
+            group.throughput(Throughput::Elements(size as u64));
+            let rows = rt.block_on(row_decode::fetch_sqlx(&pool, size as i64)).unwrap();
+            bench_decode(&mut group, "sqlx_by_name", size, &rows, row_decode::decode_sqlx);
+            bench_decode(&mut group, "sqlx_by_index", size, &rows, row_decode::decode_sqlx_by_index);
+        }
+    });
+
+    group.finish();
+}
+
+// ============================================================================
+// Row Allocation Benchmarks
+// ============================================================================
+//
+// The `row_decode` rows for the two drivers that expose them, decoded into
+// the shared `User` (four `String`s, four allocations per row), into a
+// `PackedUser` (the text copied into one `Arc<str>` per row) and into a
+// `UserRef` (the text borrowed from the row, no allocation). What `owned`
+// takes beyond `borrowed` is what the shared model's allocations cost.
+
+/// [`bench_decode`] for a decoding that borrows from the row
+fn bench_decode_borrowed<R, E: Debug>(
+    group: &mut BenchmarkGroup<WallTime>,
+    backend: &str,
+    size: usize,
+    rows: &[R],
+    decode: impl for<'r> Fn(&'r R) -> Result<row_decode::UserRef<'r>, E>,
+) {
+    group.bench_with_input(BenchmarkId::new(backend, size), rows, |b, rows| {
+        b.iter(|| checked(backend, rows.iter().map(&decode).collect::<Result<Vec<_>, _>>()).map(consume))
+    });
+}
+
+fn bench_row_allocation(c: &mut Criterion) {
+    if !tagged(&[Tag::Types]) {
+        return;
+    }
+    let rt = create_runtime();
+    let mut group = benchmark_group(c, "row_allocation", Duration::from_secs(10), 100);
+
+    guarded("tokio_postgres", || {
+        let client = rt.block_on(TokioPostgresBench::connect()).unwrap();
+        for &size in SIZES {
+            group.throughput(Throughput::Elements(size as u64));
+            let rows = rt.block_on(row_decode::fetch_tokio_postgres(&client, size as i64)).unwrap();
+            bench_decode(&mut group, "tokio_postgres_owned", size, &rows, row_decode::decode_tokio_postgres);
+            bench_decode(&mut group, "tokio_postgres_packed", size, &rows, row_decode::decode_tokio_postgres_packed);
+            bench_decode_borrowed(
+                &mut group,
+                "tokio_postgres_borrowed",
+                size,
+                &rows,
+                row_decode::decode_tokio_postgres_borrowed,
+            );
+        }
+    });
+
+    guarded("sqlx", || {
+        let pool = rt.block_on(SqlxBench::connect()).unwrap();
+        for &size in SIZES {
+            group.throughput(Throughput::Elements(size as u64));
+            let rows = rt.block_on(row_decode::fetch_sqlx(&pool, size as i64)).unwrap();
+            bench_decode(&mut group, "sqlx_owned", size, &rows, row_decode::decode_sqlx);
+            bench_decode(&mut group, "sqlx_packed", size, &rows, row_decode::decode_sqlx_packed);
+            bench_decode_borrowed(&mut group, "sqlx_borrowed", size, &rows, row_decode::decode_sqlx_borrowed);
+        }
+    });
+
+    group.finish();
+}
+
+// ============================================================================
+// sqlx Statement Cache Benchmarks
+// ============================================================================
+
+/// sqlx statement cache capacities: off, smaller than and larger than the distinct queries
+const STATEMENT_CACHE_CAPACITIES: &[usize] = &[0, 10, 100];
+/// Distinct query texts per iteration: every cache holds 5, a 10-entry one thrashes on 50
+const DISTINCT_QUERIES: &[usize] = &[5, 50];
+
+fn bench_sqlx_statement_cache(c: &mut Criterion) {
+    if !tagged(&[Tag::Read]) {
+        return;
+    }
+    let rt = create_runtime();
+    let mut group = benchmark_group(c, "sqlx_statement_cache", Duration::from_secs(10), 50);
+
+    let run_queries = |pool: &PgPool, distinct: usize| {
+        for variant in 0..distinct {
+            checked("sqlx", rt.block_on(SqlxBench::select_usernames_variant(pool, variant, 30)));
+        }
+    };
+
+    guarded("sqlx", || {
+        for capacity in STATEMENT_CACHE_CAPACITIES {
+            let config = SqlxConfig::from_env().with_statement_cache_capacity(*capacity);
+            for distinct in DISTINCT_QUERIES {
+                group.throughput(Throughput::Elements(*distinct as u64));
+                group.bench_function(BenchmarkId::new(format!("distinct_{distinct}"), capacity), |b| {
+                    b.iter_custom(|iters| {
+                        // A fresh single connection per sample, so every query meets the same
+                        // cache and the statements an uncached one leaves behind don't pile up
+                        let pool = rt.block_on(SqlxBench::connect_with_config(&database_url(), 1, &config)).unwrap();
+                        run_queries(&pool, *distinct);
+
+                        let start = Instant::now();
+                        for _ in 0..iters {
+                            run_queries(&pool, *distinct);
+                        }
+                        start.elapsed()
+                    })
+                });
+            }
+        }
     });
 
     group.finish();
 }
 
 // ============================================================================
-// Transaction Benchmarks
+// sqlx Query Style Benchmarks
 // ============================================================================
 
-fn bench_transaction_insert(c: &mut Criterion) {
+fn bench_sqlx_query_styles(c: &mut Criterion) {
+    if !tagged(&[Tag::Read, Tag::Types]) {
+        return;
+    }
     let rt = create_runtime();
-    let mut group = c.benchmark_group("transaction_insert_user_with_posts");
-    group.measurement_time(Duration::from_secs(15));
-    group.sample_size(30);
-
-    for size in &[1, 5, 10] {
-        let posts: Vec<NewPost> = (0..*size)
-            .map(|i| NewPost::generate(Uuid::nil(), i))
-            .collect();
-
-        // sqlx (has proper transaction support)
-        group.bench_with_input(BenchmarkId::new("sqlx", size), size, |b, _| {
-            let pool = rt.block_on(SqlxBench::connect()).unwrap();
-            let mut counter = 0usize;
+    let mut group = benchmark_group(c, "sqlx_query_styles", Duration::from_secs(10), 100);
+    let user_ids = sample_user_ids(&rt);
+
+    guarded("sqlx", || {
+        let pool = rt.block_on(SqlxBench::connect()).unwrap();
+
+        // Each query as a `query` string decoded through `Row`, a `query!` macro
+        // and `query_as` into a `FromRow` struct
+        let mut i = 0;
+        group.bench_function(BenchmarkId::new("select_user_by_id", "query"), |b| {
             b.iter(|| {
-                counter += 1;
-                let user = NewUser::generate(counter);
-                rt.block_on(SqlxBench::insert_user_with_posts(&pool, &user, &posts))
-                    .unwrap()
-            });
-            rt.block_on(SqlxBench::cleanup(&pool)).unwrap();
+                i += 1;
+                checked("sqlx", rt.block_on(SqlxBench::select_user_by_id(&pool, user_ids[i % user_ids.len()])))
+            })
         });
-
-        // sea-orm
-        group.bench_with_input(BenchmarkId::new("sea_orm", size), size, |b, _| {
-            let db = rt.block_on(SeaOrmBench::connect()).unwrap();
-            let mut counter = 0usize;
+        group.bench_function(BenchmarkId::new("select_user_by_id", "query_macro"), |b| {
             b.iter(|| {
-                counter += 1;
-                let user = NewUser::generate(counter);
-                rt.block_on(SeaOrmBench::insert_user_with_posts(&db, &user, &posts))
-                    .unwrap()
-            });
-            rt.block_on(SeaOrmBench::cleanup(&db)).unwrap();
+                i += 1;
+                checked("sqlx", rt.block_on(SqlxBench::select_user_by_id_macro(&pool, user_ids[i % user_ids.len()])))
+            })
         });
-
-        // diesel
-        group.bench_with_input(BenchmarkId::new("diesel", size), size, |b, _| {
-            let pool = DieselBench::connect().unwrap();
-            let mut conn = pool.get().unwrap();
-            let mut counter = 0usize;
+        group.bench_function(BenchmarkId::new("select_user_by_id", "query_as"), |b| {
             b.iter(|| {
-                counter += 1;
-                let user = NewUser::generate(counter);
-                DieselBench::insert_user_with_posts(&mut conn, &user, &posts).unwrap()
-            });
-            DieselBench::cleanup(&mut conn).unwrap();
+                i += 1;
+                checked("sqlx", rt.block_on(SqlxBench::select_user_by_id_as(&pool, user_ids[i % user_ids.len()])))
+            })
         });
 
-        // clorinde (using sequential inserts)
-        group.bench_with_input(BenchmarkId::new("clorinde", size), size, |b, _| {
-            let client = rt.block_on(ClorindeBench::connect()).unwrap();
-            let mut counter = 0usize;
-            b.iter(|| {
-                counter += 1;
-                let user = NewUser::generate(counter);
-                rt.block_on(ClorindeBench::insert_user_with_posts(&client, &user, &posts))
-                    .unwrap()
+        for &size in SIZES {
+            let limit = size as i64;
+            group.throughput(Throughput::Elements(size as u64));
+            group.bench_function(BenchmarkId::new(format!("select_users_limit_{size}"), "query"), |b| {
+                b.iter(|| checked("sqlx", rt.block_on(SqlxBench::select_users_limit(&pool, limit))))
             });
-            rt.block_on(ClorindeBench::cleanup(&client)).unwrap();
-        });
-    }
+            group.bench_function(BenchmarkId::new(format!("select_users_limit_{size}"), "query_macro"), |b| {
+                b.iter(|| checked("sqlx", rt.block_on(SqlxBench::select_users_limit_macro(&pool, limit))))
+            });
+            group.bench_function(BenchmarkId::new(format!("select_users_limit_{size}"), "query_as"), |b| {
+                b.iter(|| checked("sqlx", rt.block_on(SqlxBench::select_users_limit_as(&pool, limit))))
+            });
+            group.bench_function(BenchmarkId::new(format!("join_posts_users_{size}"), "query"), |b| {
+                b.iter(|| checked("sqlx", rt.block_on(SqlxBench::select_posts_with_user(&pool, limit))))
+            });
+            group.bench_function(BenchmarkId::new(format!("join_posts_users_{size}"), "query_macro"), |b| {
+                b.iter(|| checked("sqlx", rt.block_on(SqlxBench::select_posts_with_user_macro(&pool, limit))))
+            });
+            group.bench_function(BenchmarkId::new(format!("join_posts_users_{size}"), "query_as"), |b| {
+                b.iter(|| checked("sqlx", rt.block_on(SqlxBench::select_posts_with_user_as(&pool, limit))))
+            });
+        }
+    });
 
     group.finish();
 }
 
 // ============================================================================
-// Heavy Workload Benchmarks
+// SeaORM Partial Model Benchmarks
 // ============================================================================
 
-fn bench_heavy_mixed_workload(c: &mut Criterion) {
+fn bench_sea_orm_partial_model(c: &mut Criterion) {
+    if !tagged(&[Tag::Read, Tag::Join]) {
+        return;
+    }
     let rt = create_runtime();
-    let mut group = c.benchmark_group("heavy_mixed_workload");
-    group.measurement_time(Duration::from_secs(30));
-    group.sample_size(20);
+    // Sized for the query-per-comment triple join, as in its scenario
+    let mut group = benchmark_group(c, "sea_orm_partial_model", Duration::from_secs(15), 30);
+
+    guarded("sea_orm", || {
+        let db = rt.block_on(SeaOrmBench::connect()).unwrap();
+
+        // Each read through the whole entities (`entity`, as in every other group) and
+        // narrowed to the columns a listing shows, or joined in one statement
+        for &size in SIZES {
+            let limit = size as u64;
+            group.throughput(Throughput::Elements(size as u64));
+            group.bench_function(BenchmarkId::new(format!("select_users_limit_{size}"), "entity"), |b| {
+                b.iter(|| checked("sea_orm", rt.block_on(SeaOrmBench::select_users_limit(&db, limit))))
+            });
+            group.bench_function(BenchmarkId::new(format!("select_users_limit_{size}"), "partial_model"), |b| {
+                b.iter(|| checked("sea_orm", rt.block_on(SeaOrmBench::select_user_summaries(&db, limit))))
+            });
+            group.bench_function(BenchmarkId::new(format!("join_posts_users_{size}"), "entity"), |b| {
+                b.iter(|| checked("sea_orm", rt.block_on(SeaOrmBench::select_posts_with_user(&db, limit))))
+            });
+            group.bench_function(BenchmarkId::new(format!("join_posts_users_{size}"), "partial_model"), |b| {
+                b.iter(|| checked("sea_orm", rt.block_on(SeaOrmBench::select_post_summaries(&db, limit))))
+            });
+            group.bench_function(BenchmarkId::new(format!("join_users_posts_comments_{size}"), "entity"), |b| {
+                b.iter(|| checked("sea_orm", rt.block_on(SeaOrmBench::select_users_posts_comments(&db, limit))))
+            });
+            group.bench_function(BenchmarkId::new(format!("join_users_posts_comments_{size}"), "single_join"), |b| {
+                b.iter(|| checked("sea_orm", rt.block_on(SeaOrmBench::select_users_posts_comments_joined(&db, limit))))
+            });
+        }
+    });
+
+    group.finish();
+}
+
+// ============================================================================
+// Column Subset Benchmarks
+// ============================================================================
 
-    // Heavy workload: mix of reads (80%) and writes (20%)
-    let operations = 100;
+fn bench_column_subset(c: &mut Criterion) {
+    if !tagged(&[Tag::Read, Tag::Types]) {
+        return;
+    }
+    let rt = create_runtime();
+    let mut group = benchmark_group(c, "column_subset", Duration::from_secs(10), 50);
 
-    // tokio-postgres
-    group.bench_function("tokio_postgres", |b| {
+    // The user list as every backend reads it (`full_*`: all eight columns into a
+    // `User`) and with only the id and username (`id_username_*`), so the ORMs'
+    // entity hydration can be told apart from the drivers' decoding
+    guarded("tokio_postgres", || {
         let client = rt.block_on(TokioPostgresBench::connect()).unwrap();
-        let mut counter = 0usize;
-        b.iter(|| {
-            rt.block_on(async {
-                for i in 0..operations {
-                    counter += 1;
-                    if i % 5 == 0 {
-                        // Write (20%)
-                        let user = NewUser::generate(counter);
-                        let _ = TokioPostgresBench::insert_user(&client, &user).await;
-                    } else {
-                        // Read (80%)
-                        let _ = TokioPostgresBench::select_users_limit(&client, 50).await;
-                    }
-                }
+        for &size in SIZES {
+            let limit = size as i64;
+            group.throughput(Throughput::Elements(size as u64));
+            group.bench_function(BenchmarkId::new("tokio_postgres", format!("full_{size}")), |b| {
+                b.iter(|| checked("tokio_postgres", rt.block_on(TokioPostgresBench::select_users_limit(&client, limit))))
             });
-        });
-        rt.block_on(TokioPostgresBench::cleanup(&client)).unwrap();
+            group.bench_function(BenchmarkId::new("tokio_postgres", format!("id_username_{size}")), |b| {
+                b.iter(|| checked("tokio_postgres", rt.block_on(TokioPostgresBench::select_usernames_limit(&client, limit))))
+            });
+        }
     });
 
-    // sqlx
-    group.bench_function("sqlx", |b| {
+    guarded("sqlx", || {
         let pool = rt.block_on(SqlxBench::connect()).unwrap();
-        let mut counter = 0usize;
-        b.iter(|| {
-            rt.block_on(async {
-                for i in 0..operations {
-                    counter += 1;
-                    if i % 5 == 0 {
-                        let user = NewUser::generate(counter);
-                        let _ = SqlxBench::insert_user(&pool, &user).await;
-                    } else {
-                        let _ = SqlxBench::select_users_limit(&pool, 50).await;
-                    }
-                }
+        for &size in SIZES {
+            let limit = size as i64;
+            group.throughput(Throughput::Elements(size as u64));
+            group.bench_function(BenchmarkId::new("sqlx", format!("full_{size}")), |b| {
+                b.iter(|| checked("sqlx", rt.block_on(SqlxBench::select_users_limit(&pool, limit))))
             });
-        });
-        rt.block_on(SqlxBench::cleanup(&pool)).unwrap();
+            group.bench_function(BenchmarkId::new("sqlx", format!("id_username_{size}")), |b| {
+                b.iter(|| checked("sqlx", rt.block_on(SqlxBench::select_usernames_limit(&pool, limit))))
+            });
+        }
     });
 
-    // sea-orm
-    group.bench_function("sea_orm", |b| {
+    guarded("sea_orm", || {
         let db = rt.block_on(SeaOrmBench::connect()).unwrap();
-        let mut counter = 0usize;
-        b.iter(|| {
-            rt.block_on(async {
-                for i in 0..operations {
-                    counter += 1;
-                    if i % 5 == 0 {
-                        let user = NewUser::generate(counter);
-                        let _ = SeaOrmBench::insert_user(&db, &user).await;
-                    } else {
-                        let _ = SeaOrmBench::select_users_limit(&db, 50).await;
-                    }
-                }
+        for &size in SIZES {
+            let limit = size as u64;
+            group.throughput(Throughput::Elements(size as u64));
+            group.bench_function(BenchmarkId::new("sea_orm", format!("full_{size}")), |b| {
+                b.iter(|| checked("sea_orm", rt.block_on(SeaOrmBench::select_users_limit(&db, limit))))
             });
-        });
-        rt.block_on(SeaOrmBench::cleanup(&db)).unwrap();
+            group.bench_function(BenchmarkId::new("sea_orm", format!("id_username_{size}")), |b| {
+                b.iter(|| checked("sea_orm", rt.block_on(SeaOrmBench::select_usernames_limit(&db, limit))))
+            });
+        }
     });
 
-    // diesel
-    group.bench_function("diesel", |b| {
+    guarded("diesel", || {
         let pool = DieselBench::connect().unwrap();
         let mut conn = pool.get().unwrap();
-        let mut counter = 0usize;
-        b.iter(|| {
-            for i in 0..operations {
-                counter += 1;
-                if i % 5 == 0 {
-                    let user = NewUser::generate(counter);
-                    let _ = DieselBench::insert_user(&mut conn, &user);
-                } else {
-                    let _ = DieselBench::select_users_limit(&mut conn, 50);
-                }
+        for &size in SIZES {
+            let limit = size as i64;
+            group.throughput(Throughput::Elements(size as u64));
+            group.bench_function(BenchmarkId::new("diesel", format!("full_{size}")), |b| {
+                b.iter(|| checked("diesel", DieselBench::select_users_limit(&mut conn, limit)))
+            });
+            group.bench_function(BenchmarkId::new("diesel", format!("id_username_{size}")), |b| {
+                b.iter(|| checked("diesel", DieselBench::select_usernames_limit(&mut conn, limit)))
+            });
+        }
+    });
+
+    group.finish();
+}
+
+// ============================================================================
+// Primary Key Type Benchmarks
+// ============================================================================
+//
+// The insert, point lookup and posts-with-author join on the UUID-keyed tables
+// of `init.sql` and on their BIGSERIAL-keyed copy in the `bigserial` schema
+// (`cargo run -- seed bigserial`), for the backends implementing
+// `primary_key::SerialKeys`. `BENCH_KEY_TYPES` picks the key types.
+
+/// Posts fetched with their author by `primary_key_join`
+const PRIMARY_KEY_JOIN_LIMIT: i64 = 100;
+
+/// The operation of one `primary_key_*` group
+#[derive(Clone, Copy)]
+enum KeyedOp<'a> {
+    Insert,
+    Select { uuids: &'a [Uuid], serials: &'a [i64] },
+    Join,
+}
+
+impl KeyedOp<'_> {
+    async fn run<B: SerialKeys>(self, conn: &B::Connection, key: KeyType, iteration: usize) -> Result<(), B::Error> {
+        match (self, key) {
+            (KeyedOp::Insert, KeyType::Uuid) => B::insert_user(conn, &NewUser::generate(iteration)).await.map(consume),
+            (KeyedOp::Insert, KeyType::Bigserial) => {
+                B::insert_user_serial(conn, &NewUser::generate(iteration)).await.map(consume)
+            }
+            (KeyedOp::Select { uuids, .. }, KeyType::Uuid) => {
+                B::select_user_by_id(conn, uuids[iteration % uuids.len()]).await.map(consume)
+            }
+            (KeyedOp::Select { serials, .. }, KeyType::Bigserial) => {
+                B::select_user_by_serial_id(conn, serials[iteration % serials.len()]).await.map(consume)
+            }
+            (KeyedOp::Join, KeyType::Uuid) => B::select_posts_with_user(conn, PRIMARY_KEY_JOIN_LIMIT).await.map(consume),
+            (KeyedOp::Join, KeyType::Bigserial) => {
+                B::select_posts_with_user_serial(conn, PRIMARY_KEY_JOIN_LIMIT).await.map(consume)
+            }
+        }
+    }
+}
+
+/// Register one backend for `op` on `key`; inserts are cleaned up at every
+/// sample boundary, as in `register`
+fn register_keyed<B: SerialKeys>(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    rt: &Runtime,
+    client: &tokio_postgres::Client,
+    name: &str,
+    key: KeyType,
+    op: KeyedOp<'_>,
+) {
+    let cleanup = || match key {
+        KeyType::Uuid => rt.block_on(TokioPostgresBench::cleanup(client)),
+        KeyType::Bigserial => rt.block_on(primary_key::cleanup(client)),
+    };
+
+    guarded(name, || {
+        group.bench_function(BenchmarkId::new(name, key), |b| {
+            let conn = rt.block_on(B::connect()).unwrap();
+            if let KeyedOp::Insert = op {
+                b.iter_custom(|iters| {
+                    checked(name, cleanup());
+                    let start = Instant::now();
+                    for iteration in 1..=iters as usize {
+                        checked(name, rt.block_on(op.run::<B>(&conn, key, iteration)));
+                    }
+                    start.elapsed()
+                });
+                checked(name, cleanup());
+            } else {
+                let mut iteration = 0usize;
+                b.iter(|| {
+                    iteration += 1;
+                    checked(name, rt.block_on(op.run::<B>(&conn, key, iteration)))
+                });
             }
         });
-        DieselBench::cleanup(&mut conn).unwrap();
     });
+}
 
-    // clorinde
-    group.bench_function("clorinde", |b| {
-        let client = rt.block_on(ClorindeBench::connect()).unwrap();
-        let mut counter = 0usize;
-        b.iter(|| {
-            rt.block_on(async {
-                for i in 0..operations {
-                    counter += 1;
-                    if i % 5 == 0 {
-                        let user = NewUser::generate(counter);
-                        let _ = ClorindeBench::insert_user(&client, &user).await;
-                    } else {
-                        let _ = ClorindeBench::select_users_limit(&client, 50).await;
+fn bench_primary_key(c: &mut Criterion) {
+    if !tagged(&[Tag::Read, Tag::Write, Tag::Join, Tag::Types]) {
+        return;
+    }
+    let rt = create_runtime();
+    let client = rt.block_on(TokioPostgresBench::connect()).unwrap();
+    let mut key_types = KeyType::from_env();
+    if key_types.contains(&KeyType::Bigserial) && rt.block_on(primary_key::seeded_users(&client)).unwrap() == 0 {
+        eprintln!("skipping bigserial keys, the bigserial schema is empty (run `cargo run -- seed bigserial`)");
+        key_types.retain(|&key| key != KeyType::Bigserial);
+    }
+    if key_types.is_empty() {
+        return;
+    }
+
+    let uuids = sample_user_ids(&rt);
+    let serials = if key_types.contains(&KeyType::Bigserial) {
+        rt.block_on(primary_key::sample_user_ids(&client, 100)).unwrap()
+    } else {
+        Vec::new()
+    };
+    let groups = [
+        ("primary_key_insert", KeyedOp::Insert),
+        ("primary_key_select", KeyedOp::Select { uuids: &uuids, serials: &serials }),
+        ("primary_key_join", KeyedOp::Join),
+    ];
+
+    for (group_name, op) in groups {
+        let mut group = benchmark_group(c, group_name, Duration::from_secs(10), 100);
+        for &key in &key_types {
+            for_each_backend!(
+                [
+                    TokioPostgresBench => "tokio_postgres",
+                    SqlxBench => "sqlx",
+                    SeaOrmBench => "sea_orm",
+                    DieselBench => "diesel",
+                ]
+                |B, name| register_keyed::<B>(&mut group, &rt, &client, name, key, op)
+            );
+        }
+        group.finish();
+    }
+    assert_cleaned_up(&rt, "primary_key_insert");
+}
+
+// ============================================================================
+// Generated Column Benchmarks
+// ============================================================================
+//
+// The copy of `users` with a stored `full_name` in the `generated` schema,
+// created from the dataset for the run and dropped after it: inserts into it
+// against inserts into `users`, reading its newest users and looking users up
+// by the indexed generated column.
+
+/// Users read by `generated_column_select`
+const GENERATED_COLUMN_LIMIT: i64 = 100;
+
+fn register_generated_insert<B: GeneratedColumn>(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    rt: &Runtime,
+    client: &tokio_postgres::Client,
+    name: &str,
+) {
+    guarded(name, || {
+        let conn = rt.block_on(B::connect()).unwrap();
+        for table in ["users", "generated"] {
+            let cleanup = || match table {
+                "users" => rt.block_on(TokioPostgresBench::cleanup(client)),
+                _ => rt.block_on(generated_column::cleanup(client)),
+            };
+            group.bench_function(BenchmarkId::new(name, table), |b| {
+                b.iter_custom(|iters| {
+                    checked(name, cleanup());
+                    let start = Instant::now();
+                    for iteration in 1..=iters as usize {
+                        let user = NewUser::generate(iteration);
+                        let inserted = match table {
+                            "users" => rt.block_on(B::insert_user(&conn, &user)),
+                            _ => rt.block_on(B::insert_named_user(&conn, &user)),
+                        };
+                        checked(name, inserted);
                     }
-                }
+                    start.elapsed()
+                })
             });
-        });
-        rt.block_on(ClorindeBench::cleanup(&client)).unwrap();
+            checked(name, cleanup());
+        }
     });
+}
 
-    group.finish();
+/// Read `limit` newest users of the copy when `full_names` is `None`, and look
+/// them up by each of `full_names` in turn otherwise
+fn register_generated_read<B: GeneratedColumn>(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    rt: &Runtime,
+    name: &str,
+    full_names: Option<&[String]>,
+) {
+    guarded(name, || {
+        let conn = rt.block_on(B::connect()).unwrap();
+        let mut iteration = 0usize;
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                iteration += 1;
+                match full_names {
+                    None => checked(name, rt.block_on(B::select_named_users(&conn, GENERATED_COLUMN_LIMIT))),
+                    Some(full_names) => {
+                        let full_name = &full_names[iteration % full_names.len()];
+                        checked(name, rt.block_on(B::select_by_full_name(&conn, full_name)))
+                    }
+                }
+            })
+        });
+    });
 }
 
-fn bench_heavy_read_intensive(c: &mut Criterion) {
+fn bench_generated_column(c: &mut Criterion) {
+    if !tagged(&[Tag::Read, Tag::Write, Tag::Types]) {
+        return;
+    }
     let rt = create_runtime();
-    let mut group = c.benchmark_group("heavy_read_intensive");
-    group.measurement_time(Duration::from_secs(20));
-    group.sample_size(30);
+    let client = rt.block_on(TokioPostgresBench::connect()).unwrap();
+    rt.block_on(generated_column::create(&client)).unwrap();
+    let full_names = rt.block_on(generated_column::sample_full_names(&client, 100)).unwrap();
+
+    let mut group = benchmark_group(c, "generated_column_insert", Duration::from_secs(10), 100);
+    for_each_backend!(
+        [
+            TokioPostgresBench => "tokio_postgres",
+            SqlxBench => "sqlx",
+            SeaOrmBench => "sea_orm",
+            DieselBench => "diesel",
+            ClorindeBench => "clorinde",
+        ]
+        |B, name| register_generated_insert::<B>(&mut group, &rt, &client, name)
+    );
+    group.finish();
 
-    let operations = 200;
+    for (group_name, full_names) in [("generated_column_select", None), ("generated_column_filter", Some(&full_names[..]))] {
+        let mut group = benchmark_group(c, group_name, Duration::from_secs(10), 100);
+        for_each_backend!(
+            [
+                TokioPostgresBench => "tokio_postgres",
+                SqlxBench => "sqlx",
+                SeaOrmBench => "sea_orm",
+                DieselBench => "diesel",
+                ClorindeBench => "clorinde",
+            ]
+            |B, name| register_generated_read::<B>(&mut group, &rt, name, full_names)
+        );
+        group.finish();
+    }
 
-    // tokio-postgres
-    group.bench_function("tokio_postgres", |b| {
-        let client = rt.block_on(TokioPostgresBench::connect()).unwrap();
-        b.iter(|| {
-            rt.block_on(async {
-                for i in 0..operations {
-                    match i % 4 {
-                        0 => {
-                            let _ = TokioPostgresBench::select_users_limit(&client, 100).await;
-                        }
-                        1 => {
-                            let _ =
-                                TokioPostgresBench::select_users_filtered(&client, 25, 55, 50)
-                                    .await;
-                        }
-                        2 => {
-                            let _ = TokioPostgresBench::select_posts_with_user(&client, 50).await;
-                        }
-                        _ => {
-                            let _ = TokioPostgresBench::count_posts_per_user(&client).await;
-                        }
+    rt.block_on(generated_column::drop(&client)).unwrap();
+    assert_cleaned_up(&rt, "generated_column_insert");
+}
+
+// ============================================================================
+// Email Lookup Benchmarks
+// ============================================================================
+//
+// Two copies of `users` in the `email_lookup` schema, created from the dataset
+// for the run and dropped after it: one with a `citext` email compared with a
+// plain `=`, one with a `VARCHAR` email found through an index on
+// `LOWER(email)`. The emails looked up are upper-cased, so a backend that lost
+// the case-insensitive comparison would find nothing and fail the run.
+
+/// Look up each of `emails` in turn on the `citext` copy, or on the
+/// `LOWER(email)` copy when `lower` is set
+fn register_email_lookup<B: EmailLookup>(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    rt: &Runtime,
+    name: &str,
+    emails: &[String],
+) {
+    guarded(name, || {
+        let conn = rt.block_on(<B as DatabaseBenchmark>::connect()).unwrap();
+        for variant in ["citext", "lower"] {
+            let mut iteration = 0usize;
+            group.bench_function(BenchmarkId::new(name, variant), |b| {
+                b.iter(|| {
+                    iteration += 1;
+                    let email = &emails[iteration % emails.len()];
+                    let found = match variant {
+                        "citext" => rt.block_on(B::select_user_by_email(&conn, email)),
+                        _ => rt.block_on(B::select_user_by_lower_email(&conn, email)),
+                    };
+                    if let Some(None) = checked(name, found) {
+                        panic!("{name}: no user for {email}");
                     }
-                }
+                })
             });
-        });
+        }
     });
+}
 
-    // sqlx
-    group.bench_function("sqlx", |b| {
-        let pool = rt.block_on(SqlxBench::connect()).unwrap();
-        b.iter(|| {
-            rt.block_on(async {
-                for i in 0..operations {
-                    match i % 4 {
-                        0 => {
-                            let _ = SqlxBench::select_users_limit(&pool, 100).await;
-                        }
-                        1 => {
-                            let _ = SqlxBench::select_users_filtered(&pool, 25, 55, 50).await;
-                        }
-                        2 => {
-                            let _ = SqlxBench::select_posts_with_user(&pool, 50).await;
-                        }
-                        _ => {
-                            let _ = SqlxBench::count_posts_per_user(&pool).await;
-                        }
+fn bench_email_lookup(c: &mut Criterion) {
+    if !tagged(&[Tag::Read, Tag::Types]) {
+        return;
+    }
+    let rt = create_runtime();
+    let client = rt.block_on(TokioPostgresBench::connect()).unwrap();
+    rt.block_on(email_lookup::create(&client)).unwrap();
+    let emails = rt.block_on(email_lookup::sample_emails(&client, 100)).unwrap();
+
+    let mut group = benchmark_group(c, "email_lookup", Duration::from_secs(10), 100);
+    for_each_backend!(
+        [
+            TokioPostgresBench => "tokio_postgres",
+            SqlxBench => "sqlx",
+            SeaOrmBench => "sea_orm",
+            DieselBench => "diesel",
+            ClorindeBench => "clorinde",
+        ]
+        |B, name| register_email_lookup::<B>(&mut group, &rt, name, &emails)
+    );
+    group.finish();
+
+    rt.block_on(email_lookup::drop(&client)).unwrap();
+}
+
+// ============================================================================
+// Deferred Constraint Benchmarks
+// ============================================================================
+//
+// The copy of users, posts and comments in the `deferred` schema, whose
+// foreign keys are `DEFERRABLE`, created from the dataset for the run and
+// dropped after it. Each iteration is one transaction inserting a user, its
+// posts and comments on every post, with the foreign keys checked after every
+// statement or all at `COMMIT`.
+
+fn register_deferred_constraints<B: DeferredConstraints>(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    rt: &Runtime,
+    client: &tokio_postgres::Client,
+    name: &str,
+) {
+    let (posts, comments) = deferred_constraints::thread();
+    guarded(name, || {
+        let conn = rt.block_on(<B as DatabaseBenchmark>::connect()).unwrap();
+        for timing in ConstraintTiming::ALL {
+            group.bench_function(BenchmarkId::new(name, timing), |b| {
+                b.iter_custom(|iters| {
+                    checked(name, rt.block_on(deferred_constraints::cleanup(client)));
+                    let start = Instant::now();
+                    for iteration in 1..=iters as usize {
+                        let user = NewUser::generate(iteration);
+                        checked(
+                            name,
+                            rt.block_on(B::insert_user_posts_comments(&conn, &user, &posts, &comments, timing)),
+                        );
                     }
-                }
+                    start.elapsed()
+                })
             });
-        });
+            checked(name, rt.block_on(deferred_constraints::cleanup(client)));
+        }
     });
+}
+
+fn bench_deferred_constraints(c: &mut Criterion) {
+    if !tagged(&[Tag::Write, Tag::Transaction]) {
+        return;
+    }
+    let rt = create_runtime();
+    let client = rt.block_on(TokioPostgresBench::connect()).unwrap();
+    rt.block_on(deferred_constraints::create(&client)).unwrap();
+
+    let mut group = benchmark_group(c, "deferred_constraints", Duration::from_secs(10), 50);
+    for_each_backend!(
+        [
+            TokioPostgresBench => "tokio_postgres",
+            SqlxBench => "sqlx",
+            SeaOrmBench => "sea_orm",
+            DieselBench => "diesel",
+            ClorindeBench => "clorinde",
+        ]
+        |B, name| register_deferred_constraints::<B>(&mut group, &rt, &client, name)
+    );
+    group.finish();
+
+    rt.block_on(deferred_constraints::drop(&client)).unwrap();
+}
+
+// ============================================================================
+// Session Configuration Benchmarks
+// ============================================================================
+
+/// PostgreSQL's minimum and default `work_mem`, and a generous one
+const WORK_MEM_VALUES: &[&str] = &["64kB", "4MB", "64MB"];
+
+fn bench_work_mem(c: &mut Criterion) {
+    if !tagged(&[Tag::Read, Tag::Join]) {
+        return;
+    }
+    let rt = create_runtime();
+    let mut group = benchmark_group(c, "work_mem", Duration::from_secs(15), 30);
+
+    // `work_mem` only changes the server's plans, so one driver shows its effect for all
+    let limit = *SIZES.last().unwrap() as i64;
+    for work_mem in WORK_MEM_VALUES {
+        let url = database_url_with(&SessionConfig::from_env().with_work_mem(work_mem));
+        let join = format!("join_users_posts_comments_{limit}");
+        let connect = TokioPostgresBench::connect_to(&url);
+        bench_on_connection::<TokioPostgresBench, _>(&mut group, &rt, &join, work_mem, connect, &JoinTriple { limit });
+        let connect = TokioPostgresBench::connect_to(&url);
+        bench_on_connection::<TokioPostgresBench, _>(
+            &mut group,
+            &rt,
+            "aggregate_count_posts_per_user",
+            work_mem,
+            connect,
+            &CountPostsPerUser,
+        );
+    }
+
+    group.finish();
+}
+
+// ============================================================================
+// Index Comparison Benchmarks
+// ============================================================================
+//
+// The select and join scenarios at their typical size, with the `created_at`,
+// `age` and `status` indexes of `indexes::SECONDARY` in place (`indexed`) and
+// dropped (`unindexed`). A backend whose time grows with the plan's is waiting
+// on the server; one that barely moves is dominated by its own overhead. This
+// drops indexes from the shared database, so it only runs with
+// `BENCH_INDEX_COMPARISON=1`.
+
+/// Whether the index comparison runs
+fn index_comparison() -> bool {
+    std::env::var_os("BENCH_INDEX_COMPARISON").is_some_and(|v| v != "0")
+}
 
-    // sea-orm
-    group.bench_function("sea_orm", |b| {
-        let db = rt.block_on(SeaOrmBench::connect()).unwrap();
-        b.iter(|| {
-            rt.block_on(async {
-                for i in 0..operations {
-                    match i % 4 {
-                        0 => {
-                            let _ = SeaOrmBench::select_users_limit(&db, 100).await;
-                        }
-                        1 => {
-                            let _ = SeaOrmBench::select_users_filtered(&db, 25, 55, 50).await;
-                        }
-                        2 => {
-                            let _ = SeaOrmBench::select_posts_with_user(&db, 50).await;
-                        }
-                        _ => {
-                            let _ = SeaOrmBench::count_posts_per_user(&db).await;
-                        }
-                    }
+/// Whether the secondary indexes are in place for a run
+#[derive(Clone, Copy)]
+enum IndexState {
+    Indexed,
+    Unindexed,
+}
+
+impl Display for IndexState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            IndexState::Indexed => "indexed",
+            IndexState::Unindexed => "unindexed",
+        })
+    }
+}
+
+/// A read-only operation on `B::connect`, returning its mean time per
+/// iteration over every iteration criterion ran, warm-up included
+fn bench_mean<B: DatabaseBenchmark, O: Operation>(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    rt: &Runtime,
+    name: &str,
+    parameter: impl Display,
+    op: &O,
+) -> Option<Duration> {
+    let mut total = Duration::ZERO;
+    let mut iterations = 0usize;
+    guarded(name, || {
+        let conn = rt.block_on(B::connect()).unwrap();
+        group.bench_function(BenchmarkId::new(name, parameter), |b| {
+            b.iter_custom(|iters| {
+                let start = Instant::now();
+                for _ in 0..iters {
+                    iterations += 1;
+                    checked(name, rt.block_on(op.run::<B>(&conn, iterations)));
                 }
-            });
+                let elapsed = start.elapsed();
+                total += elapsed;
+                elapsed
+            })
         });
     });
+    (iterations > 0).then(|| total.div_f64(iterations as f64))
+}
 
-    // diesel
-    group.bench_function("diesel", |b| {
-        let pool = DieselBench::connect().unwrap();
-        let mut conn = pool.get().unwrap();
-        b.iter(|| {
-            for i in 0..operations {
-                match i % 4 {
-                    0 => {
-                        let _ = DieselBench::select_users_limit(&mut conn, 100);
-                    }
-                    1 => {
-                        let _ = DieselBench::select_users_filtered(&mut conn, 25, 55, 50);
-                    }
-                    2 => {
-                        let _ = DieselBench::select_posts_with_user(&mut conn, 50);
-                    }
-                    _ => {
-                        let _ = DieselBench::count_posts_per_user(&mut conn);
-                    }
+fn bench_index_comparison(c: &mut Criterion) {
+    if !tagged(&[Tag::Read, Tag::Join]) {
+        return;
+    }
+    if !index_comparison() {
+        eprintln!("skipping index comparison, BENCH_INDEX_COMPARISON is not set");
+        return;
+    }
+    let rt = create_runtime();
+    let client = rt.block_on(TokioPostgresBench::connect()).unwrap();
+    // An interrupted comparison leaves them dropped
+    rt.block_on(indexes::restore(&client)).unwrap();
+
+    let scenarios = SCENARIOS
+        .iter()
+        .filter(|scenario| matches!(scenario.category, Category::Select | Category::Join));
+    for scenario in scenarios {
+        let group_name = format!("index_{}", scenario.name);
+        let mut group = benchmark_group(c, &group_name, scenario.measurement_time, scenario.sample_size);
+        let fixture = rt.block_on(scenario.setup::<TokioPostgresBench>(&client)).unwrap();
+        let op = ScenarioRun {
+            scenario,
+            fixture: &fixture,
+            size: scenario.typical_size(),
+        };
+
+        let mut means: BTreeMap<&str, Vec<Duration>> = BTreeMap::new();
+        for state in [IndexState::Indexed, IndexState::Unindexed] {
+            if let IndexState::Unindexed = state {
+                rt.block_on(indexes::drop(&client)).unwrap();
+            }
+            for_each_backend!(|B, name| if scenario.runs_on(name) {
+                if let Some(mean) = bench_mean::<B, _>(&mut group, &rt, name, state, &op) {
+                    means.entry(name).or_default().push(mean);
                 }
+            });
+        }
+        rt.block_on(indexes::restore(&client)).unwrap();
+        group.finish();
+        rt.block_on(scenario.teardown::<TokioPostgresBench>(&client)).unwrap();
+
+        for (name, means) in means {
+            if let [indexed, unindexed] = means[..] {
+                eprintln!(
+                    "{group_name} ({name}): {:.3} ms indexed, {:.3} ms unindexed, {:.2}x",
+                    indexed.as_secs_f64() * 1000.0,
+                    unindexed.as_secs_f64() * 1000.0,
+                    unindexed.as_secs_f64() / indexed.as_secs_f64()
+                );
             }
-        });
-    });
+        }
+    }
+}
 
-    // clorinde
-    group.bench_function("clorinde", |b| {
-        let client = rt.block_on(ClorindeBench::connect()).unwrap();
-        b.iter(|| {
-            rt.block_on(async {
-                for i in 0..operations {
-                    match i % 4 {
-                        0 => {
-                            let _ = ClorindeBench::select_users_limit(&client, 100).await;
-                        }
-                        1 => {
-                            let _ =
-                                ClorindeBench::select_users_filtered(&client, 25, 55, 50).await;
-                        }
-                        2 => {
-                            let _ = ClorindeBench::select_posts_with_user(&client, 50).await;
-                        }
-                        _ => {
-                            let _ = ClorindeBench::count_posts_per_user(&client).await;
+// ============================================================================
+// Failover Benchmarks
+// ============================================================================
+
+/// Give up on a backend that hasn't reached the next host by then
+const FAILOVER_DEADLINE: Duration = Duration::from_secs(10);
+
+/// Time from the primary going away until a query succeeds again.
+///
+/// Every iteration connects through a fresh [`TcpProxy`] standing in for the
+/// primary (untimed), shuts it down, then calls `recover` (a query, or a
+/// reconnect after a failed one) until it reports a successful query.
+fn bench_failover<C>(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    rt: &Runtime,
+    name: &str,
+    server: &(String, u16),
+    mut connect: impl FnMut(&str) -> Option<C>,
+    mut recover: impl FnMut(&mut C, &str) -> bool,
+) {
+    guarded(name, || {
+        group.bench_function(name, |b| {
+            b.iter_custom(|iters| {
+                let mut total = Duration::ZERO;
+                for _ in 0..iters {
+                    let proxy = rt.block_on(TcpProxy::start(server.clone())).unwrap();
+                    let url = failover::failover_url(&database_url(), &proxy.address(), server);
+                    let Some(mut conn) = connect(&url) else { continue };
+                    assert!(proxy.connections() > 0, "{name} did not connect through the primary");
+                    rt.block_on(proxy.shutdown());
+
+                    let start = Instant::now();
+                    while !recover(&mut conn, &url) {
+                        if start.elapsed() > FAILOVER_DEADLINE {
+                            record_error(name, format!("no failover within {FAILOVER_DEADLINE:?}"));
+                            break;
                         }
                     }
+                    total += start.elapsed();
                 }
-            });
+                total
+            })
         });
     });
+}
 
-    group.finish();
+/// `TokioPostgresBench::connect_to` without reporting the connection errors every failover causes
+async fn connect_quietly(url: &str) -> Result<tokio_postgres::Client, tokio_postgres::Error> {
+    let (client, connection) = tokio_postgres::connect(url, tokio_postgres::NoTls).await?;
+    tokio::spawn(connection);
+    Ok(client)
 }
 
-fn bench_heavy_write_intensive(c: &mut Criterion) {
+fn bench_failover_detection(c: &mut Criterion) {
+    if !tagged(&[Tag::Connection]) {
+        return;
+    }
     let rt = create_runtime();
-    let mut group = c.benchmark_group("heavy_write_intensive");
-    group.measurement_time(Duration::from_secs(20));
-    group.sample_size(20);
-
-    let batch_size = 50;
+    let Ok(Some(server)) = failover::server_address(&database_url()) else {
+        eprintln!("skipping failover benchmarks, DATABASE_URL does not point at a TCP host");
+        return;
+    };
+    let mut group = benchmark_group(c, "failover", Duration::from_secs(10), 20);
+
+    // clorinde shares tokio-postgres' client; sqlx and sea-orm take a single host
+    for backend in failover::SINGLE_HOST_BACKENDS {
+        eprintln!("skipping {backend} failover, its driver takes a single host");
+    }
 
-    // tokio-postgres
-    group.bench_function("tokio_postgres", |b| {
-        let client = rt.block_on(TokioPostgresBench::connect()).unwrap();
-        let mut counter = 0usize;
-        b.iter(|| {
+    bench_failover(
+        &mut group,
+        &rt,
+        "tokio_postgres",
+        &server,
+        |url| {
+            let client = checked("tokio_postgres", rt.block_on(connect_quietly(url)))?;
+            checked("tokio_postgres", rt.block_on(TokioPostgresBench::ping(&client)))?;
+            Some(client)
+        },
+        |client, url| {
+            if rt.block_on(TokioPostgresBench::ping(client)).is_ok() {
+                return true;
+            }
+            if let Ok(reconnected) = rt.block_on(connect_quietly(url)) {
+                *client = reconnected;
+            }
+            false
+        },
+    );
+    // deadpool discards the closed connection when it is next checked out
+    bench_failover(
+        &mut group,
+        &rt,
+        "tokio_postgres_pooled",
+        &server,
+        |url| {
+            let pool = TokioPostgresBench::create_pool_with_url(url, 1);
+            checked("tokio_postgres_pooled", rt.block_on(TokioPostgresBench::warm_pool(&pool)))?;
+            Some(pool)
+        },
+        |pool, _| {
             rt.block_on(async {
-                for _ in 0..batch_size {
-                    counter += 1;
-                    let user = NewUser::generate(counter);
-                    let user_id = TokioPostgresBench::insert_user(&client, &user).await.unwrap();
-                    
-                    // Insert a post for this user
-                    let post = NewPost::generate(user_id, counter);
-                    TokioPostgresBench::insert_post(&client, &post).await.unwrap();
-                    
-                    // Update the user
-                    TokioPostgresBench::update_user(&client, user_id, "Modified", "Name")
-                        .await
-                        .unwrap();
+                match pool.get().await {
+                    Ok(client) => TokioPostgresBench::ping(&client).await.is_ok(),
+                    Err(_) => false,
                 }
-            });
-        });
-        rt.block_on(TokioPostgresBench::cleanup(&client)).unwrap();
-    });
+            })
+        },
+    );
+    bench_failover(
+        &mut group,
+        &rt,
+        "diesel",
+        &server,
+        |url| {
+            let mut conn = checked("diesel", DieselBench::establish(url))?;
+            checked("diesel", DieselBench::ping(&mut conn))?;
+            Some(conn)
+        },
+        |conn, url| {
+            if DieselBench::ping(conn).is_ok() {
+                return true;
+            }
+            if let Ok(reconnected) = DieselBench::establish(url) {
+                *conn = reconnected;
+            }
+            false
+        },
+    );
+    // r2d2 tests each connection on checkout and replaces the broken one
+    bench_failover(
+        &mut group,
+        &rt,
+        "diesel_pooled",
+        &server,
+        |url| checked("diesel_pooled", DieselBench::connect_with_url(url, 1)),
+        |pool, _| pool.get().is_ok_and(|mut conn| DieselBench::ping(&mut conn).is_ok()),
+    );
 
-    // sqlx
-    group.bench_function("sqlx", |b| {
-        let pool = rt.block_on(SqlxBench::connect()).unwrap();
-        let mut counter = 0usize;
-        b.iter(|| {
-            rt.block_on(async {
-                for _ in 0..batch_size {
-                    counter += 1;
-                    let user = NewUser::generate(counter);
-                    let user_id = SqlxBench::insert_user(&pool, &user).await.unwrap();
-                    
-                    let post = NewPost::generate(user_id, counter);
-                    SqlxBench::insert_post(&pool, &post).await.unwrap();
-                    
-                    SqlxBench::update_user(&pool, user_id, "Modified", "Name")
-                        .await
-                        .unwrap();
-                }
-            });
-        });
-        rt.block_on(SqlxBench::cleanup(&pool)).unwrap();
-    });
+    group.finish();
+}
 
-    // sea-orm
-    group.bench_function("sea_orm", |b| {
-        let db = rt.block_on(SeaOrmBench::connect()).unwrap();
-        let mut counter = 0usize;
-        b.iter(|| {
-            rt.block_on(async {
-                for _ in 0..batch_size {
-                    counter += 1;
-                    let user = NewUser::generate(counter);
-                    let user_id = SeaOrmBench::insert_user(&db, &user).await.unwrap();
-                    
-                    let post = NewPost::generate(user_id, counter);
-                    SeaOrmBench::insert_post(&db, &post).await.unwrap();
-                    
-                    SeaOrmBench::update_user(&db, user_id, "Modified", "Name")
-                        .await
-                        .unwrap();
-                }
-            });
-        });
-        rt.block_on(SeaOrmBench::cleanup(&db)).unwrap();
-    });
+// ============================================================================
+// Read Replica Benchmarks
+// ============================================================================
 
-    // diesel
-    group.bench_function("diesel", |b| {
-        let pool = DieselBench::connect().unwrap();
-        let mut conn = pool.get().unwrap();
-        let mut counter = 0usize;
-        b.iter(|| {
-            for _ in 0..batch_size {
-                counter += 1;
-                let user = NewUser::generate(counter);
-                let user_id = DieselBench::insert_user(&mut conn, &user).unwrap();
-                
-                let post = NewPost::generate(user_id, counter);
-                DieselBench::insert_post(&mut conn, &post).unwrap();
-                
-                DieselBench::update_user(&mut conn, user_id, "Modified", "Name").unwrap();
+/// Users each backend writes while checking how stale its replica reads are
+const FRESHNESS_WRITES: usize = 200;
+/// Count a write as never replicated if the replica still misses it by then
+const FRESHNESS_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// One `heavy_mixed_workload` iteration with reads on the replica when `replica_reads`
+/// is set and everything on the primary otherwise
+async fn run_routed<B: DatabaseBenchmark>(
+    routed: &Routed<B::Connection>,
+    mix: &[WorkloadOp],
+    iteration: usize,
+    replica_reads: bool,
+) -> Result<(), B::Error> {
+    let start = (iteration - 1) * mix.len();
+    for (i, op) in mix.iter().enumerate() {
+        let conn = if replica_reads { routed.route(*op) } else { &routed.primary };
+        match op {
+            WorkloadOp::Write => {
+                B::insert_user(conn, &NewUser::generate(start + i)).await?;
             }
-        });
-        DieselBench::cleanup(&mut conn).unwrap();
+            WorkloadOp::Read => {
+                consume(B::select_users_limit(conn, 50).await?);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The mixed workload on the primary alone and routed across primary and replica,
+/// then a freshness check of the replica reads
+fn register_routed<B: DatabaseBenchmark>(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    rt: &Runtime,
+    name: &str,
+    replica_url: &str,
+    mix: &[WorkloadOp],
+) {
+    guarded(name, || {
+        let routed = rt.block_on(Routed::connect::<B>(replica_url)).unwrap();
+        for (mode, replica_reads) in [("primary_only", false), ("routed", true)] {
+            group.bench_function(BenchmarkId::new(name, mode), |b| {
+                b.iter_custom(|iters| {
+                    checked(name, rt.block_on(B::cleanup(&routed.primary)));
+                    let start = Instant::now();
+                    for iteration in 1..=iters as usize {
+                        checked(name, rt.block_on(run_routed::<B>(&routed, mix, iteration, replica_reads)));
+                    }
+                    start.elapsed()
+                })
+            });
+        }
+
+        checked(name, rt.block_on(B::cleanup(&routed.primary)));
+        let report = rt.block_on(replica::check_freshness::<B>(&routed, FRESHNESS_WRITES, 0, FRESHNESS_TIMEOUT));
+        if let Some(report) = checked(name, report) {
+            eprintln!("read_replica freshness ({name}): {report}");
+        }
+        checked(name, rt.block_on(B::cleanup(&routed.primary)));
+        assert_cleaned_up(rt, name);
     });
+}
 
-    // clorinde
-    group.bench_function("clorinde", |b| {
-        let client = rt.block_on(ClorindeBench::connect()).unwrap();
-        let mut counter = 0usize;
-        b.iter(|| {
-            rt.block_on(async {
-                for _ in 0..batch_size {
-                    counter += 1;
-                    let user = NewUser::generate(counter);
-                    let user_id = ClorindeBench::insert_user(&client, &user).await.unwrap();
-                    
-                    let post = NewPost::generate(user_id, counter);
-                    ClorindeBench::insert_post(&client, &post).await.unwrap();
-                    
-                    ClorindeBench::update_user(&client, user_id, "Modified", "Name")
-                        .await
-                        .unwrap();
+fn bench_read_replica(c: &mut Criterion) {
+    if !tagged(&[Tag::Read, Tag::Write]) {
+        return;
+    }
+    let Some(replica_url) = replica::replica_url() else {
+        eprintln!("skipping read replica benchmarks, REPLICA_DATABASE_URL is not set");
+        return;
+    };
+    let rt = create_runtime();
+    match rt.block_on(async { replica::in_recovery(&TokioPostgresBench::connect_to(&replica_url).await?).await }) {
+        Ok(true) => {}
+        Ok(false) => eprintln!("REPLICA_DATABASE_URL is not a standby, its reads will never be stale"),
+        Err(e) => {
+            eprintln!("skipping read replica benchmarks, can't reach REPLICA_DATABASE_URL: {e}");
+            return;
+        }
+    }
+
+    let mut group = benchmark_group(c, "read_replica", Duration::from_secs(30), 20);
+
+    let config = HeavyWorkloadConfig::default();
+    let mix = config.workload_mix(config.operations_per_connection);
+    group.throughput(Throughput::Elements(mix.len() as u64));
+    for_each_backend!(|B, name| register_routed::<B>(&mut group, &rt, name, &replica_url, &mix));
+
+    group.finish();
+}
+
+// ============================================================================
+// Read-Your-Writes Benchmarks
+// ============================================================================
+//
+// A user inserted on one connection and looked up on another of the same
+// backend until it is returned, as after a write-then-redirect. Only the time
+// from the insert returning to the lookup returning the row is measured.
+
+/// Count a write as never visible if the other connection still misses it by then
+const VISIBILITY_TIMEOUT: Duration = Duration::from_secs(5);
+
+fn register_read_your_writes<B: DatabaseBenchmark>(group: &mut BenchmarkGroup<'_, WallTime>, rt: &Runtime, name: &str) {
+    guarded(name, || {
+        let pair = rt.block_on(Pair::connect::<B>()).unwrap();
+        let mut stale_reads = 0;
+        let mut invisible = 0;
+        group.bench_function(name, |b| {
+            b.iter_custom(|iters| {
+                checked(name, rt.block_on(B::cleanup(&pair.writer)));
+                let mut elapsed = Duration::ZERO;
+                for index in 0..iters as usize {
+                    let visibility = rt.block_on(read_your_writes::write_then_read::<B>(&pair, index, VISIBILITY_TIMEOUT));
+                    if let Some(visibility) = checked(name, visibility) {
+                        elapsed += visibility.latency;
+                        stale_reads += visibility.stale_reads;
+                        invisible += usize::from(!visibility.visible);
+                    }
                 }
-            });
+                elapsed
+            })
         });
-        rt.block_on(ClorindeBench::cleanup(&client)).unwrap();
+        if stale_reads > 0 || invisible > 0 {
+            eprintln!("read_your_writes ({name}): {stale_reads} stale reads, {invisible} writes never visible");
+        }
+
+        checked(name, rt.block_on(B::cleanup(&pair.writer)));
+        assert_cleaned_up(rt, name);
     });
+}
 
+fn bench_read_your_writes(c: &mut Criterion) {
+    if !tagged(&[Tag::Read, Tag::Write]) {
+        return;
+    }
+    let rt = create_runtime();
+    let mut group = benchmark_group(c, "read_your_writes", Duration::from_secs(10), 50);
+    for_each_backend!(|B, name| register_read_your_writes::<B>(&mut group, &rt, name));
     group.finish();
 }
 
 // ============================================================================
-// Concurrent Query Benchmarks (Connection Pooling)
+// Logical Replication Benchmarks
 // ============================================================================
-
-fn bench_concurrent_reads(c: &mut Criterion) {
+//
+// The writing scenarios at their typical size while a logical replication
+// subscriber applies them, with the subscriber's lag sampled throughout each
+// backend's run. The times are the primary's as usual; the lag printed after
+// each run is what a CDC consumer of that backend's writes would see.
+
+/// Wait this long for the subscriber to catch up before the next backend's run
+const CATCH_UP_TIMEOUT: Duration = Duration::from_secs(60);
+
+fn bench_logical_replication(c: &mut Criterion) {
+    if !tagged(&[Tag::Write]) {
+        return;
+    }
+    if logical::subscriber_url().is_none() {
+        eprintln!("skipping logical replication benchmarks, SUBSCRIBER_DATABASE_URL is not set");
+        return;
+    }
     let rt = create_runtime();
-    let mut group = c.benchmark_group("concurrent_reads");
-    group.measurement_time(Duration::from_secs(20));
-    group.sample_size(20);
+    let client = rt.block_on(TokioPostgresBench::connect()).unwrap();
+    match rt.block_on(logical::lag(&client)) {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            eprintln!("skipping logical replication benchmarks, no {} subscriber is streaming", logical::SUBSCRIPTION);
+            return;
+        }
+        Err(e) => {
+            eprintln!("skipping logical replication benchmarks, can't read pg_stat_replication: {e}");
+            return;
+        }
+    }
 
-    // Test with different concurrency levels
-    for concurrency in &[10, 50, 100] {
-        group.throughput(Throughput::Elements(*concurrency as u64));
+    let scenarios = SCENARIOS
+        .iter()
+        .filter(|scenario| matches!(scenario.category, Category::Insert | Category::Update | Category::Transaction));
+    for scenario in scenarios {
+        let group_name = format!("logical_{}", scenario.name);
+        let mut group = benchmark_group(c, &group_name, scenario.measurement_time, scenario.sample_size);
+        let fixture = rt.block_on(scenario.setup::<TokioPostgresBench>(&client)).unwrap();
+        let size = scenario.typical_size();
+        let op = ScenarioRun {
+            scenario,
+            fixture: &fixture,
+            size,
+        };
+
+        for_each_backend!(|B, name| if scenario.runs_on(name) {
+            if !rt.block_on(logical::wait_for_catch_up(&client, CATCH_UP_TIMEOUT)).unwrap() {
+                eprintln!("{group_name} ({name}): subscriber still behind after {CATCH_UP_TIMEOUT:?}");
+            }
+            // Spawns its sampling task, so it starts on the runtime
+            let monitor = rt.block_on(async { logical::LagMonitor::start(TokioPostgresBench::connect().await.unwrap()) });
+            register::<B, _>(&mut group, &rt, name, Some(size), &op);
+            if let Some(report) = checked(name, rt.block_on(monitor.stop())) {
+                eprintln!("{group_name} ({name}): {report}");
+            }
+        });
+        group.finish();
+        rt.block_on(scenario.teardown::<TokioPostgresBench>(&client)).unwrap();
+    }
+}
 
-        // tokio-postgres with deadpool
-        group.bench_with_input(
-            BenchmarkId::new("tokio_postgres_pooled", concurrency),
-            concurrency,
-            |b, &conc| {
-                let pool = TokioPostgresBench::create_pool(conc);
-                b.iter(|| {
-                    rt.block_on(async {
-                        let mut handles = Vec::with_capacity(conc);
-                        for _ in 0..conc {
-                            let pool = pool.clone();
-                            handles.push(tokio::spawn(async move {
-                                TokioPostgresBench::pooled_select_users_limit(&pool, 50).await
-                            }));
-                        }
-                        for handle in handles {
-                            let _ = handle.await;
-                        }
-                    });
-                });
-            },
-        );
+// ============================================================================
+// Large Result Benchmarks
+// ============================================================================
 
-        // sqlx (already pooled)
-        group.bench_with_input(BenchmarkId::new("sqlx", concurrency), concurrency, |b, &conc| {
-            let pool = rt.block_on(SqlxBench::connect_with_pool_size(conc as u32)).unwrap();
-            b.iter(|| {
-                rt.block_on(async {
-                    let mut handles = Vec::with_capacity(conc);
-                    for _ in 0..conc {
-                        let pool = pool.clone();
-                        handles.push(tokio::spawn(async move {
-                            SqlxBench::select_users_limit(&pool, 50).await
-                        }));
-                    }
-                    for handle in handles {
-                        let _ = handle.await;
-                    }
-                });
-            });
-        });
+/// Copies of every post in one result: 100k wide rows over the default seed's 25k posts
+const LARGE_RESULT_COPIES: i32 = 4;
 
-        // sea-orm (uses sqlx pool)
-        group.bench_with_input(BenchmarkId::new("sea_orm", concurrency), concurrency, |b, &conc| {
-            let db = rt.block_on(SeaOrmBench::connect_with_pool_size(conc as u32)).unwrap();
-            b.iter(|| {
-                rt.block_on(async {
-                    let mut handles = Vec::with_capacity(conc);
-                    for _ in 0..conc {
-                        let db = db.clone();
-                        handles.push(tokio::spawn(async move {
-                            SeaOrmBench::select_users_limit(&db, 50).await
-                        }));
-                    }
-                    for handle in handles {
-                        let _ = handle.await;
-                    }
-                });
-            });
-        });
+struct SelectPostsRepeated {
+    copies: i32,
+}
 
-        // diesel with r2d2 (sync - uses thread pool)
-        group.bench_with_input(BenchmarkId::new("diesel", concurrency), concurrency, |b, &conc| {
-            let pool = DieselBench::connect_with_pool_size(conc as u32).unwrap();
-            b.iter(|| {
-                let pool = pool.clone();
-                std::thread::scope(|s| {
-                    for _ in 0..conc {
-                        let pool = pool.clone();
-                        s.spawn(move || {
-                            let mut conn = pool.get().unwrap();
-                            let _ = DieselBench::select_users_limit(&mut conn, 50);
-                        });
-                    }
-                });
-            });
-        });
+impl Operation for SelectPostsRepeated {
+    fn writes(&self) -> bool {
+        false
     }
 
-    group.finish();
+    async fn run<B: DatabaseBenchmark>(&self, conn: &B::Connection, _: usize) -> Result<(), B::Error> {
+        B::select_posts_repeated(conn, self.copies).await.map(consume)
+    }
 }
 
-fn bench_concurrent_mixed(c: &mut Criterion) {
+/// Fetch the large result once outside the timed runs and report how far it
+/// pushed the process's resident set
+fn report_peak_memory<B: DatabaseBenchmark>(rt: &Runtime, name: &str, link: &str, copies: i32) {
+    guarded(name, || {
+        let conn = rt.block_on(B::connect()).unwrap();
+        match memory::measure_peak(|| rt.block_on(B::select_posts_repeated(&conn, copies))) {
+            Ok((rows, peak)) => {
+                if let Some(rows) = checked(name, rows) {
+                    eprintln!(
+                        "large_result peak memory ({name}, {link}): {:.1} MiB for {} rows",
+                        peak as f64 / (1024.0 * 1024.0),
+                        rows.len()
+                    );
+                }
+            }
+            Err(e) => eprintln!("skipping large_result peak memory ({name}): {e}"),
+        }
+    });
+}
+
+fn bench_large_result(c: &mut Criterion) {
+    if !tagged(&[Tag::Read, Tag::Types]) {
+        return;
+    }
     let rt = create_runtime();
-    let mut group = c.benchmark_group("concurrent_mixed_workload");
-    group.measurement_time(Duration::from_secs(30));
-    group.sample_size(15);
+    let posts: i64 = rt.block_on(async {
+        let client = TokioPostgresBench::connect().await.unwrap();
+        client.query_one("SELECT count(*) FROM posts", &[]).await.unwrap().get(0)
+    });
+    let op = SelectPostsRepeated { copies: LARGE_RESULT_COPIES };
 
-    let concurrency = 50;
-    let ops_per_task = 20;
+    let mut group = benchmark_group(c, "large_result", Duration::from_secs(30), 10);
+    // Seconds per iteration behind a slow link; one iteration per sample keeps that bounded
+    group.sampling_mode(SamplingMode::Flat);
+    group.throughput(Throughput::Elements(posts as u64 * LARGE_RESULT_COPIES as u64));
 
-    // tokio-postgres with deadpool
-    group.bench_function("tokio_postgres_pooled", |b| {
-        let pool = TokioPostgresBench::create_pool(concurrency);
-        let counter = std::sync::atomic::AtomicUsize::new(0);
-        b.iter(|| {
-            rt.block_on(async {
-                let mut handles = Vec::with_capacity(concurrency);
-                for _ in 0..concurrency {
-                    let pool = pool.clone();
-                    let cnt = counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-                    handles.push(tokio::spawn(async move {
-                        for i in 0..ops_per_task {
-                            if (cnt + i) % 5 == 0 {
-                                let user = NewUser::generate(cnt * 1000 + i);
-                                let _ = TokioPostgresBench::pooled_insert_user(&pool, &user).await;
-                            } else {
-                                let _ = TokioPostgresBench::pooled_select_users_limit(&pool, 50).await;
-                            }
-                        }
-                    }));
-                }
-                for handle in handles {
-                    let _ = handle.await;
-                }
-            });
-        });
-        rt.block_on(TokioPostgresBench::pooled_cleanup(&pool)).unwrap();
+    let link = "unlimited";
+    for_each_backend!(|B, name| report_peak_memory::<B>(&rt, name, link, op.copies));
+    for_each_backend!(|B, name| {
+        bench_on_connection::<B, _>(&mut group, &rt, name, link, <B as DatabaseBenchmark>::connect(), &op)
     });
 
-    // sqlx
-    group.bench_function("sqlx", |b| {
-        let pool = rt.block_on(SqlxBench::connect_with_pool_size(concurrency as u32)).unwrap();
-        let counter = std::sync::atomic::AtomicUsize::new(0);
-        b.iter(|| {
-            rt.block_on(async {
-                let mut handles = Vec::with_capacity(concurrency);
-                for _ in 0..concurrency {
-                    let pool = pool.clone();
-                    let cnt = counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-                    handles.push(tokio::spawn(async move {
-                        for i in 0..ops_per_task {
-                            if (cnt + i) % 5 == 0 {
-                                let user = NewUser::generate(cnt * 1000 + i);
-                                let _ = SqlxBench::insert_user(&pool, &user).await;
-                            } else {
-                                let _ = SqlxBench::select_users_limit(&pool, 50).await;
-                            }
-                        }
-                    }));
-                }
-                for handle in handles {
-                    let _ = handle.await;
-                }
-            });
-        });
-        rt.block_on(SqlxBench::cleanup(&pool)).unwrap();
+    let Some(bytes_per_sec) = proxy::bandwidth_limit() else {
+        eprintln!("skipping bandwidth-limited large_result, BENCH_BANDWIDTH_MBIT is not set");
+        return group.finish();
+    };
+    let Some(server) = failover::server_address(&database_url()).unwrap() else {
+        eprintln!("skipping bandwidth-limited large_result, DATABASE_URL is not a TCP address");
+        return group.finish();
+    };
+    let proxy = rt.block_on(TcpProxy::start_throttled(server, bytes_per_sec)).unwrap();
+    let url = failover::with_hosts(&database_url(), &[proxy.address()]);
+    let link = format!("{}mbit", bytes_per_sec * 8 / 1_000_000);
+    for_each_backend!(|B, name| {
+        bench_on_connection::<B, _>(&mut group, &rt, name, &link, <B as DatabaseBenchmark>::connect_to(&url), &op)
     });
 
-    // sea-orm
-    group.bench_function("sea_orm", |b| {
-        let db = rt.block_on(SeaOrmBench::connect_with_pool_size(concurrency as u32)).unwrap();
-        let counter = std::sync::atomic::AtomicUsize::new(0);
-        b.iter(|| {
-            rt.block_on(async {
-                let mut handles = Vec::with_capacity(concurrency);
-                for _ in 0..concurrency {
-                    let db = db.clone();
-                    let cnt = counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-                    handles.push(tokio::spawn(async move {
-                        for i in 0..ops_per_task {
-                            if (cnt + i) % 5 == 0 {
-                                let user = NewUser::generate(cnt * 1000 + i);
-                                let _ = SeaOrmBench::insert_user(&db, &user).await;
-                            } else {
-                                let _ = SeaOrmBench::select_users_limit(&db, 50).await;
-                            }
-                        }
-                    }));
+    group.finish();
+    rt.block_on(proxy.shutdown());
+}
+
+// ============================================================================
+// Streaming vs Collecting Benchmarks
+// ============================================================================
+//
+// The large result again, processed one post at a time as the rows arrive
+// (`stream`) and after collecting every post into a `Vec` first (`collect`).
+// The work per post is the same either way, so the times show what holding the
+// whole result costs, and the peak memory printed per backend shows what
+// streaming saves.
+
+/// How the posts of the large result reach the code processing them
+#[derive(Clone, Copy)]
+enum Delivery {
+    Collect,
+    Stream,
+}
+
+impl Display for Delivery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            Delivery::Collect => "collect",
+            Delivery::Stream => "stream",
+        })
+    }
+}
+
+/// Sum the content length of every post of the large result
+struct ProcessPosts {
+    copies: i32,
+    delivery: Delivery,
+}
+
+impl Operation for ProcessPosts {
+    fn writes(&self) -> bool {
+        false
+    }
+
+    async fn run<B: DatabaseBenchmark>(&self, conn: &B::Connection, _: usize) -> Result<(), B::Error> {
+        let mut content_bytes = 0;
+        match self.delivery {
+            Delivery::Collect => {
+                for post in B::select_posts_repeated(conn, self.copies).await? {
+                    content_bytes += post.content.len();
                 }
-                for handle in handles {
-                    let _ = handle.await;
+            }
+            Delivery::Stream => {
+                B::stream_posts_repeated(conn, self.copies, |post| content_bytes += post.content.len()).await?;
+            }
+        }
+        consume(content_bytes);
+        Ok(())
+    }
+}
+
+/// Process the large result once per delivery outside the timed runs and
+/// report how far each pushed the process's resident set
+fn report_delivery_memory<B: DatabaseBenchmark>(rt: &Runtime, name: &str, copies: i32) {
+    guarded(name, || {
+        let conn = rt.block_on(B::connect()).unwrap();
+        let mut peaks = Vec::new();
+        // Streaming first, so its peak can't hide in pages the collected result faulted in
+        for delivery in [Delivery::Stream, Delivery::Collect] {
+            let op = ProcessPosts { copies, delivery };
+            match memory::measure_peak(|| rt.block_on(op.run::<B>(&conn, 1))) {
+                Ok((result, peak)) => {
+                    checked(name, result);
+                    peaks.push(peak as f64 / (1024.0 * 1024.0));
                 }
-            });
-        });
-        rt.block_on(SeaOrmBench::cleanup(&db)).unwrap();
+                Err(e) => return eprintln!("skipping stream_vs_collect peak memory ({name}): {e}"),
+            }
+        }
+        eprintln!(
+            "stream_vs_collect peak memory ({name}): {:.1} MiB stream, {:.1} MiB collect",
+            peaks[0], peaks[1]
+        );
     });
+}
 
-    // diesel with r2d2
-    group.bench_function("diesel", |b| {
-        let pool = DieselBench::connect_with_pool_size(concurrency as u32).unwrap();
-        let counter = std::sync::atomic::AtomicUsize::new(0);
-        b.iter(|| {
-            let pool = pool.clone();
-            std::thread::scope(|s| {
-                for _ in 0..concurrency {
-                    let pool = pool.clone();
-                    let cnt = counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-                    s.spawn(move || {
-                        let mut conn = pool.get().unwrap();
-                        for i in 0..ops_per_task {
-                            if (cnt + i) % 5 == 0 {
-                                let user = NewUser::generate(cnt * 1000 + i);
-                                let _ = DieselBench::insert_user(&mut conn, &user);
-                            } else {
-                                let _ = DieselBench::select_users_limit(&mut conn, 50);
-                            }
-                        }
-                    });
-                }
-            });
-        });
-        let mut conn = pool.get().unwrap();
-        DieselBench::cleanup(&mut conn).unwrap();
+fn bench_stream_vs_collect(c: &mut Criterion) {
+    if !tagged(&[Tag::Read]) {
+        return;
+    }
+    let rt = create_runtime();
+    let posts: i64 = rt.block_on(async {
+        let client = TokioPostgresBench::connect().await.unwrap();
+        client.query_one("SELECT count(*) FROM posts", &[]).await.unwrap().get(0)
+    });
+
+    let mut group = benchmark_group(c, "stream_vs_collect", Duration::from_secs(30), 10);
+    group.sampling_mode(SamplingMode::Flat);
+    group.throughput(Throughput::Elements(posts as u64 * LARGE_RESULT_COPIES as u64));
+
+    for_each_backend!(|B, name| report_delivery_memory::<B>(&rt, name, LARGE_RESULT_COPIES));
+    for_each_backend!(|B, name| {
+        for delivery in [Delivery::Collect, Delivery::Stream] {
+            let op = ProcessPosts {
+                copies: LARGE_RESULT_COPIES,
+                delivery,
+            };
+            bench_on_connection::<B, _>(&mut group, &rt, name, delivery, <B as DatabaseBenchmark>::connect(), &op);
+        }
     });
 
     group.finish();
 }
 
+// ============================================================================
+// Buffer Cache Benchmarks
+// ============================================================================
+//
+// The select and join scenarios with a controlled cache state instead of
+// whatever the groups before them left behind: `warm` after `cache::prewarm`
+// loaded every table and index into shared buffers, and cold runs each timing
+// the first operation on a fresh connection right after the server restarted
+// through `BENCH_COLD_CACHE_COMMAND`. A restart per iteration takes seconds,
+// so the cold runs are a fixed number outside criterion, printed next to the
+// warm mean. This restarts the shared server, so it only runs with the command
+// set.
+
+/// Restarts, and so timed operations, per backend and scenario in the cold state
+const COLD_RUNS: usize = 5;
+
+/// Time `op` on a fresh connection after each of [`COLD_RUNS`] restarts; the
+/// mean of the runs that succeeded
+fn cold_mean<B: DatabaseBenchmark, O: Operation>(rt: &Runtime, name: &str, command: &str, op: &O) -> Option<Duration> {
+    let mut times = Vec::with_capacity(COLD_RUNS);
+    guarded(name, || {
+        for iteration in 1..=COLD_RUNS {
+            cache::clear(command).unwrap();
+            rt.block_on(cache::wait_until_ready(cache::RESTART_TIMEOUT)).unwrap();
+            let conn = rt.block_on(B::connect()).unwrap();
+            let start = Instant::now();
+            let result = rt.block_on(op.run::<B>(&conn, iteration));
+            let elapsed = start.elapsed();
+            if checked(name, result).is_some() {
+                times.push(elapsed);
+            }
+        }
+    });
+    (!times.is_empty()).then(|| times.iter().sum::<Duration>() / times.len() as u32)
+}
+
+fn bench_buffer_cache(c: &mut Criterion) {
+    if !tagged(&[Tag::Read, Tag::Join]) {
+        return;
+    }
+    let Some(command) = cache::cold_command() else {
+        eprintln!("skipping buffer cache comparison, BENCH_COLD_CACHE_COMMAND is not set");
+        return;
+    };
+    let rt = create_runtime();
+
+    let scenarios = SCENARIOS
+        .iter()
+        .filter(|scenario| matches!(scenario.category, Category::Select | Category::Join));
+    for scenario in scenarios {
+        let group_name = format!("cache_{}", scenario.name);
+        let mut group = benchmark_group(c, &group_name, scenario.measurement_time, scenario.sample_size);
+        // The restarts end every earlier connection, so each step opens its own
+        let fixture = rt.block_on(async {
+            let client = TokioPostgresBench::connect().await.unwrap();
+            scenario.setup::<TokioPostgresBench>(&client).await.unwrap()
+        });
+        let op = ScenarioRun {
+            scenario,
+            fixture: &fixture,
+            size: scenario.typical_size(),
+        };
+
+        let mut means: BTreeMap<&str, (Option<Duration>, Option<Duration>)> = BTreeMap::new();
+        for_each_backend!(|B, name| if scenario.runs_on(name) {
+            let cold = cold_mean::<B, _>(&rt, name, &command, &op);
+            let blocks = rt.block_on(async { cache::prewarm(&TokioPostgresBench::connect().await?).await }).unwrap();
+            eprintln!("{group_name} ({name}): prewarmed {blocks} blocks");
+            let warm = bench_mean::<B, _>(&mut group, &rt, name, "warm", &op);
+            means.insert(name, (warm, cold));
+        });
+        group.finish();
+        rt.block_on(async {
+            let client = TokioPostgresBench::connect().await.unwrap();
+            scenario.teardown::<TokioPostgresBench>(&client).await.unwrap()
+        });
+
+        for (name, means) in means {
+            if let (Some(warm), Some(cold)) = means {
+                eprintln!(
+                    "{group_name} ({name}): {:.3} ms warm, {:.3} ms cold (mean of {COLD_RUNS} restarts), {:.2}x",
+                    warm.as_secs_f64() * 1000.0,
+                    cold.as_secs_f64() * 1000.0,
+                    cold.as_secs_f64() / warm.as_secs_f64()
+                );
+            }
+        }
+    }
+}
+
 // ============================================================================
 // Criterion Configuration
 // ============================================================================
 
 criterion_group!(
     benches,
-    // Insert benchmarks
-    bench_insert_single,
-    bench_insert_batch,
-    // Select benchmarks
-    bench_select_by_id,
-    bench_select_limit,
-    bench_select_filtered,
-    // Update benchmarks
-    bench_update_user,
-    // Join benchmarks
-    bench_join_posts_users,
-    bench_join_triple,
-    // Aggregate benchmarks
-    bench_aggregate_count,
-    // Transaction benchmarks
-    bench_transaction_insert,
+    // Insert, select, update, join, aggregate and transaction scenarios
+    bench_scenarios,
     // Heavy workload benchmarks
     bench_heavy_mixed_workload,
     bench_heavy_read_intensive,
     bench_heavy_write_intensive,
+    bench_heavy_write_batched,
+    // Unicode text benchmarks
+    bench_unicode,
+    // Zero- and single-row benchmarks
+    bench_fast_path,
+    // JSON response benchmarks
+    bench_json_response,
+    // Comment pagination benchmarks
+    bench_comment_pagination,
+    // Status filter benchmarks
+    bench_status_filter,
+    // Query builder benchmarks
+    bench_query_build,
+);
+
+// Groups built on backend-specific pools and server behaviour, which the
+// in-memory backend can't stand in for
+criterion_group!(
+    driver_benches,
+    // Calibration benchmarks
+    bench_calibration,
+    // Sync vs async driver benchmarks
+    bench_sync_driver,
     // Concurrent benchmarks
     bench_concurrent_reads,
     bench_concurrent_mixed,
+    // Pool comparison benchmarks
+    bench_pool_comparison,
+    // Web API benchmarks
+    bench_web_api,
+    // Work queue benchmarks
+    bench_work_queue,
+    // Aggregate interference benchmarks
+    bench_aggregate_interference,
+    // Temp table benchmarks
+    bench_temp_table,
+    // Chunked export benchmarks
+    bench_chunked_export,
+    // Batch size sweep benchmarks
+    bench_batch_sweep,
+    // Unique violation benchmarks
+    bench_unique_violation,
+    // Retry benchmarks
+    bench_retry,
+    // Statement invalidation benchmarks
+    bench_statement_invalidation,
+    // Statement timeout and cancellation benchmarks
+    bench_statement_timeout,
+    bench_client_timeout,
+    // Backpressure benchmarks
+    bench_backpressure,
+    // TLS benchmarks
+    bench_tls_connect,
+    bench_tls_query,
+    bench_tls_overhead,
+    // Authentication benchmarks
+    bench_auth_connect,
+    // Connection setup benchmarks
+    bench_connection_setup,
+    // Result format benchmarks
+    bench_result_format,
+    // Row decoding benchmarks
+    bench_row_decode,
+    // Column access benchmarks
+    bench_column_access,
+    // Row allocation benchmarks
+    bench_row_allocation,
+    // sqlx statement cache benchmarks
+    bench_sqlx_statement_cache,
+    // sqlx query style benchmarks
+    bench_sqlx_query_styles,
+    // SeaORM partial model benchmarks
+    bench_sea_orm_partial_model,
+    // Column subset benchmarks
+    bench_column_subset,
+    // Primary key type benchmarks
+    bench_primary_key,
+    // Generated column benchmarks
+    bench_generated_column,
+    // Email lookup benchmarks
+    bench_email_lookup,
+    // Deferred constraint benchmarks
+    bench_deferred_constraints,
+    // Session configuration benchmarks
+    bench_work_mem,
+    // Index comparison benchmarks
+    bench_index_comparison,
+    // Failover benchmarks
+    bench_failover_detection,
+    // Read replica benchmarks
+    bench_read_replica,
+    // Read-your-writes benchmarks
+    bench_read_your_writes,
+    // Logical replication benchmarks
+    bench_logical_replication,
+    // Large result benchmarks
+    bench_large_result,
+    bench_stream_vs_collect,
+    // Buffer cache benchmarks
+    bench_buffer_cache,
 );
 
-criterion_main!(benches);
+/// Record every pool size the benchmarks use and refuse to run if the backends
+/// would not be configured alike
+fn check_connection_parity() {
+    let rt = create_runtime();
+    let mut pool_sizes: Vec<usize> = CONCURRENCY_LEVELS.to_vec();
+    pool_sizes.extend([
+        MIXED_CONCURRENCY,
+        POOL_COMPARISON_SIZE,
+        BACKPRESSURE_POOL_SIZE,
+        CONNECT_POOL_SIZE,
+        WEB_CONNECTIONS,
+        QUEUE_LOAD.connections(),
+    ]);
+    pool_sizes.sort();
+    pool_sizes.dedup();
+
+    let mut errors = 0;
+    for (index, pool_size) in pool_sizes.into_iter().enumerate() {
+        let settings = rt.block_on(parity::pooled_settings(pool_size as u32)).unwrap();
+        for issue in parity::check(&settings, &ParityConfig::with_pool_size(pool_size as u32)) {
+            match issue.severity {
+                Severity::Error => errors += 1,
+                // Library-inherent differences are the same for every pool size
+                Severity::Warning if index > 0 => continue,
+                Severity::Warning => {}
+            }
+            eprintln!("connection parity (pool size {pool_size}): {issue}");
+        }
+    }
+    assert_eq!(errors, 0, "backends are not configured alike, see above");
+}
+
+fn main() {
+    if smoke_test() {
+        benches();
+    } else {
+        check_connection_parity();
+        benches();
+        driver_benches();
+    }
+    Criterion::default().configure_from_args().final_summary();
+    print_error_summary();
+}
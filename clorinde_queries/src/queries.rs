@@ -0,0 +1,11 @@
+// This file was generated with `clorinde`. Do not modify.
+
+pub mod comments;
+pub mod deferred;
+pub mod email_lookup;
+pub mod generated_users;
+pub mod jobs;
+pub mod posts;
+pub mod session;
+pub mod staged_users;
+pub mod users;
@@ -0,0 +1,358 @@
+// This file was generated with `clorinde`. Do not modify.
+
+#[derive(Debug)]
+pub struct InsertCommentParams<T1: crate::StringSql> {
+    pub post_id: uuid::Uuid,
+    pub user_id: uuid::Uuid,
+    pub content: T1,
+}
+#[derive(Clone, Copy, Debug)]
+pub struct SelectCommentsForPostParams {
+    pub post_id: uuid::Uuid,
+    pub limit: i64,
+}
+#[derive(Clone, Copy, Debug)]
+pub struct SelectCommentsForPostBeforeParams {
+    pub post_id: uuid::Uuid,
+    pub before_created_at: chrono::DateTime<chrono::Utc>,
+    pub before_id: uuid::Uuid,
+    pub limit: i64,
+}
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comment {
+    pub id: uuid::Uuid,
+    pub post_id: uuid::Uuid,
+    pub user_id: uuid::Uuid,
+    pub content: String,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+pub struct CommentBorrowed<'a> {
+    pub id: uuid::Uuid,
+    pub post_id: uuid::Uuid,
+    pub user_id: uuid::Uuid,
+    pub content: &'a str,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+impl<'a> From<CommentBorrowed<'a>> for Comment {
+    fn from(
+        CommentBorrowed {
+            id,
+            post_id,
+            user_id,
+            content,
+            created_at,
+        }: CommentBorrowed<'a>,
+    ) -> Self {
+        Self {
+            id,
+            post_id,
+            user_id,
+            content: content.into(),
+            created_at,
+        }
+    }
+}
+use crate::client::async_::GenericClient;
+use futures::{self, StreamExt, TryStreamExt};
+pub struct UuidUuidQuery<'c, 'a, 's, C: GenericClient, T, const N: usize> {
+    client: &'c C,
+    params: [&'a (dyn postgres_types::ToSql + Sync); N],
+    query: &'static str,
+    cached: Option<&'s tokio_postgres::Statement>,
+    extractor: fn(&tokio_postgres::Row) -> Result<uuid::Uuid, tokio_postgres::Error>,
+    mapper: fn(uuid::Uuid) -> T,
+}
+impl<'c, 'a, 's, C, T: 'c, const N: usize> UuidUuidQuery<'c, 'a, 's, C, T, N>
+where
+    C: GenericClient,
+{
+    pub fn map<R>(self, mapper: fn(uuid::Uuid) -> R) -> UuidUuidQuery<'c, 'a, 's, C, R, N> {
+        UuidUuidQuery {
+            client: self.client,
+            params: self.params,
+            query: self.query,
+            cached: self.cached,
+            extractor: self.extractor,
+            mapper,
+        }
+    }
+    pub async fn one(self) -> Result<T, tokio_postgres::Error> {
+        let row =
+            crate::client::async_::one(self.client, self.query, &self.params, self.cached).await?;
+        Ok((self.mapper)((self.extractor)(&row)?))
+    }
+    pub async fn all(self) -> Result<Vec<T>, tokio_postgres::Error> {
+        self.iter().await?.try_collect().await
+    }
+    pub async fn opt(self) -> Result<Option<T>, tokio_postgres::Error> {
+        let opt_row =
+            crate::client::async_::opt(self.client, self.query, &self.params, self.cached).await?;
+        Ok(opt_row
+            .map(|row| {
+                let extracted = (self.extractor)(&row)?;
+                Ok((self.mapper)(extracted))
+            })
+            .transpose()?)
+    }
+    pub async fn iter(
+        self,
+    ) -> Result<
+        impl futures::Stream<Item = Result<T, tokio_postgres::Error>> + 'c,
+        tokio_postgres::Error,
+    > {
+        let stream = crate::client::async_::raw(
+            self.client,
+            self.query,
+            crate::slice_iter(&self.params),
+            self.cached,
+        )
+        .await?;
+        let mapped = stream
+            .map(move |res| {
+                res.and_then(|row| {
+                    let extracted = (self.extractor)(&row)?;
+                    Ok((self.mapper)(extracted))
+                })
+            })
+            .into_stream();
+        Ok(mapped)
+    }
+}
+pub struct CommentQuery<'c, 'a, 's, C: GenericClient, T, const N: usize> {
+    client: &'c C,
+    params: [&'a (dyn postgres_types::ToSql + Sync); N],
+    query: &'static str,
+    cached: Option<&'s tokio_postgres::Statement>,
+    extractor: fn(&tokio_postgres::Row) -> Result<CommentBorrowed, tokio_postgres::Error>,
+    mapper: fn(CommentBorrowed) -> T,
+}
+impl<'c, 'a, 's, C, T: 'c, const N: usize> CommentQuery<'c, 'a, 's, C, T, N>
+where
+    C: GenericClient,
+{
+    pub fn map<R>(self, mapper: fn(CommentBorrowed) -> R) -> CommentQuery<'c, 'a, 's, C, R, N> {
+        CommentQuery {
+            client: self.client,
+            params: self.params,
+            query: self.query,
+            cached: self.cached,
+            extractor: self.extractor,
+            mapper,
+        }
+    }
+    pub async fn one(self) -> Result<T, tokio_postgres::Error> {
+        let row =
+            crate::client::async_::one(self.client, self.query, &self.params, self.cached).await?;
+        Ok((self.mapper)((self.extractor)(&row)?))
+    }
+    pub async fn all(self) -> Result<Vec<T>, tokio_postgres::Error> {
+        self.iter().await?.try_collect().await
+    }
+    pub async fn opt(self) -> Result<Option<T>, tokio_postgres::Error> {
+        let opt_row =
+            crate::client::async_::opt(self.client, self.query, &self.params, self.cached).await?;
+        Ok(opt_row
+            .map(|row| {
+                let extracted = (self.extractor)(&row)?;
+                Ok((self.mapper)(extracted))
+            })
+            .transpose()?)
+    }
+    pub async fn iter(
+        self,
+    ) -> Result<
+        impl futures::Stream<Item = Result<T, tokio_postgres::Error>> + 'c,
+        tokio_postgres::Error,
+    > {
+        let stream = crate::client::async_::raw(
+            self.client,
+            self.query,
+            crate::slice_iter(&self.params),
+            self.cached,
+        )
+        .await?;
+        let mapped = stream
+            .map(move |res| {
+                res.and_then(|row| {
+                    let extracted = (self.extractor)(&row)?;
+                    Ok((self.mapper)(extracted))
+                })
+            })
+            .into_stream();
+        Ok(mapped)
+    }
+}
+pub struct InsertCommentStmt(&'static str, Option<tokio_postgres::Statement>);
+pub fn insert_comment() -> InsertCommentStmt {
+    InsertCommentStmt(
+        "INSERT INTO comments (post_id, user_id, content) VALUES ($1, $2, $3) RETURNING id",
+        None,
+    )
+}
+impl InsertCommentStmt {
+    pub async fn prepare<'a, C: GenericClient>(
+        mut self,
+        client: &'a C,
+    ) -> Result<Self, tokio_postgres::Error> {
+        self.1 = Some(client.prepare(self.0).await?);
+        Ok(self)
+    }
+    pub fn bind<'c, 'a, 's, C: GenericClient, T1: crate::StringSql>(
+        &'s self,
+        client: &'c C,
+        post_id: &'a uuid::Uuid,
+        user_id: &'a uuid::Uuid,
+        content: &'a T1,
+    ) -> UuidUuidQuery<'c, 'a, 's, C, uuid::Uuid, 3> {
+        UuidUuidQuery {
+            client,
+            params: [post_id, user_id, content],
+            query: self.0,
+            cached: self.1.as_ref(),
+            extractor: |row| Ok(row.try_get(0)?),
+            mapper: |it| it,
+        }
+    }
+}
+impl<'c, 'a, 's, C: GenericClient, T1: crate::StringSql>
+    crate::client::async_::Params<
+        'c,
+        'a,
+        's,
+        InsertCommentParams<T1>,
+        UuidUuidQuery<'c, 'a, 's, C, uuid::Uuid, 3>,
+        C,
+    > for InsertCommentStmt
+{
+    fn params(
+        &'s self,
+        client: &'c C,
+        params: &'a InsertCommentParams<T1>,
+    ) -> UuidUuidQuery<'c, 'a, 's, C, uuid::Uuid, 3> {
+        self.bind(client, &params.post_id, &params.user_id, &params.content)
+    }
+}
+pub struct SelectCommentsForPostStmt(&'static str, Option<tokio_postgres::Statement>);
+pub fn select_comments_for_post() -> SelectCommentsForPostStmt {
+    SelectCommentsForPostStmt(
+        "SELECT id, post_id, user_id, content, created_at FROM comments WHERE post_id = $1 ORDER BY created_at DESC, id DESC LIMIT $2",
+        None,
+    )
+}
+impl SelectCommentsForPostStmt {
+    pub async fn prepare<'a, C: GenericClient>(
+        mut self,
+        client: &'a C,
+    ) -> Result<Self, tokio_postgres::Error> {
+        self.1 = Some(client.prepare(self.0).await?);
+        Ok(self)
+    }
+    pub fn bind<'c, 'a, 's, C: GenericClient>(
+        &'s self,
+        client: &'c C,
+        post_id: &'a uuid::Uuid,
+        limit: &'a i64,
+    ) -> CommentQuery<'c, 'a, 's, C, Comment, 2> {
+        CommentQuery {
+            client,
+            params: [post_id, limit],
+            query: self.0,
+            cached: self.1.as_ref(),
+            extractor:
+                |row: &tokio_postgres::Row| -> Result<CommentBorrowed, tokio_postgres::Error> {
+                    Ok(CommentBorrowed {
+                        id: row.try_get(0)?,
+                        post_id: row.try_get(1)?,
+                        user_id: row.try_get(2)?,
+                        content: row.try_get(3)?,
+                        created_at: row.try_get(4)?,
+                    })
+                },
+            mapper: |it| Comment::from(it),
+        }
+    }
+}
+impl<'c, 'a, 's, C: GenericClient>
+    crate::client::async_::Params<
+        'c,
+        'a,
+        's,
+        SelectCommentsForPostParams,
+        CommentQuery<'c, 'a, 's, C, Comment, 2>,
+        C,
+    > for SelectCommentsForPostStmt
+{
+    fn params(
+        &'s self,
+        client: &'c C,
+        params: &'a SelectCommentsForPostParams,
+    ) -> CommentQuery<'c, 'a, 's, C, Comment, 2> {
+        self.bind(client, &params.post_id, &params.limit)
+    }
+}
+pub struct SelectCommentsForPostBeforeStmt(&'static str, Option<tokio_postgres::Statement>);
+pub fn select_comments_for_post_before() -> SelectCommentsForPostBeforeStmt {
+    SelectCommentsForPostBeforeStmt(
+        "SELECT id, post_id, user_id, content, created_at FROM comments WHERE post_id = $1 AND (created_at, id) < ($2, $3) ORDER BY created_at DESC, id DESC LIMIT $4",
+        None,
+    )
+}
+impl SelectCommentsForPostBeforeStmt {
+    pub async fn prepare<'a, C: GenericClient>(
+        mut self,
+        client: &'a C,
+    ) -> Result<Self, tokio_postgres::Error> {
+        self.1 = Some(client.prepare(self.0).await?);
+        Ok(self)
+    }
+    pub fn bind<'c, 'a, 's, C: GenericClient>(
+        &'s self,
+        client: &'c C,
+        post_id: &'a uuid::Uuid,
+        before_created_at: &'a chrono::DateTime<chrono::Utc>,
+        before_id: &'a uuid::Uuid,
+        limit: &'a i64,
+    ) -> CommentQuery<'c, 'a, 's, C, Comment, 4> {
+        CommentQuery {
+            client,
+            params: [post_id, before_created_at, before_id, limit],
+            query: self.0,
+            cached: self.1.as_ref(),
+            extractor:
+                |row: &tokio_postgres::Row| -> Result<CommentBorrowed, tokio_postgres::Error> {
+                    Ok(CommentBorrowed {
+                        id: row.try_get(0)?,
+                        post_id: row.try_get(1)?,
+                        user_id: row.try_get(2)?,
+                        content: row.try_get(3)?,
+                        created_at: row.try_get(4)?,
+                    })
+                },
+            mapper: |it| Comment::from(it),
+        }
+    }
+}
+impl<'c, 'a, 's, C: GenericClient>
+    crate::client::async_::Params<
+        'c,
+        'a,
+        's,
+        SelectCommentsForPostBeforeParams,
+        CommentQuery<'c, 'a, 's, C, Comment, 4>,
+        C,
+    > for SelectCommentsForPostBeforeStmt
+{
+    fn params(
+        &'s self,
+        client: &'c C,
+        params: &'a SelectCommentsForPostBeforeParams,
+    ) -> CommentQuery<'c, 'a, 's, C, Comment, 4> {
+        self.bind(
+            client,
+            &params.post_id,
+            &params.before_created_at,
+            &params.before_id,
+            &params.limit,
+        )
+    }
+}
@@ -0,0 +1,334 @@
+// This file was generated with `clorinde`. Do not modify.
+
+#[derive(Debug)]
+pub struct InsertNamedUserParams<
+    T1: crate::StringSql,
+    T2: crate::StringSql,
+    T3: crate::StringSql,
+    T4: crate::StringSql,
+> {
+    pub username: T1,
+    pub email: T2,
+    pub first_name: T3,
+    pub last_name: T4,
+    pub age: Option<i32>,
+}
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamedUser {
+    pub id: uuid::Uuid,
+    pub username: String,
+    pub first_name: String,
+    pub last_name: String,
+    pub full_name: String,
+}
+pub struct NamedUserBorrowed<'a> {
+    pub id: uuid::Uuid,
+    pub username: &'a str,
+    pub first_name: &'a str,
+    pub last_name: &'a str,
+    pub full_name: &'a str,
+}
+impl<'a> From<NamedUserBorrowed<'a>> for NamedUser {
+    fn from(
+        NamedUserBorrowed {
+            id,
+            username,
+            first_name,
+            last_name,
+            full_name,
+        }: NamedUserBorrowed<'a>,
+    ) -> Self {
+        Self {
+            id,
+            username: username.into(),
+            first_name: first_name.into(),
+            last_name: last_name.into(),
+            full_name: full_name.into(),
+        }
+    }
+}
+use crate::client::async_::GenericClient;
+use futures::{self, StreamExt, TryStreamExt};
+pub struct UuidUuidQuery<'c, 'a, 's, C: GenericClient, T, const N: usize> {
+    client: &'c C,
+    params: [&'a (dyn postgres_types::ToSql + Sync); N],
+    query: &'static str,
+    cached: Option<&'s tokio_postgres::Statement>,
+    extractor: fn(&tokio_postgres::Row) -> Result<uuid::Uuid, tokio_postgres::Error>,
+    mapper: fn(uuid::Uuid) -> T,
+}
+impl<'c, 'a, 's, C, T: 'c, const N: usize> UuidUuidQuery<'c, 'a, 's, C, T, N>
+where
+    C: GenericClient,
+{
+    pub fn map<R>(self, mapper: fn(uuid::Uuid) -> R) -> UuidUuidQuery<'c, 'a, 's, C, R, N> {
+        UuidUuidQuery {
+            client: self.client,
+            params: self.params,
+            query: self.query,
+            cached: self.cached,
+            extractor: self.extractor,
+            mapper,
+        }
+    }
+    pub async fn one(self) -> Result<T, tokio_postgres::Error> {
+        let row =
+            crate::client::async_::one(self.client, self.query, &self.params, self.cached).await?;
+        Ok((self.mapper)((self.extractor)(&row)?))
+    }
+    pub async fn all(self) -> Result<Vec<T>, tokio_postgres::Error> {
+        self.iter().await?.try_collect().await
+    }
+    pub async fn opt(self) -> Result<Option<T>, tokio_postgres::Error> {
+        let opt_row =
+            crate::client::async_::opt(self.client, self.query, &self.params, self.cached).await?;
+        Ok(opt_row
+            .map(|row| {
+                let extracted = (self.extractor)(&row)?;
+                Ok((self.mapper)(extracted))
+            })
+            .transpose()?)
+    }
+    pub async fn iter(
+        self,
+    ) -> Result<
+        impl futures::Stream<Item = Result<T, tokio_postgres::Error>> + 'c,
+        tokio_postgres::Error,
+    > {
+        let stream = crate::client::async_::raw(
+            self.client,
+            self.query,
+            crate::slice_iter(&self.params),
+            self.cached,
+        )
+        .await?;
+        let mapped = stream
+            .map(move |res| {
+                res.and_then(|row| {
+                    let extracted = (self.extractor)(&row)?;
+                    Ok((self.mapper)(extracted))
+                })
+            })
+            .into_stream();
+        Ok(mapped)
+    }
+}
+pub struct NamedUserQuery<'c, 'a, 's, C: GenericClient, T, const N: usize> {
+    client: &'c C,
+    params: [&'a (dyn postgres_types::ToSql + Sync); N],
+    query: &'static str,
+    cached: Option<&'s tokio_postgres::Statement>,
+    extractor: fn(&tokio_postgres::Row) -> Result<NamedUserBorrowed, tokio_postgres::Error>,
+    mapper: fn(NamedUserBorrowed) -> T,
+}
+impl<'c, 'a, 's, C, T: 'c, const N: usize> NamedUserQuery<'c, 'a, 's, C, T, N>
+where
+    C: GenericClient,
+{
+    pub fn map<R>(self, mapper: fn(NamedUserBorrowed) -> R) -> NamedUserQuery<'c, 'a, 's, C, R, N> {
+        NamedUserQuery {
+            client: self.client,
+            params: self.params,
+            query: self.query,
+            cached: self.cached,
+            extractor: self.extractor,
+            mapper,
+        }
+    }
+    pub async fn one(self) -> Result<T, tokio_postgres::Error> {
+        let row =
+            crate::client::async_::one(self.client, self.query, &self.params, self.cached).await?;
+        Ok((self.mapper)((self.extractor)(&row)?))
+    }
+    pub async fn all(self) -> Result<Vec<T>, tokio_postgres::Error> {
+        self.iter().await?.try_collect().await
+    }
+    pub async fn opt(self) -> Result<Option<T>, tokio_postgres::Error> {
+        let opt_row =
+            crate::client::async_::opt(self.client, self.query, &self.params, self.cached).await?;
+        Ok(opt_row
+            .map(|row| {
+                let extracted = (self.extractor)(&row)?;
+                Ok((self.mapper)(extracted))
+            })
+            .transpose()?)
+    }
+    pub async fn iter(
+        self,
+    ) -> Result<
+        impl futures::Stream<Item = Result<T, tokio_postgres::Error>> + 'c,
+        tokio_postgres::Error,
+    > {
+        let stream = crate::client::async_::raw(
+            self.client,
+            self.query,
+            crate::slice_iter(&self.params),
+            self.cached,
+        )
+        .await?;
+        let mapped = stream
+            .map(move |res| {
+                res.and_then(|row| {
+                    let extracted = (self.extractor)(&row)?;
+                    Ok((self.mapper)(extracted))
+                })
+            })
+            .into_stream();
+        Ok(mapped)
+    }
+}
+pub struct InsertNamedUserStmt(&'static str, Option<tokio_postgres::Statement>);
+pub fn insert_named_user() -> InsertNamedUserStmt {
+    InsertNamedUserStmt(
+        "INSERT INTO generated.users (username, email, first_name, last_name, age) VALUES ($1, $2, $3, $4, $5) RETURNING id",
+        None,
+    )
+}
+impl InsertNamedUserStmt {
+    pub async fn prepare<'a, C: GenericClient>(
+        mut self,
+        client: &'a C,
+    ) -> Result<Self, tokio_postgres::Error> {
+        self.1 = Some(client.prepare(self.0).await?);
+        Ok(self)
+    }
+    pub fn bind<
+        'c,
+        'a,
+        's,
+        C: GenericClient,
+        T1: crate::StringSql,
+        T2: crate::StringSql,
+        T3: crate::StringSql,
+        T4: crate::StringSql,
+    >(
+        &'s self,
+        client: &'c C,
+        username: &'a T1,
+        email: &'a T2,
+        first_name: &'a T3,
+        last_name: &'a T4,
+        age: &'a Option<i32>,
+    ) -> UuidUuidQuery<'c, 'a, 's, C, uuid::Uuid, 5> {
+        UuidUuidQuery {
+            client,
+            params: [username, email, first_name, last_name, age],
+            query: self.0,
+            cached: self.1.as_ref(),
+            extractor: |row| Ok(row.try_get(0)?),
+            mapper: |it| it,
+        }
+    }
+}
+impl<
+    'c,
+    'a,
+    's,
+    C: GenericClient,
+    T1: crate::StringSql,
+    T2: crate::StringSql,
+    T3: crate::StringSql,
+    T4: crate::StringSql,
+>
+    crate::client::async_::Params<
+        'c,
+        'a,
+        's,
+        InsertNamedUserParams<T1, T2, T3, T4>,
+        UuidUuidQuery<'c, 'a, 's, C, uuid::Uuid, 5>,
+        C,
+    > for InsertNamedUserStmt
+{
+    fn params(
+        &'s self,
+        client: &'c C,
+        params: &'a InsertNamedUserParams<T1, T2, T3, T4>,
+    ) -> UuidUuidQuery<'c, 'a, 's, C, uuid::Uuid, 5> {
+        self.bind(
+            client,
+            &params.username,
+            &params.email,
+            &params.first_name,
+            &params.last_name,
+            &params.age,
+        )
+    }
+}
+pub struct SelectNamedUsersStmt(&'static str, Option<tokio_postgres::Statement>);
+pub fn select_named_users() -> SelectNamedUsersStmt {
+    SelectNamedUsersStmt(
+        "SELECT id, username, first_name, last_name, full_name FROM generated.users ORDER BY created_at DESC LIMIT $1",
+        None,
+    )
+}
+impl SelectNamedUsersStmt {
+    pub async fn prepare<'a, C: GenericClient>(
+        mut self,
+        client: &'a C,
+    ) -> Result<Self, tokio_postgres::Error> {
+        self.1 = Some(client.prepare(self.0).await?);
+        Ok(self)
+    }
+    pub fn bind<'c, 'a, 's, C: GenericClient>(
+        &'s self,
+        client: &'c C,
+        limit: &'a i64,
+    ) -> NamedUserQuery<'c, 'a, 's, C, NamedUser, 1> {
+        NamedUserQuery {
+            client,
+            params: [limit],
+            query: self.0,
+            cached: self.1.as_ref(),
+            extractor:
+                |row: &tokio_postgres::Row| -> Result<NamedUserBorrowed, tokio_postgres::Error> {
+                    Ok(NamedUserBorrowed {
+                        id: row.try_get(0)?,
+                        username: row.try_get(1)?,
+                        first_name: row.try_get(2)?,
+                        last_name: row.try_get(3)?,
+                        full_name: row.try_get(4)?,
+                    })
+                },
+            mapper: |it| NamedUser::from(it),
+        }
+    }
+}
+pub struct SelectNamedUsersByFullNameStmt(&'static str, Option<tokio_postgres::Statement>);
+pub fn select_named_users_by_full_name() -> SelectNamedUsersByFullNameStmt {
+    SelectNamedUsersByFullNameStmt(
+        "SELECT id, username, first_name, last_name, full_name FROM generated.users WHERE full_name = $1",
+        None,
+    )
+}
+impl SelectNamedUsersByFullNameStmt {
+    pub async fn prepare<'a, C: GenericClient>(
+        mut self,
+        client: &'a C,
+    ) -> Result<Self, tokio_postgres::Error> {
+        self.1 = Some(client.prepare(self.0).await?);
+        Ok(self)
+    }
+    pub fn bind<'c, 'a, 's, C: GenericClient, T1: crate::StringSql>(
+        &'s self,
+        client: &'c C,
+        full_name: &'a T1,
+    ) -> NamedUserQuery<'c, 'a, 's, C, NamedUser, 1> {
+        NamedUserQuery {
+            client,
+            params: [full_name],
+            query: self.0,
+            cached: self.1.as_ref(),
+            extractor:
+                |row: &tokio_postgres::Row| -> Result<NamedUserBorrowed, tokio_postgres::Error> {
+                    Ok(NamedUserBorrowed {
+                        id: row.try_get(0)?,
+                        username: row.try_get(1)?,
+                        first_name: row.try_get(2)?,
+                        last_name: row.try_get(3)?,
+                        full_name: row.try_get(4)?,
+                    })
+                },
+            mapper: |it| NamedUser::from(it),
+        }
+    }
+}
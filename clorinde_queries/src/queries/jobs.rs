@@ -0,0 +1,174 @@
+// This file was generated with `clorinde`. Do not modify.
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClaimedJob {
+    pub id: i64,
+    pub payload: String,
+}
+pub struct ClaimedJobBorrowed<'a> {
+    pub id: i64,
+    pub payload: &'a str,
+}
+impl<'a> From<ClaimedJobBorrowed<'a>> for ClaimedJob {
+    fn from(ClaimedJobBorrowed { id, payload }: ClaimedJobBorrowed<'a>) -> Self {
+        Self {
+            id,
+            payload: payload.into(),
+        }
+    }
+}
+use crate::client::async_::GenericClient;
+use futures::{self, StreamExt, TryStreamExt};
+pub struct ClaimedJobQuery<'c, 'a, 's, C: GenericClient, T, const N: usize> {
+    client: &'c C,
+    params: [&'a (dyn postgres_types::ToSql + Sync); N],
+    query: &'static str,
+    cached: Option<&'s tokio_postgres::Statement>,
+    extractor: fn(&tokio_postgres::Row) -> Result<ClaimedJobBorrowed, tokio_postgres::Error>,
+    mapper: fn(ClaimedJobBorrowed) -> T,
+}
+impl<'c, 'a, 's, C, T: 'c, const N: usize> ClaimedJobQuery<'c, 'a, 's, C, T, N>
+where
+    C: GenericClient,
+{
+    pub fn map<R>(
+        self,
+        mapper: fn(ClaimedJobBorrowed) -> R,
+    ) -> ClaimedJobQuery<'c, 'a, 's, C, R, N> {
+        ClaimedJobQuery {
+            client: self.client,
+            params: self.params,
+            query: self.query,
+            cached: self.cached,
+            extractor: self.extractor,
+            mapper,
+        }
+    }
+    pub async fn one(self) -> Result<T, tokio_postgres::Error> {
+        let row =
+            crate::client::async_::one(self.client, self.query, &self.params, self.cached).await?;
+        Ok((self.mapper)((self.extractor)(&row)?))
+    }
+    pub async fn all(self) -> Result<Vec<T>, tokio_postgres::Error> {
+        self.iter().await?.try_collect().await
+    }
+    pub async fn opt(self) -> Result<Option<T>, tokio_postgres::Error> {
+        let opt_row =
+            crate::client::async_::opt(self.client, self.query, &self.params, self.cached).await?;
+        Ok(opt_row
+            .map(|row| {
+                let extracted = (self.extractor)(&row)?;
+                Ok((self.mapper)(extracted))
+            })
+            .transpose()?)
+    }
+    pub async fn iter(
+        self,
+    ) -> Result<
+        impl futures::Stream<Item = Result<T, tokio_postgres::Error>> + 'c,
+        tokio_postgres::Error,
+    > {
+        let stream = crate::client::async_::raw(
+            self.client,
+            self.query,
+            crate::slice_iter(&self.params),
+            self.cached,
+        )
+        .await?;
+        let mapped = stream
+            .map(move |res| {
+                res.and_then(|row| {
+                    let extracted = (self.extractor)(&row)?;
+                    Ok((self.mapper)(extracted))
+                })
+            })
+            .into_stream();
+        Ok(mapped)
+    }
+}
+pub struct EnqueueJobsStmt(&'static str, Option<tokio_postgres::Statement>);
+pub fn enqueue_jobs() -> EnqueueJobsStmt {
+    EnqueueJobsStmt("INSERT INTO jobs (payload) SELECT unnest($1::text[])", None)
+}
+impl EnqueueJobsStmt {
+    pub async fn prepare<'a, C: GenericClient>(
+        mut self,
+        client: &'a C,
+    ) -> Result<Self, tokio_postgres::Error> {
+        self.1 = Some(client.prepare(self.0).await?);
+        Ok(self)
+    }
+    pub async fn bind<
+        'c,
+        'a,
+        's,
+        C: GenericClient,
+        T1: crate::StringSql,
+        T2: crate::ArraySql<Item = T1>,
+    >(
+        &'s self,
+        client: &'c C,
+        payloads: &'a T2,
+    ) -> Result<u64, tokio_postgres::Error> {
+        client.execute(self.0, &[payloads]).await
+    }
+}
+pub struct ClaimJobsStmt(&'static str, Option<tokio_postgres::Statement>);
+pub fn claim_jobs() -> ClaimJobsStmt {
+    ClaimJobsStmt(
+        "UPDATE jobs SET status = 'running' WHERE id IN ( SELECT id FROM jobs WHERE status = 'pending' ORDER BY id LIMIT $1 FOR UPDATE SKIP LOCKED ) RETURNING id, payload",
+        None,
+    )
+}
+impl ClaimJobsStmt {
+    pub async fn prepare<'a, C: GenericClient>(
+        mut self,
+        client: &'a C,
+    ) -> Result<Self, tokio_postgres::Error> {
+        self.1 = Some(client.prepare(self.0).await?);
+        Ok(self)
+    }
+    pub fn bind<'c, 'a, 's, C: GenericClient>(
+        &'s self,
+        client: &'c C,
+        limit: &'a i64,
+    ) -> ClaimedJobQuery<'c, 'a, 's, C, ClaimedJob, 1> {
+        ClaimedJobQuery {
+            client,
+            params: [limit],
+            query: self.0,
+            cached: self.1.as_ref(),
+            extractor:
+                |row: &tokio_postgres::Row| -> Result<ClaimedJobBorrowed, tokio_postgres::Error> {
+                    Ok(ClaimedJobBorrowed {
+                        id: row.try_get(0)?,
+                        payload: row.try_get(1)?,
+                    })
+                },
+            mapper: |it| ClaimedJob::from(it),
+        }
+    }
+}
+pub struct CompleteJobsStmt(&'static str, Option<tokio_postgres::Statement>);
+pub fn complete_jobs() -> CompleteJobsStmt {
+    CompleteJobsStmt(
+        "UPDATE jobs SET status = 'done', done_at = NOW() WHERE id = ANY($1)",
+        None,
+    )
+}
+impl CompleteJobsStmt {
+    pub async fn prepare<'a, C: GenericClient>(
+        mut self,
+        client: &'a C,
+    ) -> Result<Self, tokio_postgres::Error> {
+        self.1 = Some(client.prepare(self.0).await?);
+        Ok(self)
+    }
+    pub async fn bind<'c, 'a, 's, C: GenericClient, T1: crate::ArraySql<Item = i64>>(
+        &'s self,
+        client: &'c C,
+        ids: &'a T1,
+    ) -> Result<u64, tokio_postgres::Error> {
+        client.execute(self.0, &[ids]).await
+    }
+}
@@ -0,0 +1,1092 @@
+// This file was generated with `clorinde`. Do not modify.
+
+#[derive(Debug)]
+pub struct InsertPostParams<T1: crate::StringSql, T2: crate::StringSql, T3: crate::StringSql> {
+    pub user_id: uuid::Uuid,
+    pub title: T1,
+    pub content: T2,
+    pub status: T3,
+}
+#[derive(Debug)]
+pub struct SelectPostsByStatusParams<T1: crate::StringSql> {
+    pub status: T1,
+    pub limit: i64,
+}
+#[derive(Clone, Copy, Debug)]
+pub struct SelectPostsAfterParams {
+    pub after: uuid::Uuid,
+    pub limit: i64,
+}
+#[derive(Debug, Clone, PartialEq)]
+pub struct PostWithUser {
+    pub post_id: uuid::Uuid,
+    pub user_id: uuid::Uuid,
+    pub title: String,
+    pub content: String,
+    pub status: String,
+    pub view_count: i32,
+    pub post_created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub post_updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub username: String,
+    pub email: String,
+    pub first_name: String,
+    pub last_name: String,
+    pub age: Option<i32>,
+    pub user_created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub user_updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+pub struct PostWithUserBorrowed<'a> {
+    pub post_id: uuid::Uuid,
+    pub user_id: uuid::Uuid,
+    pub title: &'a str,
+    pub content: &'a str,
+    pub status: &'a str,
+    pub view_count: i32,
+    pub post_created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub post_updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub username: &'a str,
+    pub email: &'a str,
+    pub first_name: &'a str,
+    pub last_name: &'a str,
+    pub age: Option<i32>,
+    pub user_created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub user_updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+impl<'a> From<PostWithUserBorrowed<'a>> for PostWithUser {
+    fn from(
+        PostWithUserBorrowed {
+            post_id,
+            user_id,
+            title,
+            content,
+            status,
+            view_count,
+            post_created_at,
+            post_updated_at,
+            username,
+            email,
+            first_name,
+            last_name,
+            age,
+            user_created_at,
+            user_updated_at,
+        }: PostWithUserBorrowed<'a>,
+    ) -> Self {
+        Self {
+            post_id,
+            user_id,
+            title: title.into(),
+            content: content.into(),
+            status: status.into(),
+            view_count,
+            post_created_at,
+            post_updated_at,
+            username: username.into(),
+            email: email.into(),
+            first_name: first_name.into(),
+            last_name: last_name.into(),
+            age,
+            user_created_at,
+            user_updated_at,
+        }
+    }
+}
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserPostComment {
+    pub user_id: uuid::Uuid,
+    pub username: String,
+    pub email: String,
+    pub first_name: String,
+    pub last_name: String,
+    pub age: Option<i32>,
+    pub user_created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub user_updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub post_id: uuid::Uuid,
+    pub title: String,
+    pub content: String,
+    pub status: String,
+    pub view_count: i32,
+    pub post_created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub post_updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub comment_id: uuid::Uuid,
+    pub comment_user_id: uuid::Uuid,
+    pub comment_content: String,
+    pub comment_created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+pub struct UserPostCommentBorrowed<'a> {
+    pub user_id: uuid::Uuid,
+    pub username: &'a str,
+    pub email: &'a str,
+    pub first_name: &'a str,
+    pub last_name: &'a str,
+    pub age: Option<i32>,
+    pub user_created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub user_updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub post_id: uuid::Uuid,
+    pub title: &'a str,
+    pub content: &'a str,
+    pub status: &'a str,
+    pub view_count: i32,
+    pub post_created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub post_updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub comment_id: uuid::Uuid,
+    pub comment_user_id: uuid::Uuid,
+    pub comment_content: &'a str,
+    pub comment_created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+impl<'a> From<UserPostCommentBorrowed<'a>> for UserPostComment {
+    fn from(
+        UserPostCommentBorrowed {
+            user_id,
+            username,
+            email,
+            first_name,
+            last_name,
+            age,
+            user_created_at,
+            user_updated_at,
+            post_id,
+            title,
+            content,
+            status,
+            view_count,
+            post_created_at,
+            post_updated_at,
+            comment_id,
+            comment_user_id,
+            comment_content,
+            comment_created_at,
+        }: UserPostCommentBorrowed<'a>,
+    ) -> Self {
+        Self {
+            user_id,
+            username: username.into(),
+            email: email.into(),
+            first_name: first_name.into(),
+            last_name: last_name.into(),
+            age,
+            user_created_at,
+            user_updated_at,
+            post_id,
+            title: title.into(),
+            content: content.into(),
+            status: status.into(),
+            view_count,
+            post_created_at,
+            post_updated_at,
+            comment_id,
+            comment_user_id,
+            comment_content: comment_content.into(),
+            comment_created_at,
+        }
+    }
+}
+#[derive(Debug, Clone, PartialEq, Copy)]
+pub struct UserPostCount {
+    pub user_id: uuid::Uuid,
+    pub post_count: i64,
+}
+#[derive(Debug, Clone, PartialEq, Copy)]
+pub struct TagPostCount {
+    pub tag_id: uuid::Uuid,
+    pub post_count: i64,
+}
+#[derive(Debug, Clone, PartialEq)]
+pub struct Post {
+    pub id: uuid::Uuid,
+    pub user_id: uuid::Uuid,
+    pub title: String,
+    pub content: String,
+    pub status: String,
+    pub view_count: i32,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+pub struct PostBorrowed<'a> {
+    pub id: uuid::Uuid,
+    pub user_id: uuid::Uuid,
+    pub title: &'a str,
+    pub content: &'a str,
+    pub status: &'a str,
+    pub view_count: i32,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+impl<'a> From<PostBorrowed<'a>> for Post {
+    fn from(
+        PostBorrowed {
+            id,
+            user_id,
+            title,
+            content,
+            status,
+            view_count,
+            created_at,
+            updated_at,
+        }: PostBorrowed<'a>,
+    ) -> Self {
+        Self {
+            id,
+            user_id,
+            title: title.into(),
+            content: content.into(),
+            status: status.into(),
+            view_count,
+            created_at,
+            updated_at,
+        }
+    }
+}
+use crate::client::async_::GenericClient;
+use futures::{self, StreamExt, TryStreamExt};
+pub struct UuidUuidQuery<'c, 'a, 's, C: GenericClient, T, const N: usize> {
+    client: &'c C,
+    params: [&'a (dyn postgres_types::ToSql + Sync); N],
+    query: &'static str,
+    cached: Option<&'s tokio_postgres::Statement>,
+    extractor: fn(&tokio_postgres::Row) -> Result<uuid::Uuid, tokio_postgres::Error>,
+    mapper: fn(uuid::Uuid) -> T,
+}
+impl<'c, 'a, 's, C, T: 'c, const N: usize> UuidUuidQuery<'c, 'a, 's, C, T, N>
+where
+    C: GenericClient,
+{
+    pub fn map<R>(self, mapper: fn(uuid::Uuid) -> R) -> UuidUuidQuery<'c, 'a, 's, C, R, N> {
+        UuidUuidQuery {
+            client: self.client,
+            params: self.params,
+            query: self.query,
+            cached: self.cached,
+            extractor: self.extractor,
+            mapper,
+        }
+    }
+    pub async fn one(self) -> Result<T, tokio_postgres::Error> {
+        let row =
+            crate::client::async_::one(self.client, self.query, &self.params, self.cached).await?;
+        Ok((self.mapper)((self.extractor)(&row)?))
+    }
+    pub async fn all(self) -> Result<Vec<T>, tokio_postgres::Error> {
+        self.iter().await?.try_collect().await
+    }
+    pub async fn opt(self) -> Result<Option<T>, tokio_postgres::Error> {
+        let opt_row =
+            crate::client::async_::opt(self.client, self.query, &self.params, self.cached).await?;
+        Ok(opt_row
+            .map(|row| {
+                let extracted = (self.extractor)(&row)?;
+                Ok((self.mapper)(extracted))
+            })
+            .transpose()?)
+    }
+    pub async fn iter(
+        self,
+    ) -> Result<
+        impl futures::Stream<Item = Result<T, tokio_postgres::Error>> + 'c,
+        tokio_postgres::Error,
+    > {
+        let stream = crate::client::async_::raw(
+            self.client,
+            self.query,
+            crate::slice_iter(&self.params),
+            self.cached,
+        )
+        .await?;
+        let mapped = stream
+            .map(move |res| {
+                res.and_then(|row| {
+                    let extracted = (self.extractor)(&row)?;
+                    Ok((self.mapper)(extracted))
+                })
+            })
+            .into_stream();
+        Ok(mapped)
+    }
+}
+pub struct PostWithUserQuery<'c, 'a, 's, C: GenericClient, T, const N: usize> {
+    client: &'c C,
+    params: [&'a (dyn postgres_types::ToSql + Sync); N],
+    query: &'static str,
+    cached: Option<&'s tokio_postgres::Statement>,
+    extractor: fn(&tokio_postgres::Row) -> Result<PostWithUserBorrowed, tokio_postgres::Error>,
+    mapper: fn(PostWithUserBorrowed) -> T,
+}
+impl<'c, 'a, 's, C, T: 'c, const N: usize> PostWithUserQuery<'c, 'a, 's, C, T, N>
+where
+    C: GenericClient,
+{
+    pub fn map<R>(
+        self,
+        mapper: fn(PostWithUserBorrowed) -> R,
+    ) -> PostWithUserQuery<'c, 'a, 's, C, R, N> {
+        PostWithUserQuery {
+            client: self.client,
+            params: self.params,
+            query: self.query,
+            cached: self.cached,
+            extractor: self.extractor,
+            mapper,
+        }
+    }
+    pub async fn one(self) -> Result<T, tokio_postgres::Error> {
+        let row =
+            crate::client::async_::one(self.client, self.query, &self.params, self.cached).await?;
+        Ok((self.mapper)((self.extractor)(&row)?))
+    }
+    pub async fn all(self) -> Result<Vec<T>, tokio_postgres::Error> {
+        self.iter().await?.try_collect().await
+    }
+    pub async fn opt(self) -> Result<Option<T>, tokio_postgres::Error> {
+        let opt_row =
+            crate::client::async_::opt(self.client, self.query, &self.params, self.cached).await?;
+        Ok(opt_row
+            .map(|row| {
+                let extracted = (self.extractor)(&row)?;
+                Ok((self.mapper)(extracted))
+            })
+            .transpose()?)
+    }
+    pub async fn iter(
+        self,
+    ) -> Result<
+        impl futures::Stream<Item = Result<T, tokio_postgres::Error>> + 'c,
+        tokio_postgres::Error,
+    > {
+        let stream = crate::client::async_::raw(
+            self.client,
+            self.query,
+            crate::slice_iter(&self.params),
+            self.cached,
+        )
+        .await?;
+        let mapped = stream
+            .map(move |res| {
+                res.and_then(|row| {
+                    let extracted = (self.extractor)(&row)?;
+                    Ok((self.mapper)(extracted))
+                })
+            })
+            .into_stream();
+        Ok(mapped)
+    }
+}
+pub struct UserPostCommentQuery<'c, 'a, 's, C: GenericClient, T, const N: usize> {
+    client: &'c C,
+    params: [&'a (dyn postgres_types::ToSql + Sync); N],
+    query: &'static str,
+    cached: Option<&'s tokio_postgres::Statement>,
+    extractor: fn(&tokio_postgres::Row) -> Result<UserPostCommentBorrowed, tokio_postgres::Error>,
+    mapper: fn(UserPostCommentBorrowed) -> T,
+}
+impl<'c, 'a, 's, C, T: 'c, const N: usize> UserPostCommentQuery<'c, 'a, 's, C, T, N>
+where
+    C: GenericClient,
+{
+    pub fn map<R>(
+        self,
+        mapper: fn(UserPostCommentBorrowed) -> R,
+    ) -> UserPostCommentQuery<'c, 'a, 's, C, R, N> {
+        UserPostCommentQuery {
+            client: self.client,
+            params: self.params,
+            query: self.query,
+            cached: self.cached,
+            extractor: self.extractor,
+            mapper,
+        }
+    }
+    pub async fn one(self) -> Result<T, tokio_postgres::Error> {
+        let row =
+            crate::client::async_::one(self.client, self.query, &self.params, self.cached).await?;
+        Ok((self.mapper)((self.extractor)(&row)?))
+    }
+    pub async fn all(self) -> Result<Vec<T>, tokio_postgres::Error> {
+        self.iter().await?.try_collect().await
+    }
+    pub async fn opt(self) -> Result<Option<T>, tokio_postgres::Error> {
+        let opt_row =
+            crate::client::async_::opt(self.client, self.query, &self.params, self.cached).await?;
+        Ok(opt_row
+            .map(|row| {
+                let extracted = (self.extractor)(&row)?;
+                Ok((self.mapper)(extracted))
+            })
+            .transpose()?)
+    }
+    pub async fn iter(
+        self,
+    ) -> Result<
+        impl futures::Stream<Item = Result<T, tokio_postgres::Error>> + 'c,
+        tokio_postgres::Error,
+    > {
+        let stream = crate::client::async_::raw(
+            self.client,
+            self.query,
+            crate::slice_iter(&self.params),
+            self.cached,
+        )
+        .await?;
+        let mapped = stream
+            .map(move |res| {
+                res.and_then(|row| {
+                    let extracted = (self.extractor)(&row)?;
+                    Ok((self.mapper)(extracted))
+                })
+            })
+            .into_stream();
+        Ok(mapped)
+    }
+}
+pub struct UserPostCountQuery<'c, 'a, 's, C: GenericClient, T, const N: usize> {
+    client: &'c C,
+    params: [&'a (dyn postgres_types::ToSql + Sync); N],
+    query: &'static str,
+    cached: Option<&'s tokio_postgres::Statement>,
+    extractor: fn(&tokio_postgres::Row) -> Result<UserPostCount, tokio_postgres::Error>,
+    mapper: fn(UserPostCount) -> T,
+}
+impl<'c, 'a, 's, C, T: 'c, const N: usize> UserPostCountQuery<'c, 'a, 's, C, T, N>
+where
+    C: GenericClient,
+{
+    pub fn map<R>(self, mapper: fn(UserPostCount) -> R) -> UserPostCountQuery<'c, 'a, 's, C, R, N> {
+        UserPostCountQuery {
+            client: self.client,
+            params: self.params,
+            query: self.query,
+            cached: self.cached,
+            extractor: self.extractor,
+            mapper,
+        }
+    }
+    pub async fn one(self) -> Result<T, tokio_postgres::Error> {
+        let row =
+            crate::client::async_::one(self.client, self.query, &self.params, self.cached).await?;
+        Ok((self.mapper)((self.extractor)(&row)?))
+    }
+    pub async fn all(self) -> Result<Vec<T>, tokio_postgres::Error> {
+        self.iter().await?.try_collect().await
+    }
+    pub async fn opt(self) -> Result<Option<T>, tokio_postgres::Error> {
+        let opt_row =
+            crate::client::async_::opt(self.client, self.query, &self.params, self.cached).await?;
+        Ok(opt_row
+            .map(|row| {
+                let extracted = (self.extractor)(&row)?;
+                Ok((self.mapper)(extracted))
+            })
+            .transpose()?)
+    }
+    pub async fn iter(
+        self,
+    ) -> Result<
+        impl futures::Stream<Item = Result<T, tokio_postgres::Error>> + 'c,
+        tokio_postgres::Error,
+    > {
+        let stream = crate::client::async_::raw(
+            self.client,
+            self.query,
+            crate::slice_iter(&self.params),
+            self.cached,
+        )
+        .await?;
+        let mapped = stream
+            .map(move |res| {
+                res.and_then(|row| {
+                    let extracted = (self.extractor)(&row)?;
+                    Ok((self.mapper)(extracted))
+                })
+            })
+            .into_stream();
+        Ok(mapped)
+    }
+}
+pub struct TagPostCountQuery<'c, 'a, 's, C: GenericClient, T, const N: usize> {
+    client: &'c C,
+    params: [&'a (dyn postgres_types::ToSql + Sync); N],
+    query: &'static str,
+    cached: Option<&'s tokio_postgres::Statement>,
+    extractor: fn(&tokio_postgres::Row) -> Result<TagPostCount, tokio_postgres::Error>,
+    mapper: fn(TagPostCount) -> T,
+}
+impl<'c, 'a, 's, C, T: 'c, const N: usize> TagPostCountQuery<'c, 'a, 's, C, T, N>
+where
+    C: GenericClient,
+{
+    pub fn map<R>(self, mapper: fn(TagPostCount) -> R) -> TagPostCountQuery<'c, 'a, 's, C, R, N> {
+        TagPostCountQuery {
+            client: self.client,
+            params: self.params,
+            query: self.query,
+            cached: self.cached,
+            extractor: self.extractor,
+            mapper,
+        }
+    }
+    pub async fn one(self) -> Result<T, tokio_postgres::Error> {
+        let row =
+            crate::client::async_::one(self.client, self.query, &self.params, self.cached).await?;
+        Ok((self.mapper)((self.extractor)(&row)?))
+    }
+    pub async fn all(self) -> Result<Vec<T>, tokio_postgres::Error> {
+        self.iter().await?.try_collect().await
+    }
+    pub async fn opt(self) -> Result<Option<T>, tokio_postgres::Error> {
+        let opt_row =
+            crate::client::async_::opt(self.client, self.query, &self.params, self.cached).await?;
+        Ok(opt_row
+            .map(|row| {
+                let extracted = (self.extractor)(&row)?;
+                Ok((self.mapper)(extracted))
+            })
+            .transpose()?)
+    }
+    pub async fn iter(
+        self,
+    ) -> Result<
+        impl futures::Stream<Item = Result<T, tokio_postgres::Error>> + 'c,
+        tokio_postgres::Error,
+    > {
+        let stream = crate::client::async_::raw(
+            self.client,
+            self.query,
+            crate::slice_iter(&self.params),
+            self.cached,
+        )
+        .await?;
+        let mapped = stream
+            .map(move |res| {
+                res.and_then(|row| {
+                    let extracted = (self.extractor)(&row)?;
+                    Ok((self.mapper)(extracted))
+                })
+            })
+            .into_stream();
+        Ok(mapped)
+    }
+}
+pub struct PostQuery<'c, 'a, 's, C: GenericClient, T, const N: usize> {
+    client: &'c C,
+    params: [&'a (dyn postgres_types::ToSql + Sync); N],
+    query: &'static str,
+    cached: Option<&'s tokio_postgres::Statement>,
+    extractor: fn(&tokio_postgres::Row) -> Result<PostBorrowed, tokio_postgres::Error>,
+    mapper: fn(PostBorrowed) -> T,
+}
+impl<'c, 'a, 's, C, T: 'c, const N: usize> PostQuery<'c, 'a, 's, C, T, N>
+where
+    C: GenericClient,
+{
+    pub fn map<R>(self, mapper: fn(PostBorrowed) -> R) -> PostQuery<'c, 'a, 's, C, R, N> {
+        PostQuery {
+            client: self.client,
+            params: self.params,
+            query: self.query,
+            cached: self.cached,
+            extractor: self.extractor,
+            mapper,
+        }
+    }
+    pub async fn one(self) -> Result<T, tokio_postgres::Error> {
+        let row =
+            crate::client::async_::one(self.client, self.query, &self.params, self.cached).await?;
+        Ok((self.mapper)((self.extractor)(&row)?))
+    }
+    pub async fn all(self) -> Result<Vec<T>, tokio_postgres::Error> {
+        self.iter().await?.try_collect().await
+    }
+    pub async fn opt(self) -> Result<Option<T>, tokio_postgres::Error> {
+        let opt_row =
+            crate::client::async_::opt(self.client, self.query, &self.params, self.cached).await?;
+        Ok(opt_row
+            .map(|row| {
+                let extracted = (self.extractor)(&row)?;
+                Ok((self.mapper)(extracted))
+            })
+            .transpose()?)
+    }
+    pub async fn iter(
+        self,
+    ) -> Result<
+        impl futures::Stream<Item = Result<T, tokio_postgres::Error>> + 'c,
+        tokio_postgres::Error,
+    > {
+        let stream = crate::client::async_::raw(
+            self.client,
+            self.query,
+            crate::slice_iter(&self.params),
+            self.cached,
+        )
+        .await?;
+        let mapped = stream
+            .map(move |res| {
+                res.and_then(|row| {
+                    let extracted = (self.extractor)(&row)?;
+                    Ok((self.mapper)(extracted))
+                })
+            })
+            .into_stream();
+        Ok(mapped)
+    }
+}
+pub struct InsertPostStmt(&'static str, Option<tokio_postgres::Statement>);
+pub fn insert_post() -> InsertPostStmt {
+    InsertPostStmt(
+        "INSERT INTO posts (user_id, title, content, status) VALUES ($1, $2, $3, $4) RETURNING id",
+        None,
+    )
+}
+impl InsertPostStmt {
+    pub async fn prepare<'a, C: GenericClient>(
+        mut self,
+        client: &'a C,
+    ) -> Result<Self, tokio_postgres::Error> {
+        self.1 = Some(client.prepare(self.0).await?);
+        Ok(self)
+    }
+    pub fn bind<
+        'c,
+        'a,
+        's,
+        C: GenericClient,
+        T1: crate::StringSql,
+        T2: crate::StringSql,
+        T3: crate::StringSql,
+    >(
+        &'s self,
+        client: &'c C,
+        user_id: &'a uuid::Uuid,
+        title: &'a T1,
+        content: &'a T2,
+        status: &'a T3,
+    ) -> UuidUuidQuery<'c, 'a, 's, C, uuid::Uuid, 4> {
+        UuidUuidQuery {
+            client,
+            params: [user_id, title, content, status],
+            query: self.0,
+            cached: self.1.as_ref(),
+            extractor: |row| Ok(row.try_get(0)?),
+            mapper: |it| it,
+        }
+    }
+}
+impl<'c, 'a, 's, C: GenericClient, T1: crate::StringSql, T2: crate::StringSql, T3: crate::StringSql>
+    crate::client::async_::Params<
+        'c,
+        'a,
+        's,
+        InsertPostParams<T1, T2, T3>,
+        UuidUuidQuery<'c, 'a, 's, C, uuid::Uuid, 4>,
+        C,
+    > for InsertPostStmt
+{
+    fn params(
+        &'s self,
+        client: &'c C,
+        params: &'a InsertPostParams<T1, T2, T3>,
+    ) -> UuidUuidQuery<'c, 'a, 's, C, uuid::Uuid, 4> {
+        self.bind(
+            client,
+            &params.user_id,
+            &params.title,
+            &params.content,
+            &params.status,
+        )
+    }
+}
+pub struct SelectPostsWithUserStmt(&'static str, Option<tokio_postgres::Statement>);
+pub fn select_posts_with_user() -> SelectPostsWithUserStmt {
+    SelectPostsWithUserStmt(
+        "SELECT p.id AS post_id, p.user_id, p.title, p.content, p.status, p.view_count, p.created_at AS post_created_at, p.updated_at AS post_updated_at, u.username, u.email, u.first_name, u.last_name, u.age, u.created_at AS user_created_at, u.updated_at AS user_updated_at FROM posts p JOIN users u ON p.user_id = u.id ORDER BY p.created_at DESC LIMIT $1",
+        None,
+    )
+}
+impl SelectPostsWithUserStmt {
+    pub async fn prepare<'a, C: GenericClient>(
+        mut self,
+        client: &'a C,
+    ) -> Result<Self, tokio_postgres::Error> {
+        self.1 = Some(client.prepare(self.0).await?);
+        Ok(self)
+    }
+    pub fn bind<'c, 'a, 's, C: GenericClient>(
+        &'s self,
+        client: &'c C,
+        limit: &'a i64,
+    ) -> PostWithUserQuery<'c, 'a, 's, C, PostWithUser, 1> {
+        PostWithUserQuery {
+            client,
+            params: [limit],
+            query: self.0,
+            cached: self.1.as_ref(),
+            extractor:
+                |row: &tokio_postgres::Row| -> Result<PostWithUserBorrowed, tokio_postgres::Error> {
+                    Ok(PostWithUserBorrowed {
+                        post_id: row.try_get(0)?,
+                        user_id: row.try_get(1)?,
+                        title: row.try_get(2)?,
+                        content: row.try_get(3)?,
+                        status: row.try_get(4)?,
+                        view_count: row.try_get(5)?,
+                        post_created_at: row.try_get(6)?,
+                        post_updated_at: row.try_get(7)?,
+                        username: row.try_get(8)?,
+                        email: row.try_get(9)?,
+                        first_name: row.try_get(10)?,
+                        last_name: row.try_get(11)?,
+                        age: row.try_get(12)?,
+                        user_created_at: row.try_get(13)?,
+                        user_updated_at: row.try_get(14)?,
+                    })
+                },
+            mapper: |it| PostWithUser::from(it),
+        }
+    }
+}
+pub struct SelectUsersPostsCommentsStmt(&'static str, Option<tokio_postgres::Statement>);
+pub fn select_users_posts_comments() -> SelectUsersPostsCommentsStmt {
+    SelectUsersPostsCommentsStmt(
+        "SELECT u.id AS user_id, u.username, u.email, u.first_name, u.last_name, u.age, u.created_at AS user_created_at, u.updated_at AS user_updated_at, p.id AS post_id, p.title, p.content, p.status, p.view_count, p.created_at AS post_created_at, p.updated_at AS post_updated_at, c.id AS comment_id, c.user_id AS comment_user_id, c.content AS comment_content, c.created_at AS comment_created_at FROM users u JOIN posts p ON u.id = p.user_id JOIN comments c ON p.id = c.post_id ORDER BY u.created_at DESC, p.created_at DESC, c.created_at DESC LIMIT $1",
+        None,
+    )
+}
+impl SelectUsersPostsCommentsStmt {
+    pub async fn prepare<'a, C: GenericClient>(
+        mut self,
+        client: &'a C,
+    ) -> Result<Self, tokio_postgres::Error> {
+        self.1 = Some(client.prepare(self.0).await?);
+        Ok(self)
+    }
+    pub fn bind<'c, 'a, 's, C: GenericClient>(
+        &'s self,
+        client: &'c C,
+        limit: &'a i64,
+    ) -> UserPostCommentQuery<'c, 'a, 's, C, UserPostComment, 1> {
+        UserPostCommentQuery {
+            client,
+            params: [limit],
+            query: self.0,
+            cached: self.1.as_ref(),
+            extractor: |
+                row: &tokio_postgres::Row,
+            | -> Result<UserPostCommentBorrowed, tokio_postgres::Error> {
+                Ok(UserPostCommentBorrowed {
+                    user_id: row.try_get(0)?,
+                    username: row.try_get(1)?,
+                    email: row.try_get(2)?,
+                    first_name: row.try_get(3)?,
+                    last_name: row.try_get(4)?,
+                    age: row.try_get(5)?,
+                    user_created_at: row.try_get(6)?,
+                    user_updated_at: row.try_get(7)?,
+                    post_id: row.try_get(8)?,
+                    title: row.try_get(9)?,
+                    content: row.try_get(10)?,
+                    status: row.try_get(11)?,
+                    view_count: row.try_get(12)?,
+                    post_created_at: row.try_get(13)?,
+                    post_updated_at: row.try_get(14)?,
+                    comment_id: row.try_get(15)?,
+                    comment_user_id: row.try_get(16)?,
+                    comment_content: row.try_get(17)?,
+                    comment_created_at: row.try_get(18)?,
+                })
+            },
+            mapper: |it| UserPostComment::from(it),
+        }
+    }
+}
+pub struct CountPostsPerUserStmt(&'static str, Option<tokio_postgres::Statement>);
+pub fn count_posts_per_user() -> CountPostsPerUserStmt {
+    CountPostsPerUserStmt(
+        "SELECT u.id AS user_id, COUNT(p.id) AS post_count FROM users u LEFT JOIN posts p ON u.id = p.user_id GROUP BY u.id ORDER BY post_count DESC",
+        None,
+    )
+}
+impl CountPostsPerUserStmt {
+    pub async fn prepare<'a, C: GenericClient>(
+        mut self,
+        client: &'a C,
+    ) -> Result<Self, tokio_postgres::Error> {
+        self.1 = Some(client.prepare(self.0).await?);
+        Ok(self)
+    }
+    pub fn bind<'c, 'a, 's, C: GenericClient>(
+        &'s self,
+        client: &'c C,
+    ) -> UserPostCountQuery<'c, 'a, 's, C, UserPostCount, 0> {
+        UserPostCountQuery {
+            client,
+            params: [],
+            query: self.0,
+            cached: self.1.as_ref(),
+            extractor: |row: &tokio_postgres::Row| -> Result<UserPostCount, tokio_postgres::Error> {
+                Ok(UserPostCount {
+                    user_id: row.try_get(0)?,
+                    post_count: row.try_get(1)?,
+                })
+            },
+            mapper: |it| UserPostCount::from(it),
+        }
+    }
+}
+pub struct CountPostsPerTagStmt(&'static str, Option<tokio_postgres::Statement>);
+pub fn count_posts_per_tag() -> CountPostsPerTagStmt {
+    CountPostsPerTagStmt(
+        "SELECT t.id AS tag_id, COUNT(pt.post_id) AS post_count FROM tags t JOIN post_tags pt ON t.id = pt.tag_id GROUP BY t.id ORDER BY post_count DESC, t.id",
+        None,
+    )
+}
+impl CountPostsPerTagStmt {
+    pub async fn prepare<'a, C: GenericClient>(
+        mut self,
+        client: &'a C,
+    ) -> Result<Self, tokio_postgres::Error> {
+        self.1 = Some(client.prepare(self.0).await?);
+        Ok(self)
+    }
+    pub fn bind<'c, 'a, 's, C: GenericClient>(
+        &'s self,
+        client: &'c C,
+    ) -> TagPostCountQuery<'c, 'a, 's, C, TagPostCount, 0> {
+        TagPostCountQuery {
+            client,
+            params: [],
+            query: self.0,
+            cached: self.1.as_ref(),
+            extractor: |row: &tokio_postgres::Row| -> Result<TagPostCount, tokio_postgres::Error> {
+                Ok(TagPostCount {
+                    tag_id: row.try_get(0)?,
+                    post_count: row.try_get(1)?,
+                })
+            },
+            mapper: |it| TagPostCount::from(it),
+        }
+    }
+}
+pub struct SelectPostsByStatusStmt(&'static str, Option<tokio_postgres::Statement>);
+pub fn select_posts_by_status() -> SelectPostsByStatusStmt {
+    SelectPostsByStatusStmt(
+        "SELECT id, user_id, title, content, status, view_count, created_at, updated_at FROM posts WHERE status = $1 ORDER BY created_at DESC LIMIT $2",
+        None,
+    )
+}
+impl SelectPostsByStatusStmt {
+    pub async fn prepare<'a, C: GenericClient>(
+        mut self,
+        client: &'a C,
+    ) -> Result<Self, tokio_postgres::Error> {
+        self.1 = Some(client.prepare(self.0).await?);
+        Ok(self)
+    }
+    pub fn bind<'c, 'a, 's, C: GenericClient, T1: crate::StringSql>(
+        &'s self,
+        client: &'c C,
+        status: &'a T1,
+        limit: &'a i64,
+    ) -> PostQuery<'c, 'a, 's, C, Post, 2> {
+        PostQuery {
+            client,
+            params: [status, limit],
+            query: self.0,
+            cached: self.1.as_ref(),
+            extractor: |row: &tokio_postgres::Row| -> Result<PostBorrowed, tokio_postgres::Error> {
+                Ok(PostBorrowed {
+                    id: row.try_get(0)?,
+                    user_id: row.try_get(1)?,
+                    title: row.try_get(2)?,
+                    content: row.try_get(3)?,
+                    status: row.try_get(4)?,
+                    view_count: row.try_get(5)?,
+                    created_at: row.try_get(6)?,
+                    updated_at: row.try_get(7)?,
+                })
+            },
+            mapper: |it| Post::from(it),
+        }
+    }
+}
+impl<'c, 'a, 's, C: GenericClient, T1: crate::StringSql>
+    crate::client::async_::Params<
+        'c,
+        'a,
+        's,
+        SelectPostsByStatusParams<T1>,
+        PostQuery<'c, 'a, 's, C, Post, 2>,
+        C,
+    > for SelectPostsByStatusStmt
+{
+    fn params(
+        &'s self,
+        client: &'c C,
+        params: &'a SelectPostsByStatusParams<T1>,
+    ) -> PostQuery<'c, 'a, 's, C, Post, 2> {
+        self.bind(client, &params.status, &params.limit)
+    }
+}
+pub struct SelectPostsRepeatedStmt(&'static str, Option<tokio_postgres::Statement>);
+pub fn select_posts_repeated() -> SelectPostsRepeatedStmt {
+    SelectPostsRepeatedStmt(
+        "SELECT p.id, p.user_id, p.title, p.content, p.status, p.view_count, p.created_at, p.updated_at FROM posts p CROSS JOIN generate_series(1, $1)",
+        None,
+    )
+}
+impl SelectPostsRepeatedStmt {
+    pub async fn prepare<'a, C: GenericClient>(
+        mut self,
+        client: &'a C,
+    ) -> Result<Self, tokio_postgres::Error> {
+        self.1 = Some(client.prepare(self.0).await?);
+        Ok(self)
+    }
+    pub fn bind<'c, 'a, 's, C: GenericClient>(
+        &'s self,
+        client: &'c C,
+        copies: &'a i32,
+    ) -> PostQuery<'c, 'a, 's, C, Post, 1> {
+        PostQuery {
+            client,
+            params: [copies],
+            query: self.0,
+            cached: self.1.as_ref(),
+            extractor: |row: &tokio_postgres::Row| -> Result<PostBorrowed, tokio_postgres::Error> {
+                Ok(PostBorrowed {
+                    id: row.try_get(0)?,
+                    user_id: row.try_get(1)?,
+                    title: row.try_get(2)?,
+                    content: row.try_get(3)?,
+                    status: row.try_get(4)?,
+                    view_count: row.try_get(5)?,
+                    created_at: row.try_get(6)?,
+                    updated_at: row.try_get(7)?,
+                })
+            },
+            mapper: |it| Post::from(it),
+        }
+    }
+}
+pub struct SelectPostsAfterStmt(&'static str, Option<tokio_postgres::Statement>);
+pub fn select_posts_after() -> SelectPostsAfterStmt {
+    SelectPostsAfterStmt(
+        "SELECT id, user_id, title, content, status, view_count, created_at, updated_at FROM posts WHERE id > $1 ORDER BY id LIMIT $2",
+        None,
+    )
+}
+impl SelectPostsAfterStmt {
+    pub async fn prepare<'a, C: GenericClient>(
+        mut self,
+        client: &'a C,
+    ) -> Result<Self, tokio_postgres::Error> {
+        self.1 = Some(client.prepare(self.0).await?);
+        Ok(self)
+    }
+    pub fn bind<'c, 'a, 's, C: GenericClient>(
+        &'s self,
+        client: &'c C,
+        after: &'a uuid::Uuid,
+        limit: &'a i64,
+    ) -> PostQuery<'c, 'a, 's, C, Post, 2> {
+        PostQuery {
+            client,
+            params: [after, limit],
+            query: self.0,
+            cached: self.1.as_ref(),
+            extractor: |row: &tokio_postgres::Row| -> Result<PostBorrowed, tokio_postgres::Error> {
+                Ok(PostBorrowed {
+                    id: row.try_get(0)?,
+                    user_id: row.try_get(1)?,
+                    title: row.try_get(2)?,
+                    content: row.try_get(3)?,
+                    status: row.try_get(4)?,
+                    view_count: row.try_get(5)?,
+                    created_at: row.try_get(6)?,
+                    updated_at: row.try_get(7)?,
+                })
+            },
+            mapper: |it| Post::from(it),
+        }
+    }
+}
+impl<'c, 'a, 's, C: GenericClient>
+    crate::client::async_::Params<
+        'c,
+        'a,
+        's,
+        SelectPostsAfterParams,
+        PostQuery<'c, 'a, 's, C, Post, 2>,
+        C,
+    > for SelectPostsAfterStmt
+{
+    fn params(
+        &'s self,
+        client: &'c C,
+        params: &'a SelectPostsAfterParams,
+    ) -> PostQuery<'c, 'a, 's, C, Post, 2> {
+        self.bind(client, &params.after, &params.limit)
+    }
+}
+pub struct IncrementViewCountStmt(&'static str, Option<tokio_postgres::Statement>);
+pub fn increment_view_count() -> IncrementViewCountStmt {
+    IncrementViewCountStmt(
+        "UPDATE posts SET view_count = view_count + 1 WHERE id = $1",
+        None,
+    )
+}
+impl IncrementViewCountStmt {
+    pub async fn prepare<'a, C: GenericClient>(
+        mut self,
+        client: &'a C,
+    ) -> Result<Self, tokio_postgres::Error> {
+        self.1 = Some(client.prepare(self.0).await?);
+        Ok(self)
+    }
+    pub async fn bind<'c, 'a, 's, C: GenericClient>(
+        &'s self,
+        client: &'c C,
+        post_id: &'a uuid::Uuid,
+    ) -> Result<u64, tokio_postgres::Error> {
+        client.execute(self.0, &[post_id]).await
+    }
+}
+pub struct DeclarePostsExportStmt(&'static str, Option<tokio_postgres::Statement>);
+pub fn declare_posts_export() -> DeclarePostsExportStmt {
+    DeclarePostsExportStmt(
+        "DECLARE posts_export CURSOR WITH HOLD FOR SELECT id, user_id, title, content, status, view_count, created_at, updated_at FROM posts ORDER BY id",
+        None,
+    )
+}
+impl DeclarePostsExportStmt {
+    pub async fn prepare<'a, C: GenericClient>(
+        mut self,
+        client: &'a C,
+    ) -> Result<Self, tokio_postgres::Error> {
+        self.1 = Some(client.prepare(self.0).await?);
+        Ok(self)
+    }
+    pub async fn bind<'c, 'a, 's, C: GenericClient>(
+        &'s self,
+        client: &'c C,
+    ) -> Result<u64, tokio_postgres::Error> {
+        client.execute(self.0, &[]).await
+    }
+}
+pub struct ClosePostsExportStmt(&'static str, Option<tokio_postgres::Statement>);
+pub fn close_posts_export() -> ClosePostsExportStmt {
+    ClosePostsExportStmt("CLOSE posts_export", None)
+}
+impl ClosePostsExportStmt {
+    pub async fn prepare<'a, C: GenericClient>(
+        mut self,
+        client: &'a C,
+    ) -> Result<Self, tokio_postgres::Error> {
+        self.1 = Some(client.prepare(self.0).await?);
+        Ok(self)
+    }
+    pub async fn bind<'c, 'a, 's, C: GenericClient>(
+        &'s self,
+        client: &'c C,
+    ) -> Result<u64, tokio_postgres::Error> {
+        client.execute(self.0, &[]).await
+    }
+}
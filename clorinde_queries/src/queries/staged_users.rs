@@ -0,0 +1,220 @@
+// This file was generated with `clorinde`. Do not modify.
+
+#[derive(Debug)]
+pub struct LoadStagedUsersParams<
+    T1: crate::ArraySql<Item = uuid::Uuid>,
+    T2: crate::ArraySql<Item = i32>,
+> {
+    pub user_ids: T1,
+    pub scores: T2,
+}
+#[derive(Debug, Clone, PartialEq, Copy)]
+pub struct StagedUserPosts {
+    pub user_id: uuid::Uuid,
+    pub score: i32,
+    pub post_count: i64,
+}
+use crate::client::async_::GenericClient;
+use futures::{self, StreamExt, TryStreamExt};
+pub struct StagedUserPostsQuery<'c, 'a, 's, C: GenericClient, T, const N: usize> {
+    client: &'c C,
+    params: [&'a (dyn postgres_types::ToSql + Sync); N],
+    query: &'static str,
+    cached: Option<&'s tokio_postgres::Statement>,
+    extractor: fn(&tokio_postgres::Row) -> Result<StagedUserPosts, tokio_postgres::Error>,
+    mapper: fn(StagedUserPosts) -> T,
+}
+impl<'c, 'a, 's, C, T: 'c, const N: usize> StagedUserPostsQuery<'c, 'a, 's, C, T, N>
+where
+    C: GenericClient,
+{
+    pub fn map<R>(
+        self,
+        mapper: fn(StagedUserPosts) -> R,
+    ) -> StagedUserPostsQuery<'c, 'a, 's, C, R, N> {
+        StagedUserPostsQuery {
+            client: self.client,
+            params: self.params,
+            query: self.query,
+            cached: self.cached,
+            extractor: self.extractor,
+            mapper,
+        }
+    }
+    pub async fn one(self) -> Result<T, tokio_postgres::Error> {
+        let row =
+            crate::client::async_::one(self.client, self.query, &self.params, self.cached).await?;
+        Ok((self.mapper)((self.extractor)(&row)?))
+    }
+    pub async fn all(self) -> Result<Vec<T>, tokio_postgres::Error> {
+        self.iter().await?.try_collect().await
+    }
+    pub async fn opt(self) -> Result<Option<T>, tokio_postgres::Error> {
+        let opt_row =
+            crate::client::async_::opt(self.client, self.query, &self.params, self.cached).await?;
+        Ok(opt_row
+            .map(|row| {
+                let extracted = (self.extractor)(&row)?;
+                Ok((self.mapper)(extracted))
+            })
+            .transpose()?)
+    }
+    pub async fn iter(
+        self,
+    ) -> Result<
+        impl futures::Stream<Item = Result<T, tokio_postgres::Error>> + 'c,
+        tokio_postgres::Error,
+    > {
+        let stream = crate::client::async_::raw(
+            self.client,
+            self.query,
+            crate::slice_iter(&self.params),
+            self.cached,
+        )
+        .await?;
+        let mapped = stream
+            .map(move |res| {
+                res.and_then(|row| {
+                    let extracted = (self.extractor)(&row)?;
+                    Ok((self.mapper)(extracted))
+                })
+            })
+            .into_stream();
+        Ok(mapped)
+    }
+}
+pub struct CreateStagedUsersStmt(&'static str, Option<tokio_postgres::Statement>);
+pub fn create_staged_users() -> CreateStagedUsersStmt {
+    CreateStagedUsersStmt(
+        "CREATE TEMP TABLE staged_users (user_id UUID PRIMARY KEY, score INTEGER NOT NULL)",
+        None,
+    )
+}
+impl CreateStagedUsersStmt {
+    pub async fn prepare<'a, C: GenericClient>(
+        mut self,
+        client: &'a C,
+    ) -> Result<Self, tokio_postgres::Error> {
+        self.1 = Some(client.prepare(self.0).await?);
+        Ok(self)
+    }
+    pub async fn bind<'c, 'a, 's, C: GenericClient>(
+        &'s self,
+        client: &'c C,
+    ) -> Result<u64, tokio_postgres::Error> {
+        client.execute(self.0, &[]).await
+    }
+}
+pub struct LoadStagedUsersStmt(&'static str, Option<tokio_postgres::Statement>);
+pub fn load_staged_users() -> LoadStagedUsersStmt {
+    LoadStagedUsersStmt(
+        "INSERT INTO staged_users (user_id, score) SELECT * FROM unnest($1::uuid[], $2::int4[])",
+        None,
+    )
+}
+impl LoadStagedUsersStmt {
+    pub async fn prepare<'a, C: GenericClient>(
+        mut self,
+        client: &'a C,
+    ) -> Result<Self, tokio_postgres::Error> {
+        self.1 = Some(client.prepare(self.0).await?);
+        Ok(self)
+    }
+    pub async fn bind<
+        'c,
+        'a,
+        's,
+        C: GenericClient,
+        T1: crate::ArraySql<Item = uuid::Uuid>,
+        T2: crate::ArraySql<Item = i32>,
+    >(
+        &'s self,
+        client: &'c C,
+        user_ids: &'a T1,
+        scores: &'a T2,
+    ) -> Result<u64, tokio_postgres::Error> {
+        client.execute(self.0, &[user_ids, scores]).await
+    }
+}
+impl<
+    'a,
+    C: GenericClient + Send + Sync,
+    T1: crate::ArraySql<Item = uuid::Uuid>,
+    T2: crate::ArraySql<Item = i32>,
+>
+    crate::client::async_::Params<
+        'a,
+        'a,
+        'a,
+        LoadStagedUsersParams<T1, T2>,
+        std::pin::Pin<
+            Box<dyn futures::Future<Output = Result<u64, tokio_postgres::Error>> + Send + 'a>,
+        >,
+        C,
+    > for LoadStagedUsersStmt
+{
+    fn params(
+        &'a self,
+        client: &'a C,
+        params: &'a LoadStagedUsersParams<T1, T2>,
+    ) -> std::pin::Pin<
+        Box<dyn futures::Future<Output = Result<u64, tokio_postgres::Error>> + Send + 'a>,
+    > {
+        Box::pin(self.bind(client, &params.user_ids, &params.scores))
+    }
+}
+pub struct StagedUserPostsStmt(&'static str, Option<tokio_postgres::Statement>);
+pub fn staged_user_posts() -> StagedUserPostsStmt {
+    StagedUserPostsStmt(
+        "SELECT s.user_id, s.score, COUNT(p.id) AS post_count FROM staged_users s JOIN posts p ON p.user_id = s.user_id GROUP BY s.user_id, s.score ORDER BY s.user_id",
+        None,
+    )
+}
+impl StagedUserPostsStmt {
+    pub async fn prepare<'a, C: GenericClient>(
+        mut self,
+        client: &'a C,
+    ) -> Result<Self, tokio_postgres::Error> {
+        self.1 = Some(client.prepare(self.0).await?);
+        Ok(self)
+    }
+    pub fn bind<'c, 'a, 's, C: GenericClient>(
+        &'s self,
+        client: &'c C,
+    ) -> StagedUserPostsQuery<'c, 'a, 's, C, StagedUserPosts, 0> {
+        StagedUserPostsQuery {
+            client,
+            params: [],
+            query: self.0,
+            cached: self.1.as_ref(),
+            extractor:
+                |row: &tokio_postgres::Row| -> Result<StagedUserPosts, tokio_postgres::Error> {
+                    Ok(StagedUserPosts {
+                        user_id: row.try_get(0)?,
+                        score: row.try_get(1)?,
+                        post_count: row.try_get(2)?,
+                    })
+                },
+            mapper: |it| StagedUserPosts::from(it),
+        }
+    }
+}
+pub struct DropStagedUsersStmt(&'static str, Option<tokio_postgres::Statement>);
+pub fn drop_staged_users() -> DropStagedUsersStmt {
+    DropStagedUsersStmt("DROP TABLE staged_users", None)
+}
+impl DropStagedUsersStmt {
+    pub async fn prepare<'a, C: GenericClient>(
+        mut self,
+        client: &'a C,
+    ) -> Result<Self, tokio_postgres::Error> {
+        self.1 = Some(client.prepare(self.0).await?);
+        Ok(self)
+    }
+    pub async fn bind<'c, 'a, 's, C: GenericClient>(
+        &'s self,
+        client: &'c C,
+    ) -> Result<u64, tokio_postgres::Error> {
+        client.execute(self.0, &[]).await
+    }
+}
@@ -0,0 +1,763 @@
+// This file was generated with `clorinde`. Do not modify.
+
+#[derive(Debug)]
+pub struct InsertUserParams<
+    T1: crate::StringSql,
+    T2: crate::StringSql,
+    T3: crate::StringSql,
+    T4: crate::StringSql,
+> {
+    pub username: T1,
+    pub email: T2,
+    pub first_name: T3,
+    pub last_name: T4,
+    pub age: Option<i32>,
+}
+#[derive(Clone, Copy, Debug)]
+pub struct SelectUsersFilteredParams {
+    pub min_age: i32,
+    pub max_age: i32,
+    pub limit: i64,
+}
+#[derive(Debug)]
+pub struct UpdateUserParams<T1: crate::StringSql, T2: crate::StringSql> {
+    pub first_name: T1,
+    pub last_name: T2,
+    pub id: uuid::Uuid,
+}
+#[derive(Debug)]
+pub struct SearchUsersByNameParams<T1: crate::StringSql> {
+    pub pattern: T1,
+    pub limit: i64,
+}
+#[derive(Debug)]
+pub struct InsertUsersUnnestParams<
+    T1: crate::StringSql,
+    T2: crate::ArraySql<Item = T1>,
+    T3: crate::StringSql,
+    T4: crate::ArraySql<Item = T3>,
+    T5: crate::StringSql,
+    T6: crate::ArraySql<Item = T5>,
+    T7: crate::StringSql,
+    T8: crate::ArraySql<Item = T7>,
+    T9: crate::ArraySql<Item = Option<i32>>,
+> {
+    pub usernames: T2,
+    pub emails: T4,
+    pub first_names: T6,
+    pub last_names: T8,
+    pub ages: T9,
+}
+#[derive(Debug)]
+pub struct InsertUserUnlessTakenParams<
+    T1: crate::StringSql,
+    T2: crate::StringSql,
+    T3: crate::StringSql,
+    T4: crate::StringSql,
+> {
+    pub username: T1,
+    pub email: T2,
+    pub first_name: T3,
+    pub last_name: T4,
+    pub age: Option<i32>,
+}
+#[derive(Debug, Clone, PartialEq)]
+pub struct User {
+    pub id: uuid::Uuid,
+    pub username: String,
+    pub email: String,
+    pub first_name: String,
+    pub last_name: String,
+    pub age: Option<i32>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+pub struct UserBorrowed<'a> {
+    pub id: uuid::Uuid,
+    pub username: &'a str,
+    pub email: &'a str,
+    pub first_name: &'a str,
+    pub last_name: &'a str,
+    pub age: Option<i32>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+impl<'a> From<UserBorrowed<'a>> for User {
+    fn from(
+        UserBorrowed {
+            id,
+            username,
+            email,
+            first_name,
+            last_name,
+            age,
+            created_at,
+            updated_at,
+        }: UserBorrowed<'a>,
+    ) -> Self {
+        Self {
+            id,
+            username: username.into(),
+            email: email.into(),
+            first_name: first_name.into(),
+            last_name: last_name.into(),
+            age,
+            created_at,
+            updated_at,
+        }
+    }
+}
+use crate::client::async_::GenericClient;
+use futures::{self, StreamExt, TryStreamExt};
+pub struct UuidUuidQuery<'c, 'a, 's, C: GenericClient, T, const N: usize> {
+    client: &'c C,
+    params: [&'a (dyn postgres_types::ToSql + Sync); N],
+    query: &'static str,
+    cached: Option<&'s tokio_postgres::Statement>,
+    extractor: fn(&tokio_postgres::Row) -> Result<uuid::Uuid, tokio_postgres::Error>,
+    mapper: fn(uuid::Uuid) -> T,
+}
+impl<'c, 'a, 's, C, T: 'c, const N: usize> UuidUuidQuery<'c, 'a, 's, C, T, N>
+where
+    C: GenericClient,
+{
+    pub fn map<R>(self, mapper: fn(uuid::Uuid) -> R) -> UuidUuidQuery<'c, 'a, 's, C, R, N> {
+        UuidUuidQuery {
+            client: self.client,
+            params: self.params,
+            query: self.query,
+            cached: self.cached,
+            extractor: self.extractor,
+            mapper,
+        }
+    }
+    pub async fn one(self) -> Result<T, tokio_postgres::Error> {
+        let row =
+            crate::client::async_::one(self.client, self.query, &self.params, self.cached).await?;
+        Ok((self.mapper)((self.extractor)(&row)?))
+    }
+    pub async fn all(self) -> Result<Vec<T>, tokio_postgres::Error> {
+        self.iter().await?.try_collect().await
+    }
+    pub async fn opt(self) -> Result<Option<T>, tokio_postgres::Error> {
+        let opt_row =
+            crate::client::async_::opt(self.client, self.query, &self.params, self.cached).await?;
+        Ok(opt_row
+            .map(|row| {
+                let extracted = (self.extractor)(&row)?;
+                Ok((self.mapper)(extracted))
+            })
+            .transpose()?)
+    }
+    pub async fn iter(
+        self,
+    ) -> Result<
+        impl futures::Stream<Item = Result<T, tokio_postgres::Error>> + 'c,
+        tokio_postgres::Error,
+    > {
+        let stream = crate::client::async_::raw(
+            self.client,
+            self.query,
+            crate::slice_iter(&self.params),
+            self.cached,
+        )
+        .await?;
+        let mapped = stream
+            .map(move |res| {
+                res.and_then(|row| {
+                    let extracted = (self.extractor)(&row)?;
+                    Ok((self.mapper)(extracted))
+                })
+            })
+            .into_stream();
+        Ok(mapped)
+    }
+}
+pub struct UserQuery<'c, 'a, 's, C: GenericClient, T, const N: usize> {
+    client: &'c C,
+    params: [&'a (dyn postgres_types::ToSql + Sync); N],
+    query: &'static str,
+    cached: Option<&'s tokio_postgres::Statement>,
+    extractor: fn(&tokio_postgres::Row) -> Result<UserBorrowed, tokio_postgres::Error>,
+    mapper: fn(UserBorrowed) -> T,
+}
+impl<'c, 'a, 's, C, T: 'c, const N: usize> UserQuery<'c, 'a, 's, C, T, N>
+where
+    C: GenericClient,
+{
+    pub fn map<R>(self, mapper: fn(UserBorrowed) -> R) -> UserQuery<'c, 'a, 's, C, R, N> {
+        UserQuery {
+            client: self.client,
+            params: self.params,
+            query: self.query,
+            cached: self.cached,
+            extractor: self.extractor,
+            mapper,
+        }
+    }
+    pub async fn one(self) -> Result<T, tokio_postgres::Error> {
+        let row =
+            crate::client::async_::one(self.client, self.query, &self.params, self.cached).await?;
+        Ok((self.mapper)((self.extractor)(&row)?))
+    }
+    pub async fn all(self) -> Result<Vec<T>, tokio_postgres::Error> {
+        self.iter().await?.try_collect().await
+    }
+    pub async fn opt(self) -> Result<Option<T>, tokio_postgres::Error> {
+        let opt_row =
+            crate::client::async_::opt(self.client, self.query, &self.params, self.cached).await?;
+        Ok(opt_row
+            .map(|row| {
+                let extracted = (self.extractor)(&row)?;
+                Ok((self.mapper)(extracted))
+            })
+            .transpose()?)
+    }
+    pub async fn iter(
+        self,
+    ) -> Result<
+        impl futures::Stream<Item = Result<T, tokio_postgres::Error>> + 'c,
+        tokio_postgres::Error,
+    > {
+        let stream = crate::client::async_::raw(
+            self.client,
+            self.query,
+            crate::slice_iter(&self.params),
+            self.cached,
+        )
+        .await?;
+        let mapped = stream
+            .map(move |res| {
+                res.and_then(|row| {
+                    let extracted = (self.extractor)(&row)?;
+                    Ok((self.mapper)(extracted))
+                })
+            })
+            .into_stream();
+        Ok(mapped)
+    }
+}
+pub struct InsertUserStmt(&'static str, Option<tokio_postgres::Statement>);
+pub fn insert_user() -> InsertUserStmt {
+    InsertUserStmt(
+        "INSERT INTO users (username, email, first_name, last_name, age) VALUES ($1, $2, $3, $4, $5) RETURNING id",
+        None,
+    )
+}
+impl InsertUserStmt {
+    pub async fn prepare<'a, C: GenericClient>(
+        mut self,
+        client: &'a C,
+    ) -> Result<Self, tokio_postgres::Error> {
+        self.1 = Some(client.prepare(self.0).await?);
+        Ok(self)
+    }
+    pub fn bind<
+        'c,
+        'a,
+        's,
+        C: GenericClient,
+        T1: crate::StringSql,
+        T2: crate::StringSql,
+        T3: crate::StringSql,
+        T4: crate::StringSql,
+    >(
+        &'s self,
+        client: &'c C,
+        username: &'a T1,
+        email: &'a T2,
+        first_name: &'a T3,
+        last_name: &'a T4,
+        age: &'a Option<i32>,
+    ) -> UuidUuidQuery<'c, 'a, 's, C, uuid::Uuid, 5> {
+        UuidUuidQuery {
+            client,
+            params: [username, email, first_name, last_name, age],
+            query: self.0,
+            cached: self.1.as_ref(),
+            extractor: |row| Ok(row.try_get(0)?),
+            mapper: |it| it,
+        }
+    }
+}
+impl<
+    'c,
+    'a,
+    's,
+    C: GenericClient,
+    T1: crate::StringSql,
+    T2: crate::StringSql,
+    T3: crate::StringSql,
+    T4: crate::StringSql,
+>
+    crate::client::async_::Params<
+        'c,
+        'a,
+        's,
+        InsertUserParams<T1, T2, T3, T4>,
+        UuidUuidQuery<'c, 'a, 's, C, uuid::Uuid, 5>,
+        C,
+    > for InsertUserStmt
+{
+    fn params(
+        &'s self,
+        client: &'c C,
+        params: &'a InsertUserParams<T1, T2, T3, T4>,
+    ) -> UuidUuidQuery<'c, 'a, 's, C, uuid::Uuid, 5> {
+        self.bind(
+            client,
+            &params.username,
+            &params.email,
+            &params.first_name,
+            &params.last_name,
+            &params.age,
+        )
+    }
+}
+pub struct SelectUserByIdStmt(&'static str, Option<tokio_postgres::Statement>);
+pub fn select_user_by_id() -> SelectUserByIdStmt {
+    SelectUserByIdStmt(
+        "SELECT id, username, email, first_name, last_name, age, created_at, updated_at FROM users WHERE id = $1",
+        None,
+    )
+}
+impl SelectUserByIdStmt {
+    pub async fn prepare<'a, C: GenericClient>(
+        mut self,
+        client: &'a C,
+    ) -> Result<Self, tokio_postgres::Error> {
+        self.1 = Some(client.prepare(self.0).await?);
+        Ok(self)
+    }
+    pub fn bind<'c, 'a, 's, C: GenericClient>(
+        &'s self,
+        client: &'c C,
+        id: &'a uuid::Uuid,
+    ) -> UserQuery<'c, 'a, 's, C, User, 1> {
+        UserQuery {
+            client,
+            params: [id],
+            query: self.0,
+            cached: self.1.as_ref(),
+            extractor: |row: &tokio_postgres::Row| -> Result<UserBorrowed, tokio_postgres::Error> {
+                Ok(UserBorrowed {
+                    id: row.try_get(0)?,
+                    username: row.try_get(1)?,
+                    email: row.try_get(2)?,
+                    first_name: row.try_get(3)?,
+                    last_name: row.try_get(4)?,
+                    age: row.try_get(5)?,
+                    created_at: row.try_get(6)?,
+                    updated_at: row.try_get(7)?,
+                })
+            },
+            mapper: |it| User::from(it),
+        }
+    }
+}
+pub struct SelectUsersLimitStmt(&'static str, Option<tokio_postgres::Statement>);
+pub fn select_users_limit() -> SelectUsersLimitStmt {
+    SelectUsersLimitStmt(
+        "SELECT id, username, email, first_name, last_name, age, created_at, updated_at FROM users ORDER BY created_at DESC LIMIT $1",
+        None,
+    )
+}
+impl SelectUsersLimitStmt {
+    pub async fn prepare<'a, C: GenericClient>(
+        mut self,
+        client: &'a C,
+    ) -> Result<Self, tokio_postgres::Error> {
+        self.1 = Some(client.prepare(self.0).await?);
+        Ok(self)
+    }
+    pub fn bind<'c, 'a, 's, C: GenericClient>(
+        &'s self,
+        client: &'c C,
+        limit: &'a i64,
+    ) -> UserQuery<'c, 'a, 's, C, User, 1> {
+        UserQuery {
+            client,
+            params: [limit],
+            query: self.0,
+            cached: self.1.as_ref(),
+            extractor: |row: &tokio_postgres::Row| -> Result<UserBorrowed, tokio_postgres::Error> {
+                Ok(UserBorrowed {
+                    id: row.try_get(0)?,
+                    username: row.try_get(1)?,
+                    email: row.try_get(2)?,
+                    first_name: row.try_get(3)?,
+                    last_name: row.try_get(4)?,
+                    age: row.try_get(5)?,
+                    created_at: row.try_get(6)?,
+                    updated_at: row.try_get(7)?,
+                })
+            },
+            mapper: |it| User::from(it),
+        }
+    }
+}
+pub struct SelectUsersFilteredStmt(&'static str, Option<tokio_postgres::Statement>);
+pub fn select_users_filtered() -> SelectUsersFilteredStmt {
+    SelectUsersFilteredStmt(
+        "SELECT id, username, email, first_name, last_name, age, created_at, updated_at FROM users WHERE age >= $1 AND age <= $2 ORDER BY age, username LIMIT $3",
+        None,
+    )
+}
+impl SelectUsersFilteredStmt {
+    pub async fn prepare<'a, C: GenericClient>(
+        mut self,
+        client: &'a C,
+    ) -> Result<Self, tokio_postgres::Error> {
+        self.1 = Some(client.prepare(self.0).await?);
+        Ok(self)
+    }
+    pub fn bind<'c, 'a, 's, C: GenericClient>(
+        &'s self,
+        client: &'c C,
+        min_age: &'a i32,
+        max_age: &'a i32,
+        limit: &'a i64,
+    ) -> UserQuery<'c, 'a, 's, C, User, 3> {
+        UserQuery {
+            client,
+            params: [min_age, max_age, limit],
+            query: self.0,
+            cached: self.1.as_ref(),
+            extractor: |row: &tokio_postgres::Row| -> Result<UserBorrowed, tokio_postgres::Error> {
+                Ok(UserBorrowed {
+                    id: row.try_get(0)?,
+                    username: row.try_get(1)?,
+                    email: row.try_get(2)?,
+                    first_name: row.try_get(3)?,
+                    last_name: row.try_get(4)?,
+                    age: row.try_get(5)?,
+                    created_at: row.try_get(6)?,
+                    updated_at: row.try_get(7)?,
+                })
+            },
+            mapper: |it| User::from(it),
+        }
+    }
+}
+impl<'c, 'a, 's, C: GenericClient>
+    crate::client::async_::Params<
+        'c,
+        'a,
+        's,
+        SelectUsersFilteredParams,
+        UserQuery<'c, 'a, 's, C, User, 3>,
+        C,
+    > for SelectUsersFilteredStmt
+{
+    fn params(
+        &'s self,
+        client: &'c C,
+        params: &'a SelectUsersFilteredParams,
+    ) -> UserQuery<'c, 'a, 's, C, User, 3> {
+        self.bind(client, &params.min_age, &params.max_age, &params.limit)
+    }
+}
+pub struct UpdateUserStmt(&'static str, Option<tokio_postgres::Statement>);
+pub fn update_user() -> UpdateUserStmt {
+    UpdateUserStmt(
+        "UPDATE users SET first_name = $1, last_name = $2, updated_at = NOW() WHERE id = $3",
+        None,
+    )
+}
+impl UpdateUserStmt {
+    pub async fn prepare<'a, C: GenericClient>(
+        mut self,
+        client: &'a C,
+    ) -> Result<Self, tokio_postgres::Error> {
+        self.1 = Some(client.prepare(self.0).await?);
+        Ok(self)
+    }
+    pub async fn bind<'c, 'a, 's, C: GenericClient, T1: crate::StringSql, T2: crate::StringSql>(
+        &'s self,
+        client: &'c C,
+        first_name: &'a T1,
+        last_name: &'a T2,
+        id: &'a uuid::Uuid,
+    ) -> Result<u64, tokio_postgres::Error> {
+        client.execute(self.0, &[first_name, last_name, id]).await
+    }
+}
+impl<'a, C: GenericClient + Send + Sync, T1: crate::StringSql, T2: crate::StringSql>
+    crate::client::async_::Params<
+        'a,
+        'a,
+        'a,
+        UpdateUserParams<T1, T2>,
+        std::pin::Pin<
+            Box<dyn futures::Future<Output = Result<u64, tokio_postgres::Error>> + Send + 'a>,
+        >,
+        C,
+    > for UpdateUserStmt
+{
+    fn params(
+        &'a self,
+        client: &'a C,
+        params: &'a UpdateUserParams<T1, T2>,
+    ) -> std::pin::Pin<
+        Box<dyn futures::Future<Output = Result<u64, tokio_postgres::Error>> + Send + 'a>,
+    > {
+        Box::pin(self.bind(client, &params.first_name, &params.last_name, &params.id))
+    }
+}
+pub struct DeleteUserStmt(&'static str, Option<tokio_postgres::Statement>);
+pub fn delete_user() -> DeleteUserStmt {
+    DeleteUserStmt("DELETE FROM users WHERE id = $1", None)
+}
+impl DeleteUserStmt {
+    pub async fn prepare<'a, C: GenericClient>(
+        mut self,
+        client: &'a C,
+    ) -> Result<Self, tokio_postgres::Error> {
+        self.1 = Some(client.prepare(self.0).await?);
+        Ok(self)
+    }
+    pub async fn bind<'c, 'a, 's, C: GenericClient>(
+        &'s self,
+        client: &'c C,
+        id: &'a uuid::Uuid,
+    ) -> Result<u64, tokio_postgres::Error> {
+        client.execute(self.0, &[id]).await
+    }
+}
+pub struct SearchUsersByNameStmt(&'static str, Option<tokio_postgres::Statement>);
+pub fn search_users_by_name() -> SearchUsersByNameStmt {
+    SearchUsersByNameStmt(
+        "SELECT id, username, email, first_name, last_name, age, created_at, updated_at FROM users WHERE first_name ILIKE $1 OR last_name ILIKE $1 ORDER BY username LIMIT $2",
+        None,
+    )
+}
+impl SearchUsersByNameStmt {
+    pub async fn prepare<'a, C: GenericClient>(
+        mut self,
+        client: &'a C,
+    ) -> Result<Self, tokio_postgres::Error> {
+        self.1 = Some(client.prepare(self.0).await?);
+        Ok(self)
+    }
+    pub fn bind<'c, 'a, 's, C: GenericClient, T1: crate::StringSql>(
+        &'s self,
+        client: &'c C,
+        pattern: &'a T1,
+        limit: &'a i64,
+    ) -> UserQuery<'c, 'a, 's, C, User, 2> {
+        UserQuery {
+            client,
+            params: [pattern, limit],
+            query: self.0,
+            cached: self.1.as_ref(),
+            extractor: |row: &tokio_postgres::Row| -> Result<UserBorrowed, tokio_postgres::Error> {
+                Ok(UserBorrowed {
+                    id: row.try_get(0)?,
+                    username: row.try_get(1)?,
+                    email: row.try_get(2)?,
+                    first_name: row.try_get(3)?,
+                    last_name: row.try_get(4)?,
+                    age: row.try_get(5)?,
+                    created_at: row.try_get(6)?,
+                    updated_at: row.try_get(7)?,
+                })
+            },
+            mapper: |it| User::from(it),
+        }
+    }
+}
+impl<'c, 'a, 's, C: GenericClient, T1: crate::StringSql>
+    crate::client::async_::Params<
+        'c,
+        'a,
+        's,
+        SearchUsersByNameParams<T1>,
+        UserQuery<'c, 'a, 's, C, User, 2>,
+        C,
+    > for SearchUsersByNameStmt
+{
+    fn params(
+        &'s self,
+        client: &'c C,
+        params: &'a SearchUsersByNameParams<T1>,
+    ) -> UserQuery<'c, 'a, 's, C, User, 2> {
+        self.bind(client, &params.pattern, &params.limit)
+    }
+}
+pub struct InsertUsersUnnestStmt(&'static str, Option<tokio_postgres::Statement>);
+pub fn insert_users_unnest() -> InsertUsersUnnestStmt {
+    InsertUsersUnnestStmt(
+        "INSERT INTO users (username, email, first_name, last_name, age) SELECT * FROM unnest($1::varchar[], $2::varchar[], $3::varchar[], $4::varchar[], $5::int4[])",
+        None,
+    )
+}
+impl InsertUsersUnnestStmt {
+    pub async fn prepare<'a, C: GenericClient>(
+        mut self,
+        client: &'a C,
+    ) -> Result<Self, tokio_postgres::Error> {
+        self.1 = Some(client.prepare(self.0).await?);
+        Ok(self)
+    }
+    pub async fn bind<
+        'c,
+        'a,
+        's,
+        C: GenericClient,
+        T1: crate::StringSql,
+        T2: crate::ArraySql<Item = T1>,
+        T3: crate::StringSql,
+        T4: crate::ArraySql<Item = T3>,
+        T5: crate::StringSql,
+        T6: crate::ArraySql<Item = T5>,
+        T7: crate::StringSql,
+        T8: crate::ArraySql<Item = T7>,
+        T9: crate::ArraySql<Item = Option<i32>>,
+    >(
+        &'s self,
+        client: &'c C,
+        usernames: &'a T2,
+        emails: &'a T4,
+        first_names: &'a T6,
+        last_names: &'a T8,
+        ages: &'a T9,
+    ) -> Result<u64, tokio_postgres::Error> {
+        client
+            .execute(self.0, &[usernames, emails, first_names, last_names, ages])
+            .await
+    }
+}
+impl<
+    'a,
+    C: GenericClient + Send + Sync,
+    T1: crate::StringSql,
+    T2: crate::ArraySql<Item = T1>,
+    T3: crate::StringSql,
+    T4: crate::ArraySql<Item = T3>,
+    T5: crate::StringSql,
+    T6: crate::ArraySql<Item = T5>,
+    T7: crate::StringSql,
+    T8: crate::ArraySql<Item = T7>,
+    T9: crate::ArraySql<Item = Option<i32>>,
+>
+    crate::client::async_::Params<
+        'a,
+        'a,
+        'a,
+        InsertUsersUnnestParams<T1, T2, T3, T4, T5, T6, T7, T8, T9>,
+        std::pin::Pin<
+            Box<dyn futures::Future<Output = Result<u64, tokio_postgres::Error>> + Send + 'a>,
+        >,
+        C,
+    > for InsertUsersUnnestStmt
+{
+    fn params(
+        &'a self,
+        client: &'a C,
+        params: &'a InsertUsersUnnestParams<T1, T2, T3, T4, T5, T6, T7, T8, T9>,
+    ) -> std::pin::Pin<
+        Box<dyn futures::Future<Output = Result<u64, tokio_postgres::Error>> + Send + 'a>,
+    > {
+        Box::pin(self.bind(
+            client,
+            &params.usernames,
+            &params.emails,
+            &params.first_names,
+            &params.last_names,
+            &params.ages,
+        ))
+    }
+}
+pub struct InsertUserUnlessTakenStmt(&'static str, Option<tokio_postgres::Statement>);
+pub fn insert_user_unless_taken() -> InsertUserUnlessTakenStmt {
+    InsertUserUnlessTakenStmt(
+        "INSERT INTO users (username, email, first_name, last_name, age) VALUES ($1, $2, $3, $4, $5) ON CONFLICT (username) DO NOTHING",
+        None,
+    )
+}
+impl InsertUserUnlessTakenStmt {
+    pub async fn prepare<'a, C: GenericClient>(
+        mut self,
+        client: &'a C,
+    ) -> Result<Self, tokio_postgres::Error> {
+        self.1 = Some(client.prepare(self.0).await?);
+        Ok(self)
+    }
+    pub async fn bind<
+        'c,
+        'a,
+        's,
+        C: GenericClient,
+        T1: crate::StringSql,
+        T2: crate::StringSql,
+        T3: crate::StringSql,
+        T4: crate::StringSql,
+    >(
+        &'s self,
+        client: &'c C,
+        username: &'a T1,
+        email: &'a T2,
+        first_name: &'a T3,
+        last_name: &'a T4,
+        age: &'a Option<i32>,
+    ) -> Result<u64, tokio_postgres::Error> {
+        client
+            .execute(self.0, &[username, email, first_name, last_name, age])
+            .await
+    }
+}
+impl<
+    'a,
+    C: GenericClient + Send + Sync,
+    T1: crate::StringSql,
+    T2: crate::StringSql,
+    T3: crate::StringSql,
+    T4: crate::StringSql,
+>
+    crate::client::async_::Params<
+        'a,
+        'a,
+        'a,
+        InsertUserUnlessTakenParams<T1, T2, T3, T4>,
+        std::pin::Pin<
+            Box<dyn futures::Future<Output = Result<u64, tokio_postgres::Error>> + Send + 'a>,
+        >,
+        C,
+    > for InsertUserUnlessTakenStmt
+{
+    fn params(
+        &'a self,
+        client: &'a C,
+        params: &'a InsertUserUnlessTakenParams<T1, T2, T3, T4>,
+    ) -> std::pin::Pin<
+        Box<dyn futures::Future<Output = Result<u64, tokio_postgres::Error>> + Send + 'a>,
+    > {
+        Box::pin(self.bind(
+            client,
+            &params.username,
+            &params.email,
+            &params.first_name,
+            &params.last_name,
+            &params.age,
+        ))
+    }
+}
+pub struct CleanupStmt(&'static str, Option<tokio_postgres::Statement>);
+pub fn cleanup() -> CleanupStmt {
+    CleanupStmt("DELETE FROM users WHERE username LIKE 'bench_user_%'", None)
+}
+impl CleanupStmt {
+    pub async fn prepare<'a, C: GenericClient>(
+        mut self,
+        client: &'a C,
+    ) -> Result<Self, tokio_postgres::Error> {
+        self.1 = Some(client.prepare(self.0).await?);
+        Ok(self)
+    }
+    pub async fn bind<'c, 'a, 's, C: GenericClient>(
+        &'s self,
+        client: &'c C,
+    ) -> Result<u64, tokio_postgres::Error> {
+        client.execute(self.0, &[]).await
+    }
+}
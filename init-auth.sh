@@ -0,0 +1,24 @@
+#!/bin/sh
+# Create one login role per authentication method for the auth_connect
+# benchmarks, each pinned to its method by a pg_hba.conf rule placed ahead of
+# the image's catch-all rules. Runs once, when docker-entrypoint initializes
+# the data directory. The password matches the one in src/auth.rs.
+set -e
+
+psql -v ON_ERROR_STOP=1 --username "$POSTGRES_USER" --dbname "$POSTGRES_DB" <<'SQL'
+CREATE ROLE bench_auth_trust LOGIN;
+SET password_encryption = 'md5';
+CREATE ROLE bench_auth_md5 LOGIN PASSWORD 'benchmark_pass';
+SET password_encryption = 'scram-sha-256';
+CREATE ROLE bench_auth_scram LOGIN PASSWORD 'benchmark_pass';
+SQL
+
+hba="$PGDATA/pg_hba.conf"
+{
+    echo "host all bench_auth_trust all trust"
+    echo "host all bench_auth_md5   all md5"
+    echo "host all bench_auth_scram all scram-sha-256"
+    cat "$hba"
+} > "$hba.new"
+mv "$hba.new" "$hba"
+echo "Authentication roles created: bench_auth_trust, bench_auth_md5, bench_auth_scram"
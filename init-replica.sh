@@ -0,0 +1,15 @@
+#!/bin/sh
+# Entry point of the postgres-replica service: on first start, clone the
+# primary with pg_basebackup (-R writes standby.signal and the connection
+# settings), then hand over to the image's entrypoint, which finds an
+# initialized data directory and starts the server as a hot standby.
+set -e
+
+if [ ! -s "$PGDATA/PG_VERSION" ]; then
+    echo "Cloning the primary into $PGDATA"
+    pg_basebackup --host=postgres --username=benchmark_user --pgdata="$PGDATA" \
+        --wal-method=stream --write-recovery-conf --checkpoint=fast
+    chmod 700 "$PGDATA"
+fi
+
+exec docker-entrypoint.sh "$@"
@@ -0,0 +1,8 @@
+#!/bin/sh
+# Let benchmark_user stream WAL to the read replica (the postgres-replica
+# service in compose.yml). The image's catch-all rules only cover regular
+# databases, so replication connections get their own pg_hba.conf rule.
+set -e
+
+echo "host replication $POSTGRES_USER all scram-sha-256" >> "$PGDATA/pg_hba.conf"
+echo "Replication enabled for $POSTGRES_USER"
@@ -0,0 +1,26 @@
+#!/bin/sh
+# Runs once when the postgres-subscriber service (compose.yml) initializes:
+# copy the primary's schema, publish users, posts and comments there if they
+# aren't yet, and subscribe to them, which copies the rows and then applies
+# every change the primary makes to them.
+set -e
+
+export PGPASSWORD="$POSTGRES_PASSWORD"
+PRIMARY="host=postgres dbname=benchmark_db user=benchmark_user"
+
+pg_dump --schema-only --no-publications --no-subscriptions "$PRIMARY" \
+    | psql -v ON_ERROR_STOP=1 --username "$POSTGRES_USER" --dbname "$POSTGRES_DB" > /dev/null
+
+psql -v ON_ERROR_STOP=1 "$PRIMARY" <<'SQL'
+DO $$
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM pg_publication WHERE pubname = 'benchmark_pub') THEN
+        CREATE PUBLICATION benchmark_pub FOR TABLE users, posts, comments;
+    END IF;
+END
+$$;
+SQL
+
+psql -v ON_ERROR_STOP=1 --username "$POSTGRES_USER" --dbname "$POSTGRES_DB" \
+    -c "CREATE SUBSCRIPTION benchmark_sub CONNECTION '$PRIMARY password=$POSTGRES_PASSWORD' PUBLICATION benchmark_pub"
+echo "Subscribed to benchmark_pub on the primary"
@@ -0,0 +1,14 @@
+#!/bin/sh
+# Enable TLS for the TLS benchmarks, using the self-signed certificate that
+# run_benchmarks.sh generates into ./certs (mounted at /certs). Runs once, when
+# docker-entrypoint initializes the data directory.
+set -e
+
+if [ -f /certs/server.crt ] && [ -f /certs/server.key ]; then
+    cp /certs/server.crt /certs/server.key "$PGDATA/"
+    chmod 600 "$PGDATA/server.key"
+    echo "ssl = on" >> "$PGDATA/postgresql.conf"
+    echo "TLS enabled with /certs/server.crt"
+else
+    echo "No certificate in /certs, TLS stays off and the TLS benchmarks are skipped"
+fi
@@ -4,6 +4,8 @@
 -- Enable required extensions
 CREATE EXTENSION IF NOT EXISTS "uuid-ossp";
 CREATE EXTENSION IF NOT EXISTS "pg_trgm";  -- For text search optimization
+CREATE EXTENSION IF NOT EXISTS "pg_prewarm";  -- For the buffer cache comparison
+CREATE EXTENSION IF NOT EXISTS "citext";  -- For the case-insensitive email lookup
 
 -- ============================================================================
 -- Core Tables
@@ -27,8 +29,8 @@ CREATE TABLE posts (
     user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
     title VARCHAR(200) NOT NULL,
     content TEXT NOT NULL,
-    status VARCHAR(20) DEFAULT 'draft' CHECK (status IN ('draft', 'published', 'archived')),
-    view_count INTEGER DEFAULT 0,
+    status VARCHAR(20) NOT NULL DEFAULT 'draft' CHECK (status IN ('draft', 'published', 'archived')),
+    view_count INTEGER NOT NULL DEFAULT 0,
     created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
     updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
 );
@@ -46,7 +48,7 @@ CREATE TABLE comments (
 CREATE TABLE tags (
     id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
     name VARCHAR(50) UNIQUE NOT NULL,
-    color VARCHAR(7) DEFAULT '#000000',
+    color VARCHAR(7) NOT NULL DEFAULT '#000000',
     created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
 );
 
@@ -75,6 +77,7 @@ CREATE INDEX idx_posts_status ON posts(status);
 CREATE INDEX idx_posts_created_at ON posts(created_at DESC);
 CREATE INDEX idx_posts_view_count ON posts(view_count DESC);
 CREATE INDEX idx_posts_user_status ON posts(user_id, status);
+CREATE INDEX idx_posts_status_created_at ON posts(status, created_at);
 
 -- Comments indexes
 CREATE INDEX idx_comments_post_id ON comments(post_id);
@@ -88,8 +91,99 @@ CREATE INDEX idx_tags_name ON tags(name);
 -- Sample Data Generation for Heavy Workload Testing
 -- ============================================================================
 
--- Insert 10,000 users for realistic heavy load testing
-INSERT INTO users (username, email, first_name, last_name, age)
+-- Seeder configuration (see src/seed.rs): the number of users, the
+-- users:posts:comments ratio, post and comment bodies with log-normal
+-- lengths, mostly short with a long tail that gets TOASTed, and how far back
+-- created_at goes. Override with the
+-- SEED_* environment variables of the postgres container.
+\getenv seed_users SEED_USERS
+\if :{?seed_users}
+\else
+\set seed_users 10000
+\endif
+\getenv seed_ratio SEED_RATIO
+\if :{?seed_ratio}
+\else
+\set seed_ratio 2:5:16
+\endif
+\getenv post_content_median SEED_POST_CONTENT_MEDIAN
+\if :{?post_content_median}
+\else
+\set post_content_median 400
+\endif
+\getenv comment_content_median SEED_COMMENT_CONTENT_MEDIAN
+\if :{?comment_content_median}
+\else
+\set comment_content_median 120
+\endif
+\getenv content_sigma SEED_CONTENT_SIGMA
+\if :{?content_sigma}
+\else
+\set content_sigma 1.0
+\endif
+\getenv post_content_max SEED_CONTENT_MAX
+\if :{?post_content_max}
+\set comment_content_max :post_content_max
+\else
+\set post_content_max 65536
+\set comment_content_max 16384
+\endif
+\getenv history_days SEED_HISTORY_DAYS
+\if :{?history_days}
+\else
+\set history_days 1095
+\endif
+\getenv history_skew SEED_HISTORY_SKEW
+\if :{?history_skew}
+\else
+\set history_skew 2.0
+\endif
+
+-- Row counts, the same arithmetic as SeedConfig: half of the users have posts,
+-- spread round-robin, and four in five posts have comments, spread the same way
+SELECT users, posts, comments,
+       least(greatest(users / 2, 1), users) AS posting_users,
+       least(greatest(posts * 4 / 5, 1), posts) AS commented_posts
+FROM (
+    SELECT :seed_users::BIGINT AS users,
+           :seed_users::BIGINT * ratio[2] / ratio[1] AS posts,
+           :seed_users::BIGINT * ratio[3] / ratio[1] AS comments
+    FROM (SELECT string_to_array(:'seed_ratio', ':')::BIGINT[] AS ratio) r
+) counts \gset seed_
+
+-- `prefix` followed by random words up to a log-normally distributed length
+CREATE FUNCTION pg_temp.seed_content(prefix TEXT, median INTEGER, sigma DOUBLE PRECISION, max_length INTEGER)
+RETURNS TEXT
+LANGUAGE plpgsql VOLATILE AS $$
+DECLARE
+    words CONSTANT TEXT[] := ARRAY[
+        'the', 'of', 'and', 'to', 'in', 'is', 'that', 'for', 'it', 'as', 'with', 'on', 'be',
+        'this', 'by', 'are', 'from', 'or', 'an', 'was', 'but', 'not', 'can', 'which', 'query',
+        'index', 'database', 'table', 'rows', 'column', 'server', 'client', 'connection',
+        'latency', 'cache', 'memory', 'disk', 'network', 'rust', 'async', 'driver', 'pool',
+        'transaction', 'commit', 'schema', 'migration', 'performance', 'benchmark', 'throughput',
+        'request', 'response', 'result', 'value', 'type', 'error', 'field', 'record', 'batch',
+        'stream', 'buffer', 'page', 'plan', 'scan', 'join'
+    ];
+    -- Box-Muller; 1 - random() keeps ln away from 0
+    target INTEGER := least(greatest(round(exp(
+        ln(median) + sigma * sqrt(-2 * ln(1 - random())) * cos(2 * pi() * random())
+    )), 1), max_length)::INTEGER;
+BEGIN
+    RETURN left(
+        prefix || coalesce((
+            SELECT string_agg(' ' || words[1 + floor(random() * array_length(words, 1))::INTEGER], '')
+            FROM generate_series(1, target / 4)
+        ), ''),
+        greatest(target, length(prefix))
+    );
+END
+$$;
+
+-- Insert the users (10,000 by default) for realistic heavy load testing,
+-- created over the last SEED_HISTORY_DAYS days: u^SEED_HISTORY_SKEW of the
+-- way back for uniform u, so most of them recently
+INSERT INTO users (username, email, first_name, last_name, age, created_at, updated_at)
 SELECT 
     'user_' || i,
     'user_' || i || '@example.com',
@@ -132,85 +226,110 @@ SELECT
         WHEN 13 THEN 'Wilson'
         ELSE 'Anderson'
     END || (i / 100),
-    (18 + (i % 62))::INTEGER
-FROM generate_series(1, 10000) i;
+    (18 + (i % 62))::INTEGER,
+    created_at,
+    created_at
+FROM (
+    SELECT i, NOW() - :history_days * INTERVAL '1 day' * power(random(), :history_skew) AS created_at
+    FROM generate_series(1, :seed_users) i
+) g;
+
+-- Insert the tag taxonomy: 300 tags, every subject on its own and narrowed
+-- down, numbered by popularity (the same names as src/seeder.rs)
+CREATE TEMP TABLE seed_tags AS
+SELECT (q.n - 1) * 60 + s.n AS tag_number, s.subject || q.qualifier AS name
+FROM unnest(ARRAY[
+    'rust', 'postgresql', 'python', 'javascript', 'databases', 'performance', 'linux', 'docker',
+    'typescript', 'go', 'async', 'security', 'kubernetes', 'sql', 'testing', 'architecture',
+    'java', 'aws', 'react', 'devops', 'redis', 'concurrency', 'networking', 'git', 'nodejs',
+    'observability', 'kafka', 'graphql', 'mysql', 'sqlite', 'web', 'wasm', 'compilers', 'embedded',
+    'cryptography', 'machine-learning', 'data-engineering', 'cpp', 'csharp', 'kotlin', 'swift',
+    'ruby', 'php', 'elixir', 'haskell', 'scala', 'zig', 'terraform', 'ansible', 'nginx', 'mongodb',
+    'elasticsearch', 'grpc', 'rest', 'websockets', 'vue', 'svelte', 'deno', 'azure', 'gcp'
+]) WITH ORDINALITY s(subject, n)
+CROSS JOIN unnest(ARRAY['', '-tips', '-internals', '-tutorial', '-news']) WITH ORDINALITY q(qualifier, n);
 
--- Insert 100 tags
 INSERT INTO tags (name, color)
-SELECT 
-    CASE (i % 20)
-        WHEN 0 THEN 'technology'
-        WHEN 1 THEN 'programming'
-        WHEN 2 THEN 'database'
-        WHEN 3 THEN 'rust'
-        WHEN 4 THEN 'postgresql'
-        WHEN 5 THEN 'performance'
-        WHEN 6 THEN 'tutorial'
-        WHEN 7 THEN 'beginner'
-        WHEN 8 THEN 'advanced'
-        WHEN 9 THEN 'tips'
-        WHEN 10 THEN 'news'
-        WHEN 11 THEN 'review'
-        WHEN 12 THEN 'howto'
-        WHEN 13 THEN 'guide'
-        WHEN 14 THEN 'best-practices'
-        WHEN 15 THEN 'architecture'
-        WHEN 16 THEN 'design'
-        WHEN 17 THEN 'testing'
-        WHEN 18 THEN 'deployment'
-        ELSE 'devops'
-    END || '_' || i,
+SELECT
+    name,
     '#' || lpad(to_hex(floor(random() * 16777215)::integer), 6, '0')
-FROM generate_series(1, 100) i;
+FROM seed_tags
+ORDER BY tag_number;
 
--- Insert 50,000 posts (5 posts per user average)
-INSERT INTO posts (user_id, title, content, status, view_count)
+-- Insert the posts (25,000 by default: 5 for each of user_1 to user_5000),
+-- the i-th going to user_(i mod posting_users + 1). The titles number the
+-- posts per user, which is what `cargo run -- seed` tops up by (src/seeder.rs).
+-- Each is created between its user's creation and now, with the same skew
+INSERT INTO posts (user_id, title, content, status, view_count, created_at, updated_at)
 SELECT 
     u.id,
     'Post Title ' || p.post_num || ' by ' || u.username,
-    'This is the content for post number ' || p.post_num || '. ' ||
-    'It contains multiple sentences to simulate realistic blog content. ' ||
-    'The post discusses various topics related to technology and programming. ' ||
-    'Lorem ipsum dolor sit amet, consectetur adipiscing elit. ' ||
-    'Sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. ' ||
-    'Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris.',
+    pg_temp.seed_content(
+        'This is the content for post number ' || p.post_num || '.',
+        :post_content_median, :content_sigma, :post_content_max
+    ),
     CASE (p.post_num % 10)
         WHEN 0 THEN 'draft'
         WHEN 1 THEN 'archived'
         ELSE 'published'
     END,
-    (random() * 10000)::INTEGER
-FROM users u
+    (random() * 10000)::INTEGER,
+    t.created_at,
+    t.created_at
+FROM generate_series(0, :seed_posts - 1) i
 CROSS JOIN LATERAL (
-    SELECT generate_series(1, 5) as post_num
+    SELECT i % :seed_posting_users + 1 AS user_number, i / :seed_posting_users + 1 AS post_num
 ) p
-WHERE u.id IN (SELECT id FROM users ORDER BY created_at LIMIT 5000);
+JOIN users u ON u.username = 'user_' || p.user_number
+CROSS JOIN LATERAL (
+    SELECT NOW() - (NOW() - u.created_at) * power(random(), :history_skew) AS created_at
+) t;
 
--- Insert 200,000 comments (4 comments per post average)
-INSERT INTO comments (post_id, user_id, content)
+-- Insert the comments (80,000 by default: 4 on each of the first 20,000 posts),
+-- spread over the commented posts the same way; the i-th post is the one the
+-- i-th post insert above created. Each is created between its post's creation and now
+INSERT INTO comments (post_id, user_id, content, created_at)
 SELECT 
     p.id,
     (SELECT id FROM users ORDER BY random() LIMIT 1),
-    'This is comment ' || c.comment_num || ' on this post. ' ||
-    'Great content! I really enjoyed reading this article. ' ||
-    'Looking forward to more posts like this.'
-FROM posts p
+    pg_temp.seed_content(
+        'This is comment ' || c.comment_num || ' on this post.',
+        :comment_content_median, :content_sigma, :comment_content_max
+    ),
+    NOW() - (NOW() - p.created_at) * power(random(), :history_skew)
+FROM generate_series(0, :seed_comments - 1) i
 CROSS JOIN LATERAL (
-    SELECT generate_series(1, 4) as comment_num
+    SELECT i % :seed_commented_posts AS post_index, i / :seed_commented_posts + 1 AS comment_num
 ) c
-WHERE p.id IN (SELECT id FROM posts ORDER BY created_at LIMIT 20000);
+JOIN posts p ON p.title = 'Post Title ' || (c.post_index / :seed_posting_users + 1)
+                          || ' by user_' || (c.post_index % :seed_posting_users + 1);
+
+-- Link the i-th post with 0 to 5 tags, as many as seeder::tags_per_post(i),
+-- drawn without replacement with Zipf popularity (tag n has weight 1/n): the
+-- tags with the smallest -ln(u) * n, for uniform u, are a weighted sample
+CREATE TEMP TABLE seed_tag_ids AS
+SELECT t.id, st.tag_number FROM seed_tags st JOIN tags t USING (name);
 
--- Link posts with tags (3 tags per post average)
 INSERT INTO post_tags (post_id, tag_id)
-SELECT DISTINCT ON (p.id, t.id)
-    p.id,
-    t.id
-FROM posts p
+SELECT p.id, t.id
+FROM (
+    SELECT id,
+           CASE ((split_part(title, ' ', 3)::BIGINT - 1) * :seed_posting_users
+                 + substr(split_part(title, ' ', 5), 6)::BIGINT - 1) * 7 % 20
+               WHEN 0 THEN 0 WHEN 1 THEN 0
+               WHEN 2 THEN 1 WHEN 3 THEN 1 WHEN 4 THEN 1 WHEN 5 THEN 1
+               WHEN 6 THEN 2 WHEN 7 THEN 2 WHEN 8 THEN 2 WHEN 9 THEN 2 WHEN 10 THEN 2
+               WHEN 11 THEN 3 WHEN 12 THEN 3 WHEN 13 THEN 3 WHEN 14 THEN 3
+               WHEN 15 THEN 4 WHEN 16 THEN 4 WHEN 17 THEN 4
+               ELSE 5
+           END AS tag_count
+    FROM posts
+) p
 CROSS JOIN LATERAL (
-    SELECT id FROM tags ORDER BY random() LIMIT 3
-) t
-WHERE p.id IN (SELECT id FROM posts ORDER BY random() LIMIT 30000)
-ON CONFLICT DO NOTHING;
+    SELECT id FROM seed_tag_ids
+    ORDER BY -ln(1 - random()) * tag_number
+    LIMIT p.tag_count
+) t;
 
 -- ============================================================================
 -- Statistics and Verification
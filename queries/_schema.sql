@@ -0,0 +1,89 @@
+-- The tables the queries run against, for `clorinde fresh` to prepare them
+-- on (see clorinde.toml). The benchmark database gets its own from init.sql,
+-- which psql runs, and from the workloads that create tables at runtime; the
+-- definitions here must describe the same columns.
+
+CREATE EXTENSION IF NOT EXISTS "uuid-ossp";
+CREATE EXTENSION IF NOT EXISTS "citext";
+
+-- init.sql
+CREATE TABLE users (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    username VARCHAR(50) UNIQUE NOT NULL,
+    email VARCHAR(100) UNIQUE NOT NULL,
+    first_name VARCHAR(50) NOT NULL,
+    last_name VARCHAR(50) NOT NULL,
+    age INTEGER CHECK (age >= 0 AND age <= 150),
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE TABLE posts (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    title VARCHAR(200) NOT NULL,
+    content TEXT NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'draft' CHECK (status IN ('draft', 'published', 'archived')),
+    view_count INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE TABLE comments (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    post_id UUID NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    content TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE TABLE tags (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    name VARCHAR(50) UNIQUE NOT NULL,
+    color VARCHAR(7) NOT NULL DEFAULT '#000000',
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE TABLE post_tags (
+    post_id UUID NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    tag_id UUID NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+    PRIMARY KEY (post_id, tag_id)
+);
+
+-- src/queue.rs
+CREATE TABLE jobs (
+    id BIGSERIAL PRIMARY KEY,
+    payload TEXT NOT NULL,
+    status VARCHAR(10) NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'running', 'done')),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    done_at TIMESTAMP WITH TIME ZONE
+);
+
+-- src/generated_column.rs
+CREATE SCHEMA generated;
+CREATE TABLE generated.users (
+    LIKE public.users INCLUDING ALL,
+    full_name TEXT GENERATED ALWAYS AS (first_name || ' ' || last_name) STORED
+);
+
+-- src/email_lookup.rs
+CREATE SCHEMA email_lookup;
+CREATE TABLE email_lookup.citext_users (LIKE public.users INCLUDING ALL);
+ALTER TABLE email_lookup.citext_users ALTER COLUMN email TYPE CITEXT;
+CREATE TABLE email_lookup.lower_users (LIKE public.users INCLUDING ALL);
+
+-- src/deferred_constraints.rs
+CREATE SCHEMA deferred;
+CREATE TABLE deferred.users (LIKE public.users INCLUDING ALL);
+CREATE TABLE deferred.posts (
+    LIKE public.posts INCLUDING ALL,
+    FOREIGN KEY (user_id) REFERENCES deferred.users(id) ON DELETE CASCADE DEFERRABLE
+);
+CREATE TABLE deferred.comments (
+    LIKE public.comments INCLUDING ALL,
+    FOREIGN KEY (post_id) REFERENCES deferred.posts(id) ON DELETE CASCADE DEFERRABLE,
+    FOREIGN KEY (user_id) REFERENCES deferred.users(id) ON DELETE CASCADE DEFERRABLE
+);
+
+-- src/temp_table.rs creates it per session as a temp table with these columns
+CREATE TABLE staged_users (user_id UUID PRIMARY KEY, score INTEGER NOT NULL);
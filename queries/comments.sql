@@ -0,0 +1,20 @@
+--: Comment(created_at?)
+
+--! insert_comment
+INSERT INTO comments (post_id, user_id, content)
+VALUES (:post_id, :user_id, :content)
+RETURNING id;
+
+--! select_comments_for_post : Comment
+SELECT id, post_id, user_id, content, created_at
+FROM comments
+WHERE post_id = :post_id
+ORDER BY created_at DESC, id DESC
+LIMIT :limit;
+
+--! select_comments_for_post_before : Comment
+SELECT id, post_id, user_id, content, created_at
+FROM comments
+WHERE post_id = :post_id AND (created_at, id) < (:before_created_at, :before_id)
+ORDER BY created_at DESC, id DESC
+LIMIT :limit;
@@ -0,0 +1,13 @@
+--! insert_deferred_user (age?)
+INSERT INTO deferred.users (username, email, first_name, last_name, age)
+VALUES (:username, :email, :first_name, :last_name, :age)
+RETURNING id;
+
+--! insert_deferred_post
+INSERT INTO deferred.posts (user_id, title, content, status)
+VALUES (:user_id, :title, :content, :status)
+RETURNING id;
+
+--! insert_deferred_comment
+INSERT INTO deferred.comments (post_id, user_id, content)
+VALUES (:post_id, :user_id, :content);
@@ -0,0 +1,10 @@
+--: CitextUser(age?, created_at?, updated_at?)
+--: LowerEmailUser(age?, created_at?, updated_at?)
+
+--! select_user_by_citext_email : CitextUser
+SELECT id, username, email, first_name, last_name, age, created_at, updated_at
+FROM email_lookup.citext_users WHERE email = :email;
+
+--! select_user_by_lower_email : LowerEmailUser
+SELECT id, username, email, first_name, last_name, age, created_at, updated_at
+FROM email_lookup.lower_users WHERE LOWER(email) = LOWER(:email);
@@ -0,0 +1,14 @@
+--: NamedUser()
+
+--! insert_named_user (age?)
+INSERT INTO generated.users (username, email, first_name, last_name, age)
+VALUES (:username, :email, :first_name, :last_name, :age)
+RETURNING id;
+
+--! select_named_users : NamedUser
+SELECT id, username, first_name, last_name, full_name
+FROM generated.users ORDER BY created_at DESC LIMIT :limit;
+
+--! select_named_users_by_full_name : NamedUser
+SELECT id, username, first_name, last_name, full_name
+FROM generated.users WHERE full_name = :full_name;
@@ -0,0 +1,14 @@
+--: ClaimedJob()
+
+--! enqueue_jobs
+INSERT INTO jobs (payload) SELECT unnest(:payloads::text[]);
+
+--! claim_jobs : ClaimedJob
+UPDATE jobs SET status = 'running'
+WHERE id IN (
+    SELECT id FROM jobs WHERE status = 'pending' ORDER BY id LIMIT :limit FOR UPDATE SKIP LOCKED
+)
+RETURNING id, payload;
+
+--! complete_jobs
+UPDATE jobs SET status = 'done', done_at = NOW() WHERE id = ANY(:ids);
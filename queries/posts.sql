@@ -0,0 +1,79 @@
+--: Post(created_at?, updated_at?)
+--: PostWithUser(post_created_at?, post_updated_at?, age?, user_created_at?, user_updated_at?)
+--: UserPostComment(age?, user_created_at?, user_updated_at?, post_created_at?, post_updated_at?, comment_created_at?)
+--: UserPostCount()
+--: TagPostCount()
+
+--! insert_post
+INSERT INTO posts (user_id, title, content, status)
+VALUES (:user_id, :title, :content, :status)
+RETURNING id;
+
+--! select_posts_with_user : PostWithUser
+SELECT
+    p.id AS post_id, p.user_id, p.title, p.content, p.status, p.view_count,
+    p.created_at AS post_created_at, p.updated_at AS post_updated_at,
+    u.username, u.email, u.first_name, u.last_name, u.age,
+    u.created_at AS user_created_at, u.updated_at AS user_updated_at
+FROM posts p
+JOIN users u ON p.user_id = u.id
+ORDER BY p.created_at DESC
+LIMIT :limit;
+
+--! select_users_posts_comments : UserPostComment
+SELECT
+    u.id AS user_id, u.username, u.email, u.first_name, u.last_name, u.age,
+    u.created_at AS user_created_at, u.updated_at AS user_updated_at,
+    p.id AS post_id, p.title, p.content, p.status, p.view_count,
+    p.created_at AS post_created_at, p.updated_at AS post_updated_at,
+    c.id AS comment_id, c.user_id AS comment_user_id, c.content AS comment_content,
+    c.created_at AS comment_created_at
+FROM users u
+JOIN posts p ON u.id = p.user_id
+JOIN comments c ON p.id = c.post_id
+ORDER BY u.created_at DESC, p.created_at DESC, c.created_at DESC
+LIMIT :limit;
+
+--! count_posts_per_user : UserPostCount
+SELECT u.id AS user_id, COUNT(p.id) AS post_count
+FROM users u
+LEFT JOIN posts p ON u.id = p.user_id
+GROUP BY u.id
+ORDER BY post_count DESC;
+
+--! count_posts_per_tag : TagPostCount
+SELECT t.id AS tag_id, COUNT(pt.post_id) AS post_count
+FROM tags t
+JOIN post_tags pt ON t.id = pt.tag_id
+GROUP BY t.id
+ORDER BY post_count DESC, t.id;
+
+--! select_posts_by_status : Post
+SELECT id, user_id, title, content, status, view_count, created_at, updated_at
+FROM posts
+WHERE status = :status
+ORDER BY created_at DESC
+LIMIT :limit;
+
+--! select_posts_repeated : Post
+SELECT p.id, p.user_id, p.title, p.content, p.status, p.view_count, p.created_at, p.updated_at
+FROM posts p
+CROSS JOIN generate_series(1, :copies);
+
+--! select_posts_after : Post
+SELECT id, user_id, title, content, status, view_count, created_at, updated_at
+FROM posts
+WHERE id > :after
+ORDER BY id
+LIMIT :limit;
+
+--! increment_view_count
+UPDATE posts SET view_count = view_count + 1 WHERE id = :post_id;
+
+--! declare_posts_export
+DECLARE posts_export CURSOR WITH HOLD FOR
+SELECT id, user_id, title, content, status, view_count, created_at, updated_at
+FROM posts ORDER BY id;
+
+--! close_posts_export
+CLOSE posts_export;
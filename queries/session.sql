@@ -0,0 +1,10 @@
+--: FixedRow()
+
+--! ping
+SELECT 1;
+
+--! pg_sleep
+SELECT FROM pg_sleep(:seconds);
+
+--! fixed_row : FixedRow
+SELECT 42::int8 AS id, 'calibration'::text AS label, 0.5::float8 AS ratio FROM pg_sleep(:seconds);
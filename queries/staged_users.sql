@@ -0,0 +1,17 @@
+--: StagedUserPosts()
+
+--! create_staged_users
+CREATE TEMP TABLE staged_users (user_id UUID PRIMARY KEY, score INTEGER NOT NULL);
+
+--! load_staged_users
+INSERT INTO staged_users (user_id, score) SELECT * FROM unnest(:user_ids::uuid[], :scores::int4[]);
+
+--! staged_user_posts : StagedUserPosts
+SELECT s.user_id, s.score, COUNT(p.id) AS post_count
+FROM staged_users s
+JOIN posts p ON p.user_id = s.user_id
+GROUP BY s.user_id, s.score
+ORDER BY s.user_id;
+
+--! drop_staged_users
+DROP TABLE staged_users;
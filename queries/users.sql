@@ -0,0 +1,46 @@
+--: User(age?, created_at?, updated_at?)
+
+--! insert_user (age?)
+INSERT INTO users (username, email, first_name, last_name, age)
+VALUES (:username, :email, :first_name, :last_name, :age)
+RETURNING id;
+
+--! select_user_by_id : User
+SELECT id, username, email, first_name, last_name, age, created_at, updated_at
+FROM users WHERE id = :id;
+
+--! select_users_limit : User
+SELECT id, username, email, first_name, last_name, age, created_at, updated_at
+FROM users ORDER BY created_at DESC LIMIT :limit;
+
+--! select_users_filtered : User
+SELECT id, username, email, first_name, last_name, age, created_at, updated_at
+FROM users
+WHERE age >= :min_age AND age <= :max_age
+ORDER BY age, username
+LIMIT :limit;
+
+--! update_user
+UPDATE users SET first_name = :first_name, last_name = :last_name, updated_at = NOW() WHERE id = :id;
+
+--! delete_user
+DELETE FROM users WHERE id = :id;
+
+--! search_users_by_name : User
+SELECT id, username, email, first_name, last_name, age, created_at, updated_at
+FROM users
+WHERE first_name ILIKE :pattern OR last_name ILIKE :pattern
+ORDER BY username
+LIMIT :limit;
+
+--! insert_users_unnest (ages[?])
+INSERT INTO users (username, email, first_name, last_name, age)
+SELECT * FROM unnest(:usernames::varchar[], :emails::varchar[], :first_names::varchar[], :last_names::varchar[], :ages::int4[]);
+
+--! insert_user_unless_taken (age?)
+INSERT INTO users (username, email, first_name, last_name, age)
+VALUES (:username, :email, :first_name, :last_name, :age)
+ON CONFLICT (username) DO NOTHING;
+
+--! cleanup
+DELETE FROM users WHERE username LIKE 'bench_user_%';
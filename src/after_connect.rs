@@ -0,0 +1,177 @@
+//! Per-connection init SQL
+//!
+//! Settings that can't travel in the startup packet (see [`crate::session`]),
+//! such as `SET ROLE`, `set_config` for row-level security or a `search_path`
+//! built at runtime, have to run as SQL on every new connection. Each driver
+//! has its own hook for that (sqlx's `after_connect`, deadpool's
+//! `post_create`, r2d2's and bb8's connection customizers, for mobc, which
+//! has none, a manager that runs the SQL after connecting, and for wtx, which
+//! has no pool here, a call right after connecting); [`AfterConnect`]
+//! holds the SQL once and hands each driver its hook, so every backend pays
+//! the same extra round trip per connection and none per query.
+
+use crate::bench_wtx::WtxExecutor;
+use deadpool_postgres::{Hook, HookError};
+use diesel::connection::SimpleConnection;
+use diesel::r2d2::CustomizeConnection;
+use diesel::PgConnection;
+use mobc::Manager;
+use mobc_postgres::PgConnectionManager;
+use sqlx::postgres::PgPoolOptions;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio_postgres::NoTls;
+use wtx::database::Executor as _;
+
+/// SQL every new connection runs before its first use
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AfterConnect {
+    statements: Vec<String>,
+}
+
+impl AfterConnect {
+    /// Read `BENCH_AFTER_CONNECT`, one or more `;`-separated statements
+    pub fn from_env() -> Self {
+        match std::env::var("BENCH_AFTER_CONNECT") {
+            Ok(sql) if !sql.trim().is_empty() => Self::default().with_sql(&sql),
+            _ => Self::default(),
+        }
+    }
+
+    /// Also run `sql` (which may hold several `;`-separated statements) after the ones already set
+    pub fn with_sql(mut self, sql: &str) -> Self {
+        self.statements.push(sql.trim().trim_end_matches(';').to_string());
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.statements.is_empty()
+    }
+
+    /// Every statement as one simple-protocol query string, or `None` if there are none
+    pub fn sql(&self) -> Option<String> {
+        (!self.is_empty()).then(|| self.statements.join("; "))
+    }
+
+    /// Run the statements on a new tokio-postgres (or clorinde) connection
+    pub async fn run_tokio_postgres(&self, client: &tokio_postgres::Client) -> Result<(), tokio_postgres::Error> {
+        match self.sql() {
+            Some(sql) => client.batch_execute(&sql).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Run the statements on a new blocking `postgres` connection
+    pub fn run_postgres(&self, client: &mut postgres::Client) -> Result<(), postgres::Error> {
+        match self.sql() {
+            Some(sql) => client.batch_execute(&sql),
+            None => Ok(()),
+        }
+    }
+
+    /// Run the statements on a new sqlx (or SeaORM) connection
+    pub async fn run_sqlx(&self, conn: &mut sqlx::PgConnection) -> Result<(), sqlx::Error> {
+        match self.sql() {
+            Some(sql) => sqlx::Executor::execute(conn, sqlx::raw_sql(&sql)).await.map(|_| ()),
+            None => Ok(()),
+        }
+    }
+
+    /// Run the statements on a new wtx connection
+    pub async fn run_wtx(&self, executor: &mut WtxExecutor) -> Result<(), wtx::Error> {
+        match self.sql() {
+            Some(sql) => executor.execute_ignored(&sql).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Run the statements on a new diesel connection
+    pub fn run_diesel(&self, conn: &mut PgConnection) -> diesel::QueryResult<()> {
+        match self.sql() {
+            Some(sql) => conn.batch_execute(&sql),
+            None => Ok(()),
+        }
+    }
+
+    /// deadpool `post_create` hook running the statements on every connection the pool opens
+    pub fn deadpool_hook(&self) -> Hook {
+        let hook = Arc::new(self.clone());
+        Hook::async_fn(move |client, _| {
+            let hook = hook.clone();
+            Box::pin(async move { hook.run_tokio_postgres(client).await.map_err(HookError::Backend) })
+        })
+    }
+
+    /// bb8 connection customizer running the statements on every connection the pool opens
+    pub fn bb8_customizer(&self) -> Box<dyn bb8::CustomizeConnection<tokio_postgres::Client, tokio_postgres::Error>> {
+        Box::new(Bb8AfterConnect(self.clone()))
+    }
+
+    /// mobc manager connecting through `manager` and running the statements on
+    /// every connection it opens
+    pub fn mobc_manager(&self, manager: PgConnectionManager<NoTls>) -> MobcAfterConnect {
+        MobcAfterConnect { manager, hook: self.clone() }
+    }
+
+    /// `options` running the statements on every connection the sqlx pool opens
+    pub fn sqlx_pool_options(&self, options: PgPoolOptions) -> PgPoolOptions {
+        if self.is_empty() {
+            return options;
+        }
+        let hook = Arc::new(self.clone());
+        options.after_connect(move |conn, _| {
+            let hook = hook.clone();
+            Box::pin(async move { hook.run_sqlx(conn).await })
+        })
+    }
+
+    /// r2d2 connection customizer running the statements on every connection the pool opens
+    pub fn diesel_customizer(&self) -> Box<dyn CustomizeConnection<PgConnection, diesel::r2d2::Error>> {
+        Box::new(DieselAfterConnect(self.clone()))
+    }
+}
+
+#[derive(Debug)]
+struct DieselAfterConnect(AfterConnect);
+
+impl CustomizeConnection<PgConnection, diesel::r2d2::Error> for DieselAfterConnect {
+    fn on_acquire(&self, conn: &mut PgConnection) -> Result<(), diesel::r2d2::Error> {
+        self.0.run_diesel(conn).map_err(diesel::r2d2::Error::QueryError)
+    }
+}
+
+#[derive(Debug)]
+struct Bb8AfterConnect(AfterConnect);
+
+impl bb8::CustomizeConnection<tokio_postgres::Client, tokio_postgres::Error> for Bb8AfterConnect {
+    fn on_acquire<'a>(
+        &'a self,
+        client: &'a mut tokio_postgres::Client,
+    ) -> Pin<Box<dyn Future<Output = Result<(), tokio_postgres::Error>> + Send + 'a>> {
+        Box::pin(self.0.run_tokio_postgres(client))
+    }
+}
+
+/// A mobc-postgres manager whose connections run [`AfterConnect`]'s statements
+/// once opened, see [`AfterConnect::mobc_manager`]
+pub struct MobcAfterConnect {
+    manager: PgConnectionManager<NoTls>,
+    hook: AfterConnect,
+}
+
+#[mobc::async_trait]
+impl Manager for MobcAfterConnect {
+    type Connection = tokio_postgres::Client;
+    type Error = tokio_postgres::Error;
+
+    async fn connect(&self) -> Result<Self::Connection, Self::Error> {
+        let client = self.manager.connect().await?;
+        self.hook.run_tokio_postgres(&client).await?;
+        Ok(client)
+    }
+
+    async fn check(&self, conn: Self::Connection) -> Result<Self::Connection, Self::Error> {
+        self.manager.check(conn).await
+    }
+}
@@ -0,0 +1,172 @@
+//! Fairness audit
+//!
+//! Runs each benchmark scenario once per backend with `pg_stat_statements`
+//! tracking enabled and records the statements the server actually executed,
+//! so extra round trips or different SQL issued by an ORM are visible next to
+//! the raw drivers.
+
+use crate::scenario::SCENARIOS;
+use crate::DatabaseBenchmark;
+use std::collections::BTreeMap;
+use std::fmt;
+use tokio_postgres::Client;
+
+/// First iteration passed to the scenarios, so the audit inserts users clear
+/// of the ranges the benchmarks use
+const FIRST_ITERATION: usize = 9_000_000;
+
+/// A normalized statement and how often it ran during one operation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Statement {
+    pub query: String,
+    pub calls: i64,
+}
+
+impl Statement {
+    /// Leading SQL keyword (`SELECT`, `INSERT`, `BEGIN`, ...)
+    pub fn verb(&self) -> String {
+        self.query
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_uppercase()
+    }
+}
+
+/// Statements executed by one backend for one operation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperationAudit {
+    pub operation: &'static str,
+    pub backend: &'static str,
+    pub statements: Vec<Statement>,
+}
+
+impl OperationAudit {
+    /// Number of statements executed, i.e. round trips to the server
+    pub fn total_calls(&self) -> i64 {
+        self.statements.iter().map(|s| s.calls).sum()
+    }
+
+    fn calls_per_verb(&self) -> BTreeMap<String, i64> {
+        let mut verbs = BTreeMap::new();
+        for statement in &self.statements {
+            *verbs.entry(statement.verb()).or_insert(0) += statement.calls;
+        }
+        verbs
+    }
+
+    /// How this backend's statements differ from `baseline`'s, by statement
+    /// kind, e.g. `["+1 BEGIN", "+1 COMMIT", "-1 INSERT"]`; empty if they match
+    pub fn differences(&self, baseline: &OperationAudit) -> Vec<String> {
+        let (ours, theirs) = (self.calls_per_verb(), baseline.calls_per_verb());
+        let mut verbs: Vec<&String> = ours.keys().chain(theirs.keys()).collect();
+        verbs.sort();
+        verbs.dedup();
+
+        verbs
+            .into_iter()
+            .filter_map(|verb| {
+                let delta = ours.get(verb).unwrap_or(&0) - theirs.get(verb).unwrap_or(&0);
+                (delta != 0).then(|| format!("{:+} {}", delta, verb))
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug)]
+pub enum AuditError<E> {
+    /// Reading or resetting `pg_stat_statements` failed
+    Stats(tokio_postgres::Error),
+    /// The backend failed to run an operation
+    Backend(E),
+}
+
+impl<E: fmt::Debug> fmt::Display for AuditError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuditError::Stats(e) => write!(f, "pg_stat_statements: {}", e),
+            AuditError::Backend(e) => write!(f, "backend error: {:?}", e),
+        }
+    }
+}
+
+impl<E: fmt::Debug> std::error::Error for AuditError<E> {}
+
+/// Whether `pg_stat_statements` is loaded and can be queried.
+///
+/// The extension is created if missing, but it only works when the server was
+/// started with `shared_preload_libraries=pg_stat_statements`.
+pub async fn available(client: &Client) -> bool {
+    client
+        .batch_execute(
+            "CREATE EXTENSION IF NOT EXISTS pg_stat_statements;
+             SELECT 1 FROM pg_stat_statements LIMIT 1",
+        )
+        .await
+        .is_ok()
+}
+
+pub(crate) async fn reset(client: &Client) -> Result<(), tokio_postgres::Error> {
+    client.execute("SELECT pg_stat_statements_reset()", &[]).await?;
+    Ok(())
+}
+
+/// Statements recorded in the current database since the last reset, leaving
+/// out the audit's own queries
+pub(crate) async fn recorded_statements(client: &Client) -> Result<Vec<Statement>, tokio_postgres::Error> {
+    let rows = client
+        .query(
+            "SELECT query, calls
+             FROM pg_stat_statements
+             WHERE dbid = (SELECT oid FROM pg_database WHERE datname = current_database())
+               AND query NOT LIKE '%pg_stat_statements%'
+             ORDER BY calls DESC, query",
+            &[],
+        )
+        .await?;
+
+    Ok(rows
+        .iter()
+        .map(|r| Statement {
+            query: r.get::<_, String>(0).split_whitespace().collect::<Vec<_>>().join(" "),
+            calls: r.get(1),
+        })
+        .collect())
+}
+
+/// Audit every scenario in [`SCENARIOS`] for one backend, at its typical size.
+///
+/// Each scenario runs once untracked first, so connection setup and statement
+/// preparation are not attributed to it, then once more between a reset and a
+/// read of `pg_stat_statements`, and is torn down before the next. Lookups and
+/// updates use the seeded users. Nothing else should use the database meanwhile.
+pub async fn audit_backend<B: DatabaseBenchmark>(
+    backend: &'static str,
+    client: &Client,
+) -> Result<Vec<OperationAudit>, AuditError<B::Error>> {
+    let conn = B::connect().await.map_err(AuditError::Backend)?;
+    B::cleanup(&conn).await.map_err(AuditError::Backend)?;
+
+    let mut iteration = FIRST_ITERATION;
+    let mut audits = Vec::with_capacity(SCENARIOS.len());
+    for scenario in SCENARIOS {
+        let fixture = scenario.setup::<B>(&conn).await.map_err(AuditError::Backend)?;
+        let size = scenario.typical_size();
+        iteration += 1;
+        scenario.run::<B>(&conn, &fixture, size, iteration).await.map_err(AuditError::Backend)?;
+
+        reset(client).await.map_err(AuditError::Stats)?;
+        iteration += 1;
+        scenario.run::<B>(&conn, &fixture, size, iteration).await.map_err(AuditError::Backend)?;
+        let statements = recorded_statements(client).await.map_err(AuditError::Stats)?;
+        scenario.teardown::<B>(&conn).await.map_err(AuditError::Backend)?;
+
+        audits.push(OperationAudit {
+            operation: scenario.name,
+            backend,
+            statements,
+        });
+    }
+
+    Ok(audits)
+}
@@ -0,0 +1,214 @@
+//! Write latency under autovacuum
+//!
+//! Steady-state benchmarks stop before enough dead tuples pile up for
+//! autovacuum to start on `users`. [`soak`] keeps a backend writing for
+//! minutes: every step inserts a user, renames it and deletes it again, three
+//! dead-tuple producers that leave the table's live rows as they were. A
+//! [`VacuumMonitor`] samples `pg_stat_activity` on its own connection meanwhile,
+//! and every write's latency is filed under the [`Phase`] it ran in: before the
+//! first autovacuum of `users` was seen, while one was running, or after.
+
+use crate::{DatabaseBenchmark, NewUser};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use tokio_postgres::Client;
+
+/// Soak time per backend unless given on the command line; long enough for
+/// several autovacuum naps (one minute by default)
+pub const DEFAULT_DURATION: Duration = Duration::from_secs(180);
+
+/// How often the monitor looks at `pg_stat_activity`
+pub const SAMPLE_INTERVAL: Duration = Duration::from_millis(50);
+
+/// First generated user index, clear of the ranges the benchmarks insert
+const FIRST_USER: usize = 8_000_000;
+
+/// Whether an autovacuum worker is vacuuming `users` right now
+pub async fn vacuuming(client: &Client) -> Result<bool, tokio_postgres::Error> {
+    let row = client
+        .query_one(
+            "SELECT EXISTS (SELECT 1 FROM pg_stat_activity
+             WHERE backend_type = 'autovacuum worker' AND query LIKE 'autovacuum: VACUUM%public.users%')",
+            &[],
+        )
+        .await?;
+    Ok(row.get(0))
+}
+
+/// How often autovacuum has vacuumed `users`, as the server last flushed it
+pub async fn autovacuum_count(client: &Client) -> Result<i64, tokio_postgres::Error> {
+    let row = client
+        .query_one(
+            "SELECT autovacuum_count FROM pg_stat_user_tables WHERE schemaname = 'public' AND relname = 'users'",
+            &[],
+        )
+        .await?;
+    Ok(row.get(0))
+}
+
+/// When a write ran, relative to autovacuum on `users`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Before,
+    During,
+    After,
+}
+
+impl Phase {
+    pub const ALL: [Phase; 3] = [Phase::Before, Phase::During, Phase::After];
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Phase::Before => "before",
+            Phase::During => "during",
+            Phase::After => "after",
+        })
+    }
+}
+
+/// The autovacuum state sampled by a [`VacuumMonitor`]
+#[derive(Debug, Default)]
+struct VacuumState {
+    vacuuming: AtomicBool,
+    seen: AtomicBool,
+    samples: AtomicUsize,
+    active_samples: AtomicUsize,
+    /// Times a sample found a vacuum where the previous one found none
+    starts: AtomicUsize,
+}
+
+/// Samples whether autovacuum is working on `users` every [`SAMPLE_INTERVAL`]
+pub struct VacuumMonitor {
+    state: Arc<VacuumState>,
+    task: JoinHandle<Result<(), tokio_postgres::Error>>,
+}
+
+impl VacuumMonitor {
+    /// Start sampling over `client`, which the monitor keeps until [`VacuumMonitor::stop`]
+    pub fn start(client: Client) -> Self {
+        let state = Arc::new(VacuumState::default());
+        let sampled = Arc::clone(&state);
+        let task = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(SAMPLE_INTERVAL);
+            loop {
+                interval.tick().await;
+                let vacuuming = vacuuming(&client).await?;
+                sampled.samples.fetch_add(1, Ordering::Relaxed);
+                if vacuuming {
+                    sampled.active_samples.fetch_add(1, Ordering::Relaxed);
+                    sampled.seen.store(true, Ordering::Relaxed);
+                    if !sampled.vacuuming.swap(true, Ordering::Relaxed) {
+                        sampled.starts.fetch_add(1, Ordering::Relaxed);
+                    }
+                } else {
+                    sampled.vacuuming.store(false, Ordering::Relaxed);
+                }
+            }
+        });
+        Self { state, task }
+    }
+
+    /// The phase a write starting now runs in
+    pub fn phase(&self) -> Phase {
+        if self.state.vacuuming.load(Ordering::Relaxed) {
+            Phase::During
+        } else if self.state.seen.load(Ordering::Relaxed) {
+            Phase::After
+        } else {
+            Phase::Before
+        }
+    }
+
+    /// Stop sampling; what the samples saw, or the error that ended them early
+    pub async fn stop(self) -> Result<Sampled, tokio_postgres::Error> {
+        self.task.abort();
+        if let Ok(Err(e)) = self.task.await {
+            return Err(e);
+        }
+        Ok(Sampled {
+            samples: self.state.samples.load(Ordering::Relaxed),
+            active_samples: self.state.active_samples.load(Ordering::Relaxed),
+            vacuums_seen: self.state.starts.load(Ordering::Relaxed),
+        })
+    }
+}
+
+/// What a [`VacuumMonitor`]'s samples saw
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Sampled {
+    pub samples: usize,
+    /// Samples that found autovacuum working on `users`
+    pub active_samples: usize,
+    /// Vacuums the samples caught; short ones fall between two samples
+    pub vacuums_seen: usize,
+}
+
+/// Write latencies of one [`Phase`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PhaseLatencies {
+    latencies: Vec<Duration>,
+}
+
+impl PhaseLatencies {
+    pub fn record(&mut self, latency: Duration) {
+        self.latencies.push(latency);
+    }
+
+    pub fn writes(&self) -> usize {
+        self.latencies.len()
+    }
+
+    pub fn mean(&self) -> Duration {
+        self.latencies.iter().sum::<Duration>() / self.latencies.len().max(1) as u32
+    }
+
+    /// The `q` quantile, e.g. 0.99; zero without writes
+    pub fn quantile(&self, q: f64) -> Duration {
+        let mut sorted = self.latencies.clone();
+        sorted.sort();
+        match sorted.len() {
+            0 => Duration::ZERO,
+            len => sorted[((len - 1) as f64 * q).round() as usize],
+        }
+    }
+}
+
+/// Write latencies per [`Phase`], in the order of [`Phase::ALL`]
+pub type Latencies = [PhaseLatencies; 3];
+
+/// Insert, rename and delete users on `conn` until `duration` has passed,
+/// filing every write's latency under `monitor`'s phase when it started
+pub async fn soak<B: DatabaseBenchmark>(
+    conn: &B::Connection,
+    monitor: &VacuumMonitor,
+    duration: Duration,
+) -> Result<Latencies, B::Error> {
+    let mut latencies = Latencies::default();
+    let mut timed = |phase: Phase, start: Instant| {
+        let index = Phase::ALL.iter().position(|&p| p == phase).unwrap();
+        latencies[index].record(start.elapsed());
+    };
+
+    let run_start = Instant::now();
+    let mut index = FIRST_USER;
+    while run_start.elapsed() < duration {
+        let (phase, start) = (monitor.phase(), Instant::now());
+        let id = B::insert_user(conn, &NewUser::generate(index)).await?;
+        timed(phase, start);
+
+        let (phase, start) = (monitor.phase(), Instant::now());
+        B::update_user(conn, id, "SoakFirst", "SoakLast").await?;
+        timed(phase, start);
+
+        let (phase, start) = (monitor.phase(), Instant::now());
+        B::delete_user(conn, id).await?;
+        timed(phase, start);
+        index += 1;
+    }
+    Ok(latencies)
+}
@@ -0,0 +1,37 @@
+//! Multi-statement batches
+//!
+//! The write-intensive step (insert a user, insert a post for it, update the
+//! user) takes three round trips through the regular methods. Sent as one
+//! simple-protocol query string it takes one, which PostgreSQL runs as a
+//! single implicit transaction. The simple protocol has no parameters, so
+//! every value is inlined as a literal, and the user id is generated by the
+//! client because the post has to reference it.
+
+use crate::{NewPost, NewUser};
+use uuid::Uuid;
+
+/// `value` as a SQL string literal, assuming `standard_conforming_strings` (the default)
+pub fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// The write-intensive step as one multi-statement string; `post.user_id` is replaced by `user_id`
+pub fn write_step_sql(user_id: Uuid, user: &NewUser, post: &NewPost, first_name: &str, last_name: &str) -> String {
+    let age = user.age.map_or("NULL".to_string(), |age| age.to_string());
+    format!(
+        "INSERT INTO users (id, username, email, first_name, last_name, age) VALUES ('{id}', {}, {}, {}, {}, {});
+         INSERT INTO posts (user_id, title, content, status) VALUES ('{id}', {}, {}, {});
+         UPDATE users SET first_name = {}, last_name = {}, updated_at = NOW() WHERE id = '{id}'",
+        quote_literal(&user.username),
+        quote_literal(&user.email),
+        quote_literal(&user.first_name),
+        quote_literal(&user.last_name),
+        age,
+        quote_literal(&post.title),
+        quote_literal(&post.content),
+        quote_literal(&post.status),
+        quote_literal(first_name),
+        quote_literal(last_name),
+        id = user_id,
+    )
+}
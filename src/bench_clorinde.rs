@@ -1,19 +1,88 @@
 //! Clorinde benchmark wrapper
 //!
-//! This module wraps the generated Clorinde queries for benchmarking.
+//! This module wraps the queries clorinde generates from `queries/*.sql` into
+//! the `clorinde_queries` crate (see `clorinde.toml`) for benchmarking. Each
+//! query is a function returning a statement whose `bind` runs it on a client:
+//! on a plain tokio-postgres client the SQL text is sent and prepared again on
+//! every call, while a statement from [`PreparedStatements`] was prepared once.
 
-use crate::{Comment, NewComment, NewPost, NewUser, Post, User, DATABASE_URL};
+use crate::after_connect::AfterConnect;
+use crate::capability::Capabilities;
+use crate::bench_tokio_postgres::transactional;
+use crate::mapping;
+use crate::tls::{self, TlsStack};
+use crate::{batch, Comment, CommentKey, DatabaseBenchmark, NewComment, NewPost, NewUser, Post, User, database_url};
+use clorinde_queries::queries::{comments, posts, session, users};
+use futures::{pin_mut, TryStreamExt};
+use tokio_postgres::error::SqlState;
 use tokio_postgres::{Client, NoTls};
 use uuid::Uuid;
 
 pub use clorinde_queries::queries;
-pub use clorinde_queries::prepared::PreparedStatements;
 
 pub struct ClorindeBench;
 
+/// The statements of the [`DatabaseBenchmark`] operations, each prepared once
+/// on a client with the generated `prepare`, so their `bind` reuses it
+pub struct PreparedStatements {
+    pub insert_user: users::InsertUserStmt,
+    pub select_user_by_id: users::SelectUserByIdStmt,
+    pub select_users_limit: users::SelectUsersLimitStmt,
+    pub select_users_filtered: users::SelectUsersFilteredStmt,
+    pub update_user: users::UpdateUserStmt,
+    pub delete_user: users::DeleteUserStmt,
+    pub insert_post: posts::InsertPostStmt,
+    pub select_posts_with_user: posts::SelectPostsWithUserStmt,
+    pub select_users_posts_comments: posts::SelectUsersPostsCommentsStmt,
+    pub count_posts_per_user: posts::CountPostsPerUserStmt,
+    pub count_posts_per_tag: posts::CountPostsPerTagStmt,
+    pub insert_comment: comments::InsertCommentStmt,
+    pub select_comments_for_post: comments::SelectCommentsForPostStmt,
+    pub select_comments_for_post_before: comments::SelectCommentsForPostBeforeStmt,
+    pub select_posts_by_status: posts::SelectPostsByStatusStmt,
+    pub increment_view_count: posts::IncrementViewCountStmt,
+    pub search_users_by_name: users::SearchUsersByNameStmt,
+    pub cleanup: users::CleanupStmt,
+}
+
+impl PreparedStatements {
+    pub async fn new(client: &Client) -> Result<Self, tokio_postgres::Error> {
+        Ok(Self {
+            insert_user: users::insert_user().prepare(client).await?,
+            select_user_by_id: users::select_user_by_id().prepare(client).await?,
+            select_users_limit: users::select_users_limit().prepare(client).await?,
+            select_users_filtered: users::select_users_filtered().prepare(client).await?,
+            update_user: users::update_user().prepare(client).await?,
+            delete_user: users::delete_user().prepare(client).await?,
+            insert_post: posts::insert_post().prepare(client).await?,
+            select_posts_with_user: posts::select_posts_with_user().prepare(client).await?,
+            select_users_posts_comments: posts::select_users_posts_comments().prepare(client).await?,
+            count_posts_per_user: posts::count_posts_per_user().prepare(client).await?,
+            count_posts_per_tag: posts::count_posts_per_tag().prepare(client).await?,
+            insert_comment: comments::insert_comment().prepare(client).await?,
+            select_comments_for_post: comments::select_comments_for_post().prepare(client).await?,
+            select_comments_for_post_before: comments::select_comments_for_post_before().prepare(client).await?,
+            select_posts_by_status: posts::select_posts_by_status().prepare(client).await?,
+            increment_view_count: posts::increment_view_count().prepare(client).await?,
+            search_users_by_name: users::search_users_by_name().prepare(client).await?,
+            cleanup: users::cleanup().prepare(client).await?,
+        })
+    }
+}
+
 impl ClorindeBench {
     pub async fn connect() -> Result<Client, tokio_postgres::Error> {
-        let (client, connection) = tokio_postgres::connect(DATABASE_URL, NoTls).await?;
+        Self::connect_to(&database_url()).await
+    }
+
+    /// Connect without TLS to `url` instead of [`database_url`]
+    pub async fn connect_to(url: &str) -> Result<Client, tokio_postgres::Error> {
+        Self::connect_with_hook(url, &AfterConnect::from_env()).await
+    }
+
+    /// Connect without TLS to `url`, running `hook` instead of the environment's init SQL
+    pub async fn connect_with_hook(url: &str, hook: &AfterConnect) -> Result<Client, tokio_postgres::Error> {
+        let (client, connection) = tokio_postgres::connect(url, NoTls).await?;
 
         tokio::spawn(async move {
             if let Err(e) = connection.await {
@@ -21,9 +90,15 @@ impl ClorindeBench {
             }
         });
 
+        hook.run_tokio_postgres(&client).await?;
         Ok(client)
     }
 
+    /// Connect through `stack`; the generated queries run on a plain tokio-postgres client
+    pub async fn connect_tls(stack: TlsStack) -> Result<Client, tls::ConnectError> {
+        tls::connect_tokio_postgres(stack).await
+    }
+
     pub async fn prepare(client: &Client) -> Result<PreparedStatements, tokio_postgres::Error> {
         PreparedStatements::new(client).await
     }
@@ -31,15 +106,10 @@ impl ClorindeBench {
     // Non-prepared statement versions (for fair comparison with unprepared queries)
 
     pub async fn insert_user(client: &Client, user: &NewUser) -> Result<Uuid, tokio_postgres::Error> {
-        queries::insert_user(
-            client,
-            &user.username,
-            &user.email,
-            &user.first_name,
-            &user.last_name,
-            user.age,
-        )
-        .await
+        users::insert_user()
+            .bind(client, &user.username, &user.email, &user.first_name, &user.last_name, &user.age)
+            .one()
+            .await
     }
 
     pub async fn insert_users_batch(
@@ -58,36 +128,18 @@ impl ClorindeBench {
         client: &Client,
         id: Uuid,
     ) -> Result<Option<User>, tokio_postgres::Error> {
-        let user = queries::select_user_by_id(client, id).await?;
-        Ok(user.map(|u| User {
-            id: u.id,
-            username: u.username,
-            email: u.email,
-            first_name: u.first_name,
-            last_name: u.last_name,
-            age: u.age,
-            created_at: u.created_at,
-            updated_at: u.updated_at,
-        }))
+        let user = users::select_user_by_id().bind(client, &id).opt().await?;
+        Ok(user.map(User::from))
     }
 
     pub async fn select_users_limit(
         client: &Client,
         limit: i64,
     ) -> Result<Vec<User>, tokio_postgres::Error> {
-        let users = queries::select_users_limit(client, limit).await?;
+        let users = users::select_users_limit().bind(client, &limit).all().await?;
         Ok(users
             .into_iter()
-            .map(|u| User {
-                id: u.id,
-                username: u.username,
-                email: u.email,
-                first_name: u.first_name,
-                last_name: u.last_name,
-                age: u.age,
-                created_at: u.created_at,
-                updated_at: u.updated_at,
-            })
+            .map(User::from)
             .collect())
     }
 
@@ -97,19 +149,13 @@ impl ClorindeBench {
         max_age: i32,
         limit: i64,
     ) -> Result<Vec<User>, tokio_postgres::Error> {
-        let users = queries::select_users_filtered(client, min_age, max_age, limit).await?;
+        let users = users::select_users_filtered()
+            .bind(client, &min_age, &max_age, &limit)
+            .all()
+            .await?;
         Ok(users
             .into_iter()
-            .map(|u| User {
-                id: u.id,
-                username: u.username,
-                email: u.email,
-                first_name: u.first_name,
-                last_name: u.last_name,
-                age: u.age,
-                created_at: u.created_at,
-                updated_at: u.updated_at,
-            })
+            .map(User::from)
             .collect())
     }
 
@@ -119,50 +165,30 @@ impl ClorindeBench {
         first_name: &str,
         last_name: &str,
     ) -> Result<bool, tokio_postgres::Error> {
-        let rows = queries::update_user(client, id, first_name, last_name).await?;
+        let rows = users::update_user().bind(client, &first_name, &last_name, &id).await?;
         Ok(rows > 0)
     }
 
     pub async fn delete_user(client: &Client, id: Uuid) -> Result<bool, tokio_postgres::Error> {
-        let rows = queries::delete_user(client, id).await?;
+        let rows = users::delete_user().bind(client, &id).await?;
         Ok(rows > 0)
     }
 
     pub async fn insert_post(client: &Client, post: &NewPost) -> Result<Uuid, tokio_postgres::Error> {
-        queries::insert_post(client, post.user_id, &post.title, &post.content, &post.status).await
+        posts::insert_post()
+            .bind(client, &post.user_id, &post.title, &post.content, &post.status)
+            .one()
+            .await
     }
 
     pub async fn select_posts_with_user(
         client: &Client,
         limit: i64,
     ) -> Result<Vec<(Post, User)>, tokio_postgres::Error> {
-        let results = queries::select_posts_with_user(client, limit).await?;
+        let results = posts::select_posts_with_user().bind(client, &limit).all().await?;
         Ok(results
             .into_iter()
-            .map(|r| {
-                (
-                    Post {
-                        id: r.post_id,
-                        user_id: r.user_id,
-                        title: r.title,
-                        content: r.content,
-                        status: r.status,
-                        view_count: r.view_count,
-                        created_at: r.post_created_at,
-                        updated_at: r.post_updated_at,
-                    },
-                    User {
-                        id: r.user_id,
-                        username: r.username,
-                        email: r.email,
-                        first_name: r.first_name,
-                        last_name: r.last_name,
-                        age: r.age,
-                        created_at: r.user_created_at,
-                        updated_at: r.user_updated_at,
-                    },
-                )
-            })
+            .map(mapping::post_with_user)
             .collect())
     }
 
@@ -170,71 +196,90 @@ impl ClorindeBench {
         client: &Client,
         limit: i64,
     ) -> Result<Vec<(User, Post, Comment)>, tokio_postgres::Error> {
-        let results = queries::select_users_posts_comments(client, limit).await?;
+        let results = posts::select_users_posts_comments().bind(client, &limit).all().await?;
         Ok(results
             .into_iter()
-            .map(|r| {
-                (
-                    User {
-                        id: r.user_id,
-                        username: r.username,
-                        email: r.email,
-                        first_name: r.first_name,
-                        last_name: r.last_name,
-                        age: r.age,
-                        created_at: r.user_created_at,
-                        updated_at: r.user_updated_at,
-                    },
-                    Post {
-                        id: r.post_id,
-                        user_id: r.user_id,
-                        title: r.title,
-                        content: r.content,
-                        status: r.status,
-                        view_count: r.view_count,
-                        created_at: r.post_created_at,
-                        updated_at: r.post_updated_at,
-                    },
-                    Comment {
-                        id: r.comment_id,
-                        post_id: r.post_id,
-                        user_id: r.user_id,
-                        content: r.comment_content,
-                        created_at: r.comment_created_at,
-                    },
-                )
-            })
+            .map(mapping::user_post_comment)
             .collect())
     }
 
+    pub async fn select_comments_for_post(
+        client: &Client,
+        post_id: Uuid,
+        limit: i64,
+        before: Option<CommentKey>,
+    ) -> Result<Vec<Comment>, tokio_postgres::Error> {
+        let comments = match before {
+            None => comments::select_comments_for_post().bind(client, &post_id, &limit).all().await?,
+            Some(key) => {
+                comments::select_comments_for_post_before()
+                    .bind(client, &post_id, &key.created_at, &key.id, &limit)
+                    .all()
+                    .await?
+            }
+        };
+        Ok(comments.into_iter().map(Comment::from).collect())
+    }
+
     pub async fn count_posts_per_user(
         client: &Client,
     ) -> Result<Vec<(Uuid, i64)>, tokio_postgres::Error> {
-        let results = queries::count_posts_per_user(client).await?;
+        let results = posts::count_posts_per_user().bind(client).all().await?;
         Ok(results
             .into_iter()
             .map(|r| (r.user_id, r.post_count))
             .collect())
     }
 
+    pub async fn count_posts_per_tag(
+        client: &Client,
+    ) -> Result<Vec<(Uuid, i64)>, tokio_postgres::Error> {
+        let results = posts::count_posts_per_tag().bind(client).all().await?;
+        Ok(results
+            .into_iter()
+            .map(|r| (r.tag_id, r.post_count))
+            .collect())
+    }
+
+    /// The generated queries in one transaction, begun and committed on the
+    /// underlying client as [`transactional`] does for tokio-postgres
     pub async fn insert_user_with_posts(
         client: &Client,
         user: &NewUser,
         posts: &[NewPost],
     ) -> Result<Uuid, tokio_postgres::Error> {
-        let user_id = Self::insert_user(client, user).await?;
+        transactional(client, async {
+            let user_id = Self::insert_user(client, user).await?;
 
-        for post in posts {
-            let mut post = post.clone();
-            post.user_id = user_id;
-            Self::insert_post(client, &post).await?;
-        }
+            for post in posts {
+                let mut post = post.clone();
+                post.user_id = user_id;
+                Self::insert_post(client, &post).await?;
+            }
+
+            Ok(user_id)
+        })
+        .await
+    }
 
+    /// Insert a user and a post for it, then update the user, as one multi-statement batch.
+    ///
+    /// Clorinde generates one function per statement, so this goes through the underlying client.
+    pub async fn insert_user_post_update_batched(
+        client: &Client,
+        user: &NewUser,
+        post: &NewPost,
+        first_name: &str,
+        last_name: &str,
+    ) -> Result<Uuid, tokio_postgres::Error> {
+        let user_id = Uuid::new_v4();
+        let sql = batch::write_step_sql(user_id, user, post, first_name, last_name);
+        client.batch_execute(&sql).await?;
         Ok(user_id)
     }
 
     pub async fn cleanup(client: &Client) -> Result<(), tokio_postgres::Error> {
-        queries::cleanup(client).await?;
+        users::cleanup().bind(client).await?;
         Ok(())
     }
 
@@ -244,7 +289,10 @@ impl ClorindeBench {
         client: &Client,
         comment: &NewComment,
     ) -> Result<Uuid, tokio_postgres::Error> {
-        queries::insert_comment(client, comment.post_id, comment.user_id, &comment.content).await
+        comments::insert_comment()
+            .bind(client, &comment.post_id, &comment.user_id, &comment.content)
+            .one()
+            .await
     }
 
     pub async fn select_posts_by_status(
@@ -252,27 +300,46 @@ impl ClorindeBench {
         status: &str,
         limit: i64,
     ) -> Result<Vec<Post>, tokio_postgres::Error> {
-        let posts = queries::select_posts_by_status(client, status, limit).await?;
+        let posts = posts::select_posts_by_status().bind(client, &status, &limit).all().await?;
         Ok(posts
             .into_iter()
-            .map(|p| Post {
-                id: p.id,
-                user_id: p.user_id,
-                title: p.title,
-                content: p.content,
-                status: p.status,
-                view_count: p.view_count,
-                created_at: p.created_at,
-                updated_at: p.updated_at,
-            })
+            .map(Post::from)
             .collect())
     }
 
+    pub async fn select_posts_repeated(
+        client: &Client,
+        copies: i32,
+    ) -> Result<Vec<Post>, tokio_postgres::Error> {
+        let posts = posts::select_posts_repeated().bind(client, &copies).all().await?;
+        Ok(posts
+            .into_iter()
+            .map(Post::from)
+            .collect())
+    }
+
+    pub async fn stream_posts_repeated(
+        client: &Client,
+        copies: i32,
+        mut each: impl FnMut(Post),
+    ) -> Result<usize, tokio_postgres::Error> {
+        let statement = posts::select_posts_repeated();
+        let posts = statement.bind(client, &copies).iter().await?;
+        pin_mut!(posts);
+
+        let mut count = 0;
+        while let Some(post) = posts.try_next().await? {
+            each(Post::from(post));
+            count += 1;
+        }
+        Ok(count)
+    }
+
     pub async fn increment_view_count(
         client: &Client,
         post_id: Uuid,
     ) -> Result<(), tokio_postgres::Error> {
-        queries::increment_view_count(client, post_id).await?;
+        posts::increment_view_count().bind(client, &post_id).await?;
         Ok(())
     }
 
@@ -281,19 +348,175 @@ impl ClorindeBench {
         pattern: &str,
         limit: i64,
     ) -> Result<Vec<User>, tokio_postgres::Error> {
-        let users = queries::search_users_by_name(client, pattern, limit).await?;
+        let pattern = format!("%{pattern}%");
+        let users = users::search_users_by_name().bind(client, &pattern, &limit).all().await?;
         Ok(users
             .into_iter()
-            .map(|u| User {
-                id: u.id,
-                username: u.username,
-                email: u.email,
-                first_name: u.first_name,
-                last_name: u.last_name,
-                age: u.age,
-                created_at: u.created_at,
-                updated_at: u.updated_at,
-            })
+            .map(User::from)
             .collect())
     }
 }
+
+// ============================================================================
+// Statement timeout and cancellation
+// ============================================================================
+
+impl ClorindeBench {
+    /// Lightweight round trip used to check that a connection is still usable
+    pub async fn ping(client: &Client) -> Result<(), tokio_postgres::Error> {
+        session::ping().bind(client).one().await?;
+        Ok(())
+    }
+
+    /// Run a query that keeps the server busy for `sleep_secs`
+    pub async fn pg_sleep(client: &Client, sleep_secs: f64) -> Result<(), tokio_postgres::Error> {
+        session::pg_sleep().bind(client, &sleep_secs).await?;
+        Ok(())
+    }
+
+    /// Run `pg_sleep` under a transaction-scoped `statement_timeout`.
+    ///
+    /// Returns `true` when the server cancelled the statement.
+    pub async fn sleep_with_statement_timeout(
+        client: &Client,
+        timeout_ms: u64,
+        sleep_secs: f64,
+    ) -> Result<bool, tokio_postgres::Error> {
+        client
+            .batch_execute(&format!("BEGIN; SET LOCAL statement_timeout = {}", timeout_ms))
+            .await?;
+        let result = session::pg_sleep().bind(client, &sleep_secs).await;
+        client.batch_execute("ROLLBACK").await?;
+
+        match result {
+            Ok(_) => Ok(false),
+            Err(e) if e.code() == Some(&SqlState::QUERY_CANCELED) => Ok(true),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+// ============================================================================
+// DatabaseBenchmark implementation
+// ============================================================================
+
+impl DatabaseBenchmark for ClorindeBench {
+    type Connection = Client;
+    type Error = tokio_postgres::Error;
+
+    async fn connect() -> Result<Self::Connection, Self::Error> {
+        ClorindeBench::connect().await
+    }
+
+    async fn connect_to(url: &str) -> Result<Self::Connection, Self::Error> {
+        ClorindeBench::connect_to(url).await
+    }
+
+    async fn insert_user(conn: &Self::Connection, user: &NewUser) -> Result<Uuid, Self::Error> {
+        ClorindeBench::insert_user(conn, user).await
+    }
+
+    async fn insert_users_batch(conn: &Self::Connection, users: &[NewUser]) -> Result<Vec<Uuid>, Self::Error> {
+        ClorindeBench::insert_users_batch(conn, users).await
+    }
+
+    async fn select_user_by_id(conn: &Self::Connection, id: Uuid) -> Result<Option<User>, Self::Error> {
+        ClorindeBench::select_user_by_id(conn, id).await
+    }
+
+    async fn select_users_limit(conn: &Self::Connection, limit: i64) -> Result<Vec<User>, Self::Error> {
+        ClorindeBench::select_users_limit(conn, limit).await
+    }
+
+    async fn select_users_filtered(conn: &Self::Connection, min_age: i32, max_age: i32, limit: i64) -> Result<Vec<User>, Self::Error> {
+        ClorindeBench::select_users_filtered(conn, min_age, max_age, limit).await
+    }
+
+    async fn update_user(conn: &Self::Connection, id: Uuid, first_name: &str, last_name: &str) -> Result<bool, Self::Error> {
+        ClorindeBench::update_user(conn, id, first_name, last_name).await
+    }
+
+    async fn delete_user(conn: &Self::Connection, id: Uuid) -> Result<bool, Self::Error> {
+        ClorindeBench::delete_user(conn, id).await
+    }
+
+    async fn insert_post(conn: &Self::Connection, post: &NewPost) -> Result<Uuid, Self::Error> {
+        ClorindeBench::insert_post(conn, post).await
+    }
+
+    async fn insert_comment(conn: &Self::Connection, comment: &NewComment) -> Result<Uuid, Self::Error> {
+        ClorindeBench::insert_comment(conn, comment).await
+    }
+
+    async fn select_posts_with_user(conn: &Self::Connection, limit: i64) -> Result<Vec<(Post, User)>, Self::Error> {
+        ClorindeBench::select_posts_with_user(conn, limit).await
+    }
+
+    async fn select_posts_by_status(conn: &Self::Connection, status: &str, limit: i64) -> Result<Vec<Post>, Self::Error> {
+        ClorindeBench::select_posts_by_status(conn, status, limit).await
+    }
+
+    async fn select_posts_repeated(conn: &Self::Connection, copies: i32) -> Result<Vec<Post>, Self::Error> {
+        ClorindeBench::select_posts_repeated(conn, copies).await
+    }
+
+    async fn stream_posts_repeated(conn: &Self::Connection, copies: i32, each: impl FnMut(Post)) -> Result<usize, Self::Error> {
+        ClorindeBench::stream_posts_repeated(conn, copies, each).await
+    }
+
+    async fn search_users_by_name(conn: &Self::Connection, pattern: &str, limit: i64) -> Result<Vec<User>, Self::Error> {
+        ClorindeBench::search_users_by_name(conn, pattern, limit).await
+    }
+
+    async fn select_users_posts_comments(conn: &Self::Connection, limit: i64) -> Result<Vec<(User, Post, Comment)>, Self::Error> {
+        ClorindeBench::select_users_posts_comments(conn, limit).await
+    }
+
+    async fn select_comments_for_post(
+        conn: &Self::Connection,
+        post_id: Uuid,
+        limit: i64,
+        before: Option<CommentKey>,
+    ) -> Result<Vec<Comment>, Self::Error> {
+        ClorindeBench::select_comments_for_post(conn, post_id, limit, before).await
+    }
+
+    async fn count_posts_per_user(conn: &Self::Connection) -> Result<Vec<(Uuid, i64)>, Self::Error> {
+        ClorindeBench::count_posts_per_user(conn).await
+    }
+
+    async fn count_posts_per_tag(conn: &Self::Connection) -> Result<Vec<(Uuid, i64)>, Self::Error> {
+        ClorindeBench::count_posts_per_tag(conn).await
+    }
+
+    async fn insert_user_with_posts(conn: &Self::Connection, user: &NewUser, posts: &[NewPost]) -> Result<Uuid, Self::Error> {
+        ClorindeBench::insert_user_with_posts(conn, user, posts).await
+    }
+
+    async fn insert_user_post_update_batched(
+        conn: &Self::Connection,
+        user: &NewUser,
+        post: &NewPost,
+        first_name: &str,
+        last_name: &str,
+    ) -> Result<Uuid, Self::Error> {
+        ClorindeBench::insert_user_post_update_batched(conn, user, post, first_name, last_name).await
+    }
+
+    async fn cleanup(conn: &Self::Connection) -> Result<(), Self::Error> {
+        ClorindeBench::cleanup(conn).await
+    }
+
+    fn capabilities() -> Capabilities {
+        Capabilities {
+            // Generated queries run on a tokio-postgres client; COPY and
+            // notifications are only reachable through that client
+            shared_transactions: false,
+            streaming: true,
+            copy: false,
+            listen_notify: false,
+            prepared_cache: true,
+            binary_format: true,
+        }
+    }
+}
@@ -1,6 +1,12 @@
 //! Diesel benchmark implementation
 
-use crate::{Comment, NewComment, NewPost, NewUser, Post, User, DATABASE_URL};
+use crate::after_connect::AfterConnect;
+use crate::capability::Capabilities;
+use crate::tls::tls_database_url;
+use crate::{batch, BoxError, Comment, CommentKey, DatabaseBenchmark, NewComment, NewPost, NewUser, Post, User, database_url};
+use std::sync::Mutex;
+use diesel::connection::SimpleConnection;
+use diesel::pg::PgRowByRowLoadingMode;
 use diesel::prelude::*;
 use diesel::r2d2::{ConnectionManager, Pool, PooledConnection};
 use uuid::Uuid;
@@ -94,7 +100,7 @@ pub struct DieselNewUser<'a> {
     pub age: Option<i32>,
 }
 
-#[derive(Queryable, Selectable, Clone, Debug)]
+#[derive(Queryable, QueryableByName, Selectable, Clone, Debug)]
 #[diesel(table_name = posts)]
 pub struct DieselPost {
     pub id: Uuid,
@@ -141,14 +147,81 @@ pub struct DieselBench;
 
 impl DieselBench {
     pub fn connect() -> Result<DbPool, diesel::r2d2::PoolError> {
-        let manager = ConnectionManager::<PgConnection>::new(DATABASE_URL);
-        Pool::builder().max_size(10).build(manager)
+        Self::connect_with_url(&database_url(), 10)
     }
 
     /// Connect with a specific pool size for concurrent benchmarks
     pub fn connect_with_pool_size(pool_size: u32) -> Result<DbPool, diesel::r2d2::PoolError> {
-        let manager = ConnectionManager::<PgConnection>::new(DATABASE_URL);
-        Pool::builder().max_size(pool_size).build(manager)
+        Self::connect_with_url(&database_url(), pool_size)
+    }
+
+    /// Connect a pool to `url` instead of [`database_url`]
+    pub fn connect_with_url(url: &str, pool_size: u32) -> Result<DbPool, diesel::r2d2::PoolError> {
+        Self::connect_with_hook(url, pool_size, &AfterConnect::from_env())
+    }
+
+    /// Connect a pool to `url` whose connections run `hook` when opened
+    pub fn connect_with_hook(url: &str, pool_size: u32, hook: &AfterConnect) -> Result<DbPool, diesel::r2d2::PoolError> {
+        Self::pool_builder(pool_size, hook).build(ConnectionManager::new(url))
+    }
+
+    /// A pool for `url` that opens no connection until one is checked out.
+    ///
+    /// r2d2 otherwise fills `min_idle` (default: `max_size`) before `build`
+    /// returns, which the async pools never do.
+    pub fn connect_unfilled(url: &str, pool_size: u32) -> Result<DbPool, diesel::r2d2::PoolError> {
+        Self::pool_builder(pool_size, &AfterConnect::from_env())
+            .min_idle(Some(0))
+            .build(ConnectionManager::new(url))
+    }
+
+    fn pool_builder(pool_size: u32, hook: &AfterConnect) -> diesel::r2d2::Builder<ConnectionManager<PgConnection>> {
+        let builder = Pool::builder().max_size(pool_size);
+        if hook.is_empty() {
+            builder
+        } else {
+            builder.connection_customizer(hook.diesel_customizer())
+        }
+    }
+
+    /// Connect over TLS, which libpq implements with the OpenSSL it was built against
+    pub fn connect_tls() -> Result<DbPool, diesel::r2d2::PoolError> {
+        Self::connect_with_url(&tls_database_url(), 10)
+    }
+
+    /// Open a single connection to `url` outside any pool
+    pub fn establish(url: &str) -> ConnectionResult<PgConnection> {
+        let mut conn = PgConnection::establish(url)?;
+        AfterConnect::from_env()
+            .run_diesel(&mut conn)
+            .map_err(ConnectionError::CouldntSetupConfiguration)?;
+        Ok(conn)
+    }
+
+    /// Check out every connection the pool can hold at once.
+    ///
+    /// r2d2 already fills `min_idle` (default: `max_size`) while building, but
+    /// holding them all guarantees the same state as the async pools.
+    pub fn warm_pool(pool: &DbPool) -> Result<(), diesel::r2d2::PoolError> {
+        let conns = (0..pool.max_size())
+            .map(|_| pool.get())
+            .collect::<Result<Vec<_>, _>>()?;
+        drop(conns);
+        Ok(())
+    }
+
+    /// Run `f` on a connection of `pool` on tokio's blocking thread pool, as a
+    /// synchronous library has to be called from async code shared with others
+    pub async fn run_blocking<T: Send + 'static>(
+        pool: &DbPool,
+        f: impl FnOnce(&mut PgConnection) -> Result<T, diesel::result::Error> + Send + 'static,
+    ) -> Result<T, BoxError> {
+        let pool = pool.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get()?;
+            Ok(f(&mut conn)?)
+        })
+        .await?
     }
 
     pub fn insert_user(conn: &mut PgConnection, user: &NewUser) -> Result<Uuid, diesel::result::Error> {
@@ -197,16 +270,7 @@ impl DieselBench {
             .first(conn)
             .optional()?;
 
-        Ok(user.map(|u| User {
-            id: u.id,
-            username: u.username,
-            email: u.email,
-            first_name: u.first_name,
-            last_name: u.last_name,
-            age: u.age,
-            created_at: u.created_at,
-            updated_at: u.updated_at,
-        }))
+        Ok(user.map(User::from))
     }
 
     pub fn select_users_limit(
@@ -221,19 +285,23 @@ impl DieselBench {
 
         Ok(users_list
             .into_iter()
-            .map(|u| User {
-                id: u.id,
-                username: u.username,
-                email: u.email,
-                first_name: u.first_name,
-                last_name: u.last_name,
-                age: u.age,
-                created_at: u.created_at,
-                updated_at: u.updated_at,
-            })
+            .map(User::from)
             .collect())
     }
 
+    /// [`Self::select_users_limit`] with only the id and username, as a tuple
+    /// instead of the whole `Selectable` struct
+    pub fn select_usernames_limit(
+        conn: &mut PgConnection,
+        limit: i64,
+    ) -> Result<Vec<(Uuid, String)>, diesel::result::Error> {
+        users::table
+            .order(users::created_at.desc())
+            .limit(limit)
+            .select((users::id, users::username))
+            .load(conn)
+    }
+
     pub fn select_users_filtered(
         conn: &mut PgConnection,
         min_age: i32,
@@ -250,16 +318,7 @@ impl DieselBench {
 
         Ok(users_list
             .into_iter()
-            .map(|u| User {
-                id: u.id,
-                username: u.username,
-                email: u.email,
-                first_name: u.first_name,
-                last_name: u.last_name,
-                age: u.age,
-                created_at: u.created_at,
-                updated_at: u.updated_at,
-            })
+            .map(User::from)
             .collect())
     }
 
@@ -312,30 +371,7 @@ impl DieselBench {
 
         Ok(results
             .into_iter()
-            .map(|(p, u)| {
-                (
-                    Post {
-                        id: p.id,
-                        user_id: p.user_id,
-                        title: p.title,
-                        content: p.content,
-                        status: p.status,
-                        view_count: p.view_count,
-                        created_at: p.created_at,
-                        updated_at: p.updated_at,
-                    },
-                    User {
-                        id: u.id,
-                        username: u.username,
-                        email: u.email,
-                        first_name: u.first_name,
-                        last_name: u.last_name,
-                        age: u.age,
-                        created_at: u.created_at,
-                        updated_at: u.updated_at,
-                    },
-                )
-            })
+            .map(|(p, u)| (p.into(), u.into()))
             .collect())
     }
 
@@ -360,40 +396,41 @@ impl DieselBench {
 
         Ok(results
             .into_iter()
-            .map(|(u, p, c)| {
-                (
-                    User {
-                        id: u.id,
-                        username: u.username,
-                        email: u.email,
-                        first_name: u.first_name,
-                        last_name: u.last_name,
-                        age: u.age,
-                        created_at: u.created_at,
-                        updated_at: u.updated_at,
-                    },
-                    Post {
-                        id: p.id,
-                        user_id: p.user_id,
-                        title: p.title,
-                        content: p.content,
-                        status: p.status,
-                        view_count: p.view_count,
-                        created_at: p.created_at,
-                        updated_at: p.updated_at,
-                    },
-                    Comment {
-                        id: c.id,
-                        post_id: c.post_id,
-                        user_id: c.user_id,
-                        content: c.content,
-                        created_at: c.created_at,
-                    },
-                )
-            })
+            .map(|(u, p, c)| (u.into(), p.into(), c.into()))
             .collect())
     }
 
+    pub fn select_comments_for_post(
+        conn: &mut PgConnection,
+        post_id: Uuid,
+        limit: i64,
+        before: Option<CommentKey>,
+    ) -> Result<Vec<Comment>, diesel::result::Error> {
+        use diesel::dsl::sql;
+        use diesel::sql_types::{Bool, Timestamptz, Uuid as SqlUuid};
+
+        let mut query = comments::table
+            .filter(comments::post_id.eq(post_id))
+            .into_boxed();
+        if let Some(key) = before {
+            // The DSL has no row comparison, so the predicate is written as SQL
+            query = query.filter(
+                sql::<Bool>("(comments.created_at, comments.id) < (")
+                    .bind::<Timestamptz, _>(key.created_at)
+                    .sql(", ")
+                    .bind::<SqlUuid, _>(key.id)
+                    .sql(")"),
+            );
+        }
+        let results = query
+            .order((comments::created_at.desc(), comments::id.desc()))
+            .limit(limit)
+            .select(DieselComment::as_select())
+            .load::<DieselComment>(conn)?;
+
+        Ok(results.into_iter().map(Comment::from).collect())
+    }
+
     pub fn count_posts_per_user(
         conn: &mut PgConnection,
     ) -> Result<Vec<(Uuid, i64)>, diesel::result::Error> {
@@ -407,6 +444,19 @@ impl DieselBench {
             .load(conn)
     }
 
+    pub fn count_posts_per_tag(
+        conn: &mut PgConnection,
+    ) -> Result<Vec<(Uuid, i64)>, diesel::result::Error> {
+        use diesel::dsl::count;
+
+        tags::table
+            .inner_join(post_tags::table)
+            .group_by(tags::id)
+            .select((tags::id, count(post_tags::post_id)))
+            .order((count(post_tags::post_id).desc(), tags::id))
+            .load(conn)
+    }
+
     pub fn insert_user_with_posts(
         conn: &mut PgConnection,
         user: &NewUser,
@@ -431,6 +481,19 @@ impl DieselBench {
         })
     }
 
+    /// Insert a user and a post for it, then update the user, as one multi-statement batch
+    pub fn insert_user_post_update_batched(
+        conn: &mut PgConnection,
+        user: &NewUser,
+        post: &NewPost,
+        first_name: &str,
+        last_name: &str,
+    ) -> Result<Uuid, diesel::result::Error> {
+        let user_id = Uuid::new_v4();
+        conn.batch_execute(&batch::write_step_sql(user_id, user, post, first_name, last_name))?;
+        Ok(user_id)
+    }
+
     pub fn cleanup(conn: &mut PgConnection) -> Result<(), diesel::result::Error> {
         diesel::delete(users::table.filter(users::username.like("bench_user_%"))).execute(conn)?;
         Ok(())
@@ -468,19 +531,51 @@ impl DieselBench {
 
         Ok(posts_list
             .into_iter()
-            .map(|p| Post {
-                id: p.id,
-                user_id: p.user_id,
-                title: p.title,
-                content: p.content,
-                status: p.status,
-                view_count: p.view_count,
-                created_at: p.created_at,
-                updated_at: p.updated_at,
-            })
+            .map(Post::from)
+            .collect())
+    }
+
+    pub fn select_posts_repeated(
+        conn: &mut PgConnection,
+        copies: i32,
+    ) -> Result<Vec<Post>, diesel::result::Error> {
+        let posts_list: Vec<DieselPost> = diesel::sql_query(
+            "SELECT p.id, p.user_id, p.title, p.content, p.status, p.view_count, p.created_at, p.updated_at
+             FROM posts p
+             CROSS JOIN generate_series(1, $1)",
+        )
+        .bind::<diesel::sql_types::Integer, _>(copies)
+        .load(conn)?;
+
+        Ok(posts_list
+            .into_iter()
+            .map(Post::from)
             .collect())
     }
 
+    /// Reads the result row by row (libpq's single-row mode) instead of
+    /// buffering all of it first, which diesel's default loading mode does
+    pub fn stream_posts_repeated(
+        conn: &mut PgConnection,
+        copies: i32,
+        mut each: impl FnMut(Post),
+    ) -> Result<usize, diesel::result::Error> {
+        let posts_iter = diesel::sql_query(
+            "SELECT p.id, p.user_id, p.title, p.content, p.status, p.view_count, p.created_at, p.updated_at
+             FROM posts p
+             CROSS JOIN generate_series(1, $1)",
+        )
+        .bind::<diesel::sql_types::Integer, _>(copies)
+        .load_iter::<DieselPost, PgRowByRowLoadingMode>(conn)?;
+
+        let mut count = 0;
+        for post in posts_iter {
+            each(Post::from(post?));
+            count += 1;
+        }
+        Ok(count)
+    }
+
     pub fn increment_view_count(
         conn: &mut PgConnection,
         post_id: Uuid,
@@ -510,16 +605,191 @@ impl DieselBench {
 
         Ok(users_list
             .into_iter()
-            .map(|u| User {
-                id: u.id,
-                username: u.username,
-                email: u.email,
-                first_name: u.first_name,
-                last_name: u.last_name,
-                age: u.age,
-                created_at: u.created_at,
-                updated_at: u.updated_at,
-            })
+            .map(User::from)
             .collect())
     }
 }
+
+// ============================================================================
+// Statement timeout and cancellation
+// ============================================================================
+
+impl DieselBench {
+    /// Lightweight round trip used to check that a connection is still usable
+    pub fn ping(conn: &mut PgConnection) -> Result<(), diesel::result::Error> {
+        diesel::sql_query("SELECT 1").execute(conn)?;
+        Ok(())
+    }
+
+    /// Run a query that keeps the server busy for `sleep_secs`
+    pub fn pg_sleep(conn: &mut PgConnection, sleep_secs: f64) -> Result<(), diesel::result::Error> {
+        diesel::sql_query("SELECT pg_sleep($1)")
+            .bind::<diesel::sql_types::Double, _>(sleep_secs)
+            .execute(conn)?;
+        Ok(())
+    }
+
+    /// Run `pg_sleep` under a transaction-scoped `statement_timeout`.
+    ///
+    /// Returns `true` when the server cancelled the statement. Diesel reports
+    /// `query_canceled` as an unknown database error, so the message is matched.
+    pub fn sleep_with_statement_timeout(
+        conn: &mut PgConnection,
+        timeout_ms: u64,
+        sleep_secs: f64,
+    ) -> Result<bool, diesel::result::Error> {
+        let result = conn.transaction(|conn| {
+            diesel::sql_query(format!("SET LOCAL statement_timeout = {}", timeout_ms))
+                .execute(conn)?;
+            Self::pg_sleep(conn, sleep_secs)
+        });
+
+        match result {
+            Ok(()) => Ok(false),
+            Err(diesel::result::Error::DatabaseError(_, info))
+                if info.message().contains("statement timeout") =>
+            {
+                Ok(true)
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+// ============================================================================
+// DatabaseBenchmark implementation
+// ============================================================================
+//
+// Diesel is synchronous and needs `&mut PgConnection`, so the trait connection is
+// a single pooled connection behind a `Mutex`, which keeps the futures `Send`;
+// it is never contended, as each connection has one user at a time. The async
+// methods never await, they simply run the sync call to completion when polled.
+
+impl DatabaseBenchmark for DieselBench {
+    type Connection = Mutex<DbConn>;
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    const BLOCKING: bool = true;
+
+    async fn connect() -> Result<Self::Connection, Self::Error> {
+        Self::connect_to(&database_url()).await
+    }
+
+    async fn connect_to(url: &str) -> Result<Self::Connection, Self::Error> {
+        let pool = DieselBench::connect_with_url(url, 1)?;
+        Ok(Mutex::new(pool.get()?))
+    }
+
+    async fn insert_user(conn: &Self::Connection, user: &NewUser) -> Result<Uuid, Self::Error> {
+        Ok(DieselBench::insert_user(&mut conn.lock().unwrap(), user)?)
+    }
+
+    async fn insert_users_batch(conn: &Self::Connection, users: &[NewUser]) -> Result<Vec<Uuid>, Self::Error> {
+        Ok(DieselBench::insert_users_batch(&mut conn.lock().unwrap(), users)?)
+    }
+
+    async fn select_user_by_id(conn: &Self::Connection, id: Uuid) -> Result<Option<User>, Self::Error> {
+        Ok(DieselBench::select_user_by_id(&mut conn.lock().unwrap(), id)?)
+    }
+
+    async fn select_users_limit(conn: &Self::Connection, limit: i64) -> Result<Vec<User>, Self::Error> {
+        Ok(DieselBench::select_users_limit(&mut conn.lock().unwrap(), limit)?)
+    }
+
+    async fn select_users_filtered(conn: &Self::Connection, min_age: i32, max_age: i32, limit: i64) -> Result<Vec<User>, Self::Error> {
+        Ok(DieselBench::select_users_filtered(&mut conn.lock().unwrap(), min_age, max_age, limit)?)
+    }
+
+    async fn update_user(conn: &Self::Connection, id: Uuid, first_name: &str, last_name: &str) -> Result<bool, Self::Error> {
+        Ok(DieselBench::update_user(&mut conn.lock().unwrap(), id, first_name, last_name)?)
+    }
+
+    async fn delete_user(conn: &Self::Connection, id: Uuid) -> Result<bool, Self::Error> {
+        Ok(DieselBench::delete_user(&mut conn.lock().unwrap(), id)?)
+    }
+
+    async fn insert_post(conn: &Self::Connection, post: &NewPost) -> Result<Uuid, Self::Error> {
+        Ok(DieselBench::insert_post(&mut conn.lock().unwrap(), post)?)
+    }
+
+    async fn insert_comment(conn: &Self::Connection, comment: &NewComment) -> Result<Uuid, Self::Error> {
+        Ok(DieselBench::insert_comment(&mut conn.lock().unwrap(), comment)?)
+    }
+
+    async fn select_posts_with_user(conn: &Self::Connection, limit: i64) -> Result<Vec<(Post, User)>, Self::Error> {
+        Ok(DieselBench::select_posts_with_user(&mut conn.lock().unwrap(), limit)?)
+    }
+
+    async fn select_posts_by_status(conn: &Self::Connection, status: &str, limit: i64) -> Result<Vec<Post>, Self::Error> {
+        Ok(DieselBench::select_posts_by_status(&mut conn.lock().unwrap(), status, limit)?)
+    }
+
+    async fn select_posts_repeated(conn: &Self::Connection, copies: i32) -> Result<Vec<Post>, Self::Error> {
+        Ok(DieselBench::select_posts_repeated(&mut conn.lock().unwrap(), copies)?)
+    }
+
+    async fn stream_posts_repeated(conn: &Self::Connection, copies: i32, each: impl FnMut(Post)) -> Result<usize, Self::Error> {
+        Ok(DieselBench::stream_posts_repeated(&mut conn.lock().unwrap(), copies, each)?)
+    }
+
+    async fn search_users_by_name(conn: &Self::Connection, pattern: &str, limit: i64) -> Result<Vec<User>, Self::Error> {
+        Ok(DieselBench::search_users_by_name(&mut conn.lock().unwrap(), pattern, limit)?)
+    }
+
+    async fn select_users_posts_comments(conn: &Self::Connection, limit: i64) -> Result<Vec<(User, Post, Comment)>, Self::Error> {
+        Ok(DieselBench::select_users_posts_comments(&mut conn.lock().unwrap(), limit)?)
+    }
+
+    async fn select_comments_for_post(
+        conn: &Self::Connection,
+        post_id: Uuid,
+        limit: i64,
+        before: Option<CommentKey>,
+    ) -> Result<Vec<Comment>, Self::Error> {
+        Ok(DieselBench::select_comments_for_post(&mut conn.lock().unwrap(), post_id, limit, before)?)
+    }
+
+    async fn count_posts_per_user(conn: &Self::Connection) -> Result<Vec<(Uuid, i64)>, Self::Error> {
+        Ok(DieselBench::count_posts_per_user(&mut conn.lock().unwrap())?)
+    }
+
+    async fn count_posts_per_tag(conn: &Self::Connection) -> Result<Vec<(Uuid, i64)>, Self::Error> {
+        Ok(DieselBench::count_posts_per_tag(&mut conn.lock().unwrap())?)
+    }
+
+    async fn insert_user_with_posts(conn: &Self::Connection, user: &NewUser, posts: &[NewPost]) -> Result<Uuid, Self::Error> {
+        Ok(DieselBench::insert_user_with_posts(&mut conn.lock().unwrap(), user, posts)?)
+    }
+
+    async fn insert_user_post_update_batched(
+        conn: &Self::Connection,
+        user: &NewUser,
+        post: &NewPost,
+        first_name: &str,
+        last_name: &str,
+    ) -> Result<Uuid, Self::Error> {
+        Ok(DieselBench::insert_user_post_update_batched(
+            &mut conn.lock().unwrap(),
+            user,
+            post,
+            first_name,
+            last_name,
+        )?)
+    }
+
+    async fn cleanup(conn: &Self::Connection) -> Result<(), Self::Error> {
+        Ok(DieselBench::cleanup(&mut conn.lock().unwrap())?)
+    }
+
+    fn capabilities() -> Capabilities {
+        Capabilities {
+            // Everything, transactions included, takes `&mut PgConnection`
+            shared_transactions: false,
+            streaming: true,
+            copy: true,
+            listen_notify: true,
+            prepared_cache: true,
+            binary_format: true,
+        }
+    }
+}
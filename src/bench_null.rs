@@ -0,0 +1,603 @@
+//! In-memory benchmark implementation
+//!
+//! `NullBackend` runs every `DatabaseBenchmark` operation against a small
+//! dataset shaped like `init.sql` and held in process memory, so the harness
+//! itself (registration, cleanup checks, error recording, reporting) can be
+//! exercised without a PostgreSQL server. Its timings say nothing about any
+//! database library.
+
+use crate::capability::Capabilities;
+use crate::seed::SeedConfig;
+use crate::verify::LeftoverRows;
+use crate::{Comment, CommentKey, DatabaseBenchmark, NewComment, NewPost, NewUser, Post, User};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+use uuid::Uuid;
+
+/// Seeded users; the first [`SEED_POSTING_USERS`] of them have posts
+pub const SEED_USERS: usize = 1_000;
+pub const SEED_POSTING_USERS: usize = 500;
+pub const SEED_POSTS_PER_USER: usize = 5;
+/// Seeded posts that have comments, oldest first
+pub const SEED_COMMENTED_POSTS: usize = 2_000;
+pub const SEED_COMMENTS_PER_POST: usize = 4;
+
+/// Rows of the in-memory tables, each kept in insertion (= `created_at`) order
+#[derive(Debug, Clone, Default)]
+pub struct Dataset {
+    users: Vec<User>,
+    posts: Vec<Post>,
+    comments: Vec<Comment>,
+    /// Microseconds since [`Dataset::epoch`] handed to the last inserted row
+    clock: i64,
+}
+
+/// A handle to an in-memory dataset; clones share the same rows
+pub type NullConnection = Arc<Mutex<Dataset>>;
+
+/// The constraint violations PostgreSQL would report for the same writes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NullError {
+    /// A username or email that already exists
+    UniqueViolation(&'static str),
+    /// A post or comment referencing a row that does not exist
+    ForeignKeyViolation(&'static str),
+}
+
+impl fmt::Display for NullError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NullError::UniqueViolation(key) => write!(f, "duplicate key value violates unique constraint on {}", key),
+            NullError::ForeignKeyViolation(key) => write!(f, "insert violates foreign key constraint on {}", key),
+        }
+    }
+}
+
+impl std::error::Error for NullError {}
+
+impl Dataset {
+    /// A dataset with the same shape as `init.sql`, scaled down and deterministic
+    pub fn seeded() -> Self {
+        let mut data = Self::default();
+        let config = SeedConfig::get();
+
+        for i in 1..=SEED_USERS {
+            let created_at = data.tick();
+            data.users.push(User {
+                id: Uuid::from_u128(i as u128),
+                username: format!("user_{}", i),
+                email: format!("user_{}@example.com", i),
+                first_name: format!("First{}", i),
+                last_name: format!("Last{}", i / 100),
+                age: Some((18 + i % 62) as i32),
+                created_at: Some(created_at),
+                updated_at: Some(created_at),
+            });
+        }
+
+        for post_num in 1..=SEED_POSTS_PER_USER {
+            for u in 0..SEED_POSTING_USERS {
+                let created_at = data.tick();
+                let user = &data.users[u];
+                data.posts.push(Post {
+                    id: Uuid::from_u128(1 << 64 | (data.posts.len() + 1) as u128),
+                    user_id: user.id,
+                    title: format!("Post Title {} by {}", post_num, user.username),
+                    content: config
+                        .post_content
+                        .text(&format!("This is the content for post number {}.", post_num), data.posts.len()),
+                    status: match post_num % 10 {
+                        0 => "draft",
+                        1 => "archived",
+                        _ => "published",
+                    }
+                    .to_string(),
+                    view_count: ((u * 37 + post_num * 101) % 10_000) as i32,
+                    created_at: Some(created_at),
+                    updated_at: Some(created_at),
+                });
+            }
+        }
+
+        for comment_num in 1..=SEED_COMMENTS_PER_POST {
+            for p in 0..SEED_COMMENTED_POSTS {
+                let created_at = data.tick();
+                let post_id = data.posts[p].id;
+                data.comments.push(Comment {
+                    id: Uuid::from_u128(2 << 64 | (data.comments.len() + 1) as u128),
+                    post_id,
+                    user_id: data.users[(p * 7 + comment_num) % SEED_USERS].id,
+                    content: config
+                        .comment_content
+                        .text(&format!("This is comment {} on this post.", comment_num), data.comments.len()),
+                    created_at: Some(created_at),
+                });
+            }
+        }
+
+        data
+    }
+
+    fn epoch() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()
+    }
+
+    /// A timestamp later than every row inserted so far
+    fn tick(&mut self) -> DateTime<Utc> {
+        self.clock += 1;
+        Self::epoch() + Duration::microseconds(self.clock)
+    }
+
+    fn user(&self, id: Uuid) -> Option<&User> {
+        self.users.iter().find(|u| u.id == id)
+    }
+
+    /// Check the unique constraints of `users` for rows about to be inserted together
+    fn check_new_users<'a>(&self, users: impl IntoIterator<Item = &'a NewUser>) -> Result<(), NullError> {
+        let mut usernames: HashSet<&str> = self.users.iter().map(|u| u.username.as_str()).collect();
+        let mut emails: HashSet<&str> = self.users.iter().map(|u| u.email.as_str()).collect();
+        for user in users {
+            if !usernames.insert(&user.username) {
+                return Err(NullError::UniqueViolation("users.username"));
+            }
+            if !emails.insert(&user.email) {
+                return Err(NullError::UniqueViolation("users.email"));
+            }
+        }
+        Ok(())
+    }
+
+    fn push_user(&mut self, user: &NewUser) -> Uuid {
+        let created_at = self.tick();
+        let id = Uuid::new_v4();
+        self.users.push(User {
+            id,
+            username: user.username.clone(),
+            email: user.email.clone(),
+            first_name: user.first_name.clone(),
+            last_name: user.last_name.clone(),
+            age: user.age,
+            created_at: Some(created_at),
+            updated_at: Some(created_at),
+        });
+        id
+    }
+
+    fn push_post(&mut self, post: &NewPost) -> Uuid {
+        let created_at = self.tick();
+        let id = Uuid::new_v4();
+        self.posts.push(Post {
+            id,
+            user_id: post.user_id,
+            title: post.title.clone(),
+            content: post.content.clone(),
+            status: post.status.clone(),
+            view_count: 0,
+            created_at: Some(created_at),
+            updated_at: Some(created_at),
+        });
+        id
+    }
+
+    /// Delete the matching users and, like `ON DELETE CASCADE`, everything they own
+    fn delete_users(&mut self, matches: impl Fn(&User) -> bool) -> usize {
+        let deleted: HashSet<Uuid> = self.users.iter().filter(|u| matches(u)).map(|u| u.id).collect();
+        if deleted.is_empty() {
+            return 0;
+        }
+        self.users.retain(|u| !deleted.contains(&u.id));
+        let deleted_posts: HashSet<Uuid> = self
+            .posts
+            .iter()
+            .filter(|p| deleted.contains(&p.user_id))
+            .map(|p| p.id)
+            .collect();
+        self.posts.retain(|p| !deleted_posts.contains(&p.id));
+        self.comments
+            .retain(|c| !deleted.contains(&c.user_id) && !deleted_posts.contains(&c.post_id));
+        deleted.len()
+    }
+}
+
+pub struct NullBackend;
+
+impl NullBackend {
+    /// The process-wide dataset every connection shares, like one database
+    pub fn connect() -> NullConnection {
+        static DATASET: OnceLock<NullConnection> = OnceLock::new();
+        DATASET.get_or_init(Self::new_dataset).clone()
+    }
+
+    /// A freshly seeded dataset not shared with [`NullBackend::connect`]
+    pub fn new_dataset() -> NullConnection {
+        // Generating the content is the slow part, so it happens once per process
+        static SEEDED: OnceLock<Dataset> = OnceLock::new();
+        Arc::new(Mutex::new(SEEDED.get_or_init(Dataset::seeded).clone()))
+    }
+
+    fn lock(conn: &NullConnection) -> MutexGuard<'_, Dataset> {
+        conn.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn insert_user(conn: &NullConnection, user: &NewUser) -> Result<Uuid, NullError> {
+        let mut data = Self::lock(conn);
+        data.check_new_users([user])?;
+        Ok(data.push_user(user))
+    }
+
+    pub fn insert_users_batch(conn: &NullConnection, users: &[NewUser]) -> Result<Vec<Uuid>, NullError> {
+        let mut data = Self::lock(conn);
+        data.check_new_users(users)?;
+        Ok(users.iter().map(|user| data.push_user(user)).collect())
+    }
+
+    pub fn select_user_by_id(conn: &NullConnection, id: Uuid) -> Result<Option<User>, NullError> {
+        Ok(Self::lock(conn).user(id).cloned())
+    }
+
+    pub fn select_users_limit(conn: &NullConnection, limit: i64) -> Result<Vec<User>, NullError> {
+        let data = Self::lock(conn);
+        Ok(data.users.iter().rev().take(limit as usize).cloned().collect())
+    }
+
+    pub fn select_users_filtered(
+        conn: &NullConnection,
+        min_age: i32,
+        max_age: i32,
+        limit: i64,
+    ) -> Result<Vec<User>, NullError> {
+        let data = Self::lock(conn);
+        let mut users: Vec<&User> = data
+            .users
+            .iter()
+            .filter(|u| u.age.is_some_and(|age| age >= min_age && age <= max_age))
+            .collect();
+        users.sort_by(|a, b| a.age.cmp(&b.age).then_with(|| a.username.cmp(&b.username)));
+        Ok(users.into_iter().take(limit as usize).cloned().collect())
+    }
+
+    pub fn update_user(conn: &NullConnection, id: Uuid, first_name: &str, last_name: &str) -> Result<bool, NullError> {
+        let mut data = Self::lock(conn);
+        let updated_at = data.tick();
+        Ok(match data.users.iter_mut().find(|u| u.id == id) {
+            Some(user) => {
+                user.first_name = first_name.to_string();
+                user.last_name = last_name.to_string();
+                user.updated_at = Some(updated_at);
+                true
+            }
+            None => false,
+        })
+    }
+
+    pub fn delete_user(conn: &NullConnection, id: Uuid) -> Result<bool, NullError> {
+        Ok(Self::lock(conn).delete_users(|u| u.id == id) > 0)
+    }
+
+    pub fn insert_post(conn: &NullConnection, post: &NewPost) -> Result<Uuid, NullError> {
+        let mut data = Self::lock(conn);
+        if data.user(post.user_id).is_none() {
+            return Err(NullError::ForeignKeyViolation("posts.user_id"));
+        }
+        Ok(data.push_post(post))
+    }
+
+    pub fn insert_comment(conn: &NullConnection, comment: &NewComment) -> Result<Uuid, NullError> {
+        let mut data = Self::lock(conn);
+        if data.user(comment.user_id).is_none() {
+            return Err(NullError::ForeignKeyViolation("comments.user_id"));
+        }
+        if !data.posts.iter().any(|p| p.id == comment.post_id) {
+            return Err(NullError::ForeignKeyViolation("comments.post_id"));
+        }
+        let created_at = data.tick();
+        let id = Uuid::new_v4();
+        data.comments.push(Comment {
+            id,
+            post_id: comment.post_id,
+            user_id: comment.user_id,
+            content: comment.content.clone(),
+            created_at: Some(created_at),
+        });
+        Ok(id)
+    }
+
+    pub fn select_posts_with_user(conn: &NullConnection, limit: i64) -> Result<Vec<(Post, User)>, NullError> {
+        let data = Self::lock(conn);
+        let users: HashMap<Uuid, &User> = data.users.iter().map(|u| (u.id, u)).collect();
+        Ok(data
+            .posts
+            .iter()
+            .rev()
+            .filter_map(|p| users.get(&p.user_id).map(|u| (p.clone(), (*u).clone())))
+            .take(limit as usize)
+            .collect())
+    }
+
+    pub fn select_posts_by_status(conn: &NullConnection, status: &str, limit: i64) -> Result<Vec<Post>, NullError> {
+        let data = Self::lock(conn);
+        let mut posts: Vec<&Post> = data.posts.iter().filter(|p| p.status == status).collect();
+        posts.sort_by_key(|p| std::cmp::Reverse(p.created_at));
+        Ok(posts.into_iter().take(limit as usize).cloned().collect())
+    }
+
+    /// Case-insensitive substring match folding ASCII only, like `ILIKE`
+    /// under the benchmark database's `C` ctype; `%` and `_` are not wildcards
+    pub fn search_users_by_name(conn: &NullConnection, pattern: &str, limit: i64) -> Result<Vec<User>, NullError> {
+        let data = Self::lock(conn);
+        let pattern = pattern.to_ascii_lowercase();
+        let matches = |name: &str| name.to_ascii_lowercase().contains(&pattern);
+        let mut users: Vec<&User> = data
+            .users
+            .iter()
+            .filter(|u| matches(&u.first_name) || matches(&u.last_name))
+            .collect();
+        users.sort_by(|a, b| a.username.cmp(&b.username));
+        Ok(users.into_iter().take(limit as usize).cloned().collect())
+    }
+
+    pub fn select_posts_repeated(conn: &NullConnection, copies: i32) -> Result<Vec<Post>, NullError> {
+        let data = Self::lock(conn);
+        Ok((0..copies).flat_map(|_| data.posts.iter().cloned()).collect())
+    }
+
+    pub fn stream_posts_repeated(conn: &NullConnection, copies: i32, mut each: impl FnMut(Post)) -> Result<usize, NullError> {
+        let data = Self::lock(conn);
+        for post in (0..copies).flat_map(|_| data.posts.iter()) {
+            each(post.clone());
+        }
+        Ok(copies.max(0) as usize * data.posts.len())
+    }
+
+    pub fn select_users_posts_comments(
+        conn: &NullConnection,
+        limit: i64,
+    ) -> Result<Vec<(User, Post, Comment)>, NullError> {
+        let data = Self::lock(conn);
+        let mut posts_by_user: HashMap<Uuid, Vec<&Post>> = HashMap::new();
+        for post in data.posts.iter().rev() {
+            posts_by_user.entry(post.user_id).or_default().push(post);
+        }
+        let mut comments_by_post: HashMap<Uuid, Vec<&Comment>> = HashMap::new();
+        for comment in data.comments.iter().rev() {
+            comments_by_post.entry(comment.post_id).or_default().push(comment);
+        }
+
+        // Newest user, then newest post, then newest comment first
+        Ok(data
+            .users
+            .iter()
+            .rev()
+            .flat_map(|u| posts_by_user.get(&u.id).into_iter().flatten().map(move |p| (u, *p)))
+            .flat_map(|(u, p)| {
+                comments_by_post
+                    .get(&p.id)
+                    .into_iter()
+                    .flatten()
+                    .map(move |c| (u.clone(), p.clone(), (*c).clone()))
+            })
+            .take(limit as usize)
+            .collect())
+    }
+
+    /// Every comment has a `created_at`, so the keys compare as PostgreSQL's
+    /// `(created_at, id)` row comparison does
+    pub fn select_comments_for_post(
+        conn: &NullConnection,
+        post_id: Uuid,
+        limit: i64,
+        before: Option<CommentKey>,
+    ) -> Result<Vec<Comment>, NullError> {
+        let data = Self::lock(conn);
+        let mut comments: Vec<&Comment> = data
+            .comments
+            .iter()
+            .filter(|c| c.post_id == post_id)
+            .filter(|c| before.is_none_or(|key| (c.created_at, c.id) < (Some(key.created_at), key.id)))
+            .collect();
+        comments.sort_by_key(|c| std::cmp::Reverse((c.created_at, c.id)));
+        Ok(comments.into_iter().take(limit as usize).cloned().collect())
+    }
+
+    pub fn count_posts_per_user(conn: &NullConnection) -> Result<Vec<(Uuid, i64)>, NullError> {
+        let data = Self::lock(conn);
+        let mut counts: HashMap<Uuid, i64> = data.users.iter().map(|u| (u.id, 0)).collect();
+        for post in &data.posts {
+            *counts.entry(post.user_id).or_default() += 1;
+        }
+        let mut counts: Vec<(Uuid, i64)> = counts.into_iter().collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        Ok(counts)
+    }
+
+    /// The in-memory dataset has no tags, so no tag has posts
+    pub fn count_posts_per_tag(_conn: &NullConnection) -> Result<Vec<(Uuid, i64)>, NullError> {
+        Ok(Vec::new())
+    }
+
+    /// Inserts the user and posts under one lock, so either all rows appear or none do
+    pub fn insert_user_with_posts(conn: &NullConnection, user: &NewUser, posts: &[NewPost]) -> Result<Uuid, NullError> {
+        let mut data = Self::lock(conn);
+        data.check_new_users([user])?;
+        let user_id = data.push_user(user);
+        for post in posts {
+            let mut post = post.clone();
+            post.user_id = user_id;
+            data.push_post(&post);
+        }
+        Ok(user_id)
+    }
+
+    /// Like the SQL batch, all three writes happen or none do
+    pub fn insert_user_post_update_batched(
+        conn: &NullConnection,
+        user: &NewUser,
+        post: &NewPost,
+        first_name: &str,
+        last_name: &str,
+    ) -> Result<Uuid, NullError> {
+        let mut data = Self::lock(conn);
+        data.check_new_users([user])?;
+        let user_id = data.push_user(user);
+        data.push_post(&NewPost { user_id, ..post.clone() });
+        let updated_at = data.tick();
+        let stored = data.users.last_mut().expect("just inserted");
+        stored.first_name = first_name.to_string();
+        stored.last_name = last_name.to_string();
+        stored.updated_at = Some(updated_at);
+        Ok(user_id)
+    }
+
+    pub fn cleanup(conn: &NullConnection) -> Result<(), NullError> {
+        Self::lock(conn).delete_users(|u| u.username.starts_with("bench_user_"));
+        Ok(())
+    }
+
+    /// The in-memory counterpart of [`crate::verify::leftover_rows`]
+    pub fn leftover_rows(conn: &NullConnection) -> LeftoverRows {
+        let data = Self::lock(conn);
+        let bench_users: HashSet<Uuid> = data
+            .users
+            .iter()
+            .filter(|u| u.username.starts_with("bench_user_"))
+            .map(|u| u.id)
+            .collect();
+        let bench_posts: HashSet<Uuid> = data
+            .posts
+            .iter()
+            .filter(|p| bench_users.contains(&p.user_id))
+            .map(|p| p.id)
+            .collect();
+        let comments = data
+            .comments
+            .iter()
+            .filter(|c| bench_posts.contains(&c.post_id) || bench_users.contains(&c.user_id))
+            .count();
+
+        LeftoverRows {
+            users: bench_users.len() as i64,
+            posts: bench_posts.len() as i64,
+            comments: comments as i64,
+            post_tags: 0,
+        }
+    }
+}
+
+// ============================================================================
+// DatabaseBenchmark implementation
+// ============================================================================
+
+impl DatabaseBenchmark for NullBackend {
+    type Connection = NullConnection;
+    type Error = NullError;
+
+    async fn connect() -> Result<Self::Connection, Self::Error> {
+        Ok(NullBackend::connect())
+    }
+
+    /// Every URL reaches the same in-memory dataset
+    async fn connect_to(_url: &str) -> Result<Self::Connection, Self::Error> {
+        Ok(NullBackend::connect())
+    }
+
+    async fn insert_user(conn: &Self::Connection, user: &NewUser) -> Result<Uuid, Self::Error> {
+        NullBackend::insert_user(conn, user)
+    }
+
+    async fn insert_users_batch(conn: &Self::Connection, users: &[NewUser]) -> Result<Vec<Uuid>, Self::Error> {
+        NullBackend::insert_users_batch(conn, users)
+    }
+
+    async fn select_user_by_id(conn: &Self::Connection, id: Uuid) -> Result<Option<User>, Self::Error> {
+        NullBackend::select_user_by_id(conn, id)
+    }
+
+    async fn select_users_limit(conn: &Self::Connection, limit: i64) -> Result<Vec<User>, Self::Error> {
+        NullBackend::select_users_limit(conn, limit)
+    }
+
+    async fn select_users_filtered(conn: &Self::Connection, min_age: i32, max_age: i32, limit: i64) -> Result<Vec<User>, Self::Error> {
+        NullBackend::select_users_filtered(conn, min_age, max_age, limit)
+    }
+
+    async fn update_user(conn: &Self::Connection, id: Uuid, first_name: &str, last_name: &str) -> Result<bool, Self::Error> {
+        NullBackend::update_user(conn, id, first_name, last_name)
+    }
+
+    async fn delete_user(conn: &Self::Connection, id: Uuid) -> Result<bool, Self::Error> {
+        NullBackend::delete_user(conn, id)
+    }
+
+    async fn insert_post(conn: &Self::Connection, post: &NewPost) -> Result<Uuid, Self::Error> {
+        NullBackend::insert_post(conn, post)
+    }
+
+    async fn insert_comment(conn: &Self::Connection, comment: &NewComment) -> Result<Uuid, Self::Error> {
+        NullBackend::insert_comment(conn, comment)
+    }
+
+    async fn select_posts_with_user(conn: &Self::Connection, limit: i64) -> Result<Vec<(Post, User)>, Self::Error> {
+        NullBackend::select_posts_with_user(conn, limit)
+    }
+
+    async fn select_posts_by_status(conn: &Self::Connection, status: &str, limit: i64) -> Result<Vec<Post>, Self::Error> {
+        NullBackend::select_posts_by_status(conn, status, limit)
+    }
+
+    async fn select_posts_repeated(conn: &Self::Connection, copies: i32) -> Result<Vec<Post>, Self::Error> {
+        NullBackend::select_posts_repeated(conn, copies)
+    }
+
+    async fn stream_posts_repeated(conn: &Self::Connection, copies: i32, each: impl FnMut(Post)) -> Result<usize, Self::Error> {
+        NullBackend::stream_posts_repeated(conn, copies, each)
+    }
+
+    async fn search_users_by_name(conn: &Self::Connection, pattern: &str, limit: i64) -> Result<Vec<User>, Self::Error> {
+        NullBackend::search_users_by_name(conn, pattern, limit)
+    }
+
+    async fn select_users_posts_comments(conn: &Self::Connection, limit: i64) -> Result<Vec<(User, Post, Comment)>, Self::Error> {
+        NullBackend::select_users_posts_comments(conn, limit)
+    }
+
+    async fn select_comments_for_post(
+        conn: &Self::Connection,
+        post_id: Uuid,
+        limit: i64,
+        before: Option<CommentKey>,
+    ) -> Result<Vec<Comment>, Self::Error> {
+        NullBackend::select_comments_for_post(conn, post_id, limit, before)
+    }
+
+    async fn count_posts_per_user(conn: &Self::Connection) -> Result<Vec<(Uuid, i64)>, Self::Error> {
+        NullBackend::count_posts_per_user(conn)
+    }
+
+    async fn count_posts_per_tag(conn: &Self::Connection) -> Result<Vec<(Uuid, i64)>, Self::Error> {
+        NullBackend::count_posts_per_tag(conn)
+    }
+
+    async fn insert_user_with_posts(conn: &Self::Connection, user: &NewUser, posts: &[NewPost]) -> Result<Uuid, Self::Error> {
+        NullBackend::insert_user_with_posts(conn, user, posts)
+    }
+
+    async fn insert_user_post_update_batched(
+        conn: &Self::Connection,
+        user: &NewUser,
+        post: &NewPost,
+        first_name: &str,
+        last_name: &str,
+    ) -> Result<Uuid, Self::Error> {
+        NullBackend::insert_user_post_update_batched(conn, user, post, first_name, last_name)
+    }
+
+    async fn cleanup(conn: &Self::Connection) -> Result<(), Self::Error> {
+        NullBackend::cleanup(conn)
+    }
+
+    fn capabilities() -> Capabilities {
+        Capabilities::NONE
+    }
+}
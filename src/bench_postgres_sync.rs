@@ -0,0 +1,252 @@
+//! Synchronous `postgres` crate benchmark implementation
+//!
+//! The `postgres` crate is tokio-postgres behind a blocking API: every call
+//! drives the async client on a current-thread runtime of its own. Running the
+//! same SQL as [`TokioPostgresBench`](crate::bench_tokio_postgres::TokioPostgresBench)
+//! through it measures what the caller's tokio runtime costs compared to
+//! blocking on each query directly.
+//!
+//! Because the client blocks on its own runtime, it panics when called from
+//! inside another one, so [`PostgresSyncBench`] can't implement the async
+//! [`DatabaseBenchmark`](crate::DatabaseBenchmark) the way Diesel does. It has
+//! the same operations as inherent functions instead, to be called from plain
+//! threads.
+
+use crate::after_connect::AfterConnect;
+use crate::mapping::{CommentColumns, ModelRow, PostColumns, UserColumns};
+use crate::{database_url, Comment, CommentKey, NewComment, NewPost, NewUser, Post, User};
+use postgres::{Client, GenericClient, NoTls};
+use uuid::Uuid;
+
+pub struct PostgresSyncBench;
+
+impl PostgresSyncBench {
+    pub fn connect() -> Result<Client, postgres::Error> {
+        Self::connect_to(&database_url())
+    }
+
+    /// Connect without TLS to `url` instead of [`database_url`]
+    pub fn connect_to(url: &str) -> Result<Client, postgres::Error> {
+        let mut client = Client::connect(url, NoTls)?;
+        AfterConnect::from_env().run_postgres(&mut client)?;
+        Ok(client)
+    }
+
+    pub fn insert_user(client: &mut impl GenericClient, user: &NewUser) -> Result<Uuid, postgres::Error> {
+        let row = client.query_one(
+            "INSERT INTO users (username, email, first_name, last_name, age)
+             VALUES ($1, $2, $3, $4, $5)
+             RETURNING id",
+            &[&user.username, &user.email, &user.first_name, &user.last_name, &user.age],
+        )?;
+        Ok(row.get("id"))
+    }
+
+    pub fn insert_users_batch(client: &mut Client, users: &[NewUser]) -> Result<Vec<Uuid>, postgres::Error> {
+        // Individual inserts, as in every other backend's batch
+        users.iter().map(|user| Self::insert_user(client, user)).collect()
+    }
+
+    pub fn select_user_by_id(client: &mut Client, id: Uuid) -> Result<Option<User>, postgres::Error> {
+        let row = client.query_opt(
+            "SELECT id, username, email, first_name, last_name, age, created_at, updated_at
+             FROM users WHERE id = $1",
+            &[&id],
+        )?;
+
+        row.as_ref().map(User::try_from).transpose()
+    }
+
+    pub fn select_users_limit(client: &mut Client, limit: i64) -> Result<Vec<User>, postgres::Error> {
+        let rows = client.query(
+            "SELECT id, username, email, first_name, last_name, age, created_at, updated_at
+             FROM users ORDER BY created_at DESC LIMIT $1",
+            &[&limit],
+        )?;
+
+        rows.iter().map(User::try_from).collect()
+    }
+
+    pub fn select_users_filtered(
+        client: &mut Client,
+        min_age: i32,
+        max_age: i32,
+        limit: i64,
+    ) -> Result<Vec<User>, postgres::Error> {
+        let rows = client.query(
+            "SELECT id, username, email, first_name, last_name, age, created_at, updated_at
+             FROM users
+             WHERE age >= $1 AND age <= $2
+             ORDER BY age, username
+             LIMIT $3",
+            &[&min_age, &max_age, &limit],
+        )?;
+
+        rows.iter().map(User::try_from).collect()
+    }
+
+    pub fn update_user(
+        client: &mut Client,
+        id: Uuid,
+        first_name: &str,
+        last_name: &str,
+    ) -> Result<bool, postgres::Error> {
+        let rows_affected = client.execute(
+            "UPDATE users SET first_name = $1, last_name = $2, updated_at = NOW() WHERE id = $3",
+            &[&first_name, &last_name, &id],
+        )?;
+        Ok(rows_affected > 0)
+    }
+
+    pub fn delete_user(client: &mut Client, id: Uuid) -> Result<bool, postgres::Error> {
+        let rows_affected = client.execute("DELETE FROM users WHERE id = $1", &[&id])?;
+        Ok(rows_affected > 0)
+    }
+
+    pub fn insert_post(client: &mut impl GenericClient, post: &NewPost) -> Result<Uuid, postgres::Error> {
+        let row = client.query_one(
+            "INSERT INTO posts (user_id, title, content, status)
+             VALUES ($1, $2, $3, $4)
+             RETURNING id",
+            &[&post.user_id, &post.title, &post.content, &post.status],
+        )?;
+        Ok(row.get("id"))
+    }
+
+    pub fn insert_comment(client: &mut Client, comment: &NewComment) -> Result<Uuid, postgres::Error> {
+        let row = client.query_one(
+            "INSERT INTO comments (post_id, user_id, content)
+             VALUES ($1, $2, $3)
+             RETURNING id",
+            &[&comment.post_id, &comment.user_id, &comment.content],
+        )?;
+        Ok(row.get("id"))
+    }
+
+    pub fn select_posts_with_user(client: &mut Client, limit: i64) -> Result<Vec<(Post, User)>, postgres::Error> {
+        let rows = client.query(
+            "SELECT
+                p.id as post_id, p.user_id, p.title, p.content, p.status, p.view_count,
+                p.created_at as post_created_at, p.updated_at as post_updated_at,
+                u.id as user_id, u.username, u.email, u.first_name, u.last_name, u.age,
+                u.created_at as user_created_at, u.updated_at as user_updated_at
+             FROM posts p
+             JOIN users u ON p.user_id = u.id
+             ORDER BY p.created_at DESC
+             LIMIT $1",
+            &[&limit],
+        )?;
+
+        rows.iter()
+            .map(|r| Ok((r.post(&PostColumns::JOINED)?, r.user(&UserColumns::JOINED)?)))
+            .collect()
+    }
+
+    pub fn select_users_posts_comments(
+        client: &mut Client,
+        limit: i64,
+    ) -> Result<Vec<(User, Post, Comment)>, postgres::Error> {
+        let rows = client.query(
+            "SELECT
+                u.id as user_id, u.username, u.email, u.first_name, u.last_name, u.age,
+                u.created_at as user_created_at, u.updated_at as user_updated_at,
+                p.id as post_id, p.title, p.content, p.status, p.view_count,
+                p.created_at as post_created_at, p.updated_at as post_updated_at,
+                c.id as comment_id, c.user_id as comment_user_id, c.content as comment_content, c.created_at as comment_created_at
+             FROM users u
+             JOIN posts p ON u.id = p.user_id
+             JOIN comments c ON p.id = c.post_id
+             ORDER BY u.created_at DESC, p.created_at DESC, c.created_at DESC
+             LIMIT $1",
+            &[&limit],
+        )?;
+
+        rows.iter()
+            .map(|r| {
+                Ok((
+                    r.user(&UserColumns::JOINED)?,
+                    r.post(&PostColumns::JOINED)?,
+                    r.comment(&CommentColumns::JOINED)?,
+                ))
+            })
+            .collect()
+    }
+
+    pub fn select_comments_for_post(
+        client: &mut Client,
+        post_id: Uuid,
+        limit: i64,
+        before: Option<CommentKey>,
+    ) -> Result<Vec<Comment>, postgres::Error> {
+        let rows = match before {
+            None => client.query(
+                "SELECT id, post_id, user_id, content, created_at
+                 FROM comments
+                 WHERE post_id = $1
+                 ORDER BY created_at DESC, id DESC
+                 LIMIT $2",
+                &[&post_id, &limit],
+            )?,
+            Some(key) => client.query(
+                "SELECT id, post_id, user_id, content, created_at
+                 FROM comments
+                 WHERE post_id = $1 AND (created_at, id) < ($2, $3)
+                 ORDER BY created_at DESC, id DESC
+                 LIMIT $4",
+                &[&post_id, &key.created_at, &key.id, &limit],
+            )?,
+        };
+
+        rows.iter().map(Comment::try_from).collect()
+    }
+
+    pub fn count_posts_per_user(client: &mut Client) -> Result<Vec<(Uuid, i64)>, postgres::Error> {
+        let rows = client.query(
+            "SELECT u.id, COUNT(p.id) as post_count
+             FROM users u
+             LEFT JOIN posts p ON u.id = p.user_id
+             GROUP BY u.id
+             ORDER BY post_count DESC",
+            &[],
+        )?;
+
+        Ok(rows.iter().map(|r| (r.get(0), r.get(1))).collect())
+    }
+
+    pub fn count_posts_per_tag(client: &mut Client) -> Result<Vec<(Uuid, i64)>, postgres::Error> {
+        let rows = client.query(
+            "SELECT t.id, COUNT(pt.post_id) as post_count
+             FROM tags t
+             JOIN post_tags pt ON t.id = pt.tag_id
+             GROUP BY t.id
+             ORDER BY post_count DESC, t.id",
+            &[],
+        )?;
+
+        Ok(rows.iter().map(|r| (r.get(0), r.get(1))).collect())
+    }
+
+    pub fn insert_user_with_posts(
+        client: &mut Client,
+        user: &NewUser,
+        posts: &[NewPost],
+    ) -> Result<Uuid, postgres::Error> {
+        // Rolled back on drop if any insert fails
+        let mut tx = client.transaction()?;
+        let user_id = Self::insert_user(&mut tx, user)?;
+
+        for post in posts {
+            let mut post = post.clone();
+            post.user_id = user_id;
+            Self::insert_post(&mut tx, &post)?;
+        }
+
+        tx.commit()?;
+        Ok(user_id)
+    }
+
+    pub fn cleanup(client: &mut Client) -> Result<(), postgres::Error> {
+        client.execute("DELETE FROM users WHERE username LIKE 'bench_user_%'", &[])?;
+        Ok(())
+    }
+}
@@ -1,11 +1,19 @@
 //! SeaORM benchmark implementation
 
-use crate::{Comment, NewComment, NewPost, NewUser, Post, User, DATABASE_URL};
+use crate::after_connect::AfterConnect;
+use crate::capability::Capabilities;
+use crate::tls::tls_database_url;
+use crate::{batch, Comment, CommentKey, DatabaseBenchmark, NewComment, NewPost, NewUser, Post, User, database_url};
+use futures::{pin_mut, TryStreamExt};
 use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::extension::postgres::PgExpr;
 use sea_orm::{
-    ActiveModelTrait, ActiveValue, ColumnTrait, Database, DatabaseConnection, DbErr,
-    EntityTrait, QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
+    ActiveModelTrait, ActiveValue, ColumnTrait, Database, DatabaseBackend,
+    DatabaseConnection, DbErr, EntityTrait, JoinType, QueryFilter, QueryOrder, QuerySelect, RuntimeErr,
+    SqlxPostgresConnector, Statement, TransactionTrait,
 };
+use sqlx::postgres::PgConnectOptions;
+use std::str::FromStr;
 use uuid::Uuid;
 
 // Define SeaORM entities
@@ -143,14 +151,49 @@ pub struct SeaOrmBench;
 
 impl SeaOrmBench {
     pub async fn connect() -> Result<DatabaseConnection, DbErr> {
-        Database::connect(DATABASE_URL).await
+        Self::connect_to(&database_url()).await
+    }
+
+    /// Connect to `url` instead of [`database_url`]
+    pub async fn connect_to(url: &str) -> Result<DatabaseConnection, DbErr> {
+        Self::connect_with_hook(sea_orm::ConnectOptions::new(url), &AfterConnect::from_env()).await
     }
 
     /// Connect with a specific pool size for concurrent benchmarks
     pub async fn connect_with_pool_size(pool_size: u32) -> Result<DatabaseConnection, DbErr> {
-        let mut opt = sea_orm::ConnectOptions::new(DATABASE_URL);
+        let mut opt = sea_orm::ConnectOptions::new(database_url());
         opt.max_connections(pool_size);
-        Database::connect(opt).await
+        Self::connect_with_hook(opt, &AfterConnect::from_env()).await
+    }
+
+    /// Connect over TLS through sqlx's stack ([`crate::tls::sqlx_stack`])
+    pub async fn connect_tls() -> Result<DatabaseConnection, DbErr> {
+        Self::connect_to(&tls_database_url()).await
+    }
+
+    /// Connect with `opt`, running `hook` on every connection the pool opens.
+    ///
+    /// SeaORM's own `after_connect` only sets `search_path`, so with a hook the
+    /// sqlx pool is built here from the same options and handed to SeaORM.
+    pub async fn connect_with_hook(opt: sea_orm::ConnectOptions, hook: &AfterConnect) -> Result<DatabaseConnection, DbErr> {
+        if hook.is_empty() {
+            return Database::connect(opt).await;
+        }
+        let url = opt.get_url().to_string();
+        let options = PgConnectOptions::from_str(&url).map_err(|e| DbErr::Conn(RuntimeErr::SqlxError(e)))?;
+        let pool = hook
+            .sqlx_pool_options(opt.sqlx_pool_options())
+            .connect_with(options)
+            .await
+            .map_err(|e| DbErr::Conn(RuntimeErr::SqlxError(e)))?;
+        Ok(SqlxPostgresConnector::from_sqlx_postgres_pool(pool))
+    }
+
+    /// Open every connection the underlying sqlx pool can hold
+    pub async fn warm_pool(db: &DatabaseConnection) -> Result<(), DbErr> {
+        crate::bench_sqlx::SqlxBench::warm_pool(db.get_postgres_connection_pool())
+            .await
+            .map_err(|e| DbErr::Conn(RuntimeErr::SqlxError(e)))
     }
 
     pub async fn insert_user(db: &DatabaseConnection, user: &NewUser) -> Result<Uuid, DbErr> {
@@ -190,16 +233,7 @@ impl SeaOrmBench {
     ) -> Result<Option<User>, DbErr> {
         let user = users::Entity::find_by_id(id).one(db).await?;
 
-        Ok(user.map(|u| User {
-            id: u.id,
-            username: u.username,
-            email: u.email,
-            first_name: u.first_name,
-            last_name: u.last_name,
-            age: u.age,
-            created_at: u.created_at.map(|dt| dt.into()),
-            updated_at: u.updated_at.map(|dt| dt.into()),
-        }))
+        Ok(user.map(User::from))
     }
 
     pub async fn select_users_limit(
@@ -214,19 +248,26 @@ impl SeaOrmBench {
 
         Ok(users_list
             .into_iter()
-            .map(|u| User {
-                id: u.id,
-                username: u.username,
-                email: u.email,
-                first_name: u.first_name,
-                last_name: u.last_name,
-                age: u.age,
-                created_at: u.created_at.map(|dt| dt.into()),
-                updated_at: u.updated_at.map(|dt| dt.into()),
-            })
+            .map(User::from)
             .collect())
     }
 
+    /// [`Self::select_users_limit`] with only the id and username, through
+    /// `select_only` instead of the whole model
+    pub async fn select_usernames_limit(
+        db: &DatabaseConnection,
+        limit: u64,
+    ) -> Result<Vec<(Uuid, String)>, DbErr> {
+        users::Entity::find()
+            .select_only()
+            .columns([users::Column::Id, users::Column::Username])
+            .order_by_desc(users::Column::CreatedAt)
+            .limit(limit)
+            .into_tuple()
+            .all(db)
+            .await
+    }
+
     pub async fn select_users_filtered(
         db: &DatabaseConnection,
         min_age: i32,
@@ -244,16 +285,7 @@ impl SeaOrmBench {
 
         Ok(users_list
             .into_iter()
-            .map(|u| User {
-                id: u.id,
-                username: u.username,
-                email: u.email,
-                first_name: u.first_name,
-                last_name: u.last_name,
-                age: u.age,
-                created_at: u.created_at.map(|dt| dt.into()),
-                updated_at: u.updated_at.map(|dt| dt.into()),
-            })
+            .map(User::from)
             .collect())
     }
 
@@ -269,6 +301,7 @@ impl SeaOrmBench {
             let mut active: users::ActiveModel = user.into();
             active.first_name = ActiveValue::Set(first_name.to_string());
             active.last_name = ActiveValue::Set(last_name.to_string());
+            active.updated_at = ActiveValue::Set(Some(chrono::Utc::now().into()));
             active.update(db).await?;
             Ok(true)
         } else {
@@ -312,30 +345,7 @@ impl SeaOrmBench {
         Ok(posts_with_users
             .into_iter()
             .filter_map(|(p, u)| {
-                u.map(|u| {
-                    (
-                        Post {
-                            id: p.id,
-                            user_id: p.user_id,
-                            title: p.title,
-                            content: p.content,
-                            status: p.status,
-                            view_count: p.view_count,
-                            created_at: p.created_at.map(|dt| dt.into()),
-                            updated_at: p.updated_at.map(|dt| dt.into()),
-                        },
-                        User {
-                            id: u.id,
-                            username: u.username,
-                            email: u.email,
-                            first_name: u.first_name,
-                            last_name: u.last_name,
-                            age: u.age,
-                            created_at: u.created_at.map(|dt| dt.into()),
-                            updated_at: u.updated_at.map(|dt| dt.into()),
-                        },
-                    )
-                })
+                u.map(|u| (p.into(), u.into()))
             })
             .collect())
     }
@@ -344,8 +354,13 @@ impl SeaOrmBench {
         db: &DatabaseConnection,
         limit: u64,
     ) -> Result<Vec<(User, Post, Comment)>, DbErr> {
-        // SeaORM doesn't have native triple join, so we do it with separate queries
+        // SeaORM doesn't have native triple join, so the joined query only picks the
+        // comments (in the same order as the other backends) and the rest is fetched separately
         let comments_list = comments::Entity::find()
+            .join(JoinType::InnerJoin, comments::Relation::Post.def())
+            .join(JoinType::InnerJoin, posts::Relation::User.def())
+            .order_by_desc(users::Column::CreatedAt)
+            .order_by_desc(posts::Column::CreatedAt)
             .order_by_desc(comments::Column::CreatedAt)
             .limit(limit)
             .all(db)
@@ -355,35 +370,7 @@ impl SeaOrmBench {
         for c in comments_list {
             if let Some(post) = posts::Entity::find_by_id(c.post_id).one(db).await? {
                 if let Some(user) = users::Entity::find_by_id(post.user_id).one(db).await? {
-                    results.push((
-                        User {
-                            id: user.id,
-                            username: user.username,
-                            email: user.email,
-                            first_name: user.first_name,
-                            last_name: user.last_name,
-                            age: user.age,
-                            created_at: user.created_at.map(|dt| dt.into()),
-                            updated_at: user.updated_at.map(|dt| dt.into()),
-                        },
-                        Post {
-                            id: post.id,
-                            user_id: post.user_id,
-                            title: post.title,
-                            content: post.content,
-                            status: post.status,
-                            view_count: post.view_count,
-                            created_at: post.created_at.map(|dt| dt.into()),
-                            updated_at: post.updated_at.map(|dt| dt.into()),
-                        },
-                        Comment {
-                            id: c.id,
-                            post_id: c.post_id,
-                            user_id: c.user_id,
-                            content: c.content,
-                            created_at: c.created_at.map(|dt| dt.into()),
-                        },
-                    ));
+                    results.push((user.into(), post.into(), c.into()));
                 }
             }
         }
@@ -391,6 +378,30 @@ impl SeaOrmBench {
         Ok(results)
     }
 
+    pub async fn select_comments_for_post(
+        db: &DatabaseConnection,
+        post_id: Uuid,
+        limit: u64,
+        before: Option<CommentKey>,
+    ) -> Result<Vec<Comment>, DbErr> {
+        let mut query = comments::Entity::find().filter(comments::Column::PostId.eq(post_id));
+        if let Some(key) = before {
+            // Row comparison, so the pair is compared as the ORDER BY sorts it
+            query = query.filter(
+                Expr::tuple([Expr::col(comments::Column::CreatedAt).into(), Expr::col(comments::Column::Id).into()])
+                    .lt(Expr::tuple([key.created_at.into(), key.id.into()])),
+            );
+        }
+        let comments = query
+            .order_by_desc(comments::Column::CreatedAt)
+            .order_by_desc(comments::Column::Id)
+            .limit(limit)
+            .all(db)
+            .await?;
+
+        Ok(comments.into_iter().map(Comment::from).collect())
+    }
+
     pub async fn count_posts_per_user(
         db: &DatabaseConnection,
     ) -> Result<Vec<(Uuid, i64)>, DbErr> {
@@ -419,6 +430,34 @@ impl SeaOrmBench {
         Ok(results)
     }
 
+    pub async fn count_posts_per_tag(
+        db: &DatabaseConnection,
+    ) -> Result<Vec<(Uuid, i64)>, DbErr> {
+        // Raw SQL for the same reason as count_posts_per_user
+        let results: Vec<(Uuid, i64)> = db
+            .query_all(sea_orm::Statement::from_string(
+                sea_orm::DatabaseBackend::Postgres,
+                "SELECT t.id, COUNT(pt.post_id) as post_count
+                 FROM tags t
+                 JOIN post_tags pt ON t.id = pt.tag_id
+                 GROUP BY t.id
+                 ORDER BY post_count DESC, t.id".to_string(),
+            ))
+            .await?
+            .into_iter()
+            .filter_map(|row| {
+                let id: Option<Uuid> = row.try_get("", "id").ok();
+                let count: Option<i64> = row.try_get("", "post_count").ok();
+                match (id, count) {
+                    (Some(id), Some(count)) => Some((id, count)),
+                    _ => None,
+                }
+            })
+            .collect();
+
+        Ok(results)
+    }
+
     pub async fn insert_user_with_posts(
         db: &DatabaseConnection,
         user: &NewUser,
@@ -457,6 +496,22 @@ impl SeaOrmBench {
         Ok(user_id)
     }
 
+    /// Insert a user and a post for it, then update the user, as one multi-statement batch.
+    ///
+    /// `execute_unprepared` goes through sqlx's simple query protocol, which accepts several statements.
+    pub async fn insert_user_post_update_batched(
+        db: &DatabaseConnection,
+        user: &NewUser,
+        post: &NewPost,
+        first_name: &str,
+        last_name: &str,
+    ) -> Result<Uuid, DbErr> {
+        let user_id = Uuid::new_v4();
+        let sql = batch::write_step_sql(user_id, user, post, first_name, last_name);
+        db.execute_unprepared(&sql).await?;
+        Ok(user_id)
+    }
+
     pub async fn cleanup(db: &DatabaseConnection) -> Result<(), DbErr> {
         users::Entity::delete_many()
             .filter(users::Column::Username.starts_with("bench_user_"))
@@ -498,19 +553,53 @@ impl SeaOrmBench {
 
         Ok(posts_list
             .into_iter()
-            .map(|p| Post {
-                id: p.id,
-                user_id: p.user_id,
-                title: p.title,
-                content: p.content,
-                status: p.status,
-                view_count: p.view_count,
-                created_at: p.created_at.map(|dt| dt.into()),
-                updated_at: p.updated_at.map(|dt| dt.into()),
-            })
+            .map(Post::from)
+            .collect())
+    }
+
+    pub async fn select_posts_repeated(db: &DatabaseConnection, copies: i32) -> Result<Vec<Post>, DbErr> {
+        let posts_list = posts::Entity::find()
+            .from_raw_sql(Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                "SELECT p.id, p.user_id, p.title, p.content, p.status, p.view_count, p.created_at, p.updated_at
+                 FROM posts p
+                 CROSS JOIN generate_series(1, $1)",
+                [copies.into()],
+            ))
+            .all(db)
+            .await?;
+
+        Ok(posts_list
+            .into_iter()
+            .map(Post::from)
             .collect())
     }
 
+    pub async fn stream_posts_repeated(
+        db: &DatabaseConnection,
+        copies: i32,
+        mut each: impl FnMut(Post),
+    ) -> Result<usize, DbErr> {
+        let posts_stream = posts::Entity::find()
+            .from_raw_sql(Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                "SELECT p.id, p.user_id, p.title, p.content, p.status, p.view_count, p.created_at, p.updated_at
+                 FROM posts p
+                 CROSS JOIN generate_series(1, $1)",
+                [copies.into()],
+            ))
+            .stream(db)
+            .await?;
+        pin_mut!(posts_stream);
+
+        let mut count = 0;
+        while let Some(post) = posts_stream.try_next().await? {
+            each(Post::from(post));
+            count += 1;
+        }
+        Ok(count)
+    }
+
     pub async fn increment_view_count(db: &DatabaseConnection, post_id: Uuid) -> Result<(), DbErr> {
         if let Some(post) = posts::Entity::find_by_id(post_id).one(db).await? {
             let mut active: posts::ActiveModel = post.into();
@@ -530,9 +619,9 @@ impl SeaOrmBench {
         let pattern = format!("%{}%", pattern);
         let users_list = users::Entity::find()
             .filter(
-                users::Column::FirstName
-                    .contains(&pattern)
-                    .or(users::Column::LastName.contains(&pattern)),
+                Expr::col(users::Column::FirstName)
+                    .ilike(&pattern)
+                    .or(Expr::col(users::Column::LastName).ilike(&pattern)),
             )
             .order_by_asc(users::Column::Username)
             .limit(limit)
@@ -541,16 +630,275 @@ impl SeaOrmBench {
 
         Ok(users_list
             .into_iter()
-            .map(|u| User {
-                id: u.id,
-                username: u.username,
-                email: u.email,
-                first_name: u.first_name,
-                last_name: u.last_name,
-                age: u.age,
-                created_at: u.created_at.map(|dt| dt.into()),
-                updated_at: u.updated_at.map(|dt| dt.into()),
-            })
+            .map(User::from)
             .collect())
     }
 }
+
+// ============================================================================
+// Partial models and custom joins
+// ============================================================================
+
+/// The columns a user listing shows, selected instead of the whole row
+#[derive(Clone, Debug, PartialEq, DerivePartialModel)]
+#[sea_orm(entity = "users::Entity", from_query_result)]
+pub struct UserSummary {
+    pub id: Uuid,
+    pub username: String,
+    pub email: String,
+}
+
+/// A post listing with its author's name, from one join selecting only these
+#[derive(Clone, Debug, PartialEq, DerivePartialModel)]
+#[sea_orm(entity = "posts::Entity", from_query_result)]
+pub struct PostSummary {
+    pub id: Uuid,
+    pub title: String,
+    pub status: String,
+    pub view_count: i32,
+    #[sea_orm(nested)]
+    pub author: Author,
+}
+
+#[derive(Clone, Debug, PartialEq, DerivePartialModel)]
+#[sea_orm(entity = "users::Entity", from_query_result)]
+pub struct Author {
+    pub username: String,
+}
+
+/// How far SeaORM's overhead goes down when a query selects only the columns
+/// it needs or joins in one statement, next to the `Entity::find()` paths the
+/// other benchmarks use
+impl SeaOrmBench {
+    /// [`SeaOrmBench::select_users_limit`] narrowed to a [`UserSummary`]
+    pub async fn select_user_summaries(db: &DatabaseConnection, limit: u64) -> Result<Vec<UserSummary>, DbErr> {
+        users::Entity::find()
+            .order_by_desc(users::Column::CreatedAt)
+            .limit(limit)
+            .into_partial_model::<UserSummary>()
+            .all(db)
+            .await
+    }
+
+    /// [`SeaOrmBench::select_posts_with_user`] narrowed to a [`PostSummary`]
+    pub async fn select_post_summaries(db: &DatabaseConnection, limit: u64) -> Result<Vec<PostSummary>, DbErr> {
+        posts::Entity::find()
+            .inner_join(users::Entity)
+            .order_by_desc(posts::Column::CreatedAt)
+            .limit(limit)
+            .into_partial_model::<PostSummary>()
+            .all(db)
+            .await
+    }
+
+    /// [`SeaOrmBench::select_users_posts_comments`] as a single three-way join
+    /// instead of a query per comment for its post and author
+    pub async fn select_users_posts_comments_joined(
+        db: &DatabaseConnection,
+        limit: u64,
+    ) -> Result<Vec<(User, Post, Comment)>, DbErr> {
+        // Inner joins, as the other backends use: `find_also_related` joins left,
+        // and sorting by the possibly missing author keeps PostgreSQL from walking
+        // the `created_at` index and stopping at the limit
+        let rows = comments::Entity::find()
+            .join(JoinType::InnerJoin, comments::Relation::Post.def())
+            .join(JoinType::InnerJoin, posts::Relation::User.def())
+            .select_also(posts::Entity)
+            .select_also(users::Entity)
+            .order_by_desc(users::Column::CreatedAt)
+            .order_by_desc(posts::Column::CreatedAt)
+            .order_by_desc(comments::Column::CreatedAt)
+            .limit(limit)
+            .all(db)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(c, p, u)| Some((u?.into(), p?.into(), c.into())))
+            .collect())
+    }
+}
+
+// ============================================================================
+// Statement timeout and cancellation
+// ============================================================================
+
+/// Whether the error is PostgreSQL's `query_canceled` (SQLSTATE 57014)
+fn is_query_canceled(err: &DbErr) -> bool {
+    match err {
+        DbErr::Exec(RuntimeErr::SqlxError(e)) | DbErr::Query(RuntimeErr::SqlxError(e)) => {
+            e.as_database_error().and_then(|d| d.code()).as_deref() == Some("57014")
+        }
+        _ => false,
+    }
+}
+
+impl SeaOrmBench {
+    /// Lightweight round trip used to check that the pool still hands out usable connections
+    pub async fn ping(db: &DatabaseConnection) -> Result<(), DbErr> {
+        db.execute_unprepared("SELECT 1").await?;
+        Ok(())
+    }
+
+    /// Run a query that keeps the server busy for `sleep_secs`
+    pub async fn pg_sleep(db: &DatabaseConnection, sleep_secs: f64) -> Result<(), DbErr> {
+        db.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            "SELECT pg_sleep($1)",
+            [sleep_secs.into()],
+        ))
+        .await?;
+        Ok(())
+    }
+
+    /// Run `pg_sleep` under a transaction-scoped `statement_timeout`.
+    ///
+    /// Returns `true` when the server cancelled the statement.
+    pub async fn sleep_with_statement_timeout(
+        db: &DatabaseConnection,
+        timeout_ms: u64,
+        sleep_secs: f64,
+    ) -> Result<bool, DbErr> {
+        let txn = db.begin().await?;
+        txn.execute_unprepared(&format!("SET LOCAL statement_timeout = {}", timeout_ms))
+            .await?;
+        let result = txn
+            .execute(Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                "SELECT pg_sleep($1)",
+                [sleep_secs.into()],
+            ))
+            .await;
+        txn.rollback().await?;
+
+        match result {
+            Ok(_) => Ok(false),
+            Err(e) if is_query_canceled(&e) => Ok(true),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+// ============================================================================
+// DatabaseBenchmark implementation
+// ============================================================================
+
+impl DatabaseBenchmark for SeaOrmBench {
+    type Connection = DatabaseConnection;
+    type Error = DbErr;
+
+    async fn connect() -> Result<Self::Connection, Self::Error> {
+        SeaOrmBench::connect().await
+    }
+
+    async fn connect_to(url: &str) -> Result<Self::Connection, Self::Error> {
+        SeaOrmBench::connect_to(url).await
+    }
+
+    async fn insert_user(conn: &Self::Connection, user: &NewUser) -> Result<Uuid, Self::Error> {
+        SeaOrmBench::insert_user(conn, user).await
+    }
+
+    async fn insert_users_batch(conn: &Self::Connection, users: &[NewUser]) -> Result<Vec<Uuid>, Self::Error> {
+        SeaOrmBench::insert_users_batch(conn, users).await
+    }
+
+    async fn select_user_by_id(conn: &Self::Connection, id: Uuid) -> Result<Option<User>, Self::Error> {
+        SeaOrmBench::select_user_by_id(conn, id).await
+    }
+
+    async fn select_users_limit(conn: &Self::Connection, limit: i64) -> Result<Vec<User>, Self::Error> {
+        SeaOrmBench::select_users_limit(conn, limit as u64).await
+    }
+
+    async fn select_users_filtered(conn: &Self::Connection, min_age: i32, max_age: i32, limit: i64) -> Result<Vec<User>, Self::Error> {
+        SeaOrmBench::select_users_filtered(conn, min_age, max_age, limit as u64).await
+    }
+
+    async fn update_user(conn: &Self::Connection, id: Uuid, first_name: &str, last_name: &str) -> Result<bool, Self::Error> {
+        SeaOrmBench::update_user(conn, id, first_name, last_name).await
+    }
+
+    async fn delete_user(conn: &Self::Connection, id: Uuid) -> Result<bool, Self::Error> {
+        SeaOrmBench::delete_user(conn, id).await
+    }
+
+    async fn insert_post(conn: &Self::Connection, post: &NewPost) -> Result<Uuid, Self::Error> {
+        SeaOrmBench::insert_post(conn, post).await
+    }
+
+    async fn insert_comment(conn: &Self::Connection, comment: &NewComment) -> Result<Uuid, Self::Error> {
+        SeaOrmBench::insert_comment(conn, comment).await
+    }
+
+    async fn select_posts_with_user(conn: &Self::Connection, limit: i64) -> Result<Vec<(Post, User)>, Self::Error> {
+        SeaOrmBench::select_posts_with_user(conn, limit as u64).await
+    }
+
+    async fn select_posts_by_status(conn: &Self::Connection, status: &str, limit: i64) -> Result<Vec<Post>, Self::Error> {
+        SeaOrmBench::select_posts_by_status(conn, status, limit as u64).await
+    }
+
+    async fn select_posts_repeated(conn: &Self::Connection, copies: i32) -> Result<Vec<Post>, Self::Error> {
+        SeaOrmBench::select_posts_repeated(conn, copies).await
+    }
+
+    async fn stream_posts_repeated(conn: &Self::Connection, copies: i32, each: impl FnMut(Post)) -> Result<usize, Self::Error> {
+        SeaOrmBench::stream_posts_repeated(conn, copies, each).await
+    }
+
+    async fn search_users_by_name(conn: &Self::Connection, pattern: &str, limit: i64) -> Result<Vec<User>, Self::Error> {
+        SeaOrmBench::search_users_by_name(conn, pattern, limit as u64).await
+    }
+
+    async fn select_users_posts_comments(conn: &Self::Connection, limit: i64) -> Result<Vec<(User, Post, Comment)>, Self::Error> {
+        SeaOrmBench::select_users_posts_comments(conn, limit as u64).await
+    }
+
+    async fn select_comments_for_post(
+        conn: &Self::Connection,
+        post_id: Uuid,
+        limit: i64,
+        before: Option<CommentKey>,
+    ) -> Result<Vec<Comment>, Self::Error> {
+        SeaOrmBench::select_comments_for_post(conn, post_id, limit as u64, before).await
+    }
+
+    async fn count_posts_per_user(conn: &Self::Connection) -> Result<Vec<(Uuid, i64)>, Self::Error> {
+        SeaOrmBench::count_posts_per_user(conn).await
+    }
+
+    async fn count_posts_per_tag(conn: &Self::Connection) -> Result<Vec<(Uuid, i64)>, Self::Error> {
+        SeaOrmBench::count_posts_per_tag(conn).await
+    }
+
+    async fn insert_user_with_posts(conn: &Self::Connection, user: &NewUser, posts: &[NewPost]) -> Result<Uuid, Self::Error> {
+        SeaOrmBench::insert_user_with_posts(conn, user, posts).await
+    }
+
+    async fn insert_user_post_update_batched(
+        conn: &Self::Connection,
+        user: &NewUser,
+        post: &NewPost,
+        first_name: &str,
+        last_name: &str,
+    ) -> Result<Uuid, Self::Error> {
+        SeaOrmBench::insert_user_post_update_batched(conn, user, post, first_name, last_name).await
+    }
+
+    async fn cleanup(conn: &Self::Connection) -> Result<(), Self::Error> {
+        SeaOrmBench::cleanup(conn).await
+    }
+
+    fn capabilities() -> Capabilities {
+        Capabilities {
+            shared_transactions: true,
+            streaming: true,
+            // Neither is part of its API, only of the sqlx pool underneath
+            copy: false,
+            listen_notify: false,
+            prepared_cache: true,
+            binary_format: true,
+        }
+    }
+}
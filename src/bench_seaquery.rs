@@ -0,0 +1,618 @@
+//! sea-query + sqlx benchmark implementation
+//!
+//! Every statement is built with sea-query, the query builder under SeaORM,
+//! and run through sqlx with `sea-query-binder` passing the bound values;
+//! rows decode through sqlx's `Row` as in [`SqlxBench`]. Between `sqlx` and
+//! `sea_orm` it separates the cost of building SQL from the rest of the ORM
+//! (entities, models, active models and its own connection layer).
+
+use crate::capability::Capabilities;
+use crate::mapping::{CommentColumns, ModelRow, PostColumns, UserColumns};
+use crate::bench_sqlx::SqlxBench;
+use crate::{Comment, CommentKey, DatabaseBenchmark, NewComment, NewPost, NewUser, Post, User};
+use futures::TryStreamExt;
+use sea_query::extension::postgres::PgExpr;
+use sea_query::{Alias, Cond, Expr, Func, Iden, Order, PostgresQueryBuilder, Query, SelectStatement};
+use sea_query_binder::{SqlxBinder, SqlxValues};
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+#[derive(Iden, Clone, Copy)]
+enum Users {
+    Table,
+    Id,
+    Username,
+    Email,
+    FirstName,
+    LastName,
+    Age,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Iden, Clone, Copy)]
+enum Posts {
+    Table,
+    Id,
+    UserId,
+    Title,
+    Content,
+    Status,
+    ViewCount,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Iden, Clone, Copy)]
+enum Comments {
+    Table,
+    Id,
+    PostId,
+    UserId,
+    Content,
+    CreatedAt,
+}
+
+#[derive(Iden, Clone, Copy)]
+enum Tags {
+    Table,
+    Id,
+}
+
+#[derive(Iden, Clone, Copy)]
+enum PostTags {
+    Table,
+    PostId,
+    TagId,
+}
+
+const USER_COLUMNS: [Users; 8] = [
+    Users::Id,
+    Users::Username,
+    Users::Email,
+    Users::FirstName,
+    Users::LastName,
+    Users::Age,
+    Users::CreatedAt,
+    Users::UpdatedAt,
+];
+
+const COMMENT_COLUMNS: [Comments; 5] = [
+    Comments::Id,
+    Comments::PostId,
+    Comments::UserId,
+    Comments::Content,
+    Comments::CreatedAt,
+];
+
+const POST_COLUMNS: [Posts; 8] = [
+    Posts::Id,
+    Posts::UserId,
+    Posts::Title,
+    Posts::Content,
+    Posts::Status,
+    Posts::ViewCount,
+    Posts::CreatedAt,
+    Posts::UpdatedAt,
+];
+
+/// Select the user columns of a join under the names [`UserColumns::JOINED`] reads
+fn joined_user_columns(select: &mut SelectStatement) -> &mut SelectStatement {
+    select
+        .expr_as(Expr::col((Users::Table, Users::Id)), Alias::new("user_id"))
+        .columns([
+            (Users::Table, Users::Username),
+            (Users::Table, Users::Email),
+            (Users::Table, Users::FirstName),
+            (Users::Table, Users::LastName),
+            (Users::Table, Users::Age),
+        ])
+        .expr_as(Expr::col((Users::Table, Users::CreatedAt)), Alias::new("user_created_at"))
+        .expr_as(Expr::col((Users::Table, Users::UpdatedAt)), Alias::new("user_updated_at"))
+}
+
+/// Select the post columns of a join under the names [`PostColumns::JOINED`] reads
+fn joined_post_columns(select: &mut SelectStatement) -> &mut SelectStatement {
+    select
+        .expr_as(Expr::col((Posts::Table, Posts::Id)), Alias::new("post_id"))
+        .columns([
+            (Posts::Table, Posts::Title),
+            (Posts::Table, Posts::Content),
+            (Posts::Table, Posts::Status),
+            (Posts::Table, Posts::ViewCount),
+        ])
+        .expr_as(Expr::col((Posts::Table, Posts::CreatedAt)), Alias::new("post_created_at"))
+        .expr_as(Expr::col((Posts::Table, Posts::UpdatedAt)), Alias::new("post_updated_at"))
+}
+
+/// Every post `copies` times over, as `FROM posts, generate_series(1, copies)`
+fn posts_repeated(copies: i32) -> (String, SqlxValues) {
+    Query::select()
+        .columns(POST_COLUMNS.map(|c| (Posts::Table, c)))
+        .from(Posts::Table)
+        .from_function(Func::cust(Alias::new("generate_series")).arg(1).arg(copies), Alias::new("copy"))
+        .build_sqlx(PostgresQueryBuilder)
+}
+
+fn insert_user_statement(user: &NewUser) -> (String, SqlxValues) {
+    Query::insert()
+        .into_table(Users::Table)
+        .columns([Users::Username, Users::Email, Users::FirstName, Users::LastName, Users::Age])
+        .values_panic([
+            user.username.as_str().into(),
+            user.email.as_str().into(),
+            user.first_name.as_str().into(),
+            user.last_name.as_str().into(),
+            user.age.into(),
+        ])
+        .returning_col(Users::Id)
+        .build_sqlx(PostgresQueryBuilder)
+}
+
+fn insert_post_statement(user_id: Uuid, post: &NewPost) -> (String, SqlxValues) {
+    Query::insert()
+        .into_table(Posts::Table)
+        .columns([Posts::UserId, Posts::Title, Posts::Content, Posts::Status])
+        .values_panic([
+            user_id.into(),
+            post.title.as_str().into(),
+            post.content.as_str().into(),
+            post.status.as_str().into(),
+        ])
+        .returning_col(Posts::Id)
+        .build_sqlx(PostgresQueryBuilder)
+}
+
+pub struct SeaQueryBench;
+
+/// The statements of the reads the query builder microbenchmark times
+impl SeaQueryBench {
+    pub fn select_user_by_id_statement(id: Uuid) -> (String, SqlxValues) {
+        Query::select()
+            .columns(USER_COLUMNS)
+            .from(Users::Table)
+            .and_where(Expr::col(Users::Id).eq(id))
+            .build_sqlx(PostgresQueryBuilder)
+    }
+
+    pub fn select_users_limit_statement(limit: i64) -> (String, SqlxValues) {
+        Query::select()
+            .columns(USER_COLUMNS)
+            .from(Users::Table)
+            .order_by(Users::CreatedAt, Order::Desc)
+            .limit(limit as u64)
+            .build_sqlx(PostgresQueryBuilder)
+    }
+
+    pub fn select_posts_with_user_statement(limit: i64) -> (String, SqlxValues) {
+        let mut select = Query::select();
+        joined_post_columns(&mut select).column((Posts::Table, Posts::UserId));
+        joined_user_columns(&mut select)
+            .from(Posts::Table)
+            .inner_join(
+                Users::Table,
+                Expr::col((Posts::Table, Posts::UserId)).equals((Users::Table, Users::Id)),
+            )
+            .order_by((Posts::Table, Posts::CreatedAt), Order::Desc)
+            .limit(limit as u64)
+            .build_sqlx(PostgresQueryBuilder)
+    }
+}
+
+impl SeaQueryBench {
+    pub async fn insert_user(pool: &PgPool, user: &NewUser) -> Result<Uuid, sqlx::Error> {
+        let (sql, values) = insert_user_statement(user);
+        let row = sqlx::query_with(&sql, values).fetch_one(pool).await?;
+        row.try_get("id")
+    }
+
+    pub async fn insert_users_batch(pool: &PgPool, users: &[NewUser]) -> Result<Vec<Uuid>, sqlx::Error> {
+        let mut ids = Vec::with_capacity(users.len());
+        for user in users {
+            ids.push(Self::insert_user(pool, user).await?);
+        }
+        Ok(ids)
+    }
+
+    pub async fn select_user_by_id(pool: &PgPool, id: Uuid) -> Result<Option<User>, sqlx::Error> {
+        let (sql, values) = Self::select_user_by_id_statement(id);
+        let row = sqlx::query_with(&sql, values).fetch_optional(pool).await?;
+        row.as_ref().map(User::try_from).transpose()
+    }
+
+    pub async fn select_users_limit(pool: &PgPool, limit: i64) -> Result<Vec<User>, sqlx::Error> {
+        let (sql, values) = Self::select_users_limit_statement(limit);
+        let rows = sqlx::query_with(&sql, values).fetch_all(pool).await?;
+        rows.iter().map(User::try_from).collect()
+    }
+
+    pub async fn select_users_filtered(
+        pool: &PgPool,
+        min_age: i32,
+        max_age: i32,
+        limit: i64,
+    ) -> Result<Vec<User>, sqlx::Error> {
+        let (sql, values) = Query::select()
+            .columns(USER_COLUMNS)
+            .from(Users::Table)
+            .and_where(Expr::col(Users::Age).gte(min_age))
+            .and_where(Expr::col(Users::Age).lte(max_age))
+            .order_by(Users::Age, Order::Asc)
+            .order_by(Users::Username, Order::Asc)
+            .limit(limit as u64)
+            .build_sqlx(PostgresQueryBuilder);
+        let rows = sqlx::query_with(&sql, values).fetch_all(pool).await?;
+        rows.iter().map(User::try_from).collect()
+    }
+
+    pub async fn update_user(pool: &PgPool, id: Uuid, first_name: &str, last_name: &str) -> Result<bool, sqlx::Error> {
+        let (sql, values) = Query::update()
+            .table(Users::Table)
+            .value(Users::FirstName, first_name)
+            .value(Users::LastName, last_name)
+            .value(Users::UpdatedAt, Expr::cust("NOW()"))
+            .and_where(Expr::col(Users::Id).eq(id))
+            .build_sqlx(PostgresQueryBuilder);
+        let result = sqlx::query_with(&sql, values).execute(pool).await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn delete_user(pool: &PgPool, id: Uuid) -> Result<bool, sqlx::Error> {
+        let (sql, values) = Query::delete()
+            .from_table(Users::Table)
+            .and_where(Expr::col(Users::Id).eq(id))
+            .build_sqlx(PostgresQueryBuilder);
+        let result = sqlx::query_with(&sql, values).execute(pool).await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn insert_post(pool: &PgPool, post: &NewPost) -> Result<Uuid, sqlx::Error> {
+        let (sql, values) = insert_post_statement(post.user_id, post);
+        let row = sqlx::query_with(&sql, values).fetch_one(pool).await?;
+        row.try_get("id")
+    }
+
+    pub async fn insert_comment(pool: &PgPool, comment: &NewComment) -> Result<Uuid, sqlx::Error> {
+        let (sql, values) = Query::insert()
+            .into_table(Comments::Table)
+            .columns([Comments::PostId, Comments::UserId, Comments::Content])
+            .values_panic([comment.post_id.into(), comment.user_id.into(), comment.content.as_str().into()])
+            .returning_col(Comments::Id)
+            .build_sqlx(PostgresQueryBuilder);
+        let row = sqlx::query_with(&sql, values).fetch_one(pool).await?;
+        row.try_get("id")
+    }
+
+    pub async fn select_posts_with_user(pool: &PgPool, limit: i64) -> Result<Vec<(Post, User)>, sqlx::Error> {
+        let (sql, values) = Self::select_posts_with_user_statement(limit);
+        let rows = sqlx::query_with(&sql, values).fetch_all(pool).await?;
+        rows.iter()
+            .map(|r| Ok((r.post(&PostColumns::JOINED)?, r.user(&UserColumns::JOINED)?)))
+            .collect()
+    }
+
+    pub async fn select_posts_by_status(pool: &PgPool, status: &str, limit: i64) -> Result<Vec<Post>, sqlx::Error> {
+        let (sql, values) = Query::select()
+            .columns(POST_COLUMNS)
+            .from(Posts::Table)
+            .and_where(Expr::col(Posts::Status).eq(status))
+            .order_by(Posts::CreatedAt, Order::Desc)
+            .limit(limit as u64)
+            .build_sqlx(PostgresQueryBuilder);
+        let rows = sqlx::query_with(&sql, values).fetch_all(pool).await?;
+        rows.iter().map(Post::try_from).collect()
+    }
+
+    pub async fn select_posts_repeated(pool: &PgPool, copies: i32) -> Result<Vec<Post>, sqlx::Error> {
+        let (sql, values) = posts_repeated(copies);
+        let rows = sqlx::query_with(&sql, values).fetch_all(pool).await?;
+        rows.iter().map(Post::try_from).collect()
+    }
+
+    pub async fn stream_posts_repeated(pool: &PgPool, copies: i32, mut each: impl FnMut(Post)) -> Result<usize, sqlx::Error> {
+        let (sql, values) = posts_repeated(copies);
+        let mut rows = sqlx::query_with(&sql, values).fetch(pool);
+
+        let mut count = 0;
+        while let Some(row) = rows.try_next().await? {
+            each(Post::try_from(&row)?);
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    pub async fn search_users_by_name(pool: &PgPool, pattern: &str, limit: i64) -> Result<Vec<User>, sqlx::Error> {
+        let pattern = format!("%{}%", pattern);
+        let (sql, values) = Query::select()
+            .columns(USER_COLUMNS)
+            .from(Users::Table)
+            .cond_where(
+                Cond::any()
+                    .add(Expr::col(Users::FirstName).ilike(&pattern))
+                    .add(Expr::col(Users::LastName).ilike(&pattern)),
+            )
+            .order_by(Users::Username, Order::Asc)
+            .limit(limit as u64)
+            .build_sqlx(PostgresQueryBuilder);
+        let rows = sqlx::query_with(&sql, values).fetch_all(pool).await?;
+        rows.iter().map(User::try_from).collect()
+    }
+
+    pub async fn select_users_posts_comments(pool: &PgPool, limit: i64) -> Result<Vec<(User, Post, Comment)>, sqlx::Error> {
+        let mut select = Query::select();
+        joined_user_columns(&mut select);
+        joined_post_columns(&mut select)
+            .expr_as(Expr::col((Comments::Table, Comments::Id)), Alias::new("comment_id"))
+            .expr_as(Expr::col((Comments::Table, Comments::UserId)), Alias::new("comment_user_id"))
+            .expr_as(Expr::col((Comments::Table, Comments::Content)), Alias::new("comment_content"))
+            .expr_as(Expr::col((Comments::Table, Comments::CreatedAt)), Alias::new("comment_created_at"))
+            .from(Users::Table)
+            .inner_join(
+                Posts::Table,
+                Expr::col((Users::Table, Users::Id)).equals((Posts::Table, Posts::UserId)),
+            )
+            .inner_join(
+                Comments::Table,
+                Expr::col((Posts::Table, Posts::Id)).equals((Comments::Table, Comments::PostId)),
+            )
+            .order_by((Users::Table, Users::CreatedAt), Order::Desc)
+            .order_by((Posts::Table, Posts::CreatedAt), Order::Desc)
+            .order_by((Comments::Table, Comments::CreatedAt), Order::Desc)
+            .limit(limit as u64);
+        let (sql, values) = select.build_sqlx(PostgresQueryBuilder);
+        let rows = sqlx::query_with(&sql, values).fetch_all(pool).await?;
+        rows.iter()
+            .map(|r| {
+                Ok((
+                    r.user(&UserColumns::JOINED)?,
+                    r.post(&PostColumns::JOINED)?,
+                    r.comment(&CommentColumns::JOINED)?,
+                ))
+            })
+            .collect()
+    }
+
+    pub async fn select_comments_for_post(
+        pool: &PgPool,
+        post_id: Uuid,
+        limit: i64,
+        before: Option<CommentKey>,
+    ) -> Result<Vec<Comment>, sqlx::Error> {
+        let mut select = Query::select();
+        select
+            .columns(COMMENT_COLUMNS)
+            .from(Comments::Table)
+            .and_where(Expr::col(Comments::PostId).eq(post_id));
+        if let Some(key) = before {
+            select.and_where(
+                Expr::tuple([Expr::col(Comments::CreatedAt).into(), Expr::col(Comments::Id).into()])
+                    .lt(Expr::tuple([key.created_at.into(), key.id.into()])),
+            );
+        }
+        let (sql, values) = select
+            .order_by(Comments::CreatedAt, Order::Desc)
+            .order_by(Comments::Id, Order::Desc)
+            .limit(limit as u64)
+            .build_sqlx(PostgresQueryBuilder);
+        let rows = sqlx::query_with(&sql, values).fetch_all(pool).await?;
+        rows.iter().map(Comment::try_from).collect()
+    }
+
+    pub async fn count_posts_per_user(pool: &PgPool) -> Result<Vec<(Uuid, i64)>, sqlx::Error> {
+        let (sql, values) = Query::select()
+            .column((Users::Table, Users::Id))
+            .expr_as(Func::count(Expr::col((Posts::Table, Posts::Id))), Alias::new("post_count"))
+            .from(Users::Table)
+            .left_join(
+                Posts::Table,
+                Expr::col((Users::Table, Users::Id)).equals((Posts::Table, Posts::UserId)),
+            )
+            .group_by_col((Users::Table, Users::Id))
+            .order_by(Alias::new("post_count"), Order::Desc)
+            .build_sqlx(PostgresQueryBuilder);
+        let rows = sqlx::query_with(&sql, values).fetch_all(pool).await?;
+        rows.iter().map(|r| Ok((r.try_get(0)?, r.try_get(1)?))).collect()
+    }
+
+    pub async fn count_posts_per_tag(pool: &PgPool) -> Result<Vec<(Uuid, i64)>, sqlx::Error> {
+        let (sql, values) = Query::select()
+            .column((Tags::Table, Tags::Id))
+            .expr_as(Func::count(Expr::col((PostTags::Table, PostTags::PostId))), Alias::new("post_count"))
+            .from(Tags::Table)
+            .inner_join(
+                PostTags::Table,
+                Expr::col((Tags::Table, Tags::Id)).equals((PostTags::Table, PostTags::TagId)),
+            )
+            .group_by_col((Tags::Table, Tags::Id))
+            .order_by(Alias::new("post_count"), Order::Desc)
+            .order_by((Tags::Table, Tags::Id), Order::Asc)
+            .build_sqlx(PostgresQueryBuilder);
+        let rows = sqlx::query_with(&sql, values).fetch_all(pool).await?;
+        rows.iter().map(|r| Ok((r.try_get(0)?, r.try_get(1)?))).collect()
+    }
+
+    pub async fn insert_user_with_posts(pool: &PgPool, user: &NewUser, posts: &[NewPost]) -> Result<Uuid, sqlx::Error> {
+        let mut tx = pool.begin().await?;
+
+        let (sql, values) = insert_user_statement(user);
+        let user_id: Uuid = sqlx::query_with(&sql, values).fetch_one(&mut *tx).await?.try_get("id")?;
+
+        for post in posts {
+            let (sql, values) = insert_post_statement(user_id, post);
+            sqlx::query_with(&sql, values).execute(&mut *tx).await?;
+        }
+
+        tx.commit().await?;
+        Ok(user_id)
+    }
+
+    /// The three statements rendered with their values inlined by sea-query
+    /// (`to_string`), sent as one batch
+    pub async fn insert_user_post_update_batched(
+        pool: &PgPool,
+        user: &NewUser,
+        post: &NewPost,
+        first_name: &str,
+        last_name: &str,
+    ) -> Result<Uuid, sqlx::Error> {
+        let user_id = Uuid::new_v4();
+        let insert_user = Query::insert()
+            .into_table(Users::Table)
+            .columns([Users::Id, Users::Username, Users::Email, Users::FirstName, Users::LastName, Users::Age])
+            .values_panic([
+                user_id.into(),
+                user.username.as_str().into(),
+                user.email.as_str().into(),
+                user.first_name.as_str().into(),
+                user.last_name.as_str().into(),
+                user.age.into(),
+            ])
+            .to_string(PostgresQueryBuilder);
+        let insert_post = Query::insert()
+            .into_table(Posts::Table)
+            .columns([Posts::UserId, Posts::Title, Posts::Content, Posts::Status])
+            .values_panic([
+                user_id.into(),
+                post.title.as_str().into(),
+                post.content.as_str().into(),
+                post.status.as_str().into(),
+            ])
+            .to_string(PostgresQueryBuilder);
+        let update_user = Query::update()
+            .table(Users::Table)
+            .value(Users::FirstName, first_name)
+            .value(Users::LastName, last_name)
+            .value(Users::UpdatedAt, Expr::cust("NOW()"))
+            .and_where(Expr::col(Users::Id).eq(user_id))
+            .to_string(PostgresQueryBuilder);
+
+        let sql = format!("{insert_user};\n{insert_post};\n{update_user}");
+        sqlx::raw_sql(&sql).execute(pool).await?;
+        Ok(user_id)
+    }
+
+    pub async fn cleanup(pool: &PgPool) -> Result<(), sqlx::Error> {
+        let (sql, values) = Query::delete()
+            .from_table(Users::Table)
+            .and_where(Expr::col(Users::Username).like("bench_user_%"))
+            .build_sqlx(PostgresQueryBuilder);
+        sqlx::query_with(&sql, values).execute(pool).await?;
+        Ok(())
+    }
+}
+
+// ============================================================================
+// DatabaseBenchmark implementation
+// ============================================================================
+
+impl DatabaseBenchmark for SeaQueryBench {
+    type Connection = PgPool;
+    type Error = sqlx::Error;
+
+    async fn connect() -> Result<Self::Connection, Self::Error> {
+        SqlxBench::connect().await
+    }
+
+    async fn connect_to(url: &str) -> Result<Self::Connection, Self::Error> {
+        SqlxBench::connect_to(url).await
+    }
+
+    async fn insert_user(conn: &Self::Connection, user: &NewUser) -> Result<Uuid, Self::Error> {
+        SeaQueryBench::insert_user(conn, user).await
+    }
+
+    async fn insert_users_batch(conn: &Self::Connection, users: &[NewUser]) -> Result<Vec<Uuid>, Self::Error> {
+        SeaQueryBench::insert_users_batch(conn, users).await
+    }
+
+    async fn select_user_by_id(conn: &Self::Connection, id: Uuid) -> Result<Option<User>, Self::Error> {
+        SeaQueryBench::select_user_by_id(conn, id).await
+    }
+
+    async fn select_users_limit(conn: &Self::Connection, limit: i64) -> Result<Vec<User>, Self::Error> {
+        SeaQueryBench::select_users_limit(conn, limit).await
+    }
+
+    async fn select_users_filtered(conn: &Self::Connection, min_age: i32, max_age: i32, limit: i64) -> Result<Vec<User>, Self::Error> {
+        SeaQueryBench::select_users_filtered(conn, min_age, max_age, limit).await
+    }
+
+    async fn update_user(conn: &Self::Connection, id: Uuid, first_name: &str, last_name: &str) -> Result<bool, Self::Error> {
+        SeaQueryBench::update_user(conn, id, first_name, last_name).await
+    }
+
+    async fn delete_user(conn: &Self::Connection, id: Uuid) -> Result<bool, Self::Error> {
+        SeaQueryBench::delete_user(conn, id).await
+    }
+
+    async fn insert_post(conn: &Self::Connection, post: &NewPost) -> Result<Uuid, Self::Error> {
+        SeaQueryBench::insert_post(conn, post).await
+    }
+
+    async fn insert_comment(conn: &Self::Connection, comment: &NewComment) -> Result<Uuid, Self::Error> {
+        SeaQueryBench::insert_comment(conn, comment).await
+    }
+
+    async fn select_posts_with_user(conn: &Self::Connection, limit: i64) -> Result<Vec<(Post, User)>, Self::Error> {
+        SeaQueryBench::select_posts_with_user(conn, limit).await
+    }
+
+    async fn select_posts_by_status(conn: &Self::Connection, status: &str, limit: i64) -> Result<Vec<Post>, Self::Error> {
+        SeaQueryBench::select_posts_by_status(conn, status, limit).await
+    }
+
+    async fn select_posts_repeated(conn: &Self::Connection, copies: i32) -> Result<Vec<Post>, Self::Error> {
+        SeaQueryBench::select_posts_repeated(conn, copies).await
+    }
+
+    async fn stream_posts_repeated(conn: &Self::Connection, copies: i32, each: impl FnMut(Post)) -> Result<usize, Self::Error> {
+        SeaQueryBench::stream_posts_repeated(conn, copies, each).await
+    }
+
+    async fn search_users_by_name(conn: &Self::Connection, pattern: &str, limit: i64) -> Result<Vec<User>, Self::Error> {
+        SeaQueryBench::search_users_by_name(conn, pattern, limit).await
+    }
+
+    async fn select_users_posts_comments(conn: &Self::Connection, limit: i64) -> Result<Vec<(User, Post, Comment)>, Self::Error> {
+        SeaQueryBench::select_users_posts_comments(conn, limit).await
+    }
+
+    async fn select_comments_for_post(
+        conn: &Self::Connection,
+        post_id: Uuid,
+        limit: i64,
+        before: Option<CommentKey>,
+    ) -> Result<Vec<Comment>, Self::Error> {
+        SeaQueryBench::select_comments_for_post(conn, post_id, limit, before).await
+    }
+
+    async fn count_posts_per_user(conn: &Self::Connection) -> Result<Vec<(Uuid, i64)>, Self::Error> {
+        SeaQueryBench::count_posts_per_user(conn).await
+    }
+
+    async fn count_posts_per_tag(conn: &Self::Connection) -> Result<Vec<(Uuid, i64)>, Self::Error> {
+        SeaQueryBench::count_posts_per_tag(conn).await
+    }
+
+    async fn insert_user_with_posts(conn: &Self::Connection, user: &NewUser, posts: &[NewPost]) -> Result<Uuid, Self::Error> {
+        SeaQueryBench::insert_user_with_posts(conn, user, posts).await
+    }
+
+    async fn insert_user_post_update_batched(
+        conn: &Self::Connection,
+        user: &NewUser,
+        post: &NewPost,
+        first_name: &str,
+        last_name: &str,
+    ) -> Result<Uuid, Self::Error> {
+        SeaQueryBench::insert_user_post_update_batched(conn, user, post, first_name, last_name).await
+    }
+
+    async fn cleanup(conn: &Self::Connection) -> Result<(), Self::Error> {
+        SeaQueryBench::cleanup(conn).await
+    }
+
+    fn capabilities() -> Capabilities {
+        SqlxBench::capabilities()
+    }
+}
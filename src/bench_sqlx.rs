@@ -1,28 +1,119 @@
 //! SQLx benchmark implementation
 
-use crate::{Comment, NewComment, NewPost, NewUser, Post, User, DATABASE_URL};
-use sqlx::postgres::{PgPool, PgPoolOptions};
-use sqlx::Row;
+use crate::after_connect::AfterConnect;
+use crate::capability::Capabilities;
+use crate::mapping::{CommentColumns, ModelRow, PostColumns, UserColumns};
+use crate::tls::tls_database_url;
+use crate::{batch, Comment, CommentKey, DatabaseBenchmark, NewComment, NewPost, NewUser, Post, User, database_url};
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+use sqlx::postgres::{PgConnectOptions, PgConnection, PgPool, PgPoolOptions};
+use sqlx::{Connection, Row};
+use std::str::FromStr;
 use uuid::Uuid;
 
+/// sqlx's own per-connection statement cache capacity
+pub const DEFAULT_STATEMENT_CACHE_CAPACITY: usize = 100;
+
+/// Driver settings every sqlx pool is built with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SqlxConfig {
+    /// Prepared statements kept per connection, least recently used first out.
+    ///
+    /// With 0 every query is prepared again, and sqlx 0.8 still prepares it as a
+    /// named statement that is never closed, so they pile up on the server for
+    /// the connection's lifetime.
+    pub statement_cache_capacity: usize,
+}
+
+impl Default for SqlxConfig {
+    fn default() -> Self {
+        Self {
+            statement_cache_capacity: DEFAULT_STATEMENT_CACHE_CAPACITY,
+        }
+    }
+}
+
+impl SqlxConfig {
+    /// Read `BENCH_SQLX_STATEMENT_CACHE`.
+    ///
+    /// # Panics
+    ///
+    /// If `BENCH_SQLX_STATEMENT_CACHE` is not a number.
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            statement_cache_capacity: std::env::var("BENCH_SQLX_STATEMENT_CACHE")
+                .map(|capacity| {
+                    capacity
+                        .parse()
+                        .expect("BENCH_SQLX_STATEMENT_CACHE must be a number of statements")
+                })
+                .unwrap_or(defaults.statement_cache_capacity),
+        }
+    }
+
+    pub fn with_statement_cache_capacity(mut self, statement_cache_capacity: usize) -> Self {
+        self.statement_cache_capacity = statement_cache_capacity;
+        self
+    }
+}
+
 pub struct SqlxBench;
 
 impl SqlxBench {
     pub async fn connect() -> Result<PgPool, sqlx::Error> {
-        PgPoolOptions::new()
-            .max_connections(10)
-            .connect(DATABASE_URL)
-            .await
+        Self::connect_to(&database_url()).await
+    }
+    
+    /// Connect a pool to `url` instead of [`database_url`]
+    pub async fn connect_to(url: &str) -> Result<PgPool, sqlx::Error> {
+        Self::connect_with_config(url, 10, &SqlxConfig::from_env()).await
     }
     
     /// Connect with a specific pool size for concurrent benchmarks
     pub async fn connect_with_pool_size(pool_size: u32) -> Result<PgPool, sqlx::Error> {
-        PgPoolOptions::new()
-            .max_connections(pool_size)
-            .connect(DATABASE_URL)
+        Self::connect_with_config(&database_url(), pool_size, &SqlxConfig::from_env()).await
+    }
+    
+    /// Connect over TLS, through the stack sqlx is compiled with ([`crate::tls::sqlx_stack`])
+    pub async fn connect_tls() -> Result<PgPool, sqlx::Error> {
+        Self::connect_with_config(&tls_database_url(), 10, &SqlxConfig::from_env()).await
+    }
+    
+    /// Connect a pool of `pool_size` connections to `url` with `config` instead of the environment's
+    pub async fn connect_with_config(url: &str, pool_size: u32, config: &SqlxConfig) -> Result<PgPool, sqlx::Error> {
+        Self::connect_with_hook(url, pool_size, config, &AfterConnect::from_env()).await
+    }
+    
+    /// [`SqlxBench::connect_with_config`] whose connections run `hook` when opened
+    pub async fn connect_with_hook(
+        url: &str,
+        pool_size: u32,
+        config: &SqlxConfig,
+        hook: &AfterConnect,
+    ) -> Result<PgPool, sqlx::Error> {
+        let options = PgConnectOptions::from_str(url)?.statement_cache_capacity(config.statement_cache_capacity);
+        hook.sqlx_pool_options(PgPoolOptions::new().max_connections(pool_size))
+            .connect_with(options)
             .await
     }
     
+    /// Open a single connection to `url` outside any pool
+    pub async fn connect_single(url: &str) -> Result<PgConnection, sqlx::Error> {
+        let mut conn = PgConnection::connect(url).await?;
+        AfterConnect::from_env().run_sqlx(&mut conn).await?;
+        Ok(conn)
+    }
+    
+    /// Open every connection the pool can hold so measurement never pays for connects
+    pub async fn warm_pool(pool: &PgPool) -> Result<(), sqlx::Error> {
+        let size = pool.options().get_max_connections();
+        let conns = futures::future::try_join_all((0..size).map(|_| pool.acquire())).await?;
+        drop(conns);
+        Ok(())
+    }
+    
     pub async fn insert_user(pool: &PgPool, user: &NewUser) -> Result<Uuid, sqlx::Error> {
         let row = sqlx::query(
             "INSERT INTO users (username, email, first_name, last_name, age) 
@@ -33,7 +124,7 @@ impl SqlxBench {
         .bind(&user.email)
         .bind(&user.first_name)
         .bind(&user.last_name)
-        .bind(&user.age)
+        .bind(user.age)
         .fetch_one(pool)
         .await?;
         
@@ -60,16 +151,7 @@ impl SqlxBench {
         .fetch_optional(pool)
         .await?;
         
-        Ok(row.map(|r| User {
-            id: r.get("id"),
-            username: r.get("username"),
-            email: r.get("email"),
-            first_name: r.get("first_name"),
-            last_name: r.get("last_name"),
-            age: r.get("age"),
-            created_at: r.get("created_at"),
-            updated_at: r.get("updated_at"),
-        }))
+        row.as_ref().map(User::try_from).transpose()
     }
     
     pub async fn select_users_limit(pool: &PgPool, limit: i64) -> Result<Vec<User>, sqlx::Error> {
@@ -81,19 +163,15 @@ impl SqlxBench {
         .fetch_all(pool)
         .await?;
         
-        Ok(rows
-            .iter()
-            .map(|r| User {
-                id: r.get("id"),
-                username: r.get("username"),
-                email: r.get("email"),
-                first_name: r.get("first_name"),
-                last_name: r.get("last_name"),
-                age: r.get("age"),
-                created_at: r.get("created_at"),
-                updated_at: r.get("updated_at"),
-            })
-            .collect())
+        rows.iter().map(User::try_from).collect()
+    }
+    
+    /// [`Self::select_users_limit`] with only the id and username
+    pub async fn select_usernames_limit(pool: &PgPool, limit: i64) -> Result<Vec<(Uuid, String)>, sqlx::Error> {
+        sqlx::query_as("SELECT id, username FROM users ORDER BY created_at DESC LIMIT $1")
+            .bind(limit)
+            .fetch_all(pool)
+            .await
     }
     
     pub async fn select_users_filtered(
@@ -115,19 +193,7 @@ impl SqlxBench {
         .fetch_all(pool)
         .await?;
         
-        Ok(rows
-            .iter()
-            .map(|r| User {
-                id: r.get("id"),
-                username: r.get("username"),
-                email: r.get("email"),
-                first_name: r.get("first_name"),
-                last_name: r.get("last_name"),
-                age: r.get("age"),
-                created_at: r.get("created_at"),
-                updated_at: r.get("updated_at"),
-            })
-            .collect())
+        rows.iter().map(User::try_from).collect()
     }
     
     pub async fn update_user(
@@ -192,32 +258,9 @@ impl SqlxBench {
         .fetch_all(pool)
         .await?;
         
-        Ok(rows
-            .iter()
-            .map(|r| {
-                let post = Post {
-                    id: r.get("post_id"),
-                    user_id: r.get("user_id"),
-                    title: r.get("title"),
-                    content: r.get("content"),
-                    status: r.get("status"),
-                    view_count: r.get("view_count"),
-                    created_at: r.get("post_created_at"),
-                    updated_at: r.get("post_updated_at"),
-                };
-                let user = User {
-                    id: r.get("user_id"),
-                    username: r.get("username"),
-                    email: r.get("email"),
-                    first_name: r.get("first_name"),
-                    last_name: r.get("last_name"),
-                    age: r.get("age"),
-                    created_at: r.get("user_created_at"),
-                    updated_at: r.get("user_updated_at"),
-                };
-                (post, user)
-            })
-            .collect())
+        rows.iter()
+            .map(|r| Ok((r.post(&PostColumns::JOINED)?, r.user(&UserColumns::JOINED)?)))
+            .collect()
     }
     
     pub async fn select_users_posts_comments(
@@ -230,7 +273,7 @@ impl SqlxBench {
                 u.created_at as user_created_at, u.updated_at as user_updated_at,
                 p.id as post_id, p.title, p.content, p.status, p.view_count,
                 p.created_at as post_created_at, p.updated_at as post_updated_at,
-                c.id as comment_id, c.content as comment_content, c.created_at as comment_created_at
+                c.id as comment_id, c.user_id as comment_user_id, c.content as comment_content, c.created_at as comment_created_at
              FROM users u
              JOIN posts p ON u.id = p.user_id
              JOIN comments c ON p.id = c.post_id
@@ -241,39 +284,55 @@ impl SqlxBench {
         .fetch_all(pool)
         .await?;
         
-        Ok(rows
-            .iter()
+        rows.iter()
             .map(|r| {
-                let user = User {
-                    id: r.get("user_id"),
-                    username: r.get("username"),
-                    email: r.get("email"),
-                    first_name: r.get("first_name"),
-                    last_name: r.get("last_name"),
-                    age: r.get("age"),
-                    created_at: r.get("user_created_at"),
-                    updated_at: r.get("user_updated_at"),
-                };
-                let post = Post {
-                    id: r.get("post_id"),
-                    user_id: r.get("user_id"),
-                    title: r.get("title"),
-                    content: r.get("content"),
-                    status: r.get("status"),
-                    view_count: r.get("view_count"),
-                    created_at: r.get("post_created_at"),
-                    updated_at: r.get("post_updated_at"),
-                };
-                let comment = Comment {
-                    id: r.get("comment_id"),
-                    post_id: r.get("post_id"),
-                    user_id: r.get("user_id"),
-                    content: r.get("comment_content"),
-                    created_at: r.get("comment_created_at"),
-                };
-                (user, post, comment)
+                Ok((
+                    r.user(&UserColumns::JOINED)?,
+                    r.post(&PostColumns::JOINED)?,
+                    r.comment(&CommentColumns::JOINED)?,
+                ))
             })
-            .collect())
+            .collect()
+    }
+    
+    pub async fn select_comments_for_post(
+        pool: &PgPool,
+        post_id: Uuid,
+        limit: i64,
+        before: Option<CommentKey>,
+    ) -> Result<Vec<Comment>, sqlx::Error> {
+        let rows = match before {
+            None => {
+                sqlx::query(
+                    "SELECT id, post_id, user_id, content, created_at
+                     FROM comments
+                     WHERE post_id = $1
+                     ORDER BY created_at DESC, id DESC
+                     LIMIT $2"
+                )
+                .bind(post_id)
+                .bind(limit)
+                .fetch_all(pool)
+                .await?
+            }
+            Some(key) => {
+                sqlx::query(
+                    "SELECT id, post_id, user_id, content, created_at
+                     FROM comments
+                     WHERE post_id = $1 AND (created_at, id) < ($2, $3)
+                     ORDER BY created_at DESC, id DESC
+                     LIMIT $4"
+                )
+                .bind(post_id)
+                .bind(key.created_at)
+                .bind(key.id)
+                .bind(limit)
+                .fetch_all(pool)
+                .await?
+            }
+        };
+
+        rows.iter().map(Comment::try_from).collect()
     }
     
     pub async fn count_posts_per_user(pool: &PgPool) -> Result<Vec<(Uuid, i64)>, sqlx::Error> {
@@ -289,6 +348,20 @@ impl SqlxBench {
         
         Ok(rows.iter().map(|r| (r.get(0), r.get(1))).collect())
     }
+
+    pub async fn count_posts_per_tag(pool: &PgPool) -> Result<Vec<(Uuid, i64)>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT t.id, COUNT(pt.post_id) as post_count
+             FROM tags t
+             JOIN post_tags pt ON t.id = pt.tag_id
+             GROUP BY t.id
+             ORDER BY post_count DESC, t.id"
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows.iter().map(|r| (r.get(0), r.get(1))).collect())
+    }
     
     pub async fn insert_user_with_posts(
         pool: &PgPool,
@@ -306,7 +379,7 @@ impl SqlxBench {
         .bind(&user.email)
         .bind(&user.first_name)
         .bind(&user.last_name)
-        .bind(&user.age)
+        .bind(user.age)
         .fetch_one(&mut *tx)
         .await?;
         
@@ -328,6 +401,20 @@ impl SqlxBench {
         tx.commit().await?;
         Ok(user_id)
     }
+
+    /// Insert a user and a post for it, then update the user, as one multi-statement batch
+    pub async fn insert_user_post_update_batched(
+        pool: &PgPool,
+        user: &NewUser,
+        post: &NewPost,
+        first_name: &str,
+        last_name: &str,
+    ) -> Result<Uuid, sqlx::Error> {
+        let user_id = Uuid::new_v4();
+        let sql = batch::write_step_sql(user_id, user, post, first_name, last_name);
+        sqlx::raw_sql(&sql).execute(pool).await?;
+        Ok(user_id)
+    }
     
     pub async fn cleanup(pool: &PgPool) -> Result<(), sqlx::Error> {
         sqlx::query("DELETE FROM users WHERE username LIKE 'bench_user_%'")
@@ -370,19 +457,37 @@ impl SqlxBench {
         .fetch_all(pool)
         .await?;
         
-        Ok(rows
-            .iter()
-            .map(|r| Post {
-                id: r.get("id"),
-                user_id: r.get("user_id"),
-                title: r.get("title"),
-                content: r.get("content"),
-                status: r.get("status"),
-                view_count: r.get("view_count"),
-                created_at: r.get("created_at"),
-                updated_at: r.get("updated_at"),
-            })
-            .collect())
+        rows.iter().map(Post::try_from).collect()
+    }
+    
+    pub async fn select_posts_repeated(pool: &PgPool, copies: i32) -> Result<Vec<Post>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT p.id, p.user_id, p.title, p.content, p.status, p.view_count, p.created_at, p.updated_at 
+             FROM posts p 
+             CROSS JOIN generate_series(1, $1)"
+        )
+        .bind(copies)
+        .fetch_all(pool)
+        .await?;
+        
+        rows.iter().map(Post::try_from).collect()
+    }
+
+    pub async fn stream_posts_repeated(pool: &PgPool, copies: i32, mut each: impl FnMut(Post)) -> Result<usize, sqlx::Error> {
+        let mut rows = sqlx::query(
+            "SELECT p.id, p.user_id, p.title, p.content, p.status, p.view_count, p.created_at, p.updated_at
+             FROM posts p
+             CROSS JOIN generate_series(1, $1)"
+        )
+        .bind(copies)
+        .fetch(pool);
+
+        let mut count = 0;
+        while let Some(row) = rows.try_next().await? {
+            each(Post::try_from(&row)?);
+            count += 1;
+        }
+        Ok(count)
     }
     
     pub async fn increment_view_count(pool: &PgPool, post_id: Uuid) -> Result<(), sqlx::Error> {
@@ -393,6 +498,13 @@ impl SqlxBench {
         Ok(())
     }
     
+    /// One of many queries that differ only in their SQL text, so each `variant`
+    /// takes its own slot in the statement cache
+    pub async fn select_usernames_variant(pool: &PgPool, variant: usize, min_age: i32) -> Result<Vec<String>, sqlx::Error> {
+        let sql = format!("SELECT username FROM users WHERE age >= $1 ORDER BY id LIMIT {}", variant + 1);
+        sqlx::query_scalar(&sql).bind(min_age).fetch_all(pool).await
+    }
+    
     pub async fn search_users_by_name(
         pool: &PgPool,
         pattern: &str,
@@ -411,18 +523,926 @@ impl SqlxBench {
         .fetch_all(pool)
         .await?;
         
+        rows.iter().map(User::try_from).collect()
+    }
+}
+
+// ============================================================================
+// Query styles: compile-time checked macros and FromRow
+// ============================================================================
+
+/// A `users` row as `query_as` decodes it
+#[derive(sqlx::FromRow)]
+struct UserRecord {
+    id: Uuid,
+    username: String,
+    email: String,
+    first_name: String,
+    last_name: String,
+    age: Option<i32>,
+    created_at: Option<DateTime<Utc>>,
+    updated_at: Option<DateTime<Utc>>,
+}
+
+impl From<UserRecord> for User {
+    fn from(u: UserRecord) -> Self {
+        Self {
+            id: u.id,
+            username: u.username,
+            email: u.email,
+            first_name: u.first_name,
+            last_name: u.last_name,
+            age: u.age,
+            created_at: u.created_at,
+            updated_at: u.updated_at,
+        }
+    }
+}
+
+/// A row of the posts-with-author join as `query_as` decodes it
+#[derive(sqlx::FromRow)]
+struct PostWithUserRecord {
+    post_id: Uuid,
+    user_id: Uuid,
+    title: String,
+    content: String,
+    status: String,
+    view_count: i32,
+    post_created_at: Option<DateTime<Utc>>,
+    post_updated_at: Option<DateTime<Utc>>,
+    username: String,
+    email: String,
+    first_name: String,
+    last_name: String,
+    age: Option<i32>,
+    user_created_at: Option<DateTime<Utc>>,
+    user_updated_at: Option<DateTime<Utc>>,
+}
+
+impl From<PostWithUserRecord> for (Post, User) {
+    fn from(r: PostWithUserRecord) -> Self {
+        let post = Post {
+            id: r.post_id,
+            user_id: r.user_id,
+            title: r.title,
+            content: r.content,
+            status: r.status,
+            view_count: r.view_count,
+            created_at: r.post_created_at,
+            updated_at: r.post_updated_at,
+        };
+        let user = User {
+            id: r.user_id,
+            username: r.username,
+            email: r.email,
+            first_name: r.first_name,
+            last_name: r.last_name,
+            age: r.age,
+            created_at: r.user_created_at,
+            updated_at: r.user_updated_at,
+        };
+        (post, user)
+    }
+}
+
+/// The reads of [`SqlxBench::select_user_by_id`], [`SqlxBench::select_users_limit`]
+/// and [`SqlxBench::select_posts_with_user`] written with `query!`, checked
+/// against the schema at compile time (from `.sqlx/` when offline), and with
+/// `query_as` decoding into `FromRow` structs
+impl SqlxBench {
+    pub async fn select_user_by_id_macro(pool: &PgPool, id: Uuid) -> Result<Option<User>, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"SELECT id, username, email, first_name, last_name, age,
+                created_at as "created_at: DateTime<Utc>", updated_at as "updated_at: DateTime<Utc>"
+             FROM users WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(row.map(|u| User {
+            id: u.id,
+            username: u.username,
+            email: u.email,
+            first_name: u.first_name,
+            last_name: u.last_name,
+            age: u.age,
+            created_at: u.created_at,
+            updated_at: u.updated_at,
+        }))
+    }
+
+    pub async fn select_users_limit_macro(pool: &PgPool, limit: i64) -> Result<Vec<User>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"SELECT id, username, email, first_name, last_name, age,
+                created_at as "created_at: DateTime<Utc>", updated_at as "updated_at: DateTime<Utc>"
+             FROM users ORDER BY created_at DESC LIMIT $1"#,
+            limit
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|u| User {
+                id: u.id,
+                username: u.username,
+                email: u.email,
+                first_name: u.first_name,
+                last_name: u.last_name,
+                age: u.age,
+                created_at: u.created_at,
+                updated_at: u.updated_at,
+            })
+            .collect())
+    }
+
+    pub async fn select_posts_with_user_macro(pool: &PgPool, limit: i64) -> Result<Vec<(Post, User)>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"SELECT
+                p.id as post_id, p.user_id, p.title, p.content, p.status, p.view_count,
+                p.created_at as "post_created_at: DateTime<Utc>", p.updated_at as "post_updated_at: DateTime<Utc>",
+                u.username, u.email, u.first_name, u.last_name, u.age,
+                u.created_at as "user_created_at: DateTime<Utc>", u.updated_at as "user_updated_at: DateTime<Utc>"
+             FROM posts p
+             JOIN users u ON p.user_id = u.id
+             ORDER BY p.created_at DESC
+             LIMIT $1"#,
+            limit
+        )
+        .fetch_all(pool)
+        .await?;
+
         Ok(rows
-            .iter()
-            .map(|r| User {
-                id: r.get("id"),
-                username: r.get("username"),
-                email: r.get("email"),
-                first_name: r.get("first_name"),
-                last_name: r.get("last_name"),
-                age: r.get("age"),
-                created_at: r.get("created_at"),
-                updated_at: r.get("updated_at"),
+            .into_iter()
+            .map(|r| {
+                let post = Post {
+                    id: r.post_id,
+                    user_id: r.user_id,
+                    title: r.title,
+                    content: r.content,
+                    status: r.status,
+                    view_count: r.view_count,
+                    created_at: r.post_created_at,
+                    updated_at: r.post_updated_at,
+                };
+                let user = User {
+                    id: r.user_id,
+                    username: r.username,
+                    email: r.email,
+                    first_name: r.first_name,
+                    last_name: r.last_name,
+                    age: r.age,
+                    created_at: r.user_created_at,
+                    updated_at: r.user_updated_at,
+                };
+                (post, user)
             })
             .collect())
     }
+
+    pub async fn select_user_by_id_as(pool: &PgPool, id: Uuid) -> Result<Option<User>, sqlx::Error> {
+        let row = sqlx::query_as::<_, UserRecord>(
+            "SELECT id, username, email, first_name, last_name, age, created_at, updated_at
+             FROM users WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(row.map(User::from))
+    }
+
+    pub async fn select_users_limit_as(pool: &PgPool, limit: i64) -> Result<Vec<User>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, UserRecord>(
+            "SELECT id, username, email, first_name, last_name, age, created_at, updated_at
+             FROM users ORDER BY created_at DESC LIMIT $1",
+        )
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows.into_iter().map(User::from).collect())
+    }
+
+    pub async fn select_posts_with_user_as(pool: &PgPool, limit: i64) -> Result<Vec<(Post, User)>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, PostWithUserRecord>(
+            "SELECT
+                p.id as post_id, p.user_id, p.title, p.content, p.status, p.view_count,
+                p.created_at as post_created_at, p.updated_at as post_updated_at,
+                u.username, u.email, u.first_name, u.last_name, u.age,
+                u.created_at as user_created_at, u.updated_at as user_updated_at
+             FROM posts p
+             JOIN users u ON p.user_id = u.id
+             ORDER BY p.created_at DESC
+             LIMIT $1",
+        )
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+}
+
+// ============================================================================
+// Compile-time checked variant
+// ============================================================================
+
+/// A `posts` row as `query_as!` decodes it
+struct PostRecord {
+    id: Uuid,
+    user_id: Uuid,
+    title: String,
+    content: String,
+    status: String,
+    view_count: i32,
+    created_at: Option<DateTime<Utc>>,
+    updated_at: Option<DateTime<Utc>>,
+}
+
+impl From<PostRecord> for Post {
+    fn from(p: PostRecord) -> Self {
+        Self {
+            id: p.id,
+            user_id: p.user_id,
+            title: p.title,
+            content: p.content,
+            status: p.status,
+            view_count: p.view_count,
+            created_at: p.created_at,
+            updated_at: p.updated_at,
+        }
+    }
+}
+
+/// A row of the users -> posts -> comments join as `query_as!` decodes it
+struct UserPostCommentRecord {
+    user_id: Uuid,
+    username: String,
+    email: String,
+    first_name: String,
+    last_name: String,
+    age: Option<i32>,
+    user_created_at: Option<DateTime<Utc>>,
+    user_updated_at: Option<DateTime<Utc>>,
+    post_id: Uuid,
+    title: String,
+    content: String,
+    status: String,
+    view_count: i32,
+    post_created_at: Option<DateTime<Utc>>,
+    post_updated_at: Option<DateTime<Utc>>,
+    comment_id: Uuid,
+    comment_user_id: Uuid,
+    comment_content: String,
+    comment_created_at: Option<DateTime<Utc>>,
+}
+
+impl From<UserPostCommentRecord> for (User, Post, Comment) {
+    fn from(r: UserPostCommentRecord) -> Self {
+        let user = User {
+            id: r.user_id,
+            username: r.username,
+            email: r.email,
+            first_name: r.first_name,
+            last_name: r.last_name,
+            age: r.age,
+            created_at: r.user_created_at,
+            updated_at: r.user_updated_at,
+        };
+        let post = Post {
+            id: r.post_id,
+            user_id: r.user_id,
+            title: r.title,
+            content: r.content,
+            status: r.status,
+            view_count: r.view_count,
+            created_at: r.post_created_at,
+            updated_at: r.post_updated_at,
+        };
+        let comment = Comment {
+            id: r.comment_id,
+            post_id: r.post_id,
+            user_id: r.comment_user_id,
+            content: r.comment_content,
+            created_at: r.comment_created_at,
+        };
+        (user, post, comment)
+    }
+}
+
+/// A `comments` row as `query_as!` decodes it
+struct CommentRecord {
+    id: Uuid,
+    post_id: Uuid,
+    user_id: Uuid,
+    content: String,
+    created_at: Option<DateTime<Utc>>,
+}
+
+impl From<CommentRecord> for Comment {
+    fn from(c: CommentRecord) -> Self {
+        Self {
+            id: c.id,
+            post_id: c.post_id,
+            user_id: c.user_id,
+            content: c.content,
+            created_at: c.created_at,
+        }
+    }
+}
+
+/// sqlx again, with every query written with the `query!` family of macros
+/// instead of `query()` strings: the SQL is checked against the schema at
+/// compile time (from `.sqlx/` when offline), arguments are typed, and rows
+/// decode by position into structs known ahead of time rather than by
+/// looking each column up by name. Same pool, statement cache and SQL as
+/// [`SqlxBench`], so the gap between the two series is what the dynamic
+/// API costs at run time.
+pub struct SqlxMacroBench;
+
+impl SqlxMacroBench {
+    pub async fn insert_user(pool: &PgPool, user: &NewUser) -> Result<Uuid, sqlx::Error> {
+        sqlx::query_scalar!(
+            "INSERT INTO users (username, email, first_name, last_name, age)
+             VALUES ($1, $2, $3, $4, $5)
+             RETURNING id",
+            user.username,
+            user.email,
+            user.first_name,
+            user.last_name,
+            user.age
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn insert_users_batch(pool: &PgPool, users: &[NewUser]) -> Result<Vec<Uuid>, sqlx::Error> {
+        let mut ids = Vec::with_capacity(users.len());
+        for user in users {
+            ids.push(Self::insert_user(pool, user).await?);
+        }
+        Ok(ids)
+    }
+
+    pub async fn select_users_filtered(
+        pool: &PgPool,
+        min_age: i32,
+        max_age: i32,
+        limit: i64,
+    ) -> Result<Vec<User>, sqlx::Error> {
+        let rows = sqlx::query_as!(
+            UserRecord,
+            r#"SELECT id, username, email, first_name, last_name, age,
+                created_at as "created_at: DateTime<Utc>", updated_at as "updated_at: DateTime<Utc>"
+             FROM users
+             WHERE age >= $1 AND age <= $2
+             ORDER BY age, username
+             LIMIT $3"#,
+            min_age,
+            max_age,
+            limit
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows.into_iter().map(User::from).collect())
+    }
+
+    pub async fn update_user(pool: &PgPool, id: Uuid, first_name: &str, last_name: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "UPDATE users SET first_name = $1, last_name = $2, updated_at = NOW() WHERE id = $3",
+            first_name,
+            last_name,
+            id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn delete_user(pool: &PgPool, id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM users WHERE id = $1", id).execute(pool).await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn insert_post(pool: &PgPool, post: &NewPost) -> Result<Uuid, sqlx::Error> {
+        sqlx::query_scalar!(
+            "INSERT INTO posts (user_id, title, content, status)
+             VALUES ($1, $2, $3, $4)
+             RETURNING id",
+            post.user_id,
+            post.title,
+            post.content,
+            post.status
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn insert_comment(pool: &PgPool, comment: &NewComment) -> Result<Uuid, sqlx::Error> {
+        sqlx::query_scalar!(
+            "INSERT INTO comments (post_id, user_id, content)
+             VALUES ($1, $2, $3)
+             RETURNING id",
+            comment.post_id,
+            comment.user_id,
+            comment.content
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn select_posts_repeated(pool: &PgPool, copies: i32) -> Result<Vec<Post>, sqlx::Error> {
+        let rows = sqlx::query_as!(
+            PostRecord,
+            r#"SELECT p.id, p.user_id, p.title, p.content, p.status, p.view_count,
+                p.created_at as "created_at: DateTime<Utc>", p.updated_at as "updated_at: DateTime<Utc>"
+             FROM posts p
+             CROSS JOIN generate_series(1, $1)"#,
+            copies
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows.into_iter().map(Post::from).collect())
+    }
+
+    pub async fn select_posts_by_status(pool: &PgPool, status: &str, limit: i64) -> Result<Vec<Post>, sqlx::Error> {
+        let rows = sqlx::query_as!(
+            PostRecord,
+            r#"SELECT id, user_id, title, content, status, view_count,
+                created_at as "created_at: DateTime<Utc>", updated_at as "updated_at: DateTime<Utc>"
+             FROM posts
+             WHERE status = $1
+             ORDER BY created_at DESC
+             LIMIT $2"#,
+            status,
+            limit
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows.into_iter().map(Post::from).collect())
+    }
+
+    pub async fn stream_posts_repeated(pool: &PgPool, copies: i32, mut each: impl FnMut(Post)) -> Result<usize, sqlx::Error> {
+        let mut rows = sqlx::query_as!(
+            PostRecord,
+            r#"SELECT p.id, p.user_id, p.title, p.content, p.status, p.view_count,
+                p.created_at as "created_at: DateTime<Utc>", p.updated_at as "updated_at: DateTime<Utc>"
+             FROM posts p
+             CROSS JOIN generate_series(1, $1)"#,
+            copies
+        )
+        .fetch(pool);
+
+        let mut count = 0;
+        while let Some(row) = rows.try_next().await? {
+            each(row.into());
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    pub async fn search_users_by_name(pool: &PgPool, pattern: &str, limit: i64) -> Result<Vec<User>, sqlx::Error> {
+        let pattern = format!("%{}%", pattern);
+        let rows = sqlx::query_as!(
+            UserRecord,
+            r#"SELECT id, username, email, first_name, last_name, age,
+                created_at as "created_at: DateTime<Utc>", updated_at as "updated_at: DateTime<Utc>"
+             FROM users
+             WHERE first_name ILIKE $1 OR last_name ILIKE $1
+             ORDER BY username
+             LIMIT $2"#,
+            pattern,
+            limit
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows.into_iter().map(User::from).collect())
+    }
+
+    pub async fn select_users_posts_comments(pool: &PgPool, limit: i64) -> Result<Vec<(User, Post, Comment)>, sqlx::Error> {
+        let rows = sqlx::query_as!(
+            UserPostCommentRecord,
+            r#"SELECT
+                u.id as user_id, u.username, u.email, u.first_name, u.last_name, u.age,
+                u.created_at as "user_created_at: DateTime<Utc>", u.updated_at as "user_updated_at: DateTime<Utc>",
+                p.id as post_id, p.title, p.content, p.status, p.view_count,
+                p.created_at as "post_created_at: DateTime<Utc>", p.updated_at as "post_updated_at: DateTime<Utc>",
+                c.id as comment_id, c.user_id as comment_user_id, c.content as comment_content,
+                c.created_at as "comment_created_at: DateTime<Utc>"
+             FROM users u
+             JOIN posts p ON u.id = p.user_id
+             JOIN comments c ON p.id = c.post_id
+             ORDER BY u.created_at DESC, p.created_at DESC, c.created_at DESC
+             LIMIT $1"#,
+            limit
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    pub async fn select_comments_for_post(
+        pool: &PgPool,
+        post_id: Uuid,
+        limit: i64,
+        before: Option<CommentKey>,
+    ) -> Result<Vec<Comment>, sqlx::Error> {
+        let rows = match before {
+            None => {
+                sqlx::query_as!(
+                    CommentRecord,
+                    r#"SELECT id, post_id, user_id, content, created_at as "created_at: DateTime<Utc>"
+                     FROM comments
+                     WHERE post_id = $1
+                     ORDER BY created_at DESC, id DESC
+                     LIMIT $2"#,
+                    post_id,
+                    limit
+                )
+                .fetch_all(pool)
+                .await?
+            }
+            Some(key) => {
+                sqlx::query_as!(
+                    CommentRecord,
+                    r#"SELECT id, post_id, user_id, content, created_at as "created_at: DateTime<Utc>"
+                     FROM comments
+                     WHERE post_id = $1 AND (created_at, id) < ($2, $3)
+                     ORDER BY created_at DESC, id DESC
+                     LIMIT $4"#,
+                    post_id,
+                    // SeaORM turns on sqlx's `time` support, which the macros infer instead
+                    key.created_at as _,
+                    key.id,
+                    limit
+                )
+                .fetch_all(pool)
+                .await?
+            }
+        };
+
+        Ok(rows.into_iter().map(Comment::from).collect())
+    }
+
+    pub async fn count_posts_per_user(pool: &PgPool) -> Result<Vec<(Uuid, i64)>, sqlx::Error> {
+        // COUNT is never NULL, which the macro can't tell from the plan; the
+        // `!` becomes part of the alias, so ORDER BY repeats the aggregate
+        let rows = sqlx::query!(
+            r#"SELECT u.id, COUNT(p.id) as "post_count!"
+             FROM users u
+             LEFT JOIN posts p ON u.id = p.user_id
+             GROUP BY u.id
+             ORDER BY COUNT(p.id) DESC"#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|r| (r.id, r.post_count)).collect())
+    }
+
+    pub async fn count_posts_per_tag(pool: &PgPool) -> Result<Vec<(Uuid, i64)>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"SELECT t.id, COUNT(pt.post_id) as "post_count!"
+             FROM tags t
+             JOIN post_tags pt ON t.id = pt.tag_id
+             GROUP BY t.id
+             ORDER BY COUNT(pt.post_id) DESC, t.id"#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|r| (r.id, r.post_count)).collect())
+    }
+
+    pub async fn insert_user_with_posts(pool: &PgPool, user: &NewUser, posts: &[NewPost]) -> Result<Uuid, sqlx::Error> {
+        let mut tx = pool.begin().await?;
+
+        let user_id = sqlx::query_scalar!(
+            "INSERT INTO users (username, email, first_name, last_name, age)
+             VALUES ($1, $2, $3, $4, $5)
+             RETURNING id",
+            user.username,
+            user.email,
+            user.first_name,
+            user.last_name,
+            user.age
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        for post in posts {
+            sqlx::query!(
+                "INSERT INTO posts (user_id, title, content, status)
+                 VALUES ($1, $2, $3, $4)",
+                user_id,
+                post.title,
+                post.content,
+                post.status
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(user_id)
+    }
+
+    pub async fn cleanup(pool: &PgPool) -> Result<(), sqlx::Error> {
+        sqlx::query!("DELETE FROM users WHERE username LIKE 'bench_user_%'")
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Statement timeout and cancellation
+// ============================================================================
+
+impl SqlxBench {
+    /// Lightweight round trip used to check that the pool still hands out usable connections
+    pub async fn ping(pool: &PgPool) -> Result<(), sqlx::Error> {
+        sqlx::query("SELECT 1").execute(pool).await?;
+        Ok(())
+    }
+
+    /// Run a query that keeps the server busy for `sleep_secs`
+    pub async fn pg_sleep(pool: &PgPool, sleep_secs: f64) -> Result<(), sqlx::Error> {
+        sqlx::query("SELECT pg_sleep($1)")
+            .bind(sleep_secs)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Run `pg_sleep` under a transaction-scoped `statement_timeout`.
+    ///
+    /// Returns `true` when the server cancelled the statement.
+    pub async fn sleep_with_statement_timeout(
+        pool: &PgPool,
+        timeout_ms: u64,
+        sleep_secs: f64,
+    ) -> Result<bool, sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        sqlx::query(&format!("SET LOCAL statement_timeout = {}", timeout_ms))
+            .execute(&mut *tx)
+            .await?;
+        let result = sqlx::query("SELECT pg_sleep($1)")
+            .bind(sleep_secs)
+            .execute(&mut *tx)
+            .await;
+        tx.rollback().await?;
+
+        match result {
+            Ok(_) => Ok(false),
+            Err(sqlx::Error::Database(e)) if e.code().as_deref() == Some("57014") => Ok(true),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+// ============================================================================
+// DatabaseBenchmark implementation
+// ============================================================================
+
+impl DatabaseBenchmark for SqlxBench {
+    type Connection = PgPool;
+    type Error = sqlx::Error;
+
+    async fn connect() -> Result<Self::Connection, Self::Error> {
+        SqlxBench::connect().await
+    }
+
+    async fn connect_to(url: &str) -> Result<Self::Connection, Self::Error> {
+        SqlxBench::connect_to(url).await
+    }
+
+    async fn insert_user(conn: &Self::Connection, user: &NewUser) -> Result<Uuid, Self::Error> {
+        SqlxBench::insert_user(conn, user).await
+    }
+
+    async fn insert_users_batch(conn: &Self::Connection, users: &[NewUser]) -> Result<Vec<Uuid>, Self::Error> {
+        SqlxBench::insert_users_batch(conn, users).await
+    }
+
+    async fn select_user_by_id(conn: &Self::Connection, id: Uuid) -> Result<Option<User>, Self::Error> {
+        SqlxBench::select_user_by_id(conn, id).await
+    }
+
+    async fn select_users_limit(conn: &Self::Connection, limit: i64) -> Result<Vec<User>, Self::Error> {
+        SqlxBench::select_users_limit(conn, limit).await
+    }
+
+    async fn select_users_filtered(conn: &Self::Connection, min_age: i32, max_age: i32, limit: i64) -> Result<Vec<User>, Self::Error> {
+        SqlxBench::select_users_filtered(conn, min_age, max_age, limit).await
+    }
+
+    async fn update_user(conn: &Self::Connection, id: Uuid, first_name: &str, last_name: &str) -> Result<bool, Self::Error> {
+        SqlxBench::update_user(conn, id, first_name, last_name).await
+    }
+
+    async fn delete_user(conn: &Self::Connection, id: Uuid) -> Result<bool, Self::Error> {
+        SqlxBench::delete_user(conn, id).await
+    }
+
+    async fn insert_post(conn: &Self::Connection, post: &NewPost) -> Result<Uuid, Self::Error> {
+        SqlxBench::insert_post(conn, post).await
+    }
+
+    async fn insert_comment(conn: &Self::Connection, comment: &NewComment) -> Result<Uuid, Self::Error> {
+        SqlxBench::insert_comment(conn, comment).await
+    }
+
+    async fn select_posts_with_user(conn: &Self::Connection, limit: i64) -> Result<Vec<(Post, User)>, Self::Error> {
+        SqlxBench::select_posts_with_user(conn, limit).await
+    }
+
+    async fn select_posts_by_status(conn: &Self::Connection, status: &str, limit: i64) -> Result<Vec<Post>, Self::Error> {
+        SqlxBench::select_posts_by_status(conn, status, limit).await
+    }
+
+    async fn select_posts_repeated(conn: &Self::Connection, copies: i32) -> Result<Vec<Post>, Self::Error> {
+        SqlxBench::select_posts_repeated(conn, copies).await
+    }
+
+    async fn stream_posts_repeated(conn: &Self::Connection, copies: i32, each: impl FnMut(Post)) -> Result<usize, Self::Error> {
+        SqlxBench::stream_posts_repeated(conn, copies, each).await
+    }
+
+    async fn search_users_by_name(conn: &Self::Connection, pattern: &str, limit: i64) -> Result<Vec<User>, Self::Error> {
+        SqlxBench::search_users_by_name(conn, pattern, limit).await
+    }
+
+    async fn select_users_posts_comments(conn: &Self::Connection, limit: i64) -> Result<Vec<(User, Post, Comment)>, Self::Error> {
+        SqlxBench::select_users_posts_comments(conn, limit).await
+    }
+
+    async fn select_comments_for_post(
+        conn: &Self::Connection,
+        post_id: Uuid,
+        limit: i64,
+        before: Option<CommentKey>,
+    ) -> Result<Vec<Comment>, Self::Error> {
+        SqlxBench::select_comments_for_post(conn, post_id, limit, before).await
+    }
+
+    async fn count_posts_per_user(conn: &Self::Connection) -> Result<Vec<(Uuid, i64)>, Self::Error> {
+        SqlxBench::count_posts_per_user(conn).await
+    }
+
+    async fn count_posts_per_tag(conn: &Self::Connection) -> Result<Vec<(Uuid, i64)>, Self::Error> {
+        SqlxBench::count_posts_per_tag(conn).await
+    }
+
+    async fn insert_user_with_posts(conn: &Self::Connection, user: &NewUser, posts: &[NewPost]) -> Result<Uuid, Self::Error> {
+        SqlxBench::insert_user_with_posts(conn, user, posts).await
+    }
+
+    async fn insert_user_post_update_batched(
+        conn: &Self::Connection,
+        user: &NewUser,
+        post: &NewPost,
+        first_name: &str,
+        last_name: &str,
+    ) -> Result<Uuid, Self::Error> {
+        SqlxBench::insert_user_post_update_batched(conn, user, post, first_name, last_name).await
+    }
+
+    async fn cleanup(conn: &Self::Connection) -> Result<(), Self::Error> {
+        SqlxBench::cleanup(conn).await
+    }
+
+    fn capabilities() -> Capabilities {
+        Capabilities {
+            shared_transactions: true,
+            streaming: true,
+            copy: true,
+            // Through `PgListener`, on a connection of its own
+            listen_notify: true,
+            prepared_cache: true,
+            binary_format: true,
+        }
+    }
+}
+
+impl DatabaseBenchmark for SqlxMacroBench {
+    type Connection = PgPool;
+    type Error = sqlx::Error;
+
+    async fn connect() -> Result<Self::Connection, Self::Error> {
+        SqlxBench::connect().await
+    }
+
+    async fn connect_to(url: &str) -> Result<Self::Connection, Self::Error> {
+        SqlxBench::connect_to(url).await
+    }
+
+    async fn insert_user(conn: &Self::Connection, user: &NewUser) -> Result<Uuid, Self::Error> {
+        SqlxMacroBench::insert_user(conn, user).await
+    }
+
+    async fn insert_users_batch(conn: &Self::Connection, users: &[NewUser]) -> Result<Vec<Uuid>, Self::Error> {
+        SqlxMacroBench::insert_users_batch(conn, users).await
+    }
+
+    async fn select_user_by_id(conn: &Self::Connection, id: Uuid) -> Result<Option<User>, Self::Error> {
+        SqlxBench::select_user_by_id_macro(conn, id).await
+    }
+
+    async fn select_users_limit(conn: &Self::Connection, limit: i64) -> Result<Vec<User>, Self::Error> {
+        SqlxBench::select_users_limit_macro(conn, limit).await
+    }
+
+    async fn select_users_filtered(conn: &Self::Connection, min_age: i32, max_age: i32, limit: i64) -> Result<Vec<User>, Self::Error> {
+        SqlxMacroBench::select_users_filtered(conn, min_age, max_age, limit).await
+    }
+
+    async fn update_user(conn: &Self::Connection, id: Uuid, first_name: &str, last_name: &str) -> Result<bool, Self::Error> {
+        SqlxMacroBench::update_user(conn, id, first_name, last_name).await
+    }
+
+    async fn delete_user(conn: &Self::Connection, id: Uuid) -> Result<bool, Self::Error> {
+        SqlxMacroBench::delete_user(conn, id).await
+    }
+
+    async fn insert_post(conn: &Self::Connection, post: &NewPost) -> Result<Uuid, Self::Error> {
+        SqlxMacroBench::insert_post(conn, post).await
+    }
+
+    async fn insert_comment(conn: &Self::Connection, comment: &NewComment) -> Result<Uuid, Self::Error> {
+        SqlxMacroBench::insert_comment(conn, comment).await
+    }
+
+    async fn select_posts_with_user(conn: &Self::Connection, limit: i64) -> Result<Vec<(Post, User)>, Self::Error> {
+        SqlxBench::select_posts_with_user_macro(conn, limit).await
+    }
+
+    async fn select_posts_by_status(conn: &Self::Connection, status: &str, limit: i64) -> Result<Vec<Post>, Self::Error> {
+        SqlxMacroBench::select_posts_by_status(conn, status, limit).await
+    }
+
+    async fn select_posts_repeated(conn: &Self::Connection, copies: i32) -> Result<Vec<Post>, Self::Error> {
+        SqlxMacroBench::select_posts_repeated(conn, copies).await
+    }
+
+    async fn stream_posts_repeated(conn: &Self::Connection, copies: i32, each: impl FnMut(Post)) -> Result<usize, Self::Error> {
+        SqlxMacroBench::stream_posts_repeated(conn, copies, each).await
+    }
+
+    async fn search_users_by_name(conn: &Self::Connection, pattern: &str, limit: i64) -> Result<Vec<User>, Self::Error> {
+        SqlxMacroBench::search_users_by_name(conn, pattern, limit).await
+    }
+
+    async fn select_users_posts_comments(conn: &Self::Connection, limit: i64) -> Result<Vec<(User, Post, Comment)>, Self::Error> {
+        SqlxMacroBench::select_users_posts_comments(conn, limit).await
+    }
+
+    async fn select_comments_for_post(
+        conn: &Self::Connection,
+        post_id: Uuid,
+        limit: i64,
+        before: Option<CommentKey>,
+    ) -> Result<Vec<Comment>, Self::Error> {
+        SqlxMacroBench::select_comments_for_post(conn, post_id, limit, before).await
+    }
+
+    async fn count_posts_per_user(conn: &Self::Connection) -> Result<Vec<(Uuid, i64)>, Self::Error> {
+        SqlxMacroBench::count_posts_per_user(conn).await
+    }
+
+    async fn count_posts_per_tag(conn: &Self::Connection) -> Result<Vec<(Uuid, i64)>, Self::Error> {
+        SqlxMacroBench::count_posts_per_tag(conn).await
+    }
+
+    async fn insert_user_with_posts(conn: &Self::Connection, user: &NewUser, posts: &[NewPost]) -> Result<Uuid, Self::Error> {
+        SqlxMacroBench::insert_user_with_posts(conn, user, posts).await
+    }
+
+    /// A multi-statement batch has no macro form (`query!` takes one
+    /// statement), so this is [`SqlxBench`]'s `raw_sql`
+    async fn insert_user_post_update_batched(
+        conn: &Self::Connection,
+        user: &NewUser,
+        post: &NewPost,
+        first_name: &str,
+        last_name: &str,
+    ) -> Result<Uuid, Self::Error> {
+        SqlxBench::insert_user_post_update_batched(conn, user, post, first_name, last_name).await
+    }
+
+    async fn cleanup(conn: &Self::Connection) -> Result<(), Self::Error> {
+        SqlxMacroBench::cleanup(conn).await
+    }
+
+    fn capabilities() -> Capabilities {
+        SqlxBench::capabilities()
+    }
 }
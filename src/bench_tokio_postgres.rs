@@ -1,17 +1,47 @@
 //! tokio-postgres benchmark implementation
+//!
+//! [`TokioPostgresBench`] passes SQL text to every call, so tokio-postgres
+//! prepares each query again before running it. [`TokioPostgresPreparedBench`]
+//! is the same driver with every hot query prepared once per connection
+//! ([`PreparedTokioPostgres`]), the way clorinde's `PreparedStatements` are,
+//! benchmarked as a series of its own.
 
-use crate::{Comment, NewComment, NewPost, NewUser, Post, User, DATABASE_URL};
-use tokio_postgres::{Client, NoTls};
+use crate::after_connect::{AfterConnect, MobcAfterConnect};
+use crate::capability::Capabilities;
+use crate::mapping::{CommentColumns, ModelRow, PostColumns, UserColumns};
+use crate::tls::{self, TlsStack};
+use crate::{batch, Comment, CommentKey, DatabaseBenchmark, NewComment, NewPost, NewUser, Post, User, database_url};
+use tokio_postgres::error::SqlState;
+use futures::{pin_mut, TryStreamExt};
+use std::future::Future;
+use tokio_postgres::{Client, NoTls, RowStream, Statement};
 use uuid::Uuid;
 
 // Re-export deadpool types for pooled benchmarks
 pub use deadpool_postgres::{Config, Manager, ManagerConfig, Pool, RecyclingMethod, Runtime};
 
+/// A bb8 pool of tokio-postgres connections, the alternative to deadpool's [`Pool`]
+pub type Bb8Pool = bb8::Pool<bb8_postgres::PostgresConnectionManager<NoTls>>;
+
+/// A mobc pool of tokio-postgres connections, running the init SQL through
+/// [`MobcAfterConnect`] since mobc has no connection hook of its own
+pub type MobcPool = mobc::Pool<MobcAfterConnect>;
+
 pub struct TokioPostgresBench;
 
 impl TokioPostgresBench {
     pub async fn connect() -> Result<Client, tokio_postgres::Error> {
-        let (client, connection) = tokio_postgres::connect(DATABASE_URL, NoTls).await?;
+        Self::connect_to(&database_url()).await
+    }
+    
+    /// Connect without TLS to `url` instead of [`database_url`]
+    pub async fn connect_to(url: &str) -> Result<Client, tokio_postgres::Error> {
+        Self::connect_with_hook(url, &AfterConnect::from_env()).await
+    }
+    
+    /// Connect without TLS to `url`, running `hook` instead of the environment's init SQL
+    pub async fn connect_with_hook(url: &str, hook: &AfterConnect) -> Result<Client, tokio_postgres::Error> {
+        let (client, connection) = tokio_postgres::connect(url, NoTls).await?;
         
         tokio::spawn(async move {
             if let Err(e) = connection.await {
@@ -19,13 +49,29 @@ impl TokioPostgresBench {
             }
         });
         
+        hook.run_tokio_postgres(&client).await?;
         Ok(client)
     }
     
+    /// Connect through `stack` with `sslmode=require` (or without TLS for [`TlsStack::None`])
+    pub async fn connect_tls(stack: TlsStack) -> Result<Client, tls::ConnectError> {
+        tls::connect_tokio_postgres(stack).await
+    }
+    
     /// Create a deadpool connection pool for concurrent benchmarks
     pub fn create_pool(pool_size: usize) -> Pool {
+        Self::create_pool_with_url(&database_url(), pool_size)
+    }
+    
+    /// Create a deadpool connection pool for `url` instead of [`database_url`]
+    pub fn create_pool_with_url(url: &str, pool_size: usize) -> Pool {
+        Self::create_pool_with_hook(url, pool_size, &AfterConnect::from_env())
+    }
+    
+    /// Create a deadpool connection pool for `url` whose connections run `hook` when opened
+    pub fn create_pool_with_hook(url: &str, pool_size: usize, hook: &AfterConnect) -> Pool {
         let mut cfg = Config::new();
-        cfg.url = Some(DATABASE_URL.to_string());
+        cfg.url = Some(url.to_string());
         cfg.manager = Some(ManagerConfig {
             recycling_method: RecyclingMethod::Fast,
         });
@@ -34,8 +80,11 @@ impl TokioPostgresBench {
             ..Default::default()
         });
         
-        cfg.create_pool(Some(Runtime::Tokio1), NoTls)
-            .expect("Failed to create pool")
+        let mut builder = cfg.builder(NoTls).expect("Failed to create pool").runtime(Runtime::Tokio1);
+        if !hook.is_empty() {
+            builder = builder.post_create(hook.deadpool_hook());
+        }
+        builder.build().expect("Failed to create pool")
     }
     
     /// Get a client from the pool
@@ -43,6 +92,14 @@ impl TokioPostgresBench {
         pool.get().await
     }
     
+    /// Open every connection the pool can hold so measurement never pays for connects
+    pub async fn warm_pool(pool: &Pool) -> Result<(), deadpool_postgres::PoolError> {
+        let size = pool.status().max_size;
+        let clients = futures::future::try_join_all((0..size).map(|_| pool.get())).await?;
+        drop(clients);
+        Ok(())
+    }
+    
     pub async fn insert_user(client: &Client, user: &NewUser) -> Result<Uuid, tokio_postgres::Error> {
         let row = client
             .query_one(
@@ -84,16 +141,7 @@ impl TokioPostgresBench {
             )
             .await?;
         
-        Ok(row.map(|r| User {
-            id: r.get("id"),
-            username: r.get("username"),
-            email: r.get("email"),
-            first_name: r.get("first_name"),
-            last_name: r.get("last_name"),
-            age: r.get("age"),
-            created_at: r.get("created_at"),
-            updated_at: r.get("updated_at"),
-        }))
+        row.as_ref().map(User::try_from).transpose()
     }
     
     pub async fn select_users_limit(client: &Client, limit: i64) -> Result<Vec<User>, tokio_postgres::Error> {
@@ -105,19 +153,16 @@ impl TokioPostgresBench {
             )
             .await?;
         
-        Ok(rows
-            .iter()
-            .map(|r| User {
-                id: r.get("id"),
-                username: r.get("username"),
-                email: r.get("email"),
-                first_name: r.get("first_name"),
-                last_name: r.get("last_name"),
-                age: r.get("age"),
-                created_at: r.get("created_at"),
-                updated_at: r.get("updated_at"),
-            })
-            .collect())
+        rows.iter().map(User::try_from).collect()
+    }
+    
+    /// [`Self::select_users_limit`] with only the id and username
+    pub async fn select_usernames_limit(client: &Client, limit: i64) -> Result<Vec<(Uuid, String)>, tokio_postgres::Error> {
+        let rows = client
+            .query("SELECT id, username FROM users ORDER BY created_at DESC LIMIT $1", &[&limit])
+            .await?;
+        
+        rows.iter().map(|row| Ok((row.try_get(0)?, row.try_get(1)?))).collect()
     }
     
     pub async fn select_users_filtered(
@@ -137,19 +182,7 @@ impl TokioPostgresBench {
             )
             .await?;
         
-        Ok(rows
-            .iter()
-            .map(|r| User {
-                id: r.get("id"),
-                username: r.get("username"),
-                email: r.get("email"),
-                first_name: r.get("first_name"),
-                last_name: r.get("last_name"),
-                age: r.get("age"),
-                created_at: r.get("created_at"),
-                updated_at: r.get("updated_at"),
-            })
-            .collect())
+        rows.iter().map(User::try_from).collect()
     }
     
     pub async fn update_user(
@@ -205,32 +238,9 @@ impl TokioPostgresBench {
             )
             .await?;
         
-        Ok(rows
-            .iter()
-            .map(|r| {
-                let post = Post {
-                    id: r.get("post_id"),
-                    user_id: r.get("user_id"),
-                    title: r.get("title"),
-                    content: r.get("content"),
-                    status: r.get("status"),
-                    view_count: r.get("view_count"),
-                    created_at: r.get("post_created_at"),
-                    updated_at: r.get("post_updated_at"),
-                };
-                let user = User {
-                    id: r.get("user_id"),
-                    username: r.get("username"),
-                    email: r.get("email"),
-                    first_name: r.get("first_name"),
-                    last_name: r.get("last_name"),
-                    age: r.get("age"),
-                    created_at: r.get("user_created_at"),
-                    updated_at: r.get("user_updated_at"),
-                };
-                (post, user)
-            })
-            .collect())
+        rows.iter()
+            .map(|r| Ok((r.post(&PostColumns::JOINED)?, r.user(&UserColumns::JOINED)?)))
+            .collect()
     }
     
     pub async fn select_users_posts_comments(
@@ -244,7 +254,7 @@ impl TokioPostgresBench {
                     u.created_at as user_created_at, u.updated_at as user_updated_at,
                     p.id as post_id, p.title, p.content, p.status, p.view_count,
                     p.created_at as post_created_at, p.updated_at as post_updated_at,
-                    c.id as comment_id, c.content as comment_content, c.created_at as comment_created_at
+                    c.id as comment_id, c.user_id as comment_user_id, c.content as comment_content, c.created_at as comment_created_at
                  FROM users u
                  JOIN posts p ON u.id = p.user_id
                  JOIN comments c ON p.id = c.post_id
@@ -254,39 +264,51 @@ impl TokioPostgresBench {
             )
             .await?;
         
-        Ok(rows
-            .iter()
+        rows.iter()
             .map(|r| {
-                let user = User {
-                    id: r.get("user_id"),
-                    username: r.get("username"),
-                    email: r.get("email"),
-                    first_name: r.get("first_name"),
-                    last_name: r.get("last_name"),
-                    age: r.get("age"),
-                    created_at: r.get("user_created_at"),
-                    updated_at: r.get("user_updated_at"),
-                };
-                let post = Post {
-                    id: r.get("post_id"),
-                    user_id: r.get("user_id"),
-                    title: r.get("title"),
-                    content: r.get("content"),
-                    status: r.get("status"),
-                    view_count: r.get("view_count"),
-                    created_at: r.get("post_created_at"),
-                    updated_at: r.get("post_updated_at"),
-                };
-                let comment = Comment {
-                    id: r.get("comment_id"),
-                    post_id: r.get("post_id"),
-                    user_id: r.get("user_id"),
-                    content: r.get("comment_content"),
-                    created_at: r.get("comment_created_at"),
-                };
-                (user, post, comment)
+                Ok((
+                    r.user(&UserColumns::JOINED)?,
+                    r.post(&PostColumns::JOINED)?,
+                    r.comment(&CommentColumns::JOINED)?,
+                ))
             })
-            .collect())
+            .collect()
+    }
+    
+    pub async fn select_comments_for_post(
+        client: &Client,
+        post_id: Uuid,
+        limit: i64,
+        before: Option<CommentKey>,
+    ) -> Result<Vec<Comment>, tokio_postgres::Error> {
+        let rows = match before {
+            None => {
+                client
+                    .query(
+                        "SELECT id, post_id, user_id, content, created_at
+                         FROM comments
+                         WHERE post_id = $1
+                         ORDER BY created_at DESC, id DESC
+                         LIMIT $2",
+                        &[&post_id, &limit],
+                    )
+                    .await?
+            }
+            Some(key) => {
+                client
+                    .query(
+                        "SELECT id, post_id, user_id, content, created_at
+                         FROM comments
+                         WHERE post_id = $1 AND (created_at, id) < ($2, $3)
+                         ORDER BY created_at DESC, id DESC
+                         LIMIT $4",
+                        &[&post_id, &key.created_at, &key.id, &limit],
+                    )
+                    .await?
+            }
+        };
+
+        rows.iter().map(Comment::try_from).collect()
     }
     
     pub async fn count_posts_per_user(client: &Client) -> Result<Vec<(Uuid, i64)>, tokio_postgres::Error> {
@@ -303,22 +325,52 @@ impl TokioPostgresBench {
         
         Ok(rows.iter().map(|r| (r.get(0), r.get(1))).collect())
     }
+
+    pub async fn count_posts_per_tag(client: &Client) -> Result<Vec<(Uuid, i64)>, tokio_postgres::Error> {
+        let rows = client
+            .query(
+                "SELECT t.id, COUNT(pt.post_id) as post_count
+                 FROM tags t
+                 JOIN post_tags pt ON t.id = pt.tag_id
+                 GROUP BY t.id
+                 ORDER BY post_count DESC, t.id",
+                &[],
+            )
+            .await?;
+
+        Ok(rows.iter().map(|r| (r.get(0), r.get(1))).collect())
+    }
     
     pub async fn insert_user_with_posts(
         client: &Client,
         user: &NewUser,
         posts: &[NewPost],
     ) -> Result<Uuid, tokio_postgres::Error> {
-        // Note: tokio-postgres requires a mutable client for transactions
-        // For benchmarking purposes, we'll do sequential inserts
-        let user_id = Self::insert_user(client, user).await?;
-        
-        for post in posts {
-            let mut post = post.clone();
-            post.user_id = user_id;
-            Self::insert_post(client, &post).await?;
-        }
-        
+        transactional(client, async {
+            let user_id = Self::insert_user(client, user).await?;
+
+            for post in posts {
+                let mut post = post.clone();
+                post.user_id = user_id;
+                Self::insert_post(client, &post).await?;
+            }
+
+            Ok(user_id)
+        })
+        .await
+    }
+
+    /// Insert a user and a post for it, then update the user, as one multi-statement batch
+    pub async fn insert_user_post_update_batched(
+        client: &Client,
+        user: &NewUser,
+        post: &NewPost,
+        first_name: &str,
+        last_name: &str,
+    ) -> Result<Uuid, tokio_postgres::Error> {
+        let user_id = Uuid::new_v4();
+        let sql = batch::write_step_sql(user_id, user, post, first_name, last_name);
+        client.batch_execute(&sql).await?;
         Ok(user_id)
     }
     
@@ -362,19 +414,39 @@ impl TokioPostgresBench {
             )
             .await?;
         
-        Ok(rows
-            .iter()
-            .map(|r| Post {
-                id: r.get("id"),
-                user_id: r.get("user_id"),
-                title: r.get("title"),
-                content: r.get("content"),
-                status: r.get("status"),
-                view_count: r.get("view_count"),
-                created_at: r.get("created_at"),
-                updated_at: r.get("updated_at"),
-            })
-            .collect())
+        rows.iter().map(Post::try_from).collect()
+    }
+    
+    pub async fn select_posts_repeated(
+        client: &Client,
+        copies: i32,
+    ) -> Result<Vec<Post>, tokio_postgres::Error> {
+        let rows = client
+            .query(
+                "SELECT p.id, p.user_id, p.title, p.content, p.status, p.view_count, p.created_at, p.updated_at 
+                 FROM posts p 
+                 CROSS JOIN generate_series(1, $1)",
+                &[&copies],
+            )
+            .await?;
+        
+        rows.iter().map(Post::try_from).collect()
+    }
+
+    pub async fn stream_posts_repeated(
+        client: &Client,
+        copies: i32,
+        mut each: impl FnMut(Post),
+    ) -> Result<usize, tokio_postgres::Error> {
+        let rows = client
+            .query_raw(
+                "SELECT p.id, p.user_id, p.title, p.content, p.status, p.view_count, p.created_at, p.updated_at
+                 FROM posts p
+                 CROSS JOIN generate_series(1, $1)",
+                [copies],
+            )
+            .await?;
+        for_each_post(rows, &mut each).await
     }
     
     pub async fn increment_view_count(client: &Client, post_id: Uuid) -> Result<(), tokio_postgres::Error> {
@@ -404,19 +476,51 @@ impl TokioPostgresBench {
             )
             .await?;
         
-        Ok(rows
-            .iter()
-            .map(|r| User {
-                id: r.get("id"),
-                username: r.get("username"),
-                email: r.get("email"),
-                first_name: r.get("first_name"),
-                last_name: r.get("last_name"),
-                age: r.get("age"),
-                created_at: r.get("created_at"),
-                updated_at: r.get("updated_at"),
-            })
-            .collect())
+        rows.iter().map(User::try_from).collect()
+    }
+}
+
+// ============================================================================
+// Statement timeout and cancellation
+// ============================================================================
+
+impl TokioPostgresBench {
+    /// Lightweight round trip used to check that a connection is still usable
+    pub async fn ping(client: &Client) -> Result<(), tokio_postgres::Error> {
+        client.execute("SELECT 1", &[]).await?;
+        Ok(())
+    }
+
+    /// Run a query that keeps the server busy for `sleep_secs`
+    pub async fn pg_sleep(client: &Client, sleep_secs: f64) -> Result<(), tokio_postgres::Error> {
+        client.execute("SELECT pg_sleep($1)", &[&sleep_secs]).await?;
+        Ok(())
+    }
+
+    /// Run `pg_sleep` under a transaction-scoped `statement_timeout`.
+    ///
+    /// Returns `true` when the server cancelled the statement.
+    pub async fn sleep_with_statement_timeout(
+        client: &Client,
+        timeout_ms: u64,
+        sleep_secs: f64,
+    ) -> Result<bool, tokio_postgres::Error> {
+        client
+            .batch_execute(&format!("BEGIN; SET LOCAL statement_timeout = {}", timeout_ms))
+            .await?;
+        let result = client.execute("SELECT pg_sleep($1)", &[&sleep_secs]).await;
+        client.batch_execute("ROLLBACK").await?;
+
+        match result {
+            Ok(_) => Ok(false),
+            Err(e) if e.code() == Some(&SqlState::QUERY_CANCELED) => Ok(true),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Ask the server to cancel whatever the client is currently running
+    pub async fn cancel_query(client: &Client) -> Result<(), tokio_postgres::Error> {
+        client.cancel_token().cancel_query(NoTls).await
     }
 }
 
@@ -454,16 +558,7 @@ impl TokioPostgresBench {
             )
             .await?;
         
-        Ok(row.map(|r| User {
-            id: r.get("id"),
-            username: r.get("username"),
-            email: r.get("email"),
-            first_name: r.get("first_name"),
-            last_name: r.get("last_name"),
-            age: r.get("age"),
-            created_at: r.get("created_at"),
-            updated_at: r.get("updated_at"),
-        }))
+        Ok(row.as_ref().map(User::try_from).transpose()?)
     }
 
     pub async fn pooled_select_users_limit(
@@ -479,19 +574,16 @@ impl TokioPostgresBench {
             )
             .await?;
         
-        Ok(rows
-            .iter()
-            .map(|r| User {
-                id: r.get("id"),
-                username: r.get("username"),
-                email: r.get("email"),
-                first_name: r.get("first_name"),
-                last_name: r.get("last_name"),
-                age: r.get("age"),
-                created_at: r.get("created_at"),
-                updated_at: r.get("updated_at"),
-            })
-            .collect())
+        Ok(rows.iter().map(User::try_from).collect::<Result<_, _>>()?)
+    }
+
+    pub async fn pooled_pg_sleep(
+        pool: &Pool,
+        sleep_secs: f64,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let client = pool.get().await?;
+        client.execute("SELECT pg_sleep($1)", &[&sleep_secs]).await?;
+        Ok(())
     }
 
     pub async fn pooled_cleanup(pool: &Pool) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
@@ -505,3 +597,823 @@ impl TokioPostgresBench {
         Ok(())
     }
 }
+
+// ============================================================================
+// bb8-pooled versions, parallel to the deadpool ones
+// ============================================================================
+
+impl TokioPostgresBench {
+    /// Create a bb8 connection pool for concurrent benchmarks
+    pub async fn create_bb8_pool(pool_size: usize) -> Result<Bb8Pool, tokio_postgres::Error> {
+        Self::create_bb8_pool_with_url(&database_url(), pool_size).await
+    }
+
+    /// Create a bb8 connection pool for `url` instead of [`database_url`]
+    pub async fn create_bb8_pool_with_url(url: &str, pool_size: usize) -> Result<Bb8Pool, tokio_postgres::Error> {
+        Self::create_bb8_pool_with_hook(url, pool_size, &AfterConnect::from_env()).await
+    }
+
+    /// Create a bb8 connection pool for `url` whose connections run `hook` when opened.
+    ///
+    /// With `min_idle` at the pool size, `build` opens every connection before
+    /// it returns, as r2d2 does, so measurement never pays for connects. bb8
+    /// checks a connection with an empty query on every checkout by default;
+    /// that is off, as deadpool's [`RecyclingMethod::Fast`] skips it too.
+    pub async fn create_bb8_pool_with_hook(
+        url: &str,
+        pool_size: usize,
+        hook: &AfterConnect,
+    ) -> Result<Bb8Pool, tokio_postgres::Error> {
+        let manager = bb8_postgres::PostgresConnectionManager::new_from_stringlike(url, NoTls)?;
+        let mut builder = bb8::Pool::builder()
+            .max_size(pool_size as u32)
+            .min_idle(Some(pool_size as u32))
+            .test_on_check_out(false);
+        if !hook.is_empty() {
+            builder = builder.connection_customizer(hook.bb8_customizer());
+        }
+        builder.build(manager).await
+    }
+
+    pub async fn bb8_insert_user(
+        pool: &Bb8Pool,
+        user: &NewUser,
+    ) -> Result<Uuid, Box<dyn std::error::Error + Send + Sync>> {
+        let client = pool.get().await?;
+        Ok(Self::insert_user(&client, user).await?)
+    }
+
+    pub async fn bb8_select_user_by_id(
+        pool: &Bb8Pool,
+        id: Uuid,
+    ) -> Result<Option<User>, Box<dyn std::error::Error + Send + Sync>> {
+        let client = pool.get().await?;
+        Ok(Self::select_user_by_id(&client, id).await?)
+    }
+
+    pub async fn bb8_select_users_limit(
+        pool: &Bb8Pool,
+        limit: i64,
+    ) -> Result<Vec<User>, Box<dyn std::error::Error + Send + Sync>> {
+        let client = pool.get().await?;
+        Ok(Self::select_users_limit(&client, limit).await?)
+    }
+
+    pub async fn bb8_cleanup(pool: &Bb8Pool) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let client = pool.get().await?;
+        Ok(Self::cleanup(&client).await?)
+    }
+}
+
+// ============================================================================
+// mobc-pooled versions, matching the deadpool ones
+// ============================================================================
+
+impl TokioPostgresBench {
+    /// Create a mobc connection pool for concurrent benchmarks
+    pub async fn create_mobc_pool(pool_size: usize) -> Result<MobcPool, mobc::Error<tokio_postgres::Error>> {
+        Self::create_mobc_pool_with_url(&database_url(), pool_size).await
+    }
+
+    /// Create a mobc connection pool for `url` instead of [`database_url`]
+    pub async fn create_mobc_pool_with_url(
+        url: &str,
+        pool_size: usize,
+    ) -> Result<MobcPool, mobc::Error<tokio_postgres::Error>> {
+        Self::create_mobc_pool_with_hook(url, pool_size, &AfterConnect::from_env()).await
+    }
+
+    /// Create a mobc connection pool for `url` whose connections run `hook` when opened.
+    ///
+    /// mobc checks a connection with an empty query on every checkout unless
+    /// told not to; that is off here, as deadpool's [`RecyclingMethod::Fast`]
+    /// skips it too. mobc opens connections only on demand, so every one is
+    /// opened and returned idle before this returns.
+    pub async fn create_mobc_pool_with_hook(
+        url: &str,
+        pool_size: usize,
+        hook: &AfterConnect,
+    ) -> Result<MobcPool, mobc::Error<tokio_postgres::Error>> {
+        let config: tokio_postgres::Config = url.parse()?;
+        let manager = hook.mobc_manager(mobc_postgres::PgConnectionManager::new(config, NoTls));
+        let pool = mobc::Pool::builder()
+            .max_open(pool_size as u64)
+            .max_idle(pool_size as u64)
+            .test_on_check_out(false)
+            .build(manager);
+
+        let clients = futures::future::try_join_all((0..pool_size).map(|_| pool.get())).await?;
+        drop(clients);
+        // Connections go back to the pool from a spawned task
+        while pool.state().await.idle < pool_size as u64 {
+            tokio::task::yield_now().await;
+        }
+        Ok(pool)
+    }
+
+    pub async fn mobc_insert_user(
+        pool: &MobcPool,
+        user: &NewUser,
+    ) -> Result<Uuid, Box<dyn std::error::Error + Send + Sync>> {
+        let client = pool.get().await?;
+        Ok(Self::insert_user(&client, user).await?)
+    }
+
+    pub async fn mobc_select_user_by_id(
+        pool: &MobcPool,
+        id: Uuid,
+    ) -> Result<Option<User>, Box<dyn std::error::Error + Send + Sync>> {
+        let client = pool.get().await?;
+        Ok(Self::select_user_by_id(&client, id).await?)
+    }
+
+    pub async fn mobc_select_users_limit(
+        pool: &MobcPool,
+        limit: i64,
+    ) -> Result<Vec<User>, Box<dyn std::error::Error + Send + Sync>> {
+        let client = pool.get().await?;
+        Ok(Self::select_users_limit(&client, limit).await?)
+    }
+
+    pub async fn mobc_pg_sleep(
+        pool: &MobcPool,
+        sleep_secs: f64,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let client = pool.get().await?;
+        client.execute("SELECT pg_sleep($1)", &[&sleep_secs]).await?;
+        Ok(())
+    }
+
+    pub async fn mobc_cleanup(pool: &MobcPool) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let client = pool.get().await?;
+        Ok(Self::cleanup(&client).await?)
+    }
+}
+
+// ============================================================================
+// Prepared statements
+// ============================================================================
+
+/// The hot queries of [`TokioPostgresBench`], prepared once when the
+/// connection opens and reused for its lifetime
+pub struct PreparedTokioPostgres {
+    pub insert_user: Statement,
+    pub select_user_by_id: Statement,
+    pub select_users_limit: Statement,
+    pub select_users_filtered: Statement,
+    pub update_user: Statement,
+    pub delete_user: Statement,
+    pub insert_post: Statement,
+    pub insert_comment: Statement,
+    pub select_posts_with_user: Statement,
+    pub select_posts_by_status: Statement,
+    pub select_posts_repeated: Statement,
+    pub search_users_by_name: Statement,
+    pub select_users_posts_comments: Statement,
+    pub select_comments_for_post: Statement,
+    pub select_comments_for_post_before: Statement,
+    pub count_posts_per_user: Statement,
+    pub count_posts_per_tag: Statement,
+    pub cleanup: Statement,
+}
+
+impl PreparedTokioPostgres {
+    pub async fn new(client: &Client) -> Result<Self, tokio_postgres::Error> {
+        Ok(Self {
+            insert_user: client
+                .prepare(
+                    "INSERT INTO users (username, email, first_name, last_name, age) 
+                     VALUES ($1, $2, $3, $4, $5) 
+                     RETURNING id",
+                )
+                .await?,
+            select_user_by_id: client
+                .prepare(
+                    "SELECT id, username, email, first_name, last_name, age, created_at, updated_at 
+                     FROM users WHERE id = $1",
+                )
+                .await?,
+            select_users_limit: client
+                .prepare(
+                    "SELECT id, username, email, first_name, last_name, age, created_at, updated_at 
+                     FROM users ORDER BY created_at DESC LIMIT $1",
+                )
+                .await?,
+            select_users_filtered: client
+                .prepare(
+                    "SELECT id, username, email, first_name, last_name, age, created_at, updated_at 
+                     FROM users 
+                     WHERE age >= $1 AND age <= $2 
+                     ORDER BY age, username 
+                     LIMIT $3",
+                )
+                .await?,
+            update_user: client
+                .prepare("UPDATE users SET first_name = $1, last_name = $2, updated_at = NOW() WHERE id = $3")
+                .await?,
+            delete_user: client.prepare("DELETE FROM users WHERE id = $1").await?,
+            insert_post: client
+                .prepare(
+                    "INSERT INTO posts (user_id, title, content, status) 
+                     VALUES ($1, $2, $3, $4) 
+                     RETURNING id",
+                )
+                .await?,
+            insert_comment: client
+                .prepare(
+                    "INSERT INTO comments (post_id, user_id, content) 
+                     VALUES ($1, $2, $3) 
+                     RETURNING id",
+                )
+                .await?,
+            select_posts_with_user: client
+                .prepare(
+                    "SELECT 
+                        p.id as post_id, p.user_id, p.title, p.content, p.status, p.view_count,
+                        p.created_at as post_created_at, p.updated_at as post_updated_at,
+                        u.id as user_id, u.username, u.email, u.first_name, u.last_name, u.age,
+                        u.created_at as user_created_at, u.updated_at as user_updated_at
+                     FROM posts p
+                     JOIN users u ON p.user_id = u.id
+                     ORDER BY p.created_at DESC
+                     LIMIT $1",
+                )
+                .await?,
+            select_posts_by_status: client
+                .prepare(
+                    "SELECT id, user_id, title, content, status, view_count, created_at, updated_at
+                     FROM posts
+                     WHERE status = $1
+                     ORDER BY created_at DESC
+                     LIMIT $2",
+                )
+                .await?,
+            select_posts_repeated: client
+                .prepare(
+                    "SELECT p.id, p.user_id, p.title, p.content, p.status, p.view_count, p.created_at, p.updated_at 
+                     FROM posts p 
+                     CROSS JOIN generate_series(1, $1)",
+                )
+                .await?,
+            search_users_by_name: client
+                .prepare(
+                    "SELECT id, username, email, first_name, last_name, age, created_at, updated_at 
+                     FROM users 
+                     WHERE first_name ILIKE $1 OR last_name ILIKE $1 
+                     ORDER BY username 
+                     LIMIT $2",
+                )
+                .await?,
+            select_users_posts_comments: client
+                .prepare(
+                    "SELECT 
+                        u.id as user_id, u.username, u.email, u.first_name, u.last_name, u.age,
+                        u.created_at as user_created_at, u.updated_at as user_updated_at,
+                        p.id as post_id, p.title, p.content, p.status, p.view_count,
+                        p.created_at as post_created_at, p.updated_at as post_updated_at,
+                        c.id as comment_id, c.user_id as comment_user_id, c.content as comment_content, c.created_at as comment_created_at
+                     FROM users u
+                     JOIN posts p ON u.id = p.user_id
+                     JOIN comments c ON p.id = c.post_id
+                     ORDER BY u.created_at DESC, p.created_at DESC, c.created_at DESC
+                     LIMIT $1",
+                )
+                .await?,
+            select_comments_for_post: client
+                .prepare(
+                    "SELECT id, post_id, user_id, content, created_at
+                     FROM comments
+                     WHERE post_id = $1
+                     ORDER BY created_at DESC, id DESC
+                     LIMIT $2",
+                )
+                .await?,
+            select_comments_for_post_before: client
+                .prepare(
+                    "SELECT id, post_id, user_id, content, created_at
+                     FROM comments
+                     WHERE post_id = $1 AND (created_at, id) < ($2, $3)
+                     ORDER BY created_at DESC, id DESC
+                     LIMIT $4",
+                )
+                .await?,
+            count_posts_per_user: client
+                .prepare(
+                    "SELECT u.id, COUNT(p.id) as post_count
+                     FROM users u
+                     LEFT JOIN posts p ON u.id = p.user_id
+                     GROUP BY u.id
+                     ORDER BY post_count DESC",
+                )
+                .await?,
+            count_posts_per_tag: client
+                .prepare(
+                    "SELECT t.id, COUNT(pt.post_id) as post_count
+                     FROM tags t
+                     JOIN post_tags pt ON t.id = pt.tag_id
+                     GROUP BY t.id
+                     ORDER BY post_count DESC, t.id",
+                )
+                .await?,
+            cleanup: client
+                .prepare("DELETE FROM users WHERE username LIKE 'bench_user_%'")
+                .await?,
+        })
+    }
+}
+
+/// A tokio-postgres connection and the statements prepared on it
+pub struct PreparedConnection {
+    pub client: Client,
+    pub statements: PreparedTokioPostgres,
+}
+
+/// tokio-postgres running [`PreparedTokioPostgres`] statements instead of SQL text
+pub struct TokioPostgresPreparedBench;
+
+impl TokioPostgresPreparedBench {
+    pub async fn connect() -> Result<PreparedConnection, tokio_postgres::Error> {
+        Self::connect_to(&database_url()).await
+    }
+
+    /// Connect without TLS to `url` and prepare the statements
+    pub async fn connect_to(url: &str) -> Result<PreparedConnection, tokio_postgres::Error> {
+        let client = TokioPostgresBench::connect_to(url).await?;
+        let statements = PreparedTokioPostgres::new(&client).await?;
+        Ok(PreparedConnection { client, statements })
+    }
+
+    pub async fn insert_user(conn: &PreparedConnection, user: &NewUser) -> Result<Uuid, tokio_postgres::Error> {
+        let row = conn
+            .client
+            .query_one(
+                &conn.statements.insert_user,
+                &[&user.username, &user.email, &user.first_name, &user.last_name, &user.age],
+            )
+            .await?;
+        Ok(row.get("id"))
+    }
+
+    pub async fn insert_users_batch(conn: &PreparedConnection, users: &[NewUser]) -> Result<Vec<Uuid>, tokio_postgres::Error> {
+        // One insert per user, as the unprepared version does
+        let mut ids = Vec::with_capacity(users.len());
+        for user in users {
+            ids.push(Self::insert_user(conn, user).await?);
+        }
+        Ok(ids)
+    }
+
+    pub async fn select_user_by_id(conn: &PreparedConnection, id: Uuid) -> Result<Option<User>, tokio_postgres::Error> {
+        let row = conn.client.query_opt(&conn.statements.select_user_by_id, &[&id]).await?;
+        row.as_ref().map(User::try_from).transpose()
+    }
+
+    pub async fn select_users_limit(conn: &PreparedConnection, limit: i64) -> Result<Vec<User>, tokio_postgres::Error> {
+        let rows = conn.client.query(&conn.statements.select_users_limit, &[&limit]).await?;
+        rows.iter().map(User::try_from).collect()
+    }
+
+    pub async fn select_users_filtered(
+        conn: &PreparedConnection,
+        min_age: i32,
+        max_age: i32,
+        limit: i64,
+    ) -> Result<Vec<User>, tokio_postgres::Error> {
+        let rows = conn
+            .client
+            .query(&conn.statements.select_users_filtered, &[&min_age, &max_age, &limit])
+            .await?;
+        rows.iter().map(User::try_from).collect()
+    }
+
+    pub async fn update_user(
+        conn: &PreparedConnection,
+        id: Uuid,
+        first_name: &str,
+        last_name: &str,
+    ) -> Result<bool, tokio_postgres::Error> {
+        let rows_affected = conn
+            .client
+            .execute(&conn.statements.update_user, &[&first_name, &last_name, &id])
+            .await?;
+        Ok(rows_affected > 0)
+    }
+
+    pub async fn delete_user(conn: &PreparedConnection, id: Uuid) -> Result<bool, tokio_postgres::Error> {
+        let rows_affected = conn.client.execute(&conn.statements.delete_user, &[&id]).await?;
+        Ok(rows_affected > 0)
+    }
+
+    pub async fn insert_post(conn: &PreparedConnection, post: &NewPost) -> Result<Uuid, tokio_postgres::Error> {
+        let row = conn
+            .client
+            .query_one(
+                &conn.statements.insert_post,
+                &[&post.user_id, &post.title, &post.content, &post.status],
+            )
+            .await?;
+        Ok(row.get("id"))
+    }
+
+    pub async fn insert_comment(conn: &PreparedConnection, comment: &NewComment) -> Result<Uuid, tokio_postgres::Error> {
+        let row = conn
+            .client
+            .query_one(
+                &conn.statements.insert_comment,
+                &[&comment.post_id, &comment.user_id, &comment.content],
+            )
+            .await?;
+        Ok(row.get("id"))
+    }
+
+    pub async fn select_posts_with_user(
+        conn: &PreparedConnection,
+        limit: i64,
+    ) -> Result<Vec<(Post, User)>, tokio_postgres::Error> {
+        let rows = conn.client.query(&conn.statements.select_posts_with_user, &[&limit]).await?;
+        rows.iter()
+            .map(|r| Ok((r.post(&PostColumns::JOINED)?, r.user(&UserColumns::JOINED)?)))
+            .collect()
+    }
+
+    pub async fn select_posts_by_status(
+        conn: &PreparedConnection,
+        status: &str,
+        limit: i64,
+    ) -> Result<Vec<Post>, tokio_postgres::Error> {
+        let rows = conn.client.query(&conn.statements.select_posts_by_status, &[&status, &limit]).await?;
+        rows.iter().map(Post::try_from).collect()
+    }
+
+    pub async fn select_posts_repeated(conn: &PreparedConnection, copies: i32) -> Result<Vec<Post>, tokio_postgres::Error> {
+        let rows = conn.client.query(&conn.statements.select_posts_repeated, &[&copies]).await?;
+        rows.iter().map(Post::try_from).collect()
+    }
+
+    pub async fn stream_posts_repeated(
+        conn: &PreparedConnection,
+        copies: i32,
+        mut each: impl FnMut(Post),
+    ) -> Result<usize, tokio_postgres::Error> {
+        let rows = conn.client.query_raw(&conn.statements.select_posts_repeated, [copies]).await?;
+        for_each_post(rows, &mut each).await
+    }
+
+    pub async fn search_users_by_name(
+        conn: &PreparedConnection,
+        pattern: &str,
+        limit: i64,
+    ) -> Result<Vec<User>, tokio_postgres::Error> {
+        let pattern = format!("%{}%", pattern);
+        let rows = conn
+            .client
+            .query(&conn.statements.search_users_by_name, &[&pattern, &limit])
+            .await?;
+        rows.iter().map(User::try_from).collect()
+    }
+
+    pub async fn select_users_posts_comments(
+        conn: &PreparedConnection,
+        limit: i64,
+    ) -> Result<Vec<(User, Post, Comment)>, tokio_postgres::Error> {
+        let rows = conn
+            .client
+            .query(&conn.statements.select_users_posts_comments, &[&limit])
+            .await?;
+        rows.iter()
+            .map(|r| {
+                Ok((
+                    r.user(&UserColumns::JOINED)?,
+                    r.post(&PostColumns::JOINED)?,
+                    r.comment(&CommentColumns::JOINED)?,
+                ))
+            })
+            .collect()
+    }
+
+    pub async fn select_comments_for_post(
+        conn: &PreparedConnection,
+        post_id: Uuid,
+        limit: i64,
+        before: Option<CommentKey>,
+    ) -> Result<Vec<Comment>, tokio_postgres::Error> {
+        let statements = &conn.statements;
+        let rows = match before {
+            None => conn.client.query(&statements.select_comments_for_post, &[&post_id, &limit]).await?,
+            Some(key) => {
+                conn.client
+                    .query(
+                        &statements.select_comments_for_post_before,
+                        &[&post_id, &key.created_at, &key.id, &limit],
+                    )
+                    .await?
+            }
+        };
+        rows.iter().map(Comment::try_from).collect()
+    }
+
+    pub async fn count_posts_per_user(conn: &PreparedConnection) -> Result<Vec<(Uuid, i64)>, tokio_postgres::Error> {
+        let rows = conn.client.query(&conn.statements.count_posts_per_user, &[]).await?;
+        Ok(rows.iter().map(|r| (r.get(0), r.get(1))).collect())
+    }
+
+    pub async fn count_posts_per_tag(conn: &PreparedConnection) -> Result<Vec<(Uuid, i64)>, tokio_postgres::Error> {
+        let rows = conn.client.query(&conn.statements.count_posts_per_tag, &[]).await?;
+        Ok(rows.iter().map(|r| (r.get(0), r.get(1))).collect())
+    }
+
+    pub async fn insert_user_with_posts(
+        conn: &PreparedConnection,
+        user: &NewUser,
+        posts: &[NewPost],
+    ) -> Result<Uuid, tokio_postgres::Error> {
+        transactional(&conn.client, async {
+            let user_id = Self::insert_user(conn, user).await?;
+            for post in posts {
+                let mut post = post.clone();
+                post.user_id = user_id;
+                Self::insert_post(conn, &post).await?;
+            }
+            Ok(user_id)
+        })
+        .await
+    }
+
+    pub async fn cleanup(conn: &PreparedConnection) -> Result<(), tokio_postgres::Error> {
+        conn.client.execute(&conn.statements.cleanup, &[]).await?;
+        Ok(())
+    }
+}
+
+/// Run `work`, statements on `client`, in a transaction: committed if it
+/// succeeds and rolled back if it fails
+///
+/// `Client::transaction` needs `&mut Client`, but [`DatabaseBenchmark`] hands
+/// out shared connections, so this issues `BEGIN` and `COMMIT` itself, the same
+/// two round trips. Nothing else may use the client until `work` finishes.
+pub async fn transactional<T>(
+    client: &Client,
+    work: impl Future<Output = Result<T, tokio_postgres::Error>>,
+) -> Result<T, tokio_postgres::Error> {
+    client.batch_execute("BEGIN").await?;
+    match work.await {
+        Ok(value) => {
+            client.batch_execute("COMMIT").await?;
+            Ok(value)
+        }
+        Err(e) => {
+            // The error that aborted the transaction is the one worth reporting
+            let _ = client.batch_execute("ROLLBACK").await;
+            Err(e)
+        }
+    }
+}
+
+/// Decode the rows into posts for `each` as they arrive; how many there were
+async fn for_each_post(rows: RowStream, each: &mut impl FnMut(Post)) -> Result<usize, tokio_postgres::Error> {
+    pin_mut!(rows);
+    let mut count = 0;
+    while let Some(row) = rows.try_next().await? {
+        each(Post::try_from(&row)?);
+        count += 1;
+    }
+    Ok(count)
+}
+
+// ============================================================================
+// DatabaseBenchmark implementation
+// ============================================================================
+
+impl DatabaseBenchmark for TokioPostgresBench {
+    type Connection = Client;
+    type Error = tokio_postgres::Error;
+
+    async fn connect() -> Result<Self::Connection, Self::Error> {
+        TokioPostgresBench::connect().await
+    }
+
+    async fn connect_to(url: &str) -> Result<Self::Connection, Self::Error> {
+        TokioPostgresBench::connect_to(url).await
+    }
+
+    async fn insert_user(conn: &Self::Connection, user: &NewUser) -> Result<Uuid, Self::Error> {
+        TokioPostgresBench::insert_user(conn, user).await
+    }
+
+    async fn insert_users_batch(conn: &Self::Connection, users: &[NewUser]) -> Result<Vec<Uuid>, Self::Error> {
+        TokioPostgresBench::insert_users_batch(conn, users).await
+    }
+
+    async fn select_user_by_id(conn: &Self::Connection, id: Uuid) -> Result<Option<User>, Self::Error> {
+        TokioPostgresBench::select_user_by_id(conn, id).await
+    }
+
+    async fn select_users_limit(conn: &Self::Connection, limit: i64) -> Result<Vec<User>, Self::Error> {
+        TokioPostgresBench::select_users_limit(conn, limit).await
+    }
+
+    async fn select_users_filtered(conn: &Self::Connection, min_age: i32, max_age: i32, limit: i64) -> Result<Vec<User>, Self::Error> {
+        TokioPostgresBench::select_users_filtered(conn, min_age, max_age, limit).await
+    }
+
+    async fn update_user(conn: &Self::Connection, id: Uuid, first_name: &str, last_name: &str) -> Result<bool, Self::Error> {
+        TokioPostgresBench::update_user(conn, id, first_name, last_name).await
+    }
+
+    async fn delete_user(conn: &Self::Connection, id: Uuid) -> Result<bool, Self::Error> {
+        TokioPostgresBench::delete_user(conn, id).await
+    }
+
+    async fn insert_post(conn: &Self::Connection, post: &NewPost) -> Result<Uuid, Self::Error> {
+        TokioPostgresBench::insert_post(conn, post).await
+    }
+
+    async fn insert_comment(conn: &Self::Connection, comment: &NewComment) -> Result<Uuid, Self::Error> {
+        TokioPostgresBench::insert_comment(conn, comment).await
+    }
+
+    async fn select_posts_with_user(conn: &Self::Connection, limit: i64) -> Result<Vec<(Post, User)>, Self::Error> {
+        TokioPostgresBench::select_posts_with_user(conn, limit).await
+    }
+
+    async fn select_posts_by_status(conn: &Self::Connection, status: &str, limit: i64) -> Result<Vec<Post>, Self::Error> {
+        TokioPostgresBench::select_posts_by_status(conn, status, limit).await
+    }
+
+    async fn select_posts_repeated(conn: &Self::Connection, copies: i32) -> Result<Vec<Post>, Self::Error> {
+        TokioPostgresBench::select_posts_repeated(conn, copies).await
+    }
+
+    async fn stream_posts_repeated(conn: &Self::Connection, copies: i32, each: impl FnMut(Post)) -> Result<usize, Self::Error> {
+        TokioPostgresBench::stream_posts_repeated(conn, copies, each).await
+    }
+
+    async fn search_users_by_name(conn: &Self::Connection, pattern: &str, limit: i64) -> Result<Vec<User>, Self::Error> {
+        TokioPostgresBench::search_users_by_name(conn, pattern, limit).await
+    }
+
+    async fn select_users_posts_comments(conn: &Self::Connection, limit: i64) -> Result<Vec<(User, Post, Comment)>, Self::Error> {
+        TokioPostgresBench::select_users_posts_comments(conn, limit).await
+    }
+
+    async fn select_comments_for_post(
+        conn: &Self::Connection,
+        post_id: Uuid,
+        limit: i64,
+        before: Option<CommentKey>,
+    ) -> Result<Vec<Comment>, Self::Error> {
+        TokioPostgresBench::select_comments_for_post(conn, post_id, limit, before).await
+    }
+
+    async fn count_posts_per_user(conn: &Self::Connection) -> Result<Vec<(Uuid, i64)>, Self::Error> {
+        TokioPostgresBench::count_posts_per_user(conn).await
+    }
+
+    async fn count_posts_per_tag(conn: &Self::Connection) -> Result<Vec<(Uuid, i64)>, Self::Error> {
+        TokioPostgresBench::count_posts_per_tag(conn).await
+    }
+
+    async fn insert_user_with_posts(conn: &Self::Connection, user: &NewUser, posts: &[NewPost]) -> Result<Uuid, Self::Error> {
+        TokioPostgresBench::insert_user_with_posts(conn, user, posts).await
+    }
+
+    async fn insert_user_post_update_batched(
+        conn: &Self::Connection,
+        user: &NewUser,
+        post: &NewPost,
+        first_name: &str,
+        last_name: &str,
+    ) -> Result<Uuid, Self::Error> {
+        TokioPostgresBench::insert_user_post_update_batched(conn, user, post, first_name, last_name).await
+    }
+
+    async fn cleanup(conn: &Self::Connection) -> Result<(), Self::Error> {
+        TokioPostgresBench::cleanup(conn).await
+    }
+
+    fn capabilities() -> Capabilities {
+        Capabilities {
+            // `Client::transaction` takes `&mut Client`
+            shared_transactions: false,
+            streaming: true,
+            copy: true,
+            listen_notify: true,
+            // Every call prepares its SQL text again
+            prepared_cache: false,
+            binary_format: true,
+        }
+    }
+}
+
+impl DatabaseBenchmark for TokioPostgresPreparedBench {
+    type Connection = PreparedConnection;
+    type Error = tokio_postgres::Error;
+
+    async fn connect() -> Result<Self::Connection, Self::Error> {
+        TokioPostgresPreparedBench::connect().await
+    }
+
+    async fn connect_to(url: &str) -> Result<Self::Connection, Self::Error> {
+        TokioPostgresPreparedBench::connect_to(url).await
+    }
+
+    async fn insert_user(conn: &Self::Connection, user: &NewUser) -> Result<Uuid, Self::Error> {
+        TokioPostgresPreparedBench::insert_user(conn, user).await
+    }
+
+    async fn insert_users_batch(conn: &Self::Connection, users: &[NewUser]) -> Result<Vec<Uuid>, Self::Error> {
+        TokioPostgresPreparedBench::insert_users_batch(conn, users).await
+    }
+
+    async fn select_user_by_id(conn: &Self::Connection, id: Uuid) -> Result<Option<User>, Self::Error> {
+        TokioPostgresPreparedBench::select_user_by_id(conn, id).await
+    }
+
+    async fn select_users_limit(conn: &Self::Connection, limit: i64) -> Result<Vec<User>, Self::Error> {
+        TokioPostgresPreparedBench::select_users_limit(conn, limit).await
+    }
+
+    async fn select_users_filtered(conn: &Self::Connection, min_age: i32, max_age: i32, limit: i64) -> Result<Vec<User>, Self::Error> {
+        TokioPostgresPreparedBench::select_users_filtered(conn, min_age, max_age, limit).await
+    }
+
+    async fn update_user(conn: &Self::Connection, id: Uuid, first_name: &str, last_name: &str) -> Result<bool, Self::Error> {
+        TokioPostgresPreparedBench::update_user(conn, id, first_name, last_name).await
+    }
+
+    async fn delete_user(conn: &Self::Connection, id: Uuid) -> Result<bool, Self::Error> {
+        TokioPostgresPreparedBench::delete_user(conn, id).await
+    }
+
+    async fn insert_post(conn: &Self::Connection, post: &NewPost) -> Result<Uuid, Self::Error> {
+        TokioPostgresPreparedBench::insert_post(conn, post).await
+    }
+
+    async fn insert_comment(conn: &Self::Connection, comment: &NewComment) -> Result<Uuid, Self::Error> {
+        TokioPostgresPreparedBench::insert_comment(conn, comment).await
+    }
+
+    async fn select_posts_with_user(conn: &Self::Connection, limit: i64) -> Result<Vec<(Post, User)>, Self::Error> {
+        TokioPostgresPreparedBench::select_posts_with_user(conn, limit).await
+    }
+
+    async fn select_posts_by_status(conn: &Self::Connection, status: &str, limit: i64) -> Result<Vec<Post>, Self::Error> {
+        TokioPostgresPreparedBench::select_posts_by_status(conn, status, limit).await
+    }
+
+    async fn select_posts_repeated(conn: &Self::Connection, copies: i32) -> Result<Vec<Post>, Self::Error> {
+        TokioPostgresPreparedBench::select_posts_repeated(conn, copies).await
+    }
+
+    async fn stream_posts_repeated(conn: &Self::Connection, copies: i32, each: impl FnMut(Post)) -> Result<usize, Self::Error> {
+        TokioPostgresPreparedBench::stream_posts_repeated(conn, copies, each).await
+    }
+
+    async fn search_users_by_name(conn: &Self::Connection, pattern: &str, limit: i64) -> Result<Vec<User>, Self::Error> {
+        TokioPostgresPreparedBench::search_users_by_name(conn, pattern, limit).await
+    }
+
+    async fn select_users_posts_comments(conn: &Self::Connection, limit: i64) -> Result<Vec<(User, Post, Comment)>, Self::Error> {
+        TokioPostgresPreparedBench::select_users_posts_comments(conn, limit).await
+    }
+
+    async fn select_comments_for_post(
+        conn: &Self::Connection,
+        post_id: Uuid,
+        limit: i64,
+        before: Option<CommentKey>,
+    ) -> Result<Vec<Comment>, Self::Error> {
+        TokioPostgresPreparedBench::select_comments_for_post(conn, post_id, limit, before).await
+    }
+
+    async fn count_posts_per_user(conn: &Self::Connection) -> Result<Vec<(Uuid, i64)>, Self::Error> {
+        TokioPostgresPreparedBench::count_posts_per_user(conn).await
+    }
+
+    async fn count_posts_per_tag(conn: &Self::Connection) -> Result<Vec<(Uuid, i64)>, Self::Error> {
+        TokioPostgresPreparedBench::count_posts_per_tag(conn).await
+    }
+
+    async fn insert_user_with_posts(conn: &Self::Connection, user: &NewUser, posts: &[NewPost]) -> Result<Uuid, Self::Error> {
+        TokioPostgresPreparedBench::insert_user_with_posts(conn, user, posts).await
+    }
+
+    /// One simple-protocol batch, which has no statement to prepare
+    async fn insert_user_post_update_batched(
+        conn: &Self::Connection,
+        user: &NewUser,
+        post: &NewPost,
+        first_name: &str,
+        last_name: &str,
+    ) -> Result<Uuid, Self::Error> {
+        TokioPostgresBench::insert_user_post_update_batched(&conn.client, user, post, first_name, last_name).await
+    }
+
+    async fn cleanup(conn: &Self::Connection) -> Result<(), Self::Error> {
+        TokioPostgresPreparedBench::cleanup(conn).await
+    }
+
+    fn capabilities() -> Capabilities {
+        Capabilities { prepared_cache: true, ..TokioPostgresBench::capabilities() }
+    }
+}
@@ -0,0 +1,621 @@
+//! wtx benchmark implementation
+//!
+//! wtx is a newer async PostgreSQL client with its own protocol code, codecs
+//! and statement cache, here on a plain tokio `TcpStream`. Every statement
+//! with parameters goes through `execute_stmt_*`, which prepares it the first
+//! time and reuses it after, so it compares with `tokio_postgres_prepared`
+//! and sqlx rather than the unprepared `tokio_postgres`. Records are read by
+//! column name in [`crate::mapping`].
+//!
+//! Three things set it apart from the other drivers:
+//! - its executor takes `&mut self`, so [`WtxConnection`] keeps it behind a
+//!   mutex
+//! - it treats any error response as the end of the connection (its own pool
+//!   replaces such connections), so a connection that saw an error is
+//!   reopened before its next statement
+//! - its URL configuration only takes `application_name`, so the session's
+//!   `options` (see [`crate::session`]) run as `set_config` after connecting;
+//!   statements don't report how many rows they changed either, so the update
+//!   and delete return the ids they touched
+
+use crate::after_connect::AfterConnect;
+use crate::bench_sqlx::DEFAULT_STATEMENT_CACHE_CAPACITY;
+use crate::capability::Capabilities;
+use crate::mapping::{CommentColumns, ModelRow, PostColumns, UserColumns};
+use crate::{batch, database_url, Comment, CommentKey, DatabaseBenchmark, NewComment, NewPost, NewUser, Post, User};
+use chrono::{DateTime, Utc};
+use tokio::net::TcpStream;
+use tokio::sync::{Mutex, MutexGuard};
+use tokio_postgres::config::Host;
+use uuid::Uuid;
+use wtx::calendar::{DateTime as WtxDateTime, Nanosecond, Utc as WtxUtc};
+use wtx::database::client::postgres::{Config, ExecutorBuffer, PostgresExecutor};
+use wtx::database::{Executor as _, Record as _, Records as _};
+use wtx::misc::UriRef;
+use wtx::rng::{ChaCha20, CryptoSeedableRng};
+
+/// A wtx executor on a plain TCP stream
+pub type WtxExecutor = PostgresExecutor<wtx::Error, ExecutorBuffer, TcpStream>;
+
+const INSERT_USER: &str = "INSERT INTO users (username, email, first_name, last_name, age)
+     VALUES ($1, $2, $3, $4, $5)
+     RETURNING id";
+
+const INSERT_POST: &str = "INSERT INTO posts (user_id, title, content, status)
+     VALUES ($1, $2, $3, $4)
+     RETURNING id";
+
+const POSTS_REPEATED: &str = "SELECT p.id, p.user_id, p.title, p.content, p.status, p.view_count, p.created_at, p.updated_at
+     FROM posts p
+     CROSS JOIN generate_series(1, $1)";
+
+/// Characters that would split the URL handed to wtx in the wrong place
+const URI_DELIMITERS: [char; 6] = ['@', ':', '/', '?', '#', '&'];
+
+/// A wtx connection and what it takes to open it again
+pub struct WtxConnection {
+    url: String,
+    hook: AfterConnect,
+    executor: Mutex<WtxExecutor>,
+}
+
+impl WtxConnection {
+    /// The executor, reopened first if an error closed it
+    pub async fn executor(&self) -> Result<MutexGuard<'_, WtxExecutor>, wtx::Error> {
+        let mut executor = self.executor.lock().await;
+        if executor.connection_state().is_closed() {
+            *executor = WtxBench::open(&self.url, &self.hook).await?;
+        }
+        Ok(executor)
+    }
+}
+
+/// The `-c name=value` settings of a libpq `options` string as one
+/// `set_config` query, which parses each value as the startup packet would
+fn session_sql(options: &str) -> Option<String> {
+    let settings: Vec<String> = options
+        .split_whitespace()
+        .map(|setting| setting.trim_start_matches("-c").trim_start_matches("--"))
+        .filter_map(|setting| setting.split_once('='))
+        .map(|(name, value)| {
+            format!("set_config({}, {}, false)", batch::quote_literal(name), batch::quote_literal(value))
+        })
+        .collect();
+    (!settings.is_empty()).then(|| format!("SELECT {}", settings.join(", ")))
+}
+
+/// `value` as wtx's calendar type, to bind as a `timestamptz`
+fn wtx_timestamp(value: DateTime<Utc>) -> Result<WtxDateTime<WtxUtc>, wtx::Error> {
+    WtxDateTime::from_timestamp_secs_and_ns(value.timestamp(), Nanosecond::from_num(value.timestamp_subsec_nanos())?)
+}
+
+async fn insert_user(executor: &mut WtxExecutor, user: &NewUser) -> Result<Uuid, wtx::Error> {
+    let record = executor
+        .execute_stmt_single(
+            INSERT_USER,
+            (user.username.as_str(), user.email.as_str(), user.first_name.as_str(), user.last_name.as_str(), user.age),
+        )
+        .await?;
+    record.decode("id")
+}
+
+async fn insert_post(executor: &mut WtxExecutor, user_id: Uuid, post: &NewPost) -> Result<Uuid, wtx::Error> {
+    let record = executor
+        .execute_stmt_single(INSERT_POST, (user_id, post.title.as_str(), post.content.as_str(), post.status.as_str()))
+        .await?;
+    record.decode("id")
+}
+
+async fn insert_user_and_posts(executor: &mut WtxExecutor, user: &NewUser, posts: &[NewPost]) -> Result<Uuid, wtx::Error> {
+    let user_id = insert_user(executor, user).await?;
+    for post in posts {
+        insert_post(executor, user_id, post).await?;
+    }
+    Ok(user_id)
+}
+
+pub struct WtxBench;
+
+impl WtxBench {
+    pub async fn connect() -> Result<WtxConnection, wtx::Error> {
+        Self::connect_to(&database_url()).await
+    }
+
+    /// Connect without TLS to `url` instead of [`database_url`]
+    pub async fn connect_to(url: &str) -> Result<WtxConnection, wtx::Error> {
+        Self::connect_with_hook(url, &AfterConnect::from_env()).await
+    }
+
+    /// Connect without TLS to `url`, running `hook` instead of the environment's init SQL
+    pub async fn connect_with_hook(url: &str, hook: &AfterConnect) -> Result<WtxConnection, wtx::Error> {
+        let executor = Self::open(url, hook).await?;
+        Ok(WtxConnection { url: url.to_string(), hook: hook.clone(), executor: Mutex::new(executor) })
+    }
+
+    /// Open an executor on `url`, which is parsed as tokio-postgres parses it
+    /// (URL or key/value form) and handed to wtx as the URL it reads
+    async fn open(url: &str, hook: &AfterConnect) -> Result<WtxExecutor, wtx::Error> {
+        let config: tokio_postgres::Config =
+            url.parse().map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+        let Some(Host::Tcp(host)) = config.get_hosts().first() else {
+            return Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "wtx connects over TCP only").into());
+        };
+        let port = config.get_ports().first().copied().unwrap_or(5432);
+        let user = config.get_user().unwrap_or_default();
+        let password = String::from_utf8_lossy(config.get_password().unwrap_or_default());
+        let dbname = config.get_dbname().unwrap_or(user);
+        let application_name = config.get_application_name();
+        for (part, value) in [("user", user), ("password", &*password), ("dbname", dbname)]
+            .into_iter()
+            .chain(application_name.map(|name| ("application_name", name)))
+        {
+            if value.contains(URI_DELIMITERS) {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("wtx only takes a URL and doesn't percent-decode it, so the {part} can't contain any of @ : / ? # &"),
+                )
+                .into());
+            }
+        }
+        let mut wtx_url = format!("postgres://{user}:{password}@{host}:{port}/{dbname}");
+        if let Some(application_name) = application_name {
+            wtx_url.push_str(&format!("?application_name={application_name}"));
+        }
+
+        let uri = UriRef::new(&wtx_url);
+        let mut rng = ChaCha20::from_getrandom()?;
+        let stream = TcpStream::connect((host.as_str(), port)).await?;
+        stream.set_nodelay(true)?;
+        let buffer = ExecutorBuffer::new(DEFAULT_STATEMENT_CACHE_CAPACITY, &mut rng);
+        let mut executor = PostgresExecutor::connect(&Config::from_uri(&uri)?, buffer, &mut rng, stream).await?;
+
+        if let Some(sql) = config.get_options().and_then(session_sql) {
+            executor.execute_ignored(&sql).await?;
+        }
+        hook.run_wtx(&mut executor).await?;
+        Ok(executor)
+    }
+
+    pub async fn insert_user(conn: &WtxConnection, user: &NewUser) -> Result<Uuid, wtx::Error> {
+        insert_user(&mut *conn.executor().await?, user).await
+    }
+
+    pub async fn insert_users_batch(conn: &WtxConnection, users: &[NewUser]) -> Result<Vec<Uuid>, wtx::Error> {
+        let mut executor = conn.executor().await?;
+        let mut ids = Vec::with_capacity(users.len());
+        for user in users {
+            ids.push(insert_user(&mut executor, user).await?);
+        }
+        Ok(ids)
+    }
+
+    pub async fn select_user_by_id(conn: &WtxConnection, id: Uuid) -> Result<Option<User>, wtx::Error> {
+        let mut executor = conn.executor().await?;
+        let record = executor
+            .execute_stmt_optional(
+                "SELECT id, username, email, first_name, last_name, age, created_at, updated_at
+                 FROM users WHERE id = $1",
+                (id,),
+            )
+            .await?;
+        record.as_ref().map(User::try_from).transpose()
+    }
+
+    pub async fn select_users_limit(conn: &WtxConnection, limit: i64) -> Result<Vec<User>, wtx::Error> {
+        let mut executor = conn.executor().await?;
+        let records = executor
+            .execute_stmt_many(
+                "SELECT id, username, email, first_name, last_name, age, created_at, updated_at
+                 FROM users ORDER BY created_at DESC LIMIT $1",
+                (limit,),
+                |_| Ok(()),
+            )
+            .await?;
+        records.iter().map(|r| User::try_from(&r)).collect()
+    }
+
+    pub async fn select_users_filtered(
+        conn: &WtxConnection,
+        min_age: i32,
+        max_age: i32,
+        limit: i64,
+    ) -> Result<Vec<User>, wtx::Error> {
+        let mut executor = conn.executor().await?;
+        let records = executor
+            .execute_stmt_many(
+                "SELECT id, username, email, first_name, last_name, age, created_at, updated_at
+                 FROM users
+                 WHERE age >= $1 AND age <= $2
+                 ORDER BY age, username
+                 LIMIT $3",
+                (min_age, max_age, limit),
+                |_| Ok(()),
+            )
+            .await?;
+        records.iter().map(|r| User::try_from(&r)).collect()
+    }
+
+    pub async fn update_user(conn: &WtxConnection, id: Uuid, first_name: &str, last_name: &str) -> Result<bool, wtx::Error> {
+        let mut executor = conn.executor().await?;
+        let records = executor
+            .execute_stmt_many(
+                "UPDATE users SET first_name = $1, last_name = $2, updated_at = NOW() WHERE id = $3 RETURNING id",
+                (first_name, last_name, id),
+                |_| Ok(()),
+            )
+            .await?;
+        Ok(records.len() > 0)
+    }
+
+    pub async fn delete_user(conn: &WtxConnection, id: Uuid) -> Result<bool, wtx::Error> {
+        let mut executor = conn.executor().await?;
+        let records = executor
+            .execute_stmt_many("DELETE FROM users WHERE id = $1 RETURNING id", (id,), |_| Ok(()))
+            .await?;
+        Ok(records.len() > 0)
+    }
+
+    pub async fn insert_post(conn: &WtxConnection, post: &NewPost) -> Result<Uuid, wtx::Error> {
+        insert_post(&mut *conn.executor().await?, post.user_id, post).await
+    }
+
+    pub async fn insert_comment(conn: &WtxConnection, comment: &NewComment) -> Result<Uuid, wtx::Error> {
+        let mut executor = conn.executor().await?;
+        let record = executor
+            .execute_stmt_single(
+                "INSERT INTO comments (post_id, user_id, content)
+                 VALUES ($1, $2, $3)
+                 RETURNING id",
+                (comment.post_id, comment.user_id, comment.content.as_str()),
+            )
+            .await?;
+        record.decode("id")
+    }
+
+    pub async fn select_posts_with_user(conn: &WtxConnection, limit: i64) -> Result<Vec<(Post, User)>, wtx::Error> {
+        let mut executor = conn.executor().await?;
+        let records = executor
+            .execute_stmt_many(
+                "SELECT
+                    p.id as post_id, p.user_id, p.title, p.content, p.status, p.view_count,
+                    p.created_at as post_created_at, p.updated_at as post_updated_at,
+                    u.id as user_id, u.username, u.email, u.first_name, u.last_name, u.age,
+                    u.created_at as user_created_at, u.updated_at as user_updated_at
+                 FROM posts p
+                 JOIN users u ON p.user_id = u.id
+                 ORDER BY p.created_at DESC
+                 LIMIT $1",
+                (limit,),
+                |_| Ok(()),
+            )
+            .await?;
+        records
+            .iter()
+            .map(|r| Ok((r.post(&PostColumns::JOINED)?, r.user(&UserColumns::JOINED)?)))
+            .collect()
+    }
+
+    pub async fn select_posts_by_status(conn: &WtxConnection, status: &str, limit: i64) -> Result<Vec<Post>, wtx::Error> {
+        let mut executor = conn.executor().await?;
+        let records = executor
+            .execute_stmt_many(
+                "SELECT id, user_id, title, content, status, view_count, created_at, updated_at
+                 FROM posts
+                 WHERE status = $1
+                 ORDER BY created_at DESC
+                 LIMIT $2",
+                (status, limit),
+                |_| Ok(()),
+            )
+            .await?;
+        records.iter().map(|r| Post::try_from(&r)).collect()
+    }
+
+    pub async fn select_posts_repeated(conn: &WtxConnection, copies: i32) -> Result<Vec<Post>, wtx::Error> {
+        let mut executor = conn.executor().await?;
+        let records = executor.execute_stmt_many(POSTS_REPEATED, (copies,), |_| Ok(())).await?;
+        records.iter().map(|r| Post::try_from(&r)).collect()
+    }
+
+    /// The posts handed to `each` from wtx's per-record callback as each row is
+    /// read; wtx still keeps the whole result in its buffer until the
+    /// statement completes
+    pub async fn stream_posts_repeated(conn: &WtxConnection, copies: i32, mut each: impl FnMut(Post)) -> Result<usize, wtx::Error> {
+        let mut executor = conn.executor().await?;
+        let mut count = 0;
+        executor
+            .execute_stmt_many(POSTS_REPEATED, (copies,), |record| {
+                each(Post::try_from(&record)?);
+                count += 1;
+                Ok(())
+            })
+            .await?;
+        Ok(count)
+    }
+
+    pub async fn search_users_by_name(conn: &WtxConnection, pattern: &str, limit: i64) -> Result<Vec<User>, wtx::Error> {
+        let pattern = format!("%{}%", pattern);
+        let mut executor = conn.executor().await?;
+        let records = executor
+            .execute_stmt_many(
+                "SELECT id, username, email, first_name, last_name, age, created_at, updated_at
+                 FROM users
+                 WHERE first_name ILIKE $1 OR last_name ILIKE $1
+                 ORDER BY username
+                 LIMIT $2",
+                (pattern.as_str(), limit),
+                |_| Ok(()),
+            )
+            .await?;
+        records.iter().map(|r| User::try_from(&r)).collect()
+    }
+
+    pub async fn select_comments_for_post(
+        conn: &WtxConnection,
+        post_id: Uuid,
+        limit: i64,
+        before: Option<CommentKey>,
+    ) -> Result<Vec<Comment>, wtx::Error> {
+        let mut executor = conn.executor().await?;
+        let records = match before {
+            None => {
+                executor
+                    .execute_stmt_many(
+                        "SELECT id, post_id, user_id, content, created_at
+                         FROM comments
+                         WHERE post_id = $1
+                         ORDER BY created_at DESC, id DESC
+                         LIMIT $2",
+                        (post_id, limit),
+                        |_| Ok(()),
+                    )
+                    .await?
+            }
+            Some(key) => {
+                executor
+                    .execute_stmt_many(
+                        "SELECT id, post_id, user_id, content, created_at
+                         FROM comments
+                         WHERE post_id = $1 AND (created_at, id) < ($2, $3)
+                         ORDER BY created_at DESC, id DESC
+                         LIMIT $4",
+                        (post_id, wtx_timestamp(key.created_at)?, key.id, limit),
+                        |_| Ok(()),
+                    )
+                    .await?
+            }
+        };
+        records.iter().map(|r| Comment::try_from(&r)).collect()
+    }
+
+    pub async fn select_users_posts_comments(conn: &WtxConnection, limit: i64) -> Result<Vec<(User, Post, Comment)>, wtx::Error> {
+        let mut executor = conn.executor().await?;
+        let records = executor
+            .execute_stmt_many(
+                "SELECT
+                    u.id as user_id, u.username, u.email, u.first_name, u.last_name, u.age,
+                    u.created_at as user_created_at, u.updated_at as user_updated_at,
+                    p.id as post_id, p.title, p.content, p.status, p.view_count,
+                    p.created_at as post_created_at, p.updated_at as post_updated_at,
+                    c.id as comment_id, c.user_id as comment_user_id, c.content as comment_content, c.created_at as comment_created_at
+                 FROM users u
+                 JOIN posts p ON u.id = p.user_id
+                 JOIN comments c ON p.id = c.post_id
+                 ORDER BY u.created_at DESC, p.created_at DESC, c.created_at DESC
+                 LIMIT $1",
+                (limit,),
+                |_| Ok(()),
+            )
+            .await?;
+        records
+            .iter()
+            .map(|r| {
+                Ok((
+                    r.user(&UserColumns::JOINED)?,
+                    r.post(&PostColumns::JOINED)?,
+                    r.comment(&CommentColumns::JOINED)?,
+                ))
+            })
+            .collect()
+    }
+
+    pub async fn count_posts_per_user(conn: &WtxConnection) -> Result<Vec<(Uuid, i64)>, wtx::Error> {
+        let mut executor = conn.executor().await?;
+        let records = executor
+            .execute_stmt_many(
+                "SELECT u.id, COUNT(p.id) as post_count
+                 FROM users u
+                 LEFT JOIN posts p ON u.id = p.user_id
+                 GROUP BY u.id
+                 ORDER BY post_count DESC",
+                (),
+                |_| Ok(()),
+            )
+            .await?;
+        records.iter().map(|r| Ok((r.decode(0_usize)?, r.decode(1_usize)?))).collect()
+    }
+
+    pub async fn count_posts_per_tag(conn: &WtxConnection) -> Result<Vec<(Uuid, i64)>, wtx::Error> {
+        let mut executor = conn.executor().await?;
+        let records = executor
+            .execute_stmt_many(
+                "SELECT t.id, COUNT(pt.post_id) as post_count
+                 FROM tags t
+                 JOIN post_tags pt ON t.id = pt.tag_id
+                 GROUP BY t.id
+                 ORDER BY post_count DESC, t.id",
+                (),
+                |_| Ok(()),
+            )
+            .await?;
+        records.iter().map(|r| Ok((r.decode(0_usize)?, r.decode(1_usize)?))).collect()
+    }
+
+    /// `BEGIN` and `COMMIT` issued on the locked executor; a failed statement
+    /// closes the connection, so it is reopened at once rather than left
+    /// holding the aborted transaction until its next use
+    pub async fn insert_user_with_posts(conn: &WtxConnection, user: &NewUser, posts: &[NewPost]) -> Result<Uuid, wtx::Error> {
+        let mut executor = conn.executor().await?;
+        executor.execute_ignored("BEGIN").await?;
+        match insert_user_and_posts(&mut executor, user, posts).await {
+            Ok(user_id) => {
+                executor.execute_ignored("COMMIT").await?;
+                Ok(user_id)
+            }
+            Err(e) => {
+                // The error that aborted the transaction is the one worth reporting
+                if let Ok(reopened) = Self::open(&conn.url, &conn.hook).await {
+                    *executor = reopened;
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Insert a user and a post for it, then update the user, as one multi-statement batch
+    pub async fn insert_user_post_update_batched(
+        conn: &WtxConnection,
+        user: &NewUser,
+        post: &NewPost,
+        first_name: &str,
+        last_name: &str,
+    ) -> Result<Uuid, wtx::Error> {
+        let user_id = Uuid::new_v4();
+        let sql = batch::write_step_sql(user_id, user, post, first_name, last_name);
+        conn.executor().await?.execute_ignored(&sql).await?;
+        Ok(user_id)
+    }
+
+    pub async fn cleanup(conn: &WtxConnection) -> Result<(), wtx::Error> {
+        conn.executor()
+            .await?
+            .execute_stmt_none("DELETE FROM users WHERE username LIKE 'bench_user_%'", ())
+            .await
+    }
+}
+
+// ============================================================================
+// DatabaseBenchmark implementation
+// ============================================================================
+
+impl DatabaseBenchmark for WtxBench {
+    type Connection = WtxConnection;
+    type Error = wtx::Error;
+
+    async fn connect() -> Result<Self::Connection, Self::Error> {
+        WtxBench::connect().await
+    }
+
+    async fn connect_to(url: &str) -> Result<Self::Connection, Self::Error> {
+        WtxBench::connect_to(url).await
+    }
+
+    async fn insert_user(conn: &Self::Connection, user: &NewUser) -> Result<Uuid, Self::Error> {
+        WtxBench::insert_user(conn, user).await
+    }
+
+    async fn insert_users_batch(conn: &Self::Connection, users: &[NewUser]) -> Result<Vec<Uuid>, Self::Error> {
+        WtxBench::insert_users_batch(conn, users).await
+    }
+
+    async fn select_user_by_id(conn: &Self::Connection, id: Uuid) -> Result<Option<User>, Self::Error> {
+        WtxBench::select_user_by_id(conn, id).await
+    }
+
+    async fn select_users_limit(conn: &Self::Connection, limit: i64) -> Result<Vec<User>, Self::Error> {
+        WtxBench::select_users_limit(conn, limit).await
+    }
+
+    async fn select_users_filtered(conn: &Self::Connection, min_age: i32, max_age: i32, limit: i64) -> Result<Vec<User>, Self::Error> {
+        WtxBench::select_users_filtered(conn, min_age, max_age, limit).await
+    }
+
+    async fn update_user(conn: &Self::Connection, id: Uuid, first_name: &str, last_name: &str) -> Result<bool, Self::Error> {
+        WtxBench::update_user(conn, id, first_name, last_name).await
+    }
+
+    async fn delete_user(conn: &Self::Connection, id: Uuid) -> Result<bool, Self::Error> {
+        WtxBench::delete_user(conn, id).await
+    }
+
+    async fn insert_post(conn: &Self::Connection, post: &NewPost) -> Result<Uuid, Self::Error> {
+        WtxBench::insert_post(conn, post).await
+    }
+
+    async fn insert_comment(conn: &Self::Connection, comment: &NewComment) -> Result<Uuid, Self::Error> {
+        WtxBench::insert_comment(conn, comment).await
+    }
+
+    async fn select_posts_with_user(conn: &Self::Connection, limit: i64) -> Result<Vec<(Post, User)>, Self::Error> {
+        WtxBench::select_posts_with_user(conn, limit).await
+    }
+
+    async fn select_posts_by_status(conn: &Self::Connection, status: &str, limit: i64) -> Result<Vec<Post>, Self::Error> {
+        WtxBench::select_posts_by_status(conn, status, limit).await
+    }
+
+    async fn select_posts_repeated(conn: &Self::Connection, copies: i32) -> Result<Vec<Post>, Self::Error> {
+        WtxBench::select_posts_repeated(conn, copies).await
+    }
+
+    async fn stream_posts_repeated(conn: &Self::Connection, copies: i32, each: impl FnMut(Post)) -> Result<usize, Self::Error> {
+        WtxBench::stream_posts_repeated(conn, copies, each).await
+    }
+
+    async fn search_users_by_name(conn: &Self::Connection, pattern: &str, limit: i64) -> Result<Vec<User>, Self::Error> {
+        WtxBench::search_users_by_name(conn, pattern, limit).await
+    }
+
+    async fn select_comments_for_post(
+        conn: &Self::Connection,
+        post_id: Uuid,
+        limit: i64,
+        before: Option<CommentKey>,
+    ) -> Result<Vec<Comment>, Self::Error> {
+        WtxBench::select_comments_for_post(conn, post_id, limit, before).await
+    }
+
+    async fn select_users_posts_comments(conn: &Self::Connection, limit: i64) -> Result<Vec<(User, Post, Comment)>, Self::Error> {
+        WtxBench::select_users_posts_comments(conn, limit).await
+    }
+
+    async fn count_posts_per_user(conn: &Self::Connection) -> Result<Vec<(Uuid, i64)>, Self::Error> {
+        WtxBench::count_posts_per_user(conn).await
+    }
+
+    async fn count_posts_per_tag(conn: &Self::Connection) -> Result<Vec<(Uuid, i64)>, Self::Error> {
+        WtxBench::count_posts_per_tag(conn).await
+    }
+
+    async fn insert_user_with_posts(conn: &Self::Connection, user: &NewUser, posts: &[NewPost]) -> Result<Uuid, Self::Error> {
+        WtxBench::insert_user_with_posts(conn, user, posts).await
+    }
+
+    async fn insert_user_post_update_batched(
+        conn: &Self::Connection,
+        user: &NewUser,
+        post: &NewPost,
+        first_name: &str,
+        last_name: &str,
+    ) -> Result<Uuid, Self::Error> {
+        WtxBench::insert_user_post_update_batched(conn, user, post, first_name, last_name).await
+    }
+
+    async fn cleanup(conn: &Self::Connection) -> Result<(), Self::Error> {
+        WtxBench::cleanup(conn).await
+    }
+
+    fn capabilities() -> Capabilities {
+        Capabilities {
+            // The executor takes `&mut self`
+            shared_transactions: false,
+            // Records are handed to a callback as they are read, but the whole
+            // result stays in wtx's buffer until the statement completes
+            streaming: false,
+            copy: false,
+            listen_notify: false,
+            prepared_cache: true,
+            binary_format: true,
+        }
+    }
+}
@@ -0,0 +1,271 @@
+//! PostgreSQL Library Benchmark - Load-Test Runner
+//!
+//! Drives the benchmark scenarios from concurrent workers for a fixed time and
+//! reports throughput and latency percentiles per backend, independently of
+//! criterion. Settings are read from the TOML file given as the first argument,
+//! or `bench-runner.toml` if it exists; see that file for every setting. A
+//! file with `[[operations]]` runs that weighted mix of scenarios together
+//! instead, as in `workloads/`. The run fails if a result misses one of the
+//! `[slo.<scenario>]` objectives. Every result shows its noise score, the
+//! coefficient of variation of its latencies; `[noise]` decides whether noisy
+//! results are only flagged, dropped or run again. Exports are rewritten after
+//! every run; Ctrl-C stops after the current run, cleans up the active
+//! backend and keeps the exports written so far, marked as interrupted.
+//!
+//! ```bash
+//! cargo run --release --bin bench-runner -- bench-runner.toml
+//! cargo run --release --bin bench-runner -- workloads/read-mostly.toml
+//! ```
+
+use anyhow::{anyhow, Context, Result};
+use pg_benchmark::bench_tokio_postgres::TokioPostgresBench;
+use pg_benchmark::interrupt::{Interrupt, EXIT_INTERRUPTED};
+use pg_benchmark::metadata::RunMetadata;
+use pg_benchmark::profile;
+use pg_benchmark::replay::WorkloadMix;
+use pg_benchmark::runner::{self, Progress, RunResult, RunnerConfig};
+use pg_benchmark::{database_url, seeder};
+use std::io::Write;
+use std::path::Path;
+
+const DEFAULT_CONFIG: &str = "bench-runner.toml";
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt::init();
+
+    let config = match std::env::args().nth(1) {
+        Some(path) => load(Path::new(&path))?,
+        None if Path::new(DEFAULT_CONFIG).exists() => load(Path::new(DEFAULT_CONFIG))?,
+        None => RunnerConfig::default(),
+    };
+    // Before anything connects, so every backend uses the profile's database
+    if let Some(name) = &config.profile {
+        profile::select(name).map_err(|e| anyhow!(e))?;
+    }
+    let scenarios = config.scenarios().map_err(|e| anyhow!(e))?;
+    let backends = config.backends().map_err(|e| anyhow!(e))?;
+    let workload = config.workload().map_err(|e| anyhow!(e))?;
+
+    println!("PostgreSQL Library Load Test");
+    println!("============================");
+    println!("Database URL: {}", database_url());
+    if let Some(profile) = profile::selected() {
+        println!("Profile: {} ({} users)", profile.name, profile.users);
+    }
+    print!(
+        "Mode: {}, {} worker(s), {}s per run after {}s warm-up",
+        config.mode, config.concurrency, config.duration_secs, config.warmup_secs
+    );
+    if config.mode == runner::Mode::Latency {
+        print!(", {} ops/s", config.rate);
+    }
+    println!();
+    let tags = config.tag_filter().map_err(|e| anyhow!(e))?;
+    if !tags.tags.is_empty() && workload.is_none() {
+        println!("Scenarios tagged {}: {}", tags, scenarios.len());
+    }
+
+    // The in-memory backend's dataset follows the SEED_* variables only
+    if let (Some(dataset), true) = (&config.dataset, backends.iter().any(|b| *b != "null")) {
+        let seed = dataset.seed_config().map_err(|e| anyhow!(e))?;
+        println!("Topping the dataset up to {} users, ratio {}", seed.users, seed.ratio);
+        let inserted = seeder::top_up(&database_url(), &seed).await?;
+        println!("  inserted {}", inserted);
+    }
+
+    // The in-memory backend alone never connects to the database
+    let mut metadata = if config.export.is_empty() {
+        None
+    } else if backends.iter().any(|b| *b != "null") {
+        Some(RunMetadata::collect(&TokioPostgresBench::connect().await?).await?)
+    } else {
+        Some(RunMetadata::local())
+    };
+
+    let interrupt = Interrupt::listen();
+    let mut active = None;
+    let mut results = Vec::new();
+    match &workload {
+        Some(mix) => {
+            println!();
+            println!("Workload");
+            let total: u64 = mix.entries.iter().map(|e| e.calls).sum();
+            for entry in &mix.entries {
+                println!(
+                    "  {:<36} size {:>5}  {:>5.1}%",
+                    entry.scenario.name,
+                    entry.size,
+                    entry.calls as f64 * 100.0 / total as f64
+                );
+            }
+            for &backend in &backends {
+                if interrupt.requested() {
+                    break;
+                }
+                println!();
+                if let Some(entry) = mix.entries.iter().find(|e| !e.scenario.runs_on(backend)) {
+                    println!("{} skipped, {} does not run on it", backend, entry.scenario.name);
+                    continue;
+                }
+                active = Some(backend);
+                let mix_results = run_backend(&config, mix, backend).await?;
+                let throughput: f64 = mix_results.iter().map(|r| r.throughput).sum();
+                println!("{} ({:.1} ops/s in total)", backend, throughput);
+                for result in &mix_results {
+                    print_result(result.scenario, result);
+                }
+                results.extend(mix_results);
+                flush(&config, metadata.as_ref(), &results)?;
+            }
+        }
+        None => {
+            'scenarios: for scenario in scenarios {
+                if interrupt.requested() {
+                    break;
+                }
+                println!();
+                println!("{} ({}, size {})", scenario.name, scenario.category, scenario.typical_size());
+                for &backend in &backends {
+                    if interrupt.requested() {
+                        break 'scenarios;
+                    }
+                    if !scenario.runs_on(backend) {
+                        println!("  {:<24} skipped", backend);
+                        continue;
+                    }
+                    active = Some(backend);
+                    let result = run_backend(&config, &WorkloadMix::single(scenario), backend).await?;
+                    print_result(backend, &result[0]);
+                    results.extend(result);
+                    flush(&config, metadata.as_ref(), &results)?;
+                }
+            }
+        }
+    }
+
+    if interrupt.requested() {
+        println!();
+        println!("Interrupted after {} result(s)", results.len());
+        if let Some(backend) = active {
+            match runner::cleanup_backend(backend).await {
+                Ok(()) => println!("  cleaned up after {}", backend),
+                Err(e) => println!("  cleaning up after {} failed: {}", backend, e),
+            }
+        }
+        if let Some(metadata) = &mut metadata {
+            metadata.interrupted = true;
+        }
+    }
+
+    let (results, dropped) = config.noise.keep(results);
+    if !dropped.is_empty() {
+        println!();
+        println!("Dropped as noisy (cv above {})", config.noise.max_cv);
+        for r in &dropped {
+            println!("  {} on {}: cv {:.2}", r.scenario, r.backend, r.cv);
+        }
+    }
+
+    if let Some(metadata) = &metadata {
+        config.export.write(metadata, &results).context("writing the exports")?;
+        println!();
+        for path in config.export.json.iter().chain(&config.export.csv) {
+            println!("Wrote {}", path.display());
+        }
+    }
+
+    let violations = config.violations(&results);
+    if !config.slo.is_empty() {
+        println!();
+        println!("SLO violations");
+        println!("==============");
+        if violations.is_empty() {
+            println!("None");
+        }
+        for violation in &violations {
+            println!("  {}", violation);
+        }
+    }
+    if !violations.is_empty() {
+        anyhow::bail!("{} SLO violation(s)", violations.len());
+    }
+    if interrupt.requested() {
+        std::process::exit(EXIT_INTERRUPTED);
+    }
+
+    Ok(())
+}
+
+/// Rewrite the exports with the results so far, if exporting
+fn flush(config: &RunnerConfig, metadata: Option<&RunMetadata>, results: &[RunResult]) -> Result<()> {
+    let Some(metadata) = metadata else { return Ok(()) };
+    let (kept, _) = config.noise.keep(results.to_vec());
+    config.export.write(metadata, &kept).context("writing the exports")
+}
+
+fn load(path: &Path) -> Result<RunnerConfig> {
+    let text = std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    RunnerConfig::from_toml(&text).map_err(|e| anyhow!("{}: {}", path.display(), e))
+}
+
+async fn run_backend(config: &RunnerConfig, mix: &WorkloadMix, backend: &'static str) -> Result<Vec<RunResult>> {
+    // Live progress on one line, overwritten every second
+    let mut progress = |p: &Progress| {
+        eprint!(
+            "\r  {:<24} {:>5.0}s  {:>9} ops  {:>10.1} ops/s  {} error(s)",
+            backend,
+            p.elapsed.as_secs_f64(),
+            p.operations,
+            p.throughput(),
+            p.errors
+        );
+        let _ = std::io::stderr().flush();
+    };
+    let result = runner::run_backend(config, mix, backend, &mut progress)
+        .await
+        .map_err(|e| anyhow!("{} on {}: {}", mix_name(mix), backend, e));
+    eprint!("\r\x1b[K");
+    result
+}
+
+/// The scenario's name, or "workload" for a mix of several
+fn mix_name(mix: &WorkloadMix) -> &'static str {
+    match mix.entries[..] {
+        [entry] => entry.scenario.name,
+        _ => "workload",
+    }
+}
+
+/// One result on a line headed by `label`, the backend or the scenario
+fn print_result(label: &str, r: &RunResult) {
+    println!(
+        "  {:<24} {:>10.1} ops/s  p50 {:>9.1}us  p95 {:>9.1}us  p99 {:>9.1}us  max {:>9.1}us  cv {:>5.2}{}",
+        label,
+        r.throughput,
+        r.p50_us,
+        r.p95_us,
+        r.p99_us,
+        r.max_us,
+        r.cv,
+        if r.noisy { " noisy" } else { "" }
+    );
+    if r.outliers > 0 {
+        println!("  {:<24} {} outlier(s) left out of the mean", "", r.outliers);
+    }
+    if let Some(error) = &r.first_error {
+        println!("  {:<24} {} error(s), first: {}", "", r.errors, error);
+    }
+    if let Some(growth) = &r.growth {
+        println!(
+            "  {:<24} tables {:+.1} MiB ({:+.0}%) {}",
+            "",
+            growth.growth_bytes() as f64 / (1024.0 * 1024.0),
+            growth.relative_growth() * 100.0,
+            growth.curve()
+        );
+        if let Some(error) = &growth.error {
+            println!("  {:<24} size sampling stopped: {}", "", error);
+        }
+    }
+}
@@ -0,0 +1,372 @@
+//! Insert strategies for a batch of users, swept over batch sizes
+//!
+//! The `insert_batch_users` scenario runs each backend's own batch insert at
+//! three sizes, which says little about where one way of inserting overtakes
+//! another. Every [`Strategy`] here inserts the same users into `users`, and
+//! the `batch_sweep` groups run each at every one of [`BATCH_SIZES`], giving a
+//! throughput curve per backend and strategy:
+//!
+//! - [`Strategy::Looped`]: one `INSERT` per user, a round trip each
+//! - [`Strategy::MultiRow`]: one `INSERT` with a `VALUES` row per user, five
+//!   parameters a row, so a different statement for every size
+//! - [`Strategy::Unnest`]: one `INSERT ... SELECT FROM unnest(...)` of five
+//!   arrays, the same statement and plan whatever the size
+//! - [`Strategy::Copy`]: `COPY users (...) FROM STDIN`, in the binary format
+//!   for tokio-postgres and Diesel and as CSV for sqlx, which only sends raw data
+//!
+//! SeaORM and clorinde have no `COPY` of their own; theirs goes through the
+//! sqlx pool or the tokio-postgres client underneath, so the sweep leaves them
+//! out of its `COPY` group (see [`crate::capability`]). The prepared
+//! tokio-postgres variant is left out too: a multi-row insert is a different
+//! statement at every size, and the other strategies are the plain driver's.
+
+use crate::bench_clorinde::{queries, ClorindeBench};
+use crate::bench_diesel::{schema::users, DieselBench};
+use crate::bench_seaorm::SeaOrmBench;
+use crate::bench_sqlx::SqlxBench;
+use crate::bench_tokio_postgres::TokioPostgresBench;
+use crate::{DatabaseBenchmark, NewUser};
+use futures::pin_mut;
+use sea_orm::{
+    ActiveValue, ConnectionTrait, DatabaseBackend, DatabaseConnection, DbErr, EntityTrait, RuntimeErr, Statement,
+};
+use sqlx::postgres::PgPoolCopyExt;
+use sqlx::PgPool;
+use std::fmt;
+use std::fmt::Write;
+use tokio_postgres::binary_copy::BinaryCopyInWriter;
+use tokio_postgres::types::Type;
+use tokio_postgres::Client;
+
+/// Users per insert the sweep runs every strategy at
+pub const BATCH_SIZES: &[usize] = &[1, 10, 50, 100, 500, 1000];
+
+const UNNEST: &str = "INSERT INTO users (username, email, first_name, last_name, age)
+     SELECT * FROM unnest($1::varchar[], $2::varchar[], $3::varchar[], $4::varchar[], $5::int4[])";
+
+const COPY_BINARY: &str = "COPY users (username, email, first_name, last_name, age) FROM STDIN BINARY";
+
+const COPY_CSV: &str = "COPY users (username, email, first_name, last_name, age) FROM STDIN (FORMAT csv)";
+
+/// The column types [`COPY_BINARY`] writes
+const COPY_TYPES: [Type; 5] = [Type::VARCHAR, Type::VARCHAR, Type::VARCHAR, Type::VARCHAR, Type::INT4];
+
+/// How a batch of users is inserted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// One `INSERT` per user
+    Looped,
+    /// One `INSERT` with a `VALUES` row per user
+    MultiRow,
+    /// One `INSERT` of the `unnest` of a bound array per column
+    Unnest,
+    /// `COPY ... FROM STDIN`
+    Copy,
+}
+
+impl Strategy {
+    pub const ALL: [Strategy; 4] = [Strategy::Looped, Strategy::MultiRow, Strategy::Unnest, Strategy::Copy];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Strategy::Looped => "looped",
+            Strategy::MultiRow => "multi_row",
+            Strategy::Unnest => "unnest",
+            Strategy::Copy => "copy",
+        }
+    }
+}
+
+impl fmt::Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// `INSERT INTO users ... VALUES` with a row of five placeholders per user
+pub fn multi_row_sql(rows: usize) -> String {
+    let mut sql = String::from("INSERT INTO users (username, email, first_name, last_name, age) VALUES ");
+    for row in 0..rows {
+        let first = row * 5 + 1;
+        if row > 0 {
+            sql.push_str(", ");
+        }
+        let _ = write!(sql, "(${}, ${}, ${}, ${}, ${})", first, first + 1, first + 2, first + 3, first + 4);
+    }
+    sql
+}
+
+/// `users` as [`COPY_CSV`] reads them: every text quoted, a missing age empty
+pub fn csv(users: &[NewUser]) -> String {
+    fn quoted(text: &str) -> String {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    }
+    let mut csv = String::new();
+    for user in users {
+        let age = user.age.map(|age| age.to_string()).unwrap_or_default();
+        let _ = writeln!(
+            csv,
+            "{},{},{},{},{}",
+            quoted(&user.username),
+            quoted(&user.email),
+            quoted(&user.first_name),
+            quoted(&user.last_name),
+            age
+        );
+    }
+    csv
+}
+
+/// One array per column, as [`UNNEST`] binds them
+struct Columns {
+    usernames: Vec<String>,
+    emails: Vec<String>,
+    first_names: Vec<String>,
+    last_names: Vec<String>,
+    ages: Vec<Option<i32>>,
+}
+
+impl Columns {
+    fn of(users: &[NewUser]) -> Self {
+        Self {
+            usernames: users.iter().map(|u| u.username.clone()).collect(),
+            emails: users.iter().map(|u| u.email.clone()).collect(),
+            first_names: users.iter().map(|u| u.first_name.clone()).collect(),
+            last_names: users.iter().map(|u| u.last_name.clone()).collect(),
+            ages: users.iter().map(|u| u.age).collect(),
+        }
+    }
+}
+
+/// Every [`Strategy`] on the backend's own connection; each returns the
+/// number of users inserted
+#[allow(async_fn_in_trait)]
+pub trait BulkInsert: DatabaseBenchmark {
+    /// One [`DatabaseBenchmark::insert_user`] per user
+    async fn insert_looped(conn: &Self::Connection, users: &[NewUser]) -> Result<u64, Self::Error> {
+        for user in users {
+            Self::insert_user(conn, user).await?;
+        }
+        Ok(users.len() as u64)
+    }
+
+    async fn insert_multi_row(conn: &Self::Connection, users: &[NewUser]) -> Result<u64, Self::Error>;
+
+    async fn insert_unnest(conn: &Self::Connection, users: &[NewUser]) -> Result<u64, Self::Error>;
+
+    async fn insert_copy(conn: &Self::Connection, users: &[NewUser]) -> Result<u64, Self::Error>;
+
+    /// Insert `users` with `strategy`
+    async fn insert(conn: &Self::Connection, strategy: Strategy, users: &[NewUser]) -> Result<u64, Self::Error> {
+        match strategy {
+            Strategy::Looped => Self::insert_looped(conn, users).await,
+            Strategy::MultiRow => Self::insert_multi_row(conn, users).await,
+            Strategy::Unnest => Self::insert_unnest(conn, users).await,
+            Strategy::Copy => Self::insert_copy(conn, users).await,
+        }
+    }
+}
+
+// ============================================================================
+// tokio-postgres and clorinde
+// ============================================================================
+
+impl BulkInsert for TokioPostgresBench {
+    async fn insert_multi_row(client: &Client, users: &[NewUser]) -> Result<u64, tokio_postgres::Error> {
+        let params: Vec<&(dyn tokio_postgres::types::ToSql + Sync)> = users
+            .iter()
+            .flat_map(|u| [&u.username as _, &u.email as _, &u.first_name as _, &u.last_name as _, &u.age as _])
+            .collect();
+        client.execute(&multi_row_sql(users.len()), &params).await
+    }
+
+    async fn insert_unnest(client: &Client, users: &[NewUser]) -> Result<u64, tokio_postgres::Error> {
+        let c = Columns::of(users);
+        client
+            .execute(UNNEST, &[&c.usernames, &c.emails, &c.first_names, &c.last_names, &c.ages])
+            .await
+    }
+
+    async fn insert_copy(client: &Client, users: &[NewUser]) -> Result<u64, tokio_postgres::Error> {
+        let sink = client.copy_in(COPY_BINARY).await?;
+        let writer = BinaryCopyInWriter::new(sink, &COPY_TYPES);
+        pin_mut!(writer);
+        for u in users {
+            writer
+                .as_mut()
+                .write(&[&u.username, &u.email, &u.first_name, &u.last_name, &u.age])
+                .await?;
+        }
+        writer.finish().await
+    }
+}
+
+impl BulkInsert for ClorindeBench {
+    /// Clorinde generates one function per fixed statement, so this goes
+    /// through the underlying client
+    async fn insert_multi_row(client: &Client, users: &[NewUser]) -> Result<u64, tokio_postgres::Error> {
+        TokioPostgresBench::insert_multi_row(client, users).await
+    }
+
+    async fn insert_unnest(client: &Client, users: &[NewUser]) -> Result<u64, tokio_postgres::Error> {
+        let c = Columns::of(users);
+        queries::users::insert_users_unnest()
+            .bind(client, &c.usernames, &c.emails, &c.first_names, &c.last_names, &c.ages)
+            .await
+    }
+
+    /// Through the underlying client, having no `COPY` of its own
+    async fn insert_copy(client: &Client, users: &[NewUser]) -> Result<u64, tokio_postgres::Error> {
+        TokioPostgresBench::insert_copy(client, users).await
+    }
+}
+
+// ============================================================================
+// sqlx
+// ============================================================================
+
+impl BulkInsert for SqlxBench {
+    async fn insert_multi_row(pool: &PgPool, users: &[NewUser]) -> Result<u64, sqlx::Error> {
+        let sql = multi_row_sql(users.len());
+        let mut query = sqlx::query(&sql);
+        for u in users {
+            query = query
+                .bind(&u.username)
+                .bind(&u.email)
+                .bind(&u.first_name)
+                .bind(&u.last_name)
+                .bind(u.age);
+        }
+        Ok(query.execute(pool).await?.rows_affected())
+    }
+
+    async fn insert_unnest(pool: &PgPool, users: &[NewUser]) -> Result<u64, sqlx::Error> {
+        let c = Columns::of(users);
+        let done = sqlx::query(UNNEST)
+            .bind(c.usernames)
+            .bind(c.emails)
+            .bind(c.first_names)
+            .bind(c.last_names)
+            .bind(c.ages)
+            .execute(pool)
+            .await?;
+        Ok(done.rows_affected())
+    }
+
+    /// sqlx has no binary `COPY` encoder, so this sends CSV
+    async fn insert_copy(pool: &PgPool, users: &[NewUser]) -> Result<u64, sqlx::Error> {
+        let mut copy = pool.copy_in_raw(COPY_CSV).await?;
+        copy.send(csv(users).into_bytes()).await?;
+        copy.finish().await
+    }
+}
+
+// ============================================================================
+// SeaORM
+// ============================================================================
+
+impl BulkInsert for SeaOrmBench {
+    async fn insert_multi_row(db: &DatabaseConnection, users: &[NewUser]) -> Result<u64, DbErr> {
+        use crate::bench_seaorm::users as entity;
+
+        let models = users.iter().map(|u| entity::ActiveModel {
+            username: ActiveValue::Set(u.username.clone()),
+            email: ActiveValue::Set(u.email.clone()),
+            first_name: ActiveValue::Set(u.first_name.clone()),
+            last_name: ActiveValue::Set(u.last_name.clone()),
+            age: ActiveValue::Set(u.age),
+            ..Default::default()
+        });
+        entity::Entity::insert_many(models).exec_without_returning(db).await
+    }
+
+    async fn insert_unnest(db: &DatabaseConnection, users: &[NewUser]) -> Result<u64, DbErr> {
+        let c = Columns::of(users);
+        // sea-query only binds arrays without NULL elements, so the ages go as
+        // an array literal, which `$5::int4[]` casts
+        let ages: Vec<String> = c.ages.iter().map(|age| age.map_or("NULL".to_string(), |age| age.to_string())).collect();
+        let statement = Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            UNNEST,
+            [
+                c.usernames.into(),
+                c.emails.into(),
+                c.first_names.into(),
+                c.last_names.into(),
+                format!("{{{}}}", ages.join(",")).into(),
+            ],
+        );
+        Ok(db.execute(statement).await?.rows_affected())
+    }
+
+    /// Through the sqlx pool underneath, having no `COPY` of its own
+    async fn insert_copy(db: &DatabaseConnection, users: &[NewUser]) -> Result<u64, DbErr> {
+        SqlxBench::insert_copy(db.get_postgres_connection_pool(), users)
+            .await
+            .map_err(|e| DbErr::Exec(RuntimeErr::SqlxError(e)))
+    }
+}
+
+// ============================================================================
+// Diesel
+// ============================================================================
+
+impl BulkInsert for DieselBench {
+    async fn insert_multi_row(conn: &Self::Connection, users: &[NewUser]) -> Result<u64, Self::Error> {
+        use diesel::RunQueryDsl;
+
+        let rows = diesel_users(users);
+        let inserted = diesel::insert_into(users::table).values(&rows).execute(&mut *conn.lock().unwrap())?;
+        Ok(inserted as u64)
+    }
+
+    async fn insert_unnest(conn: &Self::Connection, users: &[NewUser]) -> Result<u64, Self::Error> {
+        use diesel::sql_types::{Array, Int4, Nullable, Varchar};
+        use diesel::RunQueryDsl;
+
+        let c = Columns::of(users);
+        let inserted = diesel::sql_query(UNNEST)
+            .bind::<Array<Varchar>, _>(&c.usernames)
+            .bind::<Array<Varchar>, _>(&c.emails)
+            .bind::<Array<Varchar>, _>(&c.first_names)
+            .bind::<Array<Varchar>, _>(&c.last_names)
+            .bind::<Array<Nullable<Int4>>, _>(&c.ages)
+            .execute(&mut *conn.lock().unwrap())?;
+        Ok(inserted as u64)
+    }
+
+    async fn insert_copy(conn: &Self::Connection, users: &[NewUser]) -> Result<u64, Self::Error> {
+        use diesel::ExecuteCopyFromDsl;
+
+        let rows = diesel_users(users);
+        let copied = diesel::copy_from(users::table)
+            .from_insertable(&rows)
+            .execute(&mut *conn.lock().unwrap())?;
+        Ok(copied as u64)
+    }
+}
+
+/// A user as Diesel inserts and copies it; `COPY` has no `DEFAULT`, so a
+/// missing age is written as NULL rather than left to the column default
+#[derive(diesel::Insertable)]
+#[diesel(table_name = users, treat_none_as_default_value = false)]
+struct DieselBulkUser<'a> {
+    username: &'a str,
+    email: &'a str,
+    first_name: &'a str,
+    last_name: &'a str,
+    age: Option<i32>,
+}
+
+fn diesel_users(users: &[NewUser]) -> Vec<DieselBulkUser<'_>> {
+    users
+        .iter()
+        .map(|u| DieselBulkUser {
+            username: &u.username,
+            email: &u.email,
+            first_name: &u.first_name,
+            last_name: &u.last_name,
+            age: u.age,
+        })
+        .collect()
+}
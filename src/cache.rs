@@ -0,0 +1,74 @@
+//! Warm and cold buffer cache states for the read benchmarks
+//!
+//! Every read scenario runs against whatever the groups before it left in
+//! shared buffers and the OS page cache, so its numbers depend on the run
+//! order. The cache comparison pins both ends instead: [`prewarm`] loads every
+//! table and index of the schema into shared buffers before the warm runs, and
+//! a cold run restarts the server with the command in
+//! `BENCH_COLD_CACHE_COMMAND` ([`clear`]) before timing a single operation on a
+//! fresh connection. Shared buffers are empty after any restart; the command
+//! can also drop the OS page cache (`sync; echo 3 > /proc/sys/vm/drop_caches`
+//! on the database host) to make the reads go to disk as well.
+
+use crate::bench_tokio_postgres::TokioPostgresBench;
+use std::io;
+use std::process::Command;
+use std::time::{Duration, Instant};
+use tokio_postgres::Client;
+
+/// How long [`wait_until_ready`] lets the server take to come back
+pub const RESTART_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How often [`wait_until_ready`] tries to connect meanwhile
+const RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The command that empties the caches, from `BENCH_COLD_CACHE_COMMAND`, or
+/// `None` when cold runs aren't configured
+pub fn cold_command() -> Option<String> {
+    std::env::var("BENCH_COLD_CACHE_COMMAND").ok().filter(|command| !command.trim().is_empty())
+}
+
+/// Load every table and index of the `public` schema into shared buffers,
+/// installing `pg_prewarm` first if the database was created before `init.sql`
+/// did; the number of blocks loaded
+pub async fn prewarm(client: &Client) -> Result<i64, tokio_postgres::Error> {
+    client.batch_execute("CREATE EXTENSION IF NOT EXISTS pg_prewarm").await?;
+    let row = client
+        .query_one(
+            "SELECT COALESCE(SUM(pg_prewarm(c.oid, 'buffer')), 0)::bigint
+             FROM pg_class c
+             WHERE c.relnamespace = 'public'::regnamespace AND c.relkind IN ('r', 'i', 't')",
+            &[],
+        )
+        .await?;
+    Ok(row.get(0))
+}
+
+/// Run `command` with `sh -c`, failing if it doesn't exit successfully. The
+/// server is usually still starting when it returns, see [`wait_until_ready`].
+pub fn clear(command: &str) -> io::Result<()> {
+    let status = Command::new("sh").arg("-c").arg(command).status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!("`{command}` exited with {status}")))
+    }
+}
+
+/// Wait until the server accepts connections again, up to `timeout`; the last
+/// connection error once it has passed
+pub async fn wait_until_ready(timeout: Duration) -> Result<(), tokio_postgres::Error> {
+    let start = Instant::now();
+    loop {
+        match TokioPostgresBench::connect().await {
+            Ok(client) => match client.simple_query("SELECT 1").await {
+                Ok(_) => return Ok(()),
+                Err(e) if start.elapsed() > timeout => return Err(e),
+                Err(_) => {}
+            },
+            Err(e) if start.elapsed() > timeout => return Err(e),
+            Err(_) => {}
+        }
+        tokio::time::sleep(RETRY_INTERVAL).await;
+    }
+}
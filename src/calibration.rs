@@ -0,0 +1,146 @@
+//! A fixed-cost query that isolates client overhead
+//!
+//! Every other group measures the server's work and the client's together,
+//! and how much of a difference between backends is the library's own is left
+//! to guesswork. [`Calibration::fixed_row`] runs the same statement on every
+//! backend: it sleeps [`SLEEP`] on the server and returns one constant
+//! [`FixedRow`]. The server's time is the same whatever the backend, so what
+//! the `calibration_fixed_cost` group measures beyond [`SLEEP`] is encoding
+//! the parameter, the round trip and decoding the row, a baseline for reading
+//! the rest of the report.
+
+use crate::bench_clorinde::{queries, ClorindeBench};
+use crate::bench_diesel::DieselBench;
+use crate::bench_seaorm::SeaOrmBench;
+use crate::bench_sqlx::SqlxBench;
+use crate::bench_tokio_postgres::TokioPostgresBench;
+use crate::DatabaseBenchmark;
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, DbErr, Statement};
+use sqlx::PgPool;
+use std::time::Duration;
+use tokio_postgres::Client;
+
+/// The server's share of every [`Calibration::fixed_row`]
+pub const SLEEP: Duration = Duration::from_millis(1);
+
+/// One constant row from `pg_sleep` in `FROM`, which yields a single row
+/// once the sleep is over
+const FIXED_ROW: &str = "SELECT 42::int8 AS id, 'calibration'::text AS label, 0.5::float8 AS ratio FROM pg_sleep($1)";
+
+/// The row every [`Calibration::fixed_row`] returns
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixedRow {
+    pub id: i64,
+    pub label: String,
+    pub ratio: f64,
+}
+
+impl FixedRow {
+    /// What the server returns
+    pub fn expected() -> Self {
+        Self {
+            id: 42,
+            label: "calibration".to_string(),
+            ratio: 0.5,
+        }
+    }
+}
+
+/// Client overhead per query: `elapsed` for that many queries beyond the
+/// [`SLEEP`] each spent on the server
+pub fn overhead(elapsed: Duration, queries: u32) -> Duration {
+    (elapsed / queries.max(1)).saturating_sub(SLEEP)
+}
+
+/// The fixed-cost query on the backend's own connection
+#[allow(async_fn_in_trait)]
+pub trait Calibration: DatabaseBenchmark {
+    /// Sleep `sleep` on the server and return the [`FixedRow`]
+    async fn fixed_row(conn: &Self::Connection, sleep: Duration) -> Result<FixedRow, Self::Error>;
+}
+
+// ============================================================================
+// tokio-postgres and clorinde
+// ============================================================================
+
+impl Calibration for TokioPostgresBench {
+    async fn fixed_row(client: &Client, sleep: Duration) -> Result<FixedRow, tokio_postgres::Error> {
+        let row = client.query_one(FIXED_ROW, &[&sleep.as_secs_f64()]).await?;
+        Ok(FixedRow {
+            id: row.get("id"),
+            label: row.get("label"),
+            ratio: row.get("ratio"),
+        })
+    }
+}
+
+impl Calibration for ClorindeBench {
+    async fn fixed_row(client: &Client, sleep: Duration) -> Result<FixedRow, tokio_postgres::Error> {
+        let row = queries::session::fixed_row().bind(client, &sleep.as_secs_f64()).one().await?;
+        Ok(FixedRow {
+            id: row.id,
+            label: row.label,
+            ratio: row.ratio,
+        })
+    }
+}
+
+// ============================================================================
+// sqlx
+// ============================================================================
+
+impl Calibration for SqlxBench {
+    async fn fixed_row(pool: &PgPool, sleep: Duration) -> Result<FixedRow, sqlx::Error> {
+        let (id, label, ratio) = sqlx::query_as(FIXED_ROW).bind(sleep.as_secs_f64()).fetch_one(pool).await?;
+        Ok(FixedRow { id, label, ratio })
+    }
+}
+
+// ============================================================================
+// SeaORM
+// ============================================================================
+
+impl Calibration for SeaOrmBench {
+    async fn fixed_row(db: &DatabaseConnection, sleep: Duration) -> Result<FixedRow, DbErr> {
+        let statement =
+            Statement::from_sql_and_values(DatabaseBackend::Postgres, FIXED_ROW, [sleep.as_secs_f64().into()]);
+        let row = db
+            .query_one(statement)
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound("calibration row".to_string()))?;
+        Ok(FixedRow {
+            id: row.try_get("", "id")?,
+            label: row.try_get("", "label")?,
+            ratio: row.try_get("", "ratio")?,
+        })
+    }
+}
+
+// ============================================================================
+// Diesel
+// ============================================================================
+
+#[derive(diesel::QueryableByName)]
+struct DieselFixedRow {
+    #[diesel(sql_type = diesel::sql_types::Int8)]
+    id: i64,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    label: String,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    ratio: f64,
+}
+
+impl Calibration for DieselBench {
+    async fn fixed_row(conn: &Self::Connection, sleep: Duration) -> Result<FixedRow, Self::Error> {
+        use diesel::RunQueryDsl;
+
+        let row: DieselFixedRow = diesel::sql_query(FIXED_ROW)
+            .bind::<diesel::sql_types::Double, _>(sleep.as_secs_f64())
+            .get_result(&mut *conn.lock().unwrap())?;
+        Ok(FixedRow {
+            id: row.id,
+            label: row.label,
+            ratio: row.ratio,
+        })
+    }
+}
@@ -0,0 +1,74 @@
+//! What each backend's API supports, as a matrix for the reports
+//!
+//! Several groups leave backends out, or run them in a roundabout way,
+//! because the library can't do what the group measures: tokio-postgres
+//! needs `&mut Client` for a transaction, SeaORM has no `COPY`, Diesel
+//! connections can't be shared. Every [`crate::DatabaseBenchmark`] states
+//! its [`Capabilities`], and [`matrix`] renders them as the table the
+//! `run-all` summary and `cargo run -- capabilities` print, so a missing
+//! series can be looked up rather than guessed at.
+
+use std::fmt::Write;
+
+/// What a backend's API offers, whether or not a benchmark uses it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Transactions begun from the shared connection or pool handle the
+    /// benchmarks get, without exclusive access to a connection
+    pub shared_transactions: bool,
+    /// Rows handed over as they arrive instead of collected first
+    pub streaming: bool,
+    /// `COPY FROM STDIN` and `COPY TO STDOUT`
+    pub copy: bool,
+    /// Receiving `LISTEN`/`NOTIFY` notifications
+    pub listen_notify: bool,
+    /// Statements prepared once per connection and reused
+    pub prepared_cache: bool,
+    /// Values exchanged in PostgreSQL's binary format rather than as text
+    pub binary_format: bool,
+}
+
+impl Capabilities {
+    /// Nothing at all, as for the in-memory backend, which isn't a driver
+    pub const NONE: Capabilities = Capabilities {
+        shared_transactions: false,
+        streaming: false,
+        copy: false,
+        listen_notify: false,
+        prepared_cache: false,
+        binary_format: false,
+    };
+
+    /// The features with their names, in the matrix's row order
+    pub fn features(&self) -> [(&'static str, bool); 6] {
+        [
+            ("Transactions on a shared handle", self.shared_transactions),
+            ("Streaming rows", self.streaming),
+            ("COPY", self.copy),
+            ("LISTEN/NOTIFY", self.listen_notify),
+            ("Prepared statement cache", self.prepared_cache),
+            ("Binary format", self.binary_format),
+        ]
+    }
+}
+
+/// A Markdown table with a row per feature and a column per backend
+pub fn matrix(backends: &[(&str, Capabilities)]) -> String {
+    let mut out = String::from("| Feature |");
+    for (name, _) in backends {
+        let _ = write!(out, " {name} |");
+    }
+    out.push_str("\n|---------|");
+    for _ in backends {
+        out.push_str(":---:|");
+    }
+    out.push('\n');
+    for (row, (feature, _)) in Capabilities::NONE.features().iter().enumerate() {
+        let _ = write!(out, "| {feature} |");
+        for (_, capabilities) in backends {
+            out.push_str(if capabilities.features()[row].1 { " yes |" } else { " no |" });
+        }
+        out.push('\n');
+    }
+    out
+}
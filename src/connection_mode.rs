@@ -0,0 +1,164 @@
+//! Whether a benchmark reuses one connection or checks one out of a pool per sample
+//!
+//! By default each backend's benchmark in a group opens one connection and
+//! runs every sample on it, which measures the library in steady state: its
+//! statements prepared, its type lookups cached, its session settled. A web
+//! handler that acquires a connection per request doesn't always get that
+//! connection. With [`ConnectionMode::PerSample`] each backend's benchmark
+//! opens a [`SamplePool`] of [`SAMPLE_POOL_SIZE`] connections once, and every
+//! sample checks one out before it is timed and returns it afterwards, so the
+//! samples run on the pool's connections in turn, as a handler's requests do.
+//!
+//! `BENCH_CONNECTION` chooses the mode for every group (`per_sample`) or per
+//! group (`select_user_by_id=per_sample,update_user=per_sample`), and the two
+//! combine, the group's own entry taking precedence.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+/// Connections in a [`ConnectionMode::PerSample`] benchmark's pool
+pub const SAMPLE_POOL_SIZE: usize = 4;
+
+/// How a backend's benchmark gets the connection its samples run on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConnectionMode {
+    /// One connection for the whole benchmark
+    #[default]
+    Reused,
+    /// A connection checked out of a pool, opened once per benchmark, for
+    /// each sample and returned to it after
+    PerSample,
+}
+
+impl ConnectionMode {
+    pub const ALL: [ConnectionMode; 2] = [ConnectionMode::Reused, ConnectionMode::PerSample];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ConnectionMode::Reused => "reused",
+            ConnectionMode::PerSample => "per_sample",
+        }
+    }
+}
+
+impl fmt::Display for ConnectionMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+impl FromStr for ConnectionMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ConnectionMode::ALL.into_iter().find(|mode| mode.name() == s.trim()).ok_or_else(|| {
+            let names: Vec<&str> = ConnectionMode::ALL.iter().map(ConnectionMode::name).collect();
+            format!("unknown connection mode {s:?}, expected one of {}", names.join(", "))
+        })
+    }
+}
+
+/// The connection mode of every group
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConnectionModes {
+    /// Mode of the groups without an entry of their own
+    pub default: ConnectionMode,
+    /// Modes of single groups, by group name
+    pub groups: Vec<(String, ConnectionMode)>,
+}
+
+impl ConnectionModes {
+    /// Modes from a comma-separated list of modes and `group=mode` entries,
+    /// such as `per_sample,insert_single_user=reused`; the last entry for a group wins
+    pub fn parse(list: &str) -> Result<Self, String> {
+        let mut modes = Self::default();
+        for entry in list.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+            match entry.split_once('=') {
+                Some((group, mode)) if !group.trim().is_empty() => {
+                    let mode = mode.parse()?;
+                    modes.groups.retain(|(name, _)| name != group.trim());
+                    modes.groups.push((group.trim().to_string(), mode));
+                }
+                Some(_) => return Err(format!("no group name in {entry:?}")),
+                None => modes.default = entry.parse()?,
+            }
+        }
+        Ok(modes)
+    }
+
+    /// The modes `BENCH_CONNECTION` lists, if set
+    pub fn from_env() -> Result<Self, String> {
+        match std::env::var("BENCH_CONNECTION") {
+            Ok(list) => Self::parse(&list).map_err(|e| format!("BENCH_CONNECTION: {e}")),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    /// The mode of the group named `group`
+    pub fn for_group(&self, group: &str) -> ConnectionMode {
+        self.groups
+            .iter()
+            .find(|(name, _)| name == group)
+            .map_or(self.default, |&(_, mode)| mode)
+    }
+}
+
+impl fmt::Display for ConnectionModes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.default)?;
+        for (group, mode) in &self.groups {
+            write!(f, ",{group}={mode}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Connections opened once and checked out one at a time, the least recently
+/// returned first, as a pool hands them to requests
+#[derive(Debug)]
+pub struct SamplePool<C> {
+    idle: Mutex<VecDeque<C>>,
+}
+
+impl<C> SamplePool<C> {
+    pub fn new(conns: impl IntoIterator<Item = C>) -> Self {
+        Self { idle: Mutex::new(conns.into_iter().collect()) }
+    }
+
+    /// The least recently returned connection, back in the pool when the
+    /// checkout is dropped
+    ///
+    /// # Panics
+    ///
+    /// If every connection is checked out.
+    pub fn checkout(&self) -> Checkout<'_, C> {
+        let conn = self.idle.lock().unwrap().pop_front().expect("every pooled connection is checked out");
+        Checkout { pool: self, conn: Some(conn) }
+    }
+}
+
+/// A connection checked out of a [`SamplePool`]
+#[derive(Debug)]
+pub struct Checkout<'a, C> {
+    pool: &'a SamplePool<C>,
+    conn: Option<C>,
+}
+
+impl<C> Deref for Checkout<'_, C> {
+    type Target = C;
+
+    fn deref(&self) -> &C {
+        self.conn.as_ref().expect("checked out until dropped")
+    }
+}
+
+impl<C> Drop for Checkout<'_, C> {
+    fn drop(&mut self) {
+        if let Some(conn) = self.conn.take() {
+            self.pool.idle.lock().unwrap().push_back(conn);
+        }
+    }
+}
@@ -0,0 +1,296 @@
+//! Chunked exports of every post: a `WITH HOLD` cursor vs keyset pagination
+//!
+//! A long-running export job reads a table in chunks, each in its own short
+//! transaction, so it neither holds a snapshot open for the whole export nor
+//! keeps the rows locked. [`Method::Cursor`] declares a `WITH HOLD` cursor
+//! outside any transaction block, which PostgreSQL materializes when the
+//! declaring statement commits, then `FETCH`es it chunk by chunk; the cursor
+//! outlives every transaction until it is closed. [`Method::Keyset`] instead
+//! runs a fresh `WHERE id > $last ORDER BY id LIMIT $chunk` query per chunk on
+//! the primary key index.
+//!
+//! A cursor lives in one session, so sqlx acquires one connection from its
+//! pool for a whole export. SeaORM only pins a connection inside a
+//! transaction, which would put the export back into a single one, so it is
+//! left out.
+
+use crate::bench_clorinde::{queries, ClorindeBench};
+use crate::bench_diesel::DieselBench;
+use crate::bench_sqlx::SqlxBench;
+use crate::bench_tokio_postgres::TokioPostgresBench;
+use crate::{DatabaseBenchmark, Post};
+use sqlx::PgPool;
+use std::fmt;
+use tokio_postgres::Client;
+use uuid::Uuid;
+
+/// The cursor [`Method::Cursor`] declares and closes on every export
+pub const CURSOR: &str = "posts_export";
+
+const DECLARE: &str = "DECLARE posts_export CURSOR WITH HOLD FOR
+     SELECT id, user_id, title, content, status, view_count, created_at, updated_at
+     FROM posts ORDER BY id";
+
+const CLOSE: &str = "CLOSE posts_export";
+
+const KEYSET_PAGE: &str = "SELECT id, user_id, title, content, status, view_count, created_at, updated_at
+     FROM posts WHERE id > $1 ORDER BY id LIMIT $2";
+
+/// `FETCH` takes its row count as a literal, not as a parameter
+fn fetch(chunk: i64) -> String {
+    format!("FETCH {chunk} FROM posts_export")
+}
+
+/// How an export reads the table chunk by chunk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    /// `FETCH` from a `WITH HOLD` cursor declared once per export
+    Cursor,
+    /// A new query per chunk, starting after the last key of the previous one
+    Keyset,
+}
+
+impl Method {
+    pub const ALL: [Method; 2] = [Method::Cursor, Method::Keyset];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Method::Cursor => "cursor",
+            Method::Keyset => "keyset",
+        }
+    }
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Every post by `id`, `chunk` rows per round trip, on the backend's own
+/// connection; each returns the number of posts exported
+#[allow(async_fn_in_trait)]
+pub trait ChunkedExport: DatabaseBenchmark {
+    /// Export through a `WITH HOLD` cursor, closing it at the end
+    async fn export_with_cursor(conn: &Self::Connection, chunk: i64, each: impl FnMut(Post)) -> Result<usize, Self::Error>;
+
+    /// Export by keyset pagination on `id`
+    async fn export_keyset(conn: &Self::Connection, chunk: i64, each: impl FnMut(Post)) -> Result<usize, Self::Error>;
+
+    /// Export with `method`
+    async fn export(
+        conn: &Self::Connection,
+        method: Method,
+        chunk: i64,
+        each: impl FnMut(Post),
+    ) -> Result<usize, Self::Error> {
+        match method {
+            Method::Cursor => Self::export_with_cursor(conn, chunk, each).await,
+            Method::Keyset => Self::export_keyset(conn, chunk, each).await,
+        }
+    }
+}
+
+// ============================================================================
+// tokio-postgres and clorinde
+// ============================================================================
+
+impl ChunkedExport for TokioPostgresBench {
+    async fn export_with_cursor(
+        client: &Client,
+        chunk: i64,
+        mut each: impl FnMut(Post),
+    ) -> Result<usize, tokio_postgres::Error> {
+        client.batch_execute(DECLARE).await?;
+        let fetch = client.prepare(&fetch(chunk)).await?;
+        let mut count = 0;
+        loop {
+            let rows = client.query(&fetch, &[]).await?;
+            for row in &rows {
+                each(Post::try_from(row)?);
+            }
+            count += rows.len();
+            if (rows.len() as i64) < chunk {
+                break;
+            }
+        }
+        client.batch_execute(CLOSE).await?;
+        Ok(count)
+    }
+
+    async fn export_keyset(
+        client: &Client,
+        chunk: i64,
+        mut each: impl FnMut(Post),
+    ) -> Result<usize, tokio_postgres::Error> {
+        let page = client.prepare(KEYSET_PAGE).await?;
+        let mut after = Uuid::nil();
+        let mut count = 0;
+        loop {
+            let rows = client.query(&page, &[&after, &chunk]).await?;
+            for row in &rows {
+                let post = Post::try_from(row)?;
+                after = post.id;
+                each(post);
+            }
+            count += rows.len();
+            if (rows.len() as i64) < chunk {
+                break;
+            }
+        }
+        Ok(count)
+    }
+}
+
+impl ChunkedExport for ClorindeBench {
+    /// `FETCH` takes its count as a literal, which clorinde can't generate a
+    /// statement for, so the fetches go through the underlying client
+    async fn export_with_cursor(
+        client: &Client,
+        chunk: i64,
+        mut each: impl FnMut(Post),
+    ) -> Result<usize, tokio_postgres::Error> {
+        queries::posts::declare_posts_export().bind(client).await?;
+        let mut count = 0;
+        loop {
+            let rows = client.query(&fetch(chunk), &[]).await?;
+            for row in &rows {
+                each(Post::try_from(row)?);
+            }
+            count += rows.len();
+            if (rows.len() as i64) < chunk {
+                break;
+            }
+        }
+        queries::posts::close_posts_export().bind(client).await?;
+        Ok(count)
+    }
+
+    async fn export_keyset(
+        client: &Client,
+        chunk: i64,
+        mut each: impl FnMut(Post),
+    ) -> Result<usize, tokio_postgres::Error> {
+        let mut after = Uuid::nil();
+        let mut count = 0;
+        loop {
+            let posts = queries::posts::select_posts_after().bind(client, &after, &chunk).all().await?;
+            count += posts.len();
+            let done = (posts.len() as i64) < chunk;
+            for post in posts {
+                after = post.id;
+                each(post.into());
+            }
+            if done {
+                break;
+            }
+        }
+        Ok(count)
+    }
+}
+
+// ============================================================================
+// sqlx
+// ============================================================================
+
+impl ChunkedExport for SqlxBench {
+    async fn export_with_cursor(pool: &PgPool, chunk: i64, mut each: impl FnMut(Post)) -> Result<usize, sqlx::Error> {
+        let mut conn = pool.acquire().await?;
+        sqlx::raw_sql(DECLARE).execute(&mut *conn).await?;
+        let fetch = fetch(chunk);
+        let mut count = 0;
+        loop {
+            let rows = sqlx::query(&fetch).fetch_all(&mut *conn).await?;
+            for row in &rows {
+                each(Post::try_from(row)?);
+            }
+            count += rows.len();
+            if (rows.len() as i64) < chunk {
+                break;
+            }
+        }
+        sqlx::raw_sql(CLOSE).execute(&mut *conn).await?;
+        Ok(count)
+    }
+
+    async fn export_keyset(pool: &PgPool, chunk: i64, mut each: impl FnMut(Post)) -> Result<usize, sqlx::Error> {
+        let mut after = Uuid::nil();
+        let mut count = 0;
+        loop {
+            let rows = sqlx::query(KEYSET_PAGE).bind(after).bind(chunk).fetch_all(pool).await?;
+            for row in &rows {
+                let post = Post::try_from(row)?;
+                after = post.id;
+                each(post);
+            }
+            count += rows.len();
+            if (rows.len() as i64) < chunk {
+                break;
+            }
+        }
+        Ok(count)
+    }
+}
+
+// ============================================================================
+// Diesel
+// ============================================================================
+
+impl ChunkedExport for DieselBench {
+    async fn export_with_cursor(
+        conn: &Self::Connection,
+        chunk: i64,
+        mut each: impl FnMut(Post),
+    ) -> Result<usize, Self::Error> {
+        use crate::bench_diesel::DieselPost;
+        use diesel::prelude::*;
+
+        let conn = &mut *conn.lock().unwrap();
+        diesel::sql_query(DECLARE).execute(conn)?;
+        let fetch = fetch(chunk);
+        let mut count = 0;
+        loop {
+            let posts: Vec<DieselPost> = diesel::sql_query(&fetch).load(conn)?;
+            count += posts.len();
+            let done = (posts.len() as i64) < chunk;
+            posts.into_iter().map(Post::from).for_each(&mut each);
+            if done {
+                break;
+            }
+        }
+        diesel::sql_query(CLOSE).execute(conn)?;
+        Ok(count)
+    }
+
+    async fn export_keyset(
+        conn: &Self::Connection,
+        chunk: i64,
+        mut each: impl FnMut(Post),
+    ) -> Result<usize, Self::Error> {
+        use crate::bench_diesel::schema::posts;
+        use crate::bench_diesel::DieselPost;
+        use diesel::prelude::*;
+
+        let conn = &mut *conn.lock().unwrap();
+        let mut after = Uuid::nil();
+        let mut count = 0;
+        loop {
+            let page: Vec<DieselPost> = posts::table
+                .filter(posts::id.gt(after))
+                .order(posts::id)
+                .limit(chunk)
+                .select(DieselPost::as_select())
+                .load(conn)?;
+            count += page.len();
+            let done = (page.len() as i64) < chunk;
+            for post in page {
+                after = post.id;
+                each(post.into());
+            }
+            if done {
+                break;
+            }
+        }
+        Ok(count)
+    }
+}
@@ -0,0 +1,528 @@
+//! Immediate vs deferred foreign key checks
+//!
+//! The `deferred` schema holds a copy of the seeded users, posts and comments
+//! whose foreign keys are `DEFERRABLE`. PostgreSQL checks a foreign key with a
+//! trigger on every inserted row: right after each statement when the
+//! constraint is immediate, or all together at `COMMIT` when it is deferred.
+//! [`DeferredConstraints`] inserts a user, its posts and comments on every
+//! post in one transaction, which starts with `SET CONSTRAINTS ALL IMMEDIATE`
+//! or `SET CONSTRAINTS ALL DEFERRED`, so both timings send the same
+//! statements and only when the checks run differs.
+//!
+//! [`create`] builds the copy from the current dataset, so the group and the
+//! tests need no seeding of their own.
+
+use crate::bench_clorinde::{queries, ClorindeBench};
+use crate::bench_diesel::DieselBench;
+use crate::bench_seaorm::SeaOrmBench;
+use crate::bench_sqlx::SqlxBench;
+use crate::bench_tokio_postgres::{transactional, TokioPostgresBench};
+use crate::{DatabaseBenchmark, NewComment, NewPost, NewUser};
+use sea_orm::{ActiveModelTrait, ActiveValue, ConnectionTrait, DatabaseConnection, DbErr, TransactionTrait};
+use sqlx::PgPool;
+use std::fmt;
+use tokio_postgres::Client;
+use uuid::Uuid;
+
+/// Schema holding the copy with deferrable foreign keys
+pub const SCHEMA: &str = "deferred";
+
+/// Posts inserted with each user
+pub const POSTS: usize = 5;
+
+/// Comments inserted on each of those posts
+pub const COMMENTS_PER_POST: usize = 5;
+
+/// When the foreign keys of the copy are checked within a transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstraintTiming {
+    /// After every statement, as for the constraints of `init.sql`
+    Immediate,
+    /// Once, at `COMMIT`
+    Deferred,
+}
+
+impl ConstraintTiming {
+    pub const ALL: [ConstraintTiming; 2] = [ConstraintTiming::Immediate, ConstraintTiming::Deferred];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ConstraintTiming::Immediate => "immediate",
+            ConstraintTiming::Deferred => "deferred",
+        }
+    }
+
+    /// The statement a transaction starts with to check its constraints this way
+    pub fn sql(&self) -> &'static str {
+        match self {
+            ConstraintTiming::Immediate => "SET CONSTRAINTS ALL IMMEDIATE",
+            ConstraintTiming::Deferred => "SET CONSTRAINTS ALL DEFERRED",
+        }
+    }
+}
+
+impl fmt::Display for ConstraintTiming {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+/// The three tables with the columns, indexes and checks of `init.sql`'s, and
+/// their foreign keys made `DEFERRABLE` (initially immediate)
+const CREATE: &str = "
+    DROP SCHEMA IF EXISTS deferred CASCADE;
+    CREATE SCHEMA deferred;
+
+    CREATE TABLE deferred.users (LIKE public.users INCLUDING ALL);
+    CREATE TABLE deferred.posts (
+        LIKE public.posts INCLUDING ALL,
+        FOREIGN KEY (user_id) REFERENCES deferred.users(id) ON DELETE CASCADE DEFERRABLE
+    );
+    CREATE TABLE deferred.comments (
+        LIKE public.comments INCLUDING ALL,
+        FOREIGN KEY (post_id) REFERENCES deferred.posts(id) ON DELETE CASCADE DEFERRABLE,
+        FOREIGN KEY (user_id) REFERENCES deferred.users(id) ON DELETE CASCADE DEFERRABLE
+    );
+
+    INSERT INTO deferred.users
+    SELECT id, username, email, first_name, last_name, age, created_at, updated_at
+    FROM public.users WHERE username NOT LIKE 'bench_user_%';
+
+    INSERT INTO deferred.posts
+    SELECT p.id, p.user_id, p.title, p.content, p.status, p.view_count, p.created_at, p.updated_at
+    FROM public.posts p JOIN deferred.users u ON u.id = p.user_id;
+
+    INSERT INTO deferred.comments
+    SELECT c.id, c.post_id, c.user_id, c.content, c.created_at
+    FROM public.comments c
+    JOIN deferred.posts p ON p.id = c.post_id
+    JOIN deferred.users u ON u.id = c.user_id;";
+
+/// Replace the [`SCHEMA`] schema by a fresh copy of the seeded rows,
+/// returning the number of users copied
+pub async fn create(client: &Client) -> Result<i64, tokio_postgres::Error> {
+    client.batch_execute(&format!("BEGIN; {CREATE} COMMIT;")).await?;
+    client
+        .batch_execute("ANALYZE deferred.users; ANALYZE deferred.posts; ANALYZE deferred.comments")
+        .await?;
+    Ok(client.query_one("SELECT COUNT(*) FROM deferred.users", &[]).await?.get(0))
+}
+
+/// Drop the [`SCHEMA`] schema
+pub async fn drop(client: &Client) -> Result<(), tokio_postgres::Error> {
+    client.batch_execute("DROP SCHEMA IF EXISTS deferred CASCADE").await
+}
+
+/// Remove the `bench_user_*` users the benchmarks inserted into the copy,
+/// and their posts and comments with them
+pub async fn cleanup(client: &Client) -> Result<(), tokio_postgres::Error> {
+    client
+        .execute("DELETE FROM deferred.users WHERE username LIKE 'bench_user_%'", &[])
+        .await?;
+    Ok(())
+}
+
+/// The posts and comments inserted with every user, with nil ids the inserts
+/// replace by the user's and each post's
+pub fn thread() -> (Vec<NewPost>, Vec<NewComment>) {
+    let posts = (0..POSTS).map(|i| NewPost::generate(Uuid::nil(), i)).collect();
+    let comments = (0..COMMENTS_PER_POST)
+        .map(|i| NewComment::generate(Uuid::nil(), Uuid::nil(), i))
+        .collect();
+    (posts, comments)
+}
+
+/// Inserting into the copy within one transaction, on the backend's own connection
+#[allow(async_fn_in_trait)]
+pub trait DeferredConstraints: DatabaseBenchmark {
+    /// Insert `user`, then each of `posts` by it, then each of `comments` on
+    /// every post by the same user, with the foreign keys checked as `timing`
+    /// says; returns the user's id
+    async fn insert_user_posts_comments(
+        conn: &Self::Connection,
+        user: &NewUser,
+        posts: &[NewPost],
+        comments: &[NewComment],
+        timing: ConstraintTiming,
+    ) -> Result<Uuid, Self::Error>;
+}
+
+// ============================================================================
+// tokio-postgres, sqlx and clorinde
+// ============================================================================
+
+const INSERT_USER: &str = "INSERT INTO deferred.users (username, email, first_name, last_name, age)
+     VALUES ($1, $2, $3, $4, $5)
+     RETURNING id";
+
+const INSERT_POST: &str = "INSERT INTO deferred.posts (user_id, title, content, status)
+     VALUES ($1, $2, $3, $4)
+     RETURNING id";
+
+const INSERT_COMMENT: &str = "INSERT INTO deferred.comments (post_id, user_id, content)
+     VALUES ($1, $2, $3)";
+
+impl DeferredConstraints for TokioPostgresBench {
+    async fn insert_user_posts_comments(
+        client: &Client,
+        user: &NewUser,
+        posts: &[NewPost],
+        comments: &[NewComment],
+        timing: ConstraintTiming,
+    ) -> Result<Uuid, tokio_postgres::Error> {
+        transactional(client, async {
+            client.batch_execute(timing.sql()).await?;
+            let user_id: Uuid = client
+                .query_one(
+                    INSERT_USER,
+                    &[&user.username, &user.email, &user.first_name, &user.last_name, &user.age],
+                )
+                .await?
+                .get(0);
+
+            for post in posts {
+                let post_id: Uuid = client
+                    .query_one(INSERT_POST, &[&user_id, &post.title, &post.content, &post.status])
+                    .await?
+                    .get(0);
+                for comment in comments {
+                    client.execute(INSERT_COMMENT, &[&post_id, &user_id, &comment.content]).await?;
+                }
+            }
+
+            Ok(user_id)
+        })
+        .await
+    }
+}
+
+impl DeferredConstraints for SqlxBench {
+    async fn insert_user_posts_comments(
+        pool: &PgPool,
+        user: &NewUser,
+        posts: &[NewPost],
+        comments: &[NewComment],
+        timing: ConstraintTiming,
+    ) -> Result<Uuid, sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        sqlx::query(timing.sql()).execute(&mut *tx).await?;
+
+        let user_id: Uuid = sqlx::query_scalar(INSERT_USER)
+            .bind(&user.username)
+            .bind(&user.email)
+            .bind(&user.first_name)
+            .bind(&user.last_name)
+            .bind(user.age)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        for post in posts {
+            let post_id: Uuid = sqlx::query_scalar(INSERT_POST)
+                .bind(user_id)
+                .bind(&post.title)
+                .bind(&post.content)
+                .bind(&post.status)
+                .fetch_one(&mut *tx)
+                .await?;
+            for comment in comments {
+                sqlx::query(INSERT_COMMENT)
+                    .bind(post_id)
+                    .bind(user_id)
+                    .bind(&comment.content)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+
+        tx.commit().await?;
+        Ok(user_id)
+    }
+}
+
+impl DeferredConstraints for ClorindeBench {
+    /// Clorinde generates no function for `SET CONSTRAINTS`, so that goes
+    /// through the underlying client
+    async fn insert_user_posts_comments(
+        client: &Client,
+        user: &NewUser,
+        posts: &[NewPost],
+        comments: &[NewComment],
+        timing: ConstraintTiming,
+    ) -> Result<Uuid, tokio_postgres::Error> {
+        transactional(client, async {
+            client.batch_execute(timing.sql()).await?;
+            let user_id = queries::deferred::insert_deferred_user()
+                .bind(client, &user.username, &user.email, &user.first_name, &user.last_name, &user.age)
+                .one()
+                .await?;
+
+            for post in posts {
+                let post_id = queries::deferred::insert_deferred_post()
+                    .bind(client, &user_id, &post.title, &post.content, &post.status)
+                    .one()
+                    .await?;
+                for comment in comments {
+                    queries::deferred::insert_deferred_comment()
+                        .bind(client, &post_id, &user_id, &comment.content)
+                        .await?;
+                }
+            }
+
+            Ok(user_id)
+        })
+        .await
+    }
+}
+
+// ============================================================================
+// SeaORM
+// ============================================================================
+
+pub mod deferred_users {
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+    #[sea_orm(schema_name = "deferred", table_name = "users")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: Uuid,
+        pub username: String,
+        pub email: String,
+        pub first_name: String,
+        pub last_name: String,
+        pub age: Option<i32>,
+        pub created_at: Option<DateTimeWithTimeZone>,
+        pub updated_at: Option<DateTimeWithTimeZone>,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+pub mod deferred_posts {
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+    #[sea_orm(schema_name = "deferred", table_name = "posts")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: Uuid,
+        pub user_id: Uuid,
+        pub title: String,
+        #[sea_orm(column_type = "Text")]
+        pub content: String,
+        pub status: String,
+        pub view_count: i32,
+        pub created_at: Option<DateTimeWithTimeZone>,
+        pub updated_at: Option<DateTimeWithTimeZone>,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+pub mod deferred_comments {
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+    #[sea_orm(schema_name = "deferred", table_name = "comments")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: Uuid,
+        pub post_id: Uuid,
+        pub user_id: Uuid,
+        #[sea_orm(column_type = "Text")]
+        pub content: String,
+        pub created_at: Option<DateTimeWithTimeZone>,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+impl DeferredConstraints for SeaOrmBench {
+    async fn insert_user_posts_comments(
+        db: &DatabaseConnection,
+        user: &NewUser,
+        posts: &[NewPost],
+        comments: &[NewComment],
+        timing: ConstraintTiming,
+    ) -> Result<Uuid, DbErr> {
+        let txn = db.begin().await?;
+        txn.execute_unprepared(timing.sql()).await?;
+
+        let user_id = Uuid::new_v4();
+        let user_model = deferred_users::ActiveModel {
+            id: ActiveValue::Set(user_id),
+            username: ActiveValue::Set(user.username.clone()),
+            email: ActiveValue::Set(user.email.clone()),
+            first_name: ActiveValue::Set(user.first_name.clone()),
+            last_name: ActiveValue::Set(user.last_name.clone()),
+            age: ActiveValue::Set(user.age),
+            created_at: ActiveValue::NotSet,
+            updated_at: ActiveValue::NotSet,
+        };
+        user_model.insert(&txn).await?;
+
+        for post in posts {
+            let post_id = Uuid::new_v4();
+            let post_model = deferred_posts::ActiveModel {
+                id: ActiveValue::Set(post_id),
+                user_id: ActiveValue::Set(user_id),
+                title: ActiveValue::Set(post.title.clone()),
+                content: ActiveValue::Set(post.content.clone()),
+                status: ActiveValue::Set(post.status.clone()),
+                view_count: ActiveValue::Set(0),
+                created_at: ActiveValue::NotSet,
+                updated_at: ActiveValue::NotSet,
+            };
+            post_model.insert(&txn).await?;
+
+            for comment in comments {
+                let comment_model = deferred_comments::ActiveModel {
+                    id: ActiveValue::Set(Uuid::new_v4()),
+                    post_id: ActiveValue::Set(post_id),
+                    user_id: ActiveValue::Set(user_id),
+                    content: ActiveValue::Set(comment.content.clone()),
+                    created_at: ActiveValue::NotSet,
+                };
+                comment_model.insert(&txn).await?;
+            }
+        }
+
+        txn.commit().await?;
+        Ok(user_id)
+    }
+}
+
+// ============================================================================
+// Diesel
+// ============================================================================
+
+pub mod schema {
+    diesel::table! {
+        deferred.users (id) {
+            id -> Uuid,
+            username -> Varchar,
+            email -> Varchar,
+            first_name -> Varchar,
+            last_name -> Varchar,
+            age -> Nullable<Int4>,
+            created_at -> Nullable<Timestamptz>,
+            updated_at -> Nullable<Timestamptz>,
+        }
+    }
+
+    diesel::table! {
+        deferred.posts (id) {
+            id -> Uuid,
+            user_id -> Uuid,
+            title -> Varchar,
+            content -> Text,
+            status -> Varchar,
+            view_count -> Int4,
+            created_at -> Nullable<Timestamptz>,
+            updated_at -> Nullable<Timestamptz>,
+        }
+    }
+
+    diesel::table! {
+        deferred.comments (id) {
+            id -> Uuid,
+            post_id -> Uuid,
+            user_id -> Uuid,
+            content -> Text,
+            created_at -> Nullable<Timestamptz>,
+        }
+    }
+
+    diesel::allow_tables_to_appear_in_same_query!(users, posts, comments);
+}
+
+mod diesel_models {
+    use super::schema::{comments, posts, users};
+    use diesel::prelude::*;
+
+    #[derive(Insertable)]
+    #[diesel(table_name = users)]
+    pub struct DieselNewUser<'a> {
+        pub username: &'a str,
+        pub email: &'a str,
+        pub first_name: &'a str,
+        pub last_name: &'a str,
+        pub age: Option<i32>,
+    }
+
+    #[derive(Insertable)]
+    #[diesel(table_name = posts)]
+    pub struct DieselNewPost<'a> {
+        pub user_id: uuid::Uuid,
+        pub title: &'a str,
+        pub content: &'a str,
+        pub status: &'a str,
+    }
+
+    #[derive(Insertable)]
+    #[diesel(table_name = comments)]
+    pub struct DieselNewComment<'a> {
+        pub post_id: uuid::Uuid,
+        pub user_id: uuid::Uuid,
+        pub content: &'a str,
+    }
+}
+
+impl DeferredConstraints for DieselBench {
+    async fn insert_user_posts_comments(
+        conn: &Self::Connection,
+        user: &NewUser,
+        posts: &[NewPost],
+        comments: &[NewComment],
+        timing: ConstraintTiming,
+    ) -> Result<Uuid, Self::Error> {
+        use diesel::prelude::*;
+        use diesel_models::{DieselNewComment, DieselNewPost, DieselNewUser};
+        use schema::{comments as comments_table, posts as posts_table, users};
+
+        let mut conn = conn.lock().unwrap();
+        Ok(conn.transaction(|conn| {
+            diesel::sql_query(timing.sql()).execute(conn)?;
+            let new_user = DieselNewUser {
+                username: &user.username,
+                email: &user.email,
+                first_name: &user.first_name,
+                last_name: &user.last_name,
+                age: user.age,
+            };
+            let user_id: Uuid = diesel::insert_into(users::table)
+                .values(&new_user)
+                .returning(users::id)
+                .get_result(conn)?;
+
+            for post in posts {
+                let new_post = DieselNewPost {
+                    user_id,
+                    title: &post.title,
+                    content: &post.content,
+                    status: &post.status,
+                };
+                let post_id: Uuid = diesel::insert_into(posts_table::table)
+                    .values(&new_post)
+                    .returning(posts_table::id)
+                    .get_result(conn)?;
+
+                for comment in comments {
+                    let new_comment = DieselNewComment { post_id, user_id, content: &comment.content };
+                    diesel::insert_into(comments_table::table).values(&new_comment).execute(conn)?;
+                }
+            }
+
+            diesel::QueryResult::Ok(user_id)
+        })?)
+    }
+}
@@ -0,0 +1,265 @@
+//! Environment diagnosis behind `cargo run -- doctor`
+//!
+//! Checks what a benchmark run depends on before spending an hour on one:
+//! that every backend can connect and query, the server's version and the
+//! settings that move results (`shared_buffers`, `max_connections`, `jit`),
+//! the extensions the schema and the audit need, rows and dropped indexes left
+//! behind by an interrupted run, and the round-trip time to the server, which
+//! is the floor under every per-query latency measured.
+
+use crate::indexes;
+use crate::verify;
+use crate::DatabaseBenchmark;
+use std::fmt;
+use std::time::{Duration, Instant};
+use tokio_postgres::Client;
+
+/// Connections the largest concurrent benchmark group opens at once
+pub const BENCH_CONNECTIONS: i64 = 100;
+
+/// Oldest major version the benchmarks are run against
+pub const MIN_SERVER_VERSION: i64 = 13;
+
+/// `SELECT 1` round trips timed for the RTT estimate
+pub const RTT_SAMPLES: usize = 50;
+
+/// RTT above which the server is probably not local and per-query
+/// differences between the libraries are hidden in network latency
+pub const RTT_WARNING: Duration = Duration::from_millis(1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Status {
+    Ok,
+    /// Runs, but results may be skewed or some modes unavailable
+    Warning,
+    /// A run would fail
+    Failed,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Status::Ok => "ok",
+            Status::Warning => "warn",
+            Status::Failed => "FAIL",
+        })
+    }
+}
+
+/// One diagnosed aspect of the environment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    pub name: String,
+    pub status: Status,
+    pub detail: String,
+}
+
+impl Check {
+    fn new(name: impl Into<String>, status: Status, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{:<4}] {}: {}", self.status, self.name, self.detail)
+    }
+}
+
+/// Counts of each status, for the readiness summary
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub ok: usize,
+    pub warnings: usize,
+    pub failed: usize,
+}
+
+impl Summary {
+    pub fn of(checks: &[Check]) -> Self {
+        let count = |status| checks.iter().filter(|c| c.status == status).count();
+        Self {
+            ok: count(Status::Ok),
+            warnings: count(Status::Warning),
+            failed: count(Status::Failed),
+        }
+    }
+
+    pub fn ready(&self) -> bool {
+        self.failed == 0
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verdict = match (self.failed, self.warnings) {
+            (0, 0) => "ready",
+            (0, _) => "ready, with warnings",
+            _ => "not ready",
+        };
+        write!(f, "{}: {} ok, {} warning(s), {} failed", verdict, self.ok, self.warnings, self.failed)
+    }
+}
+
+/// Whether `backend` (`B`) connects and answers a query, and how long that took
+pub async fn connectivity<B: DatabaseBenchmark>(backend: &str) -> Check {
+    let name = format!("{backend} connects");
+    let start = Instant::now();
+    let conn = match B::connect().await {
+        Ok(conn) => conn,
+        Err(e) => return Check::new(name, Status::Failed, format!("{e:?}")),
+    };
+    match B::select_users_limit(&conn, 1).await {
+        Ok(_) => Check::new(name, Status::Ok, format!("connected and queried in {:.1} ms", ms(start.elapsed()))),
+        Err(e) => Check::new(name, Status::Failed, format!("connected, but a query failed: {e:?}")),
+    }
+}
+
+/// The server version and the settings that change what the benchmarks measure
+pub async fn server_settings(client: &Client) -> Result<Vec<Check>, tokio_postgres::Error> {
+    let setting = |name: &'static str| async move {
+        let row = client.query_one("SELECT current_setting($1)", &[&name]).await?;
+        Ok::<String, tokio_postgres::Error>(row.get(0))
+    };
+
+    let version: String = setting("server_version").await?;
+    let version_num: i64 = setting("server_version_num").await?.parse().unwrap_or(0);
+    let major = version_num / 10_000;
+    let mut checks = vec![if major >= MIN_SERVER_VERSION {
+        Check::new("server version", Status::Ok, format!("PostgreSQL {version}"))
+    } else {
+        Check::new(
+            "server version",
+            Status::Warning,
+            format!("PostgreSQL {version}, older than the {MIN_SERVER_VERSION} the results are compared on"),
+        )
+    }];
+
+    checks.push(Check::new("shared_buffers", Status::Ok, setting("shared_buffers").await?));
+
+    let max_connections: i64 = setting("max_connections").await?.parse().unwrap_or(0);
+    let reserved: i64 = setting("superuser_reserved_connections").await?.parse().unwrap_or(0);
+    let available = max_connections - reserved;
+    checks.push(if available > BENCH_CONNECTIONS {
+        Check::new("max_connections", Status::Ok, format!("{max_connections} ({available} for the benchmark user)"))
+    } else {
+        Check::new(
+            "max_connections",
+            Status::Failed,
+            format!(
+                "{max_connections} ({available} for the benchmark user); the concurrent groups open {BENCH_CONNECTIONS} \
+                 connections, so raise it above {}",
+                BENCH_CONNECTIONS + reserved
+            ),
+        )
+    });
+
+    let jit = setting("jit").await?;
+    checks.push(if jit == "on" {
+        Check::new(
+            "jit",
+            Status::Warning,
+            "on; compiling the aggregate and join plans adds latency to some iterations (set jit = off)",
+        )
+    } else {
+        Check::new("jit", Status::Ok, jit)
+    });
+    Ok(checks)
+}
+
+/// The extensions `init.sql` creates, and `pg_stat_statements` for the audit
+pub async fn extensions(client: &Client) -> Result<Vec<Check>, tokio_postgres::Error> {
+    let installed: Vec<String> = client
+        .query("SELECT extname FROM pg_extension", &[])
+        .await?
+        .iter()
+        .map(|row| row.get(0))
+        .collect();
+    let preloaded: String = client
+        .query_one("SELECT current_setting('shared_preload_libraries')", &[])
+        .await?
+        .get(0);
+
+    let mut checks: Vec<Check> = ["uuid-ossp", "pg_trgm", "pg_prewarm", "citext"]
+        .into_iter()
+        .map(|name| {
+            if installed.iter().any(|e| e == name) {
+                Check::new(name, Status::Ok, "installed")
+            } else {
+                Check::new(name, Status::Failed, "missing; re-create the database from init.sql")
+            }
+        })
+        .collect();
+    checks.push(if preloaded.split(',').any(|l| l.trim() == "pg_stat_statements") {
+        Check::new("pg_stat_statements", Status::Ok, "preloaded, `cargo run -- audit` is available")
+    } else {
+        Check::new(
+            "pg_stat_statements",
+            Status::Warning,
+            "not in shared_preload_libraries; `cargo run -- audit` needs it",
+        )
+    });
+    Ok(checks)
+}
+
+/// Rows an interrupted run left behind, which later groups would run against
+pub async fn leftover_rows(client: &Client) -> Result<Check, tokio_postgres::Error> {
+    let leftover = verify::leftover_rows(client).await?;
+    Ok(if leftover.is_empty() {
+        Check::new("benchmark rows", Status::Ok, "none left over")
+    } else {
+        Check::new("benchmark rows", Status::Warning, format!("{leftover} left over; the next run cleans them up first"))
+    })
+}
+
+/// Secondary indexes an interrupted index comparison left dropped, which would
+/// slow every later read down to the unindexed plans
+pub async fn secondary_indexes(client: &Client) -> Result<Check, tokio_postgres::Error> {
+    let missing = indexes::missing(client).await?;
+    Ok(if missing.is_empty() {
+        Check::new("secondary indexes", Status::Ok, "all present")
+    } else {
+        Check::new(
+            "secondary indexes",
+            Status::Warning,
+            format!("{} missing; the next index comparison restores them first", missing.join(", ")),
+        )
+    })
+}
+
+/// The median of [`RTT_SAMPLES`] `SELECT 1` round trips
+pub async fn round_trip_time(client: &Client) -> Result<Duration, tokio_postgres::Error> {
+    let statement = client.prepare("SELECT 1").await?;
+    let mut samples = Vec::with_capacity(RTT_SAMPLES);
+    for _ in 0..RTT_SAMPLES {
+        let start = Instant::now();
+        client.query_one(&statement, &[]).await?;
+        samples.push(start.elapsed());
+    }
+    samples.sort();
+    Ok(samples[samples.len() / 2])
+}
+
+/// The RTT estimate as a check, warning when it would hide per-query differences
+pub fn rtt_check(rtt: Duration) -> Check {
+    if rtt > RTT_WARNING {
+        Check::new(
+            "round trip",
+            Status::Warning,
+            format!(
+                "{:.3} ms median; over {} ms, per-query differences between the libraries are small next to it",
+                ms(rtt),
+                ms(RTT_WARNING)
+            ),
+        )
+    } else {
+        Check::new("round trip", Status::Ok, format!("{:.3} ms median of {RTT_SAMPLES} `SELECT 1`", ms(rtt)))
+    }
+}
+
+fn ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
@@ -0,0 +1,401 @@
+//! Case-insensitive email lookup: a `citext` column against a `LOWER(email)`
+//! expression index
+//!
+//! The `email_lookup` schema holds two copies of the seeded users. In
+//! `citext_users` the email column is `citext`, whose `=` ignores case, so a
+//! plain `WHERE email = $1` finds `Alice@Example.com` for `ALICE@EXAMPLE.COM`
+//! through the unique index the column already has. `lower_users` keeps the
+//! `VARCHAR` column and an index on `LOWER(email)`, which a query only uses
+//! when it writes `LOWER(email) = LOWER($1)`.
+//!
+//! `citext` is an extension type, with an OID that differs between databases,
+//! and each library has to be told about it:
+//!
+//! - tokio-postgres and clorinde send `&str` for a `citext` parameter, which
+//!   `postgres-types` accepts by the type's name
+//! - sqlx binds `&str` as `TEXT`, and `citext = text` is resolved as a `text`
+//!   comparison that is case-sensitive, so the email is bound as `PgCiText`
+//! - SeaORM casts the value with `save_as = "citext"` on the entity's column
+//! - diesel declares the column `Citext` in its `table!`, and has no `lower()`
+//!   for text, so one is declared here
+//!
+//! [`create`] builds both copies from the current dataset, so the group and
+//! the tests need no seeding of their own.
+
+use crate::bench_clorinde::{queries, ClorindeBench};
+use crate::bench_diesel::DieselBench;
+use crate::bench_seaorm::SeaOrmBench;
+use crate::bench_sqlx::SqlxBench;
+use crate::bench_tokio_postgres::TokioPostgresBench;
+use crate::{DatabaseBenchmark, User};
+use sea_orm::sea_query::{Expr, Func};
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter};
+use sqlx::postgres::types::PgCiText;
+use sqlx::PgPool;
+use tokio_postgres::Client;
+
+/// Schema holding both copies of `users`
+pub const SCHEMA: &str = "email_lookup";
+
+/// Both copies with the indexes and constraints of `users`; `citext_users`
+/// keeps its unique index on `email`, rebuilt for `citext` by the type change
+const CREATE: &str = "
+    CREATE EXTENSION IF NOT EXISTS citext;
+    DROP SCHEMA IF EXISTS email_lookup CASCADE;
+    CREATE SCHEMA email_lookup;
+
+    CREATE TABLE email_lookup.citext_users (LIKE public.users INCLUDING ALL);
+    ALTER TABLE email_lookup.citext_users ALTER COLUMN email TYPE CITEXT;
+
+    CREATE TABLE email_lookup.lower_users (LIKE public.users INCLUDING ALL);
+    CREATE UNIQUE INDEX ON email_lookup.lower_users (LOWER(email));
+
+    INSERT INTO email_lookup.citext_users
+    SELECT id, username, email, first_name, last_name, age, created_at, updated_at
+    FROM public.users WHERE username NOT LIKE 'bench_user_%';
+
+    INSERT INTO email_lookup.lower_users
+    SELECT id, username, email, first_name, last_name, age, created_at, updated_at
+    FROM public.users WHERE username NOT LIKE 'bench_user_%';";
+
+/// Replace the [`SCHEMA`] schema by fresh copies of the seeded users,
+/// returning the number of users copied into each
+pub async fn create(client: &Client) -> Result<i64, tokio_postgres::Error> {
+    client.batch_execute(&format!("BEGIN; {CREATE} COMMIT;")).await?;
+    client
+        .batch_execute("ANALYZE email_lookup.citext_users; ANALYZE email_lookup.lower_users")
+        .await?;
+    Ok(client.query_one("SELECT COUNT(*) FROM email_lookup.citext_users", &[]).await?.get(0))
+}
+
+/// Drop the [`SCHEMA`] schema
+pub async fn drop(client: &Client) -> Result<(), tokio_postgres::Error> {
+    client.batch_execute("DROP SCHEMA IF EXISTS email_lookup CASCADE").await
+}
+
+/// Emails of the newest `limit` users of the copies, upper-cased so that only
+/// a case-insensitive comparison finds them
+pub async fn sample_emails(client: &Client, limit: i64) -> Result<Vec<String>, tokio_postgres::Error> {
+    let rows = client
+        .query(
+            "SELECT UPPER(email) FROM email_lookup.lower_users ORDER BY created_at DESC LIMIT $1",
+            &[&limit],
+        )
+        .await?;
+    Ok(rows.iter().map(|row| row.get(0)).collect())
+}
+
+/// Looking a user up by email, whatever its case, on the backend's own connection
+#[allow(async_fn_in_trait)]
+pub trait EmailLookup: DatabaseBenchmark {
+    /// The user of `citext_users` with `email`, compared as `citext`
+    async fn select_user_by_email(conn: &Self::Connection, email: &str) -> Result<Option<User>, Self::Error>;
+
+    /// The user of `lower_users` whose lower-cased email is `email`'s
+    async fn select_user_by_lower_email(conn: &Self::Connection, email: &str) -> Result<Option<User>, Self::Error>;
+}
+
+// ============================================================================
+// tokio-postgres, sqlx and clorinde
+// ============================================================================
+
+const SELECT_BY_CITEXT: &str = "SELECT id, username, email, first_name, last_name, age, created_at, updated_at
+     FROM email_lookup.citext_users WHERE email = $1";
+
+const SELECT_BY_LOWER: &str = "SELECT id, username, email, first_name, last_name, age, created_at, updated_at
+     FROM email_lookup.lower_users WHERE LOWER(email) = LOWER($1)";
+
+impl EmailLookup for TokioPostgresBench {
+    async fn select_user_by_email(client: &Client, email: &str) -> Result<Option<User>, tokio_postgres::Error> {
+        let row = client.query_opt(SELECT_BY_CITEXT, &[&email]).await?;
+        row.as_ref().map(User::try_from).transpose()
+    }
+
+    async fn select_user_by_lower_email(client: &Client, email: &str) -> Result<Option<User>, tokio_postgres::Error> {
+        let row = client.query_opt(SELECT_BY_LOWER, &[&email]).await?;
+        row.as_ref().map(User::try_from).transpose()
+    }
+}
+
+impl EmailLookup for SqlxBench {
+    async fn select_user_by_email(pool: &PgPool, email: &str) -> Result<Option<User>, sqlx::Error> {
+        let row = sqlx::query(SELECT_BY_CITEXT)
+            .bind(PgCiText(email.to_string()))
+            .fetch_optional(pool)
+            .await?;
+        row.as_ref().map(User::try_from).transpose()
+    }
+
+    async fn select_user_by_lower_email(pool: &PgPool, email: &str) -> Result<Option<User>, sqlx::Error> {
+        let row = sqlx::query(SELECT_BY_LOWER).bind(email).fetch_optional(pool).await?;
+        row.as_ref().map(User::try_from).transpose()
+    }
+}
+
+/// `select_user_by_email` as sqlx runs it with the email bound as `&str`,
+/// which misses users whose email differs in case
+pub async fn select_user_by_text_email(pool: &PgPool, email: &str) -> Result<Option<User>, sqlx::Error> {
+    let row = sqlx::query(SELECT_BY_CITEXT).bind(email).fetch_optional(pool).await?;
+    row.as_ref().map(User::try_from).transpose()
+}
+
+macro_rules! clorinde_user {
+    ($user:ty) => {
+        impl From<$user> for User {
+            fn from(u: $user) -> Self {
+                Self {
+                    id: u.id,
+                    username: u.username,
+                    email: u.email,
+                    first_name: u.first_name,
+                    last_name: u.last_name,
+                    age: u.age,
+                    created_at: u.created_at,
+                    updated_at: u.updated_at,
+                }
+            }
+        }
+    };
+}
+
+clorinde_user!(queries::email_lookup::CitextUser);
+clorinde_user!(queries::email_lookup::LowerEmailUser);
+
+impl EmailLookup for ClorindeBench {
+    async fn select_user_by_email(client: &Client, email: &str) -> Result<Option<User>, tokio_postgres::Error> {
+        let user = queries::email_lookup::select_user_by_citext_email()
+            .bind(client, &email)
+            .opt()
+            .await?;
+        Ok(user.map(Into::into))
+    }
+
+    async fn select_user_by_lower_email(client: &Client, email: &str) -> Result<Option<User>, tokio_postgres::Error> {
+        let user = queries::email_lookup::select_user_by_lower_email()
+            .bind(client, &email)
+            .opt()
+            .await?;
+        Ok(user.map(Into::into))
+    }
+}
+
+// ============================================================================
+// SeaORM
+// ============================================================================
+
+pub mod citext_users {
+    use sea_orm::entity::prelude::*;
+
+    /// `save_as` casts every value compared with or written to `email` to
+    /// `citext`; without it SeaORM binds a `TEXT` and the match is case-sensitive
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+    #[sea_orm(schema_name = "email_lookup", table_name = "citext_users")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: Uuid,
+        pub username: String,
+        #[sea_orm(save_as = "citext")]
+        pub email: String,
+        pub first_name: String,
+        pub last_name: String,
+        pub age: Option<i32>,
+        pub created_at: Option<DateTimeWithTimeZone>,
+        pub updated_at: Option<DateTimeWithTimeZone>,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+pub mod lower_users {
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+    #[sea_orm(schema_name = "email_lookup", table_name = "lower_users")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: Uuid,
+        pub username: String,
+        pub email: String,
+        pub first_name: String,
+        pub last_name: String,
+        pub age: Option<i32>,
+        pub created_at: Option<DateTimeWithTimeZone>,
+        pub updated_at: Option<DateTimeWithTimeZone>,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+macro_rules! sea_orm_user {
+    ($model:ty) => {
+        impl From<$model> for User {
+            fn from(u: $model) -> Self {
+                Self {
+                    id: u.id,
+                    username: u.username,
+                    email: u.email,
+                    first_name: u.first_name,
+                    last_name: u.last_name,
+                    age: u.age,
+                    created_at: u.created_at.map(Into::into),
+                    updated_at: u.updated_at.map(Into::into),
+                }
+            }
+        }
+    };
+}
+
+sea_orm_user!(citext_users::Model);
+sea_orm_user!(lower_users::Model);
+
+impl EmailLookup for SeaOrmBench {
+    async fn select_user_by_email(db: &DatabaseConnection, email: &str) -> Result<Option<User>, DbErr> {
+        let user = citext_users::Entity::find()
+            .filter(citext_users::Column::Email.eq(email))
+            .one(db)
+            .await?;
+        Ok(user.map(Into::into))
+    }
+
+    async fn select_user_by_lower_email(db: &DatabaseConnection, email: &str) -> Result<Option<User>, DbErr> {
+        let user = lower_users::Entity::find()
+            .filter(Expr::expr(Func::lower(Expr::col(lower_users::Column::Email))).eq(Func::lower(email)))
+            .one(db)
+            .await?;
+        Ok(user.map(Into::into))
+    }
+}
+
+// ============================================================================
+// Diesel
+// ============================================================================
+
+pub mod schema {
+    diesel::table! {
+        use diesel::sql_types::*;
+
+        email_lookup.citext_users (id) {
+            id -> Uuid,
+            username -> Varchar,
+            email -> Citext,
+            first_name -> Varchar,
+            last_name -> Varchar,
+            age -> Nullable<Int4>,
+            created_at -> Nullable<Timestamptz>,
+            updated_at -> Nullable<Timestamptz>,
+        }
+    }
+
+    diesel::table! {
+        email_lookup.lower_users (id) {
+            id -> Uuid,
+            username -> Varchar,
+            email -> Varchar,
+            first_name -> Varchar,
+            last_name -> Varchar,
+            age -> Nullable<Int4>,
+            created_at -> Nullable<Timestamptz>,
+            updated_at -> Nullable<Timestamptz>,
+        }
+    }
+
+    diesel::allow_tables_to_appear_in_same_query!(citext_users, lower_users);
+}
+
+mod diesel_sql {
+    use diesel::sql_types::Text;
+
+    diesel::define_sql_function! {
+        /// PostgreSQL's `lower(text)`; diesel's own `lower` is the range function
+        fn lower(x: Text) -> Text;
+    }
+}
+
+mod diesel_models {
+    use super::schema::{citext_users, lower_users};
+    use crate::User;
+    use diesel::prelude::*;
+
+    #[derive(Queryable, Selectable)]
+    #[diesel(table_name = citext_users)]
+    pub struct DieselCitextUser {
+        pub id: uuid::Uuid,
+        pub username: String,
+        pub email: String,
+        pub first_name: String,
+        pub last_name: String,
+        pub age: Option<i32>,
+        pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+        pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    }
+
+    #[derive(Queryable, Selectable)]
+    #[diesel(table_name = lower_users)]
+    pub struct DieselLowerUser {
+        pub id: uuid::Uuid,
+        pub username: String,
+        pub email: String,
+        pub first_name: String,
+        pub last_name: String,
+        pub age: Option<i32>,
+        pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+        pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    }
+
+    macro_rules! diesel_user {
+        ($model:ty) => {
+            impl From<$model> for User {
+                fn from(u: $model) -> Self {
+                    Self {
+                        id: u.id,
+                        username: u.username,
+                        email: u.email,
+                        first_name: u.first_name,
+                        last_name: u.last_name,
+                        age: u.age,
+                        created_at: u.created_at,
+                        updated_at: u.updated_at,
+                    }
+                }
+            }
+        };
+    }
+
+    diesel_user!(DieselCitextUser);
+    diesel_user!(DieselLowerUser);
+}
+
+impl EmailLookup for DieselBench {
+    async fn select_user_by_email(conn: &Self::Connection, email: &str) -> Result<Option<User>, Self::Error> {
+        use diesel::prelude::*;
+        use diesel_models::DieselCitextUser;
+        use schema::citext_users;
+
+        let user = citext_users::table
+            .filter(citext_users::email.eq(email))
+            .select(DieselCitextUser::as_select())
+            .first(&mut *conn.lock().unwrap())
+            .optional()?;
+        Ok(user.map(Into::into))
+    }
+
+    async fn select_user_by_lower_email(conn: &Self::Connection, email: &str) -> Result<Option<User>, Self::Error> {
+        use diesel::prelude::*;
+        use diesel_models::DieselLowerUser;
+        use diesel_sql::lower;
+        use schema::lower_users;
+
+        let user = lower_users::table
+            .filter(lower(lower_users::email).eq(lower(email)))
+            .select(DieselLowerUser::as_select())
+            .first(&mut *conn.lock().unwrap())
+            .optional()?;
+        Ok(user.map(Into::into))
+    }
+}
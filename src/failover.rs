@@ -0,0 +1,50 @@
+//! Multi-host connection strings and failover
+//!
+//! tokio-postgres (and so clorinde and the deadpool pool) and libpq (and so
+//! diesel) accept several hosts in one connection string, either as
+//! `postgres://user@a:5432,b:5433/db` or `host=a,b port=5432,5433`, and try
+//! them in order. With `target_session_attrs=read-write` they also skip hosts
+//! that only accept reads. sqlx, and so SeaORM, take a single host.
+//!
+//! The failover scenario needs a primary that can go away while the database
+//! keeps running, so the primary is a [`crate::proxy::TcpProxy`] in front of
+//! the server and the server itself is the next host in the list.
+
+use crate::with_param;
+
+/// Backends whose driver only takes a single host
+pub const SINGLE_HOST_BACKENDS: &[&str] = &["sqlx", "sea_orm"];
+
+/// The TCP host and port `url` points at, or `None` for a Unix socket
+pub fn server_address(url: &str) -> Result<Option<(String, u16)>, tokio_postgres::Error> {
+    let config: tokio_postgres::Config = url.parse()?;
+    let port = config.get_ports().first().copied().unwrap_or(5432);
+    Ok(match config.get_hosts().first() {
+        Some(tokio_postgres::config::Host::Tcp(host)) => Some((host.clone(), port)),
+        _ => None,
+    })
+}
+
+/// `url` pointing at `hosts` in order, replacing its host and port
+pub fn with_hosts(url: &str, hosts: &[(String, u16)]) -> String {
+    let Some((scheme, rest)) = url.split_once("://") else {
+        let names: Vec<&str> = hosts.iter().map(|(host, _)| host.as_str()).collect();
+        let ports: Vec<String> = hosts.iter().map(|(_, port)| port.to_string()).collect();
+        return with_param(&with_param(url, "host", &names.join(",")), "port", &ports.join(","));
+    };
+
+    let authority_end = rest.find(['/', '?']).unwrap_or(rest.len());
+    let (authority, path) = rest.split_at(authority_end);
+    let userinfo = match authority.rfind('@') {
+        Some(at) => &authority[..=at],
+        None => "",
+    };
+    let hosts: Vec<String> = hosts.iter().map(|(host, port)| format!("{}:{}", host, port)).collect();
+    format!("{}://{}{}{}", scheme, userinfo, hosts.join(","), path)
+}
+
+/// `url` trying `primary` first, then `standby`, and only settling on a host that accepts writes
+pub fn failover_url(url: &str, primary: &(String, u16), standby: &(String, u16)) -> String {
+    let url = with_hosts(url, &[primary.clone(), standby.clone()]);
+    with_param(&url, "target_session_attrs", "read-write")
+}
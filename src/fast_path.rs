@@ -0,0 +1,85 @@
+//! Lookups returning zero rows or exactly one
+//!
+//! Most groups read rows that exist, but real apps miss as often as they hit:
+//! a cache-miss lookup by id, a filter nothing matches. A point lookup goes
+//! through each library's optional-row path (`query_opt`, `fetch_optional`,
+//! `one`, `.optional()`) and a list query through its collecting path, and
+//! what each does for an empty result differs. [`FastPath`] is the four
+//! combinations, every one on the same indexes so the server's share stays
+//! close.
+
+use crate::DatabaseBenchmark;
+use uuid::Uuid;
+
+/// Ages the list lookups filter on that no user has; the column's check
+/// constraint stops at 150
+pub const EMPTY_AGE_RANGE: (i32, i32) = (151, 200);
+
+/// Ages every seeded user has
+pub const FULL_AGE_RANGE: (i32, i32) = (0, 150);
+
+/// One lookup and how many rows it returns
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FastPath {
+    /// `select_user_by_id` on an existing user
+    PointHit,
+    /// `select_user_by_id` on an id no user has
+    PointMiss,
+    /// `select_users_filtered` on [`EMPTY_AGE_RANGE`]
+    ListEmpty,
+    /// `select_users_filtered` on [`FULL_AGE_RANGE`] with `LIMIT 1`
+    ListOne,
+}
+
+impl FastPath {
+    pub const ALL: [FastPath; 4] = [FastPath::PointHit, FastPath::PointMiss, FastPath::ListEmpty, FastPath::ListOne];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            FastPath::PointHit => "point_hit",
+            FastPath::PointMiss => "point_miss",
+            FastPath::ListEmpty => "list_empty",
+            FastPath::ListOne => "list_one",
+        }
+    }
+
+    /// Rows the lookup returns
+    pub fn expected_rows(self) -> usize {
+        match self {
+            FastPath::PointHit | FastPath::ListOne => 1,
+            FastPath::PointMiss | FastPath::ListEmpty => 0,
+        }
+    }
+
+    /// Run the lookup, with the `iteration`th of `hits` or `misses` for the
+    /// point lookups, and return how many rows it returned
+    pub async fn run<B: DatabaseBenchmark>(
+        self,
+        conn: &B::Connection,
+        hits: &[Uuid],
+        misses: &[Uuid],
+        iteration: usize,
+    ) -> Result<usize, B::Error> {
+        match self {
+            FastPath::PointHit => {
+                B::select_user_by_id(conn, hits[iteration % hits.len()]).await.map(|u| u.iter().count())
+            }
+            FastPath::PointMiss => {
+                B::select_user_by_id(conn, misses[iteration % misses.len()]).await.map(|u| u.iter().count())
+            }
+            FastPath::ListEmpty => {
+                let (min, max) = EMPTY_AGE_RANGE;
+                B::select_users_filtered(conn, min, max, 10).await.map(|users| users.len())
+            }
+            FastPath::ListOne => {
+                let (min, max) = FULL_AGE_RANGE;
+                B::select_users_filtered(conn, min, max, 1).await.map(|users| users.len())
+            }
+        }
+    }
+}
+
+/// `count` random ids, which no user has
+pub fn missing_ids(count: usize) -> Vec<Uuid> {
+    (0..count).map(|_| Uuid::new_v4()).collect()
+}
@@ -0,0 +1,412 @@
+//! A `GENERATED ALWAYS AS` column on a copy of `users`
+//!
+//! The `generated` schema holds a copy of the seeded users with a stored
+//! `full_name` computed by the server from `first_name` and `last_name`, and
+//! a B-tree index on it. Writing to a generated column is an error, so every
+//! backend's model for the copy has to leave it out of inserts and read it
+//! back like any other column: [`GeneratedColumn`] inserts users, reads them
+//! and filters on `full_name`, and the `generated_column_*` groups set that
+//! against the plain `users` table.
+//!
+//! [`create`] builds the copy from the current dataset, so the groups and
+//! tests need no seeding of their own.
+
+use crate::bench_clorinde::{queries, ClorindeBench};
+use crate::bench_diesel::DieselBench;
+use crate::bench_seaorm::SeaOrmBench;
+use crate::bench_sqlx::SqlxBench;
+use crate::bench_tokio_postgres::TokioPostgresBench;
+use crate::{DatabaseBenchmark, NewUser};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect,
+};
+use sqlx::PgPool;
+use tokio_postgres::Client;
+use uuid::Uuid;
+
+/// Schema holding the copy of `users` with `full_name`
+pub const SCHEMA: &str = "generated";
+
+/// The copy, `full_name` last, with the indexes and constraints of `users`
+const CREATE: &str = "
+    DROP SCHEMA IF EXISTS generated CASCADE;
+    CREATE SCHEMA generated;
+
+    CREATE TABLE generated.users (
+        LIKE public.users INCLUDING ALL,
+        full_name TEXT GENERATED ALWAYS AS (first_name || ' ' || last_name) STORED
+    );
+    CREATE INDEX ON generated.users(full_name);
+
+    INSERT INTO generated.users (id, username, email, first_name, last_name, age, created_at, updated_at)
+    SELECT id, username, email, first_name, last_name, age, created_at, updated_at
+    FROM public.users WHERE username NOT LIKE 'bench_user_%';";
+
+/// A user of the copy, with the column the server computed
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamedUser {
+    pub id: Uuid,
+    pub username: String,
+    pub first_name: String,
+    pub last_name: String,
+    pub full_name: String,
+}
+
+/// The `full_name` the server computes for a user
+pub fn full_name(first_name: &str, last_name: &str) -> String {
+    format!("{first_name} {last_name}")
+}
+
+/// Replace the [`SCHEMA`] schema by a fresh copy of the seeded users,
+/// returning the number of users copied
+pub async fn create(client: &Client) -> Result<i64, tokio_postgres::Error> {
+    client.batch_execute(&format!("BEGIN; {CREATE} COMMIT;")).await?;
+    client.batch_execute("ANALYZE generated.users").await?;
+    Ok(client.query_one("SELECT COUNT(*) FROM generated.users", &[]).await?.get(0))
+}
+
+/// Drop the [`SCHEMA`] schema
+pub async fn drop(client: &Client) -> Result<(), tokio_postgres::Error> {
+    client.batch_execute("DROP SCHEMA IF EXISTS generated CASCADE").await
+}
+
+/// Remove the `bench_user_*` rows the benchmarks inserted into the copy
+pub async fn cleanup(client: &Client) -> Result<(), tokio_postgres::Error> {
+    client
+        .execute("DELETE FROM generated.users WHERE username LIKE 'bench_user_%'", &[])
+        .await?;
+    Ok(())
+}
+
+/// `full_name`s of the newest `limit` users of the copy, for the filter
+pub async fn sample_full_names(client: &Client, limit: i64) -> Result<Vec<String>, tokio_postgres::Error> {
+    let rows = client
+        .query("SELECT full_name FROM generated.users ORDER BY created_at DESC LIMIT $1", &[&limit])
+        .await?;
+    Ok(rows.iter().map(|row| row.get(0)).collect())
+}
+
+/// Inserting into, reading and filtering the copy on the backend's own connection
+#[allow(async_fn_in_trait)]
+pub trait GeneratedColumn: DatabaseBenchmark {
+    /// Insert a user into the copy the way [`DatabaseBenchmark::insert_user`]
+    /// inserts into `users`, `full_name` left to the server
+    async fn insert_named_user(conn: &Self::Connection, user: &NewUser) -> Result<Uuid, Self::Error>;
+
+    /// The newest `limit` users of the copy
+    async fn select_named_users(conn: &Self::Connection, limit: i64) -> Result<Vec<NamedUser>, Self::Error>;
+
+    /// The users of the copy with `full_name`
+    async fn select_by_full_name(conn: &Self::Connection, full_name: &str) -> Result<Vec<NamedUser>, Self::Error>;
+}
+
+// ============================================================================
+// tokio-postgres, sqlx and clorinde
+// ============================================================================
+
+const INSERT_USER: &str = "INSERT INTO generated.users (username, email, first_name, last_name, age)
+     VALUES ($1, $2, $3, $4, $5)
+     RETURNING id";
+
+const SELECT_USERS: &str = "SELECT id, username, first_name, last_name, full_name
+     FROM generated.users ORDER BY created_at DESC LIMIT $1";
+
+const SELECT_BY_FULL_NAME: &str = "SELECT id, username, first_name, last_name, full_name
+     FROM generated.users WHERE full_name = $1";
+
+impl From<&tokio_postgres::Row> for NamedUser {
+    fn from(row: &tokio_postgres::Row) -> Self {
+        Self {
+            id: row.get("id"),
+            username: row.get("username"),
+            first_name: row.get("first_name"),
+            last_name: row.get("last_name"),
+            full_name: row.get("full_name"),
+        }
+    }
+}
+
+impl GeneratedColumn for TokioPostgresBench {
+    async fn insert_named_user(client: &Client, user: &NewUser) -> Result<Uuid, tokio_postgres::Error> {
+        let row = client
+            .query_one(
+                INSERT_USER,
+                &[&user.username, &user.email, &user.first_name, &user.last_name, &user.age],
+            )
+            .await?;
+        Ok(row.get("id"))
+    }
+
+    async fn select_named_users(client: &Client, limit: i64) -> Result<Vec<NamedUser>, tokio_postgres::Error> {
+        Ok(client.query(SELECT_USERS, &[&limit]).await?.iter().map(Into::into).collect())
+    }
+
+    async fn select_by_full_name(client: &Client, full_name: &str) -> Result<Vec<NamedUser>, tokio_postgres::Error> {
+        Ok(client.query(SELECT_BY_FULL_NAME, &[&full_name]).await?.iter().map(Into::into).collect())
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct SqlxNamedUser {
+    id: Uuid,
+    username: String,
+    first_name: String,
+    last_name: String,
+    full_name: String,
+}
+
+impl From<SqlxNamedUser> for NamedUser {
+    fn from(u: SqlxNamedUser) -> Self {
+        Self {
+            id: u.id,
+            username: u.username,
+            first_name: u.first_name,
+            last_name: u.last_name,
+            full_name: u.full_name,
+        }
+    }
+}
+
+impl GeneratedColumn for SqlxBench {
+    async fn insert_named_user(pool: &PgPool, user: &NewUser) -> Result<Uuid, sqlx::Error> {
+        sqlx::query_scalar(INSERT_USER)
+            .bind(&user.username)
+            .bind(&user.email)
+            .bind(&user.first_name)
+            .bind(&user.last_name)
+            .bind(user.age)
+            .fetch_one(pool)
+            .await
+    }
+
+    async fn select_named_users(pool: &PgPool, limit: i64) -> Result<Vec<NamedUser>, sqlx::Error> {
+        let users = sqlx::query_as::<_, SqlxNamedUser>(SELECT_USERS).bind(limit).fetch_all(pool).await?;
+        Ok(users.into_iter().map(Into::into).collect())
+    }
+
+    async fn select_by_full_name(pool: &PgPool, full_name: &str) -> Result<Vec<NamedUser>, sqlx::Error> {
+        let users = sqlx::query_as::<_, SqlxNamedUser>(SELECT_BY_FULL_NAME)
+            .bind(full_name)
+            .fetch_all(pool)
+            .await?;
+        Ok(users.into_iter().map(Into::into).collect())
+    }
+}
+
+impl From<queries::generated_users::NamedUser> for NamedUser {
+    fn from(u: queries::generated_users::NamedUser) -> Self {
+        Self {
+            id: u.id,
+            username: u.username,
+            first_name: u.first_name,
+            last_name: u.last_name,
+            full_name: u.full_name,
+        }
+    }
+}
+
+impl GeneratedColumn for ClorindeBench {
+    async fn insert_named_user(client: &Client, user: &NewUser) -> Result<Uuid, tokio_postgres::Error> {
+        queries::generated_users::insert_named_user()
+            .bind(client, &user.username, &user.email, &user.first_name, &user.last_name, &user.age)
+            .one()
+            .await
+    }
+
+    async fn select_named_users(client: &Client, limit: i64) -> Result<Vec<NamedUser>, tokio_postgres::Error> {
+        let users = queries::generated_users::select_named_users().bind(client, &limit).all().await?;
+        Ok(users.into_iter().map(Into::into).collect())
+    }
+
+    async fn select_by_full_name(client: &Client, full_name: &str) -> Result<Vec<NamedUser>, tokio_postgres::Error> {
+        let users = queries::generated_users::select_named_users_by_full_name()
+            .bind(client, &full_name)
+            .all()
+            .await?;
+        Ok(users.into_iter().map(Into::into).collect())
+    }
+}
+
+// ============================================================================
+// SeaORM
+// ============================================================================
+
+pub mod named_users {
+    use sea_orm::entity::prelude::*;
+
+    /// `full_name` is a column like any other to SeaORM; it is never set
+    /// on an active model, which leaves it out of the `INSERT`
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+    #[sea_orm(schema_name = "generated", table_name = "users")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: Uuid,
+        pub username: String,
+        pub email: String,
+        pub first_name: String,
+        pub last_name: String,
+        pub age: Option<i32>,
+        pub created_at: Option<DateTimeWithTimeZone>,
+        pub updated_at: Option<DateTimeWithTimeZone>,
+        pub full_name: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+impl From<named_users::Model> for NamedUser {
+    fn from(u: named_users::Model) -> Self {
+        Self {
+            id: u.id,
+            username: u.username,
+            first_name: u.first_name,
+            last_name: u.last_name,
+            full_name: u.full_name,
+        }
+    }
+}
+
+impl GeneratedColumn for SeaOrmBench {
+    async fn insert_named_user(db: &DatabaseConnection, user: &NewUser) -> Result<Uuid, DbErr> {
+        let id = Uuid::new_v4();
+        let model = named_users::ActiveModel {
+            id: ActiveValue::Set(id),
+            username: ActiveValue::Set(user.username.clone()),
+            email: ActiveValue::Set(user.email.clone()),
+            first_name: ActiveValue::Set(user.first_name.clone()),
+            last_name: ActiveValue::Set(user.last_name.clone()),
+            age: ActiveValue::Set(user.age),
+            created_at: ActiveValue::NotSet,
+            updated_at: ActiveValue::NotSet,
+            full_name: ActiveValue::NotSet,
+        };
+
+        model.insert(db).await?;
+        Ok(id)
+    }
+
+    async fn select_named_users(db: &DatabaseConnection, limit: i64) -> Result<Vec<NamedUser>, DbErr> {
+        let users = named_users::Entity::find()
+            .order_by_desc(named_users::Column::CreatedAt)
+            .limit(limit as u64)
+            .all(db)
+            .await?;
+        Ok(users.into_iter().map(Into::into).collect())
+    }
+
+    async fn select_by_full_name(db: &DatabaseConnection, full_name: &str) -> Result<Vec<NamedUser>, DbErr> {
+        let users = named_users::Entity::find()
+            .filter(named_users::Column::FullName.eq(full_name))
+            .all(db)
+            .await?;
+        Ok(users.into_iter().map(Into::into).collect())
+    }
+}
+
+// ============================================================================
+// Diesel
+// ============================================================================
+
+pub mod schema {
+    diesel::table! {
+        generated.users (id) {
+            id -> Uuid,
+            username -> Varchar,
+            email -> Varchar,
+            first_name -> Varchar,
+            last_name -> Varchar,
+            age -> Nullable<Int4>,
+            created_at -> Nullable<Timestamptz>,
+            updated_at -> Nullable<Timestamptz>,
+            full_name -> Text,
+        }
+    }
+}
+
+mod diesel_models {
+    use super::schema::users;
+    use super::NamedUser;
+    use diesel::prelude::*;
+
+    #[derive(Queryable, Selectable)]
+    #[diesel(table_name = users)]
+    pub struct DieselNamedUser {
+        pub id: uuid::Uuid,
+        pub username: String,
+        pub first_name: String,
+        pub last_name: String,
+        pub full_name: String,
+    }
+
+    /// Without `full_name`, which the server refuses a value for
+    #[derive(Insertable)]
+    #[diesel(table_name = users)]
+    pub struct DieselNewNamedUser<'a> {
+        pub username: &'a str,
+        pub email: &'a str,
+        pub first_name: &'a str,
+        pub last_name: &'a str,
+        pub age: Option<i32>,
+    }
+
+    impl From<DieselNamedUser> for NamedUser {
+        fn from(u: DieselNamedUser) -> Self {
+            Self {
+                id: u.id,
+                username: u.username,
+                first_name: u.first_name,
+                last_name: u.last_name,
+                full_name: u.full_name,
+            }
+        }
+    }
+}
+
+impl GeneratedColumn for DieselBench {
+    async fn insert_named_user(conn: &Self::Connection, user: &NewUser) -> Result<Uuid, Self::Error> {
+        use diesel::prelude::*;
+        use diesel_models::DieselNewNamedUser;
+        use schema::users;
+
+        let new_user = DieselNewNamedUser {
+            username: &user.username,
+            email: &user.email,
+            first_name: &user.first_name,
+            last_name: &user.last_name,
+            age: user.age,
+        };
+        Ok(diesel::insert_into(users::table)
+            .values(&new_user)
+            .returning(users::id)
+            .get_result(&mut *conn.lock().unwrap())?)
+    }
+
+    async fn select_named_users(conn: &Self::Connection, limit: i64) -> Result<Vec<NamedUser>, Self::Error> {
+        use diesel::prelude::*;
+        use diesel_models::DieselNamedUser;
+        use schema::users;
+
+        let users = users::table
+            .order(users::created_at.desc())
+            .limit(limit)
+            .select(DieselNamedUser::as_select())
+            .load(&mut *conn.lock().unwrap())?;
+        Ok(users.into_iter().map(Into::into).collect())
+    }
+
+    async fn select_by_full_name(conn: &Self::Connection, full_name: &str) -> Result<Vec<NamedUser>, Self::Error> {
+        use diesel::prelude::*;
+        use diesel_models::DieselNamedUser;
+        use schema::users;
+
+        let users = users::table
+            .filter(users::full_name.eq(full_name))
+            .select(DieselNamedUser::as_select())
+            .load(&mut *conn.lock().unwrap())?;
+        Ok(users.into_iter().map(Into::into).collect())
+    }
+}
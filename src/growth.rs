@@ -0,0 +1,207 @@
+//! Table and index growth during long write-heavy runs
+//!
+//! A few seconds of inserts and updates barely change the size of the tables,
+//! but minutes of them show how much each backend's write pattern bloats them:
+//! row versions left behind by updates, half-filled pages from batches, index
+//! pages split by random keys. With `growth_interval_secs` set, the runner
+//! starts every measured run from compacted tables ([`compact`]) and samples
+//! the size of [`TABLES`] and their indexes at that interval, so each result
+//! carries its growth curve ([`Growth`]) into the exports and the summary.
+
+use crate::bench_tokio_postgres::TokioPostgresBench;
+use serde::Serialize;
+use std::cell::RefCell;
+use std::time::{Duration, Instant};
+use tokio_postgres::Client;
+
+/// The tables the scenarios write to
+pub const TABLES: [&str; 3] = ["users", "posts", "comments"];
+
+/// Characters of [`sparkline`], from the smallest value to the largest
+const CURVE: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Size of one table at one sample
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct RelationSize {
+    pub table: &'static str,
+    /// Heap and TOAST
+    pub table_bytes: i64,
+    pub index_bytes: i64,
+}
+
+impl RelationSize {
+    /// Everything `pg_total_relation_size` counts
+    pub fn total_bytes(&self) -> i64 {
+        self.table_bytes + self.index_bytes
+    }
+}
+
+/// The sizes of [`TABLES`] at one point of a run
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SizeSample {
+    /// Seconds since the measured run started
+    pub elapsed_secs: f64,
+    pub relations: Vec<RelationSize>,
+}
+
+impl SizeSample {
+    pub fn total_bytes(&self) -> i64 {
+        self.relations.iter().map(RelationSize::total_bytes).sum()
+    }
+}
+
+/// The current sizes of [`TABLES`]
+pub async fn sizes(client: &Client) -> Result<Vec<RelationSize>, tokio_postgres::Error> {
+    let mut sizes = Vec::with_capacity(TABLES.len());
+    for table in TABLES {
+        let row = client
+            .query_one("SELECT pg_table_size($1::text::regclass), pg_indexes_size($1::text::regclass)", &[&table])
+            .await?;
+        sizes.push(RelationSize {
+            table,
+            table_bytes: row.get(0),
+            index_bytes: row.get(1),
+        });
+    }
+    Ok(sizes)
+}
+
+/// Rewrite [`TABLES`] and their indexes without dead tuples or free space, so
+/// every run starts from the same sizes instead of reusing the space the last
+/// one's deleted rows left
+pub async fn compact(client: &Client) -> Result<(), tokio_postgres::Error> {
+    client
+        .batch_execute(&format!("VACUUM (FULL, ANALYZE) {}", TABLES.join(", ")))
+        .await
+}
+
+/// The size samples of one run
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Growth {
+    pub interval_secs: f64,
+    pub samples: Vec<SizeSample>,
+    /// Why sampling stopped early, if it did
+    pub error: Option<String>,
+}
+
+impl Growth {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval_secs: interval.as_secs_f64(),
+            ..Self::default()
+        }
+    }
+
+    /// Total size at the first sample
+    pub fn start_bytes(&self) -> i64 {
+        self.samples.first().map_or(0, SizeSample::total_bytes)
+    }
+
+    /// Total size at the last sample
+    pub fn end_bytes(&self) -> i64 {
+        self.samples.last().map_or(0, SizeSample::total_bytes)
+    }
+
+    /// Growth of the total size over the run
+    pub fn growth_bytes(&self) -> i64 {
+        self.end_bytes() - self.start_bytes()
+    }
+
+    /// Growth of the total size as a fraction of where it started
+    pub fn relative_growth(&self) -> f64 {
+        self.growth_bytes() as f64 / self.start_bytes().max(1) as f64
+    }
+
+    /// The total size of every sample as one character, from the smallest
+    /// sample (`▁`) to the largest (`█`)
+    pub fn curve(&self) -> String {
+        sparkline(&self.samples.iter().map(SizeSample::total_bytes).collect::<Vec<_>>())
+    }
+}
+
+/// `values` as one character each, scaled from the smallest (`▁`) to the largest (`█`)
+pub fn sparkline(values: &[i64]) -> String {
+    let (Some(&min), Some(&max)) = (values.iter().min(), values.iter().max()) else {
+        return String::new();
+    };
+    let steps = (CURVE.len() - 1) as f64;
+    values
+        .iter()
+        .map(|&value| {
+            let level = (value - min) as f64 / (max - min).max(1) as f64;
+            CURVE[(level * steps).round() as usize]
+        })
+        .collect()
+}
+
+/// Samples the sizes of one measured run on a connection of its own
+pub struct Sampler {
+    interval: Duration,
+    /// `None` if connecting or compacting failed, which the growth records
+    client: Option<Client>,
+    growth: RefCell<Growth>,
+}
+
+impl Sampler {
+    /// Connect and [`compact`] the tables, ready to sample every `interval`
+    pub async fn start(interval: Duration) -> Self {
+        let mut growth = Growth::new(interval);
+        let client = match Self::connect().await {
+            Ok(client) => Some(client),
+            Err(e) => {
+                growth.error = Some(e.to_string());
+                None
+            }
+        };
+        Self {
+            interval,
+            client,
+            growth: RefCell::new(growth),
+        }
+    }
+
+    async fn connect() -> Result<Client, tokio_postgres::Error> {
+        let client = TokioPostgresBench::connect().await?;
+        compact(&client).await?;
+        Ok(client)
+    }
+
+    /// Add a sample taken at `elapsed` into the run, or record why it failed
+    async fn sample(&self, elapsed: Duration) -> bool {
+        let Some(client) = &self.client else { return false };
+        if self.growth.borrow().error.is_some() {
+            return false;
+        }
+        match sizes(client).await {
+            Ok(relations) => {
+                let sample = SizeSample {
+                    elapsed_secs: elapsed.as_secs_f64(),
+                    relations,
+                };
+                self.growth.borrow_mut().samples.push(sample);
+                true
+            }
+            Err(e) => {
+                self.growth.borrow_mut().error = Some(e.to_string());
+                false
+            }
+        }
+    }
+
+    /// Sample every interval from `start` on, starting right away, until a sample fails
+    pub async fn run(&self, start: Instant) {
+        let mut ticker = tokio::time::interval_at(start.into(), self.interval);
+        loop {
+            ticker.tick().await;
+            if !self.sample(start.elapsed()).await {
+                return;
+            }
+        }
+    }
+
+    /// Take the last sample, at the end of a run that took `elapsed`, and return the growth
+    pub async fn finish(self, elapsed: Duration) -> Growth {
+        self.sample(elapsed).await;
+        self.growth.into_inner()
+    }
+}
@@ -0,0 +1,62 @@
+//! Hot posts: a few posts with thousands of comments each, paged through
+//!
+//! `init.sql` spreads its comments evenly, a handful per post, so no seeded
+//! post has enough of them to page through. Real threads are skewed: most
+//! posts get a few comments and a few get thousands, and those are the ones
+//! readers page through. [`seed`] adds that skew, [`HOT_POSTS`] posts with
+//! [`COMMENTS_PER_POST`] comments each, for `select_comments_for_post` to
+//! read a page at a time: a point lookup on the comments' foreign key that
+//! matches thousands of rows and returns a page of them.
+//!
+//! The posts and comments belong to one `bench_user_hot_posts` author, so the
+//! backends' cleanup removes them with it.
+
+use crate::{CommentKey, DatabaseBenchmark, NewComment, NewPost, NewUser};
+use uuid::Uuid;
+
+/// Username of the author of the hot posts and their comments
+pub const AUTHOR: &str = "bench_user_hot_posts";
+
+/// Posts [`seed`] inserts
+pub const HOT_POSTS: usize = 3;
+
+/// Comments on each hot post
+pub const COMMENTS_PER_POST: usize = 2_000;
+
+/// Comments per page
+pub const PAGE_SIZE: i64 = 50;
+
+/// Insert [`HOT_POSTS`] posts with [`COMMENTS_PER_POST`] comments each and
+/// return the posts' ids
+pub async fn seed<B: DatabaseBenchmark>(conn: &B::Connection) -> Result<Vec<Uuid>, B::Error> {
+    let author = NewUser {
+        username: AUTHOR.to_string(),
+        email: format!("{AUTHOR}@benchmark.com"),
+        ..NewUser::generate(0)
+    };
+    let author = B::insert_user(conn, &author).await?;
+
+    let mut posts = Vec::with_capacity(HOT_POSTS);
+    for i in 0..HOT_POSTS {
+        let post = B::insert_post(conn, &NewPost::generate(author, i)).await?;
+        for n in 0..COMMENTS_PER_POST {
+            B::insert_comment(conn, &NewComment::generate(post, author, n)).await?;
+        }
+        posts.push(post);
+    }
+    Ok(posts)
+}
+
+/// The key a page starting `depth` comments into `post_id` is read after, or
+/// `None` for the first page
+pub async fn key_at<B: DatabaseBenchmark>(
+    conn: &B::Connection,
+    post_id: Uuid,
+    depth: usize,
+) -> Result<Option<CommentKey>, B::Error> {
+    if depth == 0 {
+        return Ok(None);
+    }
+    let skipped = B::select_comments_for_post(conn, post_id, depth as i64, None).await?;
+    Ok(skipped.last().and_then(CommentKey::of))
+}
@@ -0,0 +1,153 @@
+//! Long-running updates against `users` copies with different fillfactors
+//!
+//! The `update_user` group measures its first seconds, before dead tuples pile
+//! up. Here each backend runs the same update for minutes against a freshly
+//! packed copy of `users` per fillfactor in [`FILLFACTORS`], in its own schema
+//! that the connection's `search_path` puts ahead of `public`, so the backends'
+//! unqualified SQL hits the copy. Every [`WINDOW`] the update latency and the
+//! table's statistics are recorded, which shows how the latency drifts as the
+//! table bloats and whether the free space left on each page keeps the new row
+//! versions on the same page.
+//!
+//! `update_user` writes the same names every time, so only a user's first
+//! update changes the trigram-indexed `first_name` and `last_name`. Later ones
+//! can be HOT (heap-only, without new index entries) as long as the page has
+//! room for the new version, which is what a lower fillfactor leaves;
+//! [`TableStats::hot_updates`] shows how many were.
+
+use crate::session::SessionConfig;
+use crate::{database_url_with, DatabaseBenchmark};
+use std::fmt;
+use std::time::{Duration, Instant};
+use tokio_postgres::Client;
+use uuid::Uuid;
+
+/// PostgreSQL's default, which packs pages full, and one leaving 30% free
+pub const FILLFACTORS: [u32; 2] = [100, 70];
+
+/// Length of each recorded window
+pub const WINDOW: Duration = Duration::from_secs(5);
+
+/// Run time per backend and fillfactor unless given on the command line
+pub const DEFAULT_DURATION: Duration = Duration::from_secs(60);
+
+/// Users the updates cycle through, the newest as in the `update_user` group
+pub const UPDATED_USERS: i64 = 100;
+
+/// Schema holding the `users` copy with `fillfactor`
+pub fn schema(fillfactor: u32) -> String {
+    format!("fillfactor_{fillfactor}")
+}
+
+/// [`crate::database_url`] with `users` resolving to the copy with `fillfactor`
+pub fn database_url(fillfactor: u32) -> String {
+    let search_path = format!("{},public", schema(fillfactor));
+    database_url_with(&SessionConfig::from_env().with_search_path(&search_path))
+}
+
+/// Replace the copy with `fillfactor` by a freshly packed copy of the seeded users,
+/// with the same indexes and constraints
+pub async fn create(client: &Client, fillfactor: u32) -> Result<(), tokio_postgres::Error> {
+    let schema = schema(fillfactor);
+    client
+        .batch_execute(&format!(
+            "DROP SCHEMA IF EXISTS {schema} CASCADE;
+             CREATE SCHEMA {schema};
+             CREATE TABLE {schema}.users (LIKE public.users INCLUDING ALL) WITH (fillfactor = {fillfactor});
+             INSERT INTO {schema}.users SELECT * FROM public.users WHERE username NOT LIKE 'bench_user_%';"
+        ))
+        .await?;
+    // Not in the batch above, which runs as one transaction
+    client.batch_execute(&format!("VACUUM ANALYZE {schema}.users")).await
+}
+
+/// Drop the copy with `fillfactor`
+pub async fn drop(client: &Client, fillfactor: u32) -> Result<(), tokio_postgres::Error> {
+    client
+        .batch_execute(&format!("DROP SCHEMA IF EXISTS {} CASCADE", schema(fillfactor)))
+        .await
+}
+
+/// Update the users of `ids` in turn, from `*next` on, until `window` has passed;
+/// the number of updates and their mean latency
+pub async fn update_for<B: DatabaseBenchmark>(
+    conn: &B::Connection,
+    ids: &[Uuid],
+    next: &mut usize,
+    window: Duration,
+) -> Result<(usize, Duration), B::Error> {
+    let start = Instant::now();
+    let mut updates = 0;
+    while start.elapsed() < window {
+        B::update_user(conn, ids[*next % ids.len()], "UpdatedFirst", "UpdatedLast").await?;
+        *next += 1;
+        updates += 1;
+    }
+    Ok((updates, start.elapsed() / updates.max(1) as u32))
+}
+
+/// The cumulative statistics of a copy, as the server last flushed them
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TableStats {
+    pub dead_tuples: i64,
+    pub updates: i64,
+    /// Updates that kept the new version on the same page without new index entries
+    pub hot_updates: i64,
+    pub autovacuums: i64,
+    /// Size of the heap alone, without indexes and TOAST
+    pub heap_bytes: i64,
+}
+
+/// Statistics of the copy with `fillfactor`
+pub async fn table_stats(client: &Client, fillfactor: u32) -> Result<TableStats, tokio_postgres::Error> {
+    let row = client
+        .query_one(
+            "SELECT n_dead_tup, n_tup_upd, n_tup_hot_upd, autovacuum_count, pg_relation_size(relid)
+             FROM pg_stat_user_tables WHERE schemaname = $1 AND relname = 'users'",
+            &[&schema(fillfactor)],
+        )
+        .await?;
+    Ok(TableStats {
+        dead_tuples: row.get(0),
+        updates: row.get(1),
+        hot_updates: row.get(2),
+        autovacuums: row.get(3),
+        heap_bytes: row.get(4),
+    })
+}
+
+/// One recorded window of a run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Window {
+    /// Time since the run started, at the end of the window
+    pub elapsed: Duration,
+    pub updates: usize,
+    pub mean_latency: Duration,
+    pub stats: TableStats,
+}
+
+impl fmt::Display for Window {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:>4}s {:>7} updates {:>8.3} ms  dead {:>7}  hot {:>7}/{:<7}  heap {:>7.1} MiB  autovacuum {}",
+            self.elapsed.as_secs(),
+            self.updates,
+            self.mean_latency.as_secs_f64() * 1000.0,
+            self.stats.dead_tuples,
+            self.stats.hot_updates,
+            self.stats.updates,
+            self.stats.heap_bytes as f64 / (1024.0 * 1024.0),
+            self.stats.autovacuums
+        )
+    }
+}
+
+/// Mean latency of the last window over the first's, or `None` with fewer
+/// than two windows; above 1 the updates got slower as the run went on
+pub fn drift(windows: &[Window]) -> Option<f64> {
+    match windows {
+        [first, .., last] => Some(last.mean_latency.as_secs_f64() / first.mean_latency.as_secs_f64()),
+        _ => None,
+    }
+}
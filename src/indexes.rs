@@ -0,0 +1,60 @@
+//! The secondary indexes the index comparison drops and restores
+//!
+//! The select and join scenarios order by `created_at`, filter on `age` and the
+//! other read paths filter on `status`. Running them with [`SECONDARY`] dropped
+//! as well as in place shows how much of a backend's time is the server's plan
+//! rather than the library: a backend whose time barely moves is dominated by
+//! its own overhead. The primary keys, the foreign key indexes the joins need
+//! and the name indexes stay, so the point lookup by id is a control that no
+//! drop should change.
+//!
+//! The definitions are the ones in `init.sql`, so [`restore`] puts back exactly
+//! what the database was created with.
+
+use tokio_postgres::Client;
+
+/// An index of `init.sql`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Index {
+    pub name: &'static str,
+    /// `ON` clause of its `CREATE INDEX`
+    pub on: &'static str,
+}
+
+/// The `created_at`, `age` and `status` indexes
+pub const SECONDARY: &[Index] = &[
+    Index { name: "idx_users_created_at", on: "users(created_at DESC)" },
+    Index { name: "idx_users_age", on: "users(age)" },
+    Index { name: "idx_posts_status", on: "posts(status)" },
+    Index { name: "idx_posts_created_at", on: "posts(created_at DESC)" },
+    Index { name: "idx_posts_status_created_at", on: "posts(status, created_at)" },
+    Index { name: "idx_comments_created_at", on: "comments(created_at DESC)" },
+];
+
+/// Drop every index of [`SECONDARY`] that exists
+pub async fn drop(client: &Client) -> Result<(), tokio_postgres::Error> {
+    let names: Vec<&str> = SECONDARY.iter().map(|index| index.name).collect();
+    client.batch_execute(&format!("DROP INDEX IF EXISTS {}", names.join(", "))).await
+}
+
+/// Create every index of [`SECONDARY`] that is missing, as `init.sql` does
+pub async fn restore(client: &Client) -> Result<(), tokio_postgres::Error> {
+    let statements: Vec<String> = SECONDARY
+        .iter()
+        .map(|index| format!("CREATE INDEX IF NOT EXISTS {} ON {};", index.name, index.on))
+        .collect();
+    client.batch_execute(&statements.concat()).await
+}
+
+/// The indexes of [`SECONDARY`] the database doesn't have, in their order there
+pub async fn missing(client: &Client) -> Result<Vec<&'static str>, tokio_postgres::Error> {
+    let rows = client
+        .query("SELECT indexname FROM pg_indexes WHERE schemaname = 'public'", &[])
+        .await?;
+    let present: Vec<String> = rows.iter().map(|row| row.get(0)).collect();
+    Ok(SECONDARY
+        .iter()
+        .map(|index| index.name)
+        .filter(|name| !present.iter().any(|p| p == name))
+        .collect())
+}
@@ -0,0 +1,187 @@
+//! The heavy aggregate while writers insert posts, on one shared pool
+//!
+//! `count_posts_per_user` scans and groups every post; run alone it measures
+//! the server's aggregate speed, but in an app it shares the pool with the
+//! writes. [`Writers`] keeps tasks inserting posts through the same
+//! [`PostPool`] for as long as the aggregate is timed, so the aggregate's
+//! latency shows what the writes cost it: a pool smaller than the two
+//! workloads together makes the aggregate wait for connections behind the
+//! writers, and the new posts are more rows to scan. How many posts were
+//! written meanwhile shows how the pool shared its connections back.
+//!
+//! The posts belong to one `bench_user_interference` author created by
+//! [`create_author`], so the backends' cleanup removes them with it.
+
+use crate::bench_clorinde::ClorindeBench;
+use crate::bench_diesel::{DbPool, DieselBench};
+use crate::bench_seaorm::SeaOrmBench;
+use crate::bench_sqlx::SqlxBench;
+use crate::bench_tokio_postgres::TokioPostgresBench;
+use crate::{BoxError, NewPost, NewUser};
+use deadpool_postgres::Pool;
+use sea_orm::DatabaseConnection;
+use sqlx::PgPool;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::task::JoinSet;
+use tokio_postgres::Client;
+use uuid::Uuid;
+
+/// Connections in every backend's pool, fewer than the aggregate and the
+/// most writers need at once, so they compete for them
+pub const POOL_SIZE: usize = 4;
+
+/// Writer tasks inserting posts while the aggregate runs: none for the
+/// baseline, some and more than the pool has connections
+pub const WRITER_COUNTS: [usize; 3] = [0, 2, 8];
+
+/// The user every writer's posts belong to
+const AUTHOR: &str = "bench_user_interference";
+
+/// The two workloads' statements on one backend's pool
+pub trait PostPool: Clone + Send + Sync + 'static {
+    /// Run `count_posts_per_user` and return how many users it counted
+    fn count_posts_per_user(&self) -> impl Future<Output = Result<usize, BoxError>> + Send;
+
+    /// Insert `post`
+    fn insert_post(&self, post: NewPost) -> impl Future<Output = Result<(), BoxError>> + Send;
+}
+
+/// Insert the author of the writers' posts and return its id
+pub async fn create_author(client: &Client) -> Result<Uuid, tokio_postgres::Error> {
+    let author = NewUser {
+        username: AUTHOR.to_string(),
+        email: format!("{AUTHOR}@benchmark.com"),
+        ..NewUser::generate(0)
+    };
+    TokioPostgresBench::insert_user(client, &author).await
+}
+
+/// Remove the posts the writers inserted, so each sample scans the same table
+pub async fn reset(client: &Client, author: Uuid) -> Result<u64, tokio_postgres::Error> {
+    client.execute("DELETE FROM posts WHERE user_id = $1", &[&author]).await
+}
+
+/// Tasks inserting posts by one author until stopped
+pub struct Writers {
+    stop: Arc<AtomicBool>,
+    written: Arc<AtomicUsize>,
+    tasks: JoinSet<Result<(), BoxError>>,
+}
+
+impl Writers {
+    /// Spawn `count` writers on `pool`; must be called within a tokio runtime
+    pub fn start<P: PostPool>(pool: &P, count: usize, author: Uuid) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let written = Arc::new(AtomicUsize::new(0));
+        let mut tasks = JoinSet::new();
+        for writer in 0..count {
+            let (pool, stop, written) = (pool.clone(), Arc::clone(&stop), Arc::clone(&written));
+            tasks.spawn(async move {
+                let mut index = writer;
+                while !stop.load(Ordering::SeqCst) {
+                    pool.insert_post(NewPost::generate(author, index)).await?;
+                    written.fetch_add(1, Ordering::SeqCst);
+                    index += count;
+                }
+                Ok(())
+            });
+        }
+        Self { stop, written, tasks }
+    }
+
+    /// Posts inserted so far
+    pub fn written(&self) -> usize {
+        self.written.load(Ordering::SeqCst)
+    }
+
+    /// Stop every writer after its current insert and return how many posts
+    /// they inserted, or the first writer's error
+    pub async fn stop(mut self) -> Result<usize, BoxError> {
+        self.stop.store(true, Ordering::SeqCst);
+        while let Some(result) = self.tasks.join_next().await {
+            result??;
+        }
+        Ok(self.written())
+    }
+}
+
+/// tokio-postgres behind a deadpool pool
+#[derive(Clone)]
+pub struct TokioPostgresPosts(pub Pool);
+
+impl PostPool for TokioPostgresPosts {
+    async fn count_posts_per_user(&self) -> Result<usize, BoxError> {
+        let client = self.0.get().await?;
+        Ok(TokioPostgresBench::count_posts_per_user(&client).await?.len())
+    }
+
+    async fn insert_post(&self, post: NewPost) -> Result<(), BoxError> {
+        let client = self.0.get().await?;
+        TokioPostgresBench::insert_post(&client, &post).await?;
+        Ok(())
+    }
+}
+
+/// sqlx's own pool
+#[derive(Clone)]
+pub struct SqlxPosts(pub PgPool);
+
+impl PostPool for SqlxPosts {
+    async fn count_posts_per_user(&self) -> Result<usize, BoxError> {
+        Ok(SqlxBench::count_posts_per_user(&self.0).await?.len())
+    }
+
+    async fn insert_post(&self, post: NewPost) -> Result<(), BoxError> {
+        SqlxBench::insert_post(&self.0, &post).await?;
+        Ok(())
+    }
+}
+
+/// SeaORM over its sqlx pool
+#[derive(Clone)]
+pub struct SeaOrmPosts(pub DatabaseConnection);
+
+impl PostPool for SeaOrmPosts {
+    async fn count_posts_per_user(&self) -> Result<usize, BoxError> {
+        Ok(SeaOrmBench::count_posts_per_user(&self.0).await?.len())
+    }
+
+    async fn insert_post(&self, post: NewPost) -> Result<(), BoxError> {
+        SeaOrmBench::insert_post(&self.0, &post).await?;
+        Ok(())
+    }
+}
+
+/// diesel behind r2d2, on the blocking thread pool ([`DieselBench::run_blocking`])
+#[derive(Clone)]
+pub struct DieselPosts(pub DbPool);
+
+impl PostPool for DieselPosts {
+    async fn count_posts_per_user(&self) -> Result<usize, BoxError> {
+        DieselBench::run_blocking(&self.0, |conn| DieselBench::count_posts_per_user(conn).map(|counts| counts.len()))
+            .await
+    }
+
+    async fn insert_post(&self, post: NewPost) -> Result<(), BoxError> {
+        DieselBench::run_blocking(&self.0, move |conn| DieselBench::insert_post(conn, &post).map(drop)).await
+    }
+}
+
+/// The clorinde queries over tokio-postgres clients from a deadpool pool
+#[derive(Clone)]
+pub struct ClorindePosts(pub Pool);
+
+impl PostPool for ClorindePosts {
+    async fn count_posts_per_user(&self) -> Result<usize, BoxError> {
+        let client = self.0.get().await?;
+        Ok(ClorindeBench::count_posts_per_user(&client).await?.len())
+    }
+
+    async fn insert_post(&self, post: NewPost) -> Result<(), BoxError> {
+        let client = self.0.get().await?;
+        ClorindeBench::insert_post(&client, &post).await?;
+        Ok(())
+    }
+}
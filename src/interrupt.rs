@@ -0,0 +1,47 @@
+//! Ctrl-C during a long load test
+//!
+//! Killing a 40-minute `bench-runner` or `run-all` run used to lose every
+//! result and leave the active backend's `bench_user_*` rows behind. With an
+//! [`Interrupt`] listening, the first Ctrl-C only asks the run to stop: the
+//! scenario in progress finishes and tears down, the active backend's rows
+//! are cleaned up, and the results so far are written, marked partial. A
+//! second Ctrl-C exits at once for when that takes too long.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Exit status of a process stopped by SIGINT
+pub const EXIT_INTERRUPTED: i32 = 130;
+
+/// Whether a stop was requested, shared with the signal listener
+#[derive(Debug, Clone, Default)]
+pub struct Interrupt {
+    requested: Arc<AtomicBool>,
+}
+
+impl Interrupt {
+    /// Listen for Ctrl-C on the current tokio runtime
+    pub fn listen() -> Self {
+        let interrupt = Self::default();
+        let requested = interrupt.requested.clone();
+        tokio::spawn(async move {
+            while tokio::signal::ctrl_c().await.is_ok() {
+                if requested.swap(true, Ordering::SeqCst) {
+                    eprintln!("\nInterrupted again, exiting without cleanup");
+                    std::process::exit(EXIT_INTERRUPTED);
+                }
+                eprintln!("\nInterrupted, stopping after the current run; press Ctrl-C again to exit at once");
+            }
+        });
+        interrupt
+    }
+
+    /// Ask the run to stop, as the first Ctrl-C does
+    pub fn request(&self) {
+        self.requested.store(true, Ordering::SeqCst);
+    }
+
+    pub fn requested(&self) -> bool {
+        self.requested.load(Ordering::SeqCst)
+    }
+}
@@ -0,0 +1,238 @@
+//! Schema changes under a running workload: prepared statement invalidation
+//!
+//! PostgreSQL fixes a prepared statement's result columns when it is
+//! prepared. If the table under a `SELECT *` gains or loses a column, the next
+//! execution fails with `cached plan must not change result type` (SQLSTATE
+//! `0A000`) instead of returning the wider row, and keeps failing until the
+//! statement is prepared again. Queries that name their columns are only
+//! replanned, which is why [`LOOKUP`] selects `*`.
+//!
+//! [`measure`] warms a [`CachedLookup`] so its statement is prepared, changes
+//! the table's columns with [`alter`] from another connection, then runs a
+//! fixed number of lookups, counting the failures and timing how long until
+//! one succeeded again. That is down to the library's statement cache:
+//!
+//! - tokio-postgres passing SQL text, and clorinde's generated queries on a
+//!   plain client, prepare the SQL again on every call, so nothing goes stale
+//! - a statement prepared once and reused, as `tokio_postgres_prepared` and
+//!   clorinde's `PreparedStatements` do, fails until the caller prepares it again
+//! - sqlx, and SeaORM on top of it, keep the failing statement in the
+//!   connection's cache, so that connection fails this lookup until it closes
+//! - Diesel never caches `sql_query` statements, and its query builder names
+//!   every column, so neither runs into the error
+//!
+//! The table isn't part of `init.sql`; [`measure`] creates it on the first
+//! change and again every [`CHANGES_PER_RESET`], as a dropped column keeps
+//! counting towards PostgreSQL's limit of 1600 per table.
+
+use crate::bench_diesel::DieselBench;
+use crate::bench_seaorm::SeaOrmBench;
+use crate::bench_sqlx::SqlxBench;
+use crate::bench_tokio_postgres::TokioPostgresBench;
+use crate::BoxError;
+use diesel::sql_types::{Integer, Text};
+use diesel::{PgConnection, QueryableByName, RunQueryDsl};
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend, Statement};
+use sqlx::{PgPool, Row};
+use std::future::Future;
+use std::time::{Duration, Instant};
+use tokio_postgres::Client;
+
+/// The table the lookups read and [`alter`] changes
+pub const TABLE: &str = "invalidation_probe";
+
+/// Rows in [`TABLE`], with ids from 1
+pub const PROBE_ROWS: i32 = 100;
+
+/// The lookup every backend caches: a point read of every column
+pub const LOOKUP: &str = "SELECT * FROM invalidation_probe WHERE id = $1";
+
+/// Adds a column, changing the result type of [`LOOKUP`]
+pub const ADD_COLUMN: &str = "ALTER TABLE invalidation_probe ADD COLUMN IF NOT EXISTS note TEXT";
+
+/// Drops the column [`ADD_COLUMN`] added, changing the result type back
+pub const DROP_COLUMN: &str = "ALTER TABLE invalidation_probe DROP COLUMN IF EXISTS note";
+
+const CREATE_TABLE: &str = "
+    DROP TABLE IF EXISTS invalidation_probe;
+    CREATE TABLE invalidation_probe (id INTEGER PRIMARY KEY, label TEXT NOT NULL);
+    INSERT INTO invalidation_probe SELECT i, 'probe_' || i FROM generate_series(1, 100) i;";
+
+/// Lookups [`measure`] runs before [`alter`], so the statement is cached
+pub const WARMUP_LOOKUPS: usize = 5;
+
+/// Schema changes after which [`measure`] creates [`TABLE`] afresh
+pub const CHANGES_PER_RESET: usize = 1000;
+
+/// Create [`TABLE`] afresh, without the column [`ADD_COLUMN`] adds
+pub async fn reset(client: &Client) -> Result<(), tokio_postgres::Error> {
+    client.batch_execute(CREATE_TABLE).await
+}
+
+/// Drop [`TABLE`]
+pub async fn drop(client: &Client) -> Result<(), tokio_postgres::Error> {
+    client.batch_execute("DROP TABLE IF EXISTS invalidation_probe").await
+}
+
+/// The label of probe row `id`
+pub fn label(id: i32) -> String {
+    format!("probe_{id}")
+}
+
+/// The `change`th schema change: [`ADD_COLUMN`] and [`DROP_COLUMN`] in turn,
+/// so the table is never more than one column wider than [`reset`] leaves it
+pub fn alter(change: usize) -> &'static str {
+    if change.is_multiple_of(2) { ADD_COLUMN } else { DROP_COLUMN }
+}
+
+/// The innermost message of `error`, e.g. the server's rather than the
+/// driver's `db error`
+fn innermost(error: &BoxError) -> String {
+    let mut inner: &(dyn std::error::Error + 'static) = error.as_ref();
+    while let Some(source) = inner.source() {
+        inner = source;
+    }
+    inner.to_string()
+}
+
+/// [`LOOKUP`] through one backend's usual path, on a single connection so
+/// every call sees the same statement cache
+pub trait CachedLookup: Sized {
+    /// Connect to [`crate::database_url`]
+    fn connect() -> impl Future<Output = Result<Self, BoxError>>;
+
+    /// The label of row `id`
+    fn lookup(&mut self, id: i32) -> impl Future<Output = Result<String, BoxError>>;
+}
+
+/// What happened to the lookups after a schema change
+#[derive(Debug, Clone, PartialEq)]
+pub struct Invalidation {
+    /// Lookups that failed
+    pub errors: usize,
+    /// The first failure's message, e.g. `cached plan must not change result type`
+    pub first_error: Option<String>,
+    /// From the change until a lookup succeeded, or `None` if none did
+    pub recovery: Option<Duration>,
+    /// Time the lookups took, failed ones included
+    pub elapsed: Duration,
+}
+
+/// Warm `lookup`, make the `change`th schema change ([`alter`]) through
+/// `admin`, then run `lookups` lookups of [`TABLE`]'s rows in turn. Changes
+/// are counted from 0 and must be made in order.
+pub async fn measure<L: CachedLookup>(
+    lookup: &mut L,
+    admin: &Client,
+    change: usize,
+    lookups: usize,
+) -> Result<Invalidation, BoxError> {
+    if change.is_multiple_of(CHANGES_PER_RESET) {
+        reset(admin).await?;
+    }
+    for id in 1..=WARMUP_LOOKUPS as i32 {
+        lookup.lookup(id).await?;
+    }
+    admin.batch_execute(alter(change)).await?;
+
+    let start = Instant::now();
+    let (mut errors, mut first_error, mut recovery) = (0, None, None);
+    for n in 0..lookups {
+        let id = n as i32 % PROBE_ROWS + 1;
+        match lookup.lookup(id).await {
+            Ok(found) if found == label(id) => {
+                recovery.get_or_insert_with(|| start.elapsed());
+            }
+            Ok(found) => return Err(format!("row {id} read back as {found:?}").into()),
+            Err(e) => {
+                errors += 1;
+                first_error.get_or_insert_with(|| innermost(&e));
+            }
+        }
+    }
+    Ok(Invalidation {
+        errors,
+        first_error,
+        recovery,
+        elapsed: start.elapsed(),
+    })
+}
+
+/// tokio-postgres passing SQL text, prepared again on every call
+pub struct TokioPostgresLookup(pub Client);
+
+impl CachedLookup for TokioPostgresLookup {
+    async fn connect() -> Result<Self, BoxError> {
+        Ok(Self(TokioPostgresBench::connect().await?))
+    }
+
+    async fn lookup(&mut self, id: i32) -> Result<String, BoxError> {
+        Ok(self.0.query_one(LOOKUP, &[&id]).await?.try_get("label")?)
+    }
+}
+
+/// tokio-postgres running a statement prepared once, when connecting
+pub struct TokioPostgresPreparedLookup {
+    pub client: Client,
+    pub statement: tokio_postgres::Statement,
+}
+
+impl CachedLookup for TokioPostgresPreparedLookup {
+    async fn connect() -> Result<Self, BoxError> {
+        let client = TokioPostgresBench::connect().await?;
+        let statement = client.prepare(LOOKUP).await?;
+        Ok(Self { client, statement })
+    }
+
+    async fn lookup(&mut self, id: i32) -> Result<String, BoxError> {
+        Ok(self.client.query_one(&self.statement, &[&id]).await?.try_get("label")?)
+    }
+}
+
+/// sqlx with its statement cache, on a pool of one connection
+pub struct SqlxLookup(pub PgPool);
+
+impl CachedLookup for SqlxLookup {
+    async fn connect() -> Result<Self, BoxError> {
+        Ok(Self(SqlxBench::connect_with_pool_size(1).await?))
+    }
+
+    async fn lookup(&mut self, id: i32) -> Result<String, BoxError> {
+        Ok(sqlx::query(LOOKUP).bind(id).fetch_one(&self.0).await?.try_get("label")?)
+    }
+}
+
+/// SeaORM raw SQL, through sqlx's statement cache, on a pool of one connection
+pub struct SeaOrmLookup(pub DatabaseConnection);
+
+impl CachedLookup for SeaOrmLookup {
+    async fn connect() -> Result<Self, BoxError> {
+        Ok(Self(SeaOrmBench::connect_with_pool_size(1).await?))
+    }
+
+    async fn lookup(&mut self, id: i32) -> Result<String, BoxError> {
+        let statement = Statement::from_sql_and_values(DbBackend::Postgres, LOOKUP, [id.into()]);
+        let row = self.0.query_one(statement).await?.ok_or("probe row missing")?;
+        Ok(row.try_get("", "label")?)
+    }
+}
+
+#[derive(QueryableByName)]
+struct ProbeLabel {
+    #[diesel(sql_type = Text)]
+    label: String,
+}
+
+/// Diesel's `sql_query`, which it prepares again on every call
+pub struct DieselLookup(pub PgConnection);
+
+impl CachedLookup for DieselLookup {
+    async fn connect() -> Result<Self, BoxError> {
+        Ok(Self(DieselBench::establish(&crate::database_url())?))
+    }
+
+    async fn lookup(&mut self, id: i32) -> Result<String, BoxError> {
+        let row: ProbeLabel = diesel::sql_query(LOOKUP).bind::<Integer, _>(id).get_result(&mut self.0)?;
+        Ok(row.label)
+    }
+}
@@ -0,0 +1,172 @@
+//! Logical replication lag during write benchmarks
+//!
+//! With `SUBSCRIBER_DATABASE_URL` set, the write scenarios also run while a
+//! logical replication subscriber (the `postgres-subscriber` service in
+//! `compose.yml`) applies the primary's changes to `users`, `posts` and
+//! `comments`. A [`LagMonitor`] samples the subscription's walsender in
+//! `pg_stat_replication` on the primary meanwhile: how many bytes of WAL the
+//! subscriber has yet to apply, and how long ago the WAL it last applied was
+//! written. A backend whose writes produce more WAL per operation (wider
+//! updates, one transaction per row) shows up as a subscriber falling further
+//! behind at the same rate of operations.
+
+use crate::session::SessionConfig;
+use crate::{configure_url, growth};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use tokio_postgres::Client;
+
+/// The subscription `init-subscriber.sh` creates, and so the walsender's `application_name`
+pub const SUBSCRIPTION: &str = "benchmark_sub";
+
+/// How often the monitor looks at `pg_stat_replication`
+pub const SAMPLE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Longest [`LagReport::curve`]
+pub const CURVE_WIDTH: usize = 40;
+
+/// Subscriber connection URL from `SUBSCRIBER_DATABASE_URL`, configured like
+/// [`crate::database_url`], or `None` when no subscriber is configured
+pub fn subscriber_url() -> Option<String> {
+    let url = std::env::var("SUBSCRIBER_DATABASE_URL").ok()?;
+    Some(configure_url(&url, &SessionConfig::from_env()))
+}
+
+/// How far behind the primary the subscriber was at one point
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Lag {
+    /// WAL written on the primary that the subscriber hasn't applied yet
+    pub bytes: i64,
+    /// Time since the WAL the subscriber last applied was written; `None`
+    /// once it has caught up and nothing new was written since
+    pub time: Option<Duration>,
+}
+
+/// The subscriber's lag, as the primary behind `client` sees it, or `None`
+/// when [`SUBSCRIPTION`] isn't streaming from it
+pub async fn lag(client: &Client) -> Result<Option<Lag>, tokio_postgres::Error> {
+    let row = client
+        .query_opt(
+            "SELECT pg_wal_lsn_diff(pg_current_wal_lsn(), replay_lsn)::bigint, EXTRACT(EPOCH FROM replay_lag)::float8
+             FROM pg_stat_replication WHERE application_name = $1",
+            &[&SUBSCRIPTION],
+        )
+        .await?;
+    Ok(row.map(|row| Lag {
+        bytes: row.get::<_, Option<i64>>(0).unwrap_or(0),
+        time: row.get::<_, Option<f64>>(1).map(Duration::from_secs_f64),
+    }))
+}
+
+/// Wait until the subscriber has applied everything written on the primary
+/// so far; whether it did before `timeout`
+pub async fn wait_for_catch_up(client: &Client, timeout: Duration) -> Result<bool, tokio_postgres::Error> {
+    let start = Instant::now();
+    loop {
+        if lag(client).await?.is_some_and(|lag| lag.bytes <= 0) {
+            return Ok(true);
+        }
+        if start.elapsed() > timeout {
+            return Ok(false);
+        }
+        tokio::time::sleep(SAMPLE_INTERVAL).await;
+    }
+}
+
+/// One sample of a [`LagMonitor`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LagSample {
+    /// Time since the monitor started
+    pub elapsed: Duration,
+    pub lag: Lag,
+}
+
+/// The lag samples of one run
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LagReport {
+    pub samples: Vec<LagSample>,
+}
+
+impl LagReport {
+    /// Most WAL the subscriber was behind by
+    pub fn max_bytes(&self) -> i64 {
+        self.samples.iter().map(|s| s.lag.bytes).max().unwrap_or(0)
+    }
+
+    /// Longest time lag over the samples that had one
+    pub fn max_time(&self) -> Duration {
+        self.samples.iter().filter_map(|s| s.lag.time).max().unwrap_or_default()
+    }
+
+    /// Mean time lag, counting samples without one (caught up) as zero
+    pub fn mean_time(&self) -> Duration {
+        let total: Duration = self.samples.iter().filter_map(|s| s.lag.time).sum();
+        total / self.samples.len().max(1) as u32
+    }
+
+    /// The WAL lag over the run, at most [`CURVE_WIDTH`] characters from the
+    /// smallest to the largest, each the peak of its share of the samples
+    pub fn curve(&self) -> String {
+        let bytes: Vec<i64> = self.samples.iter().map(|s| s.lag.bytes).collect();
+        let chunk = bytes.len().div_ceil(CURVE_WIDTH).max(1);
+        let peaks: Vec<i64> = bytes.chunks(chunk).filter_map(|c| c.iter().max().copied()).collect();
+        growth::sparkline(&peaks)
+    }
+}
+
+impl fmt::Display for LagReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "lag max {:.1} KiB {:.1} ms, mean {:.1} ms over {} samples {}",
+            self.max_bytes() as f64 / 1024.0,
+            self.max_time().as_secs_f64() * 1000.0,
+            self.mean_time().as_secs_f64() * 1000.0,
+            self.samples.len(),
+            self.curve()
+        )
+    }
+}
+
+/// Samples the subscriber's [`lag`] every [`SAMPLE_INTERVAL`]
+pub struct LagMonitor {
+    samples: Arc<Mutex<Vec<LagSample>>>,
+    task: JoinHandle<Result<(), tokio_postgres::Error>>,
+}
+
+impl LagMonitor {
+    /// Start sampling over `client`, a connection to the primary that the
+    /// monitor keeps until [`LagMonitor::stop`]
+    pub fn start(client: Client) -> Self {
+        let samples = Arc::new(Mutex::new(Vec::new()));
+        let sampled = Arc::clone(&samples);
+        let task = tokio::spawn(async move {
+            let start = Instant::now();
+            let mut interval = tokio::time::interval(SAMPLE_INTERVAL);
+            loop {
+                interval.tick().await;
+                // Skipped while the subscriber reconnects
+                if let Some(lag) = lag(&client).await? {
+                    let sample = LagSample {
+                        elapsed: start.elapsed(),
+                        lag,
+                    };
+                    sampled.lock().unwrap().push(sample);
+                }
+            }
+        });
+        Self { samples, task }
+    }
+
+    /// Stop sampling; the samples taken, or the error that ended them early
+    pub async fn stop(self) -> Result<LagReport, tokio_postgres::Error> {
+        self.task.abort();
+        if let Ok(Err(e)) = self.task.await {
+            return Err(e);
+        }
+        let samples = std::mem::take(&mut *self.samples.lock().unwrap());
+        Ok(LagReport { samples })
+    }
+}
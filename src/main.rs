@@ -1,25 +1,83 @@
 //! PostgreSQL Library Benchmark - Utility Runner
 //!
 //! This binary provides utilities for setting up and testing the benchmark environment.
+//!
+//! `cargo run -- verify` checks the ORM schema mappings against the database and
+//! `cargo run -- verify-data` checks the seeded dataset against `init.sql`; both
+//! exit with an error on any mismatch. `cargo run -- seed` tops the seeded dataset
+//! up to the configured size, inserting only the rows that are missing, and
+//! `cargo run -- seed bigserial` copies it into the BIGSERIAL-keyed schema.
+//! `cargo run -- audit` lists the statements every backend executes for each
+//! benchmarked operation. `cargo run -- replay <file> [operations]` maps a
+//! recorded workload onto the benchmarked operations and replays it on every backend.
+//! `cargo run -- doctor` diagnoses the environment and exits with an error if a
+//! run would fail. `cargo run -- report diff old.json new.json` compares two
+//! `bench-runner` JSON exports benchmark by benchmark, and `cargo run -- report rank
+//! results.json...` ranks the backends of one or more exports by composite score. `cargo run --release -- run-all
+//! [config.toml]` sets up and seeds the database, runs every scenario on every
+//! backend and writes a results directory with a summary. `cargo run --release --
+//! hot-update [seconds]` runs every backend's updates for minutes against `users`
+//! copies with different fillfactors, tracking latency drift and dead tuples.
+//! `cargo run --release -- autovacuum [seconds]` keeps every backend writing until
+//! autovacuum runs and compares the write latency before, during and after it.
+//! `cargo run -- profiles` lists the database profiles and how far each is seeded,
+//! and `cargo run --release -- profiles prepare <name>...` creates and seeds them.
+//! `cargo run -- capabilities` prints which features each backend's library supports.
 
 use anyhow::Result;
-use pg_benchmark::DATABASE_URL;
+use pg_benchmark::audit::{self, OperationAudit};
+use pg_benchmark::capability;
+use pg_benchmark::doctor::{self, Summary};
+use pg_benchmark::metadata::RunMetadata;
+use pg_benchmark::replay::{self, ReplayReport, WorkloadMix};
+use pg_benchmark::report::{self, Significance};
+use pg_benchmark::score::{self, Measurement};
+use pg_benchmark::profile::{self, Profile, PROFILES};
+use pg_benchmark::run_all;
+use pg_benchmark::tags::Tag;
+use pg_benchmark::interrupt::{Interrupt, EXIT_INTERRUPTED};
+use pg_benchmark::runner::{self, ExportConfig, Progress, RunnerConfig};
+use std::io::Write;
+use pg_benchmark::{
+    bench_clorinde::ClorindeBench, bench_diesel::DieselBench, bench_seaorm::SeaOrmBench, bench_seaquery::SeaQueryBench,
+    autovacuum::{self, Phase, VacuumMonitor}, bench_sqlx::{SqlxBench, SqlxMacroBench}, bench_tokio_postgres::{TokioPostgresBench, TokioPostgresPreparedBench}, bench_wtx::WtxBench, database_url,
+    hot_update::{self, Window, FILLFACTORS}, primary_key, DatabaseBenchmark, scenario::{self, Category, SCENARIOS}, seed::SeedConfig, seeder, verify,
+};
 
 #[tokio::main]
 async fn main() -> Result<()> {
     tracing_subscriber::fmt::init();
+
+    match std::env::args().nth(1).as_deref() {
+        Some("verify") => return verify_schema().await,
+        Some("verify-data") => return verify_data().await,
+        Some("seed") => return seed().await,
+        Some("audit") => return audit_statements().await,
+        Some("replay") => return replay_workload().await,
+        Some("doctor") => return diagnose().await,
+        Some("report") => return report(),
+        Some("run-all") => return run_everything().await,
+        Some("hot-update") => return hot_update_drift().await,
+        Some("autovacuum") => return autovacuum_soak().await,
+        Some("profiles") => return profiles().await,
+        Some("capabilities") => return print_capabilities(),
+        _ => {}
+    }
     
     println!("PostgreSQL Library Benchmark Suite");
     println!("===================================");
     println!();
     println!("Libraries being benchmarked:");
-    println!("  - tokio-postgres (async low-level driver)");
-    println!("  - sqlx (compile-time verified SQL)");
+    println!("  - tokio-postgres (async low-level driver), also with prepared statements");
+    println!("  - postgres (the sync driver over tokio-postgres, in the sync vs async group)");
+    println!("  - sqlx (SQL strings), also with compile-time verified query! macros");
     println!("  - sea-orm (async ORM)");
+    println!("  - sea-query (SeaORM's query builder, run through sqlx)");
     println!("  - diesel (sync ORM)");
     println!("  - clorinde (generated type-safe queries)");
+    println!("  - wtx (newer async driver with its own codecs)");
     println!();
-    println!("Database URL: {}", DATABASE_URL);
+    println!("Database URL: {}", database_url());
     println!();
     println!("To run benchmarks:");
     println!("  cargo bench");
@@ -30,6 +88,58 @@ async fn main() -> Result<()> {
     println!("  cargo bench -- join");
     println!("  cargo bench -- heavy");
     println!();
+    println!("To run the groups and runner scenarios with some of the tags {}:", tag_names());
+    println!("  BENCH_TAGS=join,write cargo bench");
+    println!();
+    println!("Scenarios (benchmarked and audited):");
+    for category in Category::ALL {
+        let names: Vec<&str> = scenario::in_category(category).map(|s| s.name).collect();
+        let tags: Vec<&str> = category.tags().iter().map(Tag::name).collect();
+        println!("  {:<12} {} [{}]", category, names.join(", "), tags.join(", "));
+    }
+    println!();
+    println!("To check the ORM schema mappings against the database:");
+    println!("  cargo run -- verify");
+    println!();
+    println!("To check the seeded dataset matches init.sql:");
+    println!("  cargo run -- verify-data");
+    println!();
+    println!("To top the seeded dataset up to the configured size:");
+    println!("  cargo run -- seed");
+    println!();
+    println!("To create and seed a database per profile (small, medium, large), then run one with BENCH_PROFILE:");
+    println!("  cargo run --release -- profiles prepare small");
+    println!();
+    println!("To copy the seeded dataset into the BIGSERIAL-keyed schema for the primary_key benchmarks:");
+    println!("  cargo run -- seed bigserial");
+    println!();
+    println!("To compare the SQL each backend executes (needs pg_stat_statements):");
+    println!("  cargo run -- audit");
+    println!();
+    println!("To replay a recorded workload (server log or pg_stat_statements dump) on every backend:");
+    println!("  cargo run --release -- replay postgresql.log [operations]");
+    println!();
+    println!("To check the environment is ready for a run:");
+    println!("  cargo run -- doctor");
+    println!();
+    println!("To set up, seed, run every scenario and write a results directory in one go:");
+    println!("  cargo run --release -- run-all [bench-runner config]");
+    println!();
+    println!("To run each backend's updates for minutes on users copies with fillfactor 100 and 70:");
+    println!("  cargo run --release -- hot-update [seconds per backend and fillfactor]");
+    println!();
+    println!("To compare each backend's write latency before, during and after autovacuum:");
+    println!("  cargo run --release -- autovacuum [seconds per backend]");
+    println!();
+    println!("To compare two bench-runner JSON exports, e.g. before and after a dependency bump:");
+    println!("  cargo run -- report diff old.json new.json");
+    println!();
+    println!("To rank the backends of bench-runner JSON exports by composite score:");
+    println!("  cargo run -- report rank results.json...");
+    println!();
+    println!("To load-test the scenarios with concurrent workers (settings in bench-runner.toml):");
+    println!("  cargo run --release --bin bench-runner");
+    println!();
     
     // Test database connectivity
     println!("Testing database connection...");
@@ -41,14 +151,696 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-async fn test_connection() -> Result<()> {
-    let (client, connection) = tokio_postgres::connect(DATABASE_URL, tokio_postgres::NoTls).await?;
+fn tag_names() -> String {
+    Tag::ALL.iter().map(Tag::name).collect::<Vec<_>>().join(", ")
+}
+
+async fn connect() -> Result<tokio_postgres::Client> {
+    let (client, connection) = tokio_postgres::connect(&database_url(), tokio_postgres::NoTls).await?;
     
     tokio::spawn(async move {
         if let Err(e) = connection.await {
             eprintln!("connection error: {}", e);
         }
     });
+
+    Ok(client)
+}
+
+async fn diagnose() -> Result<()> {
+    println!("Diagnosing the benchmark environment at {}", database_url());
+
+    let mut checks = vec![
+        doctor::connectivity::<TokioPostgresBench>("tokio_postgres").await,
+        doctor::connectivity::<TokioPostgresPreparedBench>("tokio_postgres_prepared").await,
+        doctor::connectivity::<SqlxBench>("sqlx").await,
+        doctor::connectivity::<SqlxMacroBench>("sqlx_macro").await,
+        doctor::connectivity::<SeaOrmBench>("sea_orm").await,
+        doctor::connectivity::<SeaQueryBench>("sea_query").await,
+        doctor::connectivity::<DieselBench>("diesel").await,
+        doctor::connectivity::<ClorindeBench>("clorinde").await,
+        doctor::connectivity::<WtxBench>("wtx").await,
+    ];
+    // The server checks need the raw driver; its failure is already reported above
+    if let Ok(client) = connect().await {
+        checks.extend(doctor::server_settings(&client).await?);
+        checks.extend(doctor::extensions(&client).await?);
+        checks.push(doctor::leftover_rows(&client).await?);
+        checks.push(doctor::secondary_indexes(&client).await?);
+        checks.push(doctor::rtt_check(doctor::round_trip_time(&client).await?));
+    }
+    for check in &checks {
+        println!("  {}", check);
+    }
+
+    let summary = Summary::of(&checks);
+    println!();
+    println!("{}", summary);
+    if !summary.ready() {
+        anyhow::bail!("{} check(s) failed", summary.failed);
+    }
+    Ok(())
+}
+
+async fn verify_schema() -> Result<()> {
+    println!("Verifying diesel and sea-orm schema mappings against {}", database_url());
+
+    let client = connect().await?;
+    let mismatches = verify::check_schema(&client).await?;
+    if !mismatches.is_empty() {
+        for mismatch in &mismatches {
+            println!("  {}", mismatch);
+        }
+        anyhow::bail!("{} schema mismatch(es); benchmark results would not be comparable", mismatches.len());
+    }
+
+    println!("Schema matches the database");
+    Ok(())
+}
+
+async fn verify_data() -> Result<()> {
+    println!("Verifying seeded dataset in {}", database_url());
+
+    let client = connect().await?;
+    let checks = verify::check_seed_data(&client).await?;
+    for check in &checks {
+        let mark = if check.passed() { "ok" } else { "MISMATCH" };
+        println!("  [{}] {}", mark, check);
+    }
+
+    let failed = checks.iter().filter(|c| !c.passed()).count();
+    if failed > 0 {
+        anyhow::bail!("{} seed data check(s) failed; re-create the database from init.sql", failed);
+    }
+
+    println!("Seeded dataset matches init.sql");
+    Ok(())
+}
+
+async fn seed() -> Result<()> {
+    match std::env::args().nth(2).as_deref() {
+        None => {}
+        Some("bigserial") => return seed_bigserial().await,
+        Some(other) => anyhow::bail!("unknown dataset {:?}; usage: cargo run -- seed [bigserial]", other),
+    }
+
+    let config = SeedConfig::get();
+    println!(
+        "Topping up {} to {} users, {} posts and {} comments",
+        database_url(),
+        config.users,
+        config.posts(),
+        config.comments()
+    );
+
+    let inserted = seeder::top_up(&database_url(), config).await?;
+    if inserted.is_empty() {
+        println!("Nothing to insert, the dataset is complete");
+    } else {
+        println!("  inserted {}", inserted);
+    }
+    println!("  now {}", seeder::count(&connect().await?).await?);
+    Ok(())
+}
+
+fn print_capabilities() -> Result<()> {
+    println!("Backend capabilities");
+    println!("====================");
+    let backends: Vec<_> = runner::BACKENDS
+        .iter()
+        .filter(|b| **b != "null")
+        .filter_map(|b| Some((*b, runner::capabilities(b)?)))
+        .collect();
+    print!("{}", capability::matrix(&backends));
+    Ok(())
+}
+
+async fn profiles() -> Result<()> {
+    let names: Vec<String> = std::env::args().skip(3).collect();
+    match std::env::args().nth(2).as_deref() {
+        None => list_profiles().await,
+        Some("prepare") if !names.is_empty() => {
+            for name in &names {
+                prepare_profile(Profile::named(name).map_err(|e| anyhow::anyhow!(e))?).await?;
+            }
+            Ok(())
+        }
+        _ => anyhow::bail!("usage: cargo run -- profiles [prepare <name>...]"),
+    }
+}
+
+async fn list_profiles() -> Result<()> {
+    println!("Database profiles on {}", pg_benchmark::server_url());
+    let admin = TokioPostgresBench::connect_to(&pg_benchmark::server_url()).await?;
+    for p in PROFILES {
+        let selected = if profile::selected() == Some(p) { " (selected)" } else { "" };
+        println!("  {:<8} {:<18} {:>9} users{}", p.name, p.database, p.users, selected);
+        let exists: bool = admin
+            .query_one("SELECT EXISTS (SELECT 1 FROM pg_database WHERE datname = $1)", &[&p.database])
+            .await?
+            .get(0);
+        if !exists {
+            println!("  {:<8} not created; cargo run --release -- profiles prepare {}", "", p.name);
+            continue;
+        }
+        let url = p.url(&pg_benchmark::server_url());
+        match TokioPostgresBench::connect_to(&url).await {
+            Ok(client) => match seeder::count(&client).await {
+                Ok(rows) => println!("  {:<8} {}", "", rows),
+                Err(_) => println!("  {:<8} no schema yet", ""),
+            },
+            Err(e) => println!("  {:<8} can't connect: {}", "", e),
+        }
+    }
+    Ok(())
+}
+
+async fn prepare_profile(p: &Profile) -> Result<()> {
+    let url = p.url(&pg_benchmark::server_url());
+    println!("Preparing profile {} in {} ({} users)", p.name, p.database, p.users);
+    let admin = TokioPostgresBench::connect_to(&pg_benchmark::server_url()).await?;
+    if profile::create_database(&admin, p).await? {
+        println!("  created the database");
+    }
+    let inserted = profile::prepare(&url, p).await?;
+    if inserted.is_empty() {
+        println!("  nothing to insert, the dataset is complete");
+    } else {
+        println!("  inserted {}", inserted);
+    }
+    Ok(())
+}
+
+async fn seed_bigserial() -> Result<()> {
+    println!("Copying the dataset in {} into the {} schema", database_url(), primary_key::SCHEMA);
+    let users = primary_key::seed(&connect().await?).await?;
+    println!("  copied {} users with their posts and comments", users);
+    Ok(())
+}
+
+async fn audit_statements() -> Result<()> {
+    println!("Auditing executed statements per backend in {}", database_url());
+
+    let client = connect().await?;
+    if !audit::available(&client).await {
+        anyhow::bail!(
+            "pg_stat_statements is not available; start PostgreSQL with \
+             -c shared_preload_libraries=pg_stat_statements"
+        );
+    }
+
+    let backends = [
+        audit::audit_backend::<TokioPostgresBench>("tokio_postgres", &client).await?,
+        audit::audit_backend::<TokioPostgresPreparedBench>("tokio_postgres_prepared", &client).await?,
+        audit::audit_backend::<SqlxBench>("sqlx", &client).await?,
+        audit::audit_backend::<SqlxMacroBench>("sqlx_macro", &client).await?,
+        audit::audit_backend::<SeaOrmBench>("sea_orm", &client).await?,
+        audit::audit_backend::<SeaQueryBench>("sea_query", &client).await?,
+        audit::audit_backend::<DieselBench>("diesel", &client).await?,
+        audit::audit_backend::<ClorindeBench>("clorinde", &client).await?,
+        audit::audit_backend::<WtxBench>("wtx", &client).await?,
+    ];
+
+    // tokio-postgres sends the hand-written SQL directly, so it is the baseline
+    for (index, scenario) in SCENARIOS.iter().enumerate() {
+        println!();
+        println!("{} ({})", scenario.name, scenario.category);
+        let baseline = &backends[0][index];
+        for audits in &backends {
+            print_audit(&audits[index], baseline);
+        }
+    }
+
+    Ok(())
+}
+
+/// Operations replayed per backend unless given on the command line
+const REPLAY_OPERATIONS: usize = 1000;
+
+async fn run_everything() -> Result<()> {
+    let config = match std::env::args().nth(2) {
+        Some(path) => {
+            let text = std::fs::read_to_string(&path)?;
+            RunnerConfig::from_toml(&text).map_err(|e| anyhow::anyhow!("{}: {}", path, e))?
+        }
+        None => run_all::default_config(),
+    };
+    if let Some(name) = &config.profile {
+        profile::select(name).map_err(|e| anyhow::anyhow!(e))?;
+    }
+    if !config.operations.is_empty() {
+        anyhow::bail!("run-all runs the scenarios one at a time; run workloads with bench-runner");
+    }
+    let scenarios = config.scenarios().map_err(|e| anyhow::anyhow!(e))?;
+    let backends = config.backends().map_err(|e| anyhow::anyhow!(e))?;
+    let seed = match &config.dataset {
+        Some(dataset) => dataset.seed_config().map_err(|e| anyhow::anyhow!(e))?,
+        None => *SeedConfig::get(),
+    };
+    let started = chrono::Local::now();
+
+    println!("Running the benchmark suite against {}", database_url());
+    println!();
+    println!("[1/4] Schema");
+    let client = connect().await?;
+    if run_all::create_schema(&client).await? {
+        println!("  created from init.sql");
+    } else {
+        println!("  already present");
+    }
+    let mismatches = verify::check_schema(&client).await?;
+    if !mismatches.is_empty() {
+        for mismatch in &mismatches {
+            println!("  {}", mismatch);
+        }
+        anyhow::bail!("{} schema mismatch(es); benchmark results would not be comparable", mismatches.len());
+    }
+
+    println!("[2/4] Seeding {} users, ratio {}", seed.users, seed.ratio);
+    let inserted = seeder::top_up(&database_url(), &seed).await?;
+    if inserted.is_empty() {
+        println!("  nothing to insert, the dataset is complete");
+    } else {
+        println!("  inserted {}", inserted);
+    }
+    let mut metadata = RunMetadata::collect(&client).await?;
+    let dir = run_all::results_dir(started);
+    // Rewritten after every run, so an interrupted run keeps its results
+    let exports = ExportConfig {
+        json: Some(dir.join("results.json")),
+        csv: Some(dir.join("results.csv")),
+    };
+
+    println!(
+        "[3/4] Running {} scenario(s) on {}: {}s warm-up and {}s measured per run, about {:.1} min",
+        scenarios.len(),
+        backends.join(", "),
+        config.warmup_secs,
+        config.duration_secs,
+        run_all::estimated_secs(&config, &scenarios, &backends) / 60.0
+    );
+    let interrupt = Interrupt::listen();
+    let mut active = None;
+    let mut results = Vec::new();
+    'scenarios: for scenario in scenarios {
+        println!("  {} (size {})", scenario.name, scenario.typical_size());
+        for &backend in &backends {
+            if interrupt.requested() {
+                break 'scenarios;
+            }
+            if !scenario.runs_on(backend) {
+                continue;
+            }
+            active = Some(backend);
+            let mut progress = |p: &Progress| {
+                eprint!("\r    {:<24} {:>5.0}s  {:>10.1} ops/s", backend, p.elapsed.as_secs_f64(), p.throughput());
+                let _ = std::io::stderr().flush();
+            };
+            let result = runner::run_backend(&config, &WorkloadMix::single(scenario), backend, &mut progress).await;
+            eprint!("\r\x1b[K");
+            let result = result.map_err(|e| anyhow::anyhow!("{} on {}: {}", scenario.name, backend, e))?;
+            for r in &result {
+                println!(
+                    "    {:<24} {:>10.1} ops/s  p50 {:>9.1}us  p99 {:>9.1}us  {} error(s)  cv {:.2}{}",
+                    backend,
+                    r.throughput,
+                    r.p50_us,
+                    r.p99_us,
+                    r.errors,
+                    r.cv,
+                    if r.noisy { " noisy" } else { "" }
+                );
+            }
+            results.extend(result);
+            exports.write(&metadata, &config.noise.keep(results.clone()).0)?;
+        }
+    }
+
+    if interrupt.requested() {
+        println!("  interrupted after {} result(s)", results.len());
+        if let Some(backend) = active {
+            if let Err(e) = runner::cleanup_backend(backend).await {
+                println!("  cleaning up after {} failed: {}", backend, e);
+            }
+        }
+        metadata.interrupted = true;
+    }
+
+    let (results, dropped) = config.noise.keep(results);
+    for r in &dropped {
+        println!("  dropped {} on {} as noisy (cv {:.2})", r.scenario, r.backend, r.cv);
+    }
+
+    println!("[4/4] Writing the report to {}", dir.display());
+    let entries = metadata.entries();
+    let mut environment = vec![("Date", started.format("%Y-%m-%d %H:%M:%S %Z").to_string())];
+    environment.extend(entries.iter().map(|(name, value)| (name.as_str(), value.clone())));
+    exports.write(&metadata, &results)?;
+    std::fs::write(dir.join("SUMMARY.md"), run_all::summary(&config, &environment, &results))?;
+    println!("  results.json, results.csv and SUMMARY.md");
+
+    let violations = config.violations(&results);
+    for violation in &violations {
+        println!("  SLO violation: {}", violation);
+    }
+    if !violations.is_empty() {
+        anyhow::bail!("{} SLO violation(s)", violations.len());
+    }
+    if interrupt.requested() {
+        std::process::exit(EXIT_INTERRUPTED);
+    }
+    Ok(())
+}
+
+const REPORT_USAGE: &str = "usage: cargo run -- report diff <old.json> <new.json> | report rank <results.json>...";
+
+fn report() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(2).collect();
+    match args.split_first() {
+        Some((command, paths)) if command == "diff" => report_diff(paths),
+        Some((command, paths)) if command == "rank" => report_rank(paths),
+        Some((command, _)) => anyhow::bail!("unknown report {:?}; {}", command, REPORT_USAGE),
+        None => anyhow::bail!(REPORT_USAGE),
+    }
+}
+
+fn load_export(path: &String) -> Result<Vec<report::ExportedResult>> {
+    report::load(path.as_ref()).map_err(|e| anyhow::anyhow!("reading {}: {}", path, e))
+}
+
+fn report_diff(paths: &[String]) -> Result<()> {
+    let [old, new] = paths else {
+        anyhow::bail!(REPORT_USAGE);
+    };
+    let diff = report::diff(&load_export(old)?, &load_export(new)?);
+
+    println!("Changes from {} to {}", old, new);
+    println!(
+        "{:<56} {:>9} {:>9} {:>9} {:>9}  significance",
+        "benchmark", "ops/s", "mean", "p50", "p99"
+    );
+    for comparison in &diff.compared {
+        println!("{}", comparison);
+    }
+    for benchmark in &diff.removed {
+        println!("{:<56} only in {}", benchmark, old);
+    }
+    for benchmark in &diff.added {
+        println!("{:<56} only in {}", benchmark, new);
+    }
+    println!();
+    println!(
+        "{} improved, {} regressed, {} unchanged, {} unknown",
+        diff.count(Significance::Improved),
+        diff.count(Significance::Regressed),
+        diff.count(Significance::NoChange),
+        diff.count(Significance::Unknown)
+    );
+    if diff.noisy() > 0 {
+        println!("{} comparison(s) involve a noisy run and may not be reproducible", diff.noisy());
+    }
+    Ok(())
+}
+
+fn report_rank(paths: &[String]) -> Result<()> {
+    if paths.is_empty() {
+        anyhow::bail!(REPORT_USAGE);
+    }
+    let mut results = Vec::new();
+    for path in paths {
+        results.extend(load_export(path)?);
+    }
+    let scores = score::rank(results.iter().map(Measurement::from));
+    if scores.is_empty() {
+        anyhow::bail!("no benchmark in {} ran on more than one backend", paths.join(", "));
+    }
+
+    println!("Geometric mean slowdown against the fastest backend of each benchmark (1.00 = fastest)");
+    print!("{:<4} {:<24} {:>8}", "rank", "backend", "overall");
+    for tag in score::CATEGORIES {
+        print!(" {:>11}", tag);
+    }
+    println!(" {:>8} {:>10}", "fastest", "benchmarks");
+    for (rank, backend) in scores.iter().enumerate() {
+        print!("{:<4} {:<24} {:>8.2}", rank + 1, backend.backend, backend.overall);
+        for tag in score::CATEGORIES {
+            match backend.category(tag) {
+                Some(slowdown) => print!(" {:>11.2}", slowdown),
+                None => print!(" {:>11}", "-"),
+            }
+        }
+        println!(" {:>8} {:>10}", backend.wins, backend.benchmarks);
+    }
+    Ok(())
+}
+
+async fn replay_workload() -> Result<()> {
+    let path = std::env::args()
+        .nth(2)
+        .ok_or_else(|| anyhow::anyhow!("usage: cargo run -- replay <log or pg_stat_statements dump> [operations]"))?;
+    let operations = match std::env::args().nth(3) {
+        Some(n) => n.parse()?,
+        None => REPLAY_OPERATIONS,
+    };
+
+    let text = std::fs::read_to_string(&path)?;
+    let mix = WorkloadMix::from_recorded(&replay::parse(&text));
+    if mix.entries.is_empty() {
+        anyhow::bail!("no statement in {} maps onto a benchmarked operation", path);
+    }
+
+    let (matched, unmatched) = (mix.matched_calls(), mix.unmatched_calls());
+    println!(
+        "Workload from {}: {} statement(s) mapped, {} unmatched, {} transaction control or session",
+        path, matched, unmatched, mix.ignored
+    );
+    for entry in &mix.entries {
+        println!(
+            "  {:>5.1}%  {} (size {})",
+            entry.calls as f64 * 100.0 / matched as f64,
+            entry.scenario.name,
+            entry.size
+        );
+    }
+    if !mix.unmatched.is_empty() {
+        println!("Not replayed:");
+        for (statement, calls) in mix.unmatched.iter().take(10) {
+            println!("  {:>6}x  {}", calls, statement);
+        }
+    }
+
+    println!();
+    println!("Replaying {} operations per backend against {}", operations, database_url());
+    let reports = [
+        replay::replay::<TokioPostgresBench>(&mix, operations, "tokio_postgres").await.map_err(replay_error)?,
+        replay::replay::<TokioPostgresPreparedBench>(&mix, operations, "tokio_postgres_prepared")
+            .await
+            .map_err(replay_error)?,
+        replay::replay::<SqlxBench>(&mix, operations, "sqlx").await.map_err(replay_error)?,
+        replay::replay::<SqlxMacroBench>(&mix, operations, "sqlx_macro").await.map_err(replay_error)?,
+        replay::replay::<SeaOrmBench>(&mix, operations, "sea_orm").await.map_err(replay_error)?,
+        replay::replay::<SeaQueryBench>(&mix, operations, "sea_query").await.map_err(replay_error)?,
+        replay::replay::<DieselBench>(&mix, operations, "diesel").await.map_err(replay_error)?,
+        replay::replay::<ClorindeBench>(&mix, operations, "clorinde").await.map_err(replay_error)?,
+        replay::replay::<WtxBench>(&mix, operations, "wtx").await.map_err(replay_error)?,
+    ];
+    for report in &reports {
+        print_replay(report);
+    }
+
+    Ok(())
+}
+
+async fn hot_update_drift() -> Result<()> {
+    let duration = match std::env::args().nth(2) {
+        Some(secs) => std::time::Duration::from_secs(secs.parse()?),
+        None => hot_update::DEFAULT_DURATION,
+    };
+    println!(
+        "Updating the {} newest users for {}s per backend on copies of users with fillfactor {:?}",
+        hot_update::UPDATED_USERS,
+        duration.as_secs(),
+        FILLFACTORS
+    );
+
+    let client = connect().await?;
+    let mut drifts = Vec::new();
+    drifts.extend(hot_update_runs::<TokioPostgresBench>("tokio_postgres", &client, duration).await?);
+    drifts.extend(hot_update_runs::<TokioPostgresPreparedBench>("tokio_postgres_prepared", &client, duration).await?);
+    drifts.extend(hot_update_runs::<SqlxBench>("sqlx", &client, duration).await?);
+    drifts.extend(hot_update_runs::<SeaOrmBench>("sea_orm", &client, duration).await?);
+    drifts.extend(hot_update_runs::<DieselBench>("diesel", &client, duration).await?);
+    drifts.extend(hot_update_runs::<ClorindeBench>("clorinde", &client, duration).await?);
+    for fillfactor in FILLFACTORS {
+        hot_update::drop(&client, fillfactor).await?;
+    }
+
+    println!();
+    println!("Latency drift, last window's mean over the first's:");
+    for (backend, fillfactor, drift) in drifts {
+        match drift {
+            Some(drift) => println!("  {:<24} fillfactor {:>3}  {:.2}x", backend, fillfactor, drift),
+            None => println!("  {:<24} fillfactor {:>3}  too short to tell", backend, fillfactor),
+        }
+    }
+    Ok(())
+}
+
+/// One run of `B` per fillfactor, each on a fresh copy, printing its windows
+async fn hot_update_runs<B: DatabaseBenchmark>(
+    backend: &'static str,
+    client: &tokio_postgres::Client,
+    duration: std::time::Duration,
+) -> Result<Vec<(&'static str, u32, Option<f64>)>> {
+    let mut drifts = Vec::new();
+    for fillfactor in FILLFACTORS {
+        hot_update::create(client, fillfactor).await?;
+        let url = hot_update::database_url(fillfactor);
+        let conn = B::connect_to(&url).await.map_err(hot_update_error)?;
+        let on_copy = TokioPostgresBench::connect_to(&url).await?;
+        let users = TokioPostgresBench::select_users_limit(&on_copy, hot_update::UPDATED_USERS).await?;
+        let ids: Vec<_> = users.iter().map(|u| u.id).collect();
+
+        println!();
+        println!("{} (fillfactor {})", backend, fillfactor);
+        let mut windows: Vec<Window> = Vec::new();
+        let mut next = 0;
+        let start = std::time::Instant::now();
+        while start.elapsed() < duration {
+            let window = hot_update::WINDOW.min(duration.saturating_sub(start.elapsed()));
+            let (updates, mean_latency) = hot_update::update_for::<B>(&conn, &ids, &mut next, window)
+                .await
+                .map_err(hot_update_error)?;
+            let window = Window {
+                elapsed: start.elapsed(),
+                updates,
+                mean_latency,
+                stats: hot_update::table_stats(client, fillfactor).await?,
+            };
+            println!("  {}", window);
+            windows.push(window);
+        }
+        drifts.push((backend, fillfactor, hot_update::drift(&windows)));
+    }
+    Ok(drifts)
+}
+
+async fn autovacuum_soak() -> Result<()> {
+    let duration = match std::env::args().nth(2) {
+        Some(secs) => std::time::Duration::from_secs(secs.parse()?),
+        None => autovacuum::DEFAULT_DURATION,
+    };
+    let client = connect().await?;
+    let enabled: String = client.query_one("SHOW autovacuum", &[]).await?.get(0);
+    if enabled != "on" {
+        anyhow::bail!("autovacuum is off on the server, so there is nothing to wait for");
+    }
+    println!(
+        "Inserting, renaming and deleting users for {}s per backend, sampling autovacuum every {} ms",
+        duration.as_secs(),
+        autovacuum::SAMPLE_INTERVAL.as_millis()
+    );
+
+    autovacuum_backend::<TokioPostgresBench>("tokio_postgres", &client, duration).await?;
+    autovacuum_backend::<TokioPostgresPreparedBench>("tokio_postgres_prepared", &client, duration).await?;
+    autovacuum_backend::<SqlxBench>("sqlx", &client, duration).await?;
+    autovacuum_backend::<SeaOrmBench>("sea_orm", &client, duration).await?;
+    autovacuum_backend::<DieselBench>("diesel", &client, duration).await?;
+    autovacuum_backend::<ClorindeBench>("clorinde", &client, duration).await?;
+    Ok(())
+}
+
+/// Soak `B` and print its write latency per phase
+async fn autovacuum_backend<B: DatabaseBenchmark>(
+    backend: &str,
+    client: &tokio_postgres::Client,
+    duration: std::time::Duration,
+) -> Result<()> {
+    let conn = B::connect().await.map_err(autovacuum_error)?;
+    let vacuums_before = autovacuum::autovacuum_count(client).await?;
+    let monitor = VacuumMonitor::start(connect().await?);
+    let soaked = autovacuum::soak::<B>(&conn, &monitor, duration).await;
+    let sampled = monitor.stop().await?;
+    B::cleanup(&conn).await.map_err(autovacuum_error)?;
+    let latencies = soaked.map_err(autovacuum_error)?;
+    let vacuums = autovacuum::autovacuum_count(client).await? - vacuums_before;
+
+    println!();
+    println!(
+        "{}: {} autovacuum(s) of users, {} caught by the samples, running in {:.1}% of them",
+        backend,
+        vacuums,
+        sampled.vacuums_seen,
+        sampled.active_samples as f64 * 100.0 / sampled.samples.max(1) as f64
+    );
+    for (phase, latencies) in Phase::ALL.iter().zip(&latencies) {
+        println!(
+            "  {:<8} {:>8} writes {:>8.3} ms mean {:>8.3} ms p99 {:>8.3} ms max",
+            phase,
+            latencies.writes(),
+            latencies.mean().as_secs_f64() * 1000.0,
+            latencies.quantile(0.99).as_secs_f64() * 1000.0,
+            latencies.quantile(1.0).as_secs_f64() * 1000.0
+        );
+    }
+    Ok(())
+}
+
+fn autovacuum_error(e: impl std::fmt::Debug) -> anyhow::Error {
+    anyhow::anyhow!("autovacuum soak failed: {:?}", e)
+}
+
+fn hot_update_error(e: impl std::fmt::Debug) -> anyhow::Error {
+    anyhow::anyhow!("hot-update failed: {:?}", e)
+}
+
+fn replay_error(e: impl std::fmt::Debug) -> anyhow::Error {
+    anyhow::anyhow!("replay failed: {:?}", e)
+}
+
+fn print_replay(report: &ReplayReport) {
+    println!();
+    println!(
+        "  {:<24} {:>8.1} ms  {:>8.1} ops/s  {} error(s)",
+        report.backend,
+        report.elapsed.as_secs_f64() * 1000.0,
+        report.throughput(),
+        report.errors()
+    );
+    for entry in &report.entries {
+        println!(
+            "      {:>6}x  {:>9.1}us mean  {} (size {})",
+            entry.operations,
+            entry.mean().as_secs_f64() * 1e6,
+            entry.scenario,
+            entry.size
+        );
+    }
+    if let Some(error) = &report.first_error {
+        println!("      first error: {}", error);
+    }
+}
+
+fn print_audit(audit: &OperationAudit, baseline: &OperationAudit) {
+    let differences = audit.differences(baseline);
+    if differences.is_empty() {
+        println!("  {:<24} {} statement(s)", audit.backend, audit.total_calls());
+    } else {
+        println!(
+            "  {:<24} {} statement(s)  <-- {} vs {}",
+            audit.backend,
+            audit.total_calls(),
+            differences.join(", "),
+            baseline.backend
+        );
+    }
+    for statement in &audit.statements {
+        println!("      {:>3}x  {}", statement.calls, statement.query);
+    }
+}
+
+async fn test_connection() -> Result<()> {
+    let client = connect().await?;
     
     let row = client.query_one("SELECT COUNT(*) as count FROM users", &[]).await?;
     let count: i64 = row.get("count");
@@ -0,0 +1,450 @@
+//! Conversions from driver rows and ORM models into the shared models
+//!
+//! Every backend builds its `User`, `Post` and `Comment` values here, so a new
+//! column is mapped in one place. Raw rows (tokio-postgres and sqlx) are read
+//! by column name: a plain query uses the table's own names, and a join aliases
+//! the names its tables share, which [`UserColumns`], [`PostColumns`] and
+//! [`CommentColumns`] describe. wtx records are read the same way, with its
+//! `timestamptz` values converted from wtx's own calendar type. [`ModelRow::user_by_index`] reads a user by
+//! position instead, for the `column_access` benchmarks. ORM models convert
+//! with `From`.
+
+use crate::bench_diesel::{DieselComment, DieselPost, DieselUser};
+use crate::bench_seaorm::{comments, posts, users};
+use crate::{Comment, Post, User};
+use chrono::{DateTime, Utc};
+use clorinde_queries::queries as clorinde;
+use wtx::calendar::{DateTime as WtxDateTime, Utc as WtxUtc};
+use wtx::database::client::postgres::PostgresRecord;
+use wtx::database::{Record, ValueIdent};
+
+/// Names of the `users` columns a join aliases
+#[derive(Debug, Clone, Copy)]
+pub struct UserColumns {
+    pub id: &'static str,
+    pub created_at: &'static str,
+    pub updated_at: &'static str,
+}
+
+impl UserColumns {
+    /// `SELECT id, ..., created_at, updated_at FROM users`
+    pub const TABLE: Self = Self { id: "id", created_at: "created_at", updated_at: "updated_at" };
+    /// `u.id AS user_id, ..., u.created_at AS user_created_at, u.updated_at AS user_updated_at`
+    pub const JOINED: Self = Self { id: "user_id", created_at: "user_created_at", updated_at: "user_updated_at" };
+}
+
+/// Names of the `posts` columns a join aliases
+#[derive(Debug, Clone, Copy)]
+pub struct PostColumns {
+    pub id: &'static str,
+    pub created_at: &'static str,
+    pub updated_at: &'static str,
+}
+
+impl PostColumns {
+    pub const TABLE: Self = Self { id: "id", created_at: "created_at", updated_at: "updated_at" };
+    /// `p.id AS post_id, ..., p.created_at AS post_created_at, p.updated_at AS post_updated_at`
+    pub const JOINED: Self = Self { id: "post_id", created_at: "post_created_at", updated_at: "post_updated_at" };
+}
+
+/// Names of the `comments` columns a join aliases
+#[derive(Debug, Clone, Copy)]
+pub struct CommentColumns {
+    pub id: &'static str,
+    pub user_id: &'static str,
+    pub content: &'static str,
+    pub created_at: &'static str,
+}
+
+impl CommentColumns {
+    pub const TABLE: Self = Self { id: "id", user_id: "user_id", content: "content", created_at: "created_at" };
+    /// `c.id AS comment_id, c.user_id AS comment_user_id, c.content AS comment_content, c.created_at AS comment_created_at`
+    pub const JOINED: Self = Self {
+        id: "comment_id",
+        user_id: "comment_user_id",
+        content: "comment_content",
+        created_at: "comment_created_at",
+    };
+}
+
+/// A driver row the shared models can be read from by column name
+pub trait ModelRow {
+    type Error;
+
+    fn user(&self, columns: &UserColumns) -> Result<User, Self::Error>;
+
+    /// [`ModelRow::user`] reading the columns by position rather than looking
+    /// each name up, for rows in `users` column order (`SELECT id, username,
+    /// email, first_name, last_name, age, created_at, updated_at`)
+    fn user_by_index(&self) -> Result<User, Self::Error>;
+
+    fn post(&self, columns: &PostColumns) -> Result<Post, Self::Error>;
+
+    fn comment(&self, columns: &CommentColumns) -> Result<Comment, Self::Error>;
+}
+
+/// Implement [`ModelRow`], and `TryFrom` with the tables' own column names,
+/// for a row type with a `try_get` by name or position that decodes any column type
+macro_rules! model_row {
+    ($row:ty, $error:ty) => {
+        impl ModelRow for $row {
+            type Error = $error;
+
+            fn user(&self, columns: &UserColumns) -> Result<User, $error> {
+                Ok(User {
+                    id: self.try_get(columns.id)?,
+                    username: self.try_get("username")?,
+                    email: self.try_get("email")?,
+                    first_name: self.try_get("first_name")?,
+                    last_name: self.try_get("last_name")?,
+                    age: self.try_get("age")?,
+                    created_at: self.try_get(columns.created_at)?,
+                    updated_at: self.try_get(columns.updated_at)?,
+                })
+            }
+
+            fn user_by_index(&self) -> Result<User, $error> {
+                Ok(User {
+                    id: self.try_get(0_usize)?,
+                    username: self.try_get(1_usize)?,
+                    email: self.try_get(2_usize)?,
+                    first_name: self.try_get(3_usize)?,
+                    last_name: self.try_get(4_usize)?,
+                    age: self.try_get(5_usize)?,
+                    created_at: self.try_get(6_usize)?,
+                    updated_at: self.try_get(7_usize)?,
+                })
+            }
+
+            fn post(&self, columns: &PostColumns) -> Result<Post, $error> {
+                Ok(Post {
+                    id: self.try_get(columns.id)?,
+                    user_id: self.try_get("user_id")?,
+                    title: self.try_get("title")?,
+                    content: self.try_get("content")?,
+                    status: self.try_get("status")?,
+                    view_count: self.try_get("view_count")?,
+                    created_at: self.try_get(columns.created_at)?,
+                    updated_at: self.try_get(columns.updated_at)?,
+                })
+            }
+
+            fn comment(&self, columns: &CommentColumns) -> Result<Comment, $error> {
+                Ok(Comment {
+                    id: self.try_get(columns.id)?,
+                    post_id: self.try_get("post_id")?,
+                    user_id: self.try_get(columns.user_id)?,
+                    content: self.try_get(columns.content)?,
+                    created_at: self.try_get(columns.created_at)?,
+                })
+            }
+        }
+
+        impl TryFrom<&$row> for User {
+            type Error = $error;
+
+            fn try_from(row: &$row) -> Result<Self, $error> {
+                row.user(&UserColumns::TABLE)
+            }
+        }
+
+        impl TryFrom<&$row> for Post {
+            type Error = $error;
+
+            fn try_from(row: &$row) -> Result<Self, $error> {
+                row.post(&PostColumns::TABLE)
+            }
+        }
+
+        impl TryFrom<&$row> for Comment {
+            type Error = $error;
+
+            fn try_from(row: &$row) -> Result<Self, $error> {
+                row.comment(&CommentColumns::TABLE)
+            }
+        }
+    };
+}
+
+mod sqlx_row {
+    use super::*;
+    use sqlx::Row as _;
+
+    model_row!(sqlx::postgres::PgRow, sqlx::Error);
+}
+
+model_row!(tokio_postgres::Row, tokio_postgres::Error);
+
+/// A wtx record's `timestamptz` column, which wtx decodes into its own calendar type
+fn wtx_timestamp<'exec, C>(record: &PostgresRecord<'exec, wtx::Error>, column: C) -> Result<Option<DateTime<Utc>>, wtx::Error>
+where
+    C: ValueIdent<PostgresRecord<'exec, wtx::Error>>,
+{
+    let value: Option<WtxDateTime<WtxUtc>> = record.decode_opt(column)?;
+    Ok(value.map(|value| {
+        let (secs, nanos) = value.timestamp_secs_and_ns();
+        DateTime::from_timestamp(secs, nanos.num()).expect("wtx's dates are within chrono's range")
+    }))
+}
+
+impl ModelRow for PostgresRecord<'_, wtx::Error> {
+    type Error = wtx::Error;
+
+    fn user(&self, columns: &UserColumns) -> Result<User, wtx::Error> {
+        Ok(User {
+            id: self.decode(columns.id)?,
+            username: self.decode("username")?,
+            email: self.decode("email")?,
+            first_name: self.decode("first_name")?,
+            last_name: self.decode("last_name")?,
+            age: self.decode_opt("age")?,
+            created_at: wtx_timestamp(self, columns.created_at)?,
+            updated_at: wtx_timestamp(self, columns.updated_at)?,
+        })
+    }
+
+    fn user_by_index(&self) -> Result<User, wtx::Error> {
+        Ok(User {
+            id: self.decode(0_usize)?,
+            username: self.decode(1_usize)?,
+            email: self.decode(2_usize)?,
+            first_name: self.decode(3_usize)?,
+            last_name: self.decode(4_usize)?,
+            age: self.decode_opt(5_usize)?,
+            created_at: wtx_timestamp(self, 6_usize)?,
+            updated_at: wtx_timestamp(self, 7_usize)?,
+        })
+    }
+
+    fn post(&self, columns: &PostColumns) -> Result<Post, wtx::Error> {
+        Ok(Post {
+            id: self.decode(columns.id)?,
+            user_id: self.decode("user_id")?,
+            title: self.decode("title")?,
+            content: self.decode("content")?,
+            status: self.decode("status")?,
+            view_count: self.decode("view_count")?,
+            created_at: wtx_timestamp(self, columns.created_at)?,
+            updated_at: wtx_timestamp(self, columns.updated_at)?,
+        })
+    }
+
+    fn comment(&self, columns: &CommentColumns) -> Result<Comment, wtx::Error> {
+        Ok(Comment {
+            id: self.decode(columns.id)?,
+            post_id: self.decode("post_id")?,
+            user_id: self.decode(columns.user_id)?,
+            content: self.decode(columns.content)?,
+            created_at: wtx_timestamp(self, columns.created_at)?,
+        })
+    }
+}
+
+impl TryFrom<&PostgresRecord<'_, wtx::Error>> for User {
+    type Error = wtx::Error;
+
+    fn try_from(record: &PostgresRecord<'_, wtx::Error>) -> Result<Self, wtx::Error> {
+        record.user(&UserColumns::TABLE)
+    }
+}
+
+impl TryFrom<&PostgresRecord<'_, wtx::Error>> for Post {
+    type Error = wtx::Error;
+
+    fn try_from(record: &PostgresRecord<'_, wtx::Error>) -> Result<Self, wtx::Error> {
+        record.post(&PostColumns::TABLE)
+    }
+}
+
+impl TryFrom<&PostgresRecord<'_, wtx::Error>> for Comment {
+    type Error = wtx::Error;
+
+    fn try_from(record: &PostgresRecord<'_, wtx::Error>) -> Result<Self, wtx::Error> {
+        record.comment(&CommentColumns::TABLE)
+    }
+}
+
+impl From<users::Model> for User {
+    fn from(u: users::Model) -> Self {
+        Self {
+            id: u.id,
+            username: u.username,
+            email: u.email,
+            first_name: u.first_name,
+            last_name: u.last_name,
+            age: u.age,
+            created_at: u.created_at.map(Into::into),
+            updated_at: u.updated_at.map(Into::into),
+        }
+    }
+}
+
+impl From<posts::Model> for Post {
+    fn from(p: posts::Model) -> Self {
+        Self {
+            id: p.id,
+            user_id: p.user_id,
+            title: p.title,
+            content: p.content,
+            status: p.status,
+            view_count: p.view_count,
+            created_at: p.created_at.map(Into::into),
+            updated_at: p.updated_at.map(Into::into),
+        }
+    }
+}
+
+impl From<comments::Model> for Comment {
+    fn from(c: comments::Model) -> Self {
+        Self {
+            id: c.id,
+            post_id: c.post_id,
+            user_id: c.user_id,
+            content: c.content,
+            created_at: c.created_at.map(Into::into),
+        }
+    }
+}
+
+impl From<DieselUser> for User {
+    fn from(u: DieselUser) -> Self {
+        Self {
+            id: u.id,
+            username: u.username,
+            email: u.email,
+            first_name: u.first_name,
+            last_name: u.last_name,
+            age: u.age,
+            created_at: u.created_at,
+            updated_at: u.updated_at,
+        }
+    }
+}
+
+impl From<DieselPost> for Post {
+    fn from(p: DieselPost) -> Self {
+        Self {
+            id: p.id,
+            user_id: p.user_id,
+            title: p.title,
+            content: p.content,
+            status: p.status,
+            view_count: p.view_count,
+            created_at: p.created_at,
+            updated_at: p.updated_at,
+        }
+    }
+}
+
+impl From<DieselComment> for Comment {
+    fn from(c: DieselComment) -> Self {
+        Self {
+            id: c.id,
+            post_id: c.post_id,
+            user_id: c.user_id,
+            content: c.content,
+            created_at: c.created_at,
+        }
+    }
+}
+
+impl From<clorinde::users::User> for User {
+    fn from(u: clorinde::users::User) -> Self {
+        Self {
+            id: u.id,
+            username: u.username,
+            email: u.email,
+            first_name: u.first_name,
+            last_name: u.last_name,
+            age: u.age,
+            created_at: u.created_at,
+            updated_at: u.updated_at,
+        }
+    }
+}
+
+impl From<clorinde::posts::Post> for Post {
+    fn from(p: clorinde::posts::Post) -> Self {
+        Self {
+            id: p.id,
+            user_id: p.user_id,
+            title: p.title,
+            content: p.content,
+            status: p.status,
+            view_count: p.view_count,
+            created_at: p.created_at,
+            updated_at: p.updated_at,
+        }
+    }
+}
+
+impl From<clorinde::comments::Comment> for Comment {
+    fn from(c: clorinde::comments::Comment) -> Self {
+        Self {
+            id: c.id,
+            post_id: c.post_id,
+            user_id: c.user_id,
+            content: c.content,
+            created_at: c.created_at,
+        }
+    }
+}
+
+/// Split clorinde's flattened post-and-author row
+pub fn post_with_user(r: clorinde::posts::PostWithUser) -> (Post, User) {
+    (
+        Post {
+            id: r.post_id,
+            user_id: r.user_id,
+            title: r.title,
+            content: r.content,
+            status: r.status,
+            view_count: r.view_count,
+            created_at: r.post_created_at,
+            updated_at: r.post_updated_at,
+        },
+        User {
+            id: r.user_id,
+            username: r.username,
+            email: r.email,
+            first_name: r.first_name,
+            last_name: r.last_name,
+            age: r.age,
+            created_at: r.user_created_at,
+            updated_at: r.user_updated_at,
+        },
+    )
+}
+
+/// Split clorinde's flattened user, post and comment row
+pub fn user_post_comment(r: clorinde::posts::UserPostComment) -> (User, Post, Comment) {
+    (
+        User {
+            id: r.user_id,
+            username: r.username,
+            email: r.email,
+            first_name: r.first_name,
+            last_name: r.last_name,
+            age: r.age,
+            created_at: r.user_created_at,
+            updated_at: r.user_updated_at,
+        },
+        Post {
+            id: r.post_id,
+            user_id: r.user_id,
+            title: r.title,
+            content: r.content,
+            status: r.status,
+            view_count: r.view_count,
+            created_at: r.post_created_at,
+            updated_at: r.post_updated_at,
+        },
+        Comment {
+            id: r.comment_id,
+            post_id: r.post_id,
+            user_id: r.comment_user_id,
+            content: r.comment_content,
+            created_at: r.comment_created_at,
+        },
+    )
+}
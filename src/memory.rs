@@ -0,0 +1,56 @@
+//! Peak memory of a single operation
+//!
+//! Rust-side allocations alone would miss libpq's result buffers (diesel) and
+//! anything else allocated outside the global allocator, so the peak is the
+//! process's resident set as the kernel tracks it. Linux only: the high-water
+//! mark is reset through `/proc/self/clear_refs` and read from
+//! `/proc/self/status`; elsewhere [`measure_peak`] returns an error.
+
+use std::fs;
+use std::io;
+
+/// Reset the resident set high-water mark (`VmHWM`) to the current resident set
+pub fn reset_peak() -> io::Result<()> {
+    fs::write("/proc/self/clear_refs", "5")
+}
+
+/// Highest resident set size in bytes since the process started or [`reset_peak`]
+pub fn peak_rss() -> io::Result<u64> {
+    status_bytes("VmHWM:")
+}
+
+/// Current resident set size in bytes
+pub fn current_rss() -> io::Result<u64> {
+    status_bytes("VmRSS:")
+}
+
+fn status_bytes(field: &str) -> io::Result<u64> {
+    let status = fs::read_to_string("/proc/self/status")?;
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix(field))
+        .and_then(|value| value.trim().strip_suffix("kB"))
+        .and_then(|kb| kb.trim().parse::<u64>().ok())
+        .map(|kb| kb * 1024)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("no {field} in /proc/self/status")))
+}
+
+/// Hand memory freed by earlier operations back to the kernel, so it isn't
+/// reused unseen by the next measurement
+fn release_free_memory() {
+    #[cfg(all(target_os = "linux", target_env = "gnu"))]
+    // SAFETY: malloc_trim only releases free pages of glibc's own heap
+    unsafe {
+        libc::malloc_trim(0);
+    }
+}
+
+/// Run `operation` and return its output along with how far the resident set
+/// grew above where it started, in bytes
+pub fn measure_peak<T>(operation: impl FnOnce() -> T) -> io::Result<(T, u64)> {
+    release_free_memory();
+    reset_peak()?;
+    let baseline = current_rss()?;
+    let output = operation();
+    Ok((output, peak_rss()?.saturating_sub(baseline)))
+}
@@ -0,0 +1,215 @@
+//! What a run was measured on, attached to every exported result set
+//!
+//! Published numbers can only be reproduced, or two runs compared, with the
+//! environment they came from. [`RunMetadata`] records the crate's commit,
+//! the versions of the compared libraries as `Cargo.lock` resolved them for
+//! this build, the server's version and the settings that move the results
+//! most, the host, the database profile, and the size of the seeded dataset. `bench-runner` and
+//! `run-all` write it next to the results in their JSON exports, as `#`
+//! comment lines above the CSV header, and into the `run-all` summary.
+
+use crate::profile;
+use crate::seeder::{self, SeedRows};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::process::Command;
+use tokio_postgres::Client;
+
+/// The packages behind the compared backends, as `Cargo.lock` names them;
+/// clorinde's generated code is the `clorinde_queries` crate
+pub const LIBRARIES: &[&str] = &["tokio-postgres", "sqlx", "sea-orm", "sea-query", "diesel", "clorinde_queries", "wtx"];
+
+/// Server settings recorded with every run
+pub const SETTINGS: &[&str] = &[
+    "effective_cache_size",
+    "fsync",
+    "jit",
+    "max_connections",
+    "max_parallel_workers_per_gather",
+    "random_page_cost",
+    "shared_buffers",
+    "synchronous_commit",
+    "wal_level",
+    "work_mem",
+];
+
+/// The lock file this binary was built with
+const CARGO_LOCK: &str = include_str!("../Cargo.lock");
+
+/// The environment of one run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunMetadata {
+    /// `git rev-parse HEAD` of the crate, suffixed `-dirty` with uncommitted
+    /// changes to tracked files; `None` outside a git checkout
+    pub git_commit: Option<String>,
+    /// Version of each of [`LIBRARIES`]
+    pub libraries: BTreeMap<String, String>,
+    pub host: Host,
+    /// The [`crate::profile`] the run used, if any
+    #[serde(default)]
+    pub profile: Option<String>,
+    /// `None` for runs that never connected, such as the in-memory backend's
+    pub server: Option<Server>,
+    /// The run was stopped with Ctrl-C, so its results are partial
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub interrupted: bool,
+}
+
+/// The machine the benchmarks ran on
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Host {
+    pub os: String,
+    pub arch: String,
+    /// Kernel release, where `/proc` has it
+    pub kernel: Option<String>,
+    /// CPU model name, where `/proc/cpuinfo` has it
+    pub cpu: Option<String>,
+    pub cpus: usize,
+}
+
+/// The database server and its data
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Server {
+    pub version: String,
+    /// Value of each of [`SETTINGS`], with its unit
+    pub settings: BTreeMap<String, String>,
+    pub dataset: SeedRows,
+}
+
+impl RunMetadata {
+    /// The metadata that doesn't need the database
+    pub fn local() -> Self {
+        Self {
+            git_commit: git_commit(),
+            libraries: library_versions(CARGO_LOCK),
+            host: Host::current(),
+            profile: profile::selected().map(|p| p.name.to_string()),
+            server: None,
+            interrupted: false,
+        }
+    }
+
+    /// [`RunMetadata::local`] with the server `client` is connected to
+    pub async fn collect(client: &Client) -> Result<Self, tokio_postgres::Error> {
+        Ok(Self {
+            server: Some(Server::collect(client).await?),
+            ..Self::local()
+        })
+    }
+
+    /// Labelled values in the order the CSV comments and the summary list them
+    pub fn entries(&self) -> Vec<(String, String)> {
+        let mut entries = vec![("Commit".to_string(), self.git_commit.clone().unwrap_or_else(|| "unknown".to_string()))];
+        entries.extend(self.libraries.iter().map(|(name, version)| (name.clone(), version.clone())));
+        entries.push(("Host".to_string(), self.host.to_string()));
+        if let Some(profile) = &self.profile {
+            entries.push(("Profile".to_string(), profile.clone()));
+        }
+        if let Some(server) = &self.server {
+            entries.push(("PostgreSQL".to_string(), server.version.clone()));
+            let settings: Vec<String> = server.settings.iter().map(|(name, value)| format!("{name}={value}")).collect();
+            entries.push(("Settings".to_string(), settings.join(", ")));
+            entries.push(("Dataset".to_string(), server.dataset.to_string()));
+        }
+        if self.interrupted {
+            entries.push(("Interrupted".to_string(), "yes, the results are partial".to_string()));
+        }
+        entries
+    }
+}
+
+impl Host {
+    /// The machine this process runs on
+    pub fn current() -> Self {
+        let cpuinfo = std::fs::read_to_string("/proc/cpuinfo").unwrap_or_default();
+        Self {
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            kernel: std::fs::read_to_string("/proc/sys/kernel/osrelease")
+                .ok()
+                .map(|release| release.trim().to_string()),
+            cpu: cpu_model(&cpuinfo),
+            cpus: std::thread::available_parallelism().map_or(1, |n| n.get()),
+        }
+    }
+}
+
+impl fmt::Display for Host {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.os, self.arch)?;
+        if let Some(kernel) = &self.kernel {
+            write!(f, " ({kernel})")?;
+        }
+        write!(f, ", {} CPU(s)", self.cpus)?;
+        if let Some(cpu) = &self.cpu {
+            write!(f, ", {cpu}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Server {
+    pub async fn collect(client: &Client) -> Result<Self, tokio_postgres::Error> {
+        let version = client.query_one("SHOW server_version", &[]).await?.get(0);
+        let rows = client
+            .query(
+                "SELECT name, current_setting(name) FROM pg_settings WHERE name = ANY($1)",
+                &[&SETTINGS],
+            )
+            .await?;
+        Ok(Self {
+            version,
+            settings: rows.iter().map(|row| (row.get(0), row.get(1))).collect(),
+            dataset: seeder::count(client).await?,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct Lockfile {
+    #[serde(default)]
+    package: Vec<LockedPackage>,
+}
+
+#[derive(Deserialize)]
+struct LockedPackage {
+    name: String,
+    version: String,
+}
+
+/// Version of each of [`LIBRARIES`] in the lock file `lock`, comma-separated
+/// where it resolved several; libraries it lacks are left out
+pub fn library_versions(lock: &str) -> BTreeMap<String, String> {
+    let Ok(lockfile) = toml::from_str::<Lockfile>(lock) else {
+        return BTreeMap::new();
+    };
+    let mut versions: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for package in lockfile.package.into_iter().filter(|p| LIBRARIES.contains(&p.name.as_str())) {
+        versions.entry(package.name).or_default().push(package.version);
+    }
+    versions.into_iter().map(|(name, versions)| (name, versions.join(", "))).collect()
+}
+
+/// The first `model name` of a `/proc/cpuinfo`
+pub fn cpu_model(cpuinfo: &str) -> Option<String> {
+    cpuinfo
+        .lines()
+        .find_map(|line| line.strip_prefix("model name")?.split_once(':'))
+        .map(|(_, model)| model.trim().to_string())
+}
+
+fn git_commit() -> Option<String> {
+    let git = |args: &[&str]| {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(env!("CARGO_MANIFEST_DIR"))
+            .args(args)
+            .output()
+            .ok()?;
+        output.status.success().then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    let commit = git(&["rev-parse", "HEAD"])?;
+    let dirty = git(&["status", "--porcelain", "--untracked-files=no"]).is_some_and(|status| !status.is_empty());
+    Some(if dirty { format!("{commit}-dirty") } else { commit })
+}
@@ -0,0 +1,151 @@
+//! Outlier filtering and a noise score for load-test results
+//!
+//! A mean latency is only as trustworthy as the spread behind it: a run that
+//! caught a checkpoint or a noisy neighbour reports a mean that the next run
+//! won't reproduce. Every result carries its coefficient of variation (the
+//! standard deviation over the mean, [`RunResult::cv`]) as a noise score, and
+//! results above [`NoiseConfig::max_cv`] are marked noisy in the printout, the
+//! exports, the `run-all` summary and `report diff`. A runner configuration
+//! can also tune how noise is handled, e.g.
+//!
+//! ```toml
+//! [noise]
+//! outlier_fence = 3.0
+//! max_cv = 0.5
+//! action = "rerun"
+//! reruns = 2
+//! ```
+//!
+//! With `outlier_fence` set, latencies more than that many interquartile
+//! ranges beyond the quartiles (Tukey's fences, criterion's "severe" outliers
+//! at 3.0) are left out of the mean and standard deviation; the percentiles
+//! still cover every operation. `action` decides what happens to a noisy
+//! result: `flag` only marks it, `drop` leaves it out of the exports, the
+//! summary and the objectives, and `rerun` runs it again up to `reruns` times
+//! and keeps the least noisy run.
+
+use crate::runner::RunResult;
+use serde::Deserialize;
+use std::fmt;
+use std::time::Duration;
+
+/// Coefficient of variation above which a result is noisy unless configured
+pub const DEFAULT_MAX_CV: f64 = 0.5;
+
+/// How the runner treats noise, the `[noise]` table of its configuration
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NoiseConfig {
+    /// Tukey fence multiplier for outliers; unset keeps every latency
+    pub outlier_fence: Option<f64>,
+    /// Coefficient of variation above which a result is noisy
+    pub max_cv: f64,
+    pub action: NoiseAction,
+    /// Further runs of a noisy result with [`NoiseAction::Rerun`]
+    pub reruns: u32,
+}
+
+impl Default for NoiseConfig {
+    fn default() -> Self {
+        Self {
+            outlier_fence: None,
+            max_cv: DEFAULT_MAX_CV,
+            action: NoiseAction::Flag,
+            reruns: 2,
+        }
+    }
+}
+
+/// What happens to a noisy result
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NoiseAction {
+    /// Keep it, marked noisy
+    Flag,
+    /// Leave it out of the exports, the summary and the objectives
+    Drop,
+    /// Run it again and keep the least noisy run
+    Rerun,
+}
+
+impl fmt::Display for NoiseAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            NoiseAction::Flag => "flag",
+            NoiseAction::Drop => "drop",
+            NoiseAction::Rerun => "rerun",
+        })
+    }
+}
+
+impl NoiseConfig {
+    /// Reject thresholds no result could meet or that are not numbers
+    pub fn validate(&self) -> Result<(), String> {
+        if self.outlier_fence.is_some_and(|fence| !fence.is_finite() || fence <= 0.0) {
+            return Err("outlier_fence must be a positive number".to_string());
+        }
+        if !self.max_cv.is_finite() || self.max_cv <= 0.0 {
+            return Err("max_cv must be a positive number".to_string());
+        }
+        Ok(())
+    }
+
+    /// The latencies of `sorted` inside the configured fences, and how many
+    /// were left out
+    pub fn without_outliers<'a>(&self, sorted: &'a [Duration]) -> (&'a [Duration], usize) {
+        let Some(fence) = self.outlier_fence else {
+            return (sorted, 0);
+        };
+        let (low, high) = fences(sorted, fence);
+        let start = sorted.partition_point(|&l| l < low);
+        let end = sorted.partition_point(|&l| l <= high);
+        (&sorted[start..end], sorted.len() - (end - start))
+    }
+
+    pub fn is_noisy(&self, cv: f64) -> bool {
+        cv > self.max_cv
+    }
+
+    /// Whether `results`, the entries of one run, should be run again
+    pub fn wants_rerun(&self, results: &[RunResult]) -> bool {
+        self.action == NoiseAction::Rerun && results.iter().any(|r| r.noisy)
+    }
+
+    /// Split `results` into those to report and those dropped as noisy; only
+    /// [`NoiseAction::Drop`] drops any
+    pub fn keep(&self, results: Vec<RunResult>) -> (Vec<RunResult>, Vec<RunResult>) {
+        if self.action != NoiseAction::Drop {
+            return (results, Vec::new());
+        }
+        results.into_iter().partition(|r| !r.noisy)
+    }
+}
+
+/// Tukey's fences of `sorted`: `k` interquartile ranges below the first and
+/// above the third quartile
+pub fn fences(sorted: &[Duration], k: f64) -> (Duration, Duration) {
+    if sorted.is_empty() {
+        return (Duration::ZERO, Duration::MAX);
+    }
+    let quartile = |q: f64| sorted[((sorted.len() - 1) as f64 * q).round() as usize].as_secs_f64();
+    let (q1, q3) = (quartile(0.25), quartile(0.75));
+    let iqr = q3 - q1;
+    (
+        Duration::from_secs_f64((q1 - k * iqr).max(0.0)),
+        Duration::from_secs_f64(q3 + k * iqr),
+    )
+}
+
+/// Standard deviation over the mean, 0 without a mean
+pub fn coefficient_of_variation(mean: f64, stddev: f64) -> f64 {
+    if mean > 0.0 {
+        stddev / mean
+    } else {
+        0.0
+    }
+}
+
+/// The noise of a run: its noisiest entry's coefficient of variation
+pub fn run_noise(results: &[RunResult]) -> f64 {
+    results.iter().map(|r| r.cv).fold(0.0, f64::max)
+}
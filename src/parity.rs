@@ -0,0 +1,235 @@
+//! Connection-settings parity
+//!
+//! Builds every backend's pool exactly as the concurrent benchmarks do and
+//! records the settings that actually took effect, so a benchmark never pits,
+//! say, a 10-connection pool against a 100-connection one.
+
+use crate::bench_diesel::DieselBench;
+use crate::bench_seaorm::SeaOrmBench;
+use crate::bench_sqlx::SqlxBench;
+use crate::bench_tokio_postgres::TokioPostgresBench;
+use crate::database_url;
+use std::fmt;
+
+/// How a backend reuses prepared statements across calls
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatementCache {
+    /// Every query is prepared again (e.g. `tokio_postgres::Client::query` with a `&str`)
+    None,
+    /// Up to this many prepared statements are kept per connection
+    Capacity(usize),
+    /// Every prepared statement is kept for the connection's lifetime
+    Unbounded,
+    /// The setting couldn't be read back from the library
+    Unknown,
+}
+
+impl fmt::Display for StatementCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StatementCache::None => write!(f, "none"),
+            StatementCache::Capacity(capacity) => write!(f, "{} per connection", capacity),
+            StatementCache::Unbounded => write!(f, "unbounded"),
+            StatementCache::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// Effective connection settings of one backend
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectionSettings {
+    pub backend: &'static str,
+    pub pool_size: u32,
+    pub statement_cache: StatementCache,
+    pub tcp_nodelay: bool,
+    pub tcp_keepalive: bool,
+}
+
+/// The values every backend is expected to run with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParityConfig {
+    pub pool_size: u32,
+    pub tcp_nodelay: bool,
+}
+
+impl ParityConfig {
+    pub fn with_pool_size(pool_size: u32) -> Self {
+        Self {
+            pool_size,
+            tcp_nodelay: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// The benchmark would compare differently configured backends
+    Error,
+    /// A difference built into the library that can't be configured away
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParityIssue {
+    pub severity: Severity,
+    pub backend: &'static str,
+    pub setting: &'static str,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl fmt::Display for ParityIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let level = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        write!(
+            f,
+            "{}: {} {} is {}, expected {}",
+            level, self.backend, self.setting, self.actual, self.expected
+        )
+    }
+}
+
+/// SQLx keeps `statement_cache_capacity` private; read it from the options' `Debug` output.
+///
+/// Reports [`StatementCache::Unknown`] when that output no longer carries the
+/// field, rather than guessing.
+fn sqlx_statement_cache(pool: &sqlx::PgPool) -> StatementCache {
+    let options = format!("{:?}", pool.connect_options());
+    let capacity = options
+        .split("statement_cache_capacity: ")
+        .nth(1)
+        .and_then(|rest| rest.split(|c: char| !c.is_ascii_digit()).next())
+        .and_then(|digits| digits.parse().ok());
+    match capacity {
+        None => StatementCache::Unknown,
+        Some(0) => StatementCache::None,
+        Some(capacity) => StatementCache::Capacity(capacity),
+    }
+}
+
+/// Settings of the pools the concurrent benchmarks build for `pool_size` tasks.
+///
+/// All three drivers set `TCP_NODELAY` unconditionally (tokio-postgres and SQLx
+/// in their socket setup, libpq for Diesel). Keepalives are on by default in
+/// tokio-postgres and libpq but never enabled by SQLx.
+pub async fn pooled_settings(
+    pool_size: u32,
+) -> Result<Vec<ConnectionSettings>, Box<dyn std::error::Error + Send + Sync>> {
+    let url = database_url();
+    let pg_config: tokio_postgres::Config = url.parse()?;
+    let libpq_keepalives = !url.contains("keepalives=0");
+
+    // Every pool is closed before the next one opens, so the pools that open
+    // all their connections up front never hold the server's connections together
+    let tokio_pool_size = TokioPostgresBench::create_pool(pool_size as usize).status().max_size as u32;
+    // bb8 keeps its maximum private, but the pool opens every connection
+    // before it is returned
+    let bb8_pool_size = TokioPostgresBench::create_bb8_pool(pool_size as usize).await?.state().connections;
+    let mobc_pool_size = TokioPostgresBench::create_mobc_pool(pool_size as usize).await?.state().await.max_open as u32;
+    let sqlx_pool = SqlxBench::connect_with_pool_size(pool_size).await?;
+    let sqlx = (sqlx_pool.options().get_max_connections(), sqlx_statement_cache(&sqlx_pool));
+    sqlx_pool.close().await;
+    let sea_orm_db = SeaOrmBench::connect_with_pool_size(pool_size).await?;
+    let sea_orm_pool = sea_orm_db.get_postgres_connection_pool();
+    let sea_orm = (sea_orm_pool.options().get_max_connections(), sqlx_statement_cache(sea_orm_pool));
+    sea_orm_db.close().await?;
+    let diesel_pool_size = DieselBench::connect_with_pool_size(pool_size)?.max_size();
+
+    Ok(vec![
+        ConnectionSettings {
+            backend: "tokio_postgres",
+            pool_size: tokio_pool_size,
+            // The pooled methods call `query` with SQL text, not `prepare_cached`
+            statement_cache: StatementCache::None,
+            tcp_nodelay: true,
+            tcp_keepalive: pg_config.get_keepalives(),
+        },
+        ConnectionSettings {
+            backend: "tokio_postgres_bb8",
+            pool_size: bb8_pool_size,
+            statement_cache: StatementCache::None,
+            tcp_nodelay: true,
+            tcp_keepalive: pg_config.get_keepalives(),
+        },
+        ConnectionSettings {
+            backend: "tokio_postgres_mobc",
+            pool_size: mobc_pool_size,
+            statement_cache: StatementCache::None,
+            tcp_nodelay: true,
+            tcp_keepalive: pg_config.get_keepalives(),
+        },
+        ConnectionSettings {
+            backend: "sqlx",
+            pool_size: sqlx.0,
+            statement_cache: sqlx.1,
+            tcp_nodelay: true,
+            tcp_keepalive: false,
+        },
+        ConnectionSettings {
+            backend: "sea_orm",
+            pool_size: sea_orm.0,
+            statement_cache: sea_orm.1,
+            tcp_nodelay: true,
+            tcp_keepalive: false,
+        },
+        ConnectionSettings {
+            backend: "diesel",
+            pool_size: diesel_pool_size,
+            // `PgConnection` caches every query whose SQL is known at compile time
+            statement_cache: StatementCache::Unbounded,
+            tcp_nodelay: true,
+            tcp_keepalive: libpq_keepalives,
+        },
+    ])
+}
+
+/// Compare recorded settings with the parity values.
+///
+/// Pool size and `TCP_NODELAY` must match exactly. Statement caching and
+/// keepalives that differ from the first (baseline) backend are warnings.
+pub fn check(settings: &[ConnectionSettings], expected: &ParityConfig) -> Vec<ParityIssue> {
+    let mut issues = Vec::new();
+    let Some(baseline) = settings.first() else {
+        return issues;
+    };
+
+    for backend in settings {
+        let mut issue = |severity, setting, expected: String, actual: String| {
+            issues.push(ParityIssue {
+                severity,
+                backend: backend.backend,
+                setting,
+                expected,
+                actual,
+            })
+        };
+
+        if backend.pool_size != expected.pool_size {
+            issue(Severity::Error, "pool size", expected.pool_size.to_string(), backend.pool_size.to_string());
+        }
+        if backend.tcp_nodelay != expected.tcp_nodelay {
+            issue(Severity::Error, "TCP_NODELAY", expected.tcp_nodelay.to_string(), backend.tcp_nodelay.to_string());
+        }
+        if backend.statement_cache != baseline.statement_cache {
+            issue(
+                Severity::Warning,
+                "statement cache",
+                format!("{} (as {})", baseline.statement_cache, baseline.backend),
+                backend.statement_cache.to_string(),
+            );
+        }
+        if backend.tcp_keepalive != baseline.tcp_keepalive {
+            issue(
+                Severity::Warning,
+                "TCP keepalive",
+                format!("{} (as {})", baseline.tcp_keepalive, baseline.backend),
+                backend.tcp_keepalive.to_string(),
+            );
+        }
+    }
+
+    issues
+}
@@ -0,0 +1,662 @@
+//! UUID vs BIGSERIAL primary keys
+//!
+//! Every table in `init.sql` is keyed by a random UUID. The `bigserial` schema
+//! holds the same users, posts and comments keyed by `BIGSERIAL` instead, with
+//! `BIGINT` foreign keys, so the insert, point lookup and join can be run
+//! against both key types. [`seed`] (`cargo run -- seed bigserial`) copies the
+//! current dataset into it, numbering the rows in creation order.
+//!
+//! [`SerialKeys`] is the `bigserial` counterpart of the [`DatabaseBenchmark`]
+//! methods the `primary_key_*` benchmarks use. clorinde runs the queries
+//! generated into `clorinde_queries`, which are written against the UUID
+//! tables only, so it is left out.
+
+use crate::bench_diesel::DieselBench;
+use crate::bench_seaorm::SeaOrmBench;
+use crate::bench_sqlx::SqlxBench;
+use crate::bench_tokio_postgres::TokioPostgresBench;
+use crate::mapping::{PostColumns, UserColumns};
+use crate::{DatabaseBenchmark, NewUser};
+use chrono::{DateTime, Utc};
+use sea_orm::{ActiveValue, DatabaseConnection, DbErr, EntityTrait, QueryOrder, QuerySelect};
+use sqlx::PgPool;
+use std::fmt;
+use std::str::FromStr;
+use tokio_postgres::Client;
+
+/// Schema holding the `BIGSERIAL`-keyed tables
+pub const SCHEMA: &str = "bigserial";
+
+/// Primary key type of the tables a benchmark runs against
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyType {
+    /// `UUID DEFAULT uuid_generate_v4()`, the tables of `init.sql`
+    Uuid,
+    /// `BIGSERIAL`, the tables of the [`SCHEMA`] schema
+    Bigserial,
+}
+
+impl KeyType {
+    pub const ALL: [KeyType; 2] = [KeyType::Uuid, KeyType::Bigserial];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            KeyType::Uuid => "uuid",
+            KeyType::Bigserial => "bigserial",
+        }
+    }
+
+    /// The key types in `BENCH_KEY_TYPES`, a comma-separated list, or both when unset.
+    ///
+    /// # Panics
+    ///
+    /// If `BENCH_KEY_TYPES` names an unknown key type.
+    pub fn from_env() -> Vec<KeyType> {
+        match std::env::var("BENCH_KEY_TYPES") {
+            Ok(types) => types
+                .split(',')
+                .filter(|name| !name.trim().is_empty())
+                .map(|name| name.parse().unwrap_or_else(|e| panic!("BENCH_KEY_TYPES: {e}")))
+                .collect(),
+            Err(_) => Self::ALL.to_vec(),
+        }
+    }
+}
+
+impl fmt::Display for KeyType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for KeyType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|key| key.name().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| format!("unknown key type {s:?}, expected uuid or bigserial"))
+    }
+}
+
+/// A `User` from the [`SCHEMA`] schema
+#[derive(Debug, Clone, PartialEq)]
+pub struct SerialUser {
+    pub id: i64,
+    pub username: String,
+    pub email: String,
+    pub first_name: String,
+    pub last_name: String,
+    pub age: Option<i32>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// A `Post` from the [`SCHEMA`] schema
+#[derive(Debug, Clone, PartialEq)]
+pub struct SerialPost {
+    pub id: i64,
+    pub user_id: i64,
+    pub title: String,
+    pub content: String,
+    pub status: String,
+    pub view_count: i32,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// The tables of `init.sql` that matter here, keyed by `BIGSERIAL`, with the
+/// indexes the UUID tables have on the same columns
+const CREATE_SCHEMA: &str = "
+    DROP SCHEMA IF EXISTS bigserial CASCADE;
+    CREATE SCHEMA bigserial;
+
+    CREATE TABLE bigserial.users (
+        id BIGSERIAL PRIMARY KEY,
+        username VARCHAR(50) UNIQUE NOT NULL,
+        email VARCHAR(100) UNIQUE NOT NULL,
+        first_name VARCHAR(50) NOT NULL,
+        last_name VARCHAR(50) NOT NULL,
+        age INTEGER CHECK (age >= 0 AND age <= 150),
+        created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+        updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+    );
+
+    CREATE TABLE bigserial.posts (
+        id BIGSERIAL PRIMARY KEY,
+        user_id BIGINT NOT NULL REFERENCES bigserial.users(id) ON DELETE CASCADE,
+        title VARCHAR(200) NOT NULL,
+        content TEXT NOT NULL,
+        status VARCHAR(20) NOT NULL DEFAULT 'draft' CHECK (status IN ('draft', 'published', 'archived')),
+        view_count INTEGER NOT NULL DEFAULT 0,
+        created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+        updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+    );
+
+    CREATE TABLE bigserial.comments (
+        id BIGSERIAL PRIMARY KEY,
+        post_id BIGINT NOT NULL REFERENCES bigserial.posts(id) ON DELETE CASCADE,
+        user_id BIGINT NOT NULL REFERENCES bigserial.users(id) ON DELETE CASCADE,
+        content TEXT NOT NULL,
+        created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+    );
+
+    CREATE INDEX ON bigserial.users(created_at DESC);
+    CREATE INDEX ON bigserial.users(age);
+    CREATE INDEX ON bigserial.posts(user_id);
+    CREATE INDEX ON bigserial.posts(status);
+    CREATE INDEX ON bigserial.posts(created_at DESC);
+    CREATE INDEX ON bigserial.comments(post_id);
+    CREATE INDEX ON bigserial.comments(user_id);
+    CREATE INDEX ON bigserial.comments(created_at DESC);";
+
+/// Copy of the UUID tables, each row keyed by its position in creation order
+const COPY_DATASET: &str = "
+    CREATE TEMP TABLE user_keys ON COMMIT DROP AS
+    SELECT id, row_number() OVER (ORDER BY created_at, id) AS key FROM public.users;
+    CREATE TEMP TABLE post_keys ON COMMIT DROP AS
+    SELECT id, row_number() OVER (ORDER BY created_at, id) AS key FROM public.posts;
+
+    INSERT INTO bigserial.users (id, username, email, first_name, last_name, age, created_at, updated_at)
+    SELECT k.key, u.username, u.email, u.first_name, u.last_name, u.age, u.created_at, u.updated_at
+    FROM public.users u JOIN user_keys k USING (id);
+
+    INSERT INTO bigserial.posts (id, user_id, title, content, status, view_count, created_at, updated_at)
+    SELECT k.key, uk.key, p.title, p.content, p.status, p.view_count, p.created_at, p.updated_at
+    FROM public.posts p JOIN post_keys k USING (id) JOIN user_keys uk ON uk.id = p.user_id;
+
+    INSERT INTO bigserial.comments (post_id, user_id, content, created_at)
+    SELECT pk.key, uk.key, c.content, c.created_at
+    FROM public.comments c
+    JOIN post_keys pk ON pk.id = c.post_id
+    JOIN user_keys uk ON uk.id = c.user_id
+    ORDER BY c.created_at, c.id;
+
+    SELECT setval(pg_get_serial_sequence('bigserial.users', 'id'), max(id)) FROM bigserial.users;
+    SELECT setval(pg_get_serial_sequence('bigserial.posts', 'id'), max(id)) FROM bigserial.posts;";
+
+/// Re-create the [`SCHEMA`] schema as a copy of the current UUID-keyed
+/// dataset, returning the number of users copied
+pub async fn seed(client: &Client) -> Result<i64, tokio_postgres::Error> {
+    client
+        .batch_execute(&format!("BEGIN; {CREATE_SCHEMA} {COPY_DATASET} COMMIT;"))
+        .await?;
+    client.batch_execute("ANALYZE bigserial.users, bigserial.posts, bigserial.comments").await?;
+    seeded_users(client).await
+}
+
+/// Users in the [`SCHEMA`] schema, 0 when it doesn't exist
+pub async fn seeded_users(client: &Client) -> Result<i64, tokio_postgres::Error> {
+    let exists: bool = client
+        .query_one("SELECT to_regclass('bigserial.users') IS NOT NULL", &[])
+        .await?
+        .get(0);
+    if !exists {
+        return Ok(0);
+    }
+    Ok(client.query_one("SELECT COUNT(*) FROM bigserial.users", &[]).await?.get(0))
+}
+
+/// Keys of the newest `limit` users in the [`SCHEMA`] schema, for point lookups
+pub async fn sample_user_ids(client: &Client, limit: i64) -> Result<Vec<i64>, tokio_postgres::Error> {
+    let rows = client
+        .query("SELECT id FROM bigserial.users ORDER BY created_at DESC LIMIT $1", &[&limit])
+        .await?;
+    Ok(rows.iter().map(|row| row.get(0)).collect())
+}
+
+/// Remove the `bench_user_*` rows (and with them their posts and comments)
+/// the benchmarks inserted into the [`SCHEMA`] schema
+pub async fn cleanup(client: &Client) -> Result<(), tokio_postgres::Error> {
+    client
+        .execute("DELETE FROM bigserial.users WHERE username LIKE 'bench_user_%'", &[])
+        .await?;
+    Ok(())
+}
+
+/// The [`SCHEMA`] counterparts of the `DatabaseBenchmark` insert, point lookup
+/// and join, on the backend's own connection
+#[allow(async_fn_in_trait)]
+pub trait SerialKeys: DatabaseBenchmark {
+    /// Insert a single user, returning its generated key
+    async fn insert_user_serial(conn: &Self::Connection, user: &NewUser) -> Result<i64, Self::Error>;
+
+    /// Select a user by its key
+    async fn select_user_by_serial_id(conn: &Self::Connection, id: i64) -> Result<Option<SerialUser>, Self::Error>;
+
+    /// The newest `limit` posts with their author
+    async fn select_posts_with_user_serial(
+        conn: &Self::Connection,
+        limit: i64,
+    ) -> Result<Vec<(SerialPost, SerialUser)>, Self::Error>;
+}
+
+// ============================================================================
+// tokio-postgres and sqlx
+// ============================================================================
+
+const INSERT_USER: &str = "INSERT INTO bigserial.users (username, email, first_name, last_name, age)
+     VALUES ($1, $2, $3, $4, $5)
+     RETURNING id";
+
+const SELECT_USER_BY_ID: &str = "SELECT id, username, email, first_name, last_name, age, created_at, updated_at
+     FROM bigserial.users WHERE id = $1";
+
+const SELECT_POSTS_WITH_USER: &str = "SELECT
+        p.id as post_id, p.user_id, p.title, p.content, p.status, p.view_count,
+        p.created_at as post_created_at, p.updated_at as post_updated_at,
+        u.id as user_id, u.username, u.email, u.first_name, u.last_name, u.age,
+        u.created_at as user_created_at, u.updated_at as user_updated_at
+     FROM bigserial.posts p
+     JOIN bigserial.users u ON p.user_id = u.id
+     ORDER BY p.created_at DESC
+     LIMIT $1";
+
+/// Read [`SerialUser`]s and [`SerialPost`]s by column name, as `mapping` does
+/// for the shared models
+trait SerialRow {
+    type Error;
+
+    fn serial_user(&self, columns: &UserColumns) -> Result<SerialUser, Self::Error>;
+
+    fn serial_post(&self, columns: &PostColumns) -> Result<SerialPost, Self::Error>;
+}
+
+macro_rules! serial_row {
+    ($row:ty, $error:ty) => {
+        impl SerialRow for $row {
+            type Error = $error;
+
+            fn serial_user(&self, columns: &UserColumns) -> Result<SerialUser, $error> {
+                Ok(SerialUser {
+                    id: self.try_get(columns.id)?,
+                    username: self.try_get("username")?,
+                    email: self.try_get("email")?,
+                    first_name: self.try_get("first_name")?,
+                    last_name: self.try_get("last_name")?,
+                    age: self.try_get("age")?,
+                    created_at: self.try_get(columns.created_at)?,
+                    updated_at: self.try_get(columns.updated_at)?,
+                })
+            }
+
+            fn serial_post(&self, columns: &PostColumns) -> Result<SerialPost, $error> {
+                Ok(SerialPost {
+                    id: self.try_get(columns.id)?,
+                    user_id: self.try_get("user_id")?,
+                    title: self.try_get("title")?,
+                    content: self.try_get("content")?,
+                    status: self.try_get("status")?,
+                    view_count: self.try_get("view_count")?,
+                    created_at: self.try_get(columns.created_at)?,
+                    updated_at: self.try_get(columns.updated_at)?,
+                })
+            }
+        }
+    };
+}
+
+mod sqlx_row {
+    use super::*;
+    use sqlx::Row as _;
+
+    serial_row!(sqlx::postgres::PgRow, sqlx::Error);
+}
+
+serial_row!(tokio_postgres::Row, tokio_postgres::Error);
+
+impl SerialKeys for TokioPostgresBench {
+    async fn insert_user_serial(client: &Client, user: &NewUser) -> Result<i64, tokio_postgres::Error> {
+        let row = client
+            .query_one(
+                INSERT_USER,
+                &[&user.username, &user.email, &user.first_name, &user.last_name, &user.age],
+            )
+            .await?;
+        Ok(row.get("id"))
+    }
+
+    async fn select_user_by_serial_id(client: &Client, id: i64) -> Result<Option<SerialUser>, tokio_postgres::Error> {
+        let row = client.query_opt(SELECT_USER_BY_ID, &[&id]).await?;
+        row.map(|r| r.serial_user(&UserColumns::TABLE)).transpose()
+    }
+
+    async fn select_posts_with_user_serial(
+        client: &Client,
+        limit: i64,
+    ) -> Result<Vec<(SerialPost, SerialUser)>, tokio_postgres::Error> {
+        let rows = client.query(SELECT_POSTS_WITH_USER, &[&limit]).await?;
+        rows.iter()
+            .map(|r| Ok((r.serial_post(&PostColumns::JOINED)?, r.serial_user(&UserColumns::JOINED)?)))
+            .collect()
+    }
+}
+
+impl SerialKeys for SqlxBench {
+    async fn insert_user_serial(pool: &PgPool, user: &NewUser) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar(INSERT_USER)
+            .bind(&user.username)
+            .bind(&user.email)
+            .bind(&user.first_name)
+            .bind(&user.last_name)
+            .bind(user.age)
+            .fetch_one(pool)
+            .await
+    }
+
+    async fn select_user_by_serial_id(pool: &PgPool, id: i64) -> Result<Option<SerialUser>, sqlx::Error> {
+        let row = sqlx::query(SELECT_USER_BY_ID).bind(id).fetch_optional(pool).await?;
+        row.map(|r| r.serial_user(&UserColumns::TABLE)).transpose()
+    }
+
+    async fn select_posts_with_user_serial(
+        pool: &PgPool,
+        limit: i64,
+    ) -> Result<Vec<(SerialPost, SerialUser)>, sqlx::Error> {
+        let rows = sqlx::query(SELECT_POSTS_WITH_USER).bind(limit).fetch_all(pool).await?;
+        rows.iter()
+            .map(|r| Ok((r.serial_post(&PostColumns::JOINED)?, r.serial_user(&UserColumns::JOINED)?)))
+            .collect()
+    }
+}
+
+// ============================================================================
+// SeaORM
+// ============================================================================
+
+pub mod serial_users {
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+    #[sea_orm(schema_name = "bigserial", table_name = "users")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i64,
+        pub username: String,
+        pub email: String,
+        pub first_name: String,
+        pub last_name: String,
+        pub age: Option<i32>,
+        pub created_at: Option<DateTimeWithTimeZone>,
+        pub updated_at: Option<DateTimeWithTimeZone>,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {
+        #[sea_orm(has_many = "super::serial_posts::Entity")]
+        Posts,
+    }
+
+    impl Related<super::serial_posts::Entity> for Entity {
+        fn to() -> RelationDef {
+            Relation::Posts.def()
+        }
+    }
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+pub mod serial_posts {
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+    #[sea_orm(schema_name = "bigserial", table_name = "posts")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i64,
+        pub user_id: i64,
+        pub title: String,
+        pub content: String,
+        pub status: String,
+        pub view_count: i32,
+        pub created_at: Option<DateTimeWithTimeZone>,
+        pub updated_at: Option<DateTimeWithTimeZone>,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {
+        #[sea_orm(
+            belongs_to = "super::serial_users::Entity",
+            from = "Column::UserId",
+            to = "super::serial_users::Column::Id"
+        )]
+        User,
+    }
+
+    impl Related<super::serial_users::Entity> for Entity {
+        fn to() -> RelationDef {
+            Relation::User.def()
+        }
+    }
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+impl From<serial_users::Model> for SerialUser {
+    fn from(u: serial_users::Model) -> Self {
+        Self {
+            id: u.id,
+            username: u.username,
+            email: u.email,
+            first_name: u.first_name,
+            last_name: u.last_name,
+            age: u.age,
+            created_at: u.created_at.map(Into::into),
+            updated_at: u.updated_at.map(Into::into),
+        }
+    }
+}
+
+impl From<serial_posts::Model> for SerialPost {
+    fn from(p: serial_posts::Model) -> Self {
+        Self {
+            id: p.id,
+            user_id: p.user_id,
+            title: p.title,
+            content: p.content,
+            status: p.status,
+            view_count: p.view_count,
+            created_at: p.created_at.map(Into::into),
+            updated_at: p.updated_at.map(Into::into),
+        }
+    }
+}
+
+impl SerialKeys for SeaOrmBench {
+    async fn insert_user_serial(db: &DatabaseConnection, user: &NewUser) -> Result<i64, DbErr> {
+        let model = serial_users::ActiveModel {
+            id: ActiveValue::NotSet,
+            username: ActiveValue::Set(user.username.clone()),
+            email: ActiveValue::Set(user.email.clone()),
+            first_name: ActiveValue::Set(user.first_name.clone()),
+            last_name: ActiveValue::Set(user.last_name.clone()),
+            age: ActiveValue::Set(user.age),
+            created_at: ActiveValue::NotSet,
+            updated_at: ActiveValue::NotSet,
+        };
+        let result = serial_users::Entity::insert(model).exec(db).await?;
+        Ok(result.last_insert_id)
+    }
+
+    async fn select_user_by_serial_id(db: &DatabaseConnection, id: i64) -> Result<Option<SerialUser>, DbErr> {
+        let user = serial_users::Entity::find_by_id(id).one(db).await?;
+        Ok(user.map(Into::into))
+    }
+
+    async fn select_posts_with_user_serial(
+        db: &DatabaseConnection,
+        limit: i64,
+    ) -> Result<Vec<(SerialPost, SerialUser)>, DbErr> {
+        let posts_with_users = serial_posts::Entity::find()
+            .find_also_related(serial_users::Entity)
+            .order_by_desc(serial_posts::Column::CreatedAt)
+            .limit(limit as u64)
+            .all(db)
+            .await?;
+
+        Ok(posts_with_users
+            .into_iter()
+            .filter_map(|(p, u)| u.map(|u| (p.into(), u.into())))
+            .collect())
+    }
+}
+
+// ============================================================================
+// Diesel
+// ============================================================================
+
+pub mod schema {
+    diesel::table! {
+        bigserial.users (id) {
+            id -> Int8,
+            username -> Varchar,
+            email -> Varchar,
+            first_name -> Varchar,
+            last_name -> Varchar,
+            age -> Nullable<Int4>,
+            created_at -> Nullable<Timestamptz>,
+            updated_at -> Nullable<Timestamptz>,
+        }
+    }
+
+    diesel::table! {
+        bigserial.posts (id) {
+            id -> Int8,
+            user_id -> Int8,
+            title -> Varchar,
+            content -> Text,
+            status -> Varchar,
+            view_count -> Int4,
+            created_at -> Nullable<Timestamptz>,
+            updated_at -> Nullable<Timestamptz>,
+        }
+    }
+
+    diesel::joinable!(posts -> users (user_id));
+
+    diesel::allow_tables_to_appear_in_same_query!(users, posts,);
+}
+
+mod diesel_models {
+    use super::schema::{posts, users};
+    use super::{SerialPost, SerialUser};
+    use diesel::prelude::*;
+
+    #[derive(Queryable, Selectable)]
+    #[diesel(table_name = users)]
+    pub struct DieselSerialUser {
+        pub id: i64,
+        pub username: String,
+        pub email: String,
+        pub first_name: String,
+        pub last_name: String,
+        pub age: Option<i32>,
+        pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+        pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    }
+
+    #[derive(Insertable)]
+    #[diesel(table_name = users)]
+    pub struct DieselNewSerialUser<'a> {
+        pub username: &'a str,
+        pub email: &'a str,
+        pub first_name: &'a str,
+        pub last_name: &'a str,
+        pub age: Option<i32>,
+    }
+
+    #[derive(Queryable, Selectable)]
+    #[diesel(table_name = posts)]
+    pub struct DieselSerialPost {
+        pub id: i64,
+        pub user_id: i64,
+        pub title: String,
+        pub content: String,
+        pub status: String,
+        pub view_count: i32,
+        pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+        pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    }
+
+    impl From<DieselSerialUser> for SerialUser {
+        fn from(u: DieselSerialUser) -> Self {
+            Self {
+                id: u.id,
+                username: u.username,
+                email: u.email,
+                first_name: u.first_name,
+                last_name: u.last_name,
+                age: u.age,
+                created_at: u.created_at,
+                updated_at: u.updated_at,
+            }
+        }
+    }
+
+    impl From<DieselSerialPost> for SerialPost {
+        fn from(p: DieselSerialPost) -> Self {
+            Self {
+                id: p.id,
+                user_id: p.user_id,
+                title: p.title,
+                content: p.content,
+                status: p.status,
+                view_count: p.view_count,
+                created_at: p.created_at,
+                updated_at: p.updated_at,
+            }
+        }
+    }
+}
+
+impl SerialKeys for DieselBench {
+    async fn insert_user_serial(conn: &Self::Connection, user: &NewUser) -> Result<i64, Self::Error> {
+        use diesel::prelude::*;
+        use diesel_models::DieselNewSerialUser;
+        use schema::users;
+
+        let new_user = DieselNewSerialUser {
+            username: &user.username,
+            email: &user.email,
+            first_name: &user.first_name,
+            last_name: &user.last_name,
+            age: user.age,
+        };
+        Ok(diesel::insert_into(users::table)
+            .values(&new_user)
+            .returning(users::id)
+            .get_result(&mut *conn.lock().unwrap())?)
+    }
+
+    async fn select_user_by_serial_id(conn: &Self::Connection, id: i64) -> Result<Option<SerialUser>, Self::Error> {
+        use diesel::prelude::*;
+        use diesel_models::DieselSerialUser;
+        use schema::users;
+
+        let user = users::table
+            .find(id)
+            .select(DieselSerialUser::as_select())
+            .first(&mut *conn.lock().unwrap())
+            .optional()?;
+        Ok(user.map(Into::into))
+    }
+
+    async fn select_posts_with_user_serial(
+        conn: &Self::Connection,
+        limit: i64,
+    ) -> Result<Vec<(SerialPost, SerialUser)>, Self::Error> {
+        use diesel::prelude::*;
+        use diesel_models::{DieselSerialPost, DieselSerialUser};
+        use schema::{posts, users};
+
+        let results = posts::table
+            .inner_join(users::table)
+            .order(posts::created_at.desc())
+            .limit(limit)
+            .select((DieselSerialPost::as_select(), DieselSerialUser::as_select()))
+            .load::<(DieselSerialPost, DieselSerialUser)>(&mut *conn.lock().unwrap())?;
+
+        Ok(results.into_iter().map(|(p, u)| (p.into(), u.into())).collect())
+    }
+}
@@ -0,0 +1,119 @@
+//! Named database profiles, one database per dataset scale
+//!
+//! Topping a database up from a thousand users to a million takes minutes,
+//! and there is no way back down short of re-seeding. Each of the
+//! [`PROFILES`] instead names a database of its own on the same server,
+//! seeded once to its scale with `cargo run -- profiles prepare <name>`.
+//! Selecting a profile, with `BENCH_PROFILE=<name>` or `profile = "<name>"`
+//! in a runner configuration, points [`crate::database_url`] at its database
+//! and makes its scale the default `SEED_USERS`, so switching between quick
+//! iteration and a large-scale run is a matter of a setting.
+
+use crate::run_all;
+use crate::seed::SeedConfig;
+use crate::seeder::{self, SeedRows};
+use std::sync::OnceLock;
+use tokio_postgres::Client;
+
+/// A database seeded to one scale
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Profile {
+    pub name: &'static str,
+    /// Database on the `DATABASE_URL` server
+    pub database: &'static str,
+    /// Seeded users, the default of `SEED_USERS` with the profile selected
+    pub users: usize,
+}
+
+pub const PROFILES: &[Profile] = &[
+    Profile { name: "small", database: "benchmark_small", users: 1_000 },
+    Profile { name: "medium", database: "benchmark_medium", users: 100_000 },
+    Profile { name: "large", database: "benchmark_large", users: 1_000_000 },
+];
+
+static SELECTED: OnceLock<Option<&'static Profile>> = OnceLock::new();
+
+impl Profile {
+    /// The profile called `name`
+    pub fn named(name: &str) -> Result<&'static Profile, String> {
+        PROFILES.iter().find(|p| p.name == name).ok_or_else(|| {
+            let names: Vec<&str> = PROFILES.iter().map(|p| p.name).collect();
+            format!("unknown profile {name:?}, expected one of {}", names.join(", "))
+        })
+    }
+
+    /// The dataset this profile is seeded to: the `SEED_*` configuration
+    /// with the profile's scale
+    pub fn seed_config(&self) -> SeedConfig {
+        SeedConfig { users: self.users, ..SeedConfig::from_env() }
+    }
+
+    /// `url` pointed at this profile's database
+    pub fn url(&self, url: &str) -> String {
+        with_database(url, self.database)
+    }
+}
+
+/// The profile this process runs against: the one [`select`]ed, or else the
+/// one `BENCH_PROFILE` names; `None` uses the `DATABASE_URL` database as is
+///
+/// # Panics
+///
+/// If `BENCH_PROFILE` names no profile, rather than run against the wrong database
+pub fn selected() -> Option<&'static Profile> {
+    *SELECTED.get_or_init(|| {
+        let name = std::env::var("BENCH_PROFILE").ok().filter(|name| !name.trim().is_empty())?;
+        Some(Profile::named(name.trim()).unwrap_or_else(|e| panic!("BENCH_PROFILE: {e}")))
+    })
+}
+
+/// Run this process against the profile called `name`; only possible before
+/// the first connection, unless it is the profile already selected
+pub fn select(name: &str) -> Result<&'static Profile, String> {
+    let profile = Profile::named(name)?;
+    let selected = *SELECTED.get_or_init(|| Some(profile));
+    if selected != Some(profile) {
+        let current = selected.map_or("none", |p| p.name);
+        return Err(format!("profile {name:?} selected after profile {current} was already in use"));
+    }
+    Ok(profile)
+}
+
+/// `url` with its database replaced by `database`, in URL or key/value form
+pub fn with_database(url: &str, database: &str) -> String {
+    let Some((scheme, rest)) = url.split_once("://") else {
+        return crate::with_param(url, "dbname", database);
+    };
+    let (target, query) = match rest.split_once('?') {
+        Some((target, query)) => (target, Some(query)),
+        None => (rest, None),
+    };
+    let host = target.split_once('/').map_or(target, |(host, _)| host);
+    match query {
+        Some(query) => format!("{scheme}://{host}/{database}?{query}"),
+        None => format!("{scheme}://{host}/{database}"),
+    }
+}
+
+/// Create `profile`'s database through `admin`, a connection to another
+/// database on the server, unless it exists; whether it was created
+pub async fn create_database(admin: &Client, profile: &Profile) -> Result<bool, tokio_postgres::Error> {
+    let exists: bool = admin
+        .query_one("SELECT EXISTS (SELECT 1 FROM pg_database WHERE datname = $1)", &[&profile.database])
+        .await?
+        .get(0);
+    if exists {
+        return Ok(false);
+    }
+    // Identifiers can't be parameters; the names are this module's constants
+    admin.batch_execute(&format!("CREATE DATABASE \"{}\"", profile.database)).await?;
+    Ok(true)
+}
+
+/// Give the database at `url`, `profile`'s, the schema and seed it to the
+/// profile's scale; the rows inserted
+pub async fn prepare(url: &str, profile: &Profile) -> Result<SeedRows, tokio_postgres::Error> {
+    let client = crate::bench_tokio_postgres::TokioPostgresBench::connect_to(url).await?;
+    run_all::create_schema(&client).await?;
+    seeder::top_up(url, &profile.seed_config()).await
+}
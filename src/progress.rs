@@ -0,0 +1,204 @@
+//! Live progress display for long benchmark runs
+//!
+//! The heavy, concurrent and soak-style groups run for many minutes with
+//! criterion printing little more than "Collecting 20 samples". With
+//! `BENCH_PROGRESS=1` the benches draw a status line on stderr instead,
+//! showing the group and backend being measured, how far through its warm-up
+//! and measurement time it is, and the operations per second and errors
+//! counted so far, so a stalled or failing backend is visible while it runs.
+//!
+//! The completion percentage is estimated from the time criterion is given
+//! for a benchmark (its warm-up plus measurement time); criterion may take
+//! longer when iterations are slow, so it stops at 99% until the benchmark
+//! finishes. Nothing is drawn unless stderr is a terminal.
+
+use indicatif::{ProgressBar, ProgressStyle};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+use std::time::{Duration, Instant};
+
+/// criterion's default warm-up time, which no group changes
+pub const WARM_UP: Duration = Duration::from_secs(3);
+
+/// How often the status line is redrawn and the live rate measured
+pub const REFRESH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// The benchmark being measured and what it has done so far
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    pub group: String,
+    pub backend: String,
+    pub elapsed: Duration,
+    /// Warm-up plus measurement time of the group, if known
+    pub budget: Option<Duration>,
+    pub operations: u64,
+    pub errors: u64,
+}
+
+impl Snapshot {
+    /// Estimated completion, 0 to 99 until the benchmark finishes
+    pub fn percent(&self) -> u64 {
+        match self.budget {
+            Some(budget) if !budget.is_zero() => ((self.elapsed.as_secs_f64() / budget.as_secs_f64() * 100.0) as u64).min(99),
+            _ => 0,
+        }
+    }
+
+    /// The status line, with `rate` the operations per second measured over
+    /// the last refresh
+    pub fn line(&self, rate: f64) -> String {
+        format!(
+            "{}/{}  {:>3}%  {:>5.0}s  {:>10} ops  {:>10.1} ops/s  {} error(s)",
+            self.group,
+            self.backend,
+            self.percent(),
+            self.elapsed.as_secs_f64(),
+            self.operations,
+            rate,
+            self.errors
+        )
+    }
+}
+
+#[derive(Debug, Default)]
+struct Current {
+    group: String,
+    budget: Option<Duration>,
+    /// The backend measured and when it started, while a benchmark runs
+    running: Option<(String, Instant)>,
+}
+
+#[derive(Default)]
+struct Shared {
+    current: Mutex<Current>,
+    operations: AtomicU64,
+    errors: AtomicU64,
+    bar: Mutex<Option<ProgressBar>>,
+}
+
+/// Counts operations and errors of the benchmark being measured and, when
+/// enabled, draws them on a status line
+pub struct LiveProgress {
+    enabled: bool,
+    shared: Arc<Shared>,
+    refresher: OnceLock<()>,
+}
+
+impl LiveProgress {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            shared: Arc::default(),
+            refresher: OnceLock::new(),
+        }
+    }
+
+    /// Benchmarks from now on belong to `group`, given `measurement_time`
+    pub fn group(&self, group: &str, measurement_time: Duration) {
+        let mut current = self.shared.current();
+        current.group = group.to_string();
+        current.budget = Some(WARM_UP + measurement_time);
+    }
+
+    /// A benchmark of `backend` in the current group starts, with its counts at zero
+    pub fn start(&self, backend: &str) {
+        self.shared.operations.store(0, Ordering::Relaxed);
+        self.shared.errors.store(0, Ordering::Relaxed);
+        self.shared.current().running = Some((backend.to_string(), Instant::now()));
+        if self.enabled {
+            *self.shared.bar() = Some(new_bar());
+            self.refresher.get_or_init(|| {
+                let shared = self.shared.clone();
+                std::thread::spawn(move || refresh(&shared));
+            });
+        }
+    }
+
+    /// One operation of the running benchmark finished
+    pub fn operation(&self, succeeded: bool) {
+        self.shared.operations.fetch_add(1, Ordering::Relaxed);
+        if !succeeded {
+            self.shared.errors.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// The running benchmark finished; its status line is cleared so
+    /// criterion's report starts on a clean line
+    pub fn finish(&self) {
+        self.shared.current().running = None;
+        if let Some(bar) = self.shared.bar().take() {
+            bar.finish_and_clear();
+        }
+    }
+
+    /// The running benchmark's progress, if one is running
+    pub fn snapshot(&self) -> Option<Snapshot> {
+        self.shared.snapshot().map(|(snapshot, _)| snapshot)
+    }
+}
+
+impl Shared {
+    fn current(&self) -> MutexGuard<'_, Current> {
+        self.current.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn bar(&self) -> MutexGuard<'_, Option<ProgressBar>> {
+        self.bar.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// The running benchmark's progress and when it started
+    fn snapshot(&self) -> Option<(Snapshot, Instant)> {
+        let current = self.current();
+        let (backend, started) = current.running.as_ref()?;
+        let snapshot = Snapshot {
+            group: current.group.clone(),
+            backend: backend.clone(),
+            elapsed: started.elapsed(),
+            budget: current.budget,
+            operations: self.operations.load(Ordering::Relaxed),
+            errors: self.errors.load(Ordering::Relaxed),
+        };
+        Some((snapshot, *started))
+    }
+}
+
+/// The display the benches share, as the process has one terminal and
+/// criterion runs one benchmark at a time. Enabled by `BENCH_PROGRESS=1`.
+pub fn bench_progress() -> &'static LiveProgress {
+    static PROGRESS: OnceLock<LiveProgress> = OnceLock::new();
+    PROGRESS.get_or_init(|| LiveProgress::new(std::env::var_os("BENCH_PROGRESS").is_some_and(|v| v != "0")))
+}
+
+fn new_bar() -> ProgressBar {
+    let bar = ProgressBar::new(100);
+    bar.set_style(
+        ProgressStyle::with_template("{spinner} [{bar:24}] {msg}")
+            .expect("valid template")
+            .progress_chars("=> "),
+    );
+    bar
+}
+
+/// Redraw the status line every [`REFRESH_INTERVAL`] for the life of the process
+fn refresh(shared: &Shared) {
+    // The benchmark last seen, its operations and when they were counted
+    let mut last: Option<(Instant, u64, Instant)> = None;
+    loop {
+        std::thread::sleep(REFRESH_INTERVAL);
+        let Some((snapshot, started)) = shared.snapshot() else {
+            continue;
+        };
+        let now = Instant::now();
+        let rate = match last {
+            Some((benchmark, operations, at)) if benchmark == started => {
+                snapshot.operations.saturating_sub(operations) as f64 / now.duration_since(at).as_secs_f64()
+            }
+            _ => 0.0,
+        };
+        last = Some((started, snapshot.operations, now));
+        if let Some(bar) = shared.bar().as_ref() {
+            bar.set_position(snapshot.percent());
+            bar.set_message(snapshot.line(rate));
+        }
+    }
+}
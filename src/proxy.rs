@@ -0,0 +1,116 @@
+//! A TCP proxy in front of the database server
+//!
+//! The failover scenario uses it as a primary that can go down while the
+//! server keeps running (see [`crate::failover`]); the large-result scenario
+//! uses it to cap the bandwidth between the benchmark and the server, so
+//! transfer time dominates the way it does over a real network.
+
+use std::io;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::{JoinHandle, JoinSet};
+use tokio::time::Instant;
+
+/// Bytes forwarded per read; small enough that pacing stays smooth at a few Mbit/s
+const CHUNK_SIZE: usize = 16 * 1024;
+
+/// Link speed for the bandwidth-limited scenario from `BENCH_BANDWIDTH_MBIT`
+/// (megabits per second), in bytes per second
+pub fn bandwidth_limit() -> Option<u64> {
+    let mbit: f64 = std::env::var("BENCH_BANDWIDTH_MBIT").ok()?.trim().parse().ok()?;
+    (mbit > 0.0).then(|| (mbit * 1_000_000.0 / 8.0) as u64)
+}
+
+/// A TCP forwarder standing in for a server that can go down or sits behind a slow link
+pub struct TcpProxy {
+    addr: SocketAddr,
+    accepted: Arc<AtomicUsize>,
+    task: JoinHandle<()>,
+}
+
+impl TcpProxy {
+    /// Listen on an ephemeral localhost port, forwarding every connection to `target`
+    pub async fn start(target: (String, u16)) -> io::Result<Self> {
+        Self::listen(target, None).await
+    }
+
+    /// Like [`TcpProxy::start`], but each connection forwards at most
+    /// `bytes_per_sec` in each direction
+    pub async fn start_throttled(target: (String, u16), bytes_per_sec: u64) -> io::Result<Self> {
+        Self::listen(target, Some(bytes_per_sec.max(1))).await
+    }
+
+    async fn listen(target: (String, u16), bytes_per_sec: Option<u64>) -> io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let accepted = Arc::new(AtomicUsize::new(0));
+
+        let counter = accepted.clone();
+        let task = tokio::spawn(async move {
+            // Dropped, and so aborted, together with the listener
+            let mut forwarders = JoinSet::new();
+            while let Ok((mut client, _)) = listener.accept().await {
+                counter.fetch_add(1, Ordering::SeqCst);
+                let target = target.clone();
+                forwarders.spawn(async move {
+                    let Ok(mut server) = TcpStream::connect(target).await else { return };
+                    let Some(bytes_per_sec) = bytes_per_sec else {
+                        let _ = tokio::io::copy_bidirectional(&mut client, &mut server).await;
+                        return;
+                    };
+                    let _ = client.set_nodelay(true);
+                    let _ = server.set_nodelay(true);
+                    let (client_read, client_write) = client.into_split();
+                    let (server_read, server_write) = server.into_split();
+                    tokio::join!(
+                        forward_paced(client_read, server_write, bytes_per_sec),
+                        forward_paced(server_read, client_write, bytes_per_sec),
+                    );
+                });
+            }
+        });
+
+        Ok(Self { addr, accepted, task })
+    }
+
+    /// Host and port to put in a connection string
+    pub fn address(&self) -> (String, u16) {
+        (self.addr.ip().to_string(), self.addr.port())
+    }
+
+    /// Connections accepted so far
+    pub fn connections(&self) -> usize {
+        self.accepted.load(Ordering::SeqCst)
+    }
+
+    /// Close the listener and every forwarded connection, as if the host went down
+    pub async fn shutdown(self) {
+        self.task.abort();
+        let _ = self.task.await;
+    }
+}
+
+/// Copy `from` to `to`, sleeping after each chunk until the link would have carried it.
+///
+/// Idle time earns no credit, so a burst after a pause is paced like any other.
+async fn forward_paced(mut from: OwnedReadHalf, mut to: OwnedWriteHalf, bytes_per_sec: u64) {
+    let mut buf = vec![0; CHUNK_SIZE];
+    let mut free_at = Instant::now();
+    loop {
+        let n = match from.read(&mut buf).await {
+            Ok(0) | Err(_) => break,
+            Ok(n) => n,
+        };
+        if to.write_all(&buf[..n]).await.is_err() {
+            break;
+        }
+        free_at = free_at.max(Instant::now()) + Duration::from_secs_f64(n as f64 / bytes_per_sec as f64);
+        tokio::time::sleep_until(free_at).await;
+    }
+    let _ = to.shutdown().await;
+}
@@ -0,0 +1,117 @@
+//! SQL generation on its own, for the query builder microbenchmark
+//!
+//! Each function builds one of the benchmark's reads the way a backend does
+//! before sending it, without a connection: diesel's query DSL rendered with
+//! `debug_query`, SeaORM's `Select` built into a `Statement` through
+//! sea-query, sea-query used directly with its values bound for sqlx, and
+//! the raw drivers' SQL assembled with `format!`. The builder chains are the
+//! ones `bench_diesel`, `bench_seaorm` and `bench_seaquery` run, so the time
+//! they take is the builder overhead the database benchmarks include.
+
+use crate::bench_diesel::schema::{posts, users};
+use crate::bench_diesel::{DieselPost, DieselUser};
+use crate::bench_seaorm;
+use crate::bench_seaquery::SeaQueryBench;
+use diesel::pg::Pg;
+use diesel::prelude::*;
+use sea_orm::{DatabaseBackend, EntityTrait, QueryOrder, QuerySelect, QueryTrait, Statement};
+use sea_query_binder::SqlxValues;
+use uuid::Uuid;
+
+/// A read whose SQL every builder generates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Query {
+    /// Point lookup by primary key
+    UserById,
+    /// The newest users, ordered and limited
+    UsersLimit,
+    /// The newest posts joined with their authors
+    PostsWithUser,
+}
+
+impl Query {
+    pub const ALL: &'static [Query] = &[Query::UserById, Query::UsersLimit, Query::PostsWithUser];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Query::UserById => "select_user_by_id",
+            Query::UsersLimit => "select_users_limit",
+            Query::PostsWithUser => "join_posts_users",
+        }
+    }
+}
+
+/// diesel's query for `query`, rendered with its bind values
+pub fn diesel(query: Query, id: Uuid, limit: i64) -> String {
+    match query {
+        Query::UserById => {
+            let query = users::table.find(id).select(DieselUser::as_select()).limit(1);
+            diesel::debug_query::<Pg, _>(&query).to_string()
+        }
+        Query::UsersLimit => {
+            let query = users::table
+                .order(users::created_at.desc())
+                .limit(limit)
+                .select(DieselUser::as_select());
+            diesel::debug_query::<Pg, _>(&query).to_string()
+        }
+        Query::PostsWithUser => {
+            let query = posts::table
+                .inner_join(users::table)
+                .order(posts::created_at.desc())
+                .limit(limit)
+                .select((DieselPost::as_select(), DieselUser::as_select()));
+            diesel::debug_query::<Pg, _>(&query).to_string()
+        }
+    }
+}
+
+/// SeaORM's statement for `query`, with its values bound separately
+pub fn sea_orm(query: Query, id: Uuid, limit: u64) -> Statement {
+    use bench_seaorm::{posts, users};
+    match query {
+        Query::UserById => users::Entity::find_by_id(id).limit(1).build(DatabaseBackend::Postgres),
+        Query::UsersLimit => users::Entity::find()
+            .order_by_desc(users::Column::CreatedAt)
+            .limit(limit)
+            .build(DatabaseBackend::Postgres),
+        Query::PostsWithUser => posts::Entity::find()
+            .find_also_related(users::Entity)
+            .order_by_desc(posts::Column::CreatedAt)
+            .limit(limit)
+            .build(DatabaseBackend::Postgres),
+    }
+}
+
+/// sea-query's statement for `query` without SeaORM's entities, with its
+/// values bound for sqlx
+pub fn sea_query(query: Query, id: Uuid, limit: i64) -> (String, SqlxValues) {
+    match query {
+        Query::UserById => SeaQueryBench::select_user_by_id_statement(id),
+        Query::UsersLimit => SeaQueryBench::select_users_limit_statement(limit),
+        Query::PostsWithUser => SeaQueryBench::select_posts_with_user_statement(limit),
+    }
+}
+
+/// The raw drivers' SQL for `query` with the values written in as literals,
+/// as [`crate::batch`] does for the simple query protocol
+pub fn raw(query: Query, id: Uuid, limit: i64) -> String {
+    match query {
+        Query::UserById => format!(
+            "SELECT id, username, email, first_name, last_name, age, created_at, updated_at \
+             FROM users WHERE id = '{id}'"
+        ),
+        Query::UsersLimit => format!(
+            "SELECT id, username, email, first_name, last_name, age, created_at, updated_at \
+             FROM users ORDER BY created_at DESC LIMIT {limit}"
+        ),
+        Query::PostsWithUser => format!(
+            "SELECT p.id as post_id, p.user_id, p.title, p.content, p.status, p.view_count, \
+             p.created_at as post_created_at, p.updated_at as post_updated_at, \
+             u.id as user_id, u.username, u.email, u.first_name, u.last_name, u.age, \
+             u.created_at as user_created_at, u.updated_at as user_updated_at \
+             FROM posts p JOIN users u ON p.user_id = u.id \
+             ORDER BY p.created_at DESC LIMIT {limit}"
+        ),
+    }
+}
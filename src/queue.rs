@@ -0,0 +1,388 @@
+//! A producer/consumer work queue in a table, as a composite benchmark
+//!
+//! Producers insert batches of jobs into [`TABLE`]; consumers claim the oldest
+//! pending ones with `FOR UPDATE SKIP LOCKED`, so concurrent consumers never
+//! wait on each other's rows, process them and mark them done. [`run`] times
+//! one round from the first insert until every job of it is done, which is
+//! the throughput a job system built on each backend would reach: three
+//! statements per batch, row locking under contention and a pool shared by
+//! both sides.
+//!
+//! The table isn't part of `init.sql`; [`create`] adds it when the benchmark
+//! first runs, like the copies of the other comparative schemas.
+
+use crate::bench_clorinde::queries;
+use crate::bench_diesel::{DbPool, DieselBench};
+use crate::{consume, BoxError};
+use deadpool_postgres::Pool;
+use sea_orm::sea_query::{Expr, LockBehavior, LockType, Query};
+use sea_orm::{ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, Order, QueryFilter};
+use sqlx::PgPool;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::task::JoinSet;
+use tokio_postgres::Client;
+
+/// The queue's table
+pub const TABLE: &str = "jobs";
+
+/// How long a consumer that found nothing to claim waits before trying again
+pub const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+const CREATE_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS jobs (
+        id BIGSERIAL PRIMARY KEY,
+        payload TEXT NOT NULL,
+        status VARCHAR(10) NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'running', 'done')),
+        created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+        done_at TIMESTAMP WITH TIME ZONE
+    );
+    CREATE INDEX IF NOT EXISTS idx_jobs_pending ON jobs(id) WHERE status = 'pending';";
+
+const ENQUEUE: &str = "INSERT INTO jobs (payload) SELECT unnest($1::text[])";
+
+const CLAIM: &str = "UPDATE jobs SET status = 'running'
+     WHERE id IN (
+         SELECT id FROM jobs WHERE status = 'pending' ORDER BY id LIMIT $1 FOR UPDATE SKIP LOCKED
+     )
+     RETURNING id, payload";
+
+const COMPLETE: &str = "UPDATE jobs SET status = 'done', done_at = NOW() WHERE id = ANY($1)";
+
+/// Create [`TABLE`] if the database doesn't have it yet
+pub async fn create(client: &Client) -> Result<(), tokio_postgres::Error> {
+    client.batch_execute(CREATE_TABLE).await
+}
+
+/// Remove every job, so each round starts from an empty queue
+pub async fn reset(client: &Client) -> Result<(), tokio_postgres::Error> {
+    client.batch_execute("TRUNCATE jobs RESTART IDENTITY").await
+}
+
+/// Jobs with `status`
+pub async fn count(client: &Client, status: &str) -> Result<i64, tokio_postgres::Error> {
+    Ok(client
+        .query_one("SELECT COUNT(*) FROM jobs WHERE status = $1", &[&status])
+        .await?
+        .get(0))
+}
+
+/// Mean time from insert to done over the jobs done so far
+pub async fn mean_latency(client: &Client) -> Result<Duration, tokio_postgres::Error> {
+    let secs: Option<f64> = client
+        .query_one(
+            "SELECT EXTRACT(EPOCH FROM AVG(done_at - created_at))::float8 FROM jobs WHERE status = 'done'",
+            &[],
+        )
+        .await?
+        .get(0);
+    Ok(Duration::from_secs_f64(secs.unwrap_or(0.0)))
+}
+
+/// A claimed job
+#[derive(Debug, Clone, PartialEq)]
+pub struct Job {
+    pub id: i64,
+    pub payload: String,
+}
+
+/// The work a consumer does per job: a checksum of its payload
+pub fn process(job: &Job) -> u64 {
+    job.payload.bytes().fold(job.id as u64, |sum, b| sum.rotate_left(5) ^ u64::from(b))
+}
+
+/// The queue's statements on one backend's pool
+pub trait JobQueue: Clone + Send + Sync + 'static {
+    /// Insert a pending job per payload, in one statement
+    fn enqueue(&self, payloads: Vec<String>) -> impl Future<Output = Result<(), BoxError>> + Send;
+
+    /// Mark up to `limit` of the oldest pending jobs running and return them,
+    /// skipping rows other consumers hold locked
+    fn claim(&self, limit: i64) -> impl Future<Output = Result<Vec<Job>, BoxError>> + Send;
+
+    /// Mark the jobs `ids` done
+    fn complete(&self, ids: Vec<i64>) -> impl Future<Output = Result<(), BoxError>> + Send;
+}
+
+/// tokio-postgres behind a deadpool pool
+#[derive(Clone)]
+pub struct TokioPostgresQueue(pub Pool);
+
+impl JobQueue for TokioPostgresQueue {
+    async fn enqueue(&self, payloads: Vec<String>) -> Result<(), BoxError> {
+        self.0.get().await?.execute(ENQUEUE, &[&payloads]).await?;
+        Ok(())
+    }
+
+    async fn claim(&self, limit: i64) -> Result<Vec<Job>, BoxError> {
+        let rows = self.0.get().await?.query(CLAIM, &[&limit]).await?;
+        Ok(rows.iter().map(|row| Job { id: row.get(0), payload: row.get(1) }).collect())
+    }
+
+    async fn complete(&self, ids: Vec<i64>) -> Result<(), BoxError> {
+        self.0.get().await?.execute(COMPLETE, &[&ids]).await?;
+        Ok(())
+    }
+}
+
+/// sqlx's own pool
+#[derive(Clone)]
+pub struct SqlxQueue(pub PgPool);
+
+impl JobQueue for SqlxQueue {
+    async fn enqueue(&self, payloads: Vec<String>) -> Result<(), BoxError> {
+        sqlx::query(ENQUEUE).bind(payloads).execute(&self.0).await?;
+        Ok(())
+    }
+
+    async fn claim(&self, limit: i64) -> Result<Vec<Job>, BoxError> {
+        let jobs: Vec<(i64, String)> = sqlx::query_as(CLAIM).bind(limit).fetch_all(&self.0).await?;
+        Ok(jobs.into_iter().map(|(id, payload)| Job { id, payload }).collect())
+    }
+
+    async fn complete(&self, ids: Vec<i64>) -> Result<(), BoxError> {
+        sqlx::query(COMPLETE).bind(ids).execute(&self.0).await?;
+        Ok(())
+    }
+}
+
+pub mod jobs {
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+    #[sea_orm(table_name = "jobs")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i64,
+        pub payload: String,
+        pub status: String,
+        pub created_at: DateTimeWithTimeZone,
+        pub done_at: Option<DateTimeWithTimeZone>,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+/// SeaORM's query builder over its sqlx pool
+#[derive(Clone)]
+pub struct SeaOrmQueue(pub DatabaseConnection);
+
+impl JobQueue for SeaOrmQueue {
+    async fn enqueue(&self, payloads: Vec<String>) -> Result<(), BoxError> {
+        let models = payloads.into_iter().map(|payload| jobs::ActiveModel {
+            payload: ActiveValue::Set(payload),
+            ..Default::default()
+        });
+        jobs::Entity::insert_many(models).exec_without_returning(&self.0).await?;
+        Ok(())
+    }
+
+    async fn claim(&self, limit: i64) -> Result<Vec<Job>, BoxError> {
+        let pending = Query::select()
+            .column(jobs::Column::Id)
+            .from(jobs::Entity)
+            .and_where(jobs::Column::Status.eq("pending"))
+            .order_by(jobs::Column::Id, Order::Asc)
+            .limit(limit as u64)
+            .lock_with_behavior(LockType::Update, LockBehavior::SkipLocked)
+            .to_owned();
+        let claimed = jobs::Entity::update_many()
+            .col_expr(jobs::Column::Status, Expr::value("running"))
+            .filter(jobs::Column::Id.in_subquery(pending))
+            .exec_with_returning(&self.0)
+            .await?;
+        Ok(claimed.into_iter().map(|job| Job { id: job.id, payload: job.payload }).collect())
+    }
+
+    async fn complete(&self, ids: Vec<i64>) -> Result<(), BoxError> {
+        jobs::Entity::update_many()
+            .col_expr(jobs::Column::Status, Expr::value("done"))
+            .col_expr(jobs::Column::DoneAt, Expr::current_timestamp().into())
+            .filter(jobs::Column::Id.is_in(ids))
+            .exec(&self.0)
+            .await?;
+        Ok(())
+    }
+}
+
+pub mod schema {
+    diesel::table! {
+        jobs (id) {
+            id -> Int8,
+            payload -> Text,
+            status -> Varchar,
+            created_at -> Timestamptz,
+            done_at -> Nullable<Timestamptz>,
+        }
+    }
+}
+
+/// diesel's query builder behind r2d2, on the blocking thread pool
+/// ([`DieselBench::run_blocking`])
+#[derive(Clone)]
+pub struct DieselQueue(pub DbPool);
+
+impl JobQueue for DieselQueue {
+    async fn enqueue(&self, payloads: Vec<String>) -> Result<(), BoxError> {
+        DieselBench::run_blocking(&self.0, move |conn| {
+            use diesel::prelude::*;
+            use schema::jobs;
+
+            let rows: Vec<_> = payloads.iter().map(|payload| jobs::payload.eq(payload)).collect();
+            diesel::insert_into(jobs::table).values(&rows).execute(conn)
+        })
+        .await?;
+        Ok(())
+    }
+
+    async fn claim(&self, limit: i64) -> Result<Vec<Job>, BoxError> {
+        let claimed = DieselBench::run_blocking(&self.0, move |conn| {
+            use diesel::prelude::*;
+            use schema::jobs;
+
+            // The subquery reads the table the statement updates, which diesel needs an alias for
+            let pending_jobs = diesel::alias!(jobs as pending_jobs);
+            let pending = pending_jobs
+                .select(pending_jobs.field(jobs::id))
+                .filter(pending_jobs.field(jobs::status).eq("pending"))
+                .order(pending_jobs.field(jobs::id))
+                .limit(limit)
+                .for_update()
+                .skip_locked();
+            diesel::update(jobs::table)
+                .filter(jobs::id.eq_any(pending))
+                .set(jobs::status.eq("running"))
+                .returning((jobs::id, jobs::payload))
+                .get_results::<(i64, String)>(conn)
+        })
+        .await?;
+        Ok(claimed.into_iter().map(|(id, payload)| Job { id, payload }).collect())
+    }
+
+    async fn complete(&self, ids: Vec<i64>) -> Result<(), BoxError> {
+        DieselBench::run_blocking(&self.0, move |conn| {
+            use diesel::dsl::now;
+            use diesel::prelude::*;
+            use schema::jobs;
+
+            diesel::update(jobs::table.filter(jobs::id.eq_any(&ids)))
+                .set((jobs::status.eq("done"), jobs::done_at.eq(now)))
+                .execute(conn)
+        })
+        .await?;
+        Ok(())
+    }
+}
+
+/// The clorinde queries over tokio-postgres clients from a deadpool pool.
+/// They run on the pooled [`Client`] itself, as [`TokioPostgresQueue`]'s do:
+/// given deadpool's wrapper, the generated code would prepare them through
+/// deadpool's statement cache instead.
+#[derive(Clone)]
+pub struct ClorindeQueue(pub Pool);
+
+impl JobQueue for ClorindeQueue {
+    async fn enqueue(&self, payloads: Vec<String>) -> Result<(), BoxError> {
+        let client = self.0.get().await?;
+        let client: &Client = &client;
+        queries::jobs::enqueue_jobs().bind(client, &payloads).await?;
+        Ok(())
+    }
+
+    async fn claim(&self, limit: i64) -> Result<Vec<Job>, BoxError> {
+        let client = self.0.get().await?;
+        let client: &Client = &client;
+        let claimed = queries::jobs::claim_jobs().bind(client, &limit).all().await?;
+        Ok(claimed.into_iter().map(|job| Job { id: job.id, payload: job.payload }).collect())
+    }
+
+    async fn complete(&self, ids: Vec<i64>) -> Result<(), BoxError> {
+        let client = self.0.get().await?;
+        let client: &Client = &client;
+        queries::jobs::complete_jobs().bind(client, &ids).await?;
+        Ok(())
+    }
+}
+
+/// The shape of one round
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueueLoad {
+    /// Jobs per round
+    pub jobs: usize,
+    /// Tasks inserting the jobs, a batch at a time
+    pub producers: usize,
+    /// Tasks claiming, processing and completing them
+    pub consumers: usize,
+    /// Jobs per insert and per claim
+    pub batch: usize,
+}
+
+impl QueueLoad {
+    /// Connections the producers and consumers hold at once at most
+    pub fn connections(&self) -> usize {
+        self.producers + self.consumers
+    }
+}
+
+/// Run one round of `load` on `queue`: the time from the first insert until
+/// every job is done. `round` goes into the payloads, so rounds differ.
+pub async fn run<Q: JobQueue>(queue: &Q, load: QueueLoad, round: usize) -> Result<Duration, BoxError> {
+    let start = Instant::now();
+    let done = Arc::new(AtomicUsize::new(0));
+    let mut tasks = JoinSet::new();
+
+    let batches: Vec<Vec<String>> = (0..load.jobs)
+        .map(|job| format!("{{\"round\":{round},\"job\":{job}}}"))
+        .collect::<Vec<_>>()
+        .chunks(load.batch)
+        .map(<[String]>::to_vec)
+        .collect();
+    for producer in 0..load.producers {
+        let queue = queue.clone();
+        // Every producer takes every `producers`th batch
+        let mine: Vec<Vec<String>> = batches.iter().skip(producer).step_by(load.producers).cloned().collect();
+        tasks.spawn(async move {
+            for payloads in mine {
+                queue.enqueue(payloads).await?;
+            }
+            Ok::<_, BoxError>(())
+        });
+    }
+    for _ in 0..load.consumers {
+        let queue = queue.clone();
+        let done = Arc::clone(&done);
+        tasks.spawn(async move {
+            while done.load(Ordering::SeqCst) < load.jobs {
+                let jobs = queue.claim(load.batch as i64).await?;
+                if jobs.is_empty() {
+                    tokio::time::sleep(POLL_INTERVAL).await;
+                    continue;
+                }
+                let ids = jobs
+                    .iter()
+                    .map(|job| {
+                        consume(process(job));
+                        job.id
+                    })
+                    .collect();
+                queue.complete(ids).await?;
+                done.fetch_add(jobs.len(), Ordering::SeqCst);
+            }
+            Ok(())
+        });
+    }
+
+    while let Some(result) = tasks.join_next().await {
+        // Consumers would wait forever for the jobs of a failed producer
+        if let Err(e) = result.map_err(BoxError::from).and_then(|r| r) {
+            tasks.abort_all();
+            return Err(e);
+        }
+    }
+    Ok(start.elapsed())
+}
@@ -0,0 +1,73 @@
+//! Read-your-writes latency across connections
+//!
+//! An app that inserts a row, redirects and reads it back usually gets a
+//! different connection from its pool for the read than it had for the
+//! write. [`write_then_read`] times that flow per backend: an insert on one
+//! connection of a [`Pair`], then lookups on the other until the row shows
+//! up. On a single server the first lookup after the commit returns sees
+//! it, so the time is one round trip through the second connection;
+//! anything more is counted in [`Visibility::stale_reads`].
+
+use crate::{DatabaseBenchmark, NewUser};
+use std::time::{Duration, Instant};
+
+/// Two connections of one backend, opened separately so that the reads
+/// never share the writer's connection or pool
+pub struct Pair<C> {
+    pub writer: C,
+    pub reader: C,
+}
+
+impl<C> Pair<C> {
+    /// Connect `B` twice to [`crate::database_url`]
+    pub async fn connect<B>() -> Result<Self, B::Error>
+    where
+        B: DatabaseBenchmark<Connection = C>,
+    {
+        Ok(Self {
+            writer: B::connect().await?,
+            reader: B::connect().await?,
+        })
+    }
+}
+
+/// When one write became visible to the other connection
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Visibility {
+    /// Time from the insert returning to the lookup that returned the row,
+    /// or to giving up
+    pub latency: Duration,
+    /// Lookups that didn't return the row yet
+    pub stale_reads: usize,
+    /// Whether a lookup returned the row before the timeout
+    pub visible: bool,
+}
+
+/// Insert `bench_user_{index}` on the writer and look it up by id on the
+/// reader until it is returned or `timeout` passes.
+///
+/// The user is left in place for the caller's cleanup.
+pub async fn write_then_read<B: DatabaseBenchmark>(
+    pair: &Pair<B::Connection>,
+    index: usize,
+    timeout: Duration,
+) -> Result<Visibility, B::Error> {
+    let id = B::insert_user(&pair.writer, &NewUser::generate(index)).await?;
+    let committed = Instant::now();
+
+    let mut stale_reads = 0;
+    let visible = loop {
+        if B::select_user_by_id(&pair.reader, id).await?.is_some() {
+            break true;
+        }
+        stale_reads += 1;
+        if committed.elapsed() > timeout {
+            break false;
+        }
+    };
+    Ok(Visibility {
+        latency: committed.elapsed(),
+        stale_reads,
+        visible,
+    })
+}
@@ -0,0 +1,535 @@
+//! Workload replay
+//!
+//! Reads a recorded workload, either a server log written with
+//! `log_min_duration_statement = 0` or a `pg_stat_statements` dump, maps its
+//! statements onto the benchmark [`Operation`]s, and replays the resulting mix
+//! against a backend, so each library can be measured on the shape of a real
+//! application's traffic rather than one operation at a time.
+//!
+//! Statements are recognised by what they do, not their exact text, so the
+//! SQL of any of the benchmarked libraries maps back to the same operation:
+//! identifiers are unquoted and lowercased, and the tables named after `FROM`
+//! and `JOIN` decide what a `SELECT` is. A `BEGIN`, one user insert, post
+//! inserts and a `COMMIT` in the same session become one
+//! `transaction_insert_user_with_posts`; that needs the session's process id in
+//! `log_line_prefix` (the default `'%m [%p] '` has it). Transaction control and
+//! session settings are ignored, and statements that match no operation are
+//! counted and listed rather than replayed.
+
+use crate::scenario::{Fixture, Operation, Scenario, SCENARIOS};
+use crate::DatabaseBenchmark;
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, Instant};
+
+/// Longest statement text kept for the unmatched-statement list
+const UNMATCHED_TEXT_LEN: usize = 120;
+
+/// One statement of a recorded workload
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recorded {
+    pub sql: String,
+    /// Bound parameter values, `$1` first, when the log has them
+    pub params: Vec<String>,
+    /// How often the statement ran
+    pub calls: u64,
+    /// Server process id, which identifies the session in a log
+    pub session: Option<u32>,
+}
+
+/// Parse a recorded workload, telling a `pg_stat_statements` dump from a server log
+pub fn parse(text: &str) -> Vec<Recorded> {
+    let is_dump = text
+        .lines()
+        .find(|line| !line.trim().is_empty())
+        .and_then(|line| line.split_once('\t'))
+        .is_some_and(|(calls, _)| calls.trim().parse::<u64>().is_ok());
+    if is_dump {
+        parse_stat_statements(text)
+    } else {
+        parse_server_log(text)
+    }
+}
+
+/// Statements from a `pg_stat_statements` dump with one `calls<TAB>query` line
+/// per statement, as written by
+///
+/// ```sql
+/// \copy (SELECT calls, regexp_replace(query, '\s+', ' ', 'g') FROM pg_stat_statements) TO 'workload.tsv'
+/// ```
+///
+/// Constants are normalised to placeholders, so `LIMIT`s take their scenario's typical size.
+pub fn parse_stat_statements(text: &str) -> Vec<Recorded> {
+    text.lines()
+        .filter_map(|line| {
+            let (calls, query) = line.split_once('\t')?;
+            Some(Recorded {
+                sql: query.trim().to_string(),
+                params: Vec::new(),
+                calls: calls.trim().parse().ok()?,
+                session: None,
+            })
+        })
+        .collect()
+}
+
+/// Statements from a server log: `statement:` lines of the simple query
+/// protocol and `execute` lines of the extended one (not `parse` or `bind`),
+/// with their tab-indented continuation lines and `DETAIL:  parameters:`
+pub fn parse_server_log(text: &str) -> Vec<Recorded> {
+    let mut recorded: Vec<Recorded> = Vec::new();
+    // Whether continuation lines, and a `DETAIL` line, belong to the last recorded statement;
+    // `parse` and `bind` lines have them too
+    let (mut continues, mut detail) = (false, false);
+
+    for line in text.lines() {
+        if let Some(rest) = line.strip_prefix('\t') {
+            if continues {
+                let last = recorded.last_mut().expect("continuation without a statement");
+                last.sql.push(' ');
+                last.sql.push_str(rest.trim());
+            }
+            continue;
+        }
+
+        if let Some((_, params)) = line.split_once("DETAIL:  parameters: ") {
+            if detail {
+                recorded.last_mut().expect("detail without a statement").params = parse_parameters(params);
+            }
+            (continues, detail) = (false, false);
+        } else if let Some(sql) = statement_text(line) {
+            recorded.push(Recorded {
+                sql: sql.trim().to_string(),
+                params: Vec::new(),
+                calls: 1,
+                session: session(line),
+            });
+            (continues, detail) = (true, true);
+        } else {
+            (continues, detail) = (false, false);
+        }
+    }
+    recorded
+}
+
+/// The SQL of a `LOG:` line reporting an executed statement
+fn statement_text(line: &str) -> Option<&str> {
+    let (_, message) = line.split_once("LOG:  ")?;
+    // With both log_statement and log_min_duration_statement set a statement is logged
+    // twice; only the duration line counts
+    let message = message.strip_prefix("duration: ")?.split_once(" ms  ")?.1;
+    if let Some(sql) = message.strip_prefix("statement: ") {
+        return Some(sql);
+    }
+    message.strip_prefix("execute ")?.split_once(": ").map(|(_, sql)| sql)
+}
+
+/// The process id in `[...]` before `LOG:`, as the default `log_line_prefix` writes it
+fn session(line: &str) -> Option<u32> {
+    let prefix = line.split_once("LOG:")?.0;
+    let (_, pid) = prefix.rsplit_once('[')?;
+    pid.split_once(']')?.0.parse().ok()
+}
+
+/// `$1 = '10', $2 = NULL` to `["10", "NULL"]`
+fn parse_parameters(params: &str) -> Vec<String> {
+    params
+        .split(", $")
+        .map(|param| {
+            let value = param.split_once(" = ").map_or(param, |(_, value)| value);
+            match value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')) {
+                Some(quoted) => quoted.replace("''", "'"),
+                None => value.to_string(),
+            }
+        })
+        .collect()
+}
+
+/// What a recorded statement does
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Classified {
+    /// A benchmark operation, with the `LIMIT` or batch size when known
+    Operation(Operation, Option<usize>),
+    /// `INSERT INTO posts` of this many rows, part of a transaction scenario
+    InsertPosts(usize),
+    Begin,
+    /// `COMMIT` or `ROLLBACK`
+    End,
+    /// Session settings and other statements that are not part of the workload
+    Ignored,
+    Unmatched,
+}
+
+/// Map one statement onto a benchmark operation
+pub fn classify(sql: &str, params: &[String]) -> Classified {
+    let sql = normalize(sql);
+    let verb = sql.split(' ').next().unwrap_or_default();
+    match verb {
+        "begin" | "start" => return Classified::Begin,
+        "commit" | "rollback" | "end" | "abort" => return Classified::End,
+        "set" | "show" | "reset" | "deallocate" | "discard" | "listen" | "unlisten" => return Classified::Ignored,
+        _ => {}
+    }
+
+    if let Some(table) = sql.strip_prefix("insert into ") {
+        let rows = values_rows(&sql);
+        return match table.split([' ', '(']).next() {
+            Some("users") if rows > 1 => Classified::Operation(Operation::InsertUsersBatch, Some(rows)),
+            Some("users") => Classified::Operation(Operation::InsertUser, None),
+            Some("posts") => Classified::InsertPosts(rows),
+            Some("comments") => Classified::Operation(Operation::InsertComment, None),
+            _ => Classified::Unmatched,
+        };
+    }
+    if sql.starts_with("update users ") {
+        return Classified::Operation(Operation::UpdateUser, None);
+    }
+    if verb != "select" {
+        return Classified::Unmatched;
+    }
+
+    let tables = tables(&sql);
+    let has = |table: &str| tables.iter().any(|t| t == table);
+    let limit = limit(&sql, params);
+    let only_users = tables.iter().all(|t| t == "users");
+    let operation = if has("users") && has("posts") && sql.contains("count(") && sql.contains(" group by ") {
+        Operation::CountPostsPerUser
+    } else if has("tags") && has("post_tags") && sql.contains("count(") && sql.contains(" group by ") {
+        Operation::CountPostsPerTag
+    } else if has("users") && has("posts") && has("comments") {
+        Operation::SelectUsersPostsComments
+    } else if has("users") && has("posts") && tables.len() == 2 {
+        Operation::SelectPostsWithUser
+    } else if has("users") && only_users && where_clause(&sql).is_some_and(|w| w.contains("id = ")) {
+        return Classified::Operation(Operation::SelectUserById, None);
+    } else if has("users") && only_users && where_clause(&sql).is_some_and(|w| w.contains("age ")) {
+        Operation::SelectUsersFiltered
+    } else if has("users") && only_users && sql.contains(" limit ") {
+        Operation::SelectUsersLimit
+    } else {
+        return Classified::Unmatched;
+    };
+    Classified::Operation(operation, limit)
+}
+
+/// Lowercase with identifiers unquoted, whitespace collapsed and `public.` dropped
+fn normalize(sql: &str) -> String {
+    sql.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace('"', "")
+        .replace("public.", "")
+        .to_lowercase()
+}
+
+/// Tables named after `FROM` and `JOIN`, including in subqueries
+fn tables(sql: &str) -> Vec<String> {
+    let words: Vec<&str> = sql.split(' ').collect();
+    let mut tables: Vec<String> = words
+        .windows(2)
+        .filter(|pair| pair[0] == "from" || pair[0] == "join" || pair[0] == "(from")
+        .map(|pair| pair[1].trim_start_matches('(').trim_end_matches([',', ')', ';']).to_string())
+        .filter(|table| !table.is_empty() && !table.starts_with("select"))
+        .collect();
+    tables.sort();
+    tables.dedup();
+    tables
+}
+
+/// Text after the first ` where `, up to `ORDER BY` or `LIMIT`
+fn where_clause(sql: &str) -> Option<&str> {
+    let (_, rest) = sql.split_once(" where ")?;
+    rest.split(" order by ").next()?.split(" limit ").next()
+}
+
+/// The row limit, from a literal or a bound parameter
+fn limit(sql: &str, params: &[String]) -> Option<usize> {
+    let (_, rest) = sql.rsplit_once(" limit ")?;
+    let token = rest.split([' ', ')', ';']).next()?;
+    match token.strip_prefix('$') {
+        Some(index) => params.get(index.parse::<usize>().ok()?.checked_sub(1)?)?.parse().ok(),
+        None => token.parse().ok(),
+    }
+}
+
+/// Rows in a `VALUES` list: one per parenthesised tuple, or one per element of
+/// an `unnest` of bound arrays (counted as one, their lengths being unknown)
+fn values_rows(sql: &str) -> usize {
+    let Some((_, values)) = sql.split_once(" values ") else {
+        return 1;
+    };
+    let values = values.split(" returning ").next().unwrap_or(values);
+    let mut depth = 0usize;
+    let mut rows = 0;
+    for c in values.chars() {
+        match c {
+            '(' => {
+                if depth == 0 {
+                    rows += 1;
+                }
+                depth += 1;
+            }
+            ')' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    rows.max(1)
+}
+
+/// One scenario and size of a workload, and how often it ran
+#[derive(Debug, Clone, Copy)]
+pub struct MixEntry {
+    pub scenario: &'static Scenario,
+    pub size: usize,
+    pub calls: u64,
+}
+
+/// A recorded or declared workload as benchmark operations
+#[derive(Debug, Clone, Default)]
+pub struct WorkloadMix {
+    /// Recorded workloads order them by calls, most frequent first
+    pub entries: Vec<MixEntry>,
+    /// Statements that match no operation, with their calls, most frequent first
+    pub unmatched: Vec<(String, u64)>,
+    /// Calls of transaction control and session statements
+    pub ignored: u64,
+}
+
+impl WorkloadMix {
+    /// Just `scenario`, at its typical size
+    pub fn single(scenario: &'static Scenario) -> Self {
+        Self {
+            entries: vec![MixEntry { scenario, size: scenario.typical_size(), calls: 1 }],
+            ..Self::default()
+        }
+    }
+
+    pub fn from_recorded(recorded: &[Recorded]) -> Self {
+        let mut operations: BTreeMap<(&'static str, usize), u64> = BTreeMap::new();
+        let mut unmatched: BTreeMap<String, u64> = BTreeMap::new();
+        let mut ignored = 0;
+        // Per session, the statements of the open transaction
+        let mut transactions: HashMap<Option<u32>, Vec<(Classified, &Recorded)>> = HashMap::new();
+
+        let mut count = |classified: Classified, statement: &Recorded| match classified {
+            Classified::Operation(operation, size) => {
+                let scenario = scenario_for(operation);
+                let size = size.unwrap_or_else(|| scenario.typical_size());
+                *operations.entry((scenario.name, size)).or_default() += statement.calls;
+            }
+            Classified::InsertPosts(_) | Classified::Unmatched => {
+                let mut text = statement.sql.split_whitespace().collect::<Vec<_>>().join(" ");
+                if let Some((end, _)) = text.char_indices().nth(UNMATCHED_TEXT_LEN) {
+                    text.truncate(end);
+                }
+                *unmatched.entry(text).or_default() += statement.calls;
+            }
+            Classified::Begin | Classified::End | Classified::Ignored => unreachable!(),
+        };
+
+        for statement in recorded {
+            let classified = classify(&statement.sql, &statement.params);
+            match classified {
+                Classified::Begin if statement.session.is_some() => {
+                    ignored += statement.calls;
+                    transactions.insert(statement.session, Vec::new());
+                }
+                Classified::End if statement.session.is_some() => {
+                    ignored += statement.calls;
+                    let transaction = transactions.remove(&statement.session).unwrap_or_default();
+                    match user_with_posts(&transaction) {
+                        Some(posts) => count(Classified::Operation(Operation::InsertUserWithPosts, Some(posts)), statement),
+                        None => transaction.into_iter().for_each(|(c, s)| count(c, s)),
+                    }
+                }
+                Classified::Begin | Classified::End | Classified::Ignored => ignored += statement.calls,
+                _ => match transactions.get_mut(&statement.session) {
+                    Some(transaction) => transaction.push((classified, statement)),
+                    None => count(classified, statement),
+                },
+            }
+        }
+        // Transactions still open when the log ends
+        for (classified, statement) in transactions.into_values().flatten() {
+            count(classified, statement);
+        }
+
+        let mut entries: Vec<MixEntry> = operations
+            .into_iter()
+            .map(|((name, size), calls)| MixEntry {
+                scenario: SCENARIOS.iter().find(|s| s.name == name).unwrap(),
+                size,
+                calls,
+            })
+            .collect();
+        entries.sort_by_key(|e| std::cmp::Reverse(e.calls));
+        let mut unmatched: Vec<(String, u64)> = unmatched.into_iter().collect();
+        unmatched.sort_by_key(|(_, calls)| std::cmp::Reverse(*calls));
+        Self { entries, unmatched, ignored }
+    }
+
+    /// Calls mapped onto operations
+    pub fn matched_calls(&self) -> u64 {
+        self.entries.iter().map(|e| e.calls).sum()
+    }
+
+    pub fn unmatched_calls(&self) -> u64 {
+        self.unmatched.iter().map(|(_, calls)| calls).sum()
+    }
+
+    /// `operations` indexes into [`WorkloadMix::entries`] in proportion to their
+    /// calls, interleaved evenly (smooth weighted round-robin), so every backend
+    /// replays the same deterministic sequence
+    pub fn sequence(&self, operations: usize) -> Vec<usize> {
+        let total = self.matched_calls() as i128;
+        let mut current = vec![0i128; self.entries.len()];
+        (0..operations)
+            .map(|_| {
+                for (weight, entry) in current.iter_mut().zip(&self.entries) {
+                    *weight += entry.calls as i128;
+                }
+                let next = (0..current.len()).max_by_key(|&i| (current[i], std::cmp::Reverse(i))).unwrap();
+                current[next] -= total;
+                next
+            })
+            .collect()
+    }
+}
+
+fn scenario_for(operation: Operation) -> &'static Scenario {
+    SCENARIOS
+        .iter()
+        .find(|s| s.operation == operation)
+        .expect("every operation has a scenario")
+}
+
+/// Posts inserted if a transaction is exactly one user insert followed by post inserts
+fn user_with_posts(transaction: &[(Classified, &Recorded)]) -> Option<usize> {
+    let (first, posts) = transaction.split_first()?;
+    if first.0 != Classified::Operation(Operation::InsertUser, None) || posts.is_empty() {
+        return None;
+    }
+    posts
+        .iter()
+        .map(|(classified, statement)| match classified {
+            Classified::InsertPosts(rows) => Some(rows * statement.calls as usize),
+            _ => None,
+        })
+        .sum()
+}
+
+/// How one mix entry did in a replay
+#[derive(Debug, Clone)]
+pub struct EntryReport {
+    pub scenario: &'static str,
+    pub size: usize,
+    /// Successful operations
+    pub operations: u64,
+    pub errors: u64,
+    /// Time spent in the successful operations
+    pub time: Duration,
+}
+
+impl EntryReport {
+    pub fn mean(&self) -> Duration {
+        self.time.checked_div(self.operations as u32).unwrap_or_default()
+    }
+}
+
+/// One backend's replay of a workload
+#[derive(Debug, Clone)]
+pub struct ReplayReport {
+    pub backend: &'static str,
+    pub elapsed: Duration,
+    /// Parallel to [`WorkloadMix::entries`]
+    pub entries: Vec<EntryReport>,
+    /// The first failed operation's error, if any
+    pub first_error: Option<String>,
+}
+
+impl ReplayReport {
+    pub fn operations(&self) -> u64 {
+        self.entries.iter().map(|e| e.operations).sum()
+    }
+
+    pub fn errors(&self) -> u64 {
+        self.entries.iter().map(|e| e.errors).sum()
+    }
+
+    pub fn throughput(&self) -> f64 {
+        self.operations() as f64 / self.elapsed.as_secs_f64()
+    }
+}
+
+/// Replay `operations` operations of `mix` on one connection of `B`, in the
+/// order [`WorkloadMix::sequence`] gives.
+///
+/// Failed operations are counted and the replay goes on; connecting, setup and
+/// teardown errors are returned. Rows the writing operations insert are
+/// removed afterwards.
+pub async fn replay<B: DatabaseBenchmark>(
+    mix: &WorkloadMix,
+    operations: usize,
+    backend: &'static str,
+) -> Result<ReplayReport, B::Error> {
+    let conn = B::connect().await?;
+    let mut fixtures: Vec<Fixture> = Vec::with_capacity(mix.entries.len());
+    // Every teardown first, as one entry's would remove the rows another's setup inserted
+    for entry in &mix.entries {
+        entry.scenario.teardown::<B>(&conn).await?;
+    }
+    for entry in &mix.entries {
+        fixtures.push(entry.scenario.setup::<B>(&conn).await?);
+    }
+
+    let mut entries: Vec<EntryReport> = mix
+        .entries
+        .iter()
+        .map(|e| EntryReport {
+            scenario: e.scenario.name,
+            size: e.size,
+            operations: 0,
+            errors: 0,
+            time: Duration::ZERO,
+        })
+        .collect();
+    let mut first_error = None;
+    let mut next_user = 1;
+
+    let start = Instant::now();
+    for (step, index) in mix.sequence(operations).into_iter().enumerate() {
+        let MixEntry { scenario, size, .. } = mix.entries[index];
+        // Writing operations of different entries must not generate the same users
+        let iteration = if scenario.writes() {
+            let (iteration, next) = scenario.iteration_inserting_from(size, next_user);
+            next_user = next;
+            iteration
+        } else {
+            step + 1
+        };
+
+        let began = Instant::now();
+        let result = scenario.run::<B>(&conn, &fixtures[index], size, iteration).await;
+        let report = &mut entries[index];
+        match result {
+            Ok(()) => {
+                report.operations += 1;
+                report.time += began.elapsed();
+            }
+            Err(e) => {
+                report.errors += 1;
+                first_error.get_or_insert_with(|| format!("{} on {}: {:?}", scenario.name, backend, e));
+            }
+        }
+    }
+    let elapsed = start.elapsed();
+
+    for entry in &mix.entries {
+        entry.scenario.teardown::<B>(&conn).await?;
+    }
+    Ok(ReplayReport {
+        backend,
+        elapsed,
+        entries,
+        first_error,
+    })
+}
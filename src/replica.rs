@@ -0,0 +1,140 @@
+//! Read-replica routing
+//!
+//! With `REPLICA_DATABASE_URL` set, the routed workload sends reads to a
+//! streaming replica and writes to the primary, each over its own connection.
+//! The replica applies the primary's WAL asynchronously, so a read issued
+//! right after a write may not see it yet; [`check_freshness`] measures how
+//! often that happens and for how long.
+
+use crate::session::SessionConfig;
+use crate::{configure_url, DatabaseBenchmark, NewUser, WorkloadOp};
+use std::fmt;
+use std::time::{Duration, Instant};
+use tokio_postgres::Client;
+
+/// Replica connection URL from `REPLICA_DATABASE_URL`, configured like
+/// [`crate::database_url`], or `None` when no replica is configured
+pub fn replica_url() -> Option<String> {
+    let url = std::env::var("REPLICA_DATABASE_URL").ok()?;
+    Some(configure_url(&url, &SessionConfig::from_env()))
+}
+
+/// Whether the server behind `client` is a standby replaying WAL
+pub async fn in_recovery(client: &Client) -> Result<bool, tokio_postgres::Error> {
+    Ok(client.query_one("SELECT pg_is_in_recovery()", &[]).await?.get(0))
+}
+
+/// A primary and a replica connection of one backend
+pub struct Routed<C> {
+    pub primary: C,
+    pub replica: C,
+}
+
+impl<C> Routed<C> {
+    /// Connect `B` to [`crate::database_url`] and to `replica_url`
+    pub async fn connect<B>(replica_url: &str) -> Result<Self, B::Error>
+    where
+        B: DatabaseBenchmark<Connection = C>,
+    {
+        Ok(Self {
+            primary: B::connect().await?,
+            replica: B::connect_to(replica_url).await?,
+        })
+    }
+
+    /// The connection `op` runs on: writes on the primary, reads on the replica
+    pub fn route(&self, op: WorkloadOp) -> &C {
+        match op {
+            WorkloadOp::Read => &self.replica,
+            WorkloadOp::Write => &self.primary,
+        }
+    }
+}
+
+/// How far behind the primary the replica's reads were
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FreshnessReport {
+    /// Users written on the primary
+    pub writes: usize,
+    /// Writes the first replica read after them didn't see
+    pub stale_writes: usize,
+    /// Replica reads that missed a write, including retries
+    pub stale_reads: usize,
+    /// Writes still invisible on the replica when the check gave up
+    pub unreplicated: usize,
+    /// Longest time from a write committing to it showing up on the replica
+    pub max_lag: Duration,
+    /// Sum of those times over every replicated write, counting the ones the first read saw as zero
+    pub total_lag: Duration,
+}
+
+impl FreshnessReport {
+    /// Mean time from a write committing to it showing up on the replica
+    pub fn mean_lag(&self) -> Duration {
+        let replicated = (self.writes - self.unreplicated) as u32;
+        if replicated == 0 {
+            return Duration::ZERO;
+        }
+        self.total_lag / replicated
+    }
+}
+
+impl fmt::Display for FreshnessReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{} writes stale on first read ({} stale reads), lag max {:?} mean {:?}",
+            self.stale_writes,
+            self.writes,
+            self.stale_reads,
+            self.max_lag,
+            self.mean_lag()
+        )?;
+        if self.unreplicated > 0 {
+            write!(f, ", {} never replicated", self.unreplicated)?;
+        }
+        Ok(())
+    }
+}
+
+/// Write `writes` users on the primary (`bench_user_{first_index}` onwards) and
+/// poll the replica after each until it returns the user or `timeout` passes.
+///
+/// The users are left in place for the caller's cleanup.
+pub async fn check_freshness<B: DatabaseBenchmark>(
+    routed: &Routed<B::Connection>,
+    writes: usize,
+    first_index: usize,
+    timeout: Duration,
+) -> Result<FreshnessReport, B::Error> {
+    let mut report = FreshnessReport { writes, ..Default::default() };
+    for index in first_index..first_index + writes {
+        let id = B::insert_user(&routed.primary, &NewUser::generate(index)).await?;
+        let committed = Instant::now();
+
+        let mut missed = 0;
+        let replicated = loop {
+            if B::select_user_by_id(&routed.replica, id).await?.is_some() {
+                break true;
+            }
+            missed += 1;
+            if committed.elapsed() > timeout {
+                break false;
+            }
+        };
+        let lag = committed.elapsed();
+
+        report.stale_reads += missed;
+        if missed == 0 {
+            continue;
+        }
+        report.stale_writes += 1;
+        if replicated {
+            report.max_lag = report.max_lag.max(lag);
+            report.total_lag += lag;
+        } else {
+            report.unreplicated += 1;
+        }
+    }
+    Ok(report)
+}
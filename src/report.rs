@@ -0,0 +1,247 @@
+//! Comparison of two `bench-runner` JSON exports behind
+//! `cargo run -- report diff old.json new.json`
+//!
+//! Results are matched by scenario, backend, mode, size and concurrency, and
+//! each pair is reported as the percentage change of throughput, mean, p50
+//! and p99 latency. Whether the change is real or noise is decided like
+//! criterion does: the mean latencies must differ by more than
+//! [`NOISE_THRESHOLD`] and a Welch's t-test over the operations of both runs
+//! must reject "no difference" at the 5% level. Exports written before the
+//! mean and standard deviation were recorded can only be compared by
+//! percentage, and are flagged as unknown. A pair where either run was
+//! marked noisy (see [`crate::noise`]) is flagged too, whatever the verdict.
+
+use crate::runner::Mode;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use std::{fs, io};
+
+/// Relative change of the mean latency below which a difference is noise,
+/// criterion's default
+pub const NOISE_THRESHOLD: f64 = 0.02;
+
+/// |t| above which a difference is significant at the 5% level; the runs
+/// are thousands of operations, so the normal approximation holds
+pub const CRITICAL_T: f64 = 1.96;
+
+/// One row of a `bench-runner` JSON export
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExportedResult {
+    pub scenario: String,
+    pub backend: String,
+    pub mode: Mode,
+    pub size: usize,
+    pub concurrency: usize,
+    pub operations: u64,
+    pub errors: u64,
+    pub throughput: f64,
+    /// Missing from exports older than the diff
+    pub mean_us: Option<f64>,
+    pub stddev_us: Option<f64>,
+    pub p50_us: f64,
+    pub p99_us: f64,
+    /// Missing from exports older than the noise score
+    pub cv: Option<f64>,
+    #[serde(default)]
+    pub noisy: bool,
+}
+
+/// A JSON export, with its run metadata or, from before it was recorded, a
+/// bare array of results
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Export {
+    WithMetadata { results: Vec<ExportedResult> },
+    Results(Vec<ExportedResult>),
+}
+
+/// Read the results `bench-runner` exported to `path`
+pub fn load(path: &Path) -> io::Result<Vec<ExportedResult>> {
+    Ok(match serde_json::from_str(&fs::read_to_string(path)?)? {
+        Export::WithMetadata { results } | Export::Results(results) => results,
+    })
+}
+
+/// What identifies a result across exports
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Benchmark {
+    pub scenario: String,
+    pub backend: String,
+    pub size: usize,
+    pub concurrency: usize,
+    pub mode: Mode,
+}
+
+impl Benchmark {
+    fn of(result: &ExportedResult) -> Self {
+        Self {
+            scenario: result.scenario.clone(),
+            backend: result.backend.clone(),
+            size: result.size,
+            concurrency: result.concurrency,
+            mode: result.mode,
+        }
+    }
+}
+
+impl fmt::Display for Benchmark {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = format!(
+            "{}/{} (size {}, x{}, {})",
+            self.scenario, self.backend, self.size, self.concurrency, self.mode
+        );
+        f.pad(&name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Significance {
+    /// Significantly faster
+    Improved,
+    /// Significantly slower
+    Regressed,
+    /// Within noise, or not significant
+    NoChange,
+    /// An export lacks the mean and standard deviation
+    Unknown,
+}
+
+impl fmt::Display for Significance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Significance::Improved => "improved",
+            Significance::Regressed => "REGRESSED",
+            Significance::NoChange => "no change",
+            Significance::Unknown => "unknown",
+        })
+    }
+}
+
+/// The same benchmark in both exports; changes are in percent, `None` when
+/// the old value is zero
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub benchmark: Benchmark,
+    pub throughput: Option<f64>,
+    pub mean: Option<f64>,
+    pub p50: Option<f64>,
+    pub p99: Option<f64>,
+    pub significance: Significance,
+    /// Either run was marked noisy
+    pub noisy: bool,
+}
+
+impl Comparison {
+    fn of(benchmark: Benchmark, old: &ExportedResult, new: &ExportedResult) -> Self {
+        let mean = match (old.mean_us, new.mean_us) {
+            (Some(old), Some(new)) => change(old, new),
+            _ => None,
+        };
+        Self {
+            benchmark,
+            throughput: change(old.throughput, new.throughput),
+            mean,
+            p50: change(old.p50_us, new.p50_us),
+            p99: change(old.p99_us, new.p99_us),
+            significance: significance(old, new),
+            noisy: old.noisy || new.noisy,
+        }
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:<56} {:>9} {:>9} {:>9} {:>9}  {}",
+            self.benchmark,
+            Percent(self.throughput),
+            Percent(self.mean),
+            Percent(self.p50),
+            Percent(self.p99),
+            self.significance
+        )?;
+        if self.noisy {
+            f.write_str(" (noisy)")?;
+        }
+        Ok(())
+    }
+}
+
+/// A signed percentage, or `n/a`
+struct Percent(Option<f64>);
+
+impl fmt::Display for Percent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(percent) => f.pad(&format!("{percent:+.1}%")),
+            None => f.pad("n/a"),
+        }
+    }
+}
+
+/// Two exports compared benchmark by benchmark
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Diff {
+    pub compared: Vec<Comparison>,
+    /// Benchmarks only the old export has
+    pub removed: Vec<Benchmark>,
+    /// Benchmarks only the new export has
+    pub added: Vec<Benchmark>,
+}
+
+impl Diff {
+    pub fn count(&self, significance: Significance) -> usize {
+        self.compared.iter().filter(|c| c.significance == significance).count()
+    }
+
+    /// Comparisons with a noisy run on either side
+    pub fn noisy(&self) -> usize {
+        self.compared.iter().filter(|c| c.noisy).count()
+    }
+}
+
+/// Compare `new` against `old`, in benchmark order
+pub fn diff(old: &[ExportedResult], new: &[ExportedResult]) -> Diff {
+    let old: BTreeMap<Benchmark, &ExportedResult> = old.iter().map(|r| (Benchmark::of(r), r)).collect();
+    let mut new: BTreeMap<Benchmark, &ExportedResult> = new.iter().map(|r| (Benchmark::of(r), r)).collect();
+
+    let mut diff = Diff::default();
+    for (benchmark, old) in old {
+        match new.remove(&benchmark) {
+            Some(new) => diff.compared.push(Comparison::of(benchmark, old, new)),
+            None => diff.removed.push(benchmark),
+        }
+    }
+    diff.added = new.into_keys().collect();
+    diff
+}
+
+fn change(old: f64, new: f64) -> Option<f64> {
+    (old > 0.0).then(|| (new - old) / old * 100.0)
+}
+
+fn significance(old: &ExportedResult, new: &ExportedResult) -> Significance {
+    let (Some(old_mean), Some(old_sd), Some(new_mean), Some(new_sd)) =
+        (old.mean_us, old.stddev_us, new.mean_us, new.stddev_us)
+    else {
+        return Significance::Unknown;
+    };
+    if old.operations < 2 || new.operations < 2 || old_mean <= 0.0 {
+        return Significance::Unknown;
+    }
+    if ((new_mean - old_mean) / old_mean).abs() <= NOISE_THRESHOLD {
+        return Significance::NoChange;
+    }
+
+    let standard_error = (old_sd.powi(2) / old.operations as f64 + new_sd.powi(2) / new.operations as f64).sqrt();
+    // Means this far apart with no spread at all differ for certain
+    let significant = standard_error == 0.0 || ((new_mean - old_mean) / standard_error).abs() > CRITICAL_T;
+    match (significant, new_mean > old_mean) {
+        (false, _) => Significance::NoChange,
+        (true, true) => Significance::Regressed,
+        (true, false) => Significance::Improved,
+    }
+}
@@ -0,0 +1,101 @@
+//! Text vs binary result formats
+//!
+//! Every backend reads results in PostgreSQL's binary format: tokio-postgres
+//! (and so clorinde), sqlx (and so SeaORM), and diesel, which asks libpq for
+//! binary results. Text results only come back from the simple query
+//! protocol. Nothing in the cross-library numbers varies the format, but it
+//! decides how much work decoding a row is, so the `result_format` benchmarks
+//! measure both on the wide user row with tokio-postgres, which speaks both.
+
+use crate::User;
+use chrono::{DateTime, Utc};
+use std::fmt;
+use tokio_postgres::{Client, Row, SimpleQueryMessage, SimpleQueryRow, Statement};
+use uuid::Uuid;
+
+const SELECT_USERS: &str = "SELECT id, username, email, first_name, last_name, age, created_at, updated_at \
+     FROM users ORDER BY created_at DESC LIMIT";
+
+/// A text column that doesn't parse as its Rust type
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextDecodeError {
+    pub column: &'static str,
+    pub value: String,
+}
+
+impl fmt::Display for TextDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "can't decode column {} from {:?}", self.column, self.value)
+    }
+}
+
+impl std::error::Error for TextDecodeError {}
+
+/// Prepare the `select_users_limit` statement for [`fetch_users_binary`].
+///
+/// Prepared once, an extended-protocol query takes a single round trip like a simple one.
+pub async fn prepare_select_users(client: &Client) -> Result<Statement, tokio_postgres::Error> {
+    client.prepare(&format!("{} $1", SELECT_USERS)).await
+}
+
+/// Fetch users as binary rows through the extended protocol
+pub async fn fetch_users_binary(
+    client: &Client,
+    statement: &Statement,
+    limit: i64,
+) -> Result<Vec<Row>, tokio_postgres::Error> {
+    client.query(statement, &[&limit]).await
+}
+
+/// Fetch users as text rows through the simple protocol, with the limit inlined
+pub async fn fetch_users_text(client: &Client, limit: i64) -> Result<Vec<SimpleQueryRow>, tokio_postgres::Error> {
+    let messages = client.simple_query(&format!("{} {}", SELECT_USERS, limit)).await?;
+    Ok(messages
+        .into_iter()
+        .filter_map(|message| match message {
+            SimpleQueryMessage::Row(row) => Some(row),
+            _ => None,
+        })
+        .collect())
+}
+
+/// Decode a binary row, the way `TokioPostgresBench` does
+pub fn decode_user_binary(row: &Row) -> Result<User, tokio_postgres::Error> {
+    User::try_from(row)
+}
+
+/// Decode a text row, parsing every column from its string form
+pub fn decode_user_text(row: &SimpleQueryRow) -> Result<User, TextDecodeError> {
+    fn text<'a>(row: &'a SimpleQueryRow, column: &'static str) -> Result<&'a str, TextDecodeError> {
+        row.get(column).ok_or(TextDecodeError { column, value: "NULL".to_string() })
+    }
+
+    fn parse<T>(
+        row: &SimpleQueryRow,
+        column: &'static str,
+        parse: impl FnOnce(&str) -> Option<T>,
+    ) -> Result<Option<T>, TextDecodeError> {
+        row.get(column)
+            .map(|value| parse(value).ok_or_else(|| TextDecodeError { column, value: value.to_string() }))
+            .transpose()
+    }
+
+    fn timestamp(value: &str) -> Option<DateTime<Utc>> {
+        // `timestamptz` output in the session time zone, e.g. `2024-01-01 12:00:00.123456+00`
+        DateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f%#z")
+            .ok()
+            .map(|t| t.with_timezone(&Utc))
+    }
+
+    let id = text(row, "id")?;
+    Ok(User {
+        id: Uuid::parse_str(id).map_err(|_| TextDecodeError { column: "id", value: id.to_string() })?,
+        username: text(row, "username")?.to_string(),
+        email: text(row, "email")?.to_string(),
+        first_name: text(row, "first_name")?.to_string(),
+        last_name: text(row, "last_name")?.to_string(),
+        age: parse(row, "age", |v| v.parse().ok())?,
+        created_at: parse(row, "created_at", timestamp)?,
+        updated_at: parse(row, "updated_at", timestamp)?,
+    })
+}
@@ -0,0 +1,293 @@
+//! Retrying transient errors with exponential backoff
+//!
+//! Some errors say nothing about the request itself: a serializable
+//! transaction that lost a conflict (`40001`), a deadlock victim (`40P01`), a
+//! connection the server or the network dropped (class `08`, `57P01`), a pool
+//! that timed out handing out a connection. Sent again a little later, the
+//! request usually succeeds. [`RetryPolicy`] does that with exponential
+//! backoff and full jitter, and [`Transient`] tells each backend's transient
+//! errors from the rest; [`is_transient_boxed`] does the same for the boxed
+//! errors of the pooled wrappers.
+//!
+//! The `retry` groups induce the failures. On the attempts a
+//! [`FailureSchedule`] picks, [`Transient::flaky_lookup`] first runs
+//! [`INDUCED_FAILURE`], which raises `serialization_failure` on the server,
+//! so every library reports it through its own error type as it would a real
+//! one.
+
+use crate::bench_clorinde::ClorindeBench;
+use crate::bench_diesel::DieselBench;
+use crate::bench_seaorm::SeaOrmBench;
+use crate::bench_sqlx::SqlxBench;
+use crate::bench_tokio_postgres::TokioPostgresBench;
+use crate::{BoxError, DatabaseBenchmark, User};
+use rand::Rng;
+use sea_orm::{ConnAcquireErr, ConnectionTrait, DatabaseConnection, DbErr, RuntimeErr};
+use sqlx::PgPool;
+use std::future::Future;
+use std::time::Duration;
+use tokio_postgres::Client;
+use uuid::Uuid;
+
+/// A statement the server rejects with `serialization_failure`, as it would a
+/// serializable transaction that lost a conflict
+pub const INDUCED_FAILURE: &str =
+    "DO $$ BEGIN RAISE EXCEPTION 'induced failure' USING ERRCODE = 'serialization_failure'; END $$";
+
+/// How often and how long to wait before sending a failed request again
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Attempts in all, the first included; 1 never retries
+    pub max_attempts: usize,
+    /// Longest wait before the first retry, doubled for each retry after it
+    pub base_delay: Duration,
+    /// Longest wait before any retry
+    pub max_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Every error is returned from the first attempt
+    pub const DISABLED: RetryPolicy = RetryPolicy {
+        max_attempts: 1,
+        base_delay: Duration::ZERO,
+        max_delay: Duration::ZERO,
+    };
+
+    /// Up to 5 attempts, waiting at most 1ms, 2ms, 4ms and 8ms between them
+    pub const BACKOFF: RetryPolicy = RetryPolicy {
+        max_attempts: 5,
+        base_delay: Duration::from_millis(1),
+        max_delay: Duration::from_millis(20),
+    };
+
+    /// The wait before retry number `retry` (1 for the first): uniformly
+    /// random up to `base_delay * 2^(retry - 1)`, capped at `max_delay`, so
+    /// clients that failed together don't retry together
+    pub fn delay(&self, retry: usize, rng: &mut impl Rng) -> Duration {
+        let doublings = retry.saturating_sub(1).min(31) as u32;
+        let ceiling = self.base_delay.saturating_mul(1 << doublings).min(self.max_delay);
+        ceiling.mul_f64(rng.gen::<f64>())
+    }
+
+    /// Run `attempt` (given the attempt number, from 1) until it succeeds,
+    /// fails with an error `transient` rejects, or [`max_attempts`] have run,
+    /// waiting [`delay`] between attempts. Returns the last result and how
+    /// many attempts ran.
+    ///
+    /// [`max_attempts`]: Self::max_attempts
+    /// [`delay`]: Self::delay
+    pub async fn run<T, E, Fut>(
+        &self,
+        transient: impl Fn(&E) -> bool,
+        mut attempt: impl FnMut(usize) -> Fut,
+    ) -> (Result<T, E>, usize)
+    where
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let mut attempts = 1;
+        loop {
+            match attempt(attempts).await {
+                Err(e) if attempts < self.max_attempts && transient(&e) => {
+                    let delay = self.delay(attempts, &mut rand::thread_rng());
+                    tokio::time::sleep(delay).await;
+                    attempts += 1;
+                }
+                result => return (result, attempts),
+            }
+        }
+    }
+}
+
+/// The attempts that fail first, the same ones for every backend
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FailureSchedule {
+    /// Fraction of attempts that fail, from 0.0 to 1.0
+    pub rate: f64,
+}
+
+impl FailureSchedule {
+    /// Whether attempt `attempt` of request `request` fails, picked by a hash of
+    /// the two so that a retry is as likely to fail as the first attempt
+    pub fn fails(&self, request: usize, attempt: usize) -> bool {
+        // splitmix64's finalizer spreads neighbouring inputs over the range
+        let mut x = (request as u64) << 8 ^ attempt as u64;
+        x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        x ^= x >> 31;
+        ((x >> 11) as f64 / (1u64 << 53) as f64) < self.rate
+    }
+}
+
+/// Whether a SQLSTATE reports a failure worth retrying: a serialization
+/// failure or deadlock, a lost connection, the server shutting down or out of
+/// connection slots
+pub fn transient_sqlstate(code: &str) -> bool {
+    matches!(code, "40001" | "40P01" | "57P01" | "57P02" | "57P03" | "53300") || code.starts_with("08")
+}
+
+/// Telling transient errors from the rest, and causing one
+#[allow(async_fn_in_trait)]
+pub trait Transient: DatabaseBenchmark {
+    /// Whether `error` is worth retrying
+    fn is_transient(error: &Self::Error) -> bool;
+
+    /// Run [`INDUCED_FAILURE`], which always fails
+    async fn induce_failure(conn: &Self::Connection) -> Result<(), Self::Error>;
+
+    /// `select_user_by_id`, failing first on the attempts `schedule` picks
+    async fn flaky_lookup(
+        conn: &Self::Connection,
+        id: Uuid,
+        schedule: FailureSchedule,
+        request: usize,
+        attempt: usize,
+    ) -> Result<Option<User>, Self::Error> {
+        if schedule.fails(request, attempt) {
+            Self::induce_failure(conn).await?;
+        }
+        Self::select_user_by_id(conn, id).await
+    }
+}
+
+/// [`Transient::is_transient`] for an error boxed by a pooled wrapper or by
+/// diesel's backend: any driver's error, or a pool's timeout or lost connection
+pub fn is_transient_boxed(error: &BoxError) -> bool {
+    let error: &(dyn std::error::Error + 'static) = error.as_ref();
+    if let Some(e) = error.downcast_ref::<tokio_postgres::Error>() {
+        return tokio_postgres_transient(e);
+    }
+    if let Some(e) = error.downcast_ref::<sqlx::Error>() {
+        return sqlx_transient(e);
+    }
+    if let Some(e) = error.downcast_ref::<DbErr>() {
+        return SeaOrmBench::is_transient(e);
+    }
+    if let Some(e) = error.downcast_ref::<diesel::result::Error>() {
+        return diesel_transient(e);
+    }
+    if let Some(e) = error.downcast_ref::<deadpool_postgres::PoolError>() {
+        return match e {
+            deadpool_postgres::PoolError::Timeout(_) => true,
+            deadpool_postgres::PoolError::Backend(e) => tokio_postgres_transient(e),
+            _ => false,
+        };
+    }
+    if let Some(e) = error.downcast_ref::<bb8::RunError<tokio_postgres::Error>>() {
+        return match e {
+            bb8::RunError::TimedOut => true,
+            bb8::RunError::User(e) => tokio_postgres_transient(e),
+        };
+    }
+    if let Some(e) = error.downcast_ref::<mobc::Error<tokio_postgres::Error>>() {
+        return match e {
+            mobc::Error::Timeout | mobc::Error::BadConn => true,
+            mobc::Error::Inner(e) => tokio_postgres_transient(e),
+            mobc::Error::PoolClosed => false,
+        };
+    }
+    // r2d2 only fails a checkout when it timed out waiting for a connection
+    error.is::<diesel::r2d2::PoolError>()
+}
+
+// ============================================================================
+// tokio-postgres and clorinde
+// ============================================================================
+
+fn tokio_postgres_transient(error: &tokio_postgres::Error) -> bool {
+    error.is_closed() || error.code().is_some_and(|code| transient_sqlstate(code.code()))
+}
+
+impl Transient for TokioPostgresBench {
+    fn is_transient(error: &tokio_postgres::Error) -> bool {
+        tokio_postgres_transient(error)
+    }
+
+    async fn induce_failure(client: &Client) -> Result<(), tokio_postgres::Error> {
+        client.batch_execute(INDUCED_FAILURE).await
+    }
+}
+
+impl Transient for ClorindeBench {
+    /// Generated queries return tokio-postgres' error as it is
+    fn is_transient(error: &tokio_postgres::Error) -> bool {
+        tokio_postgres_transient(error)
+    }
+
+    async fn induce_failure(client: &Client) -> Result<(), tokio_postgres::Error> {
+        client.batch_execute(INDUCED_FAILURE).await
+    }
+}
+
+// ============================================================================
+// sqlx
+// ============================================================================
+
+fn sqlx_transient(error: &sqlx::Error) -> bool {
+    match error {
+        sqlx::Error::Database(db) => db.code().is_some_and(|code| transient_sqlstate(&code)),
+        sqlx::Error::Io(_) | sqlx::Error::PoolTimedOut => true,
+        _ => false,
+    }
+}
+
+impl Transient for SqlxBench {
+    fn is_transient(error: &sqlx::Error) -> bool {
+        sqlx_transient(error)
+    }
+
+    async fn induce_failure(pool: &PgPool) -> Result<(), sqlx::Error> {
+        sqlx::raw_sql(INDUCED_FAILURE).execute(pool).await.map(drop)
+    }
+}
+
+// ============================================================================
+// SeaORM
+// ============================================================================
+
+impl Transient for SeaOrmBench {
+    /// A pool timeout has a variant of its own; server and connection errors
+    /// are the sqlx errors SeaORM wraps
+    fn is_transient(error: &DbErr) -> bool {
+        match error {
+            DbErr::ConnectionAcquire(ConnAcquireErr::Timeout | ConnAcquireErr::ConnectionClosed) => true,
+            DbErr::Conn(RuntimeErr::SqlxError(e))
+            | DbErr::Exec(RuntimeErr::SqlxError(e))
+            | DbErr::Query(RuntimeErr::SqlxError(e)) => sqlx_transient(e),
+            _ => false,
+        }
+    }
+
+    async fn induce_failure(db: &DatabaseConnection) -> Result<(), DbErr> {
+        db.execute_unprepared(INDUCED_FAILURE).await.map(drop)
+    }
+}
+
+// ============================================================================
+// Diesel
+// ============================================================================
+
+/// libpq's SQLSTATE only reaches diesel as an error kind
+fn diesel_transient(error: &diesel::result::Error) -> bool {
+    use diesel::result::{DatabaseErrorKind, Error};
+
+    matches!(
+        error,
+        Error::DatabaseError(
+            DatabaseErrorKind::SerializationFailure | DatabaseErrorKind::ClosedConnection | DatabaseErrorKind::UnableToSendCommand,
+            _
+        )
+    )
+}
+
+impl Transient for DieselBench {
+    /// The boxed error may be diesel's or r2d2's
+    fn is_transient(error: &Self::Error) -> bool {
+        is_transient_boxed(error)
+    }
+
+    async fn induce_failure(conn: &Self::Connection) -> Result<(), Self::Error> {
+        use diesel::connection::SimpleConnection;
+
+        Ok(conn.lock().unwrap().batch_execute(INDUCED_FAILURE)?)
+    }
+}
@@ -0,0 +1,197 @@
+//! Row decoding on its own, per backend
+//!
+//! Client-side, what sets the backends apart is mostly turning a result row
+//! into a `User`. Each backend's rows are fetched once with the
+//! `select_users_limit` query and the `row_decode` benchmarks then time only
+//! the mapping, as the backend does it after every fetch: `User::try_from`
+//! on a tokio-postgres `Row` and on an sqlx `PgRow`, SeaORM's
+//! `FromQueryResult` into its model and then `From` into a `User`. diesel
+//! decodes while it loads, through libpq, and clorinde's generated code
+//! inside each query's `all` or `iter`, so neither has rows to decode later.
+//!
+//! The `_by_index` variants decode the same tokio-postgres and sqlx rows with
+//! [`ModelRow::user_by_index`], reading the columns by position, where
+//! `try_from` looks every column up by name in every row.
+//!
+//! The `_borrowed` and `_packed` variants decode the same rows into other
+//! shapes than `User`, whose four `String`s cost four allocations per row:
+//! [`UserRef`] borrows its text from the row's buffer and allocates nothing,
+//! and [`PackedUser`] copies the text into one shared `Arc<str>` per row.
+
+use crate::bench_seaorm::users;
+use crate::mapping::ModelRow;
+use crate::User;
+use chrono::{DateTime, Utc};
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, DbErr, FromQueryResult, QueryResult, Statement};
+use sqlx::postgres::{PgPool, PgRow};
+use std::sync::Arc;
+use tokio_postgres::{Client, Row};
+use uuid::Uuid;
+
+const SELECT_USERS: &str = "SELECT id, username, email, first_name, last_name, age, created_at, updated_at \
+     FROM users ORDER BY created_at DESC LIMIT $1";
+
+pub async fn fetch_tokio_postgres(client: &Client, limit: i64) -> Result<Vec<Row>, tokio_postgres::Error> {
+    client.query(SELECT_USERS, &[&limit]).await
+}
+
+pub fn decode_tokio_postgres(row: &Row) -> Result<User, tokio_postgres::Error> {
+    User::try_from(row)
+}
+
+pub fn decode_tokio_postgres_by_index(row: &Row) -> Result<User, tokio_postgres::Error> {
+    row.user_by_index()
+}
+
+pub async fn fetch_sqlx(pool: &PgPool, limit: i64) -> Result<Vec<PgRow>, sqlx::Error> {
+    sqlx::query(SELECT_USERS).bind(limit).fetch_all(pool).await
+}
+
+pub fn decode_sqlx(row: &PgRow) -> Result<User, sqlx::Error> {
+    User::try_from(row)
+}
+
+pub fn decode_sqlx_by_index(row: &PgRow) -> Result<User, sqlx::Error> {
+    row.user_by_index()
+}
+
+pub async fn fetch_sea_orm(db: &DatabaseConnection, limit: i64) -> Result<Vec<QueryResult>, DbErr> {
+    db.query_all(Statement::from_sql_and_values(DatabaseBackend::Postgres, SELECT_USERS, [limit.into()]))
+        .await
+}
+
+pub fn decode_sea_orm(row: &QueryResult) -> Result<User, DbErr> {
+    users::Model::from_query_result(row, "").map(User::from)
+}
+
+/// A user whose text columns borrow from the row they were decoded from
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserRef<'r> {
+    pub id: Uuid,
+    pub username: &'r str,
+    pub email: &'r str,
+    pub first_name: &'r str,
+    pub last_name: &'r str,
+    pub age: Option<i32>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl From<&UserRef<'_>> for User {
+    fn from(user: &UserRef<'_>) -> Self {
+        User {
+            id: user.id,
+            username: user.username.to_string(),
+            email: user.email.to_string(),
+            first_name: user.first_name.to_string(),
+            last_name: user.last_name.to_string(),
+            age: user.age,
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+        }
+    }
+}
+
+/// A user whose text columns share one allocation, cheap to clone and
+/// independent of the row
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackedUser {
+    pub id: Uuid,
+    pub age: Option<i32>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+    text: Arc<str>,
+    /// Where the username, email, first and last name end in `text`
+    ends: [u32; 4],
+}
+
+impl PackedUser {
+    fn pack(user: &UserRef<'_>) -> Self {
+        let fields = [user.username, user.email, user.first_name, user.last_name];
+        let mut text = String::with_capacity(fields.iter().map(|f| f.len()).sum());
+        let mut ends = [0; 4];
+        for (end, field) in ends.iter_mut().zip(fields) {
+            text.push_str(field);
+            *end = text.len() as u32;
+        }
+        Self {
+            id: user.id,
+            age: user.age,
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+            text: text.into(),
+            ends,
+        }
+    }
+
+    fn field(&self, index: usize) -> &str {
+        let start = if index == 0 { 0 } else { self.ends[index - 1] };
+        &self.text[start as usize..self.ends[index] as usize]
+    }
+
+    pub fn username(&self) -> &str {
+        self.field(0)
+    }
+
+    pub fn email(&self) -> &str {
+        self.field(1)
+    }
+
+    pub fn first_name(&self) -> &str {
+        self.field(2)
+    }
+
+    pub fn last_name(&self) -> &str {
+        self.field(3)
+    }
+}
+
+impl From<&PackedUser> for User {
+    fn from(user: &PackedUser) -> Self {
+        User {
+            id: user.id,
+            username: user.username().to_string(),
+            email: user.email().to_string(),
+            first_name: user.first_name().to_string(),
+            last_name: user.last_name().to_string(),
+            age: user.age,
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+        }
+    }
+}
+
+/// Decode a [`UserRef`] by column name from a row type whose `try_get` can
+/// borrow a `&str` from the row
+macro_rules! user_ref {
+    ($row:expr) => {
+        UserRef {
+            id: $row.try_get("id")?,
+            username: $row.try_get("username")?,
+            email: $row.try_get("email")?,
+            first_name: $row.try_get("first_name")?,
+            last_name: $row.try_get("last_name")?,
+            age: $row.try_get("age")?,
+            created_at: $row.try_get("created_at")?,
+            updated_at: $row.try_get("updated_at")?,
+        }
+    };
+}
+
+pub fn decode_tokio_postgres_borrowed(row: &Row) -> Result<UserRef<'_>, tokio_postgres::Error> {
+    Ok(user_ref!(row))
+}
+
+pub fn decode_tokio_postgres_packed(row: &Row) -> Result<PackedUser, tokio_postgres::Error> {
+    decode_tokio_postgres_borrowed(row).map(|user| PackedUser::pack(&user))
+}
+
+pub fn decode_sqlx_borrowed(row: &PgRow) -> Result<UserRef<'_>, sqlx::Error> {
+    use sqlx::Row;
+
+    Ok(user_ref!(row))
+}
+
+pub fn decode_sqlx_packed(row: &PgRow) -> Result<PackedUser, sqlx::Error> {
+    decode_sqlx_borrowed(row).map(|user| PackedUser::pack(&user))
+}
@@ -0,0 +1,170 @@
+//! The end-to-end run behind `cargo run --release -- run-all`
+//!
+//! For someone who just wants numbers: one command creates the schema if the
+//! database is empty, tops the seeded dataset up, runs every scenario on
+//! every backend with the load-test runner (each run warmed up before it is
+//! measured) and writes the results to a timestamped directory as JSON, CSV
+//! and a Markdown summary. The defaults keep the whole run to a few minutes;
+//! a `bench-runner` configuration file given as an argument overrides them.
+
+use crate::capability;
+use crate::runner::{self, RunResult, RunnerConfig};
+use crate::scenario::Scenario;
+use crate::score::{self, Measurement};
+use chrono::{DateTime, Local};
+use std::fmt::Write;
+use std::path::PathBuf;
+use tokio_postgres::Client;
+
+/// `init.sql` up to its sample data, which needs psql and is replaced by
+/// [`crate::seeder::top_up`]
+const SCHEMA_END: &str = "-- Sample Data Generation";
+
+/// Seconds measured per scenario and backend unless configured
+pub const DEFAULT_DURATION_SECS: f64 = 5.0;
+
+/// Seconds each run is warmed up for unless configured
+pub const DEFAULT_WARMUP_SECS: f64 = 1.0;
+
+/// The runner settings `run-all` uses without a configuration file
+pub fn default_config() -> RunnerConfig {
+    RunnerConfig {
+        duration_secs: DEFAULT_DURATION_SECS,
+        warmup_secs: DEFAULT_WARMUP_SECS,
+        ..RunnerConfig::default()
+    }
+}
+
+/// The extensions, tables and indexes of `init.sql`, without its sample data
+pub fn schema_sql() -> &'static str {
+    let init = include_str!("../init.sql");
+    let title = init.find(SCHEMA_END).expect("init.sql has a sample data section");
+    // Up to the banner line above the section title
+    let end = init[..title].rfind("-- ===").unwrap_or(title);
+    init[..end].trim_end()
+}
+
+/// Create the schema unless the database already has it; whether it did
+pub async fn create_schema(client: &Client) -> Result<bool, tokio_postgres::Error> {
+    let exists: bool = client
+        .query_one("SELECT to_regclass('public.users') IS NOT NULL", &[])
+        .await?
+        .get(0);
+    if exists {
+        return Ok(false);
+    }
+    client.batch_execute(schema_sql()).await?;
+    Ok(true)
+}
+
+/// Rough length of running `scenarios` on `backends`, warm-up included
+pub fn estimated_secs(config: &RunnerConfig, scenarios: &[&Scenario], backends: &[&str]) -> f64 {
+    let runs = scenarios
+        .iter()
+        .map(|s| backends.iter().filter(|b| s.runs_on(b)).count())
+        .sum::<usize>();
+    runs as f64 * (config.warmup_secs + config.duration_secs)
+}
+
+/// Directory the results of a run started at `started` are written to, as
+/// `run_benchmarks.sh` names it
+pub fn results_dir(started: DateTime<Local>) -> PathBuf {
+    PathBuf::from(format!("benchmark-results-{}", started.format("%Y%m%d-%H%M%S")))
+}
+
+/// The Markdown summary: the settings and dataset, a table per scenario
+/// with the backends from highest to lowest throughput, the overall ranking
+/// (see [`crate::score`]), then what each of the backends supports
+pub fn summary(config: &RunnerConfig, environment: &[(&str, String)], results: &[RunResult]) -> String {
+    let mut out = String::from("# PostgreSQL Library Benchmark Results\n\n");
+    for (name, value) in environment {
+        let _ = writeln!(out, "- {name}: {value}");
+    }
+    let _ = writeln!(
+        out,
+        "- Runs: {} mode, {} worker(s), {}s measured after {}s warm-up",
+        config.mode, config.concurrency, config.duration_secs, config.warmup_secs
+    );
+    let _ = writeln!(
+        out,
+        "- Noise: results with a coefficient of variation (CV) above {} are marked noisy\n",
+        config.noise.max_cv
+    );
+
+    let mut scenarios: Vec<(&str, usize)> = Vec::new();
+    for result in results {
+        if !scenarios.contains(&(result.scenario, result.size)) {
+            scenarios.push((result.scenario, result.size));
+        }
+    }
+    for (scenario, size) in scenarios {
+        let mut rows: Vec<&RunResult> = results
+            .iter()
+            .filter(|r| r.scenario == scenario && r.size == size)
+            .collect();
+        rows.sort_by(|a, b| b.throughput.total_cmp(&a.throughput));
+        let best = rows[0].throughput;
+
+        let _ = writeln!(out, "## {scenario} (size {size})\n");
+        out.push_str("| Backend | ops/s | Relative | Mean (us) | p50 (us) | p99 (us) | Errors | CV |\n");
+        out.push_str("|---------|------:|---------:|----------:|---------:|---------:|-------:|---:|\n");
+        for r in &rows {
+            let relative = if best > 0.0 { r.throughput / best * 100.0 } else { 0.0 };
+            let _ = writeln!(
+                out,
+                "| {} | {:.1} | {:.0}% | {:.1} | {:.1} | {:.1} | {} | {:.2}{} |",
+                r.backend,
+                r.throughput,
+                relative,
+                r.mean_us,
+                r.p50_us,
+                r.p99_us,
+                r.errors,
+                r.cv,
+                if r.noisy { " (noisy)" } else { "" }
+            );
+        }
+        out.push('\n');
+
+        if rows.iter().any(|r| r.growth.is_some()) {
+            out.push_str("| Backend | Start (MiB) | End (MiB) | Growth | Curve |\n");
+            out.push_str("|---------|------------:|----------:|-------:|-------|\n");
+            for r in &rows {
+                let Some(growth) = &r.growth else { continue };
+                let _ = writeln!(
+                    out,
+                    "| {} | {:.1} | {:.1} | {:+.0}% | {} |",
+                    r.backend,
+                    mib(growth.start_bytes()),
+                    mib(growth.end_bytes()),
+                    growth.relative_growth() * 100.0,
+                    growth.curve()
+                );
+            }
+            out.push('\n');
+        }
+    }
+
+    out.push_str(&score::ranking(&score::rank(results.iter().map(Measurement::from))));
+
+    let mut backends: Vec<(&str, capability::Capabilities)> = Vec::new();
+    for result in results {
+        if backends.iter().any(|(b, _)| *b == result.backend) {
+            continue;
+        }
+        if let Some(capabilities) = runner::capabilities(result.backend) {
+            backends.push((result.backend, capabilities));
+        }
+    }
+    if !backends.is_empty() {
+        out.push_str("## Backend Capabilities\n\n");
+        out.push_str("What each library supports, whether or not a scenario uses it; a backend missing from a\n");
+        out.push_str("group usually lacks the feature the group measures.\n\n");
+        out.push_str(&capability::matrix(&backends));
+    }
+    out
+}
+
+fn mib(bytes: i64) -> f64 {
+    bytes as f64 / (1024.0 * 1024.0)
+}
@@ -0,0 +1,753 @@
+//! Load-test runner behind the `bench-runner` binary
+//!
+//! Criterion measures one operation at a time and reports statistics about
+//! it. The runner instead drives each of the [`SCENARIOS`] from several
+//! concurrent workers for a fixed time, the way an operational load test
+//! would. The throughput mode runs the workers as fast as they go; the latency
+//! mode paces them to a target rate and measures each operation from its
+//! scheduled start, so a backend that falls behind shows its queueing delay
+//! instead of quietly running fewer operations. Settings come from a TOML
+//! file ([`RunnerConfig`]) and results can be exported as JSON and CSV.
+//! Instead of one scenario at a time, the file can declare a weighted mix of
+//! them to run together (see [`crate::workload`]), latency objectives the
+//! results must meet (see [`crate::slo`]), and how noisy results are handled
+//! (see [`crate::noise`]).
+
+use crate::bench_clorinde::ClorindeBench;
+use crate::bench_diesel::DieselBench;
+use crate::bench_null::NullBackend;
+use crate::bench_seaorm::SeaOrmBench;
+use crate::bench_seaquery::SeaQueryBench;
+use crate::bench_sqlx::{SqlxBench, SqlxMacroBench};
+use crate::bench_tokio_postgres::{TokioPostgresBench, TokioPostgresPreparedBench};
+use crate::bench_wtx::WtxBench;
+use crate::capability::Capabilities;
+use crate::growth::{Growth, Sampler};
+use crate::metadata::RunMetadata;
+use crate::noise::{self, NoiseConfig};
+use crate::profile::Profile;
+use crate::replay::{MixEntry, WorkloadMix};
+use crate::scenario::{Fixture, Scenario, SCENARIOS};
+use crate::slo::{Slo, Violation};
+use crate::tags::TagFilter;
+use crate::workload::{self, Dataset, WorkloadOperation};
+use crate::DatabaseBenchmark;
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Backends the runner can drive, by their benchmark names; `null` is the
+/// in-memory backend
+pub const BACKENDS: &[&str] = &[
+    "tokio_postgres",
+    "tokio_postgres_prepared",
+    "sqlx",
+    "sqlx_macro",
+    "sea_orm",
+    "sea_query",
+    "diesel",
+    "clorinde",
+    "wtx",
+    "null",
+];
+
+/// How often [`run`] reports progress
+pub const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Mode {
+    /// Every worker runs its next operation as soon as the last one finishes
+    Throughput,
+    /// Workers start operations at [`RunnerConfig::rate`] in total
+    Latency,
+}
+
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Mode::Throughput => "throughput",
+            Mode::Latency => "latency",
+        })
+    }
+}
+
+/// Runner settings, read from a TOML file; missing keys take their defaults
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RunnerConfig {
+    pub mode: Mode,
+    /// Seconds measured per scenario and backend
+    pub duration_secs: f64,
+    /// Seconds run unmeasured first, to fill caches and prepare statements
+    pub warmup_secs: f64,
+    /// Concurrent workers, each on its own connection
+    pub concurrency: usize,
+    /// Operations per second over all workers in latency mode
+    pub rate: f64,
+    /// Backends to run, from [`BACKENDS`]; empty runs every PostgreSQL backend
+    pub backends: Vec<String>,
+    /// Scenarios to run, by name; empty runs all of [`SCENARIOS`]
+    pub scenarios: Vec<String>,
+    /// Only scenarios with one of these [`crate::tags`]; empty uses
+    /// `BENCH_TAGS`, which when unset too keeps every scenario
+    pub tags: Vec<String>,
+    /// A mix of scenarios to run together instead of one at a time
+    pub operations: Vec<WorkloadOperation>,
+    /// Seed size to top the database up to before running
+    pub dataset: Option<Dataset>,
+    /// Database profile to run against, from [`crate::profile::PROFILES`];
+    /// unset uses `BENCH_PROFILE` or the `DATABASE_URL` database
+    pub profile: Option<String>,
+    /// Objectives per scenario name, checked once every run is done
+    pub slo: BTreeMap<String, Slo>,
+    /// Outlier filtering and what happens to noisy results
+    pub noise: NoiseConfig,
+    /// Seconds between samples of the table and index sizes during each
+    /// measured run (see [`crate::growth`]); unset skips the sampling
+    pub growth_interval_secs: Option<f64>,
+    pub export: ExportConfig,
+}
+
+/// Where results are written, if anywhere
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExportConfig {
+    pub json: Option<PathBuf>,
+    pub csv: Option<PathBuf>,
+}
+
+impl ExportConfig {
+    pub fn is_empty(&self) -> bool {
+        self.json.is_none() && self.csv.is_none()
+    }
+
+    /// Write `results` to every configured file; the runners call it after
+    /// each run, so a run that is interrupted or dies keeps what it measured
+    pub fn write(&self, metadata: &RunMetadata, results: &[RunResult]) -> io::Result<()> {
+        if let Some(path) = &self.json {
+            write_json(path, metadata, results)?;
+        }
+        if let Some(path) = &self.csv {
+            write_csv(path, metadata, results)?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for RunnerConfig {
+    fn default() -> Self {
+        Self {
+            mode: Mode::Throughput,
+            duration_secs: 10.0,
+            warmup_secs: 2.0,
+            concurrency: 4,
+            rate: 200.0,
+            backends: Vec::new(),
+            scenarios: Vec::new(),
+            tags: Vec::new(),
+            operations: Vec::new(),
+            dataset: None,
+            profile: None,
+            slo: BTreeMap::new(),
+            noise: NoiseConfig::default(),
+            growth_interval_secs: None,
+            export: ExportConfig::default(),
+        }
+    }
+}
+
+impl RunnerConfig {
+    /// Parse a configuration file's contents, rejecting unknown or invalid settings
+    pub fn from_toml(text: &str) -> Result<Self, String> {
+        let config: Self = toml::from_str(text).map_err(|e| e.to_string())?;
+        config.backends()?;
+        config.tag_filter()?;
+        config.scenarios()?;
+        config.workload()?;
+        if !config.operations.is_empty() && !config.scenarios.is_empty() {
+            return Err("set either scenarios or operations, not both".to_string());
+        }
+        if let Some(dataset) = &config.dataset {
+            dataset.seed_config()?;
+        }
+        if let Some(profile) = &config.profile {
+            Profile::named(profile)?;
+        }
+        for (name, slo) in &config.slo {
+            if !SCENARIOS.iter().any(|s| s.name == *name) {
+                return Err(format!("objectives for unknown scenario {name:?}"));
+            }
+            slo.validate().map_err(|e| format!("slo.{name}: {e}"))?;
+        }
+        config.noise.validate().map_err(|e| format!("noise: {e}"))?;
+        if config.concurrency == 0 {
+            return Err("concurrency must be at least 1".to_string());
+        }
+        if config.mode == Mode::Latency && config.rate <= 0.0 {
+            return Err("latency mode needs a positive rate".to_string());
+        }
+        if config.duration_secs <= 0.0 || config.warmup_secs < 0.0 {
+            return Err("duration_secs must be positive and warmup_secs not negative".to_string());
+        }
+        if config.growth_interval_secs.is_some_and(|secs| secs <= 0.0) {
+            return Err("growth_interval_secs must be positive".to_string());
+        }
+        Ok(config)
+    }
+
+    /// Time between size samples, if sizes are sampled
+    pub fn growth_interval(&self) -> Option<Duration> {
+        self.growth_interval_secs.map(Duration::from_secs_f64)
+    }
+
+    /// The selected backends, in [`BACKENDS`] order
+    pub fn backends(&self) -> Result<Vec<&'static str>, String> {
+        if let Some(unknown) = self.backends.iter().find(|b| !BACKENDS.contains(&b.as_str())) {
+            return Err(format!("unknown backend {unknown:?}, expected one of {}", BACKENDS.join(", ")));
+        }
+        if self.backends.is_empty() {
+            return Ok(BACKENDS.iter().copied().filter(|b| *b != "null").collect());
+        }
+        Ok(BACKENDS.iter().copied().filter(|b| self.backends.iter().any(|s| s == b)).collect())
+    }
+
+    /// The selected scenarios with one of the selected tags, in [`SCENARIOS`] order
+    pub fn scenarios(&self) -> Result<Vec<&'static Scenario>, String> {
+        if let Some(unknown) = self.scenarios.iter().find(|s| !SCENARIOS.iter().any(|known| known.name == *s)) {
+            return Err(format!("unknown scenario {unknown:?}"));
+        }
+        let tags = self.tag_filter()?;
+        Ok(SCENARIOS
+            .iter()
+            .filter(|s| self.scenarios.is_empty() || self.scenarios.iter().any(|name| name == s.name))
+            .filter(|s| tags.matches(s.tags()))
+            .collect())
+    }
+
+    /// The configured `tags`, or else those `BENCH_TAGS` lists
+    pub fn tag_filter(&self) -> Result<TagFilter, String> {
+        if self.tags.is_empty() {
+            return TagFilter::from_env();
+        }
+        TagFilter::parse(&self.tags.join(","))
+    }
+
+    /// The declared workload, if the configuration has `operations`
+    pub fn workload(&self) -> Result<Option<WorkloadMix>, String> {
+        if self.operations.is_empty() {
+            return Ok(None);
+        }
+        workload::mix(&self.operations).map(Some)
+    }
+
+    /// The objectives `results` miss, in result order
+    pub fn violations(&self, results: &[RunResult]) -> Vec<Violation> {
+        results
+            .iter()
+            .filter_map(|r| Some(self.slo.get(r.scenario)?.check(r)))
+            .flatten()
+            .collect()
+    }
+}
+
+/// Counts so far in a measured run, passed to the progress callback
+#[derive(Debug, Clone, Copy)]
+pub struct Progress {
+    pub operations: u64,
+    pub errors: u64,
+    pub elapsed: Duration,
+}
+
+impl Progress {
+    pub fn throughput(&self) -> f64 {
+        self.operations as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+/// One scenario measured on one backend
+#[derive(Debug, Clone, Serialize)]
+pub struct RunResult {
+    pub scenario: &'static str,
+    pub backend: &'static str,
+    pub mode: Mode,
+    /// Rows, batch or post count, the scenario's typical size
+    pub size: usize,
+    pub concurrency: usize,
+    /// Successful operations
+    pub operations: u64,
+    pub errors: u64,
+    /// The first error, if any
+    pub first_error: Option<String>,
+    pub elapsed_secs: f64,
+    /// Successful operations per second
+    pub throughput: f64,
+    /// Mean latency, without the outliers `noise.outlier_fence` excludes
+    pub mean_us: f64,
+    /// Sample standard deviation of the latencies, for comparing runs
+    pub stddev_us: f64,
+    pub p50_us: f64,
+    pub p95_us: f64,
+    pub p99_us: f64,
+    pub max_us: f64,
+    /// Latencies left out of the mean and standard deviation as outliers
+    pub outliers: u64,
+    /// Coefficient of variation of the latencies, the noise score
+    pub cv: f64,
+    /// Whether `cv` is above `noise.max_cv`
+    pub noisy: bool,
+    /// Table and index sizes over the run, with `growth_interval_secs` set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub growth: Option<Growth>,
+}
+
+/// Latencies and failures of one mix entry
+#[derive(Default)]
+struct EntryRecord {
+    latencies: Mutex<Vec<Duration>>,
+    errors: AtomicU64,
+    first_error: Mutex<Option<String>>,
+}
+
+/// What the workers of one phase recorded, shared with the threads a
+/// blocking backend's workers run on
+struct Recorder {
+    entries: Vec<EntryRecord>,
+    /// Operations started so far, which picks the next entry of the mix
+    next_step: AtomicUsize,
+    /// Generated index of the next user a writing scenario inserts
+    next_user: Mutex<usize>,
+}
+
+impl Recorder {
+    fn new(entries: usize, next_user: usize) -> Self {
+        Self {
+            entries: (0..entries).map(|_| EntryRecord::default()).collect(),
+            next_step: AtomicUsize::new(0),
+            next_user: Mutex::new(next_user),
+        }
+    }
+
+    fn progress(&self, start: Instant) -> Progress {
+        Progress {
+            operations: self.entries.iter().map(|e| e.latencies.lock().unwrap().len() as u64).sum(),
+            errors: self.entries.iter().map(|e| e.errors.load(Ordering::Relaxed)).sum(),
+            elapsed: start.elapsed(),
+        }
+    }
+}
+
+/// Run `scenario` at its typical size on `B` for the configured warm-up and
+/// duration, calling `progress` every [`PROGRESS_INTERVAL`] while measuring.
+///
+/// Failed operations are counted rather than ending the run; connecting,
+/// setup and teardown errors are returned.
+pub async fn run<B: DatabaseBenchmark + 'static>(
+    config: &RunnerConfig,
+    scenario: &'static Scenario,
+    backend: &'static str,
+    progress: &mut dyn FnMut(&Progress),
+) -> Result<RunResult, B::Error>
+where
+    B::Connection: Send + Sync,
+{
+    let mut results = run_mix::<B>(config, &WorkloadMix::single(scenario), backend, progress).await?;
+    Ok(results.remove(0))
+}
+
+/// Run the entries of `mix` together, interleaved in proportion to their
+/// calls, like [`run`] does one scenario. Returns a result per entry, each
+/// with its own latencies and its share of the throughput.
+pub async fn run_mix<B: DatabaseBenchmark + 'static>(
+    config: &RunnerConfig,
+    mix: &WorkloadMix,
+    backend: &'static str,
+    progress: &mut dyn FnMut(&Progress),
+) -> Result<Vec<RunResult>, B::Error>
+where
+    B::Connection: Send + Sync,
+{
+    let mut conns = Vec::with_capacity(config.concurrency);
+    for conn in join_all((0..config.concurrency).map(|_| B::connect())).await {
+        conns.push(conn?);
+    }
+    // Every teardown first, as one entry's would remove the rows another's setup inserted
+    for entry in &mix.entries {
+        entry.scenario.teardown::<B>(&conns[0]).await?;
+    }
+    let mut fixtures = Vec::with_capacity(mix.entries.len());
+    for entry in &mix.entries {
+        fixtures.push(entry.scenario.setup::<B>(&conns[0]).await?);
+    }
+    let total_calls = mix.entries.iter().map(|e| e.calls).sum::<u64>() as usize;
+    let phase = Arc::new(Phase {
+        mode: config.mode,
+        concurrency: config.concurrency,
+        rate: config.rate,
+        entries: mix.entries.clone(),
+        fixtures,
+        sequence: mix.sequence(total_calls),
+    });
+
+    // Compacted before the warm-up, whose writes are part of every backend's starting size
+    let sampler = match config.growth_interval() {
+        Some(interval) if backend != "null" => Some(Sampler::start(interval).await),
+        _ => None,
+    };
+
+    let warmup = Arc::new(Recorder::new(mix.entries.len(), 1));
+    let (conns, _) = phase
+        .drive::<B>(conns, &warmup, Duration::from_secs_f64(config.warmup_secs), None, &mut |_| {})
+        .await;
+    // Inserted users carry on from the warm-up, so they stay unique until the teardown
+    let measured = Arc::new(Recorder::new(mix.entries.len(), *warmup.next_user.lock().unwrap()));
+    let (conns, elapsed) = phase
+        .drive::<B>(conns, &measured, Duration::from_secs_f64(config.duration_secs), sampler.as_ref(), progress)
+        .await;
+    let Ok(measured) = Arc::try_unwrap(measured) else {
+        unreachable!("every worker has finished");
+    };
+    let growth = match sampler {
+        Some(sampler) => Some(sampler.finish(elapsed).await),
+        None => None,
+    };
+    for entry in &mix.entries {
+        entry.scenario.teardown::<B>(&conns[0]).await?;
+    }
+
+    Ok(mix
+        .entries
+        .iter()
+        .zip(measured.entries)
+        .map(|(entry, record)| {
+            let mut latencies = record.latencies.into_inner().unwrap();
+            latencies.sort();
+            let micros = |q: f64| match latencies.len() {
+                0 => 0.0,
+                len => latencies[((len - 1) as f64 * q).round() as usize].as_secs_f64() * 1e6,
+            };
+            let (kept, outliers) = config.noise.without_outliers(&latencies);
+            let (mean_us, stddev_us) = mean_and_stddev(kept);
+            let cv = noise::coefficient_of_variation(mean_us, stddev_us);
+            RunResult {
+                scenario: entry.scenario.name,
+                backend,
+                mode: config.mode,
+                size: entry.size,
+                concurrency: config.concurrency,
+                operations: latencies.len() as u64,
+                errors: record.errors.into_inner(),
+                first_error: record.first_error.into_inner().unwrap(),
+                elapsed_secs: elapsed.as_secs_f64(),
+                throughput: latencies.len() as f64 / elapsed.as_secs_f64(),
+                mean_us,
+                stddev_us,
+                p50_us: micros(0.5),
+                p95_us: micros(0.95),
+                p99_us: micros(0.99),
+                max_us: micros(1.0),
+                outliers: outliers as u64,
+                cv,
+                noisy: config.noise.is_noisy(cv),
+                growth: growth.clone(),
+            }
+        })
+        .collect())
+}
+
+/// [`run_mix`] on the backend named `backend`, one of [`BACKENDS`], with
+/// its error described; a noisy run is run again as `noise.action` asks, and
+/// the least noisy one kept
+pub async fn run_backend(
+    config: &RunnerConfig,
+    mix: &WorkloadMix,
+    backend: &'static str,
+    progress: &mut dyn FnMut(&Progress),
+) -> Result<Vec<RunResult>, String> {
+    let mut results = run_named(config, mix, backend, progress).await?;
+    for _ in 0..config.noise.reruns {
+        if !config.noise.wants_rerun(&results) {
+            break;
+        }
+        let rerun = run_named(config, mix, backend, progress).await?;
+        if noise::run_noise(&rerun) < noise::run_noise(&results) {
+            results = rerun;
+        }
+    }
+    Ok(results)
+}
+
+async fn run_named(
+    config: &RunnerConfig,
+    mix: &WorkloadMix,
+    backend: &'static str,
+    progress: &mut dyn FnMut(&Progress),
+) -> Result<Vec<RunResult>, String> {
+    fn described<E: fmt::Debug>(result: Result<Vec<RunResult>, E>) -> Result<Vec<RunResult>, String> {
+        result.map_err(|e| format!("{e:?}"))
+    }
+    match backend {
+        "tokio_postgres" => described(run_mix::<TokioPostgresBench>(config, mix, backend, progress).await),
+        "tokio_postgres_prepared" => described(run_mix::<TokioPostgresPreparedBench>(config, mix, backend, progress).await),
+        "sqlx" => described(run_mix::<SqlxBench>(config, mix, backend, progress).await),
+        "sqlx_macro" => described(run_mix::<SqlxMacroBench>(config, mix, backend, progress).await),
+        "sea_orm" => described(run_mix::<SeaOrmBench>(config, mix, backend, progress).await),
+        "sea_query" => described(run_mix::<SeaQueryBench>(config, mix, backend, progress).await),
+        "diesel" => described(run_mix::<DieselBench>(config, mix, backend, progress).await),
+        "clorinde" => described(run_mix::<ClorindeBench>(config, mix, backend, progress).await),
+        "wtx" => described(run_mix::<WtxBench>(config, mix, backend, progress).await),
+        "null" => described(run_mix::<NullBackend>(config, mix, backend, progress).await),
+        other => Err(format!("unknown backend {other}")),
+    }
+}
+
+/// Remove what runs on the backend named `backend` left behind, such as
+/// `bench_user_*` rows, with its error described
+pub async fn cleanup_backend(backend: &str) -> Result<(), String> {
+    async fn cleanup<B: DatabaseBenchmark>() -> Result<(), String> {
+        let conn = B::connect().await.map_err(|e| format!("{e:?}"))?;
+        B::cleanup(&conn).await.map_err(|e| format!("{e:?}"))
+    }
+    match backend {
+        "tokio_postgres" => cleanup::<TokioPostgresBench>().await,
+        "tokio_postgres_prepared" => cleanup::<TokioPostgresPreparedBench>().await,
+        "sqlx" => cleanup::<SqlxBench>().await,
+        "sqlx_macro" => cleanup::<SqlxMacroBench>().await,
+        "sea_orm" => cleanup::<SeaOrmBench>().await,
+        "sea_query" => cleanup::<SeaQueryBench>().await,
+        "diesel" => cleanup::<DieselBench>().await,
+        "clorinde" => cleanup::<ClorindeBench>().await,
+        "wtx" => cleanup::<WtxBench>().await,
+        "null" => cleanup::<NullBackend>().await,
+        other => Err(format!("unknown backend {other}")),
+    }
+}
+
+/// What the backend named `backend`, one of [`BACKENDS`], supports
+pub fn capabilities(backend: &str) -> Option<Capabilities> {
+    match backend {
+        "tokio_postgres" => Some(TokioPostgresBench::capabilities()),
+        "tokio_postgres_prepared" => Some(TokioPostgresPreparedBench::capabilities()),
+        "sqlx" => Some(SqlxBench::capabilities()),
+        "sqlx_macro" => Some(SqlxMacroBench::capabilities()),
+        "sea_orm" => Some(SeaOrmBench::capabilities()),
+        "sea_query" => Some(SeaQueryBench::capabilities()),
+        "diesel" => Some(DieselBench::capabilities()),
+        "clorinde" => Some(ClorindeBench::capabilities()),
+        "wtx" => Some(WtxBench::capabilities()),
+        "null" => Some(NullBackend::capabilities()),
+        _ => None,
+    }
+}
+
+/// Mean and sample standard deviation of `latencies` in microseconds
+fn mean_and_stddev(latencies: &[Duration]) -> (f64, f64) {
+    let n = latencies.len() as f64;
+    if latencies.is_empty() {
+        return (0.0, 0.0);
+    }
+    let mean = latencies.iter().map(|l| l.as_secs_f64() * 1e6).sum::<f64>() / n;
+    if latencies.len() == 1 {
+        return (mean, 0.0);
+    }
+    let squares: f64 = latencies.iter().map(|l| (l.as_secs_f64() * 1e6 - mean).powi(2)).sum();
+    (mean, (squares / (n - 1.0)).sqrt())
+}
+
+/// What every worker of a run shares
+struct Phase {
+    mode: Mode,
+    concurrency: usize,
+    /// Operations per second over all workers in latency mode
+    rate: f64,
+    entries: Vec<MixEntry>,
+    fixtures: Vec<Fixture>,
+    /// Entry indexes in the order operations start, repeated as needed
+    sequence: Vec<usize>,
+}
+
+impl Phase {
+    /// Run one worker per connection for `duration`, recording into
+    /// `recorder` and sampling sizes with `sampler`, and return the
+    /// connections and how long it took for the workers to finish.
+    ///
+    /// Each worker is a task of its own, spawned on the runtime's worker
+    /// threads; a [`DatabaseBenchmark::BLOCKING`] backend's would hold up the
+    /// thread it runs on, so each of its workers gets a blocking thread
+    /// instead. Either way every backend gets the same parallelism.
+    async fn drive<B: DatabaseBenchmark + 'static>(
+        self: &Arc<Self>,
+        conns: Vec<B::Connection>,
+        recorder: &Arc<Recorder>,
+        duration: Duration,
+        sampler: Option<&Sampler>,
+        progress: &mut dyn FnMut(&Progress),
+    ) -> (Vec<B::Connection>, Duration)
+    where
+        B::Connection: Send + Sync,
+    {
+        let start = Instant::now();
+        let deadline = start + duration;
+        let runtime = tokio::runtime::Handle::current();
+        let workers = conns.into_iter().enumerate().map(|(index, conn)| {
+            let (phase, recorder) = (self.clone(), recorder.clone());
+            if B::BLOCKING {
+                let runtime = runtime.clone();
+                tokio::task::spawn_blocking(move || {
+                    runtime.block_on(phase.worker::<B>(&conn, index, &recorder, start, deadline));
+                    conn
+                })
+            } else {
+                tokio::spawn(async move {
+                    phase.worker::<B>(&conn, index, &recorder, start, deadline).await;
+                    conn
+                })
+            }
+        });
+        let workers = async {
+            join_all(workers)
+                .await
+                .into_iter()
+                .map(|worker| worker.unwrap_or_else(|e| std::panic::resume_unwind(e.into_panic())))
+                .collect()
+        };
+        let report = async {
+            let mut ticker = tokio::time::interval_at((start + PROGRESS_INTERVAL).into(), PROGRESS_INTERVAL);
+            loop {
+                ticker.tick().await;
+                progress(&recorder.progress(start));
+            }
+        };
+        let sample = async {
+            if let Some(sampler) = sampler {
+                sampler.run(start).await;
+            }
+            std::future::pending::<()>().await
+        };
+        let conns = tokio::select! {
+            conns = workers => conns,
+            _ = report => unreachable!(),
+            _ = sample => unreachable!(),
+        };
+        (conns, start.elapsed())
+    }
+
+    async fn worker<B: DatabaseBenchmark>(
+        &self,
+        conn: &B::Connection,
+        index: usize,
+        recorder: &Recorder,
+        start: Instant,
+        deadline: Instant,
+    ) {
+        let interval = (self.mode == Mode::Latency).then(|| Duration::from_secs_f64(self.concurrency as f64 / self.rate));
+        // Staggered so the workers' starts are spread evenly over each interval
+        let mut scheduled = start + interval.map_or(Duration::ZERO, |i| i.mul_f64(index as f64 / self.concurrency as f64));
+
+        while scheduled < deadline && Instant::now() < deadline {
+            if interval.is_some() {
+                tokio::time::sleep_until(scheduled.into()).await;
+            } else {
+                scheduled = Instant::now();
+            }
+
+            let step = recorder.next_step.fetch_add(1, Ordering::Relaxed);
+            let entry_index = self.sequence[step % self.sequence.len()];
+            let MixEntry { scenario, size, .. } = self.entries[entry_index];
+            // Writing entries share one range of generated users, so they never insert the same one
+            let iteration = if scenario.writes() {
+                let mut next_user = recorder.next_user.lock().unwrap();
+                let (iteration, next) = scenario.iteration_inserting_from(size, *next_user);
+                *next_user = next;
+                iteration
+            } else {
+                step + 1
+            };
+
+            let record = &recorder.entries[entry_index];
+            match scenario.run::<B>(conn, &self.fixtures[entry_index], size, iteration).await {
+                Ok(()) => record.latencies.lock().unwrap().push(scheduled.elapsed()),
+                Err(e) => {
+                    record.errors.fetch_add(1, Ordering::Relaxed);
+                    record.first_error.lock().unwrap().get_or_insert_with(|| format!("{e:?}"));
+                }
+            }
+
+            if let Some(interval) = interval {
+                scheduled += interval;
+            }
+            // A backend that never suspends, like the in-memory one, would
+            // otherwise starve the other workers and the progress report
+            tokio::task::yield_now().await;
+        }
+    }
+}
+
+/// A JSON export: the run's metadata and its results
+#[derive(Serialize)]
+struct Export<'a> {
+    metadata: &'a RunMetadata,
+    results: &'a [RunResult],
+}
+
+/// Write `results` as a JSON object with `metadata` next to them, creating
+/// the parent directory if needed
+pub fn write_json(path: &Path, metadata: &RunMetadata, results: &[RunResult]) -> io::Result<()> {
+    let mut file = create(path)?;
+    serde_json::to_writer_pretty(&mut file, &Export { metadata, results })?;
+    writeln!(file)
+}
+
+/// Write `results` as CSV with a header row, preceded by `metadata` as `#`
+/// comment lines, creating the parent directory if needed
+pub fn write_csv(path: &Path, metadata: &RunMetadata, results: &[RunResult]) -> io::Result<()> {
+    let mut file = create(path)?;
+    for (name, value) in metadata.entries() {
+        writeln!(file, "# {name}: {value}")?;
+    }
+    writeln!(
+        file,
+        "scenario,backend,mode,size,concurrency,operations,errors,elapsed_secs,throughput,mean_us,stddev_us,p50_us,p95_us,p99_us,max_us,outliers,cv,noisy,first_error"
+    )?;
+    for r in results {
+        let first_error = r.first_error.as_deref().map_or(String::new(), |e| format!("\"{}\"", e.replace('"', "\"\"")));
+        writeln!(
+            file,
+            "{},{},{},{},{},{},{},{:.3},{:.1},{:.1},{:.1},{:.1},{:.1},{:.1},{:.1},{},{:.3},{},{}",
+            r.scenario,
+            r.backend,
+            r.mode,
+            r.size,
+            r.concurrency,
+            r.operations,
+            r.errors,
+            r.elapsed_secs,
+            r.throughput,
+            r.mean_us,
+            r.stddev_us,
+            r.p50_us,
+            r.p95_us,
+            r.p99_us,
+            r.max_us,
+            r.outliers,
+            r.cv,
+            r.noisy,
+            first_error
+        )?;
+    }
+    Ok(())
+}
+
+fn create(path: &Path) -> io::Result<fs::File> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    fs::File::create(path)
+}
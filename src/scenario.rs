@@ -0,0 +1,304 @@
+//! Benchmark scenarios, defined once for every mode that runs them
+//!
+//! A [`Scenario`] is one benchmarked operation: its name (the criterion group),
+//! category, the sizes it runs at, and its setup, operation and teardown. The
+//! criterion benches register a group for every entry of [`SCENARIOS`] and
+//! `cargo run -- audit` audits the same list, so a scenario added here shows up
+//! in both and in their reports.
+
+use crate::tags::Tag;
+use crate::{consume, DatabaseBenchmark, NewComment, NewPost, NewUser};
+use std::fmt;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// What kind of work a scenario measures
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    Insert,
+    Select,
+    Update,
+    Join,
+    Aggregate,
+    Transaction,
+}
+
+impl Category {
+    pub const ALL: [Category; 6] = [
+        Category::Insert,
+        Category::Select,
+        Category::Update,
+        Category::Join,
+        Category::Aggregate,
+        Category::Transaction,
+    ];
+
+    /// The [`Tag`]s of every scenario in this category
+    pub fn tags(&self) -> &'static [Tag] {
+        match self {
+            Category::Insert | Category::Update => &[Tag::Write],
+            Category::Select | Category::Aggregate => &[Tag::Read],
+            Category::Join => &[Tag::Read, Tag::Join],
+            Category::Transaction => &[Tag::Write, Tag::Transaction],
+        }
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Category::Insert => "insert",
+            Category::Select => "select",
+            Category::Update => "update",
+            Category::Join => "join",
+            Category::Aggregate => "aggregate",
+            Category::Transaction => "transaction",
+        })
+    }
+}
+
+/// The `DatabaseBenchmark` call a scenario makes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    /// Insert one new user
+    InsertUser,
+    /// Insert `size` new users in one batch
+    InsertUsersBatch,
+    /// Comment on the fixture's post
+    InsertComment,
+    /// Look up one of the sampled users by id
+    SelectUserById,
+    /// The newest `size` users
+    SelectUsersLimit,
+    /// `size` users aged 25 to 55
+    SelectUsersFiltered,
+    /// Rename one of the sampled users
+    UpdateUser,
+    /// The newest `size` posts with their authors
+    SelectPostsWithUser,
+    /// `size` rows of users, their posts and the posts' comments
+    SelectUsersPostsComments,
+    /// Posts per user over the whole table
+    CountPostsPerUser,
+    /// Posts per tag over the whole `post_tags` link table
+    CountPostsPerTag,
+    /// Insert a new user and `size` posts in one transaction
+    InsertUserWithPosts,
+}
+
+/// One benchmarked operation and how it is run
+#[derive(Debug, Clone, Copy)]
+pub struct Scenario {
+    /// Criterion group name, also used by the audit
+    pub name: &'static str,
+    pub category: Category,
+    pub operation: Operation,
+    /// Rows, batch or post counts the scenario runs at; empty for a single,
+    /// unparameterised run
+    pub sizes: &'static [usize],
+    /// Whether criterion reports each size as rows per second
+    pub throughput: bool,
+    pub measurement_time: Duration,
+    pub sample_size: usize,
+    /// Backends left out of the benchmark, with the reason in [`SCENARIOS`]
+    pub skip: &'static [&'static str],
+}
+
+/// Sizes of the read and batch scenarios
+pub const SIZES: &[usize] = &[10, 100, 1000];
+
+const fn scenario(name: &'static str, category: Category, operation: Operation) -> Scenario {
+    Scenario {
+        name,
+        category,
+        operation,
+        sizes: &[],
+        throughput: false,
+        measurement_time: Duration::from_secs(10),
+        sample_size: 100,
+        skip: &[],
+    }
+}
+
+const fn sized(name: &'static str, category: Category, operation: Operation) -> Scenario {
+    Scenario {
+        sizes: SIZES,
+        throughput: true,
+        ..scenario(name, category, operation)
+    }
+}
+
+/// Every scenario, in the order they are benchmarked and audited
+pub const SCENARIOS: &[Scenario] = &[
+    scenario("insert_single_user", Category::Insert, Operation::InsertUser),
+    Scenario {
+        measurement_time: Duration::from_secs(15),
+        sample_size: 50,
+        ..sized("insert_batch_users", Category::Insert, Operation::InsertUsersBatch)
+    },
+    scenario("insert_comment", Category::Insert, Operation::InsertComment),
+    Scenario {
+        sample_size: 200,
+        ..scenario("select_user_by_id", Category::Select, Operation::SelectUserById)
+    },
+    sized("select_users_limit", Category::Select, Operation::SelectUsersLimit),
+    sized("select_users_filtered", Category::Select, Operation::SelectUsersFiltered),
+    scenario("update_user", Category::Update, Operation::UpdateUser),
+    Scenario {
+        sample_size: 50,
+        ..sized("join_posts_users", Category::Join, Operation::SelectPostsWithUser)
+    },
+    Scenario {
+        measurement_time: Duration::from_secs(15),
+        sample_size: 30,
+        ..sized("join_users_posts_comments", Category::Join, Operation::SelectUsersPostsComments)
+    },
+    Scenario {
+        sample_size: 50,
+        ..scenario("aggregate_count_posts_per_user", Category::Aggregate, Operation::CountPostsPerUser)
+    },
+    Scenario {
+        sample_size: 50,
+        ..scenario("aggregate_count_posts_per_tag", Category::Aggregate, Operation::CountPostsPerTag)
+    },
+    Scenario {
+        sizes: &[1, 5, 10],
+        measurement_time: Duration::from_secs(15),
+        sample_size: 30,
+        ..scenario("transaction_insert_user_with_posts", Category::Transaction, Operation::InsertUserWithPosts)
+    },
+];
+
+/// State set up once per scenario and shared by its runs
+#[derive(Debug, Clone, Default)]
+pub struct Fixture {
+    /// Seeded users the lookups and updates cycle through
+    pub user_ids: Vec<Uuid>,
+    /// The post comments are added to and their author, a `bench_user_*`
+    /// row so that cleanup removes the comments with it
+    pub comment_on: Option<(Uuid, Uuid)>,
+}
+
+/// Username of the user [`Operation::InsertComment`] comments as
+pub const COMMENTER: &str = "bench_user_commenter";
+
+impl Scenario {
+    /// Whether every run inserts new `bench_user_*` rows, which
+    /// [`Scenario::teardown`] removes; such runs need fresh iterations until then
+    pub fn writes(&self) -> bool {
+        matches!(
+            self.operation,
+            Operation::InsertUser | Operation::InsertUsersBatch | Operation::InsertUserWithPosts
+        )
+    }
+
+    /// What the scenario exercises, for `BENCH_TAGS`
+    pub fn tags(&self) -> &'static [Tag] {
+        self.category.tags()
+    }
+
+    /// Whether `backend` is benchmarked for this scenario
+    pub fn runs_on(&self, backend: &str) -> bool {
+        !self.skip.contains(&backend)
+    }
+
+    /// The middle of [`Scenario::sizes`], for modes that run one size only
+    pub fn typical_size(&self) -> usize {
+        self.sizes.get(self.sizes.len() / 2).copied().unwrap_or(1)
+    }
+
+    /// For a writing scenario, the `iteration` at which [`Scenario::run`]
+    /// inserts users with generated indexes from `first_user` on, and the index
+    /// after the last one it inserts. Lets callers that mix writing scenarios
+    /// keep their generated users apart.
+    pub fn iteration_inserting_from(&self, size: usize, first_user: usize) -> (usize, usize) {
+        match self.operation {
+            Operation::InsertUsersBatch => {
+                let iteration = first_user.div_ceil(size) + 1;
+                (iteration, iteration * size)
+            }
+            _ => (first_user.max(1), first_user.max(1) + 1),
+        }
+    }
+
+    /// Sample the seeded users that lookups and updates use, or insert the
+    /// post that comments are added to
+    pub async fn setup<B: DatabaseBenchmark>(&self, conn: &B::Connection) -> Result<Fixture, B::Error> {
+        let mut fixture = Fixture::default();
+        match self.operation {
+            Operation::SelectUserById | Operation::UpdateUser => {
+                fixture.user_ids = B::select_users_limit(conn, 100).await?.iter().map(|u| u.id).collect();
+            }
+            Operation::InsertComment => {
+                let commenter = NewUser {
+                    username: COMMENTER.to_string(),
+                    email: format!("{COMMENTER}@benchmark.com"),
+                    ..NewUser::generate(0)
+                };
+                let user_id = B::insert_user(conn, &commenter).await?;
+                let post_id = B::insert_post(conn, &NewPost::generate(user_id, 0)).await?;
+                fixture.comment_on = Some((post_id, user_id));
+            }
+            _ => {}
+        }
+        Ok(fixture)
+    }
+
+    /// Run the operation once at `size`. `iteration` starts at 1 and picks
+    /// fresh users to insert, so it must not repeat between teardowns.
+    pub async fn run<B: DatabaseBenchmark>(
+        &self,
+        conn: &B::Connection,
+        fixture: &Fixture,
+        size: usize,
+        iteration: usize,
+    ) -> Result<(), B::Error> {
+        let sampled = || fixture.user_ids[iteration % fixture.user_ids.len()];
+        let limit = size as i64;
+        match self.operation {
+            Operation::InsertUser => B::insert_user(conn, &NewUser::generate(iteration)).await.map(consume),
+            Operation::InsertUsersBatch => {
+                // Usernames are unique, so every iteration inserts a fresh range of users
+                let start = (iteration - 1) * size;
+                let users: Vec<NewUser> = (start..start + size).map(NewUser::generate).collect();
+                B::insert_users_batch(conn, &users).await.map(consume)
+            }
+            Operation::InsertComment => {
+                let (post_id, user_id) = fixture.comment_on.expect("set up by Scenario::setup");
+                B::insert_comment(conn, &NewComment::generate(post_id, user_id, iteration))
+                    .await
+                    .map(consume)
+            }
+            Operation::SelectUserById => B::select_user_by_id(conn, sampled()).await.map(consume),
+            Operation::SelectUsersLimit => B::select_users_limit(conn, limit).await.map(consume),
+            Operation::SelectUsersFiltered => B::select_users_filtered(conn, 25, 55, limit).await.map(consume),
+            Operation::UpdateUser => B::update_user(conn, sampled(), "UpdatedFirst", "UpdatedLast")
+                .await
+                .map(consume),
+            Operation::SelectPostsWithUser => B::select_posts_with_user(conn, limit).await.map(consume),
+            Operation::SelectUsersPostsComments => B::select_users_posts_comments(conn, limit).await.map(consume),
+            Operation::CountPostsPerUser => B::count_posts_per_user(conn).await.map(consume),
+            Operation::CountPostsPerTag => B::count_posts_per_tag(conn).await.map(consume),
+            Operation::InsertUserWithPosts => {
+                // The backend gives the posts the new user's id, so cleanup removes them with it
+                let posts: Vec<NewPost> = (0..size).map(|i| NewPost::generate(Uuid::nil(), i)).collect();
+                B::insert_user_with_posts(conn, &NewUser::generate(iteration), &posts)
+                    .await
+                    .map(consume)
+            }
+        }
+    }
+
+    /// Remove the rows a writing scenario or its setup inserted
+    pub async fn teardown<B: DatabaseBenchmark>(&self, conn: &B::Connection) -> Result<(), B::Error> {
+        if self.writes() || self.operation == Operation::InsertComment {
+            B::cleanup(conn).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Scenarios in `category`
+pub fn in_category(category: Category) -> impl Iterator<Item = &'static Scenario> {
+    SCENARIOS.iter().filter(move |s| s.category == category)
+}
@@ -0,0 +1,200 @@
+//! A composite score per backend, for the ranking at the end of a report
+//!
+//! Each benchmark (a scenario at one size, concurrency and mode) is scored
+//! relative to the backend that did best at it: its slowdown is the best
+//! throughput over the backend's throughput, or in latency mode, where every
+//! backend runs at the configured rate, the backend's mean latency over the
+//! best one. A backend's score is the geometric mean of its slowdowns, so
+//! `1.00` means fastest everywhere and `1.25` a quarter slower on the typical
+//! benchmark, however differently the benchmarks themselves are sized. The
+//! same mean over the benchmarks of a scenario tag gives the read, write and
+//! join scores; the concurrency score covers the benchmarks run by more than
+//! one worker.
+//!
+//! Benchmarks only one backend ran, and results without a successful
+//! operation, are left out. A backend is scored over the benchmarks it ran,
+//! so one that skips scenarios is not penalised for them, but is scored over
+//! fewer.
+
+use crate::report::ExportedResult;
+use crate::runner::{Mode, RunResult};
+use crate::scenario::SCENARIOS;
+use crate::tags::Tag;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// The tags scored separately, in report order
+pub const CATEGORIES: [Tag; 4] = [Tag::Read, Tag::Write, Tag::Join, Tag::Concurrency];
+
+/// What scoring needs of a result, from a run or from an export
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measurement<'a> {
+    pub scenario: &'a str,
+    pub backend: &'a str,
+    pub mode: Mode,
+    pub size: usize,
+    pub concurrency: usize,
+    pub throughput: f64,
+    /// Missing from exports older than the diff
+    pub mean_us: Option<f64>,
+}
+
+impl<'a> From<&'a RunResult> for Measurement<'a> {
+    fn from(result: &'a RunResult) -> Self {
+        Self {
+            scenario: result.scenario,
+            backend: result.backend,
+            mode: result.mode,
+            size: result.size,
+            concurrency: result.concurrency,
+            throughput: result.throughput,
+            mean_us: Some(result.mean_us),
+        }
+    }
+}
+
+impl<'a> From<&'a ExportedResult> for Measurement<'a> {
+    fn from(result: &'a ExportedResult) -> Self {
+        Self {
+            scenario: &result.scenario,
+            backend: &result.backend,
+            mode: result.mode,
+            size: result.size,
+            concurrency: result.concurrency,
+            throughput: result.throughput,
+            mean_us: result.mean_us,
+        }
+    }
+}
+
+impl Measurement<'_> {
+    /// How fast the result was, higher being better; `None` if it can't be scored
+    fn speed(&self) -> Option<f64> {
+        let speed = match self.mode {
+            Mode::Throughput => self.throughput,
+            Mode::Latency => 1.0 / self.mean_us?,
+        };
+        (speed.is_finite() && speed > 0.0).then_some(speed)
+    }
+
+    /// The [`CATEGORIES`] the result counts towards
+    fn categories(&self) -> Vec<Tag> {
+        let tags = SCENARIOS.iter().find(|s| s.name == self.scenario).map_or(&[][..], |s| s.tags());
+        let mut categories: Vec<Tag> = CATEGORIES.into_iter().filter(|tag| tags.contains(tag)).collect();
+        if self.concurrency > 1 {
+            categories.push(Tag::Concurrency);
+        }
+        categories
+    }
+}
+
+/// What a result is compared by: scenario, size, concurrency and mode
+type Benchmark<'a> = (&'a str, usize, usize, Mode);
+
+/// One backend's place in the ranking
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackendScore {
+    pub backend: String,
+    /// Geometric mean slowdown over every benchmark the backend was scored on
+    pub overall: f64,
+    /// The same over the benchmarks of each of [`CATEGORIES`], `None` if it
+    /// ran none of them
+    pub categories: Vec<(Tag, Option<f64>)>,
+    /// Benchmarks the backend was scored on
+    pub benchmarks: usize,
+    /// Benchmarks the backend was fastest at
+    pub wins: usize,
+}
+
+impl BackendScore {
+    pub fn category(&self, tag: Tag) -> Option<f64> {
+        self.categories.iter().find(|(t, _)| *t == tag).and_then(|(_, score)| *score)
+    }
+}
+
+/// Score every backend of `results`, best first
+pub fn rank<'a>(results: impl IntoIterator<Item = Measurement<'a>>) -> Vec<BackendScore> {
+    let mut benchmarks: BTreeMap<Benchmark, Vec<(Measurement, f64)>> = BTreeMap::new();
+    for result in results {
+        if let Some(speed) = result.speed() {
+            let key = (result.scenario, result.size, result.concurrency, result.mode);
+            benchmarks.entry(key).or_default().push((result, speed));
+        }
+    }
+
+    // Per backend, the log of each slowdown with the categories it counts towards
+    let mut slowdowns: BTreeMap<&str, Vec<(f64, Vec<Tag>)>> = BTreeMap::new();
+    for results in benchmarks.values().filter(|results| results.len() > 1) {
+        let best = results.iter().map(|(_, speed)| *speed).fold(0.0, f64::max);
+        for (result, speed) in results {
+            let slowdown = (best / speed).ln();
+            slowdowns.entry(result.backend).or_default().push((slowdown, result.categories()));
+        }
+    }
+
+    let mut scores: Vec<BackendScore> = slowdowns
+        .into_iter()
+        .map(|(backend, slowdowns)| {
+            let categories = CATEGORIES
+                .into_iter()
+                .map(|tag| {
+                    let logs = slowdowns.iter().filter(|(_, tags)| tags.contains(&tag)).map(|(log, _)| *log);
+                    (tag, geometric_mean(logs))
+                })
+                .collect();
+            BackendScore {
+                backend: backend.to_string(),
+                overall: geometric_mean(slowdowns.iter().map(|(log, _)| *log)).unwrap_or(1.0),
+                categories,
+                benchmarks: slowdowns.len(),
+                wins: slowdowns.iter().filter(|(log, _)| *log == 0.0).count(),
+            }
+        })
+        .collect();
+    scores.sort_by(|a, b| a.overall.total_cmp(&b.overall));
+    scores
+}
+
+/// `exp` of the mean of `logs`, `None` for none
+fn geometric_mean(logs: impl Iterator<Item = f64>) -> Option<f64> {
+    let (sum, count) = logs.fold((0.0, 0usize), |(sum, count), log| (sum + log, count + 1));
+    (count > 0).then(|| (sum / count as f64).exp())
+}
+
+/// The Markdown ranking section, empty when no benchmark ran on two backends
+pub fn ranking(scores: &[BackendScore]) -> String {
+    if scores.is_empty() {
+        return String::new();
+    }
+    let mut out = String::from("## Overall Ranking\n\n");
+    out.push_str("Geometric mean slowdown against the fastest backend of each benchmark: 1.00 is fastest\n");
+    out.push_str("everywhere, 1.25 a quarter slower on the typical benchmark. Raw results are in the tables\n");
+    out.push_str("above; a single score hides where a backend is fast or slow.\n\n");
+
+    out.push_str("| Rank | Backend | Overall |");
+    for tag in CATEGORIES {
+        let _ = write!(out, " {} |", capitalized(tag.name()));
+    }
+    out.push_str(" Fastest in | Benchmarks |\n|-----:|---------|--------:|");
+    out.push_str(&"------:|".repeat(CATEGORIES.len()));
+    out.push_str("-----------:|-----------:|\n");
+    for (rank, score) in scores.iter().enumerate() {
+        let _ = write!(out, "| {} | {} | {:.2} |", rank + 1, score.backend, score.overall);
+        for tag in CATEGORIES {
+            match score.category(tag) {
+                Some(slowdown) => {
+                    let _ = write!(out, " {slowdown:.2} |");
+                }
+                None => out.push_str(" - |"),
+            }
+        }
+        let _ = writeln!(out, " {} | {} |", score.wins, score.benchmarks);
+    }
+    out.push('\n');
+    out
+}
+
+fn capitalized(name: &str) -> String {
+    let mut chars = name.chars();
+    chars.next().map_or_else(String::new, |first| first.to_uppercase().chain(chars).collect())
+}
@@ -0,0 +1,366 @@
+//! Seeder configuration: how many users, posts and comments there are, how
+//! long generated post and comment bodies are, and how far back they were created
+//!
+//! The row counts follow a total scale (the number of users) and a
+//! users:posts:comments ratio. Half of the users have posts and four in five
+//! posts have comments, each spread round-robin, so varying the ratio varies
+//! how many rows a join fans out to while keeping every reference valid.
+//!
+//! Real text is mostly short with a long tail, so body lengths are drawn from
+//! a log-normal distribution around a median. The tail is what exercises
+//! TOAST (values over ~2 kB are compressed, and moved out of line if still too
+//! big) and large-value decoding in the read benchmarks.
+//!
+//! Production tables hold years of rows, most of them recent, and that is what
+//! time-range and `ORDER BY created_at` plans depend on, so `created_at` is
+//! spread over a window skewed towards now rather than one `NOW()`. `init.sql` reads the
+//! same `SEED_*` variables, so the seeded dataset, the in-memory backend and
+//! the rows written by benchmarks follow one configuration.
+//!
+//! Generated names and text can also be written in scripts other than Latin
+//! ([`TextScript`]); the seeded dataset itself stays Latin.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// Words generated text is made of, so it compresses like prose rather than a repeated sentence
+const WORDS: &[&str] = &[
+    "the", "of", "and", "to", "in", "is", "that", "for", "it", "as", "with", "on", "be", "this",
+    "by", "are", "from", "or", "an", "was", "but", "not", "can", "which", "query", "index",
+    "database", "table", "rows", "column", "server", "client", "connection", "latency", "cache",
+    "memory", "disk", "network", "rust", "async", "driver", "pool", "transaction", "commit",
+    "schema", "migration", "performance", "benchmark", "throughput", "request", "response",
+    "result", "value", "type", "error", "field", "record", "batch", "stream", "buffer", "page",
+    "plan", "scan", "join",
+];
+
+/// Writing system of generated names and text
+///
+/// Latin text is all single-byte, so it leaves out the UTF-8 decoding and
+/// case-folding costs that real multilingual data has. The other scripts
+/// exercise two-byte (Cyrillic), three-byte (CJK) and four-byte (emoji,
+/// including ZWJ and flag sequences) characters, and decomposed accents
+/// written as base letters followed by combining marks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextScript {
+    Latin,
+    Cyrillic,
+    Cjk,
+    Emoji,
+    Combining,
+}
+
+impl TextScript {
+    pub const ALL: [Self; 5] = [Self::Latin, Self::Cyrillic, Self::Cjk, Self::Emoji, Self::Combining];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Latin => "latin",
+            Self::Cyrillic => "cyrillic",
+            Self::Cjk => "cjk",
+            Self::Emoji => "emoji",
+            Self::Combining => "combining",
+        }
+    }
+
+    fn words(&self) -> &'static [&'static str] {
+        match self {
+            Self::Latin => WORDS,
+            Self::Cyrillic => &[
+                "и", "в", "не", "на", "что", "это", "с", "как", "база", "данных", "запрос", "индекс",
+                "таблица", "строка", "столбец", "сервер", "клиент", "соединение", "задержка", "кэш",
+                "память", "диск", "сеть", "пул", "транзакция", "схема", "производительность",
+            ],
+            Self::Cjk => &[
+                "数据库", "查询", "索引", "表", "行", "服务器", "客户端", "连接", "延迟", "缓存", "内存",
+                "データベース", "クエリ", "インデックス", "接続", "性能", "の", "は", "데이터베이스",
+                "쿼리", "인덱스", "연결", "성능", "그리고",
+            ],
+            Self::Emoji => &[
+                "🚀", "🐘", "🦀", "✨", "🔥", "📈", "📉", "👍🏽", "👨‍👩‍👧", "🇯🇵", "🏳️‍🌈", "❤️", "⏱️",
+                "so", "fast", "query", "ship", "it",
+            ],
+            Self::Combining => &[
+                "cafe\u{301}", "nai\u{308}ve", "re\u{301}sume\u{301}", "Zu\u{308}rich", "pin\u{303}a",
+                "fac\u{327}ade", "A\u{30a}ngstro\u{308}m", "cre\u{300}me", "bru\u{302}le\u{301}e",
+                "Vie\u{323}\u{302}t", "o\u{31b}\u{309}", "z\u{334}a\u{337}l\u{336}g\u{335}o\u{338}",
+                "the", "and", "query", "index",
+            ],
+        }
+    }
+
+    /// First and last names the generated users' names start with
+    fn names(&self) -> (&'static [&'static str], &'static [&'static str]) {
+        match self {
+            Self::Latin => (&["First"], &["Last"]),
+            Self::Cyrillic => (
+                &["Александр", "Мария", "Дмитрий", "Анна", "Сергей", "Ольга", "Иван", "Елена"],
+                &["Иванов", "Смирнова", "Кузнецов", "Попова", "Соколов", "Лебедева", "Новиков", "Морозова"],
+            ),
+            Self::Cjk => (
+                &["伟", "芳", "翔太", "さくら", "민준", "서연", "秀英", "陽菜"],
+                &["王", "李", "佐藤", "鈴木", "김", "이", "张", "高橋"],
+            ),
+            Self::Emoji => (
+                &["🌸 Ana", "Leo 🦁", "Mia ✨", "Kai 🌊", "Zoe 🦋", "Sam 🔥", "Ivy 🌿", "Max 🚀"],
+                &["Rivers 🏞️", "Stone 🪨", "Fox 🦊", "Bloom 🌺", "Frost ❄️", "Reed 🎋", "Moon 🌙", "Sky ☁️"],
+            ),
+            Self::Combining => (
+                &["Zoe\u{308}", "Jose\u{301}", "Rene\u{301}e", "Noe\u{308}l", "Mari\u{301}a", "Franc\u{327}ois", "Bjo\u{308}rn", "Ana\u{303}"],
+                &["Mu\u{308}ller", "Nu\u{301}n\u{303}ez", "Dvor\u{30c}a\u{301}k", "Nguye\u{302}\u{303}n", "S\u{30c}imic\u{301}", "Garci\u{301}a", "Ha\u{30a}kansson", "Lo\u{301}pez"],
+            ),
+        }
+    }
+
+    /// First and last name of the `index`th user, distinct for every index
+    pub fn name_of(&self, index: usize) -> (String, String) {
+        let (first, last) = self.names();
+        (
+            format!("{}{index}", first[index % first.len()]),
+            format!("{}{index}", last[index / first.len() % last.len()]),
+        )
+    }
+
+    /// A name fragment that most generated users of this script contain, for
+    /// name searches; exact-case, since with `C` ctype `ILIKE` only folds ASCII
+    pub fn search_fragment(&self) -> &'static str {
+        self.names().0[0]
+    }
+}
+
+impl FromStr for TextScript {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|script| script.name().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| format!("unknown text script {s:?}"))
+    }
+}
+
+/// Log-normal distribution of generated text lengths, in characters
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContentLengths {
+    /// Half of all bodies are shorter than this
+    pub median: usize,
+    /// Spread of the underlying normal distribution; 0 makes every body `median` long
+    pub sigma: f64,
+    /// Upper bound cutting off the tail
+    pub max: usize,
+}
+
+impl ContentLengths {
+    /// Post bodies: a few hundred characters, about 1% long enough to be stored out of line
+    pub const POSTS: Self = Self { median: 400, sigma: 1.0, max: 64 * 1024 };
+    /// Comment bodies: a sentence or two
+    pub const COMMENTS: Self = Self { median: 120, sigma: 1.0, max: 16 * 1024 };
+
+    /// Length of the `index`th body; the same index always gets the same length
+    pub fn length(&self, index: usize) -> usize {
+        self.sample(&mut StdRng::seed_from_u64(index as u64))
+    }
+
+    fn sample(&self, rng: &mut StdRng) -> usize {
+        // Box-Muller; 1 - u keeps ln away from 0
+        let (u1, u2): (f64, f64) = (rng.gen(), rng.gen());
+        let normal = (-2.0 * (1.0 - u1).ln()).sqrt() * (std::f64::consts::TAU * u2).cos();
+        let length = (self.median as f64).ln() + self.sigma * normal;
+        (length.exp().round() as usize).clamp(1, self.max.max(1))
+    }
+
+    /// `prefix` followed by words up to the `index`th length, or `prefix` alone if that is longer
+    pub fn text(&self, prefix: &str, index: usize) -> String {
+        self.text_in(TextScript::Latin, prefix, index)
+    }
+
+    /// [`ContentLengths::text`] with words in `script`, the length counted in characters
+    pub fn text_in(&self, script: TextScript, prefix: &str, index: usize) -> String {
+        let words = script.words();
+        let mut rng = StdRng::seed_from_u64(index as u64);
+        let length = self.sample(&mut rng);
+        let mut text = String::with_capacity(length.max(prefix.len()) + 16);
+        text.push_str(prefix);
+        let mut chars = prefix.chars().count();
+        while chars < length {
+            let word = words[rng.gen_range(0..words.len())];
+            text.push(' ');
+            text.push_str(word);
+            chars += 1 + word.chars().count();
+        }
+        if let Some((end, _)) = text.char_indices().nth(length.max(prefix.chars().count())) {
+            text.truncate(end);
+        }
+        text
+    }
+}
+
+/// Relative numbers of users, posts and comments, written `users:posts:comments`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeedRatio {
+    pub users: usize,
+    pub posts: usize,
+    pub comments: usize,
+}
+
+impl SeedRatio {
+    /// `init.sql`'s original shape: 10,000 users, 25,000 posts, 80,000 comments
+    pub const DEFAULT: Self = Self { users: 2, posts: 5, comments: 16 };
+}
+
+impl FromStr for SeedRatio {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<usize> = s
+            .split(':')
+            .map(|part| part.trim().parse().map_err(|_| format!("invalid seed ratio {s:?}")))
+            .collect::<Result<_, _>>()?;
+        match parts[..] {
+            [users, posts, comments] if users > 0 => Ok(Self { users, posts, comments }),
+            _ => Err(format!("seed ratio {s:?} is not users:posts:comments with at least one user")),
+        }
+    }
+}
+
+impl fmt::Display for SeedRatio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.users, self.posts, self.comments)
+    }
+}
+
+/// How far back seeded rows' `created_at` goes
+///
+/// A row's age is `u^skew` of the way back through its window, for uniform
+/// `u`: a skew of 1 spreads rows evenly, and larger skews put more of them
+/// near the present, the way a growing service's tables look. Users spread
+/// over the whole window, their posts between the user's creation and now,
+/// and comments between the post's creation and now.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct History {
+    /// Length of the window in days; 0 creates every row at the same instant
+    pub days: f64,
+    pub skew: f64,
+}
+
+impl History {
+    /// Three years, half of the users from the last nine months
+    pub const DEFAULT: Self = Self { days: 3.0 * 365.0, skew: 2.0 };
+
+    /// A random age within `window`, skewed towards zero
+    pub fn age(&self, rng: &mut impl Rng, window: Duration) -> Duration {
+        window.mul_f64(rng.gen::<f64>().powf(self.skew))
+    }
+
+    /// The whole window
+    pub fn window(&self) -> Duration {
+        Duration::from_secs_f64(self.days.max(0.0) * 86_400.0)
+    }
+}
+
+/// Settings for generated data
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SeedConfig {
+    /// Total scale: seeded users, which the other counts follow through `ratio`
+    pub users: usize,
+    pub ratio: SeedRatio,
+    pub post_content: ContentLengths,
+    pub comment_content: ContentLengths,
+    pub history: History,
+}
+
+impl Default for SeedConfig {
+    fn default() -> Self {
+        Self {
+            users: 10_000,
+            ratio: SeedRatio::DEFAULT,
+            post_content: ContentLengths::POSTS,
+            comment_content: ContentLengths::COMMENTS,
+            history: History::DEFAULT,
+        }
+    }
+}
+
+impl SeedConfig {
+    /// Defaults overridden by `SEED_USERS`, `SEED_RATIO`, `SEED_POST_CONTENT_MEDIAN`,
+    /// `SEED_COMMENT_CONTENT_MEDIAN`, `SEED_CONTENT_SIGMA`, `SEED_CONTENT_MAX`,
+    /// `SEED_HISTORY_DAYS` and `SEED_HISTORY_SKEW`, the variables `init.sql` reads;
+    /// with a [`crate::profile`] selected, its scale replaces the default `users`
+    pub fn from_env() -> Self {
+        fn var<T: FromStr>(name: &str) -> Option<T> {
+            std::env::var(name).ok()?.trim().parse().ok()
+        }
+
+        let mut config = Self::default();
+        if let Some(profile) = crate::profile::selected() {
+            config.users = profile.users;
+        }
+        if let Some(users) = var("SEED_USERS") {
+            config.users = users;
+        }
+        if let Some(ratio) = var("SEED_RATIO") {
+            config.ratio = ratio;
+        }
+        if let Some(median) = var("SEED_POST_CONTENT_MEDIAN") {
+            config.post_content.median = median;
+        }
+        if let Some(median) = var("SEED_COMMENT_CONTENT_MEDIAN") {
+            config.comment_content.median = median;
+        }
+        for lengths in [&mut config.post_content, &mut config.comment_content] {
+            if let Some(sigma) = var::<f64>("SEED_CONTENT_SIGMA") {
+                lengths.sigma = sigma.max(0.0);
+            }
+            if let Some(max) = var("SEED_CONTENT_MAX") {
+                lengths.max = max;
+            }
+        }
+        if let Some(days) = var::<f64>("SEED_HISTORY_DAYS") {
+            config.history.days = days.max(0.0);
+        }
+        if let Some(skew) = var::<f64>("SEED_HISTORY_SKEW") {
+            config.history.skew = skew.max(0.0);
+        }
+        config
+    }
+
+    /// [`SeedConfig::from_env`], read once per process
+    pub fn get() -> &'static Self {
+        static CONFIG: OnceLock<SeedConfig> = OnceLock::new();
+        CONFIG.get_or_init(Self::from_env)
+    }
+
+    /// Seeded posts
+    pub fn posts(&self) -> usize {
+        self.users * self.ratio.posts / self.ratio.users
+    }
+
+    /// Seeded comments
+    pub fn comments(&self) -> usize {
+        self.users * self.ratio.comments / self.ratio.users
+    }
+
+    /// Users the posts are spread over: half of them, at least one
+    pub fn posting_users(&self) -> usize {
+        (self.users / 2).max(1).min(self.users)
+    }
+
+    /// Posts the comments are spread over: four in five, at least one
+    pub fn commented_posts(&self) -> usize {
+        (self.posts() * 4 / 5).max(1).min(self.posts())
+    }
+}
+
+/// How `total` rows spread round-robin over `over` parents: the smallest and
+/// largest number any parent gets, and how many parents get the larger one
+pub fn round_robin(total: usize, over: usize) -> (usize, usize, usize) {
+    if over == 0 {
+        return (0, 0, 0);
+    }
+    let (per_parent, remainder) = (total / over, total % over);
+    (per_parent, per_parent + usize::from(remainder > 0), remainder)
+}
@@ -0,0 +1,585 @@
+//! Seeder that tops up an existing database to the configured dataset
+//!
+//! Every seeded row has a key: users are `user_<n>`, posts are numbered per
+//! user through their title, comments and tag links are counted per post, and
+//! tags are named. [`top_up`] reads which keys are present and inserts only the
+//! missing rows, so running it against a database already seeded by `init.sql` inserts
+//! nothing, and against a larger [`SeedConfig`] inserts just the difference.
+//! Rows are only ever added: shrinking the dataset needs a fresh database.
+//!
+//! The rows are generated here rather than in SQL and streamed with binary
+//! `COPY` over several connections. Index and foreign key maintenance then
+//! runs in as many backends as there are streams instead of in the one
+//! `init.sql` uses, which is what makes datasets of millions of rows practical.
+
+use crate::bench_tokio_postgres::TokioPostgresBench;
+use crate::seed::{round_robin, SeedConfig};
+use chrono::{DateTime, TimeDelta, Utc};
+use futures::{future, pin_mut};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use tokio_postgres::binary_copy::BinaryCopyInWriter;
+use tokio_postgres::types::{ToSql, Type};
+use tokio_postgres::Client;
+use uuid::Uuid;
+
+const FIRST_NAMES: [&str; 20] = [
+    "James", "Mary", "John", "Patricia", "Robert", "Jennifer", "Michael", "Linda", "William", "Elizabeth",
+    "David", "Barbara", "Richard", "Susan", "Joseph", "Jessica", "Thomas", "Sarah", "Charles", "Karen",
+];
+
+const LAST_NAMES: [&str; 15] = [
+    "Smith", "Johnson", "Williams", "Brown", "Jones", "Garcia", "Miller", "Davis", "Rodriguez",
+    "Martinez", "Hernandez", "Lopez", "Gonzalez", "Wilson", "Anderson",
+];
+
+/// Seeded user `number` (1-based), as `init.sql` generates it:
+/// (username, email, first name, last name, age)
+pub fn user(number: usize) -> (String, String, String, String, i32) {
+    (
+        format!("user_{number}"),
+        format!("user_{number}@example.com"),
+        format!("{}{number}", FIRST_NAMES[number % FIRST_NAMES.len()]),
+        format!("{}{}", LAST_NAMES[number % LAST_NAMES.len()], number / 100),
+        (18 + number % 62) as i32,
+    )
+}
+
+/// Subjects of the tag taxonomy, roughly from most to least popular
+const TAG_SUBJECTS: [&str; 60] = [
+    "rust", "postgresql", "python", "javascript", "databases", "performance", "linux", "docker",
+    "typescript", "go", "async", "security", "kubernetes", "sql", "testing", "architecture",
+    "java", "aws", "react", "devops", "redis", "concurrency", "networking", "git", "nodejs",
+    "observability", "kafka", "graphql", "mysql", "sqlite", "web", "wasm", "compilers", "embedded",
+    "cryptography", "machine-learning", "data-engineering", "cpp", "csharp", "kotlin", "swift",
+    "ruby", "php", "elixir", "haskell", "scala", "zig", "terraform", "ansible", "nginx", "mongodb",
+    "elasticsearch", "grpc", "rest", "websockets", "vue", "svelte", "deno", "azure", "gcp",
+];
+
+/// Each subject on its own, then narrowed down
+const TAG_QUALIFIERS: [&str; 5] = ["", "-tips", "-internals", "-tutorial", "-news"];
+
+/// Tags in the seeded dataset, every subject with every qualifier
+pub const TAGS: usize = TAG_SUBJECTS.len() * TAG_QUALIFIERS.len();
+
+/// Name of seeded tag `number` (1-based), which is also its popularity rank
+pub fn tag_name(number: usize) -> String {
+    let index = number - 1;
+    format!(
+        "{}{}",
+        TAG_SUBJECTS[index % TAG_SUBJECTS.len()],
+        TAG_QUALIFIERS[index / TAG_SUBJECTS.len()]
+    )
+}
+
+/// Number of tags on the `index`th seeded post (0-based): 0 to 5, with 2 the
+/// most common. The index picks one of 20 equally likely buckets, so the
+/// counts are the same in `init.sql` and can be checked exactly.
+pub fn tags_per_post(index: usize) -> usize {
+    match index * 7 % 20 {
+        0..=1 => 0,
+        2..=5 => 1,
+        6..=10 => 2,
+        11..=14 => 3,
+        15..=17 => 4,
+        _ => 5,
+    }
+}
+
+/// Zipf distribution over the seeded tags: tag `n` is picked with weight
+/// `1 / n`, so a few tags are on most posts and most tags on a few
+#[derive(Debug, Clone)]
+pub struct TagPopularity {
+    cumulative: Vec<f64>,
+}
+
+impl Default for TagPopularity {
+    fn default() -> Self {
+        let mut total = 0.0;
+        let cumulative = (1..=TAGS)
+            .map(|rank| {
+                total += 1.0 / rank as f64;
+                total
+            })
+            .collect();
+        Self { cumulative }
+    }
+}
+
+impl TagPopularity {
+    /// Up to `count` distinct tag numbers, none of them already in `taken`
+    pub fn sample(&self, rng: &mut impl Rng, count: usize, taken: &[usize]) -> Vec<usize> {
+        let count = count.min(TAGS - taken.len());
+        let total = self.cumulative[TAGS - 1];
+        let mut tags = Vec::with_capacity(count);
+        // Drawing again on a repeat is weighted sampling without replacement
+        while tags.len() < count {
+            let target = rng.gen::<f64>() * total;
+            let tag = self.cumulative.partition_point(|&weight| weight <= target).min(TAGS - 1) + 1;
+            if !taken.contains(&tag) && !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+        tags
+    }
+}
+
+/// Title of the `post_num`th post (1-based) of seeded user `user_number`
+pub fn post_title(post_num: usize, user_number: usize) -> String {
+    format!("Post Title {post_num} by user_{user_number}")
+}
+
+/// Status of a user's `post_num`th post: one in ten archived, one in ten draft
+pub fn post_status(post_num: usize) -> &'static str {
+    match post_num % 10 {
+        0 => "draft",
+        1 => "archived",
+        _ => "published",
+    }
+}
+
+/// Where the `index`th seeded post (0-based) goes: the posting user's number
+/// and the post's number for that user, both 1-based
+pub fn post_position(index: usize, config: &SeedConfig) -> (usize, usize) {
+    let posting_users = config.posting_users();
+    (index % posting_users + 1, index / posting_users + 1)
+}
+
+/// A seeded user found in the database or just inserted
+#[derive(Debug, Clone, Copy)]
+struct SeededUser {
+    id: Uuid,
+    created_at: DateTime<Utc>,
+}
+
+/// A seeded post found in the database or just inserted
+#[derive(Debug, Clone)]
+struct SeededPost {
+    id: Uuid,
+    created_at: DateTime<Utc>,
+    comments: usize,
+    tags: Vec<Uuid>,
+}
+
+/// A `created_at` between `since` and `now`, skewed towards `now` by [`History`]
+///
+/// [`History`]: crate::seed::History
+fn created_between(config: &SeedConfig, rng: &mut impl Rng, now: DateTime<Utc>, since: DateTime<Utc>) -> DateTime<Utc> {
+    let window = (now - since).to_std().unwrap_or_default();
+    now - TimeDelta::from_std(config.history.age(rng, window)).unwrap_or_default()
+}
+
+/// Rows per table, inserted by a top-up or present in the database
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeedRows {
+    pub users: u64,
+    pub posts: u64,
+    pub comments: u64,
+    pub tags: u64,
+    pub post_tags: u64,
+}
+
+impl SeedRows {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl fmt::Display for SeedRows {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} users, {} posts, {} comments, {} tags, {} post tags",
+            self.users, self.posts, self.comments, self.tags, self.post_tags
+        )
+    }
+}
+
+/// COPY streams the seeder writes over at once: `SEED_COPY_STREAMS`, or one
+/// per CPU up to 8
+pub fn copy_streams() -> usize {
+    std::env::var("SEED_COPY_STREAMS")
+        .ok()
+        .and_then(|streams| streams.trim().parse().ok())
+        .unwrap_or_else(|| std::thread::available_parallelism().map_or(4, |cpus| cpus.get().min(8)))
+        .max(1)
+}
+
+/// Insert whatever rows are missing for the dataset `config` describes into
+/// the database at `url`, and return how many went into each table
+///
+/// Users, posts and comments are written with binary `COPY` over
+/// [`copy_streams`] connections at once, each generating its share of the
+/// rows, so a dataset of millions of rows seeds in seconds.
+pub async fn top_up(url: &str, config: &SeedConfig) -> Result<SeedRows, tokio_postgres::Error> {
+    let client = TokioPostgresBench::connect_to(url).await?;
+    let streams = copy_streams();
+    let mut inserted = SeedRows {
+        tags: top_up_tags(&client).await?,
+        ..SeedRows::default()
+    };
+
+    let now = Utc::now();
+    let mut users = seeded_users(&client).await?;
+    inserted.users = top_up_users(url, streams, config, now, &mut users).await?;
+
+    let mut posts = seeded_posts(&client).await?;
+    inserted.posts = top_up_posts(url, streams, config, now, &users, &mut posts).await?;
+    inserted.comments = top_up_comments(url, streams, config, now, &users, &posts).await?;
+    inserted.post_tags = top_up_post_tags(&client, url, streams, config, &posts).await?;
+
+    if !inserted.is_empty() {
+        client.batch_execute("ANALYZE users, posts, comments, tags, post_tags").await?;
+    }
+    Ok(inserted)
+}
+
+/// Split `items` over up to `streams` connections to `url`, each running
+/// `copy` (a `COPY ... FROM STDIN BINARY` with columns of `types`) and writing
+/// the values `row` generates for its items
+async fn copy_in<T, F>(
+    url: &str,
+    streams: usize,
+    copy: &'static str,
+    types: &'static [Type],
+    items: Vec<T>,
+    row: F,
+) -> Result<u64, tokio_postgres::Error>
+where
+    T: Send + 'static,
+    F: Fn(T) -> Vec<Box<dyn ToSql + Sync + Send>> + Send + Sync + 'static,
+{
+    if items.is_empty() {
+        return Ok(0);
+    }
+    let per_stream = items.len().div_ceil(streams);
+    let row = Arc::new(row);
+    let mut items = items.into_iter();
+    let mut tasks = Vec::with_capacity(streams);
+    loop {
+        let share: Vec<T> = items.by_ref().take(per_stream).collect();
+        if share.is_empty() {
+            break;
+        }
+        let (url, row) = (url.to_string(), Arc::clone(&row));
+        tasks.push(tokio::spawn(async move {
+            let client = TokioPostgresBench::connect_to(&url).await?;
+            let sink = client.copy_in(copy).await?;
+            let writer = BinaryCopyInWriter::new(sink, types);
+            pin_mut!(writer);
+            for item in share {
+                writer.as_mut().write_raw(row(item)).await?;
+            }
+            writer.finish().await
+        }));
+    }
+
+    let mut copied = 0;
+    for result in future::join_all(tasks).await {
+        copied += result.unwrap_or_else(|panic| std::panic::resume_unwind(panic.into_panic()))?;
+    }
+    Ok(copied)
+}
+
+async fn top_up_tags(client: &Client) -> Result<u64, tokio_postgres::Error> {
+    let names: Vec<String> = (1..=TAGS).map(tag_name).collect();
+    client
+        .execute(
+            "INSERT INTO tags (name, color)
+             SELECT name, '#' || lpad(to_hex(floor(random() * 16777215)::integer), 6, '0')
+             FROM UNNEST($1::text[]) name
+             ON CONFLICT (name) DO NOTHING",
+            &[&names],
+        )
+        .await
+}
+
+/// Seeded users by number
+async fn seeded_users(client: &Client) -> Result<HashMap<usize, SeededUser>, tokio_postgres::Error> {
+    let rows = client
+        .query(
+            "SELECT id, substr(username, 6)::bigint, coalesce(created_at, NOW())
+             FROM users WHERE username ~ '^user_[0-9]+$'",
+            &[],
+        )
+        .await?;
+    Ok(rows
+        .iter()
+        .map(|row| {
+            let user = SeededUser {
+                id: row.get(0),
+                created_at: row.get(2),
+            };
+            (row.get::<_, i64>(1) as usize, user)
+        })
+        .collect())
+}
+
+async fn top_up_users(
+    url: &str,
+    streams: usize,
+    config: &SeedConfig,
+    now: DateTime<Utc>,
+    users: &mut HashMap<usize, SeededUser>,
+) -> Result<u64, tokio_postgres::Error> {
+    let since = now - TimeDelta::from_std(config.history.window()).unwrap_or_default();
+    let mut rng = rand::thread_rng();
+    let missing: Vec<(SeededUser, usize)> = (1..=config.users)
+        .filter(|n| !users.contains_key(n))
+        .map(|number| {
+            let user = SeededUser {
+                id: Uuid::new_v4(),
+                created_at: created_between(config, &mut rng, now, since),
+            };
+            (user, number)
+        })
+        .collect();
+    users.extend(missing.iter().map(|&(user, number)| (number, user)));
+
+    copy_in(
+        url,
+        streams,
+        "COPY users (id, username, email, first_name, last_name, age, created_at, updated_at) FROM STDIN BINARY",
+        &[
+            Type::UUID,
+            Type::VARCHAR,
+            Type::VARCHAR,
+            Type::VARCHAR,
+            Type::VARCHAR,
+            Type::INT4,
+            Type::TIMESTAMPTZ,
+            Type::TIMESTAMPTZ,
+        ],
+        missing,
+        |(seeded, number)| {
+            let (username, email, first_name, last_name, age) = user(number);
+            vec![
+                Box::new(seeded.id),
+                Box::new(username),
+                Box::new(email),
+                Box::new(first_name),
+                Box::new(last_name),
+                Box::new(age),
+                Box::new(seeded.created_at),
+                Box::new(seeded.created_at),
+            ]
+        },
+    )
+    .await
+}
+
+/// Seeded posts by (user number, post number)
+async fn seeded_posts(client: &Client) -> Result<HashMap<(usize, usize), SeededPost>, tokio_postgres::Error> {
+    let rows = client
+        .query(
+            "SELECT p.id, substr(u.username, 6)::bigint, p.title,
+                    (SELECT COUNT(*) FROM comments c WHERE c.post_id = p.id),
+                    ARRAY(SELECT tag_id FROM post_tags pt WHERE pt.post_id = p.id),
+                    coalesce(p.created_at, NOW())
+             FROM posts p
+             JOIN users u ON u.id = p.user_id
+             WHERE u.username ~ '^user_[0-9]+$' AND p.title LIKE 'Post Title % by user\\_%'",
+            &[],
+        )
+        .await?;
+    Ok(rows
+        .iter()
+        .filter_map(|row| {
+            let title: &str = row.get(2);
+            let post_num = title.strip_prefix("Post Title ")?.split(' ').next()?.parse().ok()?;
+            let key = (row.get::<_, i64>(1) as usize, post_num);
+            let post = SeededPost {
+                id: row.get(0),
+                created_at: row.get(5),
+                comments: row.get::<_, i64>(3) as usize,
+                tags: row.get(4),
+            };
+            Some((key, post))
+        })
+        .collect())
+}
+
+async fn top_up_posts(
+    url: &str,
+    streams: usize,
+    config: &SeedConfig,
+    now: DateTime<Utc>,
+    users: &HashMap<usize, SeededUser>,
+    posts: &mut HashMap<(usize, usize), SeededPost>,
+) -> Result<u64, tokio_postgres::Error> {
+    let mut rng = rand::thread_rng();
+    // (id, author, created at, index) of every missing post
+    let mut missing = Vec::new();
+    for index in 0..config.posts() {
+        let position = post_position(index, config);
+        if let Entry::Vacant(entry) = posts.entry(position) {
+            let author = users[&position.0];
+            let post = entry.insert(SeededPost {
+                id: Uuid::new_v4(),
+                created_at: created_between(config, &mut rng, now, author.created_at),
+                comments: 0,
+                tags: Vec::new(),
+            });
+            missing.push((post.id, author.id, post.created_at, index));
+        }
+    }
+
+    let config = *config;
+    copy_in(
+        url,
+        streams,
+        "COPY posts (id, user_id, title, content, status, view_count, created_at, updated_at) FROM STDIN BINARY",
+        &[
+            Type::UUID,
+            Type::UUID,
+            Type::VARCHAR,
+            Type::TEXT,
+            Type::VARCHAR,
+            Type::INT4,
+            Type::TIMESTAMPTZ,
+            Type::TIMESTAMPTZ,
+        ],
+        missing,
+        move |(id, user_id, created_at, index)| {
+            let (user_number, post_num) = post_position(index, &config);
+            let content = config
+                .post_content
+                .text(&format!("This is the content for post number {post_num}."), index);
+            vec![
+                Box::new(id),
+                Box::new(user_id),
+                Box::new(post_title(post_num, user_number)),
+                Box::new(content),
+                Box::new(post_status(post_num)),
+                Box::new(rand::thread_rng().gen_range(0..=10_000i32)),
+                Box::new(created_at),
+                Box::new(created_at),
+            ]
+        },
+    )
+    .await
+}
+
+async fn top_up_comments(
+    url: &str,
+    streams: usize,
+    config: &SeedConfig,
+    now: DateTime<Utc>,
+    users: &HashMap<usize, SeededUser>,
+    posts: &HashMap<(usize, usize), SeededPost>,
+) -> Result<u64, tokio_postgres::Error> {
+    let commented_posts = config.commented_posts();
+    let (fewest, _, with_most) = round_robin(config.comments(), commented_posts);
+    let commenters: Vec<Uuid> = users.values().map(|user| user.id).collect();
+    let mut rng = rand::thread_rng();
+    // (post, commenter, created at, comment number, index) of every missing comment
+    let mut missing = Vec::new();
+    for index in 0..commented_posts {
+        let target = fewest + usize::from(index < with_most);
+        let post = &posts[&post_position(index, config)];
+        for comment_num in post.comments + 1..=target {
+            let commenter = commenters[rng.gen_range(0..commenters.len())];
+            let created_at = created_between(config, &mut rng, now, post.created_at);
+            missing.push((post.id, commenter, created_at, comment_num, (comment_num - 1) * commented_posts + index));
+        }
+    }
+
+    let config = *config;
+    copy_in(
+        url,
+        streams,
+        "COPY comments (id, post_id, user_id, content, created_at) FROM STDIN BINARY",
+        &[Type::UUID, Type::UUID, Type::UUID, Type::TEXT, Type::TIMESTAMPTZ],
+        missing,
+        move |(post_id, user_id, created_at, comment_num, index)| {
+            let content = config
+                .comment_content
+                .text(&format!("This is comment {comment_num} on this post."), index);
+            vec![
+                Box::new(Uuid::new_v4()),
+                Box::new(post_id),
+                Box::new(user_id),
+                Box::new(content),
+                Box::new(created_at),
+            ]
+        },
+    )
+    .await
+}
+
+/// Give every seeded post its [`tags_per_post`], adding tags drawn by
+/// [`TagPopularity`] to posts that have fewer
+async fn top_up_post_tags(
+    client: &Client,
+    url: &str,
+    streams: usize,
+    config: &SeedConfig,
+    posts: &HashMap<(usize, usize), SeededPost>,
+) -> Result<u64, tokio_postgres::Error> {
+    let numbers: HashMap<String, usize> = (1..=TAGS).map(|number| (tag_name(number), number)).collect();
+    let mut tags = HashMap::new();
+    for row in client.query("SELECT id, name FROM tags", &[]).await? {
+        if let Some(&number) = numbers.get(row.get::<_, &str>(1)) {
+            tags.insert(row.get::<_, Uuid>(0), number);
+        }
+    }
+    let ids: HashMap<usize, Uuid> = tags.iter().map(|(&id, &number)| (number, id)).collect();
+
+    let popularity = TagPopularity::default();
+    let mut rng = rand::thread_rng();
+    let mut missing = Vec::new();
+    for index in 0..config.posts() {
+        let post = &posts[&post_position(index, config)];
+        let wanted = tags_per_post(index).saturating_sub(post.tags.len());
+        if wanted == 0 {
+            continue;
+        }
+        let taken: Vec<usize> = post.tags.iter().filter_map(|id| tags.get(id).copied()).collect();
+        missing.extend(
+            popularity
+                .sample(&mut rng, wanted, &taken)
+                .into_iter()
+                .map(|number| (post.id, ids[&number])),
+        );
+    }
+
+    copy_in(
+        url,
+        streams,
+        "COPY post_tags (post_id, tag_id) FROM STDIN BINARY",
+        &[Type::UUID, Type::UUID],
+        missing,
+        |(post_id, tag_id)| vec![Box::new(post_id), Box::new(tag_id)],
+    )
+    .await
+}
+
+/// Rows currently in the seeded dataset, leaving out the benchmarks' `bench_user_*` rows
+pub async fn count(client: &Client) -> Result<SeedRows, tokio_postgres::Error> {
+    let row = client
+        .query_one(
+            "WITH seeded_users AS (
+                 SELECT id FROM users WHERE username NOT LIKE 'bench_user_%'
+             ), seeded_posts AS (
+                 SELECT id FROM posts WHERE user_id IN (SELECT id FROM seeded_users)
+             )
+             SELECT (SELECT COUNT(*) FROM seeded_users),
+                    (SELECT COUNT(*) FROM seeded_posts),
+                    (SELECT COUNT(*) FROM comments WHERE post_id IN (SELECT id FROM seeded_posts)),
+                    (SELECT COUNT(*) FROM tags),
+                    (SELECT COUNT(*) FROM post_tags WHERE post_id IN (SELECT id FROM seeded_posts))",
+            &[],
+        )
+        .await?;
+    Ok(SeedRows {
+        users: row.get::<_, i64>(0) as u64,
+        posts: row.get::<_, i64>(1) as u64,
+        comments: row.get::<_, i64>(2) as u64,
+        tags: row.get::<_, i64>(3) as u64,
+        post_tags: row.get::<_, i64>(4) as u64,
+    })
+}
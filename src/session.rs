@@ -0,0 +1,92 @@
+//! Session configuration
+//!
+//! Server settings every backend's sessions start with. They travel in the
+//! startup packet (`application_name`, and `options=-c name=value` for the
+//! rest) through the connection URL, which every driver parses, so no backend
+//! pays for init SQL or an extra round trip.
+
+use crate::with_param;
+
+/// `application_name` of every benchmark session unless `BENCH_APPLICATION_NAME` is set
+pub const DEFAULT_APPLICATION_NAME: &str = "pg_benchmark";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionConfig {
+    pub application_name: String,
+    /// `statement_timeout` in milliseconds; the server's default when unset
+    pub statement_timeout_ms: Option<u64>,
+    /// `work_mem` in any form PostgreSQL accepts (`64kB`, `16MB`); the server's default when unset
+    pub work_mem: Option<String>,
+    /// `search_path`, comma-separated without spaces; the server's default when unset
+    pub search_path: Option<String>,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            application_name: DEFAULT_APPLICATION_NAME.to_string(),
+            statement_timeout_ms: None,
+            work_mem: None,
+            search_path: None,
+        }
+    }
+}
+
+impl SessionConfig {
+    /// Read `BENCH_APPLICATION_NAME`, `BENCH_STATEMENT_TIMEOUT_MS` and `BENCH_WORK_MEM`.
+    ///
+    /// # Panics
+    ///
+    /// If `BENCH_STATEMENT_TIMEOUT_MS` is not a number.
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            application_name: std::env::var("BENCH_APPLICATION_NAME").unwrap_or(defaults.application_name),
+            statement_timeout_ms: std::env::var("BENCH_STATEMENT_TIMEOUT_MS").ok().map(|ms| {
+                ms.parse()
+                    .expect("BENCH_STATEMENT_TIMEOUT_MS must be a number of milliseconds")
+            }),
+            work_mem: std::env::var("BENCH_WORK_MEM").ok(),
+            search_path: None,
+        }
+    }
+
+    pub fn with_work_mem(mut self, work_mem: &str) -> Self {
+        self.work_mem = Some(work_mem.to_string());
+        self
+    }
+
+    pub fn with_search_path(mut self, search_path: &str) -> Self {
+        self.search_path = Some(search_path.to_string());
+        self
+    }
+
+    pub fn with_statement_timeout_ms(mut self, statement_timeout_ms: u64) -> Self {
+        self.statement_timeout_ms = Some(statement_timeout_ms);
+        self
+    }
+
+    /// The `options` startup parameter, or `None` if every setting keeps the server's default
+    pub fn options(&self) -> Option<String> {
+        let mut settings = Vec::new();
+        if let Some(ms) = self.statement_timeout_ms {
+            settings.push(format!("-c statement_timeout={}", ms));
+        }
+        if let Some(work_mem) = &self.work_mem {
+            settings.push(format!("-c work_mem={}", work_mem));
+        }
+        if let Some(search_path) = &self.search_path {
+            settings.push(format!("-c search_path={}", search_path));
+        }
+        (!settings.is_empty()).then(|| settings.join(" "))
+    }
+
+    /// `url` with this session's parameters, replacing any it already sets
+    pub fn apply(&self, url: &str) -> String {
+        let url = with_param(url, "application_name", &self.application_name);
+        match self.options() {
+            Some(options) => with_param(&url, "options", &options),
+            None => url,
+        }
+    }
+}
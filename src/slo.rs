@@ -0,0 +1,101 @@
+//! Latency and throughput objectives checked after a load-test run
+//!
+//! A runner configuration can set objectives per scenario, e.g.
+//!
+//! ```toml
+//! [slo.select_user_by_id]
+//! p99_ms = 5.0
+//! min_throughput = 1000.0
+//! ```
+//!
+//! and `bench-runner` checks every result of that scenario against them once
+//! all runs are done, lists the violations and exits with an error if there
+//! are any, so a library upgrade that makes a backend slower fails CI.
+
+use crate::runner::RunResult;
+use serde::Deserialize;
+use std::fmt;
+
+/// Objectives for one scenario; each is checked only if set
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Slo {
+    pub p50_ms: Option<f64>,
+    pub p95_ms: Option<f64>,
+    pub p99_ms: Option<f64>,
+    pub max_ms: Option<f64>,
+    /// Successful operations per second
+    pub min_throughput: Option<f64>,
+    /// Failed operations allowed
+    pub max_errors: Option<u64>,
+}
+
+impl Slo {
+    /// Reject objectives no result could meet or that are not numbers
+    pub fn validate(&self) -> Result<(), String> {
+        let limits = [self.p50_ms, self.p95_ms, self.p99_ms, self.max_ms, self.min_throughput];
+        if limits.into_iter().flatten().any(|limit| !limit.is_finite() || limit <= 0.0) {
+            return Err("objectives must be positive numbers".to_string());
+        }
+        Ok(())
+    }
+
+    /// The objectives `result` misses
+    pub fn check(&self, result: &RunResult) -> Vec<Violation> {
+        let latencies = [
+            ("p50", self.p50_ms, result.p50_us),
+            ("p95", self.p95_ms, result.p95_us),
+            ("p99", self.p99_ms, result.p99_us),
+            ("max", self.max_ms, result.max_us),
+        ];
+        let mut violations: Vec<Violation> = latencies
+            .into_iter()
+            .filter_map(|(metric, limit, actual_us)| {
+                let limit = limit?;
+                (actual_us / 1000.0 > limit).then(|| Violation::new(result, metric, limit, actual_us / 1000.0))
+            })
+            .collect();
+        if let Some(limit) = self.min_throughput.filter(|limit| result.throughput < *limit) {
+            violations.push(Violation::new(result, "throughput", limit, result.throughput));
+        }
+        if let Some(limit) = self.max_errors.filter(|limit| result.errors > *limit) {
+            violations.push(Violation::new(result, "errors", limit as f64, result.errors as f64));
+        }
+        violations
+    }
+}
+
+/// A result that missed an objective
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    pub scenario: &'static str,
+    pub backend: &'static str,
+    /// `p50`, `p95`, `p99`, `max`, `throughput` or `errors`
+    pub metric: &'static str,
+    /// Milliseconds for latencies, operations per second or a count otherwise
+    pub limit: f64,
+    pub actual: f64,
+}
+
+impl Violation {
+    fn new(result: &RunResult, metric: &'static str, limit: f64, actual: f64) -> Self {
+        Self {
+            scenario: result.scenario,
+            backend: result.backend,
+            metric,
+            limit,
+            actual,
+        }
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} on {}: ", self.scenario, self.backend)?;
+        match self.metric {
+            "throughput" => write!(f, "{:.1} ops/s, below {} ops/s", self.actual, self.limit),
+            "errors" => write!(f, "{} error(s), more than {}", self.actual, self.limit),
+            metric => write!(f, "{} {:.3} ms, over {} ms", metric, self.actual, self.limit),
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//! Status filter: the newest posts of one status, read through the
+//! `(status, created_at)` index
+//!
+//! `select_posts_by_status` filters on `status` and orders by `created_at`,
+//! which [`INDEX`] serves with one backward scan that stops at the limit. That
+//! only holds for SQL the index matches: a library that casts the column,
+//! wraps it in a function or orders by something else falls back to the
+//! `status` or `created_at` index alone, or to a sort, and its time then
+//! measures a different plan rather than the library.
+//!
+//! [`explain`] finds out which plan each library gets. As the audit does, it
+//! records the statements one call runs in `pg_stat_statements`, then has the
+//! server plan each of them as a generic plan, the plan a prepared statement
+//! is given whatever its parameters.
+
+use crate::audit::{self, AuditError};
+use crate::DatabaseBenchmark;
+use serde_json::Value;
+use std::fmt;
+use tokio_postgres::{Client, SimpleQueryMessage};
+
+/// The index `init.sql` creates on `posts(status, created_at)`
+pub const INDEX: &str = "idx_posts_status_created_at";
+
+/// Statuses the benchmark reads in turn; the seeded posts are published or
+/// archived
+pub const STATUSES: [&str; 2] = ["published", "archived"];
+
+/// Posts per read
+pub const LIMIT: i64 = 20;
+
+/// Name the statements are prepared under while they are planned
+const PLANNED: &str = "status_filter_plan";
+
+/// A statement a backend ran and the indexes its generic plan scans
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedStatement {
+    /// As `pg_stat_statements` normalized it
+    pub query: String,
+    pub indexes: Vec<String>,
+}
+
+/// The plans of the statements reading `posts` one backend ran for
+/// `select_posts_by_status`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexUsage {
+    pub backend: &'static str,
+    pub statements: Vec<PlannedStatement>,
+}
+
+impl IndexUsage {
+    /// Whether the backend read `posts` and every statement that did scans
+    /// [`INDEX`]
+    pub fn uses_index(&self) -> bool {
+        !self.statements.is_empty()
+            && self.statements.iter().all(|s| s.indexes.iter().any(|index| index == INDEX))
+    }
+}
+
+impl fmt::Display for IndexUsage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.uses_index() {
+            return write!(f, "{} scans {}", self.backend, INDEX);
+        }
+        write!(f, "{} doesn't scan {}", self.backend, INDEX)?;
+        if self.statements.is_empty() {
+            return write!(f, ", no statement reading posts was recorded");
+        }
+        for statement in &self.statements {
+            let indexes = match statement.indexes.as_slice() {
+                [] => "no index".to_string(),
+                indexes => indexes.join(", "),
+            };
+            write!(f, "\n  {indexes}: {}", statement.query)?;
+        }
+        Ok(())
+    }
+}
+
+/// Record the statements one `select_posts_by_status` call through `conn` runs
+/// and plan each of them on `client`.
+///
+/// The call runs once untracked first, so preparing the statement and looking
+/// up its types are not recorded. `pg_stat_statements` must be loaded (see
+/// [`audit::available`]) and nothing else should use the database meanwhile.
+pub async fn explain<B: DatabaseBenchmark>(
+    backend: &'static str,
+    conn: &B::Connection,
+    client: &Client,
+) -> Result<IndexUsage, AuditError<B::Error>> {
+    B::select_posts_by_status(conn, STATUSES[0], LIMIT).await.map_err(AuditError::Backend)?;
+    audit::reset(client).await.map_err(AuditError::Stats)?;
+    B::select_posts_by_status(conn, STATUSES[0], LIMIT).await.map_err(AuditError::Backend)?;
+    let recorded = audit::recorded_statements(client).await.map_err(AuditError::Stats)?;
+
+    let mut statements = Vec::new();
+    for statement in recorded.into_iter().filter(|s| s.verb() == "SELECT" && s.query.contains("posts")) {
+        let indexes = plan(client, &statement.query).await.map_err(AuditError::Stats)?;
+        statements.push(PlannedStatement { query: statement.query, indexes });
+    }
+    Ok(IndexUsage { backend, statements })
+}
+
+/// The indexes the generic plan of `query` scans, in plan order. Its
+/// parameters' types are inferred as `PREPARE` does, and passed as `NULL`,
+/// which a generic plan doesn't look at.
+async fn plan(client: &Client, query: &str) -> Result<Vec<String>, tokio_postgres::Error> {
+    client.batch_execute(&format!("PREPARE {PLANNED} AS {query}")).await?;
+    let explained = explain_prepared(client).await;
+    client
+        .batch_execute(&format!("DEALLOCATE {PLANNED}; RESET plan_cache_mode"))
+        .await?;
+
+    let mut indexes = Vec::new();
+    // The server's own JSON, so it always parses
+    let plan: Value = serde_json::from_str(&explained?).unwrap_or_default();
+    collect_indexes(&plan, &mut indexes);
+    Ok(indexes)
+}
+
+/// `EXPLAIN (FORMAT JSON)` of the generic plan of the statement prepared as
+/// [`PLANNED`]
+async fn explain_prepared(client: &Client) -> Result<String, tokio_postgres::Error> {
+    let row = client
+        .query_one(
+            "SELECT cardinality(parameter_types) FROM pg_prepared_statements WHERE name = $1",
+            &[&PLANNED],
+        )
+        .await?;
+    let parameters = vec!["NULL"; row.get::<_, i32>(0) as usize];
+    let arguments = if parameters.is_empty() { String::new() } else { format!("({})", parameters.join(", ")) };
+
+    // A simple query, as tokio-postgres only reads `json` columns as text there
+    let messages = client
+        .simple_query(&format!(
+            "SET plan_cache_mode = force_generic_plan; EXPLAIN (FORMAT JSON) EXECUTE {PLANNED}{arguments}"
+        ))
+        .await?;
+    Ok(messages
+        .iter()
+        .find_map(|message| match message {
+            SimpleQueryMessage::Row(row) => row.get(0).map(str::to_string),
+            _ => None,
+        })
+        .unwrap_or_default())
+}
+
+/// Every `Index Name` in `plan` and the plans below it
+fn collect_indexes(plan: &Value, indexes: &mut Vec<String>) {
+    match plan {
+        Value::Array(plans) => plans.iter().for_each(|plan| collect_indexes(plan, indexes)),
+        Value::Object(node) => {
+            if let Some(Value::String(index)) = node.get("Index Name") {
+                indexes.push(index.clone());
+            }
+            for key in ["Plan", "Plans"] {
+                if let Some(child) = node.get(key) {
+                    collect_indexes(child, indexes);
+                }
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,111 @@
+//! Tags for selecting benchmarks by the kind of work they measure
+//!
+//! Criterion only filters by a substring of the benchmark name, which can't
+//! say "everything that joins" across groups named after their topic. Every
+//! scenario (through its [`crate::scenario::Category`]) and every criterion
+//! group is tagged with the [`Tag`]s it exercises, and `BENCH_TAGS=join,write`
+//! runs only those with at least one of the listed tags, in `cargo bench` and
+//! in the load-test runner alike (where `tags = [...]` in the configuration
+//! takes precedence). Name filters still apply on top.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// What a benchmark exercises
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tag {
+    /// Reads rows
+    Read,
+    /// Inserts, updates or deletes rows
+    Write,
+    /// Joins tables
+    Join,
+    /// Runs explicit transactions
+    Transaction,
+    /// Runs operations from several tasks or connections at once
+    Concurrency,
+    /// Encodes or decodes particular column types or representations
+    Types,
+    /// Opens connections, with or without TLS and authentication
+    Connection,
+}
+
+impl Tag {
+    pub const ALL: [Tag; 7] = [
+        Tag::Read,
+        Tag::Write,
+        Tag::Join,
+        Tag::Transaction,
+        Tag::Concurrency,
+        Tag::Types,
+        Tag::Connection,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Tag::Read => "read",
+            Tag::Write => "write",
+            Tag::Join => "join",
+            Tag::Transaction => "transaction",
+            Tag::Concurrency => "concurrency",
+            Tag::Types => "types",
+            Tag::Connection => "connection",
+        }
+    }
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+impl FromStr for Tag {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Tag::ALL.into_iter().find(|tag| tag.name() == s.trim()).ok_or_else(|| {
+            let names: Vec<&str> = Tag::ALL.iter().map(Tag::name).collect();
+            format!("unknown tag {s:?}, expected one of {}", names.join(", "))
+        })
+    }
+}
+
+/// The tags benchmarks are selected by; without any, every benchmark runs
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TagFilter {
+    pub tags: Vec<Tag>,
+}
+
+impl TagFilter {
+    /// Tags from a comma-separated list such as `join,write`
+    pub fn parse(list: &str) -> Result<Self, String> {
+        let tags = list
+            .split(',')
+            .filter(|name| !name.trim().is_empty())
+            .map(str::parse)
+            .collect::<Result<_, _>>()?;
+        Ok(Self { tags })
+    }
+
+    /// The tags `BENCH_TAGS` lists, if set
+    pub fn from_env() -> Result<Self, String> {
+        match std::env::var("BENCH_TAGS") {
+            Ok(list) => Self::parse(&list).map_err(|e| format!("BENCH_TAGS: {e}")),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    /// Whether a benchmark tagged `tags` runs: it has one of the filter's
+    /// tags, or the filter has none
+    pub fn matches(&self, tags: &[Tag]) -> bool {
+        self.tags.is_empty() || self.tags.iter().any(|tag| tags.contains(tag))
+    }
+}
+
+impl fmt::Display for TagFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<&str> = self.tags.iter().map(Tag::name).collect();
+        f.write_str(&names.join(","))
+    }
+}
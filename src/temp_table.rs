@@ -0,0 +1,262 @@
+//! Session temp-table workload
+//!
+//! An ETL-style step on a single connection: create a session-local temp
+//! table ([`TABLE`]), bulk-load a batch of users with a score into it, join it
+//! against `posts` to count each staged user's posts, and drop it again. Every
+//! run pays for the DDL, the catalog churn of a fresh relation, the bulk load
+//! and the join, which the per-statement scenarios never combine.
+//!
+//! Temp tables belong to the session that created them, so every statement of a
+//! run must reach the same server connection. The drivers and diesel hold one;
+//! sqlx acquires one connection from its pool for the whole run, and SeaORM,
+//! which only pins a connection inside a transaction, runs it in one. The
+//! prepared tokio-postgres variant is left out, its statement cache would only
+//! ever hold statements against the table of a previous run.
+
+use crate::bench_clorinde::{queries, ClorindeBench};
+use crate::bench_diesel::DieselBench;
+use crate::bench_seaorm::SeaOrmBench;
+use crate::bench_sqlx::SqlxBench;
+use crate::bench_tokio_postgres::TokioPostgresBench;
+use crate::DatabaseBenchmark;
+use sea_orm::sea_query::Table;
+use sea_orm::{
+    ActiveValue, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait, JoinType, QueryOrder,
+    QuerySelect, Schema, TransactionTrait,
+};
+use sqlx::PgPool;
+use tokio_postgres::Client;
+use uuid::Uuid;
+
+/// The temp table each run creates and drops
+pub const TABLE: &str = "staged_users";
+
+const CREATE_TABLE: &str = "CREATE TEMP TABLE staged_users (user_id UUID PRIMARY KEY, score INTEGER NOT NULL)";
+
+const LOAD: &str = "INSERT INTO staged_users (user_id, score) SELECT * FROM unnest($1::uuid[], $2::int4[])";
+
+const JOIN_POSTS: &str = "SELECT s.user_id, s.score, COUNT(p.id) AS post_count
+     FROM staged_users s
+     JOIN posts p ON p.user_id = s.user_id
+     GROUP BY s.user_id, s.score
+     ORDER BY s.user_id";
+
+const DROP_TABLE: &str = "DROP TABLE staged_users";
+
+/// A row bulk-loaded into [`TABLE`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StagedUser {
+    pub user_id: Uuid,
+    pub score: i32,
+}
+
+/// A staged user with the number of posts they wrote
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StagedPosts {
+    pub user_id: Uuid,
+    pub score: i32,
+    pub post_count: i64,
+}
+
+/// Stage `user_ids`, each scored by its position
+pub fn stage(user_ids: &[Uuid]) -> Vec<StagedUser> {
+    user_ids
+        .iter()
+        .enumerate()
+        .map(|(i, &user_id)| StagedUser { user_id, score: i as i32 })
+        .collect()
+}
+
+/// The staged users' columns, as the `unnest` load binds them
+fn columns(rows: &[StagedUser]) -> (Vec<Uuid>, Vec<i32>) {
+    rows.iter().map(|row| (row.user_id, row.score)).unzip()
+}
+
+/// One temp-table run on the backend's own connection
+#[allow(async_fn_in_trait)]
+pub trait TempTable: DatabaseBenchmark {
+    /// Create [`TABLE`], load `rows` into it, join it against `posts` and drop
+    /// it; the staged users with at least one post, by user id
+    async fn stage_and_join(conn: &Self::Connection, rows: &[StagedUser]) -> Result<Vec<StagedPosts>, Self::Error>;
+}
+
+// ============================================================================
+// tokio-postgres and clorinde
+// ============================================================================
+
+impl TempTable for TokioPostgresBench {
+    async fn stage_and_join(client: &Client, rows: &[StagedUser]) -> Result<Vec<StagedPosts>, tokio_postgres::Error> {
+        let (user_ids, scores) = columns(rows);
+        client.batch_execute(CREATE_TABLE).await?;
+        client.execute(LOAD, &[&user_ids, &scores]).await?;
+        let joined = client.query(JOIN_POSTS, &[]).await?;
+        client.batch_execute(DROP_TABLE).await?;
+        Ok(joined
+            .iter()
+            .map(|row| StagedPosts {
+                user_id: row.get(0),
+                score: row.get(1),
+                post_count: row.get(2),
+            })
+            .collect())
+    }
+}
+
+impl TempTable for ClorindeBench {
+    async fn stage_and_join(client: &Client, rows: &[StagedUser]) -> Result<Vec<StagedPosts>, tokio_postgres::Error> {
+        let (user_ids, scores) = columns(rows);
+        queries::staged_users::create_staged_users().bind(client).await?;
+        queries::staged_users::load_staged_users().bind(client, &user_ids, &scores).await?;
+        let joined = queries::staged_users::staged_user_posts().bind(client).all().await?;
+        queries::staged_users::drop_staged_users().bind(client).await?;
+        Ok(joined
+            .into_iter()
+            .map(|row| StagedPosts {
+                user_id: row.user_id,
+                score: row.score,
+                post_count: row.post_count,
+            })
+            .collect())
+    }
+}
+
+// ============================================================================
+// sqlx
+// ============================================================================
+
+impl TempTable for SqlxBench {
+    async fn stage_and_join(pool: &PgPool, rows: &[StagedUser]) -> Result<Vec<StagedPosts>, sqlx::Error> {
+        let (user_ids, scores) = columns(rows);
+        let mut conn = pool.acquire().await?;
+        sqlx::query(CREATE_TABLE).execute(&mut *conn).await?;
+        sqlx::query(LOAD).bind(user_ids).bind(scores).execute(&mut *conn).await?;
+        let joined: Vec<(Uuid, i32, i64)> = sqlx::query_as(JOIN_POSTS).fetch_all(&mut *conn).await?;
+        sqlx::query(DROP_TABLE).execute(&mut *conn).await?;
+        Ok(joined
+            .into_iter()
+            .map(|(user_id, score, post_count)| StagedPosts { user_id, score, post_count })
+            .collect())
+    }
+}
+
+// ============================================================================
+// SeaORM
+// ============================================================================
+
+pub mod staged_users {
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+    #[sea_orm(table_name = "staged_users")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub user_id: Uuid,
+        pub score: i32,
+    }
+
+    // No relations: `create_table_from_entity` would turn them into foreign
+    // keys, which a temp table can't have on a regular one
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    /// `staged_users.user_id = posts.user_id`, for the join
+    pub fn posts() -> RelationDef {
+        Entity::belongs_to(crate::bench_seaorm::posts::Entity)
+            .from(Column::UserId)
+            .to(crate::bench_seaorm::posts::Column::UserId)
+            .into()
+    }
+}
+
+impl TempTable for SeaOrmBench {
+    async fn stage_and_join(db: &DatabaseConnection, rows: &[StagedUser]) -> Result<Vec<StagedPosts>, DbErr> {
+        use crate::bench_seaorm::posts;
+
+        let txn = db.begin().await?;
+        let backend = txn.get_database_backend();
+        let mut create = Schema::new(backend).create_table_from_entity(staged_users::Entity);
+        txn.execute(backend.build(create.temporary())).await?;
+
+        let models = rows.iter().map(|row| staged_users::ActiveModel {
+            user_id: ActiveValue::Set(row.user_id),
+            score: ActiveValue::Set(row.score),
+        });
+        staged_users::Entity::insert_many(models).exec_without_returning(&txn).await?;
+
+        let joined: Vec<(Uuid, i32, i64)> = staged_users::Entity::find()
+            .select_only()
+            .column(staged_users::Column::UserId)
+            .column(staged_users::Column::Score)
+            .column_as(posts::Column::Id.count(), "post_count")
+            .join(JoinType::InnerJoin, staged_users::posts())
+            .group_by(staged_users::Column::UserId)
+            .group_by(staged_users::Column::Score)
+            .order_by_asc(staged_users::Column::UserId)
+            .into_tuple()
+            .all(&txn)
+            .await?;
+
+        txn.execute(backend.build(&Table::drop().table(staged_users::Entity).to_owned())).await?;
+        txn.commit().await?;
+        Ok(joined
+            .into_iter()
+            .map(|(user_id, score, post_count)| StagedPosts { user_id, score, post_count })
+            .collect())
+    }
+}
+
+// ============================================================================
+// Diesel
+// ============================================================================
+
+pub mod schema {
+    use crate::bench_diesel::schema::posts;
+
+    diesel::table! {
+        staged_users (user_id) {
+            user_id -> Uuid,
+            score -> Int4,
+        }
+    }
+
+    diesel::joinable!(posts -> staged_users (user_id));
+
+    diesel::allow_tables_to_appear_in_same_query!(staged_users, posts);
+}
+
+#[derive(diesel::Insertable)]
+#[diesel(table_name = schema::staged_users)]
+struct DieselStagedUser {
+    user_id: Uuid,
+    score: i32,
+}
+
+impl TempTable for DieselBench {
+    async fn stage_and_join(conn: &Self::Connection, rows: &[StagedUser]) -> Result<Vec<StagedPosts>, Self::Error> {
+        use crate::bench_diesel::schema::posts;
+        use diesel::dsl::count;
+        use diesel::prelude::*;
+        use schema::staged_users;
+
+        let conn = &mut *conn.lock().unwrap();
+        let staged: Vec<_> = rows
+            .iter()
+            .map(|row| DieselStagedUser { user_id: row.user_id, score: row.score })
+            .collect();
+        diesel::sql_query(CREATE_TABLE).execute(conn)?;
+        diesel::insert_into(staged_users::table).values(&staged).execute(conn)?;
+        let joined: Vec<(Uuid, i32, i64)> = posts::table
+            .inner_join(staged_users::table)
+            .group_by((staged_users::user_id, staged_users::score))
+            .select((staged_users::user_id, staged_users::score, count(posts::id)))
+            .order(staged_users::user_id)
+            .load(conn)?;
+        diesel::sql_query(DROP_TABLE).execute(conn)?;
+        Ok(joined
+            .into_iter()
+            .map(|(user_id, score, post_count)| StagedPosts { user_id, score, post_count })
+            .collect())
+    }
+}
@@ -0,0 +1,217 @@
+//! TLS connection paths
+//!
+//! Every backend can connect with `sslmode=require` for the TLS benchmarks.
+//! The stack depends on the library: tokio-postgres (and so clorinde) takes a
+//! connector at runtime and is measured with native-tls and, with the `tls`
+//! feature, rustls (see [`TOKIO_POSTGRES_STACKS`]), sqlx
+//! and SeaORM are compiled against rustls, or native-tls with the
+//! `sqlx-native-tls` feature (see [`sqlx_stack`]), and diesel uses the OpenSSL
+//! libpq was built with. Like `sslmode=require`, none of them verify the server
+//! certificate, so a self-signed one is enough.
+
+use crate::after_connect::AfterConnect;
+use crate::{database_url, with_param};
+#[cfg(feature = "tls")]
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+#[cfg(feature = "tls")]
+use rustls::crypto::{ring, CryptoProvider};
+#[cfg(feature = "tls")]
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+#[cfg(feature = "tls")]
+use rustls::{DigitallySignedStruct, SignatureScheme};
+use std::fmt;
+#[cfg(feature = "tls")]
+use std::sync::Arc;
+use std::sync::OnceLock;
+use tokio_postgres::tls::MakeTlsConnect;
+use tokio_postgres::{Client, Config, NoTls, Socket};
+
+/// The TLS implementation a connection goes through
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TlsStack {
+    /// Plain TCP (`sslmode=disable`)
+    None,
+    Rustls,
+    /// The platform library behind `native-tls` (OpenSSL on Linux)
+    NativeTls,
+    /// libpq's built-in OpenSSL support, used by diesel
+    Libpq,
+}
+
+impl fmt::Display for TlsStack {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TlsStack::None => write!(f, "no_tls"),
+            TlsStack::Rustls => write!(f, "rustls"),
+            TlsStack::NativeTls => write!(f, "native_tls"),
+            TlsStack::Libpq => write!(f, "libpq_openssl"),
+        }
+    }
+}
+
+/// The stacks tokio-postgres connects through: plain, rustls with the `tls`
+/// feature, and native-tls
+#[cfg(feature = "tls")]
+pub const TOKIO_POSTGRES_STACKS: &[TlsStack] = &[TlsStack::None, TlsStack::Rustls, TlsStack::NativeTls];
+#[cfg(not(feature = "tls"))]
+pub const TOKIO_POSTGRES_STACKS: &[TlsStack] = &[TlsStack::None, TlsStack::NativeTls];
+
+/// The stack sqlx, and so SeaORM, was compiled with: native-tls with the
+/// `sqlx-native-tls` feature, rustls otherwise
+pub fn sqlx_stack() -> TlsStack {
+    if cfg!(feature = "sqlx-native-tls") {
+        TlsStack::NativeTls
+    } else {
+        TlsStack::Rustls
+    }
+}
+
+/// `url` with its `sslmode` set to `sslmode`, replacing any value already there.
+///
+/// Both URL (`postgres://...?sslmode=...`) and key/value connection strings are handled.
+pub fn with_sslmode(url: &str, sslmode: &str) -> String {
+    with_param(url, "sslmode", sslmode)
+}
+
+/// [`database_url`] for a TLS connection
+pub fn tls_database_url() -> String {
+    with_sslmode(&database_url(), "require")
+}
+
+/// Accepts any server certificate, matching `sslmode=require`; signatures are
+/// still checked so the handshake does the same work as a verifying client
+#[cfg(feature = "tls")]
+#[derive(Debug)]
+struct AcceptAnyServerCert(Arc<CryptoProvider>);
+
+#[cfg(feature = "tls")]
+impl ServerCertVerifier for AcceptAnyServerCert {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(message, cert, dss, &self.0.signature_verification_algorithms)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(message, cert, dss, &self.0.signature_verification_algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+/// rustls connector for tokio-postgres, using the same `ring` provider as sqlx.
+///
+/// Built once and shared, as an application would, so connects only pay for the handshake.
+#[cfg(feature = "tls")]
+pub fn rustls_connector() -> tokio_postgres_rustls::MakeRustlsConnect {
+    static CONNECTOR: OnceLock<tokio_postgres_rustls::MakeRustlsConnect> = OnceLock::new();
+    CONNECTOR
+        .get_or_init(|| {
+            let provider = Arc::new(ring::default_provider());
+            let config = rustls::ClientConfig::builder_with_provider(provider.clone())
+                .with_safe_default_protocol_versions()
+                .expect("ring supports the default protocol versions")
+                .dangerous()
+                .with_custom_certificate_verifier(Arc::new(AcceptAnyServerCert(provider)))
+                .with_no_client_auth();
+            tokio_postgres_rustls::MakeRustlsConnect::new(config)
+        })
+        .clone()
+}
+
+/// native-tls connector for tokio-postgres, built once and shared like the rustls one
+pub fn native_tls_connector() -> postgres_native_tls::MakeTlsConnector {
+    static CONNECTOR: OnceLock<postgres_native_tls::MakeTlsConnector> = OnceLock::new();
+    CONNECTOR
+        .get_or_init(|| {
+            let connector = native_tls::TlsConnector::builder()
+                .danger_accept_invalid_certs(true)
+                .build()
+                .expect("Failed to create native-tls connector");
+            postgres_native_tls::MakeTlsConnector::new(connector)
+        })
+        .clone()
+}
+
+async fn spawn_connection<T>(config: &Config, tls: T) -> Result<Client, tokio_postgres::Error>
+where
+    T: MakeTlsConnect<Socket>,
+    T::Stream: Send + 'static,
+{
+    let (client, connection) = config.connect(tls).await?;
+
+    tokio::spawn(async move {
+        if let Err(e) = connection.await {
+            eprintln!("connection error: {}", e);
+        }
+    });
+
+    AfterConnect::from_env().run_tokio_postgres(&client).await?;
+    Ok(client)
+}
+
+/// Why a tokio-postgres client couldn't connect through a [`TlsStack`]
+#[derive(Debug)]
+pub enum ConnectError {
+    /// A stack tokio-postgres can't use in this build: libpq's, or rustls
+    /// without the `tls` feature
+    Unsupported(TlsStack),
+    /// Connecting failed
+    Postgres(tokio_postgres::Error),
+}
+
+impl fmt::Display for ConnectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConnectError::Unsupported(TlsStack::Rustls) => {
+                write!(f, "tokio-postgres connects through rustls with the `tls` feature only")
+            }
+            ConnectError::Unsupported(stack) => write!(f, "tokio-postgres can't connect through {}", stack),
+            ConnectError::Postgres(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for ConnectError {}
+
+impl From<tokio_postgres::Error> for ConnectError {
+    fn from(e: tokio_postgres::Error) -> Self {
+        ConnectError::Postgres(e)
+    }
+}
+
+/// Connect a tokio-postgres client through `stack`; [`TlsStack::Libpq`],
+/// which only diesel can use, and [`TlsStack::Rustls`] without the `tls`
+/// feature are [`ConnectError::Unsupported`].
+pub async fn connect_tokio_postgres(stack: TlsStack) -> Result<Client, ConnectError> {
+    Ok(match stack {
+        TlsStack::None => spawn_connection(&database_url().parse()?, NoTls).await?,
+        #[cfg(feature = "tls")]
+        TlsStack::Rustls => spawn_connection(&tls_database_url().parse()?, rustls_connector()).await?,
+        TlsStack::NativeTls => spawn_connection(&tls_database_url().parse()?, native_tls_connector()).await?,
+        #[cfg(not(feature = "tls"))]
+        TlsStack::Rustls => return Err(ConnectError::Unsupported(stack)),
+        TlsStack::Libpq => return Err(ConnectError::Unsupported(stack)),
+    })
+}
@@ -0,0 +1,273 @@
+//! Inserts that hit the username unique constraint, and recovering from them
+//!
+//! A sign-up form that finds the username taken tries another. The
+//! `unique_violation` groups insert [`CollidingUsers`] of which a fraction
+//! repeat an earlier username, and every backend stores each one under the
+//! first free `{username}_{n}` in one of two ways:
+//!
+//! - [`Recovery::OnConflict`]: `INSERT ... ON CONFLICT (username) DO NOTHING`,
+//!   which reports a taken name as no row inserted, without an error
+//! - [`Recovery::CatchAndRetry`]: a plain insert, with the unique violation
+//!   picked out of the library's error type by [`UniqueViolation::violated`]
+//!
+//! How the constraint is reached differs per library: tokio-postgres and
+//! clorinde through the `DbError` and its SQLSTATE, sqlx through
+//! `DatabaseError::is_unique_violation`, Diesel through `DatabaseErrorKind`
+//! behind its boxed error, and SeaORM through the sqlx error inside its
+//! `DbErr`, as its own `SqlErr` carries the message but not the constraint.
+//! Outside a transaction a failed insert leaves the connection usable;
+//! inside one it would need a savepoint to retry.
+
+use crate::bench_clorinde::{queries, ClorindeBench};
+use crate::bench_diesel::{schema::users, DieselBench};
+use crate::bench_seaorm::SeaOrmBench;
+use crate::bench_sqlx::SqlxBench;
+use crate::bench_tokio_postgres::TokioPostgresBench;
+use crate::{CollidingUsers, ConflictKey, DatabaseBenchmark, NewUser};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ActiveValue, DatabaseConnection, DbErr, EntityTrait, RuntimeErr};
+use sqlx::PgPool;
+use std::fmt;
+use tokio_postgres::error::SqlState;
+use tokio_postgres::Client;
+
+/// The constraint `UNIQUE` on `users.username` gets, as `init.sql` leaves it unnamed
+pub const USERNAME_KEY: &str = "users_username_key";
+
+/// Conflict rates the groups run at when `BENCH_CONFLICT_RATES` is unset
+pub const DEFAULT_CONFLICT_RATES: &[f64] = &[0.01, 0.1, 0.5];
+
+/// The fractions of colliding inserts in `BENCH_CONFLICT_RATES`, a
+/// comma-separated list from 0.0 to 1.0, or [`DEFAULT_CONFLICT_RATES`] when unset.
+///
+/// # Panics
+///
+/// If `BENCH_CONFLICT_RATES` holds something other than a number from 0.0 to 1.0.
+pub fn conflict_rates() -> Vec<f64> {
+    match std::env::var("BENCH_CONFLICT_RATES") {
+        Ok(rates) => rates
+            .split(',')
+            .filter(|rate| !rate.trim().is_empty())
+            .map(|rate| match rate.trim().parse::<f64>() {
+                Ok(rate) if (0.0..=1.0).contains(&rate) => rate,
+                _ => panic!("BENCH_CONFLICT_RATES: {rate:?} is not a fraction from 0.0 to 1.0"),
+            })
+            .collect(),
+        Err(_) => DEFAULT_CONFLICT_RATES.to_vec(),
+    }
+}
+
+/// Users of which `conflict_rate` repeat an earlier username, each with an
+/// email of its own so only the username constraint is hit
+pub fn colliding_usernames(conflict_rate: f64) -> CollidingUsers {
+    CollidingUsers {
+        conflict_rate,
+        key: ConflictKey::Username,
+    }
+}
+
+/// The username tried after `username` was taken `attempt` times
+pub fn retry_username(username: &str, attempt: usize) -> String {
+    format!("{username}_{attempt}")
+}
+
+/// How an insert recovers from a taken username
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recovery {
+    /// `ON CONFLICT (username) DO NOTHING`, then the next name
+    OnConflict,
+    /// A plain insert, its unique violation caught, then the next name
+    CatchAndRetry,
+}
+
+impl Recovery {
+    pub const ALL: [Recovery; 2] = [Recovery::OnConflict, Recovery::CatchAndRetry];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Recovery::OnConflict => "on_conflict",
+            Recovery::CatchAndRetry => "catch_and_retry",
+        }
+    }
+}
+
+impl fmt::Display for Recovery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Telling a unique violation from other errors, and inserting around one
+#[allow(async_fn_in_trait)]
+pub trait UniqueViolation: DatabaseBenchmark {
+    /// The unique constraint `error` violated, or `None` for any other error
+    fn violated(error: &Self::Error) -> Option<&str>;
+
+    /// Insert `user` unless its username is taken; whether it was inserted
+    async fn insert_user_unless_taken(conn: &Self::Connection, user: &NewUser) -> Result<bool, Self::Error>;
+
+    /// Insert `user` under the first free name of its username and
+    /// [`retry_username`]s with `recovery`; how many names were taken
+    async fn insert_user_recovering(
+        conn: &Self::Connection,
+        recovery: Recovery,
+        user: &NewUser,
+    ) -> Result<usize, Self::Error> {
+        let mut attempt = user.clone();
+        let mut taken = 0;
+        loop {
+            let inserted = match recovery {
+                Recovery::OnConflict => Self::insert_user_unless_taken(conn, &attempt).await?,
+                Recovery::CatchAndRetry => match Self::insert_user(conn, &attempt).await {
+                    Ok(_) => true,
+                    Err(e) if Self::violated(&e) == Some(USERNAME_KEY) => false,
+                    Err(e) => return Err(e),
+                },
+            };
+            if inserted {
+                return Ok(taken);
+            }
+            taken += 1;
+            attempt.username = retry_username(&user.username, taken);
+        }
+    }
+}
+
+// ============================================================================
+// tokio-postgres and clorinde
+// ============================================================================
+
+/// The constraint of a `23505` server error
+fn violated_by(error: &tokio_postgres::Error) -> Option<&str> {
+    error
+        .as_db_error()
+        .filter(|db| *db.code() == SqlState::UNIQUE_VIOLATION)
+        .and_then(|db| db.constraint())
+}
+
+impl UniqueViolation for TokioPostgresBench {
+    fn violated(error: &tokio_postgres::Error) -> Option<&str> {
+        violated_by(error)
+    }
+
+    async fn insert_user_unless_taken(client: &Client, user: &NewUser) -> Result<bool, tokio_postgres::Error> {
+        let inserted = client
+            .execute(
+                "INSERT INTO users (username, email, first_name, last_name, age)
+                 VALUES ($1, $2, $3, $4, $5)
+                 ON CONFLICT (username) DO NOTHING",
+                &[&user.username, &user.email, &user.first_name, &user.last_name, &user.age],
+            )
+            .await?;
+        Ok(inserted == 1)
+    }
+}
+
+impl UniqueViolation for ClorindeBench {
+    /// Generated queries return tokio-postgres' error as it is
+    fn violated(error: &tokio_postgres::Error) -> Option<&str> {
+        violated_by(error)
+    }
+
+    async fn insert_user_unless_taken(client: &Client, user: &NewUser) -> Result<bool, tokio_postgres::Error> {
+        let inserted = queries::users::insert_user_unless_taken()
+            .bind(client, &user.username, &user.email, &user.first_name, &user.last_name, &user.age)
+            .await?;
+        Ok(inserted == 1)
+    }
+}
+
+// ============================================================================
+// sqlx
+// ============================================================================
+
+impl UniqueViolation for SqlxBench {
+    fn violated(error: &sqlx::Error) -> Option<&str> {
+        match error {
+            sqlx::Error::Database(db) if db.is_unique_violation() => db.constraint(),
+            _ => None,
+        }
+    }
+
+    async fn insert_user_unless_taken(pool: &PgPool, user: &NewUser) -> Result<bool, sqlx::Error> {
+        let done = sqlx::query(
+            "INSERT INTO users (username, email, first_name, last_name, age)
+             VALUES ($1, $2, $3, $4, $5)
+             ON CONFLICT (username) DO NOTHING",
+        )
+        .bind(&user.username)
+        .bind(&user.email)
+        .bind(&user.first_name)
+        .bind(&user.last_name)
+        .bind(user.age)
+        .execute(pool)
+        .await?;
+        Ok(done.rows_affected() == 1)
+    }
+}
+
+// ============================================================================
+// SeaORM
+// ============================================================================
+
+impl UniqueViolation for SeaOrmBench {
+    /// `DbErr::sql_err` classifies the error but keeps only its message, so
+    /// the constraint comes from the sqlx error it wraps
+    fn violated(error: &DbErr) -> Option<&str> {
+        match error {
+            DbErr::Exec(RuntimeErr::SqlxError(e)) | DbErr::Query(RuntimeErr::SqlxError(e)) => SqlxBench::violated(e),
+            _ => None,
+        }
+    }
+
+    async fn insert_user_unless_taken(db: &DatabaseConnection, user: &NewUser) -> Result<bool, DbErr> {
+        use crate::bench_seaorm::users as entity;
+
+        let model = entity::ActiveModel {
+            username: ActiveValue::Set(user.username.clone()),
+            email: ActiveValue::Set(user.email.clone()),
+            first_name: ActiveValue::Set(user.first_name.clone()),
+            last_name: ActiveValue::Set(user.last_name.clone()),
+            age: ActiveValue::Set(user.age),
+            ..Default::default()
+        };
+        let inserted = entity::Entity::insert(model)
+            .on_conflict(OnConflict::column(entity::Column::Username).do_nothing().to_owned())
+            .exec_without_returning(db)
+            .await?;
+        Ok(inserted == 1)
+    }
+}
+
+// ============================================================================
+// Diesel
+// ============================================================================
+
+impl UniqueViolation for DieselBench {
+    /// The boxed error is downcast back to Diesel's to reach its kind
+    fn violated(error: &Self::Error) -> Option<&str> {
+        use diesel::result::{DatabaseErrorKind, Error};
+
+        match error.downcast_ref::<Error>()? {
+            Error::DatabaseError(DatabaseErrorKind::UniqueViolation, info) => info.constraint_name(),
+            _ => None,
+        }
+    }
+
+    async fn insert_user_unless_taken(conn: &Self::Connection, user: &NewUser) -> Result<bool, Self::Error> {
+        use diesel::{ExpressionMethods, RunQueryDsl};
+
+        let inserted = diesel::insert_into(users::table)
+            .values((
+                users::username.eq(&user.username),
+                users::email.eq(&user.email),
+                users::first_name.eq(&user.first_name),
+                users::last_name.eq(&user.last_name),
+                users::age.eq(user.age),
+            ))
+            .on_conflict(users::username)
+            .do_nothing()
+            .execute(&mut *conn.lock().unwrap())?;
+        Ok(inserted == 1)
+    }
+}
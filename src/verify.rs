@@ -0,0 +1,542 @@
+//! Schema drift detection
+//!
+//! The diesel `schema.rs` tables and the SeaORM entities are written by hand, so
+//! nothing stops them from drifting away from `init.sql`. This module reads the
+//! column definitions both ORMs were compiled against and compares them with the
+//! live database's `information_schema`.
+
+use crate::bench_diesel::schema;
+use crate::bench_seaorm::{comments, posts, users};
+use crate::seed::{round_robin, SeedConfig};
+use crate::seeder;
+use sea_orm::{ColumnTrait, ColumnType, EntityTrait, IdenStatic, Iterable};
+use std::fmt;
+use tokio_postgres::Client;
+
+/// Type family of a column; `text` and `varchar` decode the same way in every backend
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColumnKind {
+    Uuid,
+    Text,
+    Integer,
+    Timestamptz,
+    Other(String),
+}
+
+impl ColumnKind {
+    /// From `information_schema.columns.data_type`
+    pub fn from_pg(data_type: &str) -> Self {
+        match data_type {
+            "uuid" => Self::Uuid,
+            "text" | "character varying" => Self::Text,
+            "integer" => Self::Integer,
+            "timestamp with time zone" => Self::Timestamptz,
+            other => Self::Other(other.to_string()),
+        }
+    }
+
+    /// From the last path segment of a diesel SQL type, e.g. `Text` or `Timestamptz`
+    fn from_diesel(sql_type: &str) -> Self {
+        match sql_type {
+            "Uuid" => Self::Uuid,
+            "Text" | "Varchar" => Self::Text,
+            "Integer" | "Int4" => Self::Integer,
+            "Timestamptz" => Self::Timestamptz,
+            other => Self::Other(other.to_string()),
+        }
+    }
+
+    fn from_sea_orm(column_type: &ColumnType) -> Self {
+        match column_type {
+            ColumnType::Uuid => Self::Uuid,
+            ColumnType::String(_) | ColumnType::Text | ColumnType::Char(_) => Self::Text,
+            ColumnType::Integer => Self::Integer,
+            ColumnType::TimestampWithTimeZone => Self::Timestamptz,
+            other => Self::Other(format!("{:?}", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnSchema {
+    pub name: String,
+    pub kind: ColumnKind,
+    pub nullable: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableSchema {
+    pub name: String,
+    pub columns: Vec<ColumnSchema>,
+}
+
+/// A difference between an ORM mapping and the database
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaMismatch {
+    pub source: &'static str,
+    pub table: String,
+    pub column: Option<String>,
+    pub problem: String,
+}
+
+impl fmt::Display for SchemaMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.column {
+            Some(column) => write!(f, "{}: {}.{}: {}", self.source, self.table, column, self.problem),
+            None => write!(f, "{}: {}: {}", self.source, self.table, self.problem),
+        }
+    }
+}
+
+// ============================================================================
+// ORM mappings
+// ============================================================================
+
+/// Build a diesel table's schema from the SQL diesel generates for `SELECT *`
+/// (column names) and the type name of its `AllColumns` SQL type (column types)
+macro_rules! diesel_table {
+    ($table:ident) => {{
+        use diesel::{Expression, QueryDsl, Table};
+        let query = schema::$table::table.select(schema::$table::all_columns);
+        let sql = diesel::debug_query::<diesel::pg::Pg, _>(&query).to_string();
+        let sql_types =
+            std::any::type_name::<<<schema::$table::table as Table>::AllColumns as Expression>::SqlType>();
+        diesel_table_schema(stringify!($table), &sql, sql_types)
+    }};
+}
+
+/// Split `(A, B<C, D>, E)` into its top-level elements
+fn split_type_tuple(types: &str) -> Vec<&str> {
+    let inner = types.trim().trim_start_matches('(').trim_end_matches(')');
+    let mut parts = Vec::new();
+    let (mut depth, mut start) = (0usize, 0usize);
+    for (i, c) in inner.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(inner[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(inner[start..].trim());
+    parts.retain(|p| !p.is_empty());
+    parts
+}
+
+fn diesel_table_schema(table: &str, select_sql: &str, sql_types: &str) -> TableSchema {
+    // `SELECT "users"."id", "users"."username" FROM "users" -- binds: []`
+    let columns = select_sql
+        .trim_start_matches("SELECT ")
+        .split(" FROM ")
+        .next()
+        .unwrap_or_default()
+        .split(", ")
+        .map(|c| c.rsplit('.').next().unwrap_or(c).trim_matches('"'));
+
+    let columns = columns
+        .zip(split_type_tuple(sql_types))
+        .map(|(name, sql_type)| {
+            let nullable = sql_type.contains("Nullable<");
+            let inner = sql_type
+                .rsplit("Nullable<")
+                .next()
+                .unwrap_or(sql_type)
+                .trim_end_matches('>');
+            ColumnSchema {
+                name: name.to_string(),
+                kind: ColumnKind::from_diesel(inner.rsplit("::").next().unwrap_or(inner)),
+                nullable,
+            }
+        })
+        .collect();
+
+    TableSchema {
+        name: table.to_string(),
+        columns,
+    }
+}
+
+/// Tables as declared in diesel's `schema.rs`
+pub fn diesel_schema() -> Vec<TableSchema> {
+    vec![
+        diesel_table!(users),
+        diesel_table!(posts),
+        diesel_table!(comments),
+        diesel_table!(tags),
+        diesel_table!(post_tags),
+    ]
+}
+
+fn sea_orm_table<E: EntityTrait>() -> TableSchema {
+    TableSchema {
+        name: E::default().table_name().to_string(),
+        columns: E::Column::iter()
+            .map(|column| {
+                let def = column.def();
+                ColumnSchema {
+                    name: column.as_str().to_string(),
+                    kind: ColumnKind::from_sea_orm(def.get_column_type()),
+                    nullable: def.is_null(),
+                }
+            })
+            .collect(),
+    }
+}
+
+/// Tables as declared by the SeaORM entities
+pub fn sea_orm_schema() -> Vec<TableSchema> {
+    vec![
+        sea_orm_table::<users::Entity>(),
+        sea_orm_table::<posts::Entity>(),
+        sea_orm_table::<comments::Entity>(),
+    ]
+}
+
+// ============================================================================
+// Live database
+// ============================================================================
+
+/// Columns of the given tables in the `public` schema, in table order
+pub async fn database_schema(
+    client: &Client,
+    tables: &[&str],
+) -> Result<Vec<TableSchema>, tokio_postgres::Error> {
+    let mut schemas = Vec::with_capacity(tables.len());
+    for table in tables {
+        let rows = client
+            .query(
+                "SELECT column_name::text, data_type::text, is_nullable = 'YES'
+                 FROM information_schema.columns
+                 WHERE table_schema = 'public' AND table_name = $1
+                 ORDER BY ordinal_position",
+                &[table],
+            )
+            .await?;
+
+        schemas.push(TableSchema {
+            name: table.to_string(),
+            columns: rows
+                .iter()
+                .map(|r| ColumnSchema {
+                    name: r.get(0),
+                    kind: ColumnKind::from_pg(r.get(1)),
+                    nullable: r.get(2),
+                })
+                .collect(),
+        });
+    }
+    Ok(schemas)
+}
+
+/// Differences between an ORM mapping and the database.
+///
+/// Columns that are nullable in the mapping but `NOT NULL` in the database are
+/// fine; the reverse fails as soon as a NULL is decoded, so it is reported.
+pub fn compare(source: &'static str, mapping: &[TableSchema], database: &[TableSchema]) -> Vec<SchemaMismatch> {
+    let mut mismatches = Vec::new();
+    let mismatch = |table: &str, column: Option<&str>, problem: String| SchemaMismatch {
+        source,
+        table: table.to_string(),
+        column: column.map(str::to_string),
+        problem,
+    };
+
+    for expected in mapping {
+        let actual = database.iter().find(|t| t.name == expected.name);
+        let Some(actual) = actual.filter(|t| !t.columns.is_empty()) else {
+            mismatches.push(mismatch(&expected.name, None, "table missing from database".into()));
+            continue;
+        };
+
+        for column in &expected.columns {
+            let Some(db_column) = actual.columns.iter().find(|c| c.name == column.name) else {
+                mismatches.push(mismatch(&expected.name, Some(&column.name), "column missing from database".into()));
+                continue;
+            };
+            if db_column.kind != column.kind {
+                mismatches.push(mismatch(
+                    &expected.name,
+                    Some(&column.name),
+                    format!("type {:?} in mapping, {:?} in database", column.kind, db_column.kind),
+                ));
+            }
+            if db_column.nullable && !column.nullable {
+                mismatches.push(mismatch(
+                    &expected.name,
+                    Some(&column.name),
+                    "nullable in database but NOT NULL in mapping".into(),
+                ));
+            }
+        }
+
+        for db_column in &actual.columns {
+            if !expected.columns.iter().any(|c| c.name == db_column.name) {
+                mismatches.push(mismatch(&expected.name, Some(&db_column.name), "column not mapped".into()));
+            }
+        }
+    }
+
+    mismatches
+}
+
+/// Compare every ORM mapping against the database `client` is connected to
+pub async fn check_schema(client: &Client) -> Result<Vec<SchemaMismatch>, tokio_postgres::Error> {
+    let mut mismatches = Vec::new();
+    for (source, mapping) in [("diesel", diesel_schema()), ("sea_orm", sea_orm_schema())] {
+        let tables: Vec<&str> = mapping.iter().map(|t| t.name.as_str()).collect();
+        let database = database_schema(client, &tables).await?;
+        mismatches.extend(compare(source, &mapping, &database));
+    }
+    Ok(mismatches)
+}
+
+// ============================================================================
+// Benchmark data leaks
+// ============================================================================
+
+/// Rows left behind by a benchmark after its cleanup ran
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LeftoverRows {
+    /// `bench_user_*` users
+    pub users: i64,
+    /// Posts by a `bench_user_*` user, or with no (or the nil) user
+    pub posts: i64,
+    /// Comments on those posts or by a `bench_user_*` user
+    pub comments: i64,
+    /// Tag links of those posts
+    pub post_tags: i64,
+}
+
+impl LeftoverRows {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl fmt::Display for LeftoverRows {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} users, {} posts, {} comments, {} post_tags",
+            self.users, self.posts, self.comments, self.post_tags
+        )
+    }
+}
+
+/// Count benchmark rows that survived cleanup.
+///
+/// Deleting the `bench_user_*` users cascades to everything they own, so any
+/// row counted here would skew the table sizes seen by the next group.
+pub async fn leftover_rows(client: &Client) -> Result<LeftoverRows, tokio_postgres::Error> {
+    let row = client
+        .query_one(
+            "WITH bench_users AS (
+                 SELECT id FROM users WHERE username LIKE 'bench_user_%'
+             ), bench_posts AS (
+                 SELECT id FROM posts
+                 WHERE user_id IN (SELECT id FROM bench_users)
+                    OR user_id = '00000000-0000-0000-0000-000000000000'
+                    OR NOT EXISTS (SELECT 1 FROM users u WHERE u.id = posts.user_id)
+             )
+             SELECT
+                 (SELECT COUNT(*) FROM bench_users),
+                 (SELECT COUNT(*) FROM bench_posts),
+                 (SELECT COUNT(*) FROM comments
+                  WHERE post_id IN (SELECT id FROM bench_posts)
+                     OR user_id IN (SELECT id FROM bench_users)),
+                 (SELECT COUNT(*) FROM post_tags WHERE post_id IN (SELECT id FROM bench_posts))",
+            &[],
+        )
+        .await?;
+
+    Ok(LeftoverRows {
+        users: row.get(0),
+        posts: row.get(1),
+        comments: row.get(2),
+        post_tags: row.get(3),
+    })
+}
+
+// ============================================================================
+// Seeded dataset
+// ============================================================================
+
+/// Rows created by `init.sql`, leaving out anything a benchmark or test inserted
+const SEEDED_ROWS: &str = "WITH seeded_users AS (
+         SELECT * FROM users WHERE username NOT LIKE 'bench_user_%'
+     ), seeded_posts AS (
+         SELECT * FROM posts WHERE user_id IN (SELECT id FROM seeded_users)
+     ), seeded_comments AS (
+         SELECT * FROM comments
+         WHERE post_id IN (SELECT id FROM seeded_posts) AND user_id IN (SELECT id FROM seeded_users)
+     )";
+
+/// `total` children spread round-robin over `over` parents: a check name such as
+/// "users with 5 posts" or "users with 5-6 posts", and the fewest and most per parent
+fn spread_name(parents: &str, total: usize, over: usize, children: &str) -> (String, usize, usize) {
+    let (fewest, most, _) = round_robin(total, over);
+    let fewest = fewest.max(1);
+    let name = if fewest == most {
+        format!("{parents} with {most} {children}")
+    } else {
+        format!("{parents} with {fewest}-{most} {children}")
+    };
+    (name, fewest, most)
+}
+
+/// Posts per status when the posts are spread round-robin over the posting
+/// users and numbered per user: (published, archived, draft)
+fn status_counts(config: &SeedConfig) -> (usize, usize, usize) {
+    let (fewest, most, with_most) = round_robin(config.posts(), config.posting_users());
+    let per_user = |posts: usize| {
+        let archived = (1..=posts).filter(|n| n % 10 == 1).count();
+        let draft = posts / 10;
+        (posts - archived - draft, archived, draft)
+    };
+    let (small, large) = (per_user(fewest), per_user(most));
+    let with_fewest = config.posting_users() - with_most;
+    (
+        small.0 * with_fewest + large.0 * with_most,
+        small.1 * with_fewest + large.1 * with_most,
+        small.2 * with_fewest + large.2 * with_most,
+    )
+}
+
+/// What `init.sql` produces for `config`: (description, query over the `SEEDED_ROWS` CTEs, expected count)
+fn seed_expectations(config: &SeedConfig) -> Vec<(String, String, i64)> {
+    let (users, posts, comments) = (config.users, config.posts(), config.comments());
+    let posting_users = config.posting_users().min(posts);
+    let commented_posts = config.commented_posts().min(comments);
+    let (users_with_posts, fewest_posts, most_posts) = spread_name("users", posts, config.posting_users(), "posts");
+    let (posts_with_comments, fewest_comments, most_comments) =
+        spread_name("posts", comments, config.commented_posts(), "comments");
+    let (published, archived, draft) = status_counts(config);
+    // Posts with 0 to 5 tags
+    let mut tag_counts = [0; 6];
+    for index in 0..posts {
+        tag_counts[seeder::tags_per_post(index)] += 1;
+    }
+
+    let mut expectations: Vec<(String, String, usize)> = vec![
+        ("users".into(), "SELECT COUNT(*) FROM seeded_users".into(), users),
+        (
+            "users aged outside 18..=79".into(),
+            "SELECT COUNT(*) FROM seeded_users WHERE age IS NULL OR age NOT BETWEEN 18 AND 79".into(),
+            0,
+        ),
+        ("posts".into(), "SELECT COUNT(*) FROM seeded_posts".into(), posts),
+        (
+            users_with_posts,
+            format!(
+                "SELECT COUNT(*) FROM (SELECT user_id FROM seeded_posts GROUP BY user_id \
+                 HAVING COUNT(*) BETWEEN {fewest_posts} AND {most_posts}) p"
+            ),
+            posting_users,
+        ),
+        (
+            "users without posts".into(),
+            "SELECT COUNT(*) FROM seeded_users u WHERE NOT EXISTS (SELECT 1 FROM seeded_posts p WHERE p.user_id = u.id)"
+                .into(),
+            users - posting_users,
+        ),
+        (
+            "published posts".into(),
+            "SELECT COUNT(*) FROM seeded_posts WHERE status = 'published'".into(),
+            published,
+        ),
+        (
+            "archived posts".into(),
+            "SELECT COUNT(*) FROM seeded_posts WHERE status = 'archived'".into(),
+            archived,
+        ),
+        ("draft posts".into(), "SELECT COUNT(*) FROM seeded_posts WHERE status = 'draft'".into(), draft),
+        ("comments".into(), "SELECT COUNT(*) FROM seeded_comments".into(), comments),
+        (
+            posts_with_comments,
+            format!(
+                "SELECT COUNT(*) FROM (SELECT post_id FROM seeded_comments GROUP BY post_id \
+                 HAVING COUNT(*) BETWEEN {fewest_comments} AND {most_comments}) c"
+            ),
+            commented_posts,
+        ),
+        (
+            "posts created before their user".into(),
+            "SELECT COUNT(*) FROM seeded_posts p JOIN seeded_users u ON u.id = p.user_id
+             WHERE p.created_at < u.created_at"
+                .into(),
+            0,
+        ),
+        (
+            "comments created before their post".into(),
+            "SELECT COUNT(*) FROM seeded_comments c JOIN seeded_posts p ON p.id = c.post_id
+             WHERE c.created_at < p.created_at"
+                .into(),
+            0,
+        ),
+        ("tags".into(), "SELECT COUNT(*) FROM tags".into(), seeder::TAGS),
+        (
+            "post tags".into(),
+            "SELECT COUNT(*) FROM post_tags WHERE post_id IN (SELECT id FROM seeded_posts)".into(),
+            tag_counts.iter().enumerate().map(|(tags, posts)| tags * posts).sum(),
+        ),
+    ];
+    expectations.extend(tag_counts.iter().enumerate().map(|(tags, &posts)| {
+        (
+            format!("posts with {tags} tag{}", if tags == 1 { "" } else { "s" }),
+            format!(
+                "SELECT COUNT(*) FROM seeded_posts p
+                 WHERE (SELECT COUNT(*) FROM post_tags pt WHERE pt.post_id = p.id) = {tags}"
+            ),
+            posts,
+        )
+    }));
+    expectations
+        .into_iter()
+        .map(|(name, query, expected)| (name, query, expected as i64))
+        .collect()
+}
+
+/// One seeded-dataset invariant and the value found in the database
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataCheck {
+    pub name: String,
+    pub expected: i64,
+    pub actual: i64,
+}
+
+impl DataCheck {
+    pub fn passed(&self) -> bool {
+        self.expected == self.actual
+    }
+}
+
+impl fmt::Display for DataCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: expected {}, found {}", self.name, self.expected, self.actual)
+    }
+}
+
+/// Check the seeded dataset against what `init.sql` generates with the
+/// [`SeedConfig`] from the environment, so results from different machines are
+/// only compared when they ran against the same data
+pub async fn check_seed_data(client: &Client) -> Result<Vec<DataCheck>, tokio_postgres::Error> {
+    check_seed_data_with(client, SeedConfig::get()).await
+}
+
+/// Check the seeded dataset against what `init.sql` generates with `config`
+pub async fn check_seed_data_with(client: &Client, config: &SeedConfig) -> Result<Vec<DataCheck>, tokio_postgres::Error> {
+    let expectations = seed_expectations(config);
+    let mut checks = Vec::with_capacity(expectations.len());
+    for (name, query, expected) in expectations {
+        let row = client.query_one(&format!("{SEEDED_ROWS} {query}"), &[]).await?;
+        checks.push(DataCheck {
+            name,
+            expected,
+            actual: row.get(0),
+        });
+    }
+    Ok(checks)
+}
@@ -0,0 +1,382 @@
+//! The same small JSON API on every backend, and an HTTP load client for it
+//!
+//! The scenario groups time a backend's calls on their own. A web service
+//! spends a request on more than the query: the handler checks a connection
+//! out of its pool, the rows become JSON, and concurrent requests contend for
+//! the pool. [`router`] serves three endpoints through axum, one [`Api`] per
+//! backend over that library's own pool:
+//!
+//! - `GET /users/{id}`: one user, `404` if there is none
+//! - `GET /posts?limit=N`: the newest posts, each with its author
+//! - `POST /comments`: insert a comment, `201` with its id
+//!
+//! [`LoadClient`] drives a running [`ApiServer`] over keep-alive HTTP/1.1
+//! connections, one request in flight per connection, so every backend sees
+//! the same requests through the same server stack and only the library
+//! behind the handlers differs.
+
+use crate::bench_clorinde::ClorindeBench;
+use crate::bench_diesel::{DbPool, DieselBench};
+use crate::bench_seaorm::SeaOrmBench;
+use crate::bench_sqlx::SqlxBench;
+use crate::bench_tokio_postgres::TokioPostgresBench;
+use crate::{BoxError, NewComment, Post, User};
+use axum::extract::{Path, Query, State};
+use axum::http::{Method, Request, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use deadpool_postgres::Pool;
+use http_body_util::{BodyExt, Full};
+use hyper::body::Bytes;
+use hyper::client::conn::http1::SendRequest;
+use hyper_util::rt::TokioIo;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::future::Future;
+use std::io;
+use std::net::SocketAddr;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+/// Posts `GET /posts` returns without a `limit`
+pub const DEFAULT_POST_LIMIT: i64 = 20;
+
+/// What the handlers need from a backend
+pub trait Api: Clone + Send + Sync + 'static {
+    fn get_user(&self, id: Uuid) -> impl Future<Output = Result<Option<User>, BoxError>> + Send;
+
+    fn list_posts(&self, limit: i64) -> impl Future<Output = Result<Vec<(Post, User)>, BoxError>> + Send;
+
+    fn create_comment(&self, comment: NewComment) -> impl Future<Output = Result<Uuid, BoxError>> + Send;
+}
+
+/// tokio-postgres behind a deadpool pool
+#[derive(Clone)]
+pub struct TokioPostgresApi(pub Pool);
+
+impl Api for TokioPostgresApi {
+    async fn get_user(&self, id: Uuid) -> Result<Option<User>, BoxError> {
+        let client = self.0.get().await?;
+        Ok(TokioPostgresBench::select_user_by_id(&client, id).await?)
+    }
+
+    async fn list_posts(&self, limit: i64) -> Result<Vec<(Post, User)>, BoxError> {
+        let client = self.0.get().await?;
+        Ok(TokioPostgresBench::select_posts_with_user(&client, limit).await?)
+    }
+
+    async fn create_comment(&self, comment: NewComment) -> Result<Uuid, BoxError> {
+        let client = self.0.get().await?;
+        Ok(TokioPostgresBench::insert_comment(&client, &comment).await?)
+    }
+}
+
+/// sqlx's own pool
+#[derive(Clone)]
+pub struct SqlxApi(pub PgPool);
+
+impl Api for SqlxApi {
+    async fn get_user(&self, id: Uuid) -> Result<Option<User>, BoxError> {
+        Ok(SqlxBench::select_user_by_id(&self.0, id).await?)
+    }
+
+    async fn list_posts(&self, limit: i64) -> Result<Vec<(Post, User)>, BoxError> {
+        Ok(SqlxBench::select_posts_with_user(&self.0, limit).await?)
+    }
+
+    async fn create_comment(&self, comment: NewComment) -> Result<Uuid, BoxError> {
+        Ok(SqlxBench::insert_comment(&self.0, &comment).await?)
+    }
+}
+
+/// SeaORM over its sqlx pool
+#[derive(Clone)]
+pub struct SeaOrmApi(pub DatabaseConnection);
+
+impl Api for SeaOrmApi {
+    async fn get_user(&self, id: Uuid) -> Result<Option<User>, BoxError> {
+        Ok(SeaOrmBench::select_user_by_id(&self.0, id).await?)
+    }
+
+    async fn list_posts(&self, limit: i64) -> Result<Vec<(Post, User)>, BoxError> {
+        Ok(SeaOrmBench::select_posts_with_user(&self.0, limit as u64).await?)
+    }
+
+    async fn create_comment(&self, comment: NewComment) -> Result<Uuid, BoxError> {
+        Ok(SeaOrmBench::insert_comment(&self.0, &comment).await?)
+    }
+}
+
+/// diesel behind r2d2, each call on the blocking thread pool
+/// ([`DieselBench::run_blocking`])
+#[derive(Clone)]
+pub struct DieselApi(pub DbPool);
+
+impl Api for DieselApi {
+    async fn get_user(&self, id: Uuid) -> Result<Option<User>, BoxError> {
+        DieselBench::run_blocking(&self.0, move |conn| DieselBench::select_user_by_id(conn, id)).await
+    }
+
+    async fn list_posts(&self, limit: i64) -> Result<Vec<(Post, User)>, BoxError> {
+        DieselBench::run_blocking(&self.0, move |conn| DieselBench::select_posts_with_user(conn, limit)).await
+    }
+
+    async fn create_comment(&self, comment: NewComment) -> Result<Uuid, BoxError> {
+        DieselBench::run_blocking(&self.0, move |conn| DieselBench::insert_comment(conn, &comment)).await
+    }
+}
+
+/// The clorinde queries over tokio-postgres clients from a deadpool pool
+#[derive(Clone)]
+pub struct ClorindeApi(pub Pool);
+
+impl Api for ClorindeApi {
+    async fn get_user(&self, id: Uuid) -> Result<Option<User>, BoxError> {
+        let client = self.0.get().await?;
+        Ok(ClorindeBench::select_user_by_id(&client, id).await?)
+    }
+
+    async fn list_posts(&self, limit: i64) -> Result<Vec<(Post, User)>, BoxError> {
+        let client = self.0.get().await?;
+        Ok(ClorindeBench::select_posts_with_user(&client, limit).await?)
+    }
+
+    async fn create_comment(&self, comment: NewComment) -> Result<Uuid, BoxError> {
+        let client = self.0.get().await?;
+        Ok(ClorindeBench::insert_comment(&client, &comment).await?)
+    }
+}
+
+/// A post of `GET /posts`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PostWithAuthor {
+    #[serde(flatten)]
+    pub post: Post,
+    pub author: User,
+}
+
+fn posts_response(posts: Vec<(Post, User)>) -> Vec<PostWithAuthor> {
+    posts.into_iter().map(|(post, author)| PostWithAuthor { post, author }).collect()
+}
+
+/// The body `GET /posts` answers with for `posts`, serialized the way axum's
+/// [`Json`] does but without a server, so the `json_response` group can time
+/// a backend's rows becoming an API response on their own
+pub fn posts_body(posts: Vec<(Post, User)>) -> serde_json::Result<Vec<u8>> {
+    serde_json::to_vec(&posts_response(posts))
+}
+
+/// Query string of `GET /posts`
+#[derive(Debug, Deserialize)]
+pub struct PostsQuery {
+    pub limit: Option<i64>,
+}
+
+/// Body of `POST /comments`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CreateComment {
+    pub post_id: Uuid,
+    pub user_id: Uuid,
+    pub content: String,
+}
+
+/// Response of `POST /comments`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct Created {
+    pub id: Uuid,
+}
+
+/// A backend error, answered with `500` and its message
+struct HandlerError(BoxError);
+
+impl IntoResponse for HandlerError {
+    fn into_response(self) -> Response {
+        (StatusCode::INTERNAL_SERVER_ERROR, self.0.to_string()).into_response()
+    }
+}
+
+impl From<BoxError> for HandlerError {
+    fn from(e: BoxError) -> Self {
+        Self(e)
+    }
+}
+
+async fn get_user<A: Api>(State(api): State<A>, Path(id): Path<Uuid>) -> Result<Response, HandlerError> {
+    Ok(match api.get_user(id).await? {
+        Some(user) => Json(user).into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    })
+}
+
+async fn list_posts<A: Api>(
+    State(api): State<A>,
+    Query(query): Query<PostsQuery>,
+) -> Result<Json<Vec<PostWithAuthor>>, HandlerError> {
+    let posts = api.list_posts(query.limit.unwrap_or(DEFAULT_POST_LIMIT)).await?;
+    Ok(Json(posts_response(posts)))
+}
+
+async fn create_comment<A: Api>(
+    State(api): State<A>,
+    Json(body): Json<CreateComment>,
+) -> Result<(StatusCode, Json<Created>), HandlerError> {
+    let comment = NewComment {
+        post_id: body.post_id,
+        user_id: body.user_id,
+        content: body.content,
+    };
+    let id = api.create_comment(comment).await?;
+    Ok((StatusCode::CREATED, Json(Created { id })))
+}
+
+/// The API's routes, served by `api`
+pub fn router<A: Api>(api: A) -> Router {
+    Router::new()
+        .route("/users/{id}", get(get_user::<A>))
+        .route("/posts", get(list_posts::<A>))
+        .route("/comments", post(create_comment::<A>))
+        .with_state(api)
+}
+
+/// [`router`] listening on an ephemeral localhost port
+pub struct ApiServer {
+    addr: SocketAddr,
+    task: JoinHandle<io::Result<()>>,
+}
+
+impl ApiServer {
+    /// Start serving `api` in a task on the current runtime
+    pub async fn start<A: Api>(api: A) -> io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let task = tokio::spawn(async move { axum::serve(listener, router(api)).await });
+        Ok(Self { addr, task })
+    }
+
+    pub fn address(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Stop accepting and drop every open connection
+    pub async fn shutdown(self) {
+        self.task.abort();
+        let _ = self.task.await;
+    }
+}
+
+/// A response's status and whole body
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: StatusCode,
+    pub body: Bytes,
+}
+
+impl HttpResponse {
+    /// The body as JSON
+    pub fn json<T: for<'de> Deserialize<'de>>(&self) -> serde_json::Result<T> {
+        serde_json::from_slice(&self.body)
+    }
+}
+
+/// One keep-alive HTTP/1.1 connection to an [`ApiServer`]
+pub struct LoadClient {
+    sender: SendRequest<Full<Bytes>>,
+    host: String,
+}
+
+impl LoadClient {
+    pub async fn connect(addr: SocketAddr) -> Result<Self, BoxError> {
+        let stream = TcpStream::connect(addr).await?;
+        stream.set_nodelay(true)?;
+        let (sender, connection) = hyper::client::conn::http1::handshake(TokioIo::new(stream)).await?;
+        // Ends with the connection, when the client or the server goes away
+        tokio::spawn(connection);
+        Ok(Self {
+            sender,
+            host: addr.to_string(),
+        })
+    }
+
+    pub async fn get(&mut self, path: &str) -> Result<HttpResponse, BoxError> {
+        self.send(Method::GET, path, None).await
+    }
+
+    pub async fn post_json(&mut self, path: &str, body: &impl Serialize) -> Result<HttpResponse, BoxError> {
+        self.send(Method::POST, path, Some(serde_json::to_vec(body)?)).await
+    }
+
+    async fn send(&mut self, method: Method, path: &str, body: Option<Vec<u8>>) -> Result<HttpResponse, BoxError> {
+        let mut request = Request::builder().method(method).uri(path).header("host", &self.host);
+        if body.is_some() {
+            request = request.header("content-type", "application/json");
+        }
+        let request = request.body(Full::new(Bytes::from(body.unwrap_or_default())))?;
+        self.sender.ready().await?;
+        let response = self.sender.send_request(request).await?;
+        let status = response.status();
+        let body = response.into_body().collect().await?.to_bytes();
+        Ok(HttpResponse { status, body })
+    }
+}
+
+/// One of the API's endpoints
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endpoint {
+    GetUser,
+    ListPosts,
+    CreateComment,
+}
+
+impl Endpoint {
+    pub const ALL: [Endpoint; 3] = [Endpoint::GetUser, Endpoint::ListPosts, Endpoint::CreateComment];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Endpoint::GetUser => "get_user",
+            Endpoint::ListPosts => "list_posts",
+            Endpoint::CreateComment => "create_comment",
+        }
+    }
+}
+
+/// The rows the load's requests refer to
+#[derive(Debug, Clone)]
+pub struct Targets {
+    /// Users `GET /users/{id}` cycles through
+    pub user_ids: Vec<Uuid>,
+    /// Posts `GET /posts` asks for
+    pub post_limit: i64,
+    /// Post and user of every comment `POST /comments` creates
+    pub comment_on: (Uuid, Uuid),
+}
+
+impl Targets {
+    /// Send request number `iteration` of `endpoint` over `client`, failing
+    /// unless the server answered with success
+    pub async fn request(&self, client: &mut LoadClient, endpoint: Endpoint, iteration: usize) -> Result<HttpResponse, BoxError> {
+        let response = match endpoint {
+            Endpoint::GetUser => {
+                let id = self.user_ids[iteration % self.user_ids.len()];
+                client.get(&format!("/users/{id}")).await?
+            }
+            Endpoint::ListPosts => client.get(&format!("/posts?limit={}", self.post_limit)).await?,
+            Endpoint::CreateComment => {
+                let (post_id, user_id) = self.comment_on;
+                let comment = NewComment::generate(post_id, user_id, iteration);
+                let body = CreateComment {
+                    post_id,
+                    user_id,
+                    content: comment.content,
+                };
+                client.post_json("/comments", &body).await?
+            }
+        };
+        if !response.status.is_success() {
+            return Err(format!("{} answered {}: {}", endpoint.name(), response.status, String::from_utf8_lossy(&response.body)).into());
+        }
+        Ok(response)
+    }
+}
@@ -0,0 +1,117 @@
+//! Declarative workloads for the load-test runner
+//!
+//! A workload is a mix of [`SCENARIOS`] run together, each at its own weight,
+//! written as `[[operations]]` tables in a `bench-runner` configuration file
+//! ([`crate::runner::RunnerConfig`]) next to the usual concurrency, duration
+//! and mode settings:
+//!
+//! ```toml
+//! concurrency = 8
+//! duration_secs = 30.0
+//!
+//! [dataset]
+//! users = 100000
+//!
+//! [[operations]]
+//! scenario = "select_user_by_id"
+//! weight = 70
+//!
+//! [[operations]]
+//! scenario = "insert_comment"
+//! weight = 20
+//!
+//! [[operations]]
+//! scenario = "join_posts_users"
+//! weight = 10
+//! size = 50
+//! ```
+//!
+//! Weights are relative, so percentages work as they are. The runner
+//! interleaves the operations in proportion ([`WorkloadMix::sequence`]) rather
+//! than picking them at random, so every backend runs exactly the same mix,
+//! and reports each operation separately. The optional `[dataset]` tops the
+//! database up to a seed size before the run (see [`crate::seeder`]).
+
+use crate::replay::{MixEntry, WorkloadMix};
+use crate::scenario::{Scenario, SCENARIOS};
+use crate::seed::{SeedConfig, SeedRatio};
+use serde::Deserialize;
+
+/// Largest sum of weights, once reduced by their common divisor; the mix
+/// repeats after that many operations
+pub const MAX_TOTAL_WEIGHT: u64 = 10_000;
+
+/// One operation of a workload and its share of it
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorkloadOperation {
+    /// Scenario name as listed by `cargo run`
+    pub scenario: String,
+    pub weight: u64,
+    /// Rows, batch or post count; the scenario's typical size if left out
+    pub size: Option<usize>,
+}
+
+/// The seeded dataset a workload runs against
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Dataset {
+    /// Seeded users, the dataset's scale
+    pub users: Option<usize>,
+    /// `users:posts:comments`, as in `SEED_RATIO`
+    pub ratio: Option<String>,
+}
+
+impl Dataset {
+    /// [`SeedConfig::get`] with the workload's scale and ratio
+    pub fn seed_config(&self) -> Result<SeedConfig, String> {
+        let mut config = *SeedConfig::get();
+        if let Some(users) = self.users {
+            config.users = users;
+        }
+        if let Some(ratio) = &self.ratio {
+            config.ratio = ratio.parse::<SeedRatio>()?;
+        }
+        Ok(config)
+    }
+}
+
+/// The mix `operations` describe, in their order, with weights reduced by
+/// their common divisor
+pub fn mix(operations: &[WorkloadOperation]) -> Result<WorkloadMix, String> {
+    let mut entries = Vec::with_capacity(operations.len());
+    for operation in operations {
+        let scenario: &'static Scenario = SCENARIOS
+            .iter()
+            .find(|s| s.name == operation.scenario)
+            .ok_or_else(|| format!("unknown scenario {:?}", operation.scenario))?;
+        if operation.weight == 0 {
+            return Err(format!("{} has no weight", scenario.name));
+        }
+        let size = match operation.size {
+            None => scenario.typical_size(),
+            Some(_) if scenario.sizes.is_empty() => return Err(format!("{} takes no size", scenario.name)),
+            Some(0) => return Err(format!("{} needs a positive size", scenario.name)),
+            Some(size) => size,
+        };
+        entries.push(MixEntry { scenario, size, calls: operation.weight });
+    }
+
+    let divisor = entries.iter().fold(0, |d, e| gcd(d, e.calls));
+    for entry in &mut entries {
+        entry.calls /= divisor.max(1);
+    }
+    let total: u64 = entries.iter().map(|e| e.calls).sum();
+    if total > MAX_TOTAL_WEIGHT {
+        return Err(format!("weights add up to {total}, more than {MAX_TOTAL_WEIGHT} once reduced"));
+    }
+    Ok(WorkloadMix { entries, ..WorkloadMix::default() })
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
@@ -0,0 +1,90 @@
+//! Per-connection init SQL: every backend's new connections run the same
+//! statements before their first query
+
+mod common;
+
+use diesel::sql_types::Text;
+use diesel::{QueryableByName, RunQueryDsl};
+use pg_benchmark::after_connect::AfterConnect;
+use pg_benchmark::bench_clorinde::ClorindeBench;
+use pg_benchmark::bench_diesel::DieselBench;
+use pg_benchmark::bench_seaorm::SeaOrmBench;
+use pg_benchmark::bench_sqlx::{SqlxBench, SqlxConfig};
+use pg_benchmark::bench_tokio_postgres::TokioPostgresBench;
+use pg_benchmark::bench_wtx::{WtxBench, WtxConnection};
+use pg_benchmark::database_url;
+use wtx::database::{Executor as _, Record as _};
+
+const INIT_SETTINGS: &str = "SELECT concat_ws(',', current_setting('search_path'), \
+     current_setting('app.bench_tenant', true)) AS settings";
+const EXPECTED: &str = "pg_catalog, public,42";
+
+#[derive(QueryableByName)]
+struct SettingsRow {
+    #[diesel(sql_type = Text)]
+    settings: String,
+}
+
+fn init_sql() -> AfterConnect {
+    AfterConnect::default()
+        .with_sql("SET search_path = pg_catalog, public;")
+        .with_sql("SELECT set_config('app.bench_tenant', '42', false)")
+}
+
+async fn tokio_postgres_settings(client: &tokio_postgres::Client) -> String {
+    client.query_one(INIT_SETTINGS, &[]).await.unwrap().get(0)
+}
+
+async fn wtx_settings(conn: &WtxConnection) -> String {
+    let mut executor = conn.executor().await.unwrap();
+    executor.execute_stmt_single(INIT_SETTINGS, ()).await.unwrap().decode(0_usize).unwrap()
+}
+
+#[test]
+fn statements_join_into_one_query() {
+    assert_eq!(AfterConnect::default().sql(), None);
+    assert_eq!(
+        init_sql().sql().unwrap(),
+        "SET search_path = pg_catalog, public; SELECT set_config('app.bench_tenant', '42', false)"
+    );
+}
+
+#[tokio::test]
+async fn every_backend_runs_the_init_sql() {
+    let Some(_db) = common::lock_database().await else { return };
+    let hook = init_sql();
+    let url = database_url();
+
+    assert_eq!(tokio_postgres_settings(&TokioPostgresBench::connect_with_hook(&url, &hook).await.unwrap()).await, EXPECTED);
+    assert_eq!(tokio_postgres_settings(&ClorindeBench::connect_with_hook(&url, &hook).await.unwrap()).await, EXPECTED);
+    let pool = TokioPostgresBench::create_pool_with_hook(&url, 2, &hook);
+    assert_eq!(tokio_postgres_settings(&pool.get().await.unwrap()).await, EXPECTED);
+    let pool = TokioPostgresBench::create_bb8_pool_with_hook(&url, 2, &hook).await.unwrap();
+    assert_eq!(tokio_postgres_settings(&pool.get().await.unwrap()).await, EXPECTED);
+    let pool = TokioPostgresBench::create_mobc_pool_with_hook(&url, 2, &hook).await.unwrap();
+    assert_eq!(tokio_postgres_settings(&pool.get().await.unwrap()).await, EXPECTED);
+
+    let pool = SqlxBench::connect_with_hook(&url, 2, &SqlxConfig::from_env(), &hook).await.unwrap();
+    let settings: String = sqlx::query_scalar(INIT_SETTINGS).fetch_one(&pool).await.unwrap();
+    assert_eq!(settings, EXPECTED);
+    let db = SeaOrmBench::connect_with_hook(sea_orm::ConnectOptions::new(&url), &hook).await.unwrap();
+    let settings: String = sqlx::query_scalar(INIT_SETTINGS)
+        .fetch_one(db.get_postgres_connection_pool())
+        .await
+        .unwrap();
+    assert_eq!(settings, EXPECTED);
+
+    let pool = DieselBench::connect_with_hook(&url, 2, &hook).unwrap();
+    let row: SettingsRow = diesel::sql_query(INIT_SETTINGS).get_result(&mut pool.get().unwrap()).unwrap();
+    assert_eq!(row.settings, EXPECTED);
+
+    let conn = WtxBench::connect_with_hook(&url, &hook).await.unwrap();
+    assert_eq!(wtx_settings(&conn).await, EXPECTED);
+
+    // Without init SQL the connections keep the server's defaults
+    let plain = TokioPostgresBench::connect_with_hook(&url, &AfterConnect::default()).await.unwrap();
+    assert_ne!(tokio_postgres_settings(&plain).await, EXPECTED);
+
+    let failing = AfterConnect::default().with_sql("SELECT 1 / 0");
+    assert!(TokioPostgresBench::connect_with_hook(&url, &failing).await.is_err());
+}
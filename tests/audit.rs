@@ -0,0 +1,73 @@
+//! Fairness audit: statement capture through `pg_stat_statements` and the
+//! per-kind comparison against the raw-driver baseline
+
+mod common;
+
+use pg_benchmark::audit::{self, OperationAudit, Statement};
+use pg_benchmark::bench_sqlx::SqlxBench;
+use pg_benchmark::bench_tokio_postgres::TokioPostgresBench;
+
+fn audit_of(backend: &'static str, statements: &[(&str, i64)]) -> OperationAudit {
+    OperationAudit {
+        operation: "test",
+        backend,
+        statements: statements
+            .iter()
+            .map(|(query, calls)| Statement {
+                query: query.to_string(),
+                calls: *calls,
+            })
+            .collect(),
+    }
+}
+
+fn find<'a>(audits: &'a [OperationAudit], operation: &str) -> &'a OperationAudit {
+    audits.iter().find(|a| a.operation == operation).unwrap()
+}
+
+#[test]
+fn differences_are_counted_per_statement_kind() {
+    let baseline = audit_of("raw", &[("INSERT INTO users VALUES ($1)", 1), ("INSERT INTO posts VALUES ($1)", 5)]);
+    let orm = audit_of(
+        "orm",
+        &[
+            ("begin", 1),
+            (r#"INSERT INTO "users" VALUES ($1)"#, 1),
+            (r#"INSERT INTO "posts" VALUES ($1), ($2)"#, 1),
+            ("SELECT 1", 2),
+            ("COMMIT", 1),
+        ],
+    );
+
+    assert_eq!(orm.total_calls(), 6);
+    assert_eq!(orm.differences(&baseline), ["+1 BEGIN", "+1 COMMIT", "-4 INSERT", "+2 SELECT"]);
+    assert!(baseline.differences(&baseline).is_empty());
+}
+
+#[tokio::test]
+async fn audit_captures_each_backends_statements() {
+    let Some(_db) = common::lock_database().await else {
+        return;
+    };
+    let client = TokioPostgresBench::connect().await.unwrap();
+    if !audit::available(&client).await {
+        eprintln!("pg_stat_statements is not loaded, skipping audit test");
+        return;
+    }
+
+    let raw = audit::audit_backend::<TokioPostgresBench>("tokio_postgres", &client).await.unwrap();
+    let sqlx = audit::audit_backend::<SqlxBench>("sqlx", &client).await.unwrap();
+    assert_eq!(raw.len(), pg_benchmark::scenario::SCENARIOS.len());
+
+    let insert = find(&raw, "insert_single_user");
+    assert_eq!(insert.total_calls(), 1);
+    assert!(insert.statements[0].query.starts_with("INSERT INTO users"));
+
+    // Same hand-written SQL, transaction control included
+    assert!(find(&sqlx, "select_users_limit").differences(find(&raw, "select_users_limit")).is_empty());
+    let transaction = "transaction_insert_user_with_posts";
+    assert!(find(&sqlx, transaction).differences(find(&raw, transaction)).is_empty());
+    assert!(find(&raw, transaction).statements.iter().any(|s| s.verb() == "BEGIN"));
+
+    assert_eq!(common::count("SELECT COUNT(*) FROM users WHERE username LIKE 'bench_user_%'").await, 0);
+}
@@ -0,0 +1,45 @@
+//! Authentication method roles: every backend logs in as each role, and only
+//! the password methods reject a wrong password
+
+mod common;
+
+use pg_benchmark::auth::{self, AuthMethod};
+use pg_benchmark::bench_diesel::DieselBench;
+use pg_benchmark::bench_sqlx::SqlxBench;
+use pg_benchmark::bench_tokio_postgres::TokioPostgresBench;
+
+#[test]
+fn credentials_are_replaced() {
+    assert_eq!(
+        auth::with_credentials("postgres://user:p@ss@localhost:5432/db?sslmode=disable", "role", "pw"),
+        "postgres://role:pw@localhost:5432/db?sslmode=disable"
+    );
+    assert_eq!(
+        auth::with_credentials("postgresql://localhost/db", "role", "pw"),
+        "postgresql://role:pw@localhost/db"
+    );
+    assert_eq!(
+        auth::with_credentials("host=localhost user=me password=secret dbname=db", "role", "pw"),
+        "host=localhost dbname=db user=role password=pw"
+    );
+}
+
+#[tokio::test]
+async fn each_role_authenticates_with_its_method() {
+    let Some(_db) = common::lock_database().await else { return };
+    if TokioPostgresBench::connect_to(&AuthMethod::Trust.database_url()).await.is_err() {
+        eprintln!("the authentication roles are missing (see init-auth.sh), skipping");
+        return;
+    }
+
+    for method in AuthMethod::ALL {
+        let url = method.database_url();
+        TokioPostgresBench::connect_to(&url).await.unwrap();
+        SqlxBench::connect_single(&url).await.unwrap();
+        DieselBench::establish(&url).unwrap();
+
+        let wrong = auth::with_credentials(&url, method.role(), "wrong_password");
+        let accepted = TokioPostgresBench::connect_to(&wrong).await.is_ok();
+        assert_eq!(accepted, method == AuthMethod::Trust, "{method} with a wrong password");
+    }
+}
@@ -0,0 +1,40 @@
+//! The autovacuum soak: phase latencies and a short run against the database
+
+mod common;
+
+use pg_benchmark::autovacuum::{self, PhaseLatencies, VacuumMonitor};
+use pg_benchmark::bench_tokio_postgres::TokioPostgresBench;
+use std::time::Duration;
+
+#[test]
+fn phase_latencies_report_mean_and_quantiles() {
+    let mut latencies = PhaseLatencies::default();
+    assert_eq!((latencies.mean(), latencies.quantile(0.99)), (Duration::ZERO, Duration::ZERO));
+    for ms in [4, 1, 3, 2] {
+        latencies.record(Duration::from_millis(ms));
+    }
+    assert_eq!(latencies.writes(), 4);
+    assert_eq!(latencies.mean(), Duration::from_micros(2500));
+    assert_eq!(latencies.quantile(0.0), Duration::from_millis(1));
+    assert_eq!(latencies.quantile(1.0), Duration::from_millis(4));
+}
+
+#[tokio::test]
+async fn soak_files_every_write_and_leaves_no_users() {
+    let Some(_db) = common::lock_database().await else { return };
+    common::cleanup().await;
+    let client = TokioPostgresBench::connect().await.unwrap();
+    assert!(autovacuum::autovacuum_count(&client).await.unwrap() >= 0);
+
+    let monitor = VacuumMonitor::start(TokioPostgresBench::connect().await.unwrap());
+    let latencies = autovacuum::soak::<TokioPostgresBench>(&client, &monitor, Duration::from_millis(300))
+        .await
+        .unwrap();
+    let sampled = monitor.stop().await.unwrap();
+    assert!(sampled.samples > 0 && sampled.active_samples <= sampled.samples);
+
+    // Every step inserts, renames and deletes one user
+    let writes: usize = latencies.iter().map(PhaseLatencies::writes).sum();
+    assert!(writes > 0 && writes.is_multiple_of(3), "{writes}");
+    assert_eq!(common::count("SELECT COUNT(*) FROM users WHERE username LIKE 'bench_user_%'").await, 0);
+}
@@ -0,0 +1,84 @@
+//! Insert strategies of the batch-size sweep: every backend stores the same
+//! users with every strategy, quotes, missing ages and non-ASCII names included
+
+mod common;
+
+use pg_benchmark::bench_clorinde::ClorindeBench;
+use pg_benchmark::bench_diesel::DieselBench;
+use pg_benchmark::bench_seaorm::SeaOrmBench;
+use pg_benchmark::bench_sqlx::SqlxBench;
+use pg_benchmark::bench_tokio_postgres::TokioPostgresBench;
+use pg_benchmark::bulk_insert::{self, BulkInsert, Strategy};
+use pg_benchmark::seed::TextScript;
+use pg_benchmark::NewUser;
+
+#[test]
+fn multi_row_statements_number_five_parameters_a_row() {
+    assert_eq!(
+        bulk_insert::multi_row_sql(2),
+        "INSERT INTO users (username, email, first_name, last_name, age) VALUES ($1, $2, $3, $4, $5), ($6, $7, $8, $9, $10)"
+    );
+}
+
+#[test]
+fn csv_quotes_text_and_leaves_missing_ages_empty() {
+    let user = NewUser { first_name: "Say \"hi\", then".into(), age: None, ..NewUser::generate(1) };
+    assert_eq!(
+        bulk_insert::csv(&[user]),
+        "\"bench_user_1\",\"bench_user_1@benchmark.com\",\"Say \"\"hi\"\", then\",\"Last1\",\n"
+    );
+}
+
+fn users() -> Vec<NewUser> {
+    let mut users: Vec<NewUser> = (0..5).map(NewUser::generate).collect();
+    users[1].first_name = "O\"Brien, \\N".into();
+    users[2].age = None;
+    users.push(NewUser::generate_in(5, TextScript::Cjk));
+    users
+}
+
+/// The `bench_user_*` rows, by username
+async fn stored() -> Vec<(String, String, String, String, Option<i32>)> {
+    let client = TokioPostgresBench::connect().await.unwrap();
+    client
+        .query(
+            "SELECT username, email, first_name, last_name, age FROM users
+             WHERE username LIKE 'bench_user_%' ORDER BY username",
+            &[],
+        )
+        .await
+        .unwrap()
+        .iter()
+        .map(|row| (row.get(0), row.get(1), row.get(2), row.get(3), row.get(4)))
+        .collect()
+}
+
+async fn insert_each_way<B: BulkInsert>(backend: &str, users: &[NewUser]) {
+    let expected: Vec<_> = users
+        .iter()
+        .map(|u| (u.username.clone(), u.email.clone(), u.first_name.clone(), u.last_name.clone(), u.age))
+        .collect();
+    let conn = B::connect().await.unwrap();
+    for strategy in Strategy::ALL {
+        B::cleanup(&conn).await.unwrap();
+        let inserted = B::insert(&conn, strategy, users).await.unwrap();
+        assert_eq!(inserted, users.len() as u64, "{backend} {strategy}");
+        assert_eq!(stored().await, expected, "{backend} {strategy}");
+    }
+    B::cleanup(&conn).await.unwrap();
+}
+
+#[tokio::test]
+async fn every_backend_inserts_the_same_users_every_way() {
+    let Some(_db) = common::lock_database().await else { return };
+    common::cleanup().await;
+    let users = users();
+
+    insert_each_way::<TokioPostgresBench>("tokio_postgres", &users).await;
+    insert_each_way::<SqlxBench>("sqlx", &users).await;
+    insert_each_way::<SeaOrmBench>("sea_orm", &users).await;
+    insert_each_way::<DieselBench>("diesel", &users).await;
+    insert_each_way::<ClorindeBench>("clorinde", &users).await;
+
+    assert!(stored().await.is_empty());
+}
@@ -0,0 +1,36 @@
+//! Buffer cache states: prewarming the schema, and the cold-cache command
+
+mod common;
+
+use pg_benchmark::bench_tokio_postgres::TokioPostgresBench;
+use pg_benchmark::cache;
+use std::time::Duration;
+
+#[test]
+fn cold_cache_command_failures_are_reported() {
+    cache::clear("true").unwrap();
+    let error = cache::clear("exit 3").unwrap_err();
+    assert!(error.to_string().contains("`exit 3` exited with"), "{error}");
+}
+
+#[tokio::test]
+async fn prewarm_loads_every_table() {
+    let Some(_db) = common::lock_database().await else { return };
+    let client = TokioPostgresBench::connect().await.unwrap();
+
+    let blocks = cache::prewarm(&client).await.unwrap();
+    let table_blocks: i64 = client
+        .query_one(
+            "SELECT (pg_relation_size('users') + pg_relation_size('posts') + pg_relation_size('comments'))
+                 / current_setting('block_size')::bigint",
+            &[],
+        )
+        .await
+        .unwrap()
+        .get(0);
+    // The indexes come on top of the tables
+    assert!(blocks > table_blocks, "{blocks} blocks prewarmed, the tables alone have {table_blocks}");
+
+    // Nothing restarted, so the server is ready right away
+    cache::wait_until_ready(Duration::from_secs(1)).await.unwrap();
+}
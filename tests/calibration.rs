@@ -0,0 +1,37 @@
+//! Calibration: every backend returns the same constant row after the
+//! server's fixed sleep
+
+mod common;
+
+use pg_benchmark::bench_clorinde::ClorindeBench;
+use pg_benchmark::bench_diesel::DieselBench;
+use pg_benchmark::bench_seaorm::SeaOrmBench;
+use pg_benchmark::bench_sqlx::SqlxBench;
+use pg_benchmark::bench_tokio_postgres::TokioPostgresBench;
+use pg_benchmark::calibration::{self, Calibration, FixedRow, SLEEP};
+use std::time::{Duration, Instant};
+
+#[test]
+fn overhead_is_what_each_query_took_beyond_the_sleep() {
+    assert_eq!(calibration::overhead(Duration::from_millis(12), 10), Duration::from_micros(200));
+    assert_eq!(calibration::overhead(Duration::from_micros(500), 1), Duration::ZERO);
+    assert_eq!(calibration::overhead(Duration::ZERO, 0), Duration::ZERO);
+}
+
+async fn returns_fixed_row<B: Calibration>(backend: &str) {
+    let conn = B::connect().await.unwrap();
+    let start = Instant::now();
+    assert_eq!(B::fixed_row(&conn, SLEEP).await.unwrap(), FixedRow::expected(), "{backend}");
+    assert!(start.elapsed() >= SLEEP, "{backend}");
+}
+
+#[tokio::test]
+async fn every_backend_returns_the_fixed_row_after_the_sleep() {
+    let Some(_db) = common::lock_database().await else { return };
+
+    returns_fixed_row::<TokioPostgresBench>("tokio_postgres").await;
+    returns_fixed_row::<SqlxBench>("sqlx").await;
+    returns_fixed_row::<SeaOrmBench>("sea_orm").await;
+    returns_fixed_row::<DieselBench>("diesel").await;
+    returns_fixed_row::<ClorindeBench>("clorinde").await;
+}
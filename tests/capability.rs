@@ -0,0 +1,42 @@
+//! The capability matrix: every backend states its capabilities and the
+//! matrix renders them. These need no database.
+
+use pg_benchmark::capability::{self, Capabilities};
+use pg_benchmark::runner::{self, BACKENDS};
+
+#[test]
+fn every_backend_states_its_capabilities() {
+    for backend in BACKENDS {
+        assert!(runner::capabilities(backend).is_some(), "{backend}");
+    }
+    assert_eq!(runner::capabilities("odbc"), None);
+
+    // The variants differ only in preparing statements once
+    let plain = runner::capabilities("tokio_postgres").unwrap();
+    let prepared = runner::capabilities("tokio_postgres_prepared").unwrap();
+    assert!(!plain.prepared_cache && prepared.prepared_cache);
+    assert_eq!(Capabilities { prepared_cache: false, ..prepared }, plain);
+    assert!(runner::capabilities("sqlx").unwrap().shared_transactions);
+    assert_eq!(runner::capabilities("sqlx_macro"), runner::capabilities("sqlx"));
+    assert_eq!(runner::capabilities("sea_query"), runner::capabilities("sqlx"));
+    // wtx caches its statements but can share neither a transaction nor a stream of rows
+    let wtx = runner::capabilities("wtx").unwrap();
+    assert!(wtx.prepared_cache && !wtx.streaming && !wtx.shared_transactions);
+    assert_eq!(runner::capabilities("null"), Some(Capabilities::NONE));
+}
+
+#[test]
+fn matrix_has_a_row_per_feature_and_a_column_per_backend() {
+    let backends = [
+        ("tokio_postgres", runner::capabilities("tokio_postgres").unwrap()),
+        ("sea_orm", runner::capabilities("sea_orm").unwrap()),
+    ];
+    let matrix = capability::matrix(&backends);
+    let lines: Vec<&str> = matrix.lines().collect();
+
+    assert_eq!(lines[0], "| Feature | tokio_postgres | sea_orm |");
+    assert_eq!(lines[1], "|---------|:---:|:---:|");
+    assert_eq!(lines.len(), 2 + Capabilities::NONE.features().len());
+    assert!(lines.contains(&"| Transactions on a shared handle | no | yes |"));
+    assert!(lines.contains(&"| COPY | yes | no |"));
+}
@@ -0,0 +1,76 @@
+//! Shared setup for the integration tests that need a live PostgreSQL
+//!
+//! Tests are skipped unless `DATABASE_URL` is set, and they hold a global lock
+//! while running because they all clean up the same `bench_user_*` rows.
+
+#![allow(dead_code)]
+
+use pg_benchmark::bench_tokio_postgres::TokioPostgresBench;
+use tokio::sync::{Mutex, MutexGuard};
+
+static DATABASE: Mutex<()> = Mutex::const_new(());
+
+/// Number of fixture users seeded by [`seed_fixture`]
+pub const FIXTURE_USERS: i64 = 6;
+/// Posts per fixture user
+pub const FIXTURE_POSTS_PER_USER: i64 = 3;
+/// Comments per fixture post
+pub const FIXTURE_COMMENTS_PER_POST: i64 = 2;
+
+/// Exclusive access to the database, or `None` when `DATABASE_URL` is not set
+pub async fn lock_database() -> Option<MutexGuard<'static, ()>> {
+    if std::env::var_os("DATABASE_URL").is_none() {
+        eprintln!("DATABASE_URL is not set, skipping database test");
+        return None;
+    }
+    Some(DATABASE.lock().await)
+}
+
+/// Replace any leftover benchmark rows with a small fixed dataset.
+///
+/// Fixture rows are named `bench_user_fixture_*` so the backends' own `cleanup`
+/// removes them, and carry distinct timestamps in the future so they sort
+/// ahead of the seeded data (which is all in the past) in every
+/// `ORDER BY created_at DESC` query.
+pub async fn seed_fixture() {
+    let client = TokioPostgresBench::connect().await.unwrap();
+    TokioPostgresBench::cleanup(&client).await.unwrap();
+
+    client
+        .batch_execute(&format!(
+            "INSERT INTO users (username, email, first_name, last_name, age, created_at, updated_at)
+             SELECT 'bench_user_fixture_' || i, 'bench_user_fixture_' || i || '@benchmark.com',
+                    'Fixture' || i, 'User' || i, 20 + i,
+                    TIMESTAMPTZ '2100-01-01 00:00:00+00' + i * INTERVAL '1 hour',
+                    TIMESTAMPTZ '2100-01-01 00:00:00+00' + i * INTERVAL '1 hour'
+             FROM generate_series(1, {FIXTURE_USERS}) i;
+
+             INSERT INTO posts (user_id, title, content, status, view_count, created_at, updated_at)
+             SELECT u.id, 'Fixture post ' || n || ' by ' || u.username, 'Fixture content ' || n,
+                    CASE n % 3 WHEN 0 THEN 'draft' ELSE 'published' END, n * 7,
+                    u.created_at + n * INTERVAL '1 minute', u.created_at + n * INTERVAL '1 minute'
+             FROM users u CROSS JOIN generate_series(1, {FIXTURE_POSTS_PER_USER}) n
+             WHERE u.username LIKE 'bench_user_fixture_%';
+
+             INSERT INTO comments (post_id, user_id, content, created_at)
+             SELECT p.id, (SELECT id FROM users WHERE username = 'bench_user_fixture_' || n),
+                    'Fixture comment ' || n || ' on ' || p.title,
+                    p.created_at + n * INTERVAL '1 second'
+             FROM posts p CROSS JOIN generate_series(1, {FIXTURE_COMMENTS_PER_POST}) n
+             WHERE p.title LIKE 'Fixture post %';"
+        ))
+        .await
+        .unwrap();
+}
+
+/// Remove everything created by a test
+pub async fn cleanup() {
+    let client = TokioPostgresBench::connect().await.unwrap();
+    TokioPostgresBench::cleanup(&client).await.unwrap();
+}
+
+/// Run a `SELECT COUNT(*) ...` and return the count
+pub async fn count(sql: &str) -> i64 {
+    let client = TokioPostgresBench::connect().await.unwrap();
+    client.query_one(sql, &[]).await.unwrap().get(0)
+}
@@ -0,0 +1,43 @@
+//! Connection modes: parsing `BENCH_CONNECTION`-style lists, which mode each
+//! group gets and the order per-sample pools hand connections out in. These
+//! need no database.
+
+use pg_benchmark::connection_mode::{ConnectionMode, ConnectionModes, SamplePool};
+
+#[test]
+fn mode_lists_parse_and_reject_unknown_modes() {
+    let modes = ConnectionModes::parse("per_sample, insert_single_user=reused,").unwrap();
+    assert_eq!(modes.default, ConnectionMode::PerSample);
+    assert_eq!(modes.groups, [("insert_single_user".to_string(), ConnectionMode::Reused)]);
+    assert_eq!(modes.to_string(), "per_sample,insert_single_user=reused");
+    assert_eq!(ConnectionModes::parse("").unwrap(), ConnectionModes::default());
+
+    assert!(ConnectionModes::parse("fresh").unwrap_err().contains("\"fresh\""));
+    assert!(ConnectionModes::parse("insert_user=fresh").unwrap_err().contains("\"fresh\""));
+    assert!(ConnectionModes::parse("=per_sample").is_err());
+}
+
+#[test]
+fn groups_take_their_own_mode_over_the_default() {
+    let modes = ConnectionModes::parse("select_user_by_id=per_sample,status_filter=per_sample,status_filter=reused").unwrap();
+    assert_eq!(modes.for_group("select_user_by_id"), ConnectionMode::PerSample);
+    assert_eq!(modes.for_group("status_filter"), ConnectionMode::Reused);
+    assert_eq!(modes.for_group("insert_single_user"), ConnectionMode::Reused);
+
+    let modes = ConnectionModes::parse("select_user_by_id=reused,per_sample").unwrap();
+    assert_eq!(modes.for_group("select_user_by_id"), ConnectionMode::Reused);
+    assert_eq!(modes.for_group("insert_single_user"), ConnectionMode::PerSample);
+    assert_eq!(ConnectionModes::default().for_group("insert_single_user"), ConnectionMode::Reused);
+}
+
+#[test]
+fn sample_pools_hand_out_the_least_recently_returned_connection() {
+    let pool = SamplePool::new([1, 2, 3]);
+    let first = pool.checkout();
+    let second = pool.checkout();
+    assert_eq!((*first, *second), (1, 2));
+    drop(first);
+    drop(second);
+    let order: Vec<i32> = (0..4).map(|_| *pool.checkout()).collect();
+    assert_eq!(order, [3, 1, 2, 3]);
+}
@@ -0,0 +1,43 @@
+//! Chunked exports: the `WITH HOLD` cursor and keyset pagination read every
+//! post once, in id order, on every backend
+
+mod common;
+
+use pg_benchmark::bench_clorinde::ClorindeBench;
+use pg_benchmark::bench_diesel::DieselBench;
+use pg_benchmark::bench_sqlx::SqlxBench;
+use pg_benchmark::bench_tokio_postgres::TokioPostgresBench;
+use pg_benchmark::cursor::{ChunkedExport, Method};
+use uuid::Uuid;
+
+/// Several chunks per export, the last one usually partial
+const CHUNK: i64 = 1000;
+
+/// The ids `method` exports, twice on one connection so the cursor must have
+/// been closed in between
+async fn export_ids<B: ChunkedExport>(method: Method) -> Vec<Uuid> {
+    let conn = B::connect().await.unwrap();
+    let mut ids = Vec::new();
+    let count = B::export(&conn, method, CHUNK, |post| ids.push(post.id)).await.unwrap();
+    assert_eq!(count, ids.len());
+    assert_eq!(B::export(&conn, method, CHUNK, |_| {}).await.unwrap(), count);
+    ids
+}
+
+#[tokio::test]
+async fn every_backend_exports_each_post_once_by_id() {
+    let Some(_db) = common::lock_database().await else { return };
+    common::seed_fixture().await;
+
+    let expected = export_ids::<TokioPostgresBench>(Method::Cursor).await;
+    assert_eq!(expected.len() as i64, common::count("SELECT COUNT(*) FROM posts").await);
+    assert!(expected.windows(2).all(|w| w[0] < w[1]));
+
+    for method in Method::ALL {
+        assert_eq!(export_ids::<TokioPostgresBench>(method).await, expected, "tokio_postgres {method}");
+        assert_eq!(export_ids::<SqlxBench>(method).await, expected, "sqlx {method}");
+        assert_eq!(export_ids::<DieselBench>(method).await, expected, "diesel {method}");
+        assert_eq!(export_ids::<ClorindeBench>(method).await, expected, "clorinde {method}");
+    }
+    common::cleanup().await;
+}
@@ -0,0 +1,87 @@
+//! Deferred constraints: every backend inserts a user with its posts and
+//! comments into the copy with immediate and deferred foreign keys, and a
+//! deferred check only fails at `COMMIT`
+
+mod common;
+
+use pg_benchmark::bench_clorinde::ClorindeBench;
+use pg_benchmark::bench_diesel::DieselBench;
+use pg_benchmark::bench_seaorm::SeaOrmBench;
+use pg_benchmark::bench_sqlx::SqlxBench;
+use pg_benchmark::bench_tokio_postgres::TokioPostgresBench;
+use pg_benchmark::deferred_constraints::{self, ConstraintTiming, DeferredConstraints, COMMENTS_PER_POST, POSTS};
+use pg_benchmark::NewUser;
+use tokio_postgres::error::SqlState;
+use tokio_postgres::Client;
+use uuid::Uuid;
+
+async fn inserts_thread<B: DeferredConstraints>(backend: &str, client: &Client, index: usize) {
+    let conn = B::connect().await.unwrap();
+    let (posts, comments) = deferred_constraints::thread();
+    for (offset, timing) in ConstraintTiming::ALL.into_iter().enumerate() {
+        let user = NewUser::generate(index * 2 + offset);
+        let id = B::insert_user_posts_comments(&conn, &user, &posts, &comments, timing).await.unwrap();
+
+        let row = client
+            .query_one(
+                "SELECT (SELECT COUNT(*) FROM deferred.posts WHERE user_id = $1),
+                        (SELECT COUNT(*) FROM deferred.comments c
+                         JOIN deferred.posts p ON p.id = c.post_id
+                         WHERE c.user_id = $1 AND p.user_id = $1)",
+                &[&id],
+            )
+            .await
+            .unwrap();
+        assert_eq!(row.get::<_, i64>(0), POSTS as i64, "{backend} ({timing})");
+        assert_eq!(row.get::<_, i64>(1), (POSTS * COMMENTS_PER_POST) as i64, "{backend} ({timing})");
+    }
+}
+
+/// Insert a comment on a post that doesn't exist, returning which statement
+/// failed: the insert or the `COMMIT`
+async fn dangling_comment(client: &Client, timing: ConstraintTiming) -> &'static str {
+    client.batch_execute(&format!("BEGIN; {}", timing.sql())).await.unwrap();
+    let inserted = client
+        .execute(
+            "INSERT INTO deferred.comments (post_id, user_id, content)
+             SELECT $1, id, 'dangling' FROM deferred.users LIMIT 1",
+            &[&Uuid::new_v4()],
+        )
+        .await;
+    if let Err(e) = inserted {
+        assert_eq!(e.code(), Some(&SqlState::FOREIGN_KEY_VIOLATION));
+        client.batch_execute("ROLLBACK").await.unwrap();
+        return "insert";
+    }
+    let e = client.batch_execute("COMMIT").await.unwrap_err();
+    assert_eq!(e.code(), Some(&SqlState::FOREIGN_KEY_VIOLATION));
+    "commit"
+}
+
+#[tokio::test]
+async fn every_backend_inserts_with_either_timing() {
+    let Some(_db) = common::lock_database().await else { return };
+    common::cleanup().await;
+    let client = TokioPostgresBench::connect().await.unwrap();
+    let copied = deferred_constraints::create(&client).await.unwrap();
+    assert_eq!(copied, common::count("SELECT COUNT(*) FROM users").await);
+
+    inserts_thread::<TokioPostgresBench>("tokio_postgres", &client, 0).await;
+    inserts_thread::<SqlxBench>("sqlx", &client, 1).await;
+    inserts_thread::<SeaOrmBench>("sea_orm", &client, 2).await;
+    inserts_thread::<DieselBench>("diesel", &client, 3).await;
+    inserts_thread::<ClorindeBench>("clorinde", &client, 4).await;
+
+    // Written to the copy only
+    assert_eq!(common::count("SELECT COUNT(*) FROM users WHERE username LIKE 'bench_user_%'").await, 0);
+
+    // The timing moves the check, not whether it fails
+    assert_eq!(dangling_comment(&client, ConstraintTiming::Immediate).await, "insert");
+    assert_eq!(dangling_comment(&client, ConstraintTiming::Deferred).await, "commit");
+
+    // The users' posts and comments go with them
+    deferred_constraints::cleanup(&client).await.unwrap();
+    let comments = client.query_one("SELECT COUNT(*) FROM deferred.comments", &[]).await.unwrap();
+    assert_eq!(comments.get::<_, i64>(0), common::count("SELECT COUNT(*) FROM comments").await);
+    deferred_constraints::drop(&client).await.unwrap();
+}
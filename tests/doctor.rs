@@ -0,0 +1,55 @@
+//! Environment diagnosis: the readiness summary, and the checks against the
+//! benchmark database
+
+mod common;
+
+use pg_benchmark::bench_sqlx::SqlxBench;
+use pg_benchmark::bench_tokio_postgres::TokioPostgresBench;
+use pg_benchmark::doctor::{self, Check, Status, Summary};
+use std::time::Duration;
+
+#[test]
+fn any_failure_means_not_ready() {
+    let check = |status| Check {
+        name: "jit".to_string(),
+        status,
+        detail: "on".to_string(),
+    };
+    assert_eq!(check(Status::Warning).to_string(), "[warn] jit: on");
+
+    let summary = Summary::of(&[check(Status::Ok), check(Status::Warning), check(Status::Ok)]);
+    assert_eq!(summary, Summary { ok: 2, warnings: 1, failed: 0 });
+    assert!(summary.ready());
+    assert_eq!(summary.to_string(), "ready, with warnings: 2 ok, 1 warning(s), 0 failed");
+
+    let summary = Summary::of(&[check(Status::Ok), check(Status::Failed)]);
+    assert!(!summary.ready());
+    assert!(summary.to_string().starts_with("not ready"));
+}
+
+#[test]
+fn a_remote_server_is_a_warning() {
+    assert_eq!(doctor::rtt_check(Duration::from_micros(80)).status, Status::Ok);
+    assert_eq!(doctor::rtt_check(Duration::from_millis(3)).status, Status::Warning);
+}
+
+#[tokio::test]
+async fn diagnoses_the_benchmark_database() {
+    let Some(_db) = common::lock_database().await else {
+        return;
+    };
+    assert_eq!(doctor::connectivity::<SqlxBench>("sqlx").await.status, Status::Ok);
+
+    let client = TokioPostgresBench::connect().await.unwrap();
+    let settings = doctor::server_settings(&client).await.unwrap();
+    let names: Vec<&str> = settings.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, ["server version", "shared_buffers", "max_connections", "jit"]);
+    assert!(settings[0].detail.starts_with("PostgreSQL "));
+
+    // init.sql creates all four; pg_stat_statements depends on how the server was started
+    let extensions = doctor::extensions(&client).await.unwrap();
+    assert!(extensions[..4].iter().all(|c| c.status == Status::Ok), "{extensions:?}");
+
+    let rtt = doctor::round_trip_time(&client).await.unwrap();
+    assert!(rtt > Duration::ZERO && rtt < Duration::from_secs(1));
+}
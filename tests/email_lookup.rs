@@ -0,0 +1,50 @@
+//! Email lookup: every backend finds a user by an email of another case, on the
+//! `citext` column and through the `LOWER(email)` index
+
+mod common;
+
+use pg_benchmark::bench_clorinde::ClorindeBench;
+use pg_benchmark::bench_diesel::DieselBench;
+use pg_benchmark::bench_seaorm::SeaOrmBench;
+use pg_benchmark::bench_sqlx::SqlxBench;
+use pg_benchmark::bench_tokio_postgres::TokioPostgresBench;
+use pg_benchmark::email_lookup::{self, EmailLookup};
+
+async fn finds_any_case<B: EmailLookup>(backend: &str, emails: &[String]) {
+    let conn = B::connect().await.unwrap();
+    for email in emails {
+        let user = B::select_user_by_email(&conn, email).await.unwrap();
+        let user = user.unwrap_or_else(|| panic!("{backend}: no citext user for {email}"));
+        assert!(user.email.eq_ignore_ascii_case(email), "{backend}");
+        assert_ne!(&user.email, email, "{backend}");
+
+        let lower = B::select_user_by_lower_email(&conn, email).await.unwrap();
+        assert_eq!(lower.as_ref(), Some(&user), "{backend}");
+    }
+    assert_eq!(B::select_user_by_email(&conn, "nobody@example.com").await.unwrap(), None, "{backend}");
+    assert_eq!(B::select_user_by_lower_email(&conn, "nobody@example.com").await.unwrap(), None, "{backend}");
+}
+
+#[tokio::test]
+async fn every_backend_matches_emails_whatever_their_case() {
+    let Some(_db) = common::lock_database().await else { return };
+    let client = TokioPostgresBench::connect().await.unwrap();
+    let copied = email_lookup::create(&client).await.unwrap();
+    assert_eq!(copied, common::count("SELECT COUNT(*) FROM users WHERE username NOT LIKE 'bench_user_%'").await);
+    let emails = email_lookup::sample_emails(&client, 3).await.unwrap();
+    assert_eq!(emails.len(), 3);
+
+    finds_any_case::<TokioPostgresBench>("tokio_postgres", &emails).await;
+    finds_any_case::<SqlxBench>("sqlx", &emails).await;
+    finds_any_case::<SeaOrmBench>("sea_orm", &emails).await;
+    finds_any_case::<DieselBench>("diesel", &emails).await;
+    finds_any_case::<ClorindeBench>("clorinde", &emails).await;
+
+    // Bound as `TEXT`, the email is compared case-sensitively
+    let pool = SqlxBench::connect().await.unwrap();
+    assert_eq!(email_lookup::select_user_by_text_email(&pool, &emails[0]).await.unwrap(), None);
+    let exact = emails[0].to_lowercase();
+    assert!(email_lookup::select_user_by_text_email(&pool, &exact).await.unwrap().is_some());
+
+    email_lookup::drop(&client).await.unwrap();
+}
@@ -0,0 +1,319 @@
+//! Cross-backend result equivalence
+//!
+//! Every backend runs the same `DatabaseBenchmark` operations against the same
+//! data and must return identical rows, in the same order, with the same
+//! values. Otherwise the benchmark would be comparing different amounts of work.
+
+mod common;
+
+use common::{FIXTURE_COMMENTS_PER_POST, FIXTURE_POSTS_PER_USER, FIXTURE_USERS};
+use pg_benchmark::{
+    bench_clorinde::ClorindeBench, bench_diesel::DieselBench, bench_seaorm::SeaOrmBench, bench_seaquery::SeaQueryBench,
+    bench_sqlx::{SqlxBench, SqlxMacroBench}, bench_tokio_postgres::{TokioPostgresBench, TokioPostgresPreparedBench}, bench_wtx::WtxBench, Comment,
+    CommentKey, DatabaseBenchmark, NewPost, NewUser, Post, User,
+};
+use uuid::Uuid;
+
+/// Results of every read query over the fixture dataset
+#[derive(Debug, PartialEq)]
+struct ReadResults {
+    users_limit: Vec<User>,
+    users_filtered: Vec<User>,
+    user_by_id: Option<User>,
+    missing_user: Option<User>,
+    posts_with_user: Vec<(Post, User)>,
+    posts_by_status: Vec<Post>,
+    posts_repeated: Vec<Post>,
+    users_posts_comments: Vec<(User, Post, Comment)>,
+    /// A fixture post's comments, one page each
+    comment_pages: Vec<Vec<Comment>>,
+    posts_per_user: Vec<(Uuid, i64)>,
+    posts_per_tag: Vec<(Uuid, i64)>,
+}
+
+async fn read_results<B: DatabaseBenchmark>() -> ReadResults {
+    let conn = B::connect().await.unwrap();
+    let users_limit = B::select_users_limit(&conn, FIXTURE_USERS).await.unwrap();
+    // Unordered by definition, so compared as a sorted multiset
+    let mut posts_repeated = B::select_posts_repeated(&conn, 2).await.unwrap();
+    posts_repeated.sort_by_key(|p| p.id);
+    let mut posts_streamed = Vec::new();
+    let streamed = B::stream_posts_repeated(&conn, 2, |post| posts_streamed.push(post)).await.unwrap();
+    assert_eq!(streamed, posts_streamed.len());
+    posts_streamed.sort_by_key(|p| p.id);
+    assert_eq!(posts_streamed, posts_repeated, "streamed and collected posts differ");
+
+    let (post, _) = &B::select_posts_with_user(&conn, 1).await.unwrap()[0];
+    let mut comment_pages = vec![B::select_comments_for_post(&conn, post.id, 1, None).await.unwrap()];
+    while let Some(before) = comment_pages.last().unwrap().last().and_then(CommentKey::of) {
+        comment_pages.push(B::select_comments_for_post(&conn, post.id, 1, Some(before)).await.unwrap());
+    }
+
+    ReadResults {
+        users_filtered: B::select_users_filtered(&conn, 25, 55, 50).await.unwrap(),
+        user_by_id: B::select_user_by_id(&conn, users_limit[0].id).await.unwrap(),
+        missing_user: B::select_user_by_id(&conn, Uuid::nil()).await.unwrap(),
+        posts_with_user: B::select_posts_with_user(&conn, FIXTURE_USERS * FIXTURE_POSTS_PER_USER)
+            .await
+            .unwrap(),
+        posts_by_status: B::select_posts_by_status(&conn, "draft", FIXTURE_USERS * FIXTURE_POSTS_PER_USER)
+            .await
+            .unwrap(),
+        posts_repeated,
+        users_posts_comments: B::select_users_posts_comments(
+            &conn,
+            FIXTURE_USERS * FIXTURE_POSTS_PER_USER * FIXTURE_COMMENTS_PER_POST,
+        )
+        .await
+        .unwrap(),
+        comment_pages,
+        posts_per_user: B::count_posts_per_user(&conn).await.unwrap(),
+        posts_per_tag: B::count_posts_per_tag(&conn).await.unwrap(),
+        users_limit,
+    }
+}
+
+/// Strip server-generated ids and timestamps so rows written in separate runs compare equal
+fn normalize_user(user: User) -> User {
+    User {
+        id: Uuid::nil(),
+        created_at: None,
+        updated_at: None,
+        ..user
+    }
+}
+
+fn normalize_post(post: Post) -> Post {
+    Post {
+        id: Uuid::nil(),
+        user_id: Uuid::nil(),
+        created_at: None,
+        updated_at: None,
+        ..post
+    }
+}
+
+/// Results of every write operation, read back through the same backend
+#[derive(Debug, PartialEq)]
+struct WriteResults {
+    inserted: Option<User>,
+    batch: Vec<Option<User>>,
+    updated: bool,
+    updated_user: Option<User>,
+    updated_at_changed: bool,
+    update_missing: bool,
+    transaction_posts: Vec<Post>,
+    batched_user: Option<User>,
+    batched_post: Post,
+    deleted: bool,
+    delete_missing: bool,
+}
+
+async fn write_results<B: DatabaseBenchmark>() -> WriteResults {
+    let conn = B::connect().await.unwrap();
+    B::cleanup(&conn).await.unwrap();
+
+    let id = B::insert_user(&conn, &NewUser::generate(1)).await.unwrap();
+    let inserted = B::select_user_by_id(&conn, id).await.unwrap();
+
+    let batch_users: Vec<NewUser> = (2..5).map(NewUser::generate).collect();
+    let mut batch = Vec::new();
+    for id in B::insert_users_batch(&conn, &batch_users).await.unwrap() {
+        batch.push(B::select_user_by_id(&conn, id).await.unwrap().map(normalize_user));
+    }
+
+    let updated = B::update_user(&conn, id, "Changed", "Person").await.unwrap();
+    let updated_user = B::select_user_by_id(&conn, id).await.unwrap().unwrap();
+    let updated_at_changed = updated_user.updated_at != inserted.as_ref().unwrap().updated_at;
+    let update_missing = B::update_user(&conn, Uuid::nil(), "Changed", "Person").await.unwrap();
+
+    let posts: Vec<NewPost> = (0..3).map(|i| NewPost::generate(Uuid::nil(), i)).collect();
+    let owner = B::insert_user_with_posts(&conn, &NewUser::generate(5), &posts)
+        .await
+        .unwrap();
+    let mut transaction_posts: Vec<Post> = B::select_posts_with_user(&conn, posts.len() as i64)
+        .await
+        .unwrap()
+        .into_iter()
+        .inspect(|(post, user)| {
+            assert_eq!(post.user_id, owner);
+            assert_eq!(user.id, owner);
+        })
+        .map(|(post, _)| normalize_post(post))
+        .collect();
+    transaction_posts.sort_by(|a, b| a.title.cmp(&b.title));
+
+    // Quotes in the inlined values must survive the multi-statement batch
+    let mut post = NewPost::generate(Uuid::nil(), 6);
+    post.title = "It's batched".to_string();
+    let batched_id = B::insert_user_post_update_batched(&conn, &NewUser::generate(6), &post, "Batched", "O'Neil")
+        .await
+        .unwrap();
+    let batched_user = B::select_user_by_id(&conn, batched_id).await.unwrap().map(normalize_user);
+    let (batched_post, _) = B::select_posts_with_user(&conn, 1).await.unwrap().remove(0);
+    assert_eq!(batched_post.user_id, batched_id);
+
+    let deleted = B::delete_user(&conn, id).await.unwrap();
+    let delete_missing = B::delete_user(&conn, id).await.unwrap();
+
+    B::cleanup(&conn).await.unwrap();
+
+    WriteResults {
+        inserted: inserted.map(normalize_user),
+        batch,
+        updated,
+        updated_user: Some(normalize_user(updated_user)),
+        updated_at_changed,
+        update_missing,
+        transaction_posts,
+        batched_user,
+        batched_post: normalize_post(batched_post),
+        deleted,
+        delete_missing,
+    }
+}
+
+/// Compare each field of `$results` against the tokio-postgres reference
+macro_rules! assert_fields_eq {
+    ($expected:expr, $actual:expr, $backend:expr, [$($field:ident),+ $(,)?]) => {
+        $(
+            assert_eq!(
+                $actual.$field, $expected.$field,
+                "{} differs from tokio_postgres in `{}`", $backend, stringify!($field)
+            );
+        )+
+    };
+}
+
+#[tokio::test]
+async fn read_queries_return_identical_results() {
+    let Some(_db) = common::lock_database().await else {
+        return;
+    };
+    common::seed_fixture().await;
+
+    let expected = read_results::<TokioPostgresBench>().await;
+    assert_eq!(expected.users_limit.len() as i64, FIXTURE_USERS);
+    assert!(expected
+        .users_limit
+        .iter()
+        .all(|u| u.username.starts_with("bench_user_fixture_")));
+    // Only the fixture has drafts, a third of its posts
+    assert_eq!(expected.posts_by_status.len() as i64, FIXTURE_USERS * FIXTURE_POSTS_PER_USER / 3);
+    assert!(expected.posts_by_status.iter().all(|p| p.status == "draft"));
+    // The seeded posts are tagged
+    assert!(!expected.posts_per_tag.is_empty());
+    // One comment per page, newest first, then an empty page
+    let pages = &expected.comment_pages;
+    assert_eq!(pages.len() as i64, FIXTURE_COMMENTS_PER_POST + 1);
+    assert!(pages[..pages.len() - 1].iter().all(|page| page.len() == 1));
+    assert!(pages.windows(2).all(|w| w[1].first().is_none_or(|next| next.created_at < w[0][0].created_at)));
+
+    let backends = [
+        ("tokio_postgres_prepared", read_results::<TokioPostgresPreparedBench>().await),
+        ("sqlx", read_results::<SqlxBench>().await),
+        ("sqlx_macro", read_results::<SqlxMacroBench>().await),
+        ("sea_orm", read_results::<SeaOrmBench>().await),
+        ("sea_query", read_results::<SeaQueryBench>().await),
+        ("diesel", read_results::<DieselBench>().await),
+        ("clorinde", read_results::<ClorindeBench>().await),
+        ("wtx", read_results::<WtxBench>().await),
+    ];
+    common::cleanup().await;
+
+    for (backend, actual) in backends {
+        assert_fields_eq!(
+            expected,
+            actual,
+            backend,
+            [
+                users_limit,
+                users_filtered,
+                user_by_id,
+                missing_user,
+                posts_with_user,
+                posts_by_status,
+                users_posts_comments,
+                comment_pages,
+                posts_per_user,
+                posts_per_tag,
+            ]
+        );
+    }
+}
+
+#[tokio::test]
+async fn write_queries_have_identical_effects() {
+    let Some(_db) = common::lock_database().await else {
+        return;
+    };
+
+    let expected = write_results::<TokioPostgresBench>().await;
+    assert!(expected.updated && expected.updated_at_changed && expected.deleted);
+    assert!(!expected.update_missing && !expected.delete_missing);
+    assert_eq!(expected.batched_post.title, "It's batched");
+    assert_eq!(expected.batched_user.as_ref().unwrap().last_name, "O'Neil");
+
+    let backends = [
+        ("tokio_postgres_prepared", write_results::<TokioPostgresPreparedBench>().await),
+        ("sqlx", write_results::<SqlxBench>().await),
+        ("sqlx_macro", write_results::<SqlxMacroBench>().await),
+        ("sea_orm", write_results::<SeaOrmBench>().await),
+        ("sea_query", write_results::<SeaQueryBench>().await),
+        ("diesel", write_results::<DieselBench>().await),
+        ("clorinde", write_results::<ClorindeBench>().await),
+        ("wtx", write_results::<WtxBench>().await),
+    ];
+
+    for (backend, actual) in backends {
+        assert_fields_eq!(
+            expected,
+            actual,
+            backend,
+            [
+                inserted,
+                batch,
+                updated,
+                updated_user,
+                updated_at_changed,
+                update_missing,
+                transaction_posts,
+                batched_user,
+                batched_post,
+                deleted,
+                delete_missing,
+            ]
+        );
+    }
+}
+
+#[tokio::test]
+async fn column_subsets_match_the_full_rows() {
+    let Some(_db) = common::lock_database().await else {
+        return;
+    };
+    common::seed_fixture().await;
+
+    let client = TokioPostgresBench::connect().await.unwrap();
+    let expected: Vec<(Uuid, String)> = TokioPostgresBench::select_users_limit(&client, FIXTURE_USERS)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|u| (u.id, u.username))
+        .collect();
+
+    let pool = SqlxBench::connect().await.unwrap();
+    let db = SeaOrmBench::connect().await.unwrap();
+    let diesel = DieselBench::connect().unwrap();
+    let backends = [
+        ("tokio_postgres", TokioPostgresBench::select_usernames_limit(&client, FIXTURE_USERS).await.unwrap()),
+        ("sqlx", SqlxBench::select_usernames_limit(&pool, FIXTURE_USERS).await.unwrap()),
+        ("sea_orm", SeaOrmBench::select_usernames_limit(&db, FIXTURE_USERS as u64).await.unwrap()),
+        ("diesel", DieselBench::select_usernames_limit(&mut diesel.get().unwrap(), FIXTURE_USERS).unwrap()),
+    ];
+    common::cleanup().await;
+
+    for (backend, actual) in backends {
+        assert_eq!(actual, expected, "{backend}");
+    }
+}
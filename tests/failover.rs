@@ -0,0 +1,67 @@
+//! Multi-host connection strings: the backends that accept a host list move
+//! on to the next host once the first goes away, and sqlx rejects the list
+
+mod common;
+
+use diesel::RunQueryDsl;
+use pg_benchmark::bench_diesel::DieselBench;
+use pg_benchmark::bench_sqlx::SqlxBench;
+use pg_benchmark::bench_tokio_postgres::TokioPostgresBench;
+use pg_benchmark::database_url;
+use pg_benchmark::failover;
+use pg_benchmark::proxy::TcpProxy;
+
+fn hosts() -> [(String, u16); 2] {
+    [("primary".to_string(), 5432), ("standby".to_string(), 5433)]
+}
+
+#[test]
+fn hosts_are_replaced_in_both_forms() {
+    assert_eq!(
+        failover::with_hosts("postgres://u:p@localhost:5432/db?sslmode=disable", &hosts()),
+        "postgres://u:p@primary:5432,standby:5433/db?sslmode=disable"
+    );
+    assert_eq!(
+        failover::with_hosts("host=localhost port=5432 dbname=db", &hosts()),
+        "dbname=db host=primary,standby port=5432,5433"
+    );
+    let [primary, standby] = hosts();
+    assert_eq!(
+        failover::failover_url("postgres://localhost/db", &primary, &standby),
+        "postgres://primary:5432,standby:5433/db?target_session_attrs=read-write"
+    );
+    assert_eq!(
+        failover::server_address("postgres://u@db.internal:6543/db").unwrap(),
+        Some(("db.internal".to_string(), 6543))
+    );
+}
+
+// diesel blocks its thread while connecting, so the proxy needs another one
+#[tokio::test(flavor = "multi_thread")]
+async fn multi_host_backends_fail_over_to_the_next_host() {
+    let Some(_db) = common::lock_database().await else { return };
+    let Some(server) = failover::server_address(&database_url()).unwrap() else {
+        eprintln!("DATABASE_URL points at a Unix socket, skipping");
+        return;
+    };
+    let proxy = TcpProxy::start(server.clone()).await.unwrap();
+    let url = failover::failover_url(&database_url(), &proxy.address(), &server);
+
+    // Both connect through the first host while it is up
+    let client = TokioPostgresBench::connect_to(&url).await.unwrap();
+    let mut conn = DieselBench::establish(&url).unwrap();
+    assert_eq!(proxy.connections(), 2);
+    client.simple_query("SELECT 1").await.unwrap();
+    diesel::sql_query("SELECT 1").execute(&mut conn).unwrap();
+
+    proxy.shutdown().await;
+    assert!(client.simple_query("SELECT 1").await.is_err());
+    assert!(diesel::sql_query("SELECT 1").execute(&mut conn).is_err());
+
+    let client = TokioPostgresBench::connect_to(&url).await.unwrap();
+    client.simple_query("SELECT 1").await.unwrap();
+    let mut conn = DieselBench::establish(&url).unwrap();
+    diesel::sql_query("SELECT 1").execute(&mut conn).unwrap();
+
+    assert!(SqlxBench::connect_single(&url).await.is_err(), "sqlx takes a single host");
+}
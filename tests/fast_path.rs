@@ -0,0 +1,50 @@
+//! Zero- and single-row lookups return the rows they are named for on every
+//! backend
+
+mod common;
+
+use pg_benchmark::bench_clorinde::ClorindeBench;
+use pg_benchmark::bench_diesel::DieselBench;
+use pg_benchmark::bench_null::NullBackend;
+use pg_benchmark::bench_seaorm::SeaOrmBench;
+use pg_benchmark::bench_sqlx::SqlxBench;
+use pg_benchmark::bench_tokio_postgres::{TokioPostgresBench, TokioPostgresPreparedBench};
+use pg_benchmark::fast_path::{self, FastPath};
+use pg_benchmark::{DatabaseBenchmark, NewUser};
+
+async fn check_rows<B: DatabaseBenchmark>(backend: &str, conn: &B::Connection) {
+    let hits: Vec<_> = B::select_users_limit(conn, 10).await.unwrap().iter().map(|u| u.id).collect();
+    let misses = fast_path::missing_ids(10);
+    for path in FastPath::ALL {
+        for iteration in 0..3 {
+            let rows = path.run::<B>(conn, &hits, &misses, iteration).await.unwrap();
+            assert_eq!(rows, path.expected_rows(), "{backend} {}", path.name());
+        }
+    }
+}
+
+async fn returns_expected_rows<B: DatabaseBenchmark>(backend: &str) {
+    check_rows::<B>(backend, &B::connect().await.unwrap()).await;
+}
+
+#[tokio::test]
+async fn null_backend_returns_expected_rows() {
+    let conn = NullBackend::new_dataset();
+    let users: Vec<NewUser> = (0..10).map(NewUser::generate).collect();
+    NullBackend::insert_users_batch(&conn, &users).unwrap();
+    check_rows::<NullBackend>("null", &conn).await;
+}
+
+#[tokio::test]
+async fn every_backend_returns_expected_rows() {
+    let Some(_db) = common::lock_database().await else { return };
+    common::seed_fixture().await;
+
+    returns_expected_rows::<TokioPostgresBench>("tokio_postgres").await;
+    returns_expected_rows::<TokioPostgresPreparedBench>("tokio_postgres_prepared").await;
+    returns_expected_rows::<SqlxBench>("sqlx").await;
+    returns_expected_rows::<SeaOrmBench>("sea_orm").await;
+    returns_expected_rows::<DieselBench>("diesel").await;
+    returns_expected_rows::<ClorindeBench>("clorinde").await;
+    common::cleanup().await;
+}
@@ -0,0 +1,66 @@
+//! Generated column: every backend inserts into the copy without writing
+//! `full_name` and reads back the value the server computed
+
+mod common;
+
+use pg_benchmark::bench_clorinde::ClorindeBench;
+use pg_benchmark::bench_diesel::DieselBench;
+use pg_benchmark::bench_seaorm::SeaOrmBench;
+use pg_benchmark::bench_sqlx::SqlxBench;
+use pg_benchmark::bench_tokio_postgres::TokioPostgresBench;
+use pg_benchmark::generated_column::{self, GeneratedColumn};
+use pg_benchmark::seed::TextScript;
+use pg_benchmark::NewUser;
+
+async fn maps_full_name<B: GeneratedColumn>(backend: &str, index: usize) {
+    let conn = B::connect().await.unwrap();
+    let user = NewUser::generate_in(index, TextScript::Cjk);
+    let id = B::insert_named_user(&conn, &user).await.unwrap();
+
+    let full_name = generated_column::full_name(&user.first_name, &user.last_name);
+    let found = B::select_by_full_name(&conn, &full_name).await.unwrap();
+    assert!(found.iter().any(|u| u.id == id && u.username == user.username), "{backend}");
+
+    let newest = B::select_named_users(&conn, 10).await.unwrap();
+    assert_eq!(newest.len(), 10, "{backend}");
+    assert_eq!(newest[0].id, id, "{backend}");
+    for u in &newest {
+        assert_eq!(u.full_name, generated_column::full_name(&u.first_name, &u.last_name), "{backend}");
+    }
+}
+
+#[tokio::test]
+async fn every_backend_reads_the_generated_column() {
+    let Some(_db) = common::lock_database().await else { return };
+    common::cleanup().await;
+    let client = TokioPostgresBench::connect().await.unwrap();
+    let copied = generated_column::create(&client).await.unwrap();
+    assert_eq!(copied, common::count("SELECT COUNT(*) FROM users").await);
+
+    maps_full_name::<TokioPostgresBench>("tokio_postgres", 0).await;
+    maps_full_name::<SqlxBench>("sqlx", 1).await;
+    maps_full_name::<SeaOrmBench>("sea_orm", 2).await;
+    maps_full_name::<DieselBench>("diesel", 3).await;
+    maps_full_name::<ClorindeBench>("clorinde", 4).await;
+
+    // Written to the copy only
+    assert_eq!(common::count("SELECT COUNT(*) FROM users WHERE username LIKE 'bench_user_%'").await, 0);
+    let written = client
+        .query_one("SELECT COUNT(*) FROM generated.users WHERE username LIKE 'bench_user_%'", &[])
+        .await
+        .unwrap();
+    assert_eq!(written.get::<_, i64>(0), 5);
+
+    // The server refuses a value for the column, which is why the models leave it out
+    let refused = client
+        .execute(
+            "INSERT INTO generated.users (username, email, first_name, last_name, full_name)
+             VALUES ('bench_user_x', 'bench_user_x@benchmark.com', 'a', 'b', 'a b')",
+            &[],
+        )
+        .await;
+    assert!(refused.is_err());
+
+    generated_column::cleanup(&client).await.unwrap();
+    generated_column::drop(&client).await.unwrap();
+}
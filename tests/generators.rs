@@ -0,0 +1,243 @@
+//! Tests for the benchmark data generators and the read/write workload mix.
+//! These need no database.
+
+use pg_benchmark::seed::{round_robin, ContentLengths, History, SeedConfig, SeedRatio, TextScript};
+use pg_benchmark::seeder::{self, TagPopularity};
+use pg_benchmark::{CollidingUsers, ConflictKey, HeavyWorkloadConfig, NewComment, NewPost, NewUser, WorkloadOp};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::collections::HashSet;
+use std::time::Duration;
+use uuid::Uuid;
+
+fn mix(ratio: f64, operations: usize) -> Vec<WorkloadOp> {
+    HeavyWorkloadConfig {
+        mixed_read_write_ratio: ratio,
+        ..HeavyWorkloadConfig::default()
+    }
+    .workload_mix(operations)
+}
+
+fn writes(mix: &[WorkloadOp]) -> usize {
+    mix.iter().filter(|op| **op == WorkloadOp::Write).count()
+}
+
+#[test]
+fn generated_users_are_unique_and_fit_the_schema() {
+    let users: Vec<NewUser> = (0..10_000).map(NewUser::generate).collect();
+
+    let usernames: HashSet<&str> = users.iter().map(|u| u.username.as_str()).collect();
+    let emails: HashSet<&str> = users.iter().map(|u| u.email.as_str()).collect();
+    assert_eq!(usernames.len(), users.len());
+    assert_eq!(emails.len(), users.len());
+
+    for user in &users {
+        // The backends' cleanup deletes by this prefix
+        assert!(user.username.starts_with("bench_user_"));
+        assert!(user.username.chars().count() <= 50);
+        assert!(user.email.chars().count() <= 100);
+        assert!(user.first_name.chars().count() <= 50);
+        assert!(user.last_name.chars().count() <= 50);
+        assert!(matches!(user.age, Some(20..=79)));
+    }
+}
+
+#[test]
+fn generated_users_are_deterministic() {
+    let a = NewUser::generate(42);
+    let b = NewUser::generate(42);
+    assert_eq!((a.username, a.email, a.age), (b.username, b.email, b.age));
+}
+
+#[test]
+fn generated_post_statuses_are_one_third_drafts() {
+    let posts: Vec<NewPost> = (0..3000).map(|i| NewPost::generate(Uuid::nil(), i)).collect();
+
+    let drafts = posts.iter().filter(|p| p.status == "draft").count();
+    let published = posts.iter().filter(|p| p.status == "published").count();
+    assert_eq!(drafts, 1000);
+    assert_eq!(published, 2000);
+    assert!(posts.iter().all(|p| p.title.chars().count() <= 200));
+}
+
+#[test]
+fn generated_comments_keep_their_ids() {
+    let (post_id, user_id) = (Uuid::new_v4(), Uuid::new_v4());
+    let comment = NewComment::generate(post_id, user_id, 7);
+    assert_eq!((comment.post_id, comment.user_id), (post_id, user_id));
+    assert!(comment.content.contains('7'));
+}
+
+#[test]
+fn content_lengths_are_log_normal() {
+    let lengths = ContentLengths::POSTS;
+    let mut sample: Vec<usize> = (0..10_000).map(|i| lengths.length(i)).collect();
+    assert_eq!(lengths.length(42), lengths.length(42));
+    sample.sort();
+
+    // Most bodies are short, a few are big enough to be TOASTed
+    let median = sample[sample.len() / 2];
+    assert!((350..=450).contains(&median), "median {median}");
+    let toasted = sample.iter().filter(|&&n| n > 2_000).count();
+    assert!((300..=800).contains(&toasted), "{toasted} over 2 kB");
+    assert!(sample.iter().all(|&n| (1..=lengths.max).contains(&n)));
+
+    let fixed = ContentLengths { sigma: 0.0, ..lengths };
+    assert!((0..100).all(|i| fixed.length(i) == lengths.median));
+}
+
+#[test]
+fn generated_content_has_the_drawn_length() {
+    let lengths = ContentLengths::COMMENTS;
+    for index in 0..1_000 {
+        let text = lengths.text("Prefix.", index);
+        assert!(text.starts_with("Prefix."));
+        assert_eq!(text.len(), lengths.length(index).max("Prefix.".len()));
+    }
+    let post = NewPost::generate(Uuid::nil(), 9);
+    assert_eq!(post.content, NewPost::generate(Uuid::nil(), 9).content);
+}
+
+#[test]
+fn seed_counts_follow_scale_and_ratio() {
+    let default = SeedConfig::default();
+    assert_eq!((default.users, default.posts(), default.comments()), (10_000, 25_000, 80_000));
+    assert_eq!((default.posting_users(), default.commented_posts()), (5_000, 20_000));
+    assert_eq!(round_robin(default.posts(), default.posting_users()), (5, 5, 0));
+
+    let ratio: SeedRatio = "1:5:20".parse().unwrap();
+    assert_eq!(ratio.to_string(), "1:5:20");
+    let config = SeedConfig { users: 1_001, ratio, ..default };
+    assert_eq!((config.posts(), config.comments()), (5_005, 20_020));
+    // 5,005 posts over 500 users: 5 users get one more
+    assert_eq!(round_robin(config.posts(), config.posting_users()), (10, 11, 5));
+
+    for invalid in ["", "1:5", "0:5:20", "1:x:20", "1:2:3:4"] {
+        assert!(invalid.parse::<SeedRatio>().is_err(), "{invalid:?}");
+    }
+}
+
+#[test]
+fn colliding_users_repeat_earlier_keys_at_the_configured_rate() {
+    let colliding = CollidingUsers::default();
+    let colliding_indexes: Vec<usize> = (0..100).filter(|&i| colliding.collides(i)).collect();
+    assert_eq!(colliding_indexes, [9, 19, 29, 39, 49, 59, 69, 79, 89, 99]);
+
+    for rate in [0.0, 0.05, 0.25, 0.5, 1.0] {
+        let colliding = CollidingUsers { conflict_rate: rate, ..colliding };
+        let users: Vec<NewUser> = (0..1_000).map(|i| colliding.generate(i)).collect();
+        let unique: HashSet<&str> = users.iter().map(|u| u.username.as_str()).collect();
+        let expected = if rate == 1.0 { 999 } else { (rate * 1_000.0) as usize };
+        assert_eq!(users.len() - unique.len(), expected, "rate {rate}");
+        assert!(!colliding.collides(0));
+    }
+
+    let user = |key| CollidingUsers { key, ..colliding }.generate(9);
+    let original = NewUser::generate(8);
+    assert_eq!((user(ConflictKey::Both).username, user(ConflictKey::Both).email), (original.username.clone(), original.email.clone()));
+    assert_eq!(user(ConflictKey::Username).username, original.username);
+    assert_eq!(user(ConflictKey::Username).email, NewUser::generate(9).email);
+    assert_eq!(user(ConflictKey::Email).username, NewUser::generate(9).username);
+    assert_eq!(user(ConflictKey::Email).email, original.email);
+}
+
+#[test]
+fn history_skews_towards_now() {
+    let history = History::DEFAULT;
+    let window = history.window();
+    assert_eq!(window, Duration::from_secs(1_095 * 86_400));
+
+    let mut rng = StdRng::seed_from_u64(3);
+    let mut ages: Vec<Duration> = (0..10_000).map(|_| history.age(&mut rng, window)).collect();
+    ages.sort();
+    assert!(ages.iter().all(|&age| age <= window));
+    // u^2 of the way back: half of the rows from the most recent quarter
+    let median = ages[ages.len() / 2].as_secs_f64() / window.as_secs_f64();
+    assert!((0.22..=0.28).contains(&median), "median {median:.3} of the window");
+
+    let flat = History { days: 0.0, ..history };
+    assert_eq!(flat.age(&mut rng, flat.window()), Duration::ZERO);
+}
+
+#[test]
+fn tags_follow_zipf_popularity() {
+    let names: HashSet<String> = (1..=seeder::TAGS).map(seeder::tag_name).collect();
+    assert_eq!(names.len(), seeder::TAGS);
+    assert!((200..=500).contains(&seeder::TAGS));
+    assert!(names.iter().all(|name| name.len() <= 50));
+
+    let popularity = TagPopularity::default();
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut picks = vec![0usize; seeder::TAGS + 1];
+    for _ in 0..20_000 {
+        let tags = popularity.sample(&mut rng, 5, &[1]);
+        assert_eq!(tags.len(), 5);
+        assert_eq!(tags.iter().collect::<HashSet<_>>().len(), 5, "{tags:?}");
+        for tag in tags {
+            picks[tag] += 1;
+        }
+    }
+    // Tag 1 is taken; otherwise the n-th tag is picked about 1/n as often as the first
+    assert_eq!(picks[1], 0);
+    assert!(picks[2] > picks[4] && picks[4] > picks[20] && picks[20] > picks[200]);
+    let ratio = picks[2] as f64 / picks[20] as f64;
+    assert!((5.0..=15.0).contains(&ratio), "tag 2 picked {ratio:.1}x as often as tag 20");
+}
+
+#[test]
+fn posts_get_zero_to_five_tags() {
+    let mut posts = [0usize; 6];
+    for index in 0..20_000 {
+        posts[seeder::tags_per_post(index)] += 1;
+    }
+    assert_eq!(posts, [2_000, 4_000, 5_000, 4_000, 3_000, 2_000]);
+}
+
+#[test]
+fn default_mix_writes_every_fifth_operation() {
+    let ops = HeavyWorkloadConfig::default().workload_mix(100);
+    assert_eq!(ops.len(), 100);
+    for (i, op) in ops.iter().enumerate() {
+        let expected = if i % 5 == 0 { WorkloadOp::Write } else { WorkloadOp::Read };
+        assert_eq!(*op, expected, "operation {i}");
+    }
+}
+
+#[test]
+fn mix_matches_the_configured_ratio() {
+    for (ratio, expected_writes) in [(1.0, 0), (0.0, 100), (0.8, 20), (0.5, 50), (0.9, 10), (0.67, 33)] {
+        assert_eq!(writes(&mix(ratio, 100)), expected_writes, "ratio {ratio}");
+    }
+}
+
+#[test]
+fn mix_spreads_writes_evenly() {
+    let ops = mix(0.75, 100);
+    for window in ops.chunks(4) {
+        assert_eq!(writes(window), 1);
+    }
+}
+
+#[test]
+fn mix_clamps_out_of_range_ratios() {
+    assert_eq!(mix(1.5, 10), mix(1.0, 10));
+    assert_eq!(mix(-0.5, 10), mix(0.0, 10));
+    assert!(mix(0.8, 0).is_empty());
+}
+
+#[test]
+fn generated_text_is_in_the_requested_script() {
+    let lengths = ContentLengths::POSTS;
+    assert_eq!(lengths.text_in(TextScript::Latin, "p", 7), lengths.text("p", 7));
+    assert!(lengths.text("p", 7).is_ascii());
+    for script in TextScript::ALL {
+        let text = lengths.text_in(script, "p", 7);
+        assert_eq!(text.chars().count(), lengths.length(7).max(1), "{script:?}");
+        assert_eq!(script.name().parse::<TextScript>(), Ok(script));
+        if script != TextScript::Latin {
+            assert!(!text.is_ascii(), "{script:?}");
+            assert!(!NewUser::generate_in(7, script).first_name.is_ascii(), "{script:?}");
+        }
+    }
+    assert!(TextScript::Combining.search_fragment().chars().any(|c| ('\u{300}'..='\u{36f}').contains(&c)));
+}
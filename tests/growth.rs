@@ -0,0 +1,56 @@
+//! Growth curves, and size samples taken during a run
+
+mod common;
+
+use pg_benchmark::growth::{self, Growth, RelationSize, SizeSample, Sampler, TABLES};
+use std::time::{Duration, Instant};
+
+fn sample(elapsed_secs: f64, table_bytes: i64) -> SizeSample {
+    SizeSample {
+        elapsed_secs,
+        relations: vec![RelationSize {
+            table: "users",
+            table_bytes,
+            index_bytes: 1000,
+        }],
+    }
+}
+
+#[test]
+fn growth_compares_the_last_sample_with_the_first() {
+    let mut growth = Growth::new(Duration::from_secs(5));
+    assert_eq!((growth.growth_bytes(), growth.curve()), (0, String::new()));
+
+    growth.samples = vec![sample(0.0, 1000), sample(5.0, 1500), sample(10.0, 3000), sample(12.5, 2000)];
+    assert_eq!((growth.start_bytes(), growth.end_bytes()), (2000, 3000));
+    assert_eq!(growth.growth_bytes(), 1000);
+    assert_eq!(growth.relative_growth(), 0.5);
+    assert_eq!(growth.curve(), "▁▃█▅");
+
+    growth.samples = vec![sample(0.0, 1000), sample(5.0, 1000)];
+    assert_eq!(growth.curve(), "▁▁");
+}
+
+#[tokio::test]
+async fn sampler_records_every_table_until_finished() {
+    let Some(_db) = common::lock_database().await else { return };
+    let sampler = Sampler::start(Duration::from_millis(100)).await;
+    let start = Instant::now();
+    tokio::select! {
+        _ = sampler.run(start) => unreachable!(),
+        _ = tokio::time::sleep(Duration::from_millis(250)) => {}
+    }
+    let growth = sampler.finish(start.elapsed()).await;
+    assert_eq!(growth.error, None);
+    // At the start, 100 and 200 ms, and the final one
+    assert!(growth.samples.len() >= 3, "{:?}", growth.samples.len());
+    for sample in &growth.samples {
+        let tables: Vec<_> = sample.relations.iter().map(|r| r.table).collect();
+        assert_eq!(tables, TABLES);
+        assert!(sample.relations.iter().all(|r| r.table_bytes > 0 && r.index_bytes > 0));
+    }
+    assert!(growth.samples.windows(2).all(|w| w[0].elapsed_secs <= w[1].elapsed_secs));
+
+    let client = pg_benchmark::bench_tokio_postgres::TokioPostgresBench::connect().await.unwrap();
+    assert_eq!(growth::sizes(&client).await.unwrap().len(), TABLES.len());
+}
@@ -0,0 +1,70 @@
+//! Hot posts: every backend pages through a hot post's comments alike, each
+//! comment once and newest first
+
+mod common;
+
+use pg_benchmark::bench_clorinde::ClorindeBench;
+use pg_benchmark::bench_diesel::DieselBench;
+use pg_benchmark::bench_seaorm::SeaOrmBench;
+use pg_benchmark::bench_seaquery::SeaQueryBench;
+use pg_benchmark::bench_sqlx::{SqlxBench, SqlxMacroBench};
+use pg_benchmark::bench_tokio_postgres::{TokioPostgresBench, TokioPostgresPreparedBench};
+use pg_benchmark::bench_wtx::WtxBench;
+use pg_benchmark::hot_posts::{self, COMMENTS_PER_POST, HOT_POSTS, PAGE_SIZE};
+use pg_benchmark::{CommentKey, DatabaseBenchmark};
+use uuid::Uuid;
+
+/// The ids of every comment on `post`, read a page at a time
+async fn page_through<B: DatabaseBenchmark>(post: Uuid) -> Vec<Uuid> {
+    let conn = B::connect().await.unwrap();
+    let mut ids = Vec::new();
+    let mut before = None;
+    loop {
+        let page = B::select_comments_for_post(&conn, post, PAGE_SIZE, before).await.unwrap();
+        assert!(page.iter().all(|c| c.post_id == post));
+        ids.extend(page.iter().map(|c| c.id));
+        match page.last() {
+            Some(last) if page.len() as i64 == PAGE_SIZE => before = CommentKey::of(last),
+            _ => return ids,
+        }
+    }
+}
+
+#[tokio::test]
+async fn every_backend_pages_through_each_comment_once() {
+    let Some(_db) = common::lock_database().await else { return };
+    let client = TokioPostgresBench::connect().await.unwrap();
+    TokioPostgresBench::cleanup(&client).await.unwrap();
+
+    let posts = hot_posts::seed::<TokioPostgresBench>(&client).await.unwrap();
+    assert_eq!(posts.len(), HOT_POSTS);
+    let post = posts[0];
+    let expected: Vec<Uuid> = client
+        .query("SELECT id FROM comments WHERE post_id = $1 ORDER BY created_at DESC, id DESC", &[&post])
+        .await
+        .unwrap()
+        .iter()
+        .map(|row| row.get(0))
+        .collect();
+    assert_eq!(expected.len(), COMMENTS_PER_POST);
+
+    assert_eq!(page_through::<TokioPostgresBench>(post).await, expected, "tokio_postgres");
+    assert_eq!(page_through::<TokioPostgresPreparedBench>(post).await, expected, "tokio_postgres_prepared");
+    assert_eq!(page_through::<SqlxBench>(post).await, expected, "sqlx");
+    assert_eq!(page_through::<SqlxMacroBench>(post).await, expected, "sqlx_macro");
+    assert_eq!(page_through::<SeaOrmBench>(post).await, expected, "sea_orm");
+    assert_eq!(page_through::<SeaQueryBench>(post).await, expected, "sea_query");
+    assert_eq!(page_through::<DieselBench>(post).await, expected, "diesel");
+    assert_eq!(page_through::<ClorindeBench>(post).await, expected, "clorinde");
+    assert_eq!(page_through::<WtxBench>(post).await, expected, "wtx");
+
+    // A page `depth` comments in starts right after the comments before it
+    let depth = COMMENTS_PER_POST / 2;
+    let before = hot_posts::key_at::<TokioPostgresBench>(&client, post, depth).await.unwrap();
+    let page = TokioPostgresBench::select_comments_for_post(&client, post, PAGE_SIZE, before).await.unwrap();
+    assert_eq!(page[0].id, expected[depth]);
+    assert_eq!(hot_posts::key_at::<TokioPostgresBench>(&client, post, 0).await.unwrap(), None);
+
+    common::cleanup().await;
+    assert_eq!(common::count(&format!("SELECT COUNT(*) FROM users WHERE username = '{}'", hot_posts::AUTHOR)).await, 0);
+}
@@ -0,0 +1,71 @@
+//! The fillfactor copies of `users` and the updates run against them
+
+mod common;
+
+use pg_benchmark::bench_sqlx::SqlxBench;
+use pg_benchmark::bench_tokio_postgres::TokioPostgresBench;
+use pg_benchmark::hot_update::{self, Window};
+use std::time::Duration;
+use tokio_postgres::Client;
+use uuid::Uuid;
+
+#[test]
+fn drift_compares_the_last_window_with_the_first() {
+    let window = |ms| Window {
+        elapsed: Duration::from_secs(5),
+        updates: 10,
+        mean_latency: Duration::from_millis(ms),
+        stats: Default::default(),
+    };
+    assert_eq!(hot_update::drift(&[window(2), window(9), window(3)]), Some(1.5));
+    assert_eq!(hot_update::drift(&[window(2)]), None);
+}
+
+/// `(id, updated_at, first_name)` of the users of `ids` in `table`, by id
+async fn snapshot(client: &Client, table: &str, ids: &[Uuid]) -> Vec<(Uuid, String, String)> {
+    let query = format!("SELECT id, updated_at::text, first_name FROM {table} WHERE id = ANY($1) ORDER BY id");
+    let rows = client.query(&query, &[&ids]).await.unwrap();
+    rows.iter().map(|row| (row.get(0), row.get(1), row.get(2))).collect()
+}
+
+#[tokio::test]
+async fn updates_reach_the_copy_and_not_the_seeded_table() {
+    let Some(_db) = common::lock_database().await else { return };
+    common::seed_fixture().await;
+    let client = TokioPostgresBench::connect().await.unwrap();
+    hot_update::create(&client, 70).await.unwrap();
+    let options: Vec<String> = client
+        .query_one("SELECT reloptions FROM pg_class WHERE oid = 'fillfactor_70.users'::regclass", &[])
+        .await
+        .unwrap()
+        .get(0);
+    assert_eq!(options, ["fillfactor=70"]);
+    let seeded = common::count("SELECT COUNT(*) FROM users WHERE username NOT LIKE 'bench_user_%'").await;
+    assert_eq!(common::count("SELECT COUNT(*) FROM fillfactor_70.users").await, seeded);
+
+    // Seeded users only, as the copy leaves the benchmark's rows out
+    let url = hot_update::database_url(70);
+    let on_copy = TokioPostgresBench::connect_to(&url).await.unwrap();
+    let users = TokioPostgresBench::select_users_limit(&on_copy, 3).await.unwrap();
+    assert!(users.iter().all(|u| !u.username.starts_with("bench_user_")));
+    let ids: Vec<_> = users.iter().map(|u| u.id).collect();
+    let seeded_before = snapshot(&client, "public.users", &ids).await;
+    let copy_before = snapshot(&client, "fillfactor_70.users", &ids).await;
+    let pool = SqlxBench::connect_to(&url).await.unwrap();
+    let mut next = 0;
+    let (updates, mean) = hot_update::update_for::<SqlxBench>(&pool, &ids, &mut next, Duration::from_millis(100))
+        .await
+        .unwrap();
+    assert!(updates > 0 && mean > Duration::ZERO);
+    assert_eq!(next, updates);
+
+    // Compared with the rows as they were, since the seeded ones may have been updated seconds ago
+    assert_eq!(snapshot(&client, "public.users", &ids).await, seeded_before);
+    let copy_after = snapshot(&client, "fillfactor_70.users", &ids).await;
+    let changed = copy_before.iter().zip(&copy_after).filter(|(before, after)| before != after).count();
+    assert_eq!(changed, updates.min(users.len()));
+    assert!(hot_update::table_stats(&client, 70).await.unwrap().heap_bytes > 0);
+
+    hot_update::drop(&client, 70).await.unwrap();
+    common::cleanup().await;
+}
@@ -0,0 +1,39 @@
+//! The secondary indexes the index comparison drops and restores
+
+mod common;
+
+use pg_benchmark::bench_tokio_postgres::TokioPostgresBench;
+use pg_benchmark::doctor::{self, Status};
+use pg_benchmark::indexes::{self, SECONDARY};
+
+async fn definitions(client: &tokio_postgres::Client) -> Vec<String> {
+    let names: Vec<&str> = SECONDARY.iter().map(|index| index.name).collect();
+    let rows = client
+        .query(
+            "SELECT indexdef FROM pg_indexes WHERE schemaname = 'public' AND indexname = ANY($1) ORDER BY indexname",
+            &[&names],
+        )
+        .await
+        .unwrap();
+    rows.iter().map(|row| row.get(0)).collect()
+}
+
+#[tokio::test]
+async fn dropped_indexes_are_restored_as_created() {
+    let Some(_db) = common::lock_database().await else { return };
+    let client = TokioPostgresBench::connect().await.unwrap();
+    indexes::restore(&client).await.unwrap();
+    let created = definitions(&client).await;
+    assert_eq!(created.len(), SECONDARY.len());
+
+    indexes::drop(&client).await.unwrap();
+    let missing = indexes::missing(&client).await.unwrap();
+    let result = doctor::secondary_indexes(&client).await.unwrap();
+    indexes::restore(&client).await.unwrap();
+    assert_eq!(missing, SECONDARY.iter().map(|index| index.name).collect::<Vec<_>>());
+    assert_eq!(result.status, Status::Warning);
+
+    assert!(indexes::missing(&client).await.unwrap().is_empty());
+    assert_eq!(doctor::secondary_indexes(&client).await.unwrap().status, Status::Ok);
+    assert_eq!(definitions(&client).await, created);
+}
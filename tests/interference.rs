@@ -0,0 +1,51 @@
+//! Aggregate interference: every backend's aggregate runs on the pool its
+//! writers share, and the writers' posts are counted and removed
+
+mod common;
+
+use pg_benchmark::bench_diesel::DieselBench;
+use pg_benchmark::bench_seaorm::SeaOrmBench;
+use pg_benchmark::bench_sqlx::SqlxBench;
+use pg_benchmark::bench_tokio_postgres::TokioPostgresBench;
+use pg_benchmark::interference::{self, PostPool, Writers, POOL_SIZE};
+use tokio_postgres::Client;
+use uuid::Uuid;
+
+async fn shares_the_pool<P: PostPool>(backend: &str, client: &Client, author: Uuid, pool: P) {
+    let users = common::count("SELECT COUNT(*) FROM users").await as usize;
+    let writers = Writers::start(&pool, 3, author);
+    for _ in 0..5 {
+        assert_eq!(pool.count_posts_per_user().await.unwrap(), users, "{backend}");
+    }
+    // Let every writer get a connection between the aggregates
+    while writers.written() < 3 {
+        tokio::task::yield_now().await;
+    }
+    let written = writers.stop().await.unwrap();
+
+    let posts = common::count(&format!("SELECT COUNT(*) FROM posts WHERE user_id = '{author}'")).await;
+    assert_eq!(posts as usize, written, "{backend}");
+    assert_eq!(interference::reset(client, author).await.unwrap() as usize, written, "{backend}");
+}
+
+#[tokio::test]
+async fn every_backend_runs_the_aggregate_alongside_writers() {
+    let Some(_db) = common::lock_database().await else { return };
+    common::cleanup().await;
+    let client = TokioPostgresBench::connect().await.unwrap();
+    let author = interference::create_author(&client).await.unwrap();
+
+    let pool = TokioPostgresBench::create_pool(POOL_SIZE);
+    shares_the_pool("tokio_postgres", &client, author, interference::TokioPostgresPosts(pool)).await;
+    let pool = SqlxBench::connect_with_pool_size(POOL_SIZE as u32).await.unwrap();
+    shares_the_pool("sqlx", &client, author, interference::SqlxPosts(pool)).await;
+    let db = SeaOrmBench::connect_with_pool_size(POOL_SIZE as u32).await.unwrap();
+    shares_the_pool("sea_orm", &client, author, interference::SeaOrmPosts(db)).await;
+    let pool = DieselBench::connect_with_pool_size(POOL_SIZE as u32).unwrap();
+    shares_the_pool("diesel", &client, author, interference::DieselPosts(pool)).await;
+    let pool = TokioPostgresBench::create_pool(POOL_SIZE);
+    shares_the_pool("clorinde", &client, author, interference::ClorindePosts(pool)).await;
+
+    common::cleanup().await;
+    assert_eq!(common::count("SELECT COUNT(*) FROM users WHERE username LIKE 'bench_user_%'").await, 0);
+}
@@ -0,0 +1,61 @@
+//! Prepared statement invalidation: which backends keep failing after a
+//! schema change changes the result type of a cached `SELECT *`
+
+mod common;
+
+use pg_benchmark::bench_tokio_postgres::TokioPostgresBench;
+use pg_benchmark::invalidation::{
+    self, CachedLookup, DieselLookup, Invalidation, SeaOrmLookup, SqlxLookup, TokioPostgresLookup,
+    TokioPostgresPreparedLookup,
+};
+use tokio_postgres::Client;
+
+const LOOKUPS: usize = 20;
+
+/// Two changes on fresh connections: adding the column, then dropping it
+async fn changes<L: CachedLookup>(admin: &Client) -> Vec<Invalidation> {
+    let mut runs = Vec::new();
+    for change in 0..2 {
+        let mut lookup = L::connect().await.unwrap();
+        runs.push(invalidation::measure(&mut lookup, admin, change, LOOKUPS).await.unwrap());
+    }
+    runs
+}
+
+#[test]
+fn changes_alternate() {
+    assert_eq!(invalidation::alter(0), invalidation::ADD_COLUMN);
+    assert_eq!(invalidation::alter(1), invalidation::DROP_COLUMN);
+    assert_eq!(invalidation::alter(2), invalidation::ADD_COLUMN);
+}
+
+#[tokio::test]
+async fn statement_caches_keep_failing_after_a_schema_change() {
+    let Some(_db) = common::lock_database().await else { return };
+    let admin = TokioPostgresBench::connect().await.unwrap();
+
+    // Prepared again on every call, so the first lookup sees the new columns
+    for run in changes::<TokioPostgresLookup>(&admin).await.into_iter().chain(changes::<DieselLookup>(&admin).await) {
+        assert_eq!((run.errors, run.first_error.as_deref()), (0, None));
+        assert!(run.recovery.is_some());
+    }
+
+    // Cached once per connection and never prepared again
+    let cached = [
+        ("tokio_postgres_prepared", changes::<TokioPostgresPreparedLookup>(&admin).await),
+        ("sqlx", changes::<SqlxLookup>(&admin).await),
+        ("sea_orm", changes::<SeaOrmLookup>(&admin).await),
+    ];
+    for (backend, runs) in cached {
+        for run in runs {
+            assert_eq!((run.errors, run.recovery), (LOOKUPS, None), "{backend}");
+            let message = run.first_error.unwrap();
+            assert!(message.contains("cached plan must not change result type"), "{backend}: {message}");
+        }
+    }
+
+    // The table is usable again on a new connection
+    let mut fresh = TokioPostgresPreparedLookup::connect().await.unwrap();
+    assert_eq!(fresh.lookup(7).await.unwrap(), invalidation::label(7));
+    invalidation::drop(&admin).await.unwrap();
+}
@@ -0,0 +1,45 @@
+//! Large-result scenario helpers: the bandwidth-limited proxy paces what it
+//! forwards, and peak memory tracks the resident set, not just Rust allocations
+
+mod common;
+
+use pg_benchmark::bench_tokio_postgres::TokioPostgresBench;
+use pg_benchmark::proxy::TcpProxy;
+use pg_benchmark::{database_url, failover, memory};
+use std::time::{Duration, Instant};
+
+#[test]
+fn peak_memory_covers_the_whole_operation() {
+    const SIZE: usize = 64 * 1024 * 1024;
+    let (len, peak) = memory::measure_peak(|| {
+        // Written to, so the pages are actually resident
+        let buffer = vec![1u8; SIZE];
+        std::hint::black_box(&buffer).len()
+    })
+    .unwrap();
+    assert_eq!(len, SIZE);
+    assert!(peak >= SIZE as u64, "peak {peak}");
+
+    // Freed memory no longer counts towards the next operation
+    let (_, peak) = memory::measure_peak(|| ()).unwrap();
+    assert!(peak < SIZE as u64 / 2, "peak {peak}");
+}
+
+#[tokio::test]
+async fn throttled_proxy_caps_the_transfer_rate() {
+    let Some(_db) = common::lock_database().await else { return };
+    let Some(server) = failover::server_address(&database_url()).unwrap() else { return };
+    // 2 Mbit/s: a 100 kB value takes at least 0.4 s
+    let proxy = TcpProxy::start_throttled(server, 250_000).await.unwrap();
+    let client = TokioPostgresBench::connect_to(&failover::with_hosts(&database_url(), &[proxy.address()]))
+        .await
+        .unwrap();
+
+    let start = Instant::now();
+    let value: String = client.query_one("SELECT repeat('x', 100000)", &[]).await.unwrap().get(0);
+    let elapsed = start.elapsed();
+    assert_eq!(value.len(), 100_000);
+    assert!(elapsed >= Duration::from_millis(350), "took {elapsed:?}");
+    assert_eq!(proxy.connections(), 1);
+    proxy.shutdown().await;
+}
@@ -0,0 +1,69 @@
+//! Logical replication lag: the report over a run's samples, and writes
+//! reaching the subscriber while the monitor samples the lag
+
+mod common;
+
+use pg_benchmark::bench_tokio_postgres::TokioPostgresBench;
+use pg_benchmark::logical::{self, Lag, LagMonitor, LagReport, LagSample};
+use pg_benchmark::NewUser;
+use std::time::Duration;
+
+#[test]
+fn report_summarizes_the_samples() {
+    assert_eq!(LagReport::default().mean_time(), Duration::ZERO);
+
+    let sample = |bytes, ms: Option<u64>| LagSample {
+        elapsed: Duration::ZERO,
+        lag: Lag {
+            bytes,
+            time: ms.map(Duration::from_millis),
+        },
+    };
+    let report = LagReport {
+        samples: vec![sample(0, None), sample(4096, Some(30)), sample(8192, Some(50)), sample(0, None)],
+    };
+    assert_eq!(report.max_bytes(), 8192);
+    assert_eq!(report.max_time(), Duration::from_millis(50));
+    // Caught-up samples count as no lag
+    assert_eq!(report.mean_time(), Duration::from_millis(20));
+    assert_eq!(report.curve(), "▁▅█▁");
+
+    // Long runs keep the peak of each share of the samples
+    let long = LagReport {
+        samples: (0..400).map(|i| sample(if i == 205 { 1000 } else { 0 }, None)).collect(),
+    };
+    assert_eq!(long.curve().chars().count(), logical::CURVE_WIDTH);
+    assert_eq!(long.curve().chars().position(|c| c == '█'), Some(20));
+}
+
+#[tokio::test]
+async fn writes_reach_the_subscriber() {
+    let Some(_db) = common::lock_database().await else { return };
+    let primary = TokioPostgresBench::connect().await.unwrap();
+    let streaming = logical::lag(&primary).await.unwrap().is_some();
+    let Some(subscriber_url) = logical::subscriber_url().filter(|_| streaming) else { return };
+    let subscriber = TokioPostgresBench::connect_to(&subscriber_url).await.unwrap();
+    assert!(logical::wait_for_catch_up(&primary, Duration::from_secs(30)).await.unwrap());
+
+    let monitor = LagMonitor::start(TokioPostgresBench::connect().await.unwrap());
+    let mut ids = Vec::new();
+    for index in 0..50 {
+        ids.push(TokioPostgresBench::insert_user(&primary, &NewUser::generate(index)).await.unwrap());
+    }
+    assert!(logical::wait_for_catch_up(&primary, Duration::from_secs(30)).await.unwrap());
+    let report = monitor.stop().await.unwrap();
+    assert!(!report.samples.is_empty(), "{report}");
+    assert!(report.samples.windows(2).all(|w| w[0].elapsed < w[1].elapsed));
+
+    for id in ids {
+        assert!(TokioPostgresBench::select_user_by_id(&subscriber, id).await.unwrap().is_some());
+    }
+    TokioPostgresBench::cleanup(&primary).await.unwrap();
+    assert!(logical::wait_for_catch_up(&primary, Duration::from_secs(30)).await.unwrap());
+    let left: i64 = subscriber
+        .query_one("SELECT COUNT(*) FROM users WHERE username LIKE 'bench_user_%'", &[])
+        .await
+        .unwrap()
+        .get(0);
+    assert_eq!(left, 0);
+}
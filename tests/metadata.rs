@@ -0,0 +1,68 @@
+//! Run metadata: library versions from the lock file, the host and, with a
+//! database, the server and dataset
+
+mod common;
+
+use pg_benchmark::bench_tokio_postgres::TokioPostgresBench;
+use pg_benchmark::metadata::{self, RunMetadata, SETTINGS};
+use pg_benchmark::seeder;
+
+#[test]
+fn library_versions_come_from_the_lock_file() {
+    let lock = r#"
+        version = 4
+
+        [[package]]
+        name = "sqlx"
+        version = "0.8.6"
+
+        [[package]]
+        name = "diesel"
+        version = "2.3.2"
+
+        [[package]]
+        name = "tokio"
+        version = "1.48.0"
+
+        [[package]]
+        name = "sqlx"
+        version = "0.7.4"
+    "#;
+    let versions = metadata::library_versions(lock);
+    assert_eq!(versions.len(), 2);
+    assert_eq!(versions["diesel"], "2.3.2");
+    assert_eq!(versions["sqlx"], "0.8.6, 0.7.4");
+    assert!(metadata::library_versions("not a lock file [").is_empty());
+}
+
+#[test]
+fn local_metadata_names_every_library_and_the_host() {
+    let local = RunMetadata::local();
+    assert_eq!(local.libraries.len(), metadata::LIBRARIES.len());
+    assert!(local.host.cpus >= 1);
+    assert_eq!(local.host.os, std::env::consts::OS);
+    assert!(local.server.is_none());
+    assert!(local.entries().iter().all(|(name, _)| name != "PostgreSQL"));
+}
+
+#[test]
+fn cpu_model_is_the_first_model_name() {
+    let cpuinfo = "processor\t: 0\nvendor_id\t: GenuineIntel\nmodel name\t: Intel(R) Xeon(R) CPU @ 2.20GHz\n\nprocessor\t: 1\nmodel name\t: other\n";
+    assert_eq!(metadata::cpu_model(cpuinfo).as_deref(), Some("Intel(R) Xeon(R) CPU @ 2.20GHz"));
+    assert_eq!(metadata::cpu_model(""), None);
+}
+
+#[tokio::test]
+async fn collected_metadata_describes_the_server_and_dataset() {
+    let Some(_db) = common::lock_database().await else { return };
+    let client = TokioPostgresBench::connect().await.unwrap();
+    let collected = RunMetadata::collect(&client).await.unwrap();
+    let server = collected.server.as_ref().unwrap();
+
+    let version: String = client.query_one("SHOW server_version", &[]).await.unwrap().get(0);
+    assert_eq!(server.version, version);
+    assert_eq!(server.settings.len(), SETTINGS.len());
+    assert_eq!(server.dataset, seeder::count(&client).await.unwrap());
+    let entries = collected.entries();
+    assert!(entries.iter().any(|(name, value)| name == "Settings" && value.contains("shared_buffers=")));
+}
@@ -0,0 +1,94 @@
+//! Outlier filtering and the noise score: the fences, the configuration and
+//! what each action does to noisy results. These need no database.
+
+use pg_benchmark::noise::{self, NoiseAction, NoiseConfig};
+use pg_benchmark::runner::{Mode, RunResult, RunnerConfig};
+use std::time::Duration;
+
+fn result(backend: &'static str, cv: f64, noisy: bool) -> RunResult {
+    RunResult {
+        scenario: "select_user_by_id",
+        backend,
+        mode: Mode::Throughput,
+        size: 1,
+        concurrency: 1,
+        operations: 100,
+        errors: 0,
+        first_error: None,
+        elapsed_secs: 1.0,
+        throughput: 100.0,
+        mean_us: 100.0,
+        stddev_us: cv * 100.0,
+        p50_us: 100.0,
+        p95_us: 150.0,
+        p99_us: 200.0,
+        max_us: 300.0,
+        outliers: 0,
+        cv,
+        noisy,
+        growth: None,
+    }
+}
+
+#[test]
+fn noise_settings_parse_with_defaults() {
+    let config = RunnerConfig::from_toml("[noise]\noutlier_fence = 3.0\naction = \"rerun\"").unwrap();
+    assert_eq!(config.noise.outlier_fence, Some(3.0));
+    assert_eq!(config.noise.action, NoiseAction::Rerun);
+    assert_eq!(config.noise.max_cv, noise::DEFAULT_MAX_CV);
+
+    let default = RunnerConfig::from_toml("").unwrap().noise;
+    assert_eq!(default, NoiseConfig::default());
+    assert_eq!(default.action, NoiseAction::Flag);
+    assert_eq!(default.outlier_fence, None);
+}
+
+#[test]
+fn latencies_beyond_the_fences_are_outliers() {
+    let mut latencies: Vec<Duration> = (100..200).map(Duration::from_micros).collect();
+    latencies.push(Duration::from_millis(50));
+    latencies.sort();
+
+    // Quartiles 125us and 175us, so the fences sit 150us beyond them
+    let (low, high) = noise::fences(&latencies, 3.0);
+    assert_eq!(low, Duration::ZERO);
+    assert_eq!(high, Duration::from_micros(325));
+
+    let filtered = NoiseConfig { outlier_fence: Some(3.0), ..NoiseConfig::default() };
+    let (kept, outliers) = filtered.without_outliers(&latencies);
+    assert_eq!(outliers, 1);
+    assert_eq!(kept.last(), Some(&Duration::from_micros(199)));
+
+    let (kept, outliers) = NoiseConfig::default().without_outliers(&latencies);
+    assert_eq!((kept.len(), outliers), (latencies.len(), 0));
+    assert_eq!(NoiseConfig::default().without_outliers(&[]), (&[][..], 0));
+}
+
+#[test]
+fn coefficient_of_variation_is_relative_spread() {
+    assert_eq!(noise::coefficient_of_variation(200.0, 50.0), 0.25);
+    assert_eq!(noise::coefficient_of_variation(0.0, 0.0), 0.0);
+    assert!(NoiseConfig::default().is_noisy(0.6));
+    assert!(!NoiseConfig::default().is_noisy(0.5));
+    assert_eq!(noise::run_noise(&[result("sqlx", 0.1, false), result("diesel", 0.7, true)]), 0.7);
+}
+
+#[test]
+fn only_the_configured_action_drops_or_reruns() {
+    let results = vec![result("sqlx", 0.1, false), result("diesel", 0.7, true)];
+
+    let flag = NoiseConfig::default();
+    assert!(!flag.wants_rerun(&results));
+    let (kept, dropped) = flag.keep(results.clone());
+    assert_eq!((kept.len(), dropped.len()), (2, 0));
+
+    let drop = NoiseConfig { action: NoiseAction::Drop, ..NoiseConfig::default() };
+    let (kept, dropped) = drop.keep(results.clone());
+    assert_eq!(kept[0].backend, "sqlx");
+    assert_eq!(dropped[0].backend, "diesel");
+
+    let rerun = NoiseConfig { action: NoiseAction::Rerun, ..NoiseConfig::default() };
+    assert!(rerun.wants_rerun(&results));
+    assert!(!rerun.wants_rerun(&results[..1]));
+    assert_eq!(rerun.keep(results).0.len(), 2);
+}
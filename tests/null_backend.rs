@@ -0,0 +1,154 @@
+//! Tests for the in-memory `NullBackend`, which need no database.
+//!
+//! Each test works on its own freshly seeded dataset, so they can run in
+//! parallel without sharing rows.
+
+use pg_benchmark::bench_null::{
+    NullBackend, NullError, SEED_COMMENTS_PER_POST, SEED_POSTING_USERS, SEED_POSTS_PER_USER, SEED_USERS,
+};
+use pg_benchmark::{CollidingUsers, CommentKey, ConflictKey, DatabaseBenchmark, NewPost, NewUser};
+use uuid::Uuid;
+
+#[test]
+fn selects_follow_the_sql_ordering() {
+    let conn = NullBackend::new_dataset();
+
+    let users = NullBackend::select_users_limit(&conn, 10).unwrap();
+    assert_eq!(users.len(), 10);
+    assert_eq!(users[0].username, format!("user_{SEED_USERS}"));
+    assert!(users.windows(2).all(|w| w[0].created_at > w[1].created_at));
+
+    let filtered = NullBackend::select_users_filtered(&conn, 25, 35, 100).unwrap();
+    assert_eq!(filtered.len(), 100);
+    assert!(filtered
+        .windows(2)
+        .all(|w| (w[0].age, &w[0].username) <= (w[1].age, &w[1].username)));
+    assert!(filtered.iter().all(|u| (25..=35).contains(&u.age.unwrap())));
+
+    let repeated = NullBackend::select_posts_repeated(&conn, 3).unwrap();
+    assert_eq!(repeated.len(), 3 * SEED_POSTING_USERS * SEED_POSTS_PER_USER);
+    assert_eq!(repeated.iter().filter(|p| p.id == repeated[0].id).count(), 3);
+    let mut streamed = Vec::new();
+    let count = NullBackend::stream_posts_repeated(&conn, 3, |post| streamed.push(post)).unwrap();
+    assert_eq!((count, streamed), (repeated.len(), repeated));
+
+    let archived = NullBackend::select_posts_by_status(&conn, "archived", 10).unwrap();
+    assert_eq!(archived.len(), 10);
+    assert!(archived.iter().all(|p| p.status == "archived"));
+    assert!(archived.windows(2).all(|w| w[0].created_at >= w[1].created_at));
+
+    let joined = NullBackend::select_users_posts_comments(&conn, 50).unwrap();
+    assert_eq!(joined.len(), 50);
+    assert!(joined
+        .iter()
+        .all(|(u, p, c)| p.user_id == u.id && c.post_id == p.id));
+
+    let post = joined[0].1.id;
+    let first = NullBackend::select_comments_for_post(&conn, post, 3, None).unwrap();
+    assert_eq!(first.len(), 3);
+    assert!(first.windows(2).all(|w| w[0].created_at > w[1].created_at));
+    let rest = NullBackend::select_comments_for_post(&conn, post, 3, CommentKey::of(&first[2])).unwrap();
+    assert_eq!(rest.len(), SEED_COMMENTS_PER_POST - 3);
+    assert!(rest.iter().all(|c| c.post_id == post && c.created_at < first[2].created_at));
+
+    let counts = NullBackend::count_posts_per_user(&conn).unwrap();
+    assert_eq!(counts.len(), SEED_USERS);
+    assert_eq!(counts[0].1, SEED_POSTS_PER_USER as i64);
+    assert_eq!(
+        counts.iter().filter(|(_, n)| *n > 0).count(),
+        SEED_POSTING_USERS
+    );
+    assert!(NullBackend::count_posts_per_tag(&conn).unwrap().is_empty());
+}
+
+#[test]
+fn writes_enforce_the_schema_constraints() {
+    let conn = NullBackend::new_dataset();
+
+    let id = NullBackend::insert_user(&conn, &NewUser::generate(1)).unwrap();
+    assert_eq!(
+        NullBackend::insert_user(&conn, &NewUser::generate(1)),
+        Err(NullError::UniqueViolation("users.username"))
+    );
+
+    // A batch with a duplicate inside is rejected as a whole
+    let batch = [NewUser::generate(2), NewUser::generate(2)];
+    assert!(NullBackend::insert_users_batch(&conn, &batch).is_err());
+    assert_eq!(NullBackend::leftover_rows(&conn).users, 1);
+
+    assert_eq!(
+        NullBackend::insert_post(&conn, &NewPost::generate(Uuid::nil(), 0)),
+        Err(NullError::ForeignKeyViolation("posts.user_id"))
+    );
+    NullBackend::insert_post(&conn, &NewPost::generate(id, 0)).unwrap();
+
+    assert!(NullBackend::update_user(&conn, id, "Updated", "Name").unwrap());
+    let user = NullBackend::select_user_by_id(&conn, id).unwrap().unwrap();
+    assert_eq!((user.first_name.as_str(), user.last_name.as_str()), ("Updated", "Name"));
+    assert!(user.updated_at > user.created_at);
+    assert!(!NullBackend::update_user(&conn, Uuid::nil(), "a", "b").unwrap());
+
+    // The batched write step is all or nothing, like the SQL batch
+    let post = NewPost::generate(Uuid::nil(), 3);
+    assert!(NullBackend::insert_user_post_update_batched(&conn, &NewUser::generate(1), &post, "a", "b").is_err());
+    let id = NullBackend::insert_user_post_update_batched(&conn, &NewUser::generate(3), &post, "a", "b").unwrap();
+    let user = NullBackend::select_user_by_id(&conn, id).unwrap().unwrap();
+    assert_eq!((user.first_name.as_str(), user.last_name.as_str()), ("a", "b"));
+    let leftover = NullBackend::leftover_rows(&conn);
+    assert_eq!((leftover.users, leftover.posts), (2, 2));
+}
+
+#[test]
+fn colliding_users_fail_at_the_configured_rate() {
+    let conn = NullBackend::new_dataset();
+
+    let colliding = CollidingUsers { conflict_rate: 0.2, key: ConflictKey::Email };
+    let failures = (0..100)
+        .filter(|&i| NullBackend::insert_user(&conn, &colliding.generate(i)).is_err())
+        .count();
+    assert_eq!(failures, 20);
+    assert_eq!(NullBackend::leftover_rows(&conn).users, 80);
+    assert_eq!(
+        NullBackend::insert_user(&conn, &colliding.generate(4)),
+        Err(NullError::UniqueViolation("users.email"))
+    );
+}
+
+#[test]
+fn deleting_a_user_cascades_to_posts_and_comments() {
+    let conn = NullBackend::new_dataset();
+    let (_, post, _) = NullBackend::select_users_posts_comments(&conn, 1)
+        .unwrap()
+        .remove(0);
+    let before = NullBackend::select_posts_with_user(&conn, i64::MAX).unwrap().len();
+
+    assert!(NullBackend::delete_user(&conn, post.user_id).unwrap());
+    assert!(!NullBackend::delete_user(&conn, post.user_id).unwrap());
+
+    let posts = NullBackend::select_posts_with_user(&conn, i64::MAX).unwrap();
+    assert_eq!(posts.len(), before - SEED_POSTS_PER_USER);
+    let comments = NullBackend::select_users_posts_comments(&conn, i64::MAX).unwrap();
+    assert!(comments.iter().all(|(_, p, c)| p.user_id != post.user_id && c.user_id != post.user_id));
+}
+
+#[tokio::test]
+async fn cleanup_removes_everything_the_benchmarks_insert() {
+    let conn = NullBackend::new_dataset();
+    let posts: Vec<NewPost> = (0..3).map(|i| NewPost::generate(Uuid::nil(), i)).collect();
+
+    <NullBackend as DatabaseBenchmark>::insert_users_batch(&conn, &[NewUser::generate(1), NewUser::generate(2)])
+        .await
+        .unwrap();
+    <NullBackend as DatabaseBenchmark>::insert_user_with_posts(&conn, &NewUser::generate(3), &posts)
+        .await
+        .unwrap();
+    let leftover = NullBackend::leftover_rows(&conn);
+    assert_eq!((leftover.users, leftover.posts), (3, 3));
+
+    <NullBackend as DatabaseBenchmark>::cleanup(&conn).await.unwrap();
+    assert!(NullBackend::leftover_rows(&conn).is_empty());
+    assert_eq!(
+        NullBackend::count_posts_per_user(&conn).unwrap().len(),
+        SEED_USERS
+    );
+}
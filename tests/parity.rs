@@ -0,0 +1,74 @@
+//! Connection-settings parity: the recorded pool settings and the checks
+//! that decide whether a benchmark run may start
+
+mod common;
+
+use pg_benchmark::parity::{self, ConnectionSettings, ParityConfig, Severity, StatementCache};
+
+fn settings(backend: &'static str, pool_size: u32, statement_cache: StatementCache) -> ConnectionSettings {
+    ConnectionSettings {
+        backend,
+        pool_size,
+        statement_cache,
+        tcp_nodelay: true,
+        tcp_keepalive: true,
+    }
+}
+
+#[test]
+fn pool_size_and_nodelay_mismatches_are_errors() {
+    let mut nagle = settings("c", 10, StatementCache::None);
+    nagle.tcp_nodelay = false;
+    let recorded = [
+        settings("a", 10, StatementCache::None),
+        settings("b", 100, StatementCache::None),
+        nagle,
+    ];
+
+    let issues = parity::check(&recorded, &ParityConfig::with_pool_size(10));
+    let problems: Vec<String> = issues.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        problems,
+        ["error: b pool size is 100, expected 10", "error: c TCP_NODELAY is false, expected true"]
+    );
+}
+
+#[test]
+fn library_differences_are_warnings() {
+    let mut no_keepalive = settings("c", 10, StatementCache::Unbounded);
+    no_keepalive.tcp_keepalive = false;
+    let recorded = [
+        settings("a", 10, StatementCache::None),
+        settings("b", 10, StatementCache::Capacity(100)),
+        no_keepalive,
+        settings("d", 10, StatementCache::Unknown),
+    ];
+
+    let issues = parity::check(&recorded, &ParityConfig::with_pool_size(10));
+    assert!(issues.iter().all(|i| i.severity == Severity::Warning));
+    let problems: Vec<String> = issues.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        problems,
+        [
+            "warning: b statement cache is 100 per connection, expected none (as a)",
+            "warning: c statement cache is unbounded, expected none (as a)",
+            "warning: c TCP keepalive is false, expected true (as a)",
+            "warning: d statement cache is unknown, expected none (as a)",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn benchmark_pools_have_the_requested_size() {
+    let Some(_db) = common::lock_database().await else {
+        return;
+    };
+
+    let recorded = parity::pooled_settings(3).await.unwrap();
+    let backends: Vec<&str> = recorded.iter().map(|s| s.backend).collect();
+    assert_eq!(backends, ["tokio_postgres", "tokio_postgres_bb8", "tokio_postgres_mobc", "sqlx", "sea_orm", "diesel"]);
+
+    let issues = parity::check(&recorded, &ParityConfig::with_pool_size(3));
+    assert!(issues.iter().all(|i| i.severity == Severity::Warning), "{issues:#?}");
+    assert_eq!(recorded[3].statement_cache, StatementCache::Capacity(100));
+}
@@ -0,0 +1,78 @@
+//! The blocking `postgres` backend reads the same rows as tokio-postgres and
+//! writes rows tokio-postgres reads back
+//!
+//! The blocking client runs on a runtime of its own, so it is only called
+//! outside `rt.block_on`.
+
+mod common;
+
+use pg_benchmark::bench_postgres_sync::PostgresSyncBench;
+use pg_benchmark::bench_tokio_postgres::TokioPostgresBench;
+use pg_benchmark::{NewPost, NewUser};
+use tokio::runtime::Runtime;
+use uuid::Uuid;
+
+#[test]
+fn reads_match_tokio_postgres() {
+    let rt = Runtime::new().unwrap();
+    let Some(_db) = rt.block_on(common::lock_database()) else {
+        return;
+    };
+    rt.block_on(common::seed_fixture());
+    let client = rt.block_on(TokioPostgresBench::connect()).unwrap();
+    let mut sync = PostgresSyncBench::connect().unwrap();
+
+    let users = PostgresSyncBench::select_users_limit(&mut sync, 10).unwrap();
+    assert_eq!(users, rt.block_on(TokioPostgresBench::select_users_limit(&client, 10)).unwrap());
+    assert_eq!(
+        PostgresSyncBench::select_user_by_id(&mut sync, users[0].id).unwrap(),
+        Some(users[0].clone())
+    );
+    assert_eq!(PostgresSyncBench::select_user_by_id(&mut sync, Uuid::nil()).unwrap(), None);
+    assert_eq!(
+        PostgresSyncBench::select_users_filtered(&mut sync, 18, 80, 10).unwrap(),
+        rt.block_on(TokioPostgresBench::select_users_filtered(&client, 18, 80, 10)).unwrap()
+    );
+    assert_eq!(
+        PostgresSyncBench::select_posts_with_user(&mut sync, 10).unwrap(),
+        rt.block_on(TokioPostgresBench::select_posts_with_user(&client, 10)).unwrap()
+    );
+    assert_eq!(
+        PostgresSyncBench::select_users_posts_comments(&mut sync, 10).unwrap(),
+        rt.block_on(TokioPostgresBench::select_users_posts_comments(&client, 10)).unwrap()
+    );
+    let counts = PostgresSyncBench::count_posts_per_user(&mut sync).unwrap();
+    assert_eq!(counts.len(), rt.block_on(common::count("SELECT COUNT(*) FROM users")) as usize);
+
+    rt.block_on(common::cleanup());
+}
+
+#[test]
+fn writes_are_read_back_by_tokio_postgres() {
+    let rt = Runtime::new().unwrap();
+    let Some(_db) = rt.block_on(common::lock_database()) else {
+        return;
+    };
+    rt.block_on(common::cleanup());
+    let client = rt.block_on(TokioPostgresBench::connect()).unwrap();
+    let mut sync = PostgresSyncBench::connect().unwrap();
+
+    let posts: Vec<NewPost> = (0..3).map(|i| NewPost::generate(Uuid::nil(), i)).collect();
+    let user_id = PostgresSyncBench::insert_user_with_posts(&mut sync, &NewUser::generate(0), &posts).unwrap();
+    let count = rt.block_on(common::count(&format!("SELECT COUNT(*) FROM posts WHERE user_id = '{user_id}'")));
+    assert_eq!(count, 3);
+
+    // A taken username fails the transaction without leaving the posts behind
+    assert!(PostgresSyncBench::insert_user_with_posts(&mut sync, &NewUser::generate(0), &posts).is_err());
+    assert_eq!(rt.block_on(common::count("SELECT COUNT(*) FROM users WHERE username LIKE 'bench_user_%'")), 1);
+
+    let ids = PostgresSyncBench::insert_users_batch(&mut sync, &[NewUser::generate(1), NewUser::generate(2)]).unwrap();
+    assert!(PostgresSyncBench::update_user(&mut sync, ids[0], "Updated", "Name").unwrap());
+    let updated = rt.block_on(TokioPostgresBench::select_user_by_id(&client, ids[0])).unwrap().unwrap();
+    assert_eq!((updated.first_name.as_str(), updated.last_name.as_str()), ("Updated", "Name"));
+    assert!(PostgresSyncBench::delete_user(&mut sync, ids[1]).unwrap());
+    assert!(!PostgresSyncBench::delete_user(&mut sync, ids[1]).unwrap());
+
+    PostgresSyncBench::cleanup(&mut sync).unwrap();
+    assert_eq!(rt.block_on(common::count("SELECT COUNT(*) FROM users WHERE username LIKE 'bench_user_%'")), 0);
+}
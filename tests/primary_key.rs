@@ -0,0 +1,67 @@
+//! The BIGSERIAL-keyed copy of the dataset, and every backend's operations on it
+
+mod common;
+
+use common::{FIXTURE_POSTS_PER_USER, FIXTURE_USERS};
+use pg_benchmark::bench_diesel::DieselBench;
+use pg_benchmark::bench_seaorm::SeaOrmBench;
+use pg_benchmark::bench_sqlx::SqlxBench;
+use pg_benchmark::bench_tokio_postgres::TokioPostgresBench;
+use pg_benchmark::primary_key::{self, KeyType, SerialKeys, SerialPost, SerialUser};
+use pg_benchmark::NewUser;
+
+#[test]
+fn key_types_parse_by_name() {
+    assert_eq!("BigSerial".parse::<KeyType>(), Ok(KeyType::Bigserial));
+    assert_eq!(KeyType::Uuid.to_string().parse::<KeyType>(), Ok(KeyType::Uuid));
+    assert!("serial".parse::<KeyType>().is_err());
+}
+
+/// The fixture's posts, which are newer than any seeded one and created at distinct times
+const FIXTURE_POSTS: i64 = FIXTURE_USERS * FIXTURE_POSTS_PER_USER;
+
+/// Insert `user`, then read it back and the fixture's posts with their author
+async fn serial_operations<B: SerialKeys>(user: &NewUser) -> (SerialUser, Vec<(SerialPost, SerialUser)>) {
+    let conn = B::connect().await.unwrap();
+    let id = B::insert_user_serial(&conn, user).await.unwrap();
+    let found = B::select_user_by_serial_id(&conn, id).await.unwrap().unwrap();
+    assert_eq!(found.id, id);
+    assert!(B::select_user_by_serial_id(&conn, -1).await.unwrap().is_none());
+    (found, B::select_posts_with_user_serial(&conn, FIXTURE_POSTS).await.unwrap())
+}
+
+#[tokio::test]
+async fn every_backend_runs_on_the_bigserial_copy() {
+    let Some(_db) = common::lock_database().await else { return };
+    common::seed_fixture().await;
+    let client = TokioPostgresBench::connect().await.unwrap();
+    let users = common::count("SELECT COUNT(*) FROM users").await;
+    assert_eq!(primary_key::seed(&client).await.unwrap(), users);
+    for table in ["posts", "comments"] {
+        let copied = common::count(&format!("SELECT COUNT(*) FROM bigserial.{table}")).await;
+        assert_eq!(copied, common::count(&format!("SELECT COUNT(*) FROM {table}")).await, "{table}");
+    }
+    assert_eq!(primary_key::sample_user_ids(&client, 5).await.unwrap().len(), 5);
+
+    // Keys follow creation order, so the newest rows have the highest keys
+    let (_, expected_join) = serial_operations::<TokioPostgresBench>(&NewUser::generate(1)).await;
+    assert!(expected_join.windows(2).all(|w| w[0].0.id > w[1].0.id));
+    let uuid_join = TokioPostgresBench::select_posts_with_user(&client, FIXTURE_POSTS).await.unwrap();
+    let serial_titles: Vec<&str> = expected_join.iter().map(|(p, _)| p.title.as_str()).collect();
+    let uuid_titles: Vec<&str> = uuid_join.iter().map(|(p, _)| p.title.as_str()).collect();
+    assert_eq!(serial_titles, uuid_titles);
+
+    let (user, join) = serial_operations::<SqlxBench>(&NewUser::generate(2)).await;
+    assert_eq!(user.username, "bench_user_2");
+    assert_eq!(join, expected_join);
+    let (user, join) = serial_operations::<SeaOrmBench>(&NewUser::generate(3)).await;
+    assert_eq!(user.username, "bench_user_3");
+    assert_eq!(join, expected_join);
+    let (user, join) = serial_operations::<DieselBench>(&NewUser::generate(4)).await;
+    assert_eq!(user.username, "bench_user_4");
+    assert_eq!(join, expected_join);
+
+    primary_key::cleanup(&client).await.unwrap();
+    assert_eq!(common::count("SELECT COUNT(*) FROM bigserial.users WHERE username LIKE 'bench_user_%'").await, 0);
+    common::cleanup().await;
+}
@@ -0,0 +1,47 @@
+//! The live progress display's counts and status line. These need no database.
+
+use pg_benchmark::progress::{LiveProgress, Snapshot, WARM_UP};
+use std::time::Duration;
+
+#[test]
+fn counts_belong_to_the_running_benchmark() {
+    let progress = LiveProgress::new(false);
+    assert!(progress.snapshot().is_none());
+
+    progress.group("heavy_mixed_workload", Duration::from_secs(30));
+    progress.start("sqlx");
+    progress.operation(true);
+    progress.operation(false);
+    progress.operation(true);
+    let snapshot = progress.snapshot().unwrap();
+    assert_eq!((snapshot.group.as_str(), snapshot.backend.as_str()), ("heavy_mixed_workload", "sqlx"));
+    assert_eq!((snapshot.operations, snapshot.errors), (3, 1));
+    assert_eq!(snapshot.budget, Some(WARM_UP + Duration::from_secs(30)));
+
+    // The next backend starts from zero, and nothing runs between benchmarks
+    progress.finish();
+    assert!(progress.snapshot().is_none());
+    progress.start("diesel");
+    assert_eq!(progress.snapshot().unwrap().operations, 0);
+}
+
+#[test]
+fn completion_is_estimated_from_the_time_budget() {
+    let snapshot = |elapsed: u64, budget: Option<u64>| Snapshot {
+        group: "concurrent_reads".to_string(),
+        backend: "sea_orm".to_string(),
+        elapsed: Duration::from_secs(elapsed),
+        budget: budget.map(Duration::from_secs),
+        operations: 1200,
+        errors: 2,
+    };
+    assert_eq!(snapshot(0, Some(23)).percent(), 0);
+    assert_eq!(snapshot(23, Some(46)).percent(), 50);
+    // Slow iterations overrun the budget, which is not yet completion
+    assert_eq!(snapshot(60, Some(23)).percent(), 99);
+    assert_eq!(snapshot(60, None).percent(), 0);
+
+    let line = snapshot(23, Some(46)).line(410.26);
+    assert!(line.starts_with("concurrent_reads/sea_orm   50%"), "{line}");
+    assert!(line.contains("1200 ops") && line.contains("410.3 ops/s") && line.ends_with("2 error(s)"), "{line}");
+}
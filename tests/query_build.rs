@@ -0,0 +1,43 @@
+//! The SQL each builder generates for the query builder microbenchmark: the
+//! same statement from every backend, with the values bound or inlined. These
+//! need no database.
+
+use pg_benchmark::query_build::{self, Query};
+use uuid::Uuid;
+
+const ID: Uuid = Uuid::from_u128(0x6a2f41a3_c54b_4c3e_9d3f_0e8d1a2b3c4d);
+
+#[test]
+fn every_builder_generates_the_same_statement() {
+    for &query in Query::ALL {
+        let diesel = query_build::diesel(query, ID, 25);
+        let sea_orm = query_build::sea_orm(query, ID, 25);
+        let (sea_query, _values) = query_build::sea_query(query, ID, 25);
+        let raw = query_build::raw(query, ID, 25);
+
+        let join = query == Query::PostsWithUser;
+        for sql in [&diesel, &sea_orm.sql, &sea_query, &raw] {
+            assert!(sql.contains("users") && sql.contains("posts") == join, "{sql}");
+            assert!(sql.contains("JOIN") == join && sql.contains("ORDER BY") != (query == Query::UserById), "{sql}");
+        }
+        // The builders bind the values, the raw SQL carries them as literals
+        let (diesel_sql, _binds) = diesel.split_once(" -- binds: ").unwrap();
+        for sql in [diesel_sql, &sea_orm.sql, &sea_query] {
+            assert!(!sql.contains("25") && !sql.contains(&ID.to_string()), "{sql}");
+        }
+        let literal = if query == Query::UserById { format!("'{ID}'") } else { "LIMIT 25".to_string() };
+        assert!(raw.contains(&literal), "{raw}");
+    }
+}
+
+#[test]
+fn point_lookups_bind_the_id_and_a_limit_of_one() {
+    let diesel = query_build::diesel(Query::UserById, ID, 25);
+    assert!(diesel.ends_with(&format!("LIMIT $2 -- binds: [{ID}, 1]")), "{diesel}");
+
+    let sea_orm = query_build::sea_orm(Query::UserById, ID, 25);
+    assert!(sea_orm.sql.ends_with("LIMIT $2"), "{}", sea_orm.sql);
+    let values = sea_orm.values.unwrap().0;
+    assert_eq!(values.len(), 2);
+    assert_eq!(values[0], ID.into());
+}
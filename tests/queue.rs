@@ -0,0 +1,43 @@
+//! The work queue: every backend's producers and consumers complete each job
+//! of a round exactly once
+
+mod common;
+
+use pg_benchmark::bench_diesel::DieselBench;
+use pg_benchmark::bench_seaorm::SeaOrmBench;
+use pg_benchmark::bench_sqlx::SqlxBench;
+use pg_benchmark::bench_tokio_postgres::TokioPostgresBench;
+use pg_benchmark::queue::{self, JobQueue, QueueLoad};
+use tokio_postgres::Client;
+
+const LOAD: QueueLoad = QueueLoad {
+    jobs: 205,
+    producers: 2,
+    consumers: 4,
+    batch: 10,
+};
+
+async fn check_round<Q: JobQueue>(name: &str, queue: Q, client: &Client) {
+    queue::reset(client).await.unwrap();
+    queue::run(&queue, LOAD, 1).await.unwrap();
+    assert_eq!(queue::count(client, "done").await.unwrap(), LOAD.jobs as i64, "{name}");
+    assert_eq!(queue::count(client, "pending").await.unwrap(), 0, "{name}");
+    assert_eq!(queue::count(client, "running").await.unwrap(), 0, "{name}");
+    assert!(queue::mean_latency(client).await.unwrap() > std::time::Duration::ZERO, "{name}");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn every_backend_completes_each_job_once() {
+    let Some(_db) = common::lock_database().await else { return };
+    let client = TokioPostgresBench::connect().await.unwrap();
+    queue::create(&client).await.unwrap();
+    let size = LOAD.connections();
+
+    check_round("tokio_postgres", queue::TokioPostgresQueue(TokioPostgresBench::create_pool(size)), &client).await;
+    check_round("sqlx", queue::SqlxQueue(SqlxBench::connect_with_pool_size(size as u32).await.unwrap()), &client).await;
+    check_round("sea_orm", queue::SeaOrmQueue(SeaOrmBench::connect_with_pool_size(size as u32).await.unwrap()), &client).await;
+    check_round("diesel", queue::DieselQueue(DieselBench::connect_with_pool_size(size as u32).unwrap()), &client).await;
+    check_round("clorinde", queue::ClorindeQueue(TokioPostgresBench::create_pool(size)), &client).await;
+
+    queue::reset(&client).await.unwrap();
+}
@@ -0,0 +1,57 @@
+//! Read-your-writes: a user inserted on one connection is returned by the
+//! first lookup on another connection of the same backend
+
+mod common;
+
+use pg_benchmark::bench_clorinde::ClorindeBench;
+use pg_benchmark::bench_diesel::DieselBench;
+use pg_benchmark::bench_null::NullBackend;
+use pg_benchmark::bench_seaorm::SeaOrmBench;
+use pg_benchmark::bench_sqlx::SqlxBench;
+use pg_benchmark::bench_tokio_postgres::{TokioPostgresBench, TokioPostgresPreparedBench};
+use pg_benchmark::read_your_writes::{self, Pair};
+use pg_benchmark::DatabaseBenchmark;
+use std::time::Duration;
+
+#[tokio::test]
+async fn a_reader_that_never_sees_the_write_gives_up() {
+    // Separate datasets are connections that never share a write
+    let detached = Pair {
+        writer: NullBackend::new_dataset(),
+        reader: NullBackend::new_dataset(),
+    };
+    let visibility = read_your_writes::write_then_read::<NullBackend>(&detached, 0, Duration::from_millis(10))
+        .await
+        .unwrap();
+    assert!(!visibility.visible);
+    assert!(visibility.stale_reads >= 1);
+    assert!(visibility.latency > Duration::from_millis(10));
+}
+
+async fn reads_back<B: DatabaseBenchmark>(backend: &str) {
+    let pair = Pair::connect::<B>().await.unwrap();
+    for index in 0..5 {
+        let visibility = read_your_writes::write_then_read::<B>(&pair, index, Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert!(visibility.visible, "{backend}");
+        assert_eq!(visibility.stale_reads, 0, "{backend}");
+    }
+    assert_eq!(common::count("SELECT COUNT(*) FROM users WHERE username LIKE 'bench_user_%'").await, 5);
+    B::cleanup(&pair.writer).await.unwrap();
+}
+
+#[tokio::test]
+async fn every_backend_reads_its_writes_on_another_connection() {
+    let Some(_db) = common::lock_database().await else { return };
+    common::cleanup().await;
+
+    reads_back::<TokioPostgresBench>("tokio_postgres").await;
+    reads_back::<TokioPostgresPreparedBench>("tokio_postgres_prepared").await;
+    reads_back::<SqlxBench>("sqlx").await;
+    reads_back::<SeaOrmBench>("sea_orm").await;
+    reads_back::<DieselBench>("diesel").await;
+    reads_back::<ClorindeBench>("clorinde").await;
+
+    assert_eq!(common::count("SELECT COUNT(*) FROM users WHERE username LIKE 'bench_user_%'").await, 0);
+}
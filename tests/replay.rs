@@ -0,0 +1,147 @@
+//! Workload replay: log and `pg_stat_statements` parsing, mapping every
+//! backend's SQL onto the benchmark operations, and replaying the mix on the
+//! in-memory backend. These need no database.
+
+use pg_benchmark::bench_null::NullBackend;
+use pg_benchmark::replay::{self, classify, Classified, WorkloadMix};
+use pg_benchmark::scenario::Operation;
+
+/// Excerpts of a `log_min_duration_statement = 0` log of the benchmark backends
+const LOG: &str = "\
+2026-10-17 11:54:15.199 UTC [21167] LOG:  duration: 1.243 ms  execute s1: INSERT INTO users (username, email, first_name, last_name, age)
+\t                 VALUES ($1, $2, $3, $4, $5)
+\t                 RETURNING id
+2026-10-17 11:54:15.199 UTC [21167] DETAIL:  parameters: $1 = 'bench_user_1', $2 = 'bench_user_1@benchmark.com', $3 = 'First1', $4 = 'O''Brien', $5 = '21'
+2026-10-17 11:54:15.300 UTC [21167] LOG:  duration: 0.090 ms  parse s2: SELECT id, username, email, first_name, last_name, age, created_at, updated_at
+\t                 FROM users ORDER BY created_at DESC LIMIT $1
+2026-10-17 11:54:15.300 UTC [21167] LOG:  duration: 0.020 ms  bind s2: SELECT id, username, email, first_name, last_name, age, created_at, updated_at
+\t                 FROM users ORDER BY created_at DESC LIMIT $1
+2026-10-17 11:54:15.300 UTC [21167] DETAIL:  parameters: $1 = '10'
+2026-10-17 11:54:15.301 UTC [21167] LOG:  duration: 0.300 ms  execute s2: SELECT id, username, email, first_name, last_name, age, created_at, updated_at
+\t                 FROM users ORDER BY created_at DESC LIMIT $1
+2026-10-17 11:54:15.301 UTC [21167] DETAIL:  parameters: $1 = '10'
+2026-10-17 11:54:15.915 UTC [21169] LOG:  duration: 0.052 ms  statement: BEGIN
+2026-10-17 11:54:15.915 UTC [21169] LOG:  duration: 0.212 ms  execute sqlx_s_1: INSERT INTO users (username, email, first_name, last_name, age) VALUES ($1, $2, $3, $4, $5) RETURNING id
+2026-10-17 11:54:15.916 UTC [21170] LOG:  duration: 0.100 ms  execute <unnamed>: UPDATE \"users\" SET \"first_name\" = $1, \"last_name\" = $2, \"updated_at\" = CURRENT_TIMESTAMP WHERE (\"users\".\"id\" = $3)
+2026-10-17 11:54:15.916 UTC [21169] LOG:  duration: 0.461 ms  execute sqlx_s_7: INSERT INTO posts (user_id, title, content, status) VALUES ($1, $2, $3, $4)
+2026-10-17 11:54:15.917 UTC [21169] LOG:  duration: 0.401 ms  execute sqlx_s_7: INSERT INTO posts (user_id, title, content, status) VALUES ($1, $2, $3, $4)
+2026-10-17 11:54:15.918 UTC [21169] LOG:  duration: 0.075 ms  statement: COMMIT
+2026-10-17 11:54:15.919 UTC [21170] LOG:  duration: 0.075 ms  statement: SET TIME ZONE 'UTC'
+2026-10-17 11:54:15.920 UTC [21170] LOG:  duration: 0.110 ms  execute <unnamed>: INSERT INTO tags (name) VALUES ($1)
+";
+
+#[test]
+fn server_log_statements_are_parsed_with_their_parameters() {
+    let recorded = replay::parse(LOG);
+    // parse and bind lines are not executions
+    assert_eq!(recorded.len(), 10);
+    assert_eq!(
+        recorded[0].sql,
+        "INSERT INTO users (username, email, first_name, last_name, age) VALUES ($1, $2, $3, $4, $5) RETURNING id"
+    );
+    assert_eq!(recorded[0].params[3], "O'Brien");
+    assert_eq!(recorded[0].session, Some(21167));
+    assert_eq!(recorded[1].params, ["10"]);
+    assert!(recorded[2].params.is_empty());
+}
+
+#[test]
+fn every_backends_sql_maps_onto_its_operation() {
+    let cases: &[(&str, &[&str], Classified)] = &[
+        (
+            r#"INSERT INTO "users" ("username", "email", "first_name", "last_name", "age") VALUES ($1, $2, $3, $4, $5), ($6, $7, $8, $9, $10) RETURNING "users"."id""#,
+            &[],
+            Classified::Operation(Operation::InsertUsersBatch, Some(2)),
+        ),
+        (
+            r#"SELECT "users"."id", "users"."username" FROM "users" WHERE "users"."id" = $1 LIMIT $2"#,
+            &["uuid", "1"],
+            Classified::Operation(Operation::SelectUserById, None),
+        ),
+        (
+            r#"SELECT "users"."id" FROM "users" WHERE (("users"."age" >= $1) AND ("users"."age" <= $2)) ORDER BY "users"."age" ASC LIMIT $3"#,
+            &["25", "55", "100"],
+            Classified::Operation(Operation::SelectUsersFiltered, Some(100)),
+        ),
+        (
+            "SELECT p.id as post_id, u.username FROM posts p JOIN users u ON p.user_id = u.id ORDER BY p.created_at DESC LIMIT 50",
+            &[],
+            Classified::Operation(Operation::SelectPostsWithUser, Some(50)),
+        ),
+        (
+            r#"SELECT "users"."id", "posts"."id", "comments"."id" FROM (("users" INNER JOIN "posts" ON ("posts"."user_id" = "users"."id")) INNER JOIN "comments" ON ("comments"."post_id" = "posts"."id")) ORDER BY "users"."created_at" DESC LIMIT $1"#,
+            &["1000"],
+            Classified::Operation(Operation::SelectUsersPostsComments, Some(1000)),
+        ),
+        (
+            r#"SELECT "users"."id", count("posts"."id") FROM ("users" LEFT OUTER JOIN "posts" ON ("posts"."user_id" = "users"."id")) GROUP BY "users"."id" ORDER BY count("posts"."id") DESC"#,
+            &[],
+            Classified::Operation(Operation::CountPostsPerUser, None),
+        ),
+        (
+            r#"SELECT "tags"."id", count("post_tags"."post_id") FROM ("tags" INNER JOIN "post_tags" ON ("post_tags"."tag_id" = "tags"."id")) GROUP BY "tags"."id" ORDER BY count("post_tags"."post_id") DESC, "tags"."id""#,
+            &[],
+            Classified::Operation(Operation::CountPostsPerTag, None),
+        ),
+        (
+            "INSERT INTO comments (post_id, user_id, content) VALUES ($1, $2, $3) RETURNING id",
+            &[],
+            Classified::Operation(Operation::InsertComment, None),
+        ),
+        ("UPDATE users SET first_name = $1, last_name = $2, updated_at = NOW() WHERE id = $3", &[], Classified::Operation(Operation::UpdateUser, None)),
+        ("begin", &[], Classified::Begin),
+        ("ROLLBACK", &[], Classified::End),
+        ("SET statement_timeout = 0", &[], Classified::Ignored),
+        (r#"SELECT "posts"."id" FROM "posts" WHERE "posts"."id" = $1 LIMIT $2"#, &[], Classified::Unmatched),
+        ("DELETE FROM users WHERE username LIKE 'bench_user_%'", &[], Classified::Unmatched),
+    ];
+    for (sql, params, expected) in cases {
+        let params: Vec<String> = params.iter().map(|p| p.to_string()).collect();
+        assert_eq!(classify(sql, &params), *expected, "{sql}");
+    }
+}
+
+#[test]
+fn transactions_in_one_session_become_the_transaction_scenario() {
+    let mix = WorkloadMix::from_recorded(&replay::parse(LOG));
+    let entries: Vec<(&str, usize, u64)> = mix.entries.iter().map(|e| (e.scenario.name, e.size, e.calls)).collect();
+    assert!(entries.contains(&("transaction_insert_user_with_posts", 2, 1)), "{entries:?}");
+    assert!(entries.contains(&("insert_single_user", 1, 1)), "{entries:?}");
+    assert!(entries.contains(&("select_users_limit", 10, 1)), "{entries:?}");
+    assert!(entries.contains(&("update_user", 1, 1)), "{entries:?}");
+    assert_eq!(mix.matched_calls(), 4);
+    assert_eq!(mix.ignored, 3);
+    assert_eq!(mix.unmatched, [("INSERT INTO tags (name) VALUES ($1)".to_string(), 1)]);
+}
+
+#[test]
+fn stat_statements_dumps_carry_call_counts() {
+    let dump = "300\tSELECT id FROM users WHERE id = $1\n100\tSELECT id FROM users ORDER BY created_at DESC LIMIT $1\n5\tBEGIN\n";
+    let mix = WorkloadMix::from_recorded(&replay::parse(dump));
+    assert_eq!(mix.matched_calls(), 400);
+    assert_eq!(mix.entries[0].scenario.name, "select_user_by_id");
+    // The placeholder hides the limit, so the scenario's typical size is used
+    assert_eq!((mix.entries[1].scenario.name, mix.entries[1].size), ("select_users_limit", 100));
+
+    // Interleaved in proportion: three lookups to every listing, spread out
+    let sequence = mix.sequence(8);
+    assert_eq!(sequence, [0, 0, 1, 0, 0, 0, 1, 0]);
+}
+
+#[tokio::test]
+async fn replays_the_mix_and_removes_what_it_inserted() {
+    let dump = "\
+50\tINSERT INTO users (username, email, first_name, last_name, age) VALUES ($1, $2, $3, $4, $5)
+10\tINSERT INTO \"users\" (\"username\") VALUES ($1), ($2), ($3)
+40\tSELECT id FROM users WHERE id = $1
+";
+    let mix = WorkloadMix::from_recorded(&replay::parse(dump));
+    let report = replay::replay::<NullBackend>(&mix, 200, "null").await.unwrap();
+
+    // Single and batch inserts of generated users never collide
+    assert_eq!(report.errors(), 0, "{:?}", report.first_error);
+    assert_eq!(report.operations(), 200);
+    let counts: Vec<u64> = report.entries.iter().map(|e| e.operations).collect();
+    assert_eq!(counts, [100, 80, 20]);
+    assert!(NullBackend::leftover_rows(&NullBackend::connect()).is_empty());
+}
@@ -0,0 +1,62 @@
+//! Read-replica routing: reads go to the replica, writes to the primary, and
+//! the freshness check tells stale replica reads from fresh ones
+
+mod common;
+
+use pg_benchmark::bench_null::NullBackend;
+use pg_benchmark::bench_tokio_postgres::TokioPostgresBench;
+use pg_benchmark::replica::{self, FreshnessReport, Routed};
+use pg_benchmark::{database_url, NewUser, WorkloadOp};
+use std::time::Duration;
+
+#[tokio::test]
+async fn freshness_check_tells_stale_reads_from_fresh_ones() {
+    // One dataset behind both connections is a replica with no lag
+    let shared = Routed {
+        primary: NullBackend::connect(),
+        replica: NullBackend::connect(),
+    };
+    let report = replica::check_freshness::<NullBackend>(&shared, 5, 0, Duration::from_millis(10))
+        .await
+        .unwrap();
+    assert_eq!(report, FreshnessReport { writes: 5, ..Default::default() });
+    NullBackend::cleanup(&shared.primary).unwrap();
+
+    // Separate datasets are a replica that never catches up
+    let detached = Routed {
+        primary: NullBackend::new_dataset(),
+        replica: NullBackend::new_dataset(),
+    };
+    let report = replica::check_freshness::<NullBackend>(&detached, 3, 0, Duration::from_millis(10))
+        .await
+        .unwrap();
+    assert_eq!((report.stale_writes, report.unreplicated), (3, 3));
+    assert!(report.stale_reads >= 3);
+    assert_eq!(report.mean_lag(), Duration::ZERO);
+
+    assert!(std::ptr::eq(detached.route(WorkloadOp::Read), &detached.replica));
+    assert!(std::ptr::eq(detached.route(WorkloadOp::Write), &detached.primary));
+}
+
+#[tokio::test]
+async fn writes_reach_the_replica() {
+    let Some(_db) = common::lock_database().await else { return };
+    // Without a replica the primary stands in for one, which is never stale
+    let replica_url = replica::replica_url().unwrap_or_else(database_url);
+    let routed = Routed::connect::<TokioPostgresBench>(&replica_url).await.unwrap();
+    let standby = replica::in_recovery(&routed.replica).await.unwrap();
+    assert!(!replica::in_recovery(&routed.primary).await.unwrap());
+
+    let report = replica::check_freshness::<TokioPostgresBench>(&routed, 20, 0, Duration::from_secs(5))
+        .await
+        .unwrap();
+    assert_eq!(report.unreplicated, 0, "{report}");
+    if !standby {
+        assert_eq!(report.stale_writes, 0, "{report}");
+    }
+
+    // A standby is read-only, so a misrouted write fails instead of diverging
+    let misrouted = TokioPostgresBench::insert_user(&routed.replica, &NewUser::generate(20)).await;
+    assert_eq!(misrouted.is_err(), standby);
+    TokioPostgresBench::cleanup(&routed.primary).await.unwrap();
+}
@@ -0,0 +1,146 @@
+//! Comparing two runner exports: matching, percentage changes and the
+//! significance of a change. These need no database.
+
+use pg_benchmark::metadata::RunMetadata;
+use pg_benchmark::report::{self, Significance};
+use pg_benchmark::runner::{self, Mode, RunResult};
+
+fn result(backend: &'static str, operations: u64, mean_us: f64, stddev_us: f64) -> RunResult {
+    RunResult {
+        scenario: "select_user_by_id",
+        backend,
+        mode: Mode::Throughput,
+        size: 1,
+        concurrency: 4,
+        operations,
+        errors: 0,
+        first_error: None,
+        elapsed_secs: 10.0,
+        throughput: operations as f64 / 10.0,
+        mean_us,
+        stddev_us,
+        p50_us: mean_us,
+        p95_us: mean_us * 2.0,
+        p99_us: mean_us * 3.0,
+        max_us: mean_us * 5.0,
+        outliers: 0,
+        cv: stddev_us / mean_us,
+        noisy: false,
+        growth: None,
+    }
+}
+
+/// Write `results` as an export and read it back as the diff does
+fn exported(name: &str, results: &[RunResult]) -> Vec<report::ExportedResult> {
+    let path = std::env::temp_dir().join(format!("report-diff-{}-{}.json", std::process::id(), name));
+    runner::write_json(&path, &RunMetadata::local(), results).unwrap();
+    let exported = report::load(&path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    exported
+}
+
+#[test]
+fn changes_are_flagged_only_when_significant() {
+    let old = exported(
+        "old",
+        &[
+            result("tokio_postgres", 10_000, 100.0, 20.0),
+            result("sqlx", 10_000, 100.0, 20.0),
+            result("sea_orm", 10_000, 100.0, 20.0),
+            result("diesel", 20, 100.0, 200.0),
+        ],
+    );
+    let new = exported(
+        "new",
+        &[
+            // 10% slower over many tight samples
+            result("tokio_postgres", 9_000, 110.0, 20.0),
+            // Faster, but within the noise threshold
+            result("sqlx", 10_100, 99.0, 20.0),
+            result("sea_orm", 12_500, 80.0, 20.0),
+            // 10% slower, but a handful of widely spread samples
+            result("diesel", 18, 110.0, 200.0),
+            result("clorinde", 10_000, 100.0, 20.0),
+        ],
+    );
+
+    let diff = report::diff(&old, &new);
+    let flag = |backend: &str| {
+        let comparison = diff.compared.iter().find(|c| c.benchmark.backend == backend).unwrap();
+        comparison.significance
+    };
+    assert_eq!(flag("tokio_postgres"), Significance::Regressed);
+    assert_eq!(flag("sqlx"), Significance::NoChange);
+    assert_eq!(flag("sea_orm"), Significance::Improved);
+    assert_eq!(flag("diesel"), Significance::NoChange);
+    assert_eq!(diff.count(Significance::NoChange), 2);
+
+    let tokio_postgres = diff.compared.iter().find(|c| c.benchmark.backend == "tokio_postgres").unwrap();
+    assert!((tokio_postgres.mean.unwrap() - 10.0).abs() < 1e-9);
+    assert!((tokio_postgres.throughput.unwrap() + 10.0).abs() < 1e-9);
+    assert!(tokio_postgres.to_string().contains("+10.0%"));
+    assert!(tokio_postgres.to_string().ends_with("REGRESSED"));
+
+    assert!(diff.removed.is_empty());
+    assert_eq!(diff.added.len(), 1);
+    assert_eq!(diff.added[0].to_string(), "select_user_by_id/clorinde (size 1, x4, throughput)");
+}
+
+#[test]
+fn results_differing_in_size_or_concurrency_are_not_compared() {
+    let old = exported("sized-old", &[result("sqlx", 1_000, 100.0, 10.0)]);
+    let new = exported("sized-new", &[RunResult { concurrency: 8, ..result("sqlx", 1_000, 100.0, 10.0) }]);
+
+    let diff = report::diff(&old, &new);
+    assert!(diff.compared.is_empty());
+    assert_eq!(diff.removed.len(), 1);
+    assert_eq!(diff.added.len(), 1);
+}
+
+#[test]
+fn exports_without_a_spread_have_unknown_significance() {
+    // As written before the runner recorded the mean and standard deviation
+    let old: Vec<report::ExportedResult> = serde_json::from_str(
+        r#"[{"scenario": "select_user_by_id", "backend": "sqlx", "mode": "latency", "size": 1,
+             "concurrency": 4, "operations": 5000, "errors": 0, "first_error": null,
+             "elapsed_secs": 10.0, "throughput": 500.0, "p50_us": 100.0, "p95_us": 150.0,
+             "p99_us": 200.0, "max_us": 900.0}]"#,
+    )
+    .unwrap();
+    let new = exported("legacy", &[RunResult { mode: Mode::Latency, ..result("sqlx", 5_000, 150.0, 10.0) }]);
+
+    let diff = report::diff(&old, &new);
+    assert_eq!(diff.compared[0].significance, Significance::Unknown);
+    assert_eq!(diff.compared[0].mean, None);
+    assert!((diff.compared[0].p50.unwrap() - 50.0).abs() < 1e-9);
+}
+
+#[test]
+fn exports_without_metadata_still_load() {
+    // As written before the runner recorded the run metadata
+    let path = std::env::temp_dir().join(format!("report-diff-{}-bare.json", std::process::id()));
+    std::fs::write(&path, serde_json::to_string(&[result("sqlx", 1_000, 100.0, 10.0)]).unwrap()).unwrap();
+    let exported = report::load(&path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    assert_eq!(exported.len(), 1);
+    assert_eq!(exported[0].backend, "sqlx");
+}
+
+#[test]
+fn comparisons_with_a_noisy_run_are_flagged() {
+    let old = exported("noisy-old", &[result("sqlx", 1_000, 100.0, 90.0), result("diesel", 1_000, 100.0, 10.0)]);
+    let new = exported(
+        "noisy-new",
+        &[
+            RunResult { noisy: true, ..result("sqlx", 1_000, 100.0, 90.0) },
+            result("diesel", 1_000, 100.0, 10.0),
+        ],
+    );
+
+    let diff = report::diff(&old, &new);
+    assert_eq!(diff.noisy(), 1);
+    assert!((old[0].cv.unwrap() - 0.9).abs() < 1e-9);
+    let sqlx = diff.compared.iter().find(|c| c.benchmark.backend == "sqlx").unwrap();
+    assert!(sqlx.noisy);
+    assert!(sqlx.to_string().ends_with("no change (noisy)"));
+}
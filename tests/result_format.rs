@@ -0,0 +1,35 @@
+//! Text and binary result formats decode the wide user row identically
+
+mod common;
+
+use pg_benchmark::bench_tokio_postgres::TokioPostgresBench;
+use pg_benchmark::result_format;
+
+#[tokio::test]
+async fn text_and_binary_rows_decode_to_the_same_users() {
+    let Some(_db) = common::lock_database().await else { return };
+    let client = TokioPostgresBench::connect().await.unwrap();
+    let statement = result_format::prepare_select_users(&client).await.unwrap();
+
+    let binary = result_format::fetch_users_binary(&client, &statement, 1000)
+        .await
+        .unwrap()
+        .iter()
+        .map(result_format::decode_user_binary)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let text = result_format::fetch_users_text(&client, 1000)
+        .await
+        .unwrap()
+        .iter()
+        .map(result_format::decode_user_text)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    assert_eq!(binary.len(), 1000);
+    assert_eq!(text, binary);
+    assert_eq!(
+        TokioPostgresBench::select_users_limit(&client, 1000).await.unwrap(),
+        binary
+    );
+}
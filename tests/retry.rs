@@ -0,0 +1,125 @@
+//! Retry policy: backoff bounds, which errors are retried, and every backend's
+//! induced failures classified as transient
+
+mod common;
+
+use pg_benchmark::bench_clorinde::ClorindeBench;
+use pg_benchmark::bench_diesel::DieselBench;
+use pg_benchmark::bench_seaorm::SeaOrmBench;
+use pg_benchmark::bench_sqlx::SqlxBench;
+use pg_benchmark::bench_tokio_postgres::TokioPostgresBench;
+use pg_benchmark::retry::{self, FailureSchedule, RetryPolicy, Transient};
+use pg_benchmark::{BoxError, NewUser};
+use std::time::Duration;
+
+const IMMEDIATE: RetryPolicy = RetryPolicy {
+    max_attempts: 4,
+    base_delay: Duration::ZERO,
+    max_delay: Duration::ZERO,
+};
+
+#[test]
+fn delays_stay_under_the_doubling_ceiling() {
+    let mut rng = rand::thread_rng();
+    let policy = RetryPolicy::BACKOFF;
+    for retry in 1..=8 {
+        let ceiling = (policy.base_delay * 2u32.pow(retry as u32 - 1)).min(policy.max_delay);
+        for _ in 0..100 {
+            assert!(policy.delay(retry, &mut rng) <= ceiling, "retry {retry}");
+        }
+    }
+    assert_eq!(RetryPolicy::DISABLED.delay(1, &mut rng), Duration::ZERO);
+}
+
+#[tokio::test]
+async fn only_transient_errors_are_retried() {
+    // Errors are `true` when transient
+    let failing_until = |success: usize| move |attempt: usize| async move {
+        if attempt < success { Err(true) } else { Ok(attempt) }
+    };
+    assert_eq!(IMMEDIATE.run(|e: &bool| *e, failing_until(3)).await, (Ok(3), 3));
+    assert_eq!(IMMEDIATE.run(|e: &bool| *e, failing_until(9)).await, (Err(true), 4));
+    assert_eq!(RetryPolicy::DISABLED.run(|e: &bool| *e, failing_until(3)).await, (Err(true), 1));
+    let permanent = |_| async { Err::<(), bool>(false) };
+    assert_eq!(IMMEDIATE.run(|e: &bool| *e, permanent).await, (Err(false), 1));
+}
+
+#[test]
+fn schedules_fail_at_their_rate() {
+    let failures = |rate| (0..10_000).filter(|&r| FailureSchedule { rate }.fails(r, 1)).count();
+    assert_eq!(failures(0.0), 0);
+    assert_eq!(failures(1.0), 10_000);
+    assert!((800..1200).contains(&failures(0.1)), "{}", failures(0.1));
+
+    // A retry of a failed request is as likely to fail as any attempt
+    let schedule = FailureSchedule { rate: 0.5 };
+    let retried = (0..10_000).filter(|&r| schedule.fails(r, 1) && schedule.fails(r, 2)).count();
+    assert!((2000..3000).contains(&retried), "{retried}");
+}
+
+#[test]
+fn transient_sqlstates() {
+    for code in ["40001", "40P01", "08006", "08001", "57P01", "53300"] {
+        assert!(retry::transient_sqlstate(code), "{code}");
+    }
+    for code in ["23505", "42P01", "57014", "22012"] {
+        assert!(!retry::transient_sqlstate(code), "{code}");
+    }
+}
+
+/// Lookups of `RETRIED` requests at a 30% failure rate under
+/// [`RetryPolicy::BACKOFF`]: how many still failed and how many attempts ran
+const RETRIED: usize = 50;
+
+async fn retries_induced_failures<B: Transient>(backend: &str) -> (usize, usize) {
+    let conn = B::connect().await.unwrap();
+    let error = B::induce_failure(&conn).await.expect_err(backend);
+    assert!(B::is_transient(&error), "{backend}: {error:?}");
+
+    let user = NewUser::generate(0);
+    B::insert_user(&conn, &user).await.unwrap();
+    let error = B::insert_user(&conn, &user).await.expect_err(backend);
+    assert!(!B::is_transient(&error), "{backend}: {error:?}");
+    B::cleanup(&conn).await.unwrap();
+
+    let id = B::select_users_limit(&conn, 1).await.unwrap()[0].id;
+    let always = FailureSchedule { rate: 1.0 };
+    let (result, attempts) = RetryPolicy::BACKOFF
+        .run(B::is_transient, |attempt| B::flaky_lookup(&conn, id, always, 0, attempt))
+        .await;
+    assert!(result.is_err() && attempts == RetryPolicy::BACKOFF.max_attempts, "{backend}");
+
+    let schedule = FailureSchedule { rate: 0.3 };
+    let (mut failed, mut attempts) = (0, 0);
+    for request in 0..RETRIED {
+        let (result, ran) = RetryPolicy::BACKOFF
+            .run(B::is_transient, |attempt| B::flaky_lookup(&conn, id, schedule, request, attempt))
+            .await;
+        failed += result.is_err() as usize;
+        attempts += ran;
+        if let Ok(found) = result {
+            assert_eq!(found.map(|u| u.id), Some(id), "{backend}");
+        }
+    }
+    (failed, attempts)
+}
+
+#[tokio::test]
+async fn every_backend_retries_induced_failures() {
+    let Some(_db) = common::lock_database().await else { return };
+    common::cleanup().await;
+
+    let expected = retries_induced_failures::<TokioPostgresBench>("tokio_postgres").await;
+    assert!(expected.1 > RETRIED, "{expected:?}");
+    // The schedule picks the same attempts on every backend
+    assert_eq!(retries_induced_failures::<SqlxBench>("sqlx").await, expected);
+    assert_eq!(retries_induced_failures::<SeaOrmBench>("sea_orm").await, expected);
+    assert_eq!(retries_induced_failures::<DieselBench>("diesel").await, expected);
+    assert_eq!(retries_induced_failures::<ClorindeBench>("clorinde").await, expected);
+
+    // Pooled wrappers box the driver's error
+    let client = TokioPostgresBench::connect().await.unwrap();
+    let error: BoxError = TokioPostgresBench::induce_failure(&client).await.unwrap_err().into();
+    assert!(retry::is_transient_boxed(&error));
+    common::cleanup().await;
+}
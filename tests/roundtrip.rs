@@ -0,0 +1,136 @@
+//! Property-based round trips through every pair of backends
+//!
+//! Arbitrary users and posts (unicode text, boundary ages, maximum-length
+//! strings) are written by one backend and read back by another, catching
+//! mapping and encoding differences that the fixed fixtures would miss.
+
+mod common;
+
+use pg_benchmark::{
+    bench_clorinde::ClorindeBench, bench_diesel::DieselBench, bench_seaorm::SeaOrmBench,
+    bench_sqlx::SqlxBench, bench_tokio_postgres::TokioPostgresBench, DatabaseBenchmark, NewPost,
+    NewUser,
+};
+use proptest::prelude::*;
+use proptest::test_runner::{Config, TestRunner};
+use tokio::runtime::Runtime;
+use uuid::Uuid;
+
+const BACKENDS: [&str; 5] = ["tokio_postgres", "sqlx", "sea_orm", "diesel", "clorinde"];
+
+/// One open connection per backend, dispatched by index into [`BACKENDS`]
+struct Backends {
+    tokio_postgres: <TokioPostgresBench as DatabaseBenchmark>::Connection,
+    sqlx: <SqlxBench as DatabaseBenchmark>::Connection,
+    sea_orm: <SeaOrmBench as DatabaseBenchmark>::Connection,
+    diesel: <DieselBench as DatabaseBenchmark>::Connection,
+    clorinde: <ClorindeBench as DatabaseBenchmark>::Connection,
+}
+
+/// Call the same `DatabaseBenchmark` method on the backend at `$index`
+macro_rules! dispatch {
+    ($backends:expr, $index:expr, $method:ident($($arg:expr),*)) => {
+        match $index {
+            0 => <TokioPostgresBench as DatabaseBenchmark>::$method(&$backends.tokio_postgres, $($arg),*).await.unwrap(),
+            1 => <SqlxBench as DatabaseBenchmark>::$method(&$backends.sqlx, $($arg),*).await.unwrap(),
+            2 => <SeaOrmBench as DatabaseBenchmark>::$method(&$backends.sea_orm, $($arg),*).await.unwrap(),
+            3 => <DieselBench as DatabaseBenchmark>::$method(&$backends.diesel, $($arg),*).await.unwrap(),
+            _ => <ClorindeBench as DatabaseBenchmark>::$method(&$backends.clorinde, $($arg),*).await.unwrap(),
+        }
+    };
+}
+
+impl Backends {
+    async fn connect() -> Self {
+        Self {
+            tokio_postgres: <TokioPostgresBench as DatabaseBenchmark>::connect().await.unwrap(),
+            sqlx: <SqlxBench as DatabaseBenchmark>::connect().await.unwrap(),
+            sea_orm: <SeaOrmBench as DatabaseBenchmark>::connect().await.unwrap(),
+            diesel: <DieselBench as DatabaseBenchmark>::connect().await.unwrap(),
+            clorinde: <ClorindeBench as DatabaseBenchmark>::connect().await.unwrap(),
+        }
+    }
+}
+
+/// Any text PostgreSQL accepts (everything but NUL), up to `max` characters
+fn text(max: usize) -> impl Strategy<Value = String> {
+    proptest::collection::vec(any::<char>().prop_filter("NUL", |c| *c != '\0'), 0..=max)
+        .prop_map(|chars| chars.into_iter().collect())
+}
+
+/// Users that satisfy the schema: VARCHAR(50/100) limits and `age BETWEEN 0 AND 150`
+fn new_user() -> impl Strategy<Value = NewUser> {
+    let age = prop_oneof![
+        Just(None),
+        Just(Some(0)),
+        Just(Some(150)),
+        (0..=150).prop_map(Some),
+    ];
+    // The `bench_user_` prefix (11 chars) lets the backends' cleanup remove the row
+    (text(39), text(100), text(50), text(50), age).prop_map(
+        |(username, email, first_name, last_name, age)| NewUser {
+            username: format!("bench_user_{username}"),
+            email,
+            first_name,
+            last_name,
+            age,
+        },
+    )
+}
+
+fn new_post() -> impl Strategy<Value = NewPost> {
+    let status = prop_oneof![Just("draft"), Just("published"), Just("archived")];
+    (text(200), text(2000), status).prop_map(|(title, content, status)| NewPost {
+        user_id: Uuid::nil(),
+        title,
+        content,
+        status: status.to_string(),
+    })
+}
+
+#[test]
+fn users_and_posts_round_trip_between_backends() {
+    let rt = Runtime::new().unwrap();
+    let Some(_db) = rt.block_on(common::lock_database()) else {
+        return;
+    };
+    rt.block_on(common::cleanup());
+    let backends = rt.block_on(Backends::connect());
+
+    let mut runner = TestRunner::new(Config::with_cases(64));
+    let strategy = (new_user(), new_post(), 0..BACKENDS.len(), 0..BACKENDS.len());
+    let result = runner.run(&strategy, |(user, post, writer, reader)| {
+        let (read_user, read_post) = rt.block_on(async {
+            let user_id = dispatch!(backends, writer, insert_user(&user));
+            let post = NewPost { user_id, ..post.clone() };
+            dispatch!(backends, writer, insert_post(&post));
+
+            let read_user = dispatch!(backends, reader, select_user_by_id(user_id));
+            // The post was just inserted, so it is the newest one
+            let read_post = dispatch!(backends, reader, select_posts_with_user(1)).pop();
+
+            TokioPostgresBench::cleanup(&backends.tokio_postgres).await.unwrap();
+            (read_user, read_post)
+        });
+
+        let context = format!("written by {}, read by {}", BACKENDS[writer], BACKENDS[reader]);
+        let read_user = read_user.expect("user not found");
+        prop_assert_eq!(&read_user.username, &user.username, "{}", context);
+        prop_assert_eq!(&read_user.email, &user.email, "{}", context);
+        prop_assert_eq!(&read_user.first_name, &user.first_name, "{}", context);
+        prop_assert_eq!(&read_user.last_name, &user.last_name, "{}", context);
+        prop_assert_eq!(read_user.age, user.age, "{}", context);
+
+        let (read_post, post_author) = read_post.expect("post not found");
+        prop_assert_eq!(&post_author, &read_user, "{}", context);
+        prop_assert_eq!(read_post.user_id, read_user.id, "{}", context);
+        prop_assert_eq!(&read_post.title, &post.title, "{}", context);
+        prop_assert_eq!(&read_post.content, &post.content, "{}", context);
+        prop_assert_eq!(&read_post.status, &post.status, "{}", context);
+        prop_assert_eq!(read_post.view_count, 0, "{}", context);
+        Ok(())
+    });
+
+    rt.block_on(common::cleanup());
+    result.unwrap();
+}
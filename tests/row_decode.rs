@@ -0,0 +1,62 @@
+//! Every backend's decoding of pre-fetched rows, by column name or position
+//! and into owned, packed or borrowed users, gives the users its query returns
+
+mod common;
+
+use pg_benchmark::bench_seaorm::SeaOrmBench;
+use pg_benchmark::bench_sqlx::SqlxBench;
+use pg_benchmark::bench_tokio_postgres::TokioPostgresBench;
+use pg_benchmark::row_decode;
+use pg_benchmark::User;
+
+#[tokio::test]
+async fn every_backend_decodes_rows_to_the_same_users() {
+    let Some(_db) = common::lock_database().await else { return };
+    let client = TokioPostgresBench::connect().await.unwrap();
+    let pool = SqlxBench::connect().await.unwrap();
+    let db = SeaOrmBench::connect().await.unwrap();
+    let expected = TokioPostgresBench::select_users_limit(&client, 100).await.unwrap();
+    assert_eq!(expected.len(), 100);
+
+    let rows = row_decode::fetch_tokio_postgres(&client, 100).await.unwrap();
+    let tokio_postgres = rows.iter().map(row_decode::decode_tokio_postgres).collect::<Result<Vec<_>, _>>().unwrap();
+    let tokio_postgres_by_index =
+        rows.iter().map(row_decode::decode_tokio_postgres_by_index).collect::<Result<Vec<_>, _>>().unwrap();
+    let sqlx_rows = row_decode::fetch_sqlx(&pool, 100).await.unwrap();
+    let sqlx = sqlx_rows.iter().map(row_decode::decode_sqlx).collect::<Result<Vec<_>, _>>().unwrap();
+    let sqlx_by_index = sqlx_rows.iter().map(row_decode::decode_sqlx_by_index).collect::<Result<Vec<_>, _>>().unwrap();
+    let sea_orm = row_decode::fetch_sea_orm(&db, 100)
+        .await
+        .unwrap()
+        .iter()
+        .map(row_decode::decode_sea_orm)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    assert_eq!(tokio_postgres, expected);
+    assert_eq!(tokio_postgres_by_index, expected);
+    assert_eq!(sqlx, expected);
+    assert_eq!(sqlx_by_index, expected);
+    assert_eq!(sea_orm, expected);
+}
+
+#[tokio::test]
+async fn borrowed_and_packed_users_hold_the_same_values() {
+    let Some(_db) = common::lock_database().await else { return };
+    let client = TokioPostgresBench::connect().await.unwrap();
+    let pool = SqlxBench::connect().await.unwrap();
+    let expected = TokioPostgresBench::select_users_limit(&client, 100).await.unwrap();
+
+    let rows = row_decode::fetch_tokio_postgres(&client, 100).await.unwrap();
+    let sqlx_rows = row_decode::fetch_sqlx(&pool, 100).await.unwrap();
+    for (i, user) in expected.iter().enumerate() {
+        let borrowed = row_decode::decode_tokio_postgres_borrowed(&rows[i]).unwrap();
+        assert_eq!(&User::from(&borrowed), user);
+        let packed = row_decode::decode_tokio_postgres_packed(&rows[i]).unwrap();
+        assert_eq!((packed.username(), packed.last_name()), (user.username.as_str(), user.last_name.as_str()));
+        assert_eq!(&User::from(&packed), user);
+
+        assert_eq!(&User::from(&row_decode::decode_sqlx_borrowed(&sqlx_rows[i]).unwrap()), user);
+        assert_eq!(&User::from(&row_decode::decode_sqlx_packed(&sqlx_rows[i]).unwrap()), user);
+    }
+}
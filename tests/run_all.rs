@@ -0,0 +1,92 @@
+//! The end-to-end run's building blocks: the schema taken from `init.sql`,
+//! the time estimate and the Markdown summary. These need no database.
+
+use pg_benchmark::run_all;
+use pg_benchmark::runner::{Mode, RunResult};
+use pg_benchmark::scenario::{Scenario, SCENARIOS};
+
+fn result(scenario: &'static str, backend: &'static str, throughput: f64) -> RunResult {
+    RunResult {
+        scenario,
+        backend,
+        mode: Mode::Throughput,
+        size: 1,
+        concurrency: 4,
+        operations: throughput as u64,
+        errors: 0,
+        first_error: None,
+        elapsed_secs: 1.0,
+        throughput,
+        mean_us: 250.0,
+        stddev_us: 20.0,
+        p50_us: 240.0,
+        p95_us: 300.0,
+        p99_us: 400.0,
+        max_us: 900.0,
+        outliers: 0,
+        cv: 0.08,
+        noisy: false,
+        growth: None,
+    }
+}
+
+#[test]
+fn schema_is_init_sql_without_its_sample_data() {
+    let schema = run_all::schema_sql();
+    for table in ["users", "posts", "comments", "tags", "post_tags"] {
+        assert!(schema.contains(&format!("CREATE TABLE {table} (")), "{table} missing");
+    }
+    assert!(schema.contains("CREATE INDEX idx_tags_name"));
+    // psql meta-commands would fail outside psql
+    assert!(!schema.contains("\\getenv"));
+    assert!(!schema.contains("INSERT INTO"));
+    assert!(!schema.ends_with("-- ="));
+}
+
+#[test]
+fn estimate_leaves_out_skipped_backends() {
+    let config = run_all::default_config();
+    let select = SCENARIOS.iter().find(|s| s.name == "select_user_by_id").unwrap();
+    let skipping = Scenario { skip: &["tokio_postgres"], ..*select };
+    let per_run = config.warmup_secs + config.duration_secs;
+    assert_eq!(run_all::estimated_secs(&config, &[select, &skipping], &["tokio_postgres", "sqlx"]), 3.0 * per_run);
+}
+
+#[test]
+fn summary_ranks_backends_by_throughput() {
+    let results = [
+        result("select_user_by_id", "tokio_postgres", 800.0),
+        result("select_user_by_id", "sqlx", 1000.0),
+        result("update_user", "sqlx", 300.0),
+    ];
+    let summary = run_all::summary(&run_all::default_config(), &[("PostgreSQL", "16.2".to_string())], &results);
+
+    assert!(summary.contains("- PostgreSQL: 16.2\n"));
+    let sqlx = summary.find("| sqlx | 1000.0 | 100% |").unwrap();
+    let tokio_postgres = summary.find("| tokio_postgres | 800.0 | 80% |").unwrap();
+    assert!(sqlx < tokio_postgres);
+    assert!(summary.find("## select_user_by_id (size 1)").unwrap() < summary.find("## update_user (size 1)").unwrap());
+
+    // Ranked over the benchmarks both ran, after the scenario tables
+    let ranking = summary.find("## Overall Ranking").unwrap();
+    assert!(summary.find("## update_user (size 1)").unwrap() < ranking);
+    assert!(summary[ranking..].contains("| 2 | tokio_postgres | 1.25 |"));
+
+    // Capabilities of the backends that ran, in order of appearance
+    let capabilities = summary.find("## Backend Capabilities").unwrap();
+    assert!(ranking < capabilities);
+    assert!(summary[capabilities..].contains("| Feature | tokio_postgres | sqlx |\n"));
+}
+
+#[test]
+fn summary_marks_noisy_results() {
+    let results = [
+        result("select_user_by_id", "sqlx", 1000.0),
+        RunResult { cv: 0.75, noisy: true, ..result("select_user_by_id", "diesel", 900.0) },
+    ];
+    let summary = run_all::summary(&run_all::default_config(), &[], &results);
+
+    assert!(summary.contains("above 0.5 are marked noisy"));
+    assert!(summary.contains("| sqlx | 1000.0 | 100% | 250.0 | 240.0 | 400.0 | 0 | 0.08 |\n"));
+    assert!(summary.contains("| 0 | 0.75 (noisy) |\n"));
+}
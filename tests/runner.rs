@@ -0,0 +1,334 @@
+//! The load-test runner: configuration parsing, both modes on the in-memory
+//! backend, a blocking backend's workers, the exports and SLO checks. These
+//! need no database.
+
+use pg_benchmark::bench_null::{NullBackend, NullConnection, NullError};
+use pg_benchmark::capability::Capabilities;
+use pg_benchmark::metadata::RunMetadata;
+use pg_benchmark::interrupt::Interrupt;
+use pg_benchmark::runner::{self, ExportConfig, Mode, RunResult, RunnerConfig};
+use pg_benchmark::scenario::SCENARIOS;
+use pg_benchmark::{Comment, CommentKey, DatabaseBenchmark, NewComment, NewPost, NewUser, Post, User};
+use std::time::Duration;
+use uuid::Uuid;
+
+fn scenario(name: &str) -> &'static pg_benchmark::scenario::Scenario {
+    SCENARIOS.iter().find(|s| s.name == name).unwrap()
+}
+
+#[test]
+fn config_defaults_fill_in_missing_keys() {
+    let config = RunnerConfig::from_toml("mode = \"latency\"\nrate = 50.0\nbackends = [\"sqlx\", \"tokio_postgres\"]").unwrap();
+    assert_eq!(config.mode, Mode::Latency);
+    assert_eq!(config.concurrency, RunnerConfig::default().concurrency);
+    // Selections follow the registry order, not the file's
+    assert_eq!(config.backends().unwrap(), ["tokio_postgres", "sqlx"]);
+    assert_eq!(config.scenarios().unwrap().len(), SCENARIOS.len());
+
+    let all = RunnerConfig::from_toml("").unwrap();
+    assert!(!all.backends().unwrap().contains(&"null"));
+}
+
+#[test]
+fn config_rejects_unknown_and_invalid_settings() {
+    for text in [
+        "durations_secs = 1.0",
+        "mode = \"fastest\"",
+        "backends = [\"mysql\"]",
+        "scenarios = [\"insert_everything\"]",
+        "concurrency = 0",
+        "mode = \"latency\"\nrate = 0.0",
+        "duration_secs = 0.0",
+        "growth_interval_secs = 0.0",
+        "[slo.select_everything]\np99_ms = 5.0",
+        "[slo.select_user_by_id]\np99_ms = -1.0",
+        "[slo.select_user_by_id]\np99_us = 5.0",
+        "profile = \"huge\"",
+        "[noise]\nmax_cv = 0.0",
+        "[noise]\noutlier_fence = -1.5",
+        "[noise]\naction = \"ignore\"",
+    ] {
+        assert!(RunnerConfig::from_toml(text).is_err(), "{text:?} was accepted");
+    }
+}
+
+#[tokio::test]
+async fn runs_each_mode_and_cleans_up() {
+    let throughput = RunnerConfig {
+        duration_secs: 0.2,
+        warmup_secs: 0.05,
+        concurrency: 3,
+        ..RunnerConfig::default()
+    };
+    let latency = RunnerConfig {
+        mode: Mode::Latency,
+        rate: 100.0,
+        ..throughput.clone()
+    };
+
+    let insert = scenario("insert_single_user");
+    let result = runner::run::<NullBackend>(&throughput, insert, "null", &mut |_| {}).await.unwrap();
+    assert!(result.operations > 0);
+    assert_eq!(result.errors, 0);
+    assert!(result.p50_us <= result.p99_us && result.p99_us <= result.max_us);
+    assert!(NullBackend::leftover_rows(&NullBackend::connect()).is_empty());
+
+    // Paced at 100 ops/s for 0.2s: 20 starts are scheduled, and a slow machine may miss the last few
+    let result = runner::run::<NullBackend>(&latency, scenario("select_user_by_id"), "null", &mut |_| {})
+        .await
+        .unwrap();
+    assert!((10..=20).contains(&result.operations), "{} operations", result.operations);
+}
+
+/// How long a [`BlockingNull`] lookup holds its thread
+const BLOCKING_LOOKUP: Duration = Duration::from_millis(10);
+
+/// The in-memory backend behind blocking calls, as diesel's are: user lookups
+/// sleep on the thread for [`BLOCKING_LOOKUP`]
+struct BlockingNull;
+
+impl DatabaseBenchmark for BlockingNull {
+    type Connection = NullConnection;
+    type Error = NullError;
+
+    const BLOCKING: bool = true;
+
+    async fn connect() -> Result<Self::Connection, Self::Error> {
+        Ok(NullBackend::connect())
+    }
+
+    async fn connect_to(_url: &str) -> Result<Self::Connection, Self::Error> {
+        Ok(NullBackend::connect())
+    }
+
+    async fn insert_user(conn: &Self::Connection, user: &NewUser) -> Result<Uuid, Self::Error> {
+        NullBackend::insert_user(conn, user)
+    }
+
+    async fn insert_users_batch(conn: &Self::Connection, users: &[NewUser]) -> Result<Vec<Uuid>, Self::Error> {
+        NullBackend::insert_users_batch(conn, users)
+    }
+
+    async fn select_user_by_id(conn: &Self::Connection, id: Uuid) -> Result<Option<User>, Self::Error> {
+        std::thread::sleep(BLOCKING_LOOKUP);
+        NullBackend::select_user_by_id(conn, id)
+    }
+
+    async fn select_users_limit(conn: &Self::Connection, limit: i64) -> Result<Vec<User>, Self::Error> {
+        NullBackend::select_users_limit(conn, limit)
+    }
+
+    async fn select_users_filtered(conn: &Self::Connection, min_age: i32, max_age: i32, limit: i64) -> Result<Vec<User>, Self::Error> {
+        NullBackend::select_users_filtered(conn, min_age, max_age, limit)
+    }
+
+    async fn update_user(conn: &Self::Connection, id: Uuid, first_name: &str, last_name: &str) -> Result<bool, Self::Error> {
+        NullBackend::update_user(conn, id, first_name, last_name)
+    }
+
+    async fn delete_user(conn: &Self::Connection, id: Uuid) -> Result<bool, Self::Error> {
+        NullBackend::delete_user(conn, id)
+    }
+
+    async fn insert_post(conn: &Self::Connection, post: &NewPost) -> Result<Uuid, Self::Error> {
+        NullBackend::insert_post(conn, post)
+    }
+
+    async fn insert_comment(conn: &Self::Connection, comment: &NewComment) -> Result<Uuid, Self::Error> {
+        NullBackend::insert_comment(conn, comment)
+    }
+
+    async fn select_posts_with_user(conn: &Self::Connection, limit: i64) -> Result<Vec<(Post, User)>, Self::Error> {
+        NullBackend::select_posts_with_user(conn, limit)
+    }
+
+    async fn select_posts_by_status(conn: &Self::Connection, status: &str, limit: i64) -> Result<Vec<Post>, Self::Error> {
+        NullBackend::select_posts_by_status(conn, status, limit)
+    }
+
+    async fn select_posts_repeated(conn: &Self::Connection, copies: i32) -> Result<Vec<Post>, Self::Error> {
+        NullBackend::select_posts_repeated(conn, copies)
+    }
+
+    async fn stream_posts_repeated(conn: &Self::Connection, copies: i32, each: impl FnMut(Post) + Send) -> Result<usize, Self::Error> {
+        NullBackend::stream_posts_repeated(conn, copies, each)
+    }
+
+    async fn search_users_by_name(conn: &Self::Connection, pattern: &str, limit: i64) -> Result<Vec<User>, Self::Error> {
+        NullBackend::search_users_by_name(conn, pattern, limit)
+    }
+
+    async fn select_users_posts_comments(conn: &Self::Connection, limit: i64) -> Result<Vec<(User, Post, Comment)>, Self::Error> {
+        NullBackend::select_users_posts_comments(conn, limit)
+    }
+
+    async fn select_comments_for_post(
+        conn: &Self::Connection,
+        post_id: Uuid,
+        limit: i64,
+        before: Option<CommentKey>,
+    ) -> Result<Vec<Comment>, Self::Error> {
+        NullBackend::select_comments_for_post(conn, post_id, limit, before)
+    }
+
+    async fn count_posts_per_user(conn: &Self::Connection) -> Result<Vec<(Uuid, i64)>, Self::Error> {
+        NullBackend::count_posts_per_user(conn)
+    }
+
+    async fn count_posts_per_tag(conn: &Self::Connection) -> Result<Vec<(Uuid, i64)>, Self::Error> {
+        NullBackend::count_posts_per_tag(conn)
+    }
+
+    async fn insert_user_with_posts(conn: &Self::Connection, user: &NewUser, posts: &[NewPost]) -> Result<Uuid, Self::Error> {
+        NullBackend::insert_user_with_posts(conn, user, posts)
+    }
+
+    async fn insert_user_post_update_batched(
+        conn: &Self::Connection,
+        user: &NewUser,
+        post: &NewPost,
+        first_name: &str,
+        last_name: &str,
+    ) -> Result<Uuid, Self::Error> {
+        NullBackend::insert_user_post_update_batched(conn, user, post, first_name, last_name)
+    }
+
+    async fn cleanup(conn: &Self::Connection) -> Result<(), Self::Error> {
+        NullBackend::cleanup(conn)
+    }
+
+    fn capabilities() -> Capabilities {
+        Capabilities::NONE
+    }
+}
+
+#[tokio::test]
+async fn blocking_backends_run_each_worker_on_its_own_thread() {
+    let config = RunnerConfig {
+        duration_secs: 0.3,
+        warmup_secs: 0.0,
+        concurrency: 4,
+        ..RunnerConfig::default()
+    };
+    let result = runner::run::<BlockingNull>(&config, scenario("select_user_by_id"), "null", &mut |_| {})
+        .await
+        .unwrap();
+    assert_eq!(result.errors, 0);
+    // One thread fits 30 lookups into 0.3s; four workers in turn would too
+    let serial = (config.duration_secs / BLOCKING_LOOKUP.as_secs_f64()) as u64;
+    assert!(result.operations > 2 * serial, "{} operations", result.operations);
+}
+
+#[tokio::test]
+async fn exports_one_row_per_result() {
+    let config = RunnerConfig {
+        duration_secs: 0.05,
+        warmup_secs: 0.0,
+        concurrency: 1,
+        ..RunnerConfig::default()
+    };
+    let mut results = Vec::new();
+    for name in ["select_users_limit", "aggregate_count_posts_per_user"] {
+        results.push(runner::run::<NullBackend>(&config, scenario(name), "null", &mut |_| {}).await.unwrap());
+    }
+
+    let dir = std::env::temp_dir().join(format!("bench-runner-{}", std::process::id()));
+    let metadata = RunMetadata::local();
+    runner::write_json(&dir.join("results.json"), &metadata, &results).unwrap();
+    runner::write_csv(&dir.join("results.csv"), &metadata, &results).unwrap();
+
+    let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(dir.join("results.json")).unwrap()).unwrap();
+    assert_eq!(serde_json::from_value::<RunMetadata>(json["metadata"].clone()).unwrap(), metadata);
+    assert_eq!(json["results"].as_array().unwrap().len(), 2);
+    assert_eq!(json["results"][0]["scenario"], "select_users_limit");
+    assert_eq!(json["results"][0]["mode"], "throughput");
+
+    let csv = std::fs::read_to_string(dir.join("results.csv")).unwrap();
+    let (comments, lines): (Vec<&str>, Vec<&str>) = csv.lines().partition(|line| line.starts_with('#'));
+    assert_eq!(comments.len(), metadata.entries().len());
+    assert!(comments.iter().any(|line| line.starts_with("# sqlx: 0.8.")));
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("scenario,backend,mode,size"));
+    assert!(lines[2].starts_with("aggregate_count_posts_per_user,null,throughput,1,1,"));
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn interrupted_runs_keep_their_results_marked_partial() {
+    let interrupt = Interrupt::default();
+    assert!(!interrupt.requested());
+    interrupt.request();
+    assert!(interrupt.clone().requested());
+
+    let dir = std::env::temp_dir().join(format!("bench-runner-interrupted-{}", std::process::id()));
+    let exports = ExportConfig { json: Some(dir.join("results.json")), csv: Some(dir.join("results.csv")) };
+    let mut metadata = RunMetadata::local();
+    exports.write(&metadata, &[result("select_user_by_id", "null", 100.0, 10.0)]).unwrap();
+    let json = std::fs::read_to_string(dir.join("results.json")).unwrap();
+    assert!(!json.contains("interrupted"));
+
+    // Rewritten with more results once interrupted
+    metadata.interrupted = true;
+    let results = [result("select_user_by_id", "null", 100.0, 10.0), result("update_user", "null", 100.0, 10.0)];
+    exports.write(&metadata, &results).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(dir.join("results.json")).unwrap()).unwrap();
+    assert_eq!(json["metadata"]["interrupted"], true);
+    assert_eq!(json["results"].as_array().unwrap().len(), 2);
+    let csv = std::fs::read_to_string(dir.join("results.csv")).unwrap();
+    assert!(csv.contains("# Interrupted: yes"));
+    std::fs::remove_dir_all(dir).unwrap();
+
+    runner::cleanup_backend("null").await.unwrap();
+    assert!(runner::cleanup_backend("mysql").await.is_err());
+}
+
+fn result(scenario: &'static str, backend: &'static str, p99_us: f64, throughput: f64) -> RunResult {
+    RunResult {
+        scenario,
+        backend,
+        mode: Mode::Throughput,
+        size: 1,
+        concurrency: 1,
+        operations: 100,
+        errors: 0,
+        first_error: None,
+        elapsed_secs: 1.0,
+        throughput,
+        mean_us: p99_us / 2.0,
+        stddev_us: p99_us / 10.0,
+        p50_us: p99_us / 2.0,
+        p95_us: p99_us,
+        p99_us,
+        max_us: p99_us,
+        outliers: 0,
+        cv: 0.2,
+        noisy: false,
+        growth: None,
+    }
+}
+
+#[test]
+fn results_missing_an_objective_are_violations() {
+    let config = RunnerConfig::from_toml(
+        "[slo.select_user_by_id]\np99_ms = 5.0\nmin_throughput = 1000.0\n[slo.update_user]\nmax_errors = 0",
+    )
+    .unwrap();
+    let results = [
+        result("select_user_by_id", "sqlx", 4_000.0, 2_000.0),
+        result("select_user_by_id", "diesel", 6_200.0, 800.0),
+        // No objectives for this scenario
+        result("select_users_limit", "diesel", 90_000.0, 1.0),
+        RunResult { errors: 2, ..result("update_user", "sea_orm", 1_000.0, 500.0) },
+    ];
+
+    let violations: Vec<String> = config.violations(&results).iter().map(|v| v.to_string()).collect();
+    assert_eq!(
+        violations,
+        [
+            "select_user_by_id on diesel: p99 6.200 ms, over 5 ms",
+            "select_user_by_id on diesel: 800.0 ops/s, below 1000 ops/s",
+            "update_user on sea_orm: 2 error(s), more than 0",
+        ]
+    );
+    assert!(RunnerConfig::default().violations(&results).is_empty());
+}
@@ -0,0 +1,36 @@
+//! The scenario registry: every scenario runs at each of its sizes and leaves
+//! nothing behind. These need no database.
+
+use pg_benchmark::bench_null::NullBackend;
+use pg_benchmark::scenario::{self, Category, SCENARIOS};
+use std::collections::HashSet;
+
+#[test]
+fn scenario_names_are_unique_and_every_category_is_covered() {
+    let names: HashSet<&str> = SCENARIOS.iter().map(|s| s.name).collect();
+    assert_eq!(names.len(), SCENARIOS.len());
+
+    for category in Category::ALL {
+        assert!(scenario::in_category(category).next().is_some(), "no {category} scenario");
+    }
+}
+
+#[tokio::test]
+async fn every_scenario_runs_and_tears_down() {
+    let conn = NullBackend::connect();
+    for scenario in SCENARIOS {
+        let fixture = scenario.setup::<NullBackend>(&conn).await.unwrap();
+        let sizes = match scenario.sizes {
+            [] => &[1][..],
+            sizes => sizes,
+        };
+        for &size in sizes {
+            for iteration in 1..=3 {
+                scenario.run::<NullBackend>(&conn, &fixture, size, iteration).await.unwrap();
+            }
+            scenario.teardown::<NullBackend>(&conn).await.unwrap();
+            let leftover = NullBackend::leftover_rows(&conn);
+            assert!(leftover.is_empty(), "{}/{size} left {leftover} behind", scenario.name);
+        }
+    }
+}
@@ -0,0 +1,120 @@
+//! Schema drift detection: the ORM mappings are read correctly, mismatches are
+//! reported, and the mappings agree with the live database.
+
+mod common;
+
+use pg_benchmark::bench_tokio_postgres::TokioPostgresBench;
+use pg_benchmark::verify::{self, ColumnKind, ColumnSchema, TableSchema};
+
+fn column(name: &str, kind: ColumnKind, nullable: bool) -> ColumnSchema {
+    ColumnSchema {
+        name: name.to_string(),
+        kind,
+        nullable,
+    }
+}
+
+fn table<'a>(schema: &'a [TableSchema], name: &str) -> &'a TableSchema {
+    schema.iter().find(|t| t.name == name).unwrap()
+}
+
+fn users_table() -> TableSchema {
+    TableSchema {
+        name: "users".to_string(),
+        columns: vec![
+            column("id", ColumnKind::Uuid, false),
+            column("username", ColumnKind::Text, false),
+            column("age", ColumnKind::Integer, true),
+        ],
+    }
+}
+
+#[test]
+fn diesel_schema_is_read_from_schema_rs() {
+    let schema = verify::diesel_schema();
+    let names: Vec<&str> = schema.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, ["users", "posts", "comments", "tags", "post_tags"]);
+
+    let posts = table(&schema, "posts");
+    assert_eq!(
+        posts.columns,
+        [
+            column("id", ColumnKind::Uuid, false),
+            column("user_id", ColumnKind::Uuid, false),
+            column("title", ColumnKind::Text, false),
+            column("content", ColumnKind::Text, false),
+            column("status", ColumnKind::Text, false),
+            column("view_count", ColumnKind::Integer, false),
+            column("created_at", ColumnKind::Timestamptz, true),
+            column("updated_at", ColumnKind::Timestamptz, true),
+        ]
+    );
+    assert_eq!(table(&schema, "users").columns.len(), 8);
+    assert_eq!(table(&schema, "post_tags").columns.len(), 2);
+}
+
+#[test]
+fn sea_orm_schema_is_read_from_entities() {
+    let schema = verify::sea_orm_schema();
+    let names: Vec<&str> = schema.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, ["users", "posts", "comments"]);
+
+    // Both ORMs describe the tables they share identically
+    let diesel = verify::diesel_schema();
+    for sea_orm_table in &schema {
+        assert_eq!(sea_orm_table, table(&diesel, &sea_orm_table.name));
+    }
+}
+
+#[test]
+fn matching_schemas_have_no_mismatches() {
+    assert!(verify::compare("test", &[users_table()], &[users_table()]).is_empty());
+}
+
+#[test]
+fn compare_reports_every_kind_of_drift() {
+    let mut database = users_table();
+    database.columns[1].kind = ColumnKind::Other("bytea".to_string()); // username
+    database.columns[0].nullable = true; // id
+    database.columns.remove(2); // age
+    database.columns.push(column("nickname", ColumnKind::Text, true));
+
+    let problems: Vec<String> = verify::compare("test", &[users_table()], &[database])
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(
+        problems,
+        [
+            "test: users.id: nullable in database but NOT NULL in mapping",
+            "test: users.username: type Text in mapping, Other(\"bytea\") in database",
+            "test: users.age: column missing from database",
+            "test: users.nickname: column not mapped",
+        ]
+    );
+
+    let missing = verify::compare("test", &[users_table()], &[]);
+    assert_eq!(missing.len(), 1);
+    assert_eq!(missing[0].to_string(), "test: users: table missing from database");
+}
+
+#[test]
+fn nullable_mapping_of_not_null_column_is_allowed() {
+    let mut mapping = users_table();
+    mapping.columns[1].nullable = true;
+    assert!(verify::compare("test", &[mapping], &[users_table()]).is_empty());
+}
+
+#[tokio::test]
+async fn orm_mappings_match_database() {
+    let Some(_db) = common::lock_database().await else {
+        return;
+    };
+    let client = TokioPostgresBench::connect().await.unwrap();
+    let mismatches = verify::check_schema(&client).await.unwrap();
+    assert!(
+        mismatches.is_empty(),
+        "schema drift:\n{}",
+        mismatches.iter().map(|m| format!("  {m}\n")).collect::<String>()
+    );
+}
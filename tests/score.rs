@@ -0,0 +1,139 @@
+//! The composite score: slowdowns against the fastest backend, their
+//! geometric mean per backend and per category, and the ranking section.
+//! These need no database.
+
+use pg_benchmark::report::ExportedResult;
+use pg_benchmark::runner::{Mode, RunResult};
+use pg_benchmark::score::{self, Measurement};
+use pg_benchmark::tags::Tag;
+
+fn result(scenario: &'static str, backend: &'static str, throughput: f64) -> RunResult {
+    RunResult {
+        scenario,
+        backend,
+        mode: Mode::Throughput,
+        size: 1,
+        concurrency: 1,
+        operations: throughput as u64,
+        errors: 0,
+        first_error: None,
+        elapsed_secs: 1.0,
+        throughput,
+        mean_us: 1_000_000.0 / throughput,
+        stddev_us: 20.0,
+        p50_us: 240.0,
+        p95_us: 300.0,
+        p99_us: 400.0,
+        max_us: 900.0,
+        outliers: 0,
+        cv: 0.08,
+        noisy: false,
+        growth: None,
+    }
+}
+
+fn rank(results: &[RunResult]) -> Vec<score::BackendScore> {
+    score::rank(results.iter().map(Measurement::from))
+}
+
+fn close(actual: Option<f64>, expected: f64) -> bool {
+    actual.is_some_and(|actual| (actual - expected).abs() < 1e-9)
+}
+
+#[test]
+fn scores_are_geometric_means_of_slowdowns() {
+    let results = [
+        result("select_user_by_id", "sqlx", 1000.0),
+        result("select_user_by_id", "diesel", 500.0),
+        result("insert_single_user", "sqlx", 100.0),
+        result("insert_single_user", "diesel", 200.0),
+        result("join_posts_users", "sqlx", 400.0),
+        result("join_posts_users", "diesel", 100.0),
+    ];
+    let scores = rank(&results);
+
+    assert_eq!(scores[0].backend, "sqlx");
+    // 1, 2 and 1: the cube root of 2
+    assert!(close(Some(scores[0].overall), 2f64.cbrt()));
+    // 2, 1 and 4
+    assert_eq!(scores[1].backend, "diesel");
+    assert!(close(Some(scores[1].overall), 2.0));
+    assert_eq!((scores[0].wins, scores[0].benchmarks), (2, 3));
+
+    // The join counts as a read too
+    assert!(close(scores[1].category(Tag::Read), (2.0f64 * 4.0).sqrt()));
+    assert!(close(scores[1].category(Tag::Join), 4.0));
+    assert!(close(scores[1].category(Tag::Write), 1.0));
+    assert!(close(scores[0].category(Tag::Write), 2.0));
+    // One worker each
+    assert_eq!(scores[0].category(Tag::Concurrency), None);
+}
+
+#[test]
+fn only_benchmarks_of_the_same_shape_are_compared() {
+    let results = [
+        result("select_user_by_id", "sqlx", 1000.0),
+        RunResult { concurrency: 8, ..result("select_user_by_id", "diesel", 4000.0) },
+        RunResult { concurrency: 8, ..result("select_user_by_id", "sqlx", 2000.0) },
+        // Alone at its size, and failing every operation
+        RunResult { size: 100, ..result("select_users_limit", "sqlx", 10.0) },
+        result("update_user", "sqlx", 300.0),
+        result("update_user", "diesel", 0.0),
+    ];
+    let scores = rank(&results);
+
+    let sqlx = scores.iter().find(|s| s.backend == "sqlx").unwrap();
+    assert_eq!(sqlx.benchmarks, 1);
+    assert!(close(Some(sqlx.overall), 2.0));
+    assert!(close(sqlx.category(Tag::Concurrency), 2.0));
+    assert_eq!(scores[0].backend, "diesel");
+    assert_eq!(scores[0].benchmarks, 1);
+}
+
+#[test]
+fn latency_mode_is_scored_by_mean_latency() {
+    // At a fixed rate both backends keep up; only their latencies differ
+    let results = [
+        RunResult { mode: Mode::Latency, mean_us: 300.0, ..result("select_user_by_id", "sqlx", 200.0) },
+        RunResult { mode: Mode::Latency, mean_us: 200.0, ..result("select_user_by_id", "diesel", 200.0) },
+    ];
+    let scores = rank(&results);
+
+    assert_eq!(scores[0].backend, "diesel");
+    assert!(close(Some(scores[1].overall), 1.5));
+}
+
+#[test]
+fn exports_are_scored_like_runs() {
+    let exported: Vec<ExportedResult> = serde_json::from_str(
+        r#"[{"scenario": "select_user_by_id", "backend": "sqlx", "mode": "throughput", "size": 1,
+             "concurrency": 4, "operations": 5000, "errors": 0, "throughput": 500.0,
+             "p50_us": 100.0, "p99_us": 200.0},
+            {"scenario": "select_user_by_id", "backend": "diesel", "mode": "throughput", "size": 1,
+             "concurrency": 4, "operations": 4000, "errors": 0, "throughput": 400.0,
+             "p50_us": 100.0, "p99_us": 200.0}]"#,
+    )
+    .unwrap();
+    let scores = score::rank(exported.iter().map(Measurement::from));
+
+    assert_eq!(scores[0].backend, "sqlx");
+    assert!(close(Some(scores[1].overall), 1.25));
+    assert!(close(scores[1].category(Tag::Concurrency), 1.25));
+}
+
+#[test]
+fn ranking_lists_backends_best_first() {
+    let results = [
+        result("select_user_by_id", "tokio_postgres", 800.0),
+        result("select_user_by_id", "sqlx", 1000.0),
+    ];
+    let ranking = score::ranking(&rank(&results));
+
+    assert!(ranking.starts_with("## Overall Ranking\n"));
+    assert!(ranking.contains("| Rank | Backend | Overall | Read | Write | Join | Concurrency | Fastest in | Benchmarks |\n"));
+    assert!(ranking.contains("| 1 | sqlx | 1.00 | 1.00 | - | - | - | 1 | 1 |\n"));
+    assert!(ranking.contains("| 2 | tokio_postgres | 1.25 | 1.25 | - | - | - | 0 | 1 |\n"));
+
+    // Nothing to compare
+    assert_eq!(score::ranking(&rank(&results[..1])), "");
+}
@@ -0,0 +1,22 @@
+//! The seeded dataset matches what `init.sql` generates
+
+mod common;
+
+use pg_benchmark::bench_tokio_postgres::TokioPostgresBench;
+use pg_benchmark::verify;
+
+#[tokio::test]
+async fn seeded_dataset_matches_init_sql() {
+    let Some(_db) = common::lock_database().await else {
+        return;
+    };
+    // Fixture rows are `bench_user_*` and must not affect the checks
+    common::seed_fixture().await;
+
+    let client = TokioPostgresBench::connect().await.unwrap();
+    let checks = verify::check_seed_data(&client).await.unwrap();
+    common::cleanup().await;
+
+    let failed: Vec<String> = checks.iter().filter(|c| !c.passed()).map(ToString::to_string).collect();
+    assert!(failed.is_empty(), "seed data mismatches: {failed:#?}");
+}
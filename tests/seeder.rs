@@ -0,0 +1,61 @@
+//! Topping up the seeded dataset inserts only the rows that are missing
+
+mod common;
+
+use pg_benchmark::bench_tokio_postgres::TokioPostgresBench;
+use pg_benchmark::seed::{round_robin, SeedConfig};
+use pg_benchmark::seeder::{self, SeedRows};
+use pg_benchmark::{database_url, verify};
+
+#[tokio::test]
+async fn top_up_inserts_only_missing_rows() {
+    let Some(_db) = common::lock_database().await else {
+        return;
+    };
+    let config = SeedConfig::get();
+    let client = TokioPostgresBench::connect().await.unwrap();
+
+    let inserted = seeder::top_up(&database_url(), config).await.unwrap();
+    assert!(inserted.is_empty(), "top-up of a complete dataset inserted {inserted}");
+
+    // Drop the first post (with its comments and tags) and one comment on the second
+    let (user_number, post_num) = seeder::post_position(0, config);
+    let deleted_post = client
+        .execute(
+            "DELETE FROM posts WHERE title = $1",
+            &[&seeder::post_title(post_num, user_number)],
+        )
+        .await
+        .unwrap();
+    assert_eq!(deleted_post, 1);
+    let (user_number, post_num) = seeder::post_position(1, config);
+    let deleted_comments = client
+        .execute(
+            "DELETE FROM comments WHERE id = (
+                 SELECT c.id FROM comments c JOIN posts p ON p.id = c.post_id
+                 WHERE p.title = $1 LIMIT 1
+             )",
+            &[&seeder::post_title(post_num, user_number)],
+        )
+        .await
+        .unwrap();
+
+    let inserted = seeder::top_up(&database_url(), config).await.unwrap();
+    let (fewest, _, with_most) = round_robin(config.comments(), config.commented_posts());
+    let first_post_comments = (fewest + usize::from(with_most > 0)) as u64;
+    assert_eq!(
+        inserted,
+        SeedRows {
+            users: 0,
+            posts: 1,
+            comments: first_post_comments + deleted_comments,
+            tags: 0,
+            post_tags: seeder::tags_per_post(0) as u64,
+        }
+    );
+
+    let checks = verify::check_seed_data(&client).await.unwrap();
+    let failed: Vec<String> = checks.iter().filter(|c| !c.passed()).map(ToString::to_string).collect();
+    assert!(failed.is_empty(), "seed data mismatches after top-up: {failed:#?}");
+    assert!(seeder::top_up(&database_url(), config).await.unwrap().is_empty());
+}
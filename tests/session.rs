@@ -0,0 +1,101 @@
+//! Session configuration: every backend's sessions start with the same
+//! `application_name`, `work_mem` and `statement_timeout`
+
+mod common;
+
+use diesel::sql_types::Text;
+use diesel::{PgConnection, QueryableByName, RunQueryDsl};
+use pg_benchmark::bench_clorinde::ClorindeBench;
+use pg_benchmark::bench_diesel::DieselBench;
+use pg_benchmark::bench_seaorm::SeaOrmBench;
+use pg_benchmark::bench_sqlx::SqlxBench;
+use pg_benchmark::bench_tokio_postgres::TokioPostgresBench;
+use pg_benchmark::bench_wtx::{WtxBench, WtxConnection};
+use pg_benchmark::session::SessionConfig;
+use pg_benchmark::{database_url_with, with_param};
+use wtx::database::{Executor as _, Record as _};
+
+const SESSION_SETTINGS: &str = "SELECT concat_ws(',', current_setting('application_name'), \
+     current_setting('work_mem'), current_setting('statement_timeout')) AS settings";
+
+#[derive(QueryableByName)]
+struct SettingsRow {
+    #[diesel(sql_type = Text)]
+    settings: String,
+}
+
+async fn tokio_postgres_settings(client: &tokio_postgres::Client) -> String {
+    client.query_one(SESSION_SETTINGS, &[]).await.unwrap().get(0)
+}
+
+async fn wtx_settings(conn: &WtxConnection) -> String {
+    let mut executor = conn.executor().await.unwrap();
+    executor.execute_stmt_single(SESSION_SETTINGS, ()).await.unwrap().decode(0_usize).unwrap()
+}
+
+fn diesel_settings(conn: &mut PgConnection) -> String {
+    diesel::sql_query(SESSION_SETTINGS).get_result::<SettingsRow>(conn).unwrap().settings
+}
+
+#[test]
+fn parameters_are_encoded_for_each_form() {
+    let session = SessionConfig::default().with_work_mem("64kB").with_statement_timeout_ms(500);
+    assert_eq!(
+        session.apply("postgres://u:p@localhost/db?application_name=old"),
+        "postgres://u:p@localhost/db?application_name=pg_benchmark\
+         &options=-c%20statement_timeout%3D500%20-c%20work_mem%3D64kB"
+    );
+    assert_eq!(
+        session.apply("host=localhost options='-c work_mem=1MB' dbname=db"),
+        "host=localhost dbname=db application_name=pg_benchmark \
+         options='-c statement_timeout=500 -c work_mem=64kB'"
+    );
+    assert_eq!(with_param("host=localhost", "application_name", "it's"), "host=localhost application_name='it\\'s'");
+    assert_eq!(SessionConfig::default().options(), None);
+}
+
+#[tokio::test]
+async fn every_backend_starts_with_the_session_settings() {
+    let Some(_db) = common::lock_database().await else { return };
+    let application_name = SessionConfig::from_env().application_name;
+    let starts_with_name = |settings: String, backend: &str| {
+        assert!(settings.starts_with(&format!("{application_name},")), "{backend}: {settings}");
+    };
+
+    starts_with_name(tokio_postgres_settings(&TokioPostgresBench::connect().await.unwrap()).await, "tokio_postgres");
+    let pooled = TokioPostgresBench::create_pool(1).get().await.unwrap();
+    starts_with_name(tokio_postgres_settings(&pooled).await, "tokio_postgres_pooled");
+    starts_with_name(tokio_postgres_settings(&ClorindeBench::connect().await.unwrap()).await, "clorinde");
+    let pool = SqlxBench::connect().await.unwrap();
+    starts_with_name(sqlx::query_scalar(SESSION_SETTINGS).fetch_one(&pool).await.unwrap(), "sqlx");
+    let db = SeaOrmBench::connect().await.unwrap();
+    let settings = sqlx::query_scalar(SESSION_SETTINGS)
+        .fetch_one(db.get_postgres_connection_pool())
+        .await
+        .unwrap();
+    starts_with_name(settings, "sea_orm");
+    starts_with_name(diesel_settings(&mut DieselBench::connect().unwrap().get().unwrap()), "diesel");
+    starts_with_name(wtx_settings(&WtxBench::connect().await.unwrap()).await, "wtx");
+}
+
+#[tokio::test]
+async fn every_driver_applies_custom_settings() {
+    let Some(_db) = common::lock_database().await else { return };
+    let session = SessionConfig {
+        application_name: "pg_benchmark_session_test".to_string(),
+        ..SessionConfig::default()
+    }
+    .with_work_mem("64kB")
+    .with_statement_timeout_ms(1234);
+    let url = database_url_with(&session);
+    let expected = "pg_benchmark_session_test,64kB,1234ms";
+
+    // sea-orm and clorinde parse their URLs with sqlx and tokio-postgres
+    assert_eq!(tokio_postgres_settings(&TokioPostgresBench::connect_to(&url).await.unwrap()).await, expected);
+    let mut conn = SqlxBench::connect_single(&url).await.unwrap();
+    let settings: String = sqlx::query_scalar(SESSION_SETTINGS).fetch_one(&mut conn).await.unwrap();
+    assert_eq!(settings, expected);
+    assert_eq!(diesel_settings(&mut DieselBench::establish(&url).unwrap()), expected);
+    // wtx takes only the application name from the URL and runs the rest as `set_config`
+    assert_eq!(wtx_settings(&WtxBench::connect_to(&url).await.unwrap()).await, expected);
+}
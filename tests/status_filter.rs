@@ -0,0 +1,57 @@
+//! Status filter: every backend's newest posts of a status are read through the
+//! `(status, created_at)` index, and a plan that isn't is reported
+
+mod common;
+
+use pg_benchmark::audit;
+use pg_benchmark::bench_clorinde::ClorindeBench;
+use pg_benchmark::bench_diesel::DieselBench;
+use pg_benchmark::bench_seaorm::SeaOrmBench;
+use pg_benchmark::bench_seaquery::SeaQueryBench;
+use pg_benchmark::bench_sqlx::{SqlxBench, SqlxMacroBench};
+use pg_benchmark::bench_tokio_postgres::{TokioPostgresBench, TokioPostgresPreparedBench};
+use pg_benchmark::bench_wtx::WtxBench;
+use pg_benchmark::indexes;
+use pg_benchmark::status_filter::{self, IndexUsage, INDEX};
+use pg_benchmark::DatabaseBenchmark;
+use tokio_postgres::Client;
+
+async fn usage<B: DatabaseBenchmark>(backend: &'static str, client: &Client) -> IndexUsage {
+    let conn = B::connect().await.unwrap();
+    status_filter::explain::<B>(backend, &conn, client).await.unwrap()
+}
+
+#[tokio::test]
+async fn every_backend_scans_the_status_created_at_index() {
+    let Some(_db) = common::lock_database().await else { return };
+    let client = TokioPostgresBench::connect().await.unwrap();
+    if !audit::available(&client).await {
+        eprintln!("skipping: pg_stat_statements is not loaded");
+        return;
+    }
+    indexes::restore(&client).await.unwrap();
+
+    let usages = [
+        usage::<TokioPostgresBench>("tokio_postgres", &client).await,
+        usage::<TokioPostgresPreparedBench>("tokio_postgres_prepared", &client).await,
+        usage::<SqlxBench>("sqlx", &client).await,
+        usage::<SqlxMacroBench>("sqlx_macro", &client).await,
+        usage::<SeaOrmBench>("sea_orm", &client).await,
+        usage::<SeaQueryBench>("sea_query", &client).await,
+        usage::<DieselBench>("diesel", &client).await,
+        usage::<ClorindeBench>("clorinde", &client).await,
+        usage::<WtxBench>("wtx", &client).await,
+    ];
+    for usage in &usages {
+        assert_eq!(usage.statements.len(), 1, "{usage}");
+        assert!(usage.uses_index(), "{usage}");
+    }
+
+    // Without the index the same statement is planned on another one
+    client.batch_execute(&format!("DROP INDEX {INDEX}")).await.unwrap();
+    let without = usage::<TokioPostgresBench>("tokio_postgres", &client).await;
+    indexes::restore(&client).await.unwrap();
+    assert!(!without.uses_index());
+    assert_eq!(without.statements[0].query, usages[0].statements[0].query);
+    assert!(without.to_string().starts_with(&format!("tokio_postgres doesn't scan {INDEX}")));
+}
@@ -0,0 +1,45 @@
+//! Benchmark tags: parsing `BENCH_TAGS`-style lists, which benchmarks a
+//! filter selects, and the runner's `tags` setting. These need no database.
+
+use pg_benchmark::runner::RunnerConfig;
+use pg_benchmark::scenario::{Category, SCENARIOS};
+use pg_benchmark::tags::{Tag, TagFilter};
+
+#[test]
+fn tag_lists_parse_and_reject_unknown_names() {
+    let filter = TagFilter::parse("join, write,").unwrap();
+    assert_eq!(filter.tags, [Tag::Join, Tag::Write]);
+    assert_eq!(filter.to_string(), "join,write");
+    assert_eq!(TagFilter::parse("").unwrap(), TagFilter::default());
+    assert!(TagFilter::parse("join,joins").unwrap_err().contains("\"joins\""));
+}
+
+#[test]
+fn filters_select_benchmarks_with_any_of_their_tags() {
+    let filter = TagFilter::parse("join,transaction").unwrap();
+    assert!(filter.matches(&[Tag::Read, Tag::Join]));
+    assert!(filter.matches(&[Tag::Write, Tag::Transaction]));
+    assert!(!filter.matches(&[Tag::Read]));
+    assert!(!filter.matches(&[]));
+    assert!(TagFilter::default().matches(&[]));
+
+    // Every scenario is tagged, and reads or writes
+    for scenario in SCENARIOS {
+        assert!(scenario.tags().iter().any(|t| matches!(t, Tag::Read | Tag::Write)), "{}", scenario.name);
+    }
+    assert!(Category::Join.tags().contains(&Tag::Read));
+}
+
+#[test]
+fn runner_tags_narrow_the_scenarios() {
+    let config = RunnerConfig::from_toml("tags = [\"join\"]").unwrap();
+    let names: Vec<&str> = config.scenarios().unwrap().iter().map(|s| s.name).collect();
+    assert_eq!(names, ["join_posts_users", "join_users_posts_comments"]);
+
+    // Scenario names and tags both apply
+    let config = RunnerConfig::from_toml("tags = [\"write\"]\nscenarios = [\"select_user_by_id\", \"update_user\"]").unwrap();
+    let names: Vec<&str> = config.scenarios().unwrap().iter().map(|s| s.name).collect();
+    assert_eq!(names, ["update_user"]);
+
+    assert!(RunnerConfig::from_toml("tags = [\"joins\"]").is_err());
+}
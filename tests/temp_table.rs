@@ -0,0 +1,67 @@
+//! The temp-table workload: every backend stages the same users, joins them
+//! against their posts and leaves no table behind
+
+mod common;
+
+use common::{FIXTURE_POSTS_PER_USER, FIXTURE_USERS};
+use pg_benchmark::bench_clorinde::ClorindeBench;
+use pg_benchmark::bench_diesel::DieselBench;
+use pg_benchmark::bench_seaorm::SeaOrmBench;
+use pg_benchmark::bench_sqlx::SqlxBench;
+use pg_benchmark::bench_tokio_postgres::TokioPostgresBench;
+use pg_benchmark::temp_table::{self, StagedPosts, StagedUser, TempTable};
+use uuid::Uuid;
+
+#[test]
+fn staged_users_are_scored_by_position() {
+    let ids = [Uuid::from_u128(3), Uuid::from_u128(1)];
+    assert_eq!(
+        temp_table::stage(&ids),
+        [
+            StagedUser { user_id: ids[0], score: 0 },
+            StagedUser { user_id: ids[1], score: 1 },
+        ]
+    );
+}
+
+/// Two runs on one connection, so the first must have dropped its table
+async fn stage_twice<B: TempTable>(rows: &[StagedUser]) -> Vec<StagedPosts> {
+    let conn = B::connect().await.unwrap();
+    let first = B::stage_and_join(&conn, rows).await.unwrap();
+    assert_eq!(B::stage_and_join(&conn, rows).await.unwrap(), first);
+    first
+}
+
+#[tokio::test]
+async fn every_backend_joins_the_staged_users() {
+    let Some(_db) = common::lock_database().await else { return };
+    common::seed_fixture().await;
+    let client = TokioPostgresBench::connect().await.unwrap();
+    let rows = client
+        .query("SELECT id FROM users WHERE username LIKE 'bench_user_fixture_%'", &[])
+        .await
+        .unwrap();
+    // A user without posts drops out of the join
+    let mut ids: Vec<Uuid> = rows.iter().map(|row| row.get(0)).collect();
+    ids.push(Uuid::new_v4());
+    let staged = temp_table::stage(&ids);
+
+    let mut expected: Vec<StagedPosts> = staged[..FIXTURE_USERS as usize]
+        .iter()
+        .map(|row| StagedPosts { user_id: row.user_id, score: row.score, post_count: FIXTURE_POSTS_PER_USER })
+        .collect();
+    expected.sort_by_key(|row| row.user_id);
+
+    assert_eq!(stage_twice::<TokioPostgresBench>(&staged).await, expected, "tokio_postgres");
+    assert_eq!(stage_twice::<SqlxBench>(&staged).await, expected, "sqlx");
+    assert_eq!(stage_twice::<SeaOrmBench>(&staged).await, expected, "sea_orm");
+    assert_eq!(stage_twice::<DieselBench>(&staged).await, expected, "diesel");
+    assert_eq!(stage_twice::<ClorindeBench>(&staged).await, expected, "clorinde");
+
+    let leftover = client
+        .query_one("SELECT to_regclass($1) IS NULL", &[&temp_table::TABLE])
+        .await
+        .unwrap();
+    assert!(leftover.get::<_, bool>(0));
+    TokioPostgresBench::cleanup(&client).await.unwrap();
+}
@@ -0,0 +1,69 @@
+//! Names and post bodies in every `TextScript` round-trip byte for byte
+//! through each backend, and name searches over them agree across backends
+
+mod common;
+
+use pg_benchmark::bench_null::NullBackend;
+use pg_benchmark::seed::TextScript;
+use pg_benchmark::{
+    bench_clorinde::ClorindeBench, bench_diesel::DieselBench, bench_seaorm::SeaOrmBench,
+    bench_sqlx::SqlxBench, bench_tokio_postgres::TokioPostgresBench, DatabaseBenchmark, NewPost,
+    NewUser,
+};
+
+const USERS: usize = 20;
+
+/// Insert [`USERS`] users with one post each in `script`, check they read back
+/// unchanged, and return the generated users a search for the script's fragment finds
+async fn round_trip<B: DatabaseBenchmark>(conn: &B::Connection, script: TextScript) -> Vec<String> {
+    B::cleanup(conn).await.unwrap();
+    let mut expected = Vec::new();
+    for i in 0..USERS {
+        let user = NewUser::generate_in(i, script);
+        let user_id = B::insert_user(conn, &user).await.unwrap();
+        let post = NewPost::generate_in(user_id, i, script);
+        B::insert_post(conn, &post).await.unwrap();
+
+        let read = B::select_user_by_id(conn, user_id).await.unwrap().expect("user not found");
+        assert_eq!((&read.first_name, &read.last_name), (&user.first_name, &user.last_name), "{script:?}");
+        expected.push(post);
+    }
+
+    let read = B::select_posts_with_user(conn, USERS as i64).await.unwrap();
+    assert_eq!(read.len(), USERS, "{script:?}");
+    // Newest first
+    for ((post, _), expected) in read.iter().zip(expected.iter().rev()) {
+        assert_eq!(post.content, expected.content, "{script:?}");
+    }
+
+    let found = B::search_users_by_name(conn, script.search_fragment(), 100).await.unwrap();
+    B::cleanup(conn).await.unwrap();
+    // The in-memory backend's seeded users are also named `First*`
+    found.into_iter().map(|u| u.username).filter(|u| u.starts_with("bench_user_")).collect()
+}
+
+#[tokio::test]
+async fn unicode_round_trips_through_every_backend() {
+    let null = NullBackend::connect();
+    let Some(_db) = common::lock_database().await else {
+        for script in TextScript::ALL {
+            assert!(!round_trip::<NullBackend>(&null, script).await.is_empty(), "{script:?}");
+        }
+        return;
+    };
+
+    let tokio_postgres = TokioPostgresBench::connect().await.unwrap();
+    let sqlx = SqlxBench::connect().await.unwrap();
+    let sea_orm = SeaOrmBench::connect().await.unwrap();
+    let diesel = <DieselBench as DatabaseBenchmark>::connect().await.unwrap();
+    let clorinde = ClorindeBench::connect().await.unwrap();
+    for script in TextScript::ALL {
+        let expected = round_trip::<NullBackend>(&null, script).await;
+        assert!(!expected.is_empty(), "{script:?}");
+        assert_eq!(round_trip::<TokioPostgresBench>(&tokio_postgres, script).await, expected, "tokio_postgres {script:?}");
+        assert_eq!(round_trip::<SqlxBench>(&sqlx, script).await, expected, "sqlx {script:?}");
+        assert_eq!(round_trip::<SeaOrmBench>(&sea_orm, script).await, expected, "sea_orm {script:?}");
+        assert_eq!(round_trip::<DieselBench>(&diesel, script).await, expected, "diesel {script:?}");
+        assert_eq!(round_trip::<ClorindeBench>(&clorinde, script).await, expected, "clorinde {script:?}");
+    }
+}
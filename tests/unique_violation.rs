@@ -0,0 +1,81 @@
+//! Unique violations: every backend picks the violated constraint out of its
+//! error and stores colliding users under the next free username either way
+
+mod common;
+
+use pg_benchmark::bench_clorinde::ClorindeBench;
+use pg_benchmark::bench_diesel::DieselBench;
+use pg_benchmark::bench_seaorm::SeaOrmBench;
+use pg_benchmark::bench_sqlx::SqlxBench;
+use pg_benchmark::bench_tokio_postgres::TokioPostgresBench;
+use pg_benchmark::unique_violation::{self, Recovery, UniqueViolation, USERNAME_KEY};
+use pg_benchmark::NewUser;
+
+#[test]
+fn colliding_users_repeat_only_the_username() {
+    let colliding = unique_violation::colliding_usernames(0.5);
+    let users: Vec<NewUser> = (0..4).map(|i| colliding.generate(i)).collect();
+    assert_eq!(users[1].username, users[0].username);
+    assert_ne!(users[1].email, users[0].email);
+    assert_eq!(unique_violation::retry_username(&users[1].username, 2), "bench_user_0_2");
+}
+
+/// The `bench_user_*` usernames, sorted
+async fn usernames() -> Vec<String> {
+    let client = TokioPostgresBench::connect().await.unwrap();
+    client
+        .query("SELECT username FROM users WHERE username LIKE 'bench_user_%' ORDER BY username", &[])
+        .await
+        .unwrap()
+        .iter()
+        .map(|row| row.get(0))
+        .collect()
+}
+
+async fn recovers<B: UniqueViolation>(backend: &str) {
+    let conn = B::connect().await.unwrap();
+    B::cleanup(&conn).await.unwrap();
+
+    let first = NewUser::generate(0);
+    B::insert_user(&conn, &first).await.unwrap();
+    let username = B::insert_user(&conn, &NewUser { email: "other@benchmark.com".into(), ..first.clone() }).await;
+    assert_eq!(B::violated(&username.unwrap_err()), Some(USERNAME_KEY), "{backend}");
+    let email = B::insert_user(&conn, &NewUser { username: "bench_user_other".into(), ..first.clone() }).await;
+    assert_eq!(B::violated(&email.unwrap_err()), Some("users_email_key"), "{backend}");
+    assert!(!B::insert_user_unless_taken(&conn, &NewUser::generate(0)).await.unwrap(), "{backend}");
+
+    // Only a taken username is recovered from, not a taken email
+    let taken_email = NewUser { username: "bench_user_other".into(), ..first };
+    assert!(B::insert_user_recovering(&conn, Recovery::CatchAndRetry, &taken_email).await.is_err(), "{backend}");
+
+    let colliding = unique_violation::colliding_usernames(0.5);
+    for recovery in Recovery::ALL {
+        B::cleanup(&conn).await.unwrap();
+        let mut taken = 0;
+        for index in 0..8 {
+            taken += B::insert_user_recovering(&conn, recovery, &colliding.generate(index)).await.unwrap();
+        }
+        // Users 1, 3, 5 and 7 repeat 0, 2, 4 and 6
+        assert_eq!(taken, 4, "{backend} {recovery}");
+        let expected: Vec<String> = [0, 2, 4, 6]
+            .iter()
+            .flat_map(|i| [format!("bench_user_{i}"), format!("bench_user_{i}_1")])
+            .collect();
+        assert_eq!(usernames().await, expected, "{backend} {recovery}");
+    }
+    B::cleanup(&conn).await.unwrap();
+}
+
+#[tokio::test]
+async fn every_backend_recovers_from_a_taken_username() {
+    let Some(_db) = common::lock_database().await else { return };
+    common::cleanup().await;
+
+    recovers::<TokioPostgresBench>("tokio_postgres").await;
+    recovers::<SqlxBench>("sqlx").await;
+    recovers::<SeaOrmBench>("sea_orm").await;
+    recovers::<DieselBench>("diesel").await;
+    recovers::<ClorindeBench>("clorinde").await;
+
+    assert!(usernames().await.is_empty());
+}
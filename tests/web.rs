@@ -0,0 +1,124 @@
+//! The web API: its JSON shapes, and every backend answering the same
+//! requests alike through the load client
+
+mod common;
+
+use pg_benchmark::bench_diesel::DieselBench;
+use pg_benchmark::bench_seaorm::SeaOrmBench;
+use pg_benchmark::bench_sqlx::SqlxBench;
+use pg_benchmark::bench_tokio_postgres::TokioPostgresBench;
+use pg_benchmark::web::{self, Api, ApiServer, Created, Endpoint, LoadClient, PostWithAuthor, Targets};
+use pg_benchmark::{Post, User};
+use axum::http::StatusCode;
+use uuid::Uuid;
+
+#[test]
+fn posts_carry_their_author_next_to_the_post_fields() {
+    let author = User {
+        id: Uuid::from_u128(1),
+        username: "author".into(),
+        email: "author@benchmark.com".into(),
+        first_name: "First".into(),
+        last_name: "Last".into(),
+        age: Some(30),
+        created_at: None,
+        updated_at: None,
+    };
+    let post = Post {
+        id: Uuid::from_u128(2),
+        user_id: author.id,
+        title: "Title".into(),
+        content: "Content".into(),
+        status: "published".into(),
+        view_count: 7,
+        created_at: None,
+        updated_at: None,
+    };
+    let json = serde_json::to_value(PostWithAuthor { post, author }).unwrap();
+    assert_eq!(json["title"], "Title");
+    assert_eq!(json["user_id"], json["author"]["id"]);
+    assert_eq!(json["author"]["username"], "author");
+}
+
+#[test]
+fn posts_body_reads_back_as_the_posts_it_was_given() {
+    let author = User {
+        id: Uuid::from_u128(1),
+        username: "author".into(),
+        email: "author@benchmark.com".into(),
+        first_name: "First".into(),
+        last_name: "Last".into(),
+        age: None,
+        created_at: None,
+        updated_at: None,
+    };
+    let posts: Vec<(Post, User)> = (2..5)
+        .map(|id| Post {
+            id: Uuid::from_u128(id),
+            user_id: author.id,
+            title: format!("Title {id}"),
+            content: "Content".into(),
+            status: "published".into(),
+            view_count: 0,
+            created_at: None,
+            updated_at: None,
+        })
+        .map(|post| (post, author.clone()))
+        .collect();
+
+    let body = web::posts_body(posts.clone()).unwrap();
+    let read: Vec<PostWithAuthor> = serde_json::from_slice(&body).unwrap();
+    let expected: Vec<PostWithAuthor> = posts.into_iter().map(|(post, author)| PostWithAuthor { post, author }).collect();
+    assert_eq!(read, expected);
+}
+
+/// Serve `api` and check each endpoint's answers against the fixture
+async fn check_api<A: Api>(name: &str, api: A, user: &User, targets: &Targets) {
+    let server = ApiServer::start(api).await.unwrap();
+    let mut client = LoadClient::connect(server.address()).await.unwrap();
+
+    let response = client.get(&format!("/users/{}", user.id)).await.unwrap();
+    assert_eq!(response.status, StatusCode::OK, "{name}");
+    assert_eq!(&response.json::<User>().unwrap(), user, "{name}");
+    let response = client.get(&format!("/users/{}", Uuid::new_v4())).await.unwrap();
+    assert_eq!(response.status, StatusCode::NOT_FOUND, "{name}");
+
+    let response = targets.request(&mut client, Endpoint::ListPosts, 1).await.unwrap();
+    let posts: Vec<PostWithAuthor> = response.json().unwrap();
+    assert_eq!(posts.len() as i64, targets.post_limit, "{name}");
+    assert!(posts.iter().all(|p| p.post.user_id == p.author.id), "{name}");
+    assert!(posts[0].post.title.starts_with("Fixture post"), "{name}");
+
+    // Served over the same keep-alive connection as the reads
+    let response = targets.request(&mut client, Endpoint::CreateComment, 1).await.unwrap();
+    assert_eq!(response.status, StatusCode::CREATED, "{name}");
+    let created: Created = response.json().unwrap();
+    let db = TokioPostgresBench::connect().await.unwrap();
+    let row = db.query_one("SELECT post_id, user_id FROM comments WHERE id = $1", &[&created.id]).await.unwrap();
+    assert_eq!((row.get(0), row.get(1)), targets.comment_on, "{name}");
+
+    server.shutdown().await;
+    assert!(client.get("/posts").await.is_err(), "{name}: server still answering after shutdown");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn every_backend_serves_the_same_api() {
+    let Some(_db) = common::lock_database().await else { return };
+    common::seed_fixture().await;
+    let client = TokioPostgresBench::connect().await.unwrap();
+    let users = TokioPostgresBench::select_users_limit(&client, 1).await.unwrap();
+    let posts = TokioPostgresBench::select_posts_with_user(&client, 1).await.unwrap();
+    let targets = Targets {
+        user_ids: vec![users[0].id],
+        post_limit: 5,
+        comment_on: (posts[0].0.id, users[0].id),
+    };
+
+    check_api("tokio_postgres", web::TokioPostgresApi(TokioPostgresBench::create_pool(2)), &users[0], &targets).await;
+    check_api("sqlx", web::SqlxApi(SqlxBench::connect_with_pool_size(2).await.unwrap()), &users[0], &targets).await;
+    check_api("sea_orm", web::SeaOrmApi(SeaOrmBench::connect_with_pool_size(2).await.unwrap()), &users[0], &targets).await;
+    check_api("diesel", web::DieselApi(DieselBench::connect_with_pool_size(2).unwrap()), &users[0], &targets).await;
+    check_api("clorinde", web::ClorindeApi(TokioPostgresBench::create_pool(2)), &users[0], &targets).await;
+
+    TokioPostgresBench::cleanup(&client).await.unwrap();
+}
@@ -0,0 +1,92 @@
+//! Declarative workloads: parsing `[[operations]]` and `[dataset]` into a mix
+//! and running it on the in-memory backend. These need no database.
+
+use pg_benchmark::bench_null::NullBackend;
+use pg_benchmark::runner::{self, RunnerConfig};
+use pg_benchmark::seed::SeedRatio;
+
+#[test]
+fn example_workloads_parse_into_their_mix() {
+    let config = RunnerConfig::from_toml(include_str!("../workloads/read-mostly.toml")).unwrap();
+    let mix = config.workload().unwrap().unwrap();
+    let entries: Vec<(&str, usize, u64)> = mix.entries.iter().map(|e| (e.scenario.name, e.size, e.calls)).collect();
+    // Weights keep their proportion, reduced so the mix repeats every 10 operations
+    assert_eq!(
+        entries,
+        [("select_user_by_id", 1, 7), ("insert_comment", 1, 2), ("join_posts_users", 50, 1)]
+    );
+    assert_eq!(config.concurrency, 8);
+
+    let config = RunnerConfig::from_toml(include_str!("../workloads/write-heavy.toml")).unwrap();
+    assert_eq!(config.dataset.unwrap().seed_config().unwrap().users, 50_000);
+    assert!(RunnerConfig::from_toml("").unwrap().workload().unwrap().is_none());
+}
+
+#[test]
+fn dataset_overrides_the_seed_scale_and_ratio() {
+    let config = RunnerConfig::from_toml("[dataset]\nratio = \"1:3:9\"").unwrap();
+    let seed = config.dataset.unwrap().seed_config().unwrap();
+    assert_eq!(seed.ratio, SeedRatio { users: 1, posts: 3, comments: 9 });
+    assert_eq!(seed.users, pg_benchmark::seed::SeedConfig::get().users);
+}
+
+#[test]
+fn invalid_workloads_are_rejected() {
+    let operation = |body: &str| format!("[[operations]]\n{body}\n");
+    for text in [
+        operation("scenario = \"select_everything\"\nweight = 1"),
+        operation("scenario = \"select_user_by_id\"\nweight = 0"),
+        operation("scenario = \"select_user_by_id\"\nweight = 1\nsize = 10"),
+        operation("scenario = \"select_users_limit\"\nweight = 1\nsize = 0"),
+        operation("scenario = \"select_users_limit\""),
+        operation("scenario = \"select_users_limit\"\nweight = 1\nlimit = 10"),
+        format!("scenarios = [\"update_user\"]\n{}", operation("scenario = \"update_user\"\nweight = 1")),
+        format!("{}{}", operation("scenario = \"update_user\"\nweight = 9999"), operation("scenario = \"insert_comment\"\nweight = 2")),
+        "[dataset]\nratio = \"many\"".to_string(),
+    ] {
+        assert!(RunnerConfig::from_toml(&text).is_err(), "{text:?} was accepted");
+    }
+}
+
+#[tokio::test]
+async fn runs_the_mix_in_proportion_and_cleans_up() {
+    let text = "
+duration_secs = 0.2
+warmup_secs = 0.05
+concurrency = 2
+
+[[operations]]
+scenario = \"select_user_by_id\"
+weight = 70
+
+[[operations]]
+scenario = \"insert_comment\"
+weight = 20
+
+[[operations]]
+scenario = \"insert_single_user\"
+weight = 5
+
+[[operations]]
+scenario = \"insert_batch_users\"
+weight = 5
+size = 10
+";
+    let config = RunnerConfig::from_toml(text).unwrap();
+    let mix = config.workload().unwrap().unwrap();
+    let results = runner::run_mix::<NullBackend>(&config, &mix, "null", &mut |_| {}).await.unwrap();
+
+    // Writing entries never insert the same generated user
+    for result in &results {
+        assert_eq!(result.errors, 0, "{}: {:?}", result.scenario, result.first_error);
+    }
+    let counts: Vec<u64> = results.iter().map(|r| r.operations).collect();
+    let total: u64 = counts.iter().sum();
+    assert!(total >= 20, "{counts:?}");
+    // Operations start in the mix's order, so every share is within a period of exact
+    for (count, weight) in counts.iter().zip([70, 20, 5, 5]) {
+        let expected = total as f64 * weight as f64 / 100.0;
+        assert!((*count as f64 - expected).abs() <= 20.0, "{counts:?}");
+    }
+    assert!(NullBackend::leftover_rows(&NullBackend::connect()).is_empty());
+}